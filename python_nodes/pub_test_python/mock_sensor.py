@@ -24,6 +24,17 @@ broadcast_type: Literal[
 
 sensor_id: str = os.environ.get("SENSOR_ID", "0")
 
+# Replies are tagged like the Rust nodes tag theirs (common::encoding::flatbuffer), so
+# subscribers pick the decoder from the encoding. Keep in step with SCHEMA_VERSION there.
+SCHEMA_VERSION = 2
+TABLES = {"imu": "sensors.IMU", "altitude": "sensors.Altitude", "gyro": "sensors.Gyro"}
+
+
+def flatbuffer_encoding(table: str) -> zenoh.Encoding:
+    return zenoh.Encoding(
+        f"application/x-flatbuffers;schema={table};version={SCHEMA_VERSION}"
+    )
+
 INITIAL_VELOCITY = 100.0
 GRAVITY = 9.81
 LAUNCH_ANGLE = math.radians(75)
@@ -211,11 +222,15 @@ def main():
                 query.reply(target_topic, b"")
                 return
 
-        query.reply(target_topic, data)
+        query.reply(
+            target_topic, data, encoding=flatbuffer_encoding(TABLES[broadcast_type])
+        )
         print(f"Query @ t={elapsed:.2f}s: {data}")
 
     subscriber = session.declare_subscriber(launch_topic, launch_handler)
     queryable = session.declare_queryable(target_topic, query_handler)
+    # Announces the sensor for fusion --discover while the mock runs.
+    token = session.liveliness().declare_token(target_topic)
 
     print(f"Subscribed to launch topic '{launch_topic}'")
     print(f"Queryable declared on '{target_topic}'")
@@ -229,6 +244,7 @@ def main():
     finally:
         subscriber.undeclare()
         queryable.undeclare()
+        token.undeclare()
         session.close()
 
 
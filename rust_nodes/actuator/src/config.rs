@@ -0,0 +1,90 @@
+use common::config;
+use common::qos::QosConfig;
+use serde::Deserialize;
+use std::path::Path;
+use zenoh::key_expr::OwnedKeyExpr;
+
+// Actuators used when no config file is given: four fins.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ActuatorConfig {
+    // Last chunk of the actuator's command and state keys.
+    pub name: String,
+    pub min: f32,
+    pub max: f32,
+    // Fastest the actuator is driven, in units per second.
+    pub max_rate: f32,
+    // Position it returns to when commands stop.
+    #[serde(default)]
+    pub neutral: f32,
+    // Time constant of the simulated servo's first-order response, in seconds.
+    #[serde(default)]
+    pub time_constant_s: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    pub actuators: Vec<ActuatorConfig>,
+    #[serde(default)]
+    pub qos: QosConfig,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.qos.validate()?;
+        if self.actuators.is_empty() {
+            return Err("no actuators configured".to_string());
+        }
+        for (i, actuator) in self.actuators.iter().enumerate() {
+            let name = &actuator.name;
+            match OwnedKeyExpr::try_from(name.clone()) {
+                Ok(key_expr) if !key_expr.is_wild() && !name.contains('/') => {}
+                _ => return Err(format!("invalid actuator name '{}'", name)),
+            }
+            if self.actuators[..i].iter().any(|other| &other.name == name) {
+                return Err(format!("actuator {} configured twice", name));
+            }
+            let values = [
+                actuator.min,
+                actuator.max,
+                actuator.max_rate,
+                actuator.neutral,
+                actuator.time_constant_s,
+            ];
+            if values.iter().any(|v| !v.is_finite()) {
+                return Err(format!("{}: limits must be finite", name));
+            }
+            if actuator.min > actuator.max
+                || !(actuator.min..=actuator.max).contains(&actuator.neutral)
+            {
+                return Err(format!("{}: neutral must lie within [min, max]", name));
+            }
+            if actuator.max_rate <= 0.0 || actuator.time_constant_s < 0.0 {
+                return Err(format!(
+                    "{}: max_rate must be positive and time_constant_s not negative",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let config: Config =
+            toml::from_str(DEFAULT_CONFIG).expect("Built-in actuator config is invalid.");
+        config
+            .validate()
+            .expect("Built-in actuator config is invalid.");
+        config
+    }
+}
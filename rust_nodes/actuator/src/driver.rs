@@ -0,0 +1,34 @@
+// Moves one actuator. Hardware drivers implement this over their servo bus; SimServo
+// stands in for one in closed-loop simulation.
+pub trait Driver: Send {
+    // Drives the actuator to a target already within its limits, dt seconds after the
+    // previous call. Returns the position it has reached.
+    fn drive(&mut self, target: f32, dt: f32) -> Result<f32, String>;
+}
+
+// A servo that follows its target with a first-order lag.
+pub struct SimServo {
+    position: f32,
+    time_constant_s: f32,
+}
+
+impl SimServo {
+    pub fn new(position: f32, time_constant_s: f32) -> Self {
+        SimServo {
+            position,
+            time_constant_s,
+        }
+    }
+}
+
+impl Driver for SimServo {
+    fn drive(&mut self, target: f32, dt: f32) -> Result<f32, String> {
+        let alpha = if self.time_constant_s > 0.0 {
+            1.0 - (-dt / self.time_constant_s).exp()
+        } else {
+            1.0
+        };
+        self.position += alpha * (target - self.position);
+        Ok(self.position)
+    }
+}
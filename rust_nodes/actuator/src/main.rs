@@ -0,0 +1,219 @@
+mod config;
+mod driver;
+
+use clap::Parser;
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use config::{ActuatorConfig, Config};
+use driver::{Driver, SimServo};
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensors_rs::sensors;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use zenoh::pubsub::Publisher;
+use zenoh::sample::Sample;
+
+#[derive(Parser)]
+#[command(
+    about = "Drives actuators from position commands on cmd/actuators/** within their limits and publishes what they reach on devices/actuators/*"
+)]
+struct Args {
+    /// Actuators to drive (TOML or JSON). Defaults to four simulated fins.
+    config: Option<PathBuf>,
+    /// Rate the actuators are driven and their states published at.
+    #[arg(long, default_value_t = 100.0)]
+    rate_hz: f64,
+    /// Return an actuator to neutral once it has had no command for this long, in
+    /// milliseconds.
+    #[arg(long, default_value_t = 500)]
+    command_timeout_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct Actuator {
+    config: ActuatorConfig,
+    // Key the actuator's commands arrive on, built once so commands are matched to it
+    // without formatting a key per sample.
+    command_key: String,
+    driver: Box<dyn Driver>,
+    // Latest command and when it arrived.
+    command: Option<(f32, Instant)>,
+    target: f32,
+    stale: bool,
+    publisher: Option<Publisher<'static>>,
+}
+
+impl Actuator {
+    fn new(config: ActuatorConfig) -> Self {
+        let driver = Box::new(SimServo::new(config.neutral, config.time_constant_s));
+        Actuator {
+            target: config.neutral,
+            command_key: keys::actuator_command(&config.name),
+            config,
+            driver,
+            command: None,
+            stale: true,
+            publisher: None,
+        }
+    }
+
+    // Moves the target toward the latest command, or neutral without a recent one, by no
+    // more than the rate limit allows in dt, and drives the actuator to it.
+    async fn drive(&mut self, dt: f32, timeout: Duration, now: Instant) -> zenoh::Result<()> {
+        let name = &self.config.name;
+        let command = self
+            .command
+            .filter(|&(_, received)| now - received <= timeout)
+            .map(|(position, _)| position);
+        match (command.is_none(), self.stale) {
+            (true, false) => info!(
+                "{}: no command for {:?}, returning to neutral",
+                name, timeout
+            ),
+            (false, true) => info!("{}: commanded", name),
+            _ => {}
+        }
+        self.stale = command.is_none();
+        let commanded = command.unwrap_or(self.config.neutral);
+        let clamped = commanded.clamp(self.config.min, self.config.max);
+        let step = self.config.max_rate * dt;
+        self.target += (clamped - self.target).clamp(-step, step);
+        let position = self
+            .driver
+            .drive(self.target, dt)
+            .map_err(|e| format!("{}: {}", name, e))?;
+
+        let timestamp_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_micros() as u64)
+            .unwrap_or(0);
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(64);
+        let state = sensors::ActuatorState::create(
+            &mut builder,
+            &sensors::ActuatorStateArgs {
+                timestamp_us,
+                commanded,
+                target: self.target,
+                position,
+                limited: self.target != commanded,
+                stale: self.stale,
+            },
+        );
+        builder.finish(state, None);
+        match &self.publisher {
+            Some(publisher) => publisher.put(builder.finished_data().to_vec()).await,
+            None => Ok(()),
+        }
+    }
+}
+
+struct ActuatorNode {
+    period: Duration,
+    timeout: Duration,
+    actuators: Vec<Actuator>,
+    last_drive: Option<Instant>,
+    qos: QosConfig,
+}
+
+impl ActuatorNode {
+    fn on_command(&mut self, sample: &Sample, now: Instant) -> zenoh::Result<()> {
+        let key = sample.key_expr().as_str();
+        let Some(actuator) = self
+            .actuators
+            .iter_mut()
+            .find(|actuator| actuator.command_key == key)
+        else {
+            return Err(format!("no actuator commanded on {}", key).into());
+        };
+        let payload = sample.payload().to_bytes();
+        let command = flatbuffers::root::<sensors::ActuatorCommand>(&payload)
+            .map_err(|e| format!("malformed command on {}: {}", key, e))?;
+        if !command.position().is_finite() {
+            return Err(format!("{}: position must be finite", key).into());
+        }
+        actuator.command = Some((command.position(), now));
+        Ok(())
+    }
+}
+
+impl Node for ActuatorNode {
+    const NAME: &'static str = "actuator";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.set_qos(self.qos.clone());
+        for actuator in &mut self.actuators {
+            let key = keys::actuator(&actuator.config.name);
+            let publisher = ctx
+                .session()
+                .declare_publisher(key.clone())
+                .encoding(encoding::flatbuffer("sensors.ActuatorState"))
+                .qos(ctx.qos(), &key)
+                .await?;
+            actuator.publisher = Some(publisher);
+        }
+        ctx.subscribe(keys::all_actuator_commands()).await?;
+        ctx.add_timer(self.period);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let now = Instant::now();
+        match event {
+            Event::Timer(_) => {
+                let dt = match self.last_drive {
+                    Some(last) => (now - last).as_secs_f32(),
+                    None => self.period.as_secs_f32(),
+                };
+                self.last_drive = Some(now);
+                for actuator in &mut self.actuators {
+                    actuator.drive(dt, self.timeout, now).await?;
+                }
+            }
+            Event::Sample(_, sample) => self.on_command(&sample, now)?,
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        for actuator in &mut self.actuators {
+            if let Some(publisher) = actuator.publisher.take() {
+                publisher.undeclare().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if !args.rate_hz.is_finite() || args.rate_hz <= 0.0 {
+        error!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    let config = match &args.config {
+        Some(path) => match Config::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid actuator config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Config::default(),
+    };
+
+    let node = ActuatorNode {
+        period: Duration::from_secs_f64(1.0 / args.rate_hz),
+        timeout: Duration::from_millis(args.command_timeout_ms),
+        actuators: config.actuators.into_iter().map(Actuator::new).collect(),
+        last_drive: None,
+        qos: config.qos,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,167 @@
+use ax25_framer::{Address, UiFrame, kiss, serial};
+use clap::Parser;
+use common::qos::PublisherBuilderQosExt;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use std::collections::HashSet;
+use std::process::ExitCode;
+use tokio::io::AsyncReadExt;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+use zenoh::bytes::Encoding;
+use zenoh::pubsub::Publisher;
+
+#[derive(Parser)]
+#[command(
+    about = "Receives AX.25 UI frames from a KISS TNC on a serial port on the ground and republishes what they carry on Zenoh"
+)]
+struct Args {
+    /// Serial port of the TNC, e.g. /dev/ttyUSB0.
+    #[arg(long, value_name = "PATH")]
+    device: String,
+    #[arg(long, default_value_t = 9600)]
+    baud: u32,
+    /// Only republish frames from this callsign, e.g. N0CALL-11; every station heard on
+    /// the channel otherwise.
+    #[arg(long)]
+    from: Option<Address>,
+    /// Only republish frames received on this TNC port, for TNCs with more than one radio.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(..=kiss::MAX_PORT as i64))]
+    tnc_port: Option<u8>,
+    /// Key to republish what each frame carries on, one sample per frame. Run
+    /// ccsds_depacketizer with --packets on this key to take the telemetry out.
+    #[arg(long, default_value = keys::ccsds_received())]
+    key: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// What the reader filters the frames it receives by.
+struct Filter {
+    from: Option<Address>,
+    tnc_port: Option<u8>,
+}
+
+struct Ax25Decoder {
+    device: String,
+    baud: u32,
+    key: String,
+    filter: Option<Filter>,
+    reader: Option<JoinHandle<()>>,
+}
+
+// Reads frames from the TNC and republishes them, opening its port again whenever reading
+// fails.
+fn spawn_reader(
+    device: String,
+    baud: u32,
+    filter: Filter,
+    publisher: Publisher<'static>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // Stations other than --from, reported once each.
+        let mut ignored = HashSet::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let mut port = serial::open(&device, baud).await;
+            let mut decoder = kiss::Decoder::default();
+            loop {
+                let len = match port.read(&mut buf).await {
+                    Ok(0) => {
+                        warn!("Lost the TNC on {}: port closed", device);
+                        break;
+                    }
+                    Ok(len) => len,
+                    Err(e) => {
+                        warn!("Lost the TNC on {}: {}", device, e);
+                        break;
+                    }
+                };
+                for &byte in &buf[..len] {
+                    let Some((tnc_port, frame)) = decoder.push(byte) else {
+                        continue;
+                    };
+                    if filter.tnc_port.is_some_and(|port| port != tnc_port) {
+                        continue;
+                    }
+                    let frame = match UiFrame::decode(&frame) {
+                        Ok(frame) => frame,
+                        // Other stations on a shared channel send frames other than UI ones.
+                        Err(e) => {
+                            debug!("Dropping frame from TNC port {}: {}", tnc_port, e);
+                            continue;
+                        }
+                    };
+                    if filter
+                        .from
+                        .as_ref()
+                        .is_some_and(|from| *from != frame.source)
+                    {
+                        if ignored.insert(frame.source.clone()) {
+                            info!("Ignoring frames from {}", frame.source);
+                        }
+                        continue;
+                    }
+                    if let Err(e) = publisher.put(frame.info.to_vec()).await {
+                        warn!("Cannot republish frame from {}: {}", frame.source, e);
+                    }
+                }
+            }
+            sleep(serial::RETRY).await;
+        }
+    })
+}
+
+impl Node for Ax25Decoder {
+    const NAME: &'static str = "ax25_decoder";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let publisher = ctx
+            .session()
+            .declare_publisher(self.key.clone())
+            .encoding(Encoding::APPLICATION_OCTET_STREAM)
+            .qos(ctx.qos(), &self.key)
+            .await?;
+        let filter = self.filter.take().expect("init runs once");
+        match &filter.from {
+            Some(from) => info!("Republishing frames from {} on {}", from, self.key),
+            None => info!("Republishing frames from every station on {}", self.key),
+        }
+        self.reader = Some(spawn_reader(
+            self.device.clone(),
+            self.baud,
+            filter,
+            publisher,
+        ));
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, _event: Event) -> zenoh::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = Ax25Decoder {
+        device: args.device,
+        baud: args.baud,
+        key: args.key,
+        filter: Some(Filter {
+            from: args.from,
+            tnc_port: args.tnc_port,
+        }),
+        reader: None,
+    };
+    node_framework::run(node, &args.node).await
+}
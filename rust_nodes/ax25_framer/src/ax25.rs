@@ -0,0 +1,270 @@
+// AX.25 v2.2 UI frames: unnumbered, unacknowledged information frames, as amateur-band
+// telemetry beacons send them. The FCS is left to the TNC, which adds it on the air and
+// checks it before handing a received frame over KISS.
+use std::fmt;
+use std::str::FromStr;
+
+// Control field of a UI frame with the poll bit clear.
+const UI: u8 = 0x03;
+// Protocol identifier of information with no layer 3 protocol.
+const NO_LAYER_3: u8 = 0xF0;
+const ADDRESS_LEN: usize = 7;
+const CALLSIGN_LEN: usize = 6;
+// Most digipeater addresses a frame carries after its destination and source.
+const MAX_DIGIPEATERS: usize = 8;
+
+// Most information a frame carries by default, AX.25's N1.
+pub const DEFAULT_MAX_INFO_LEN: usize = 256;
+
+// A station's callsign and secondary station identifier, written as N0CALL or N0CALL-7.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Address {
+    callsign: String,
+    ssid: u8,
+}
+
+impl FromStr for Address {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let (callsign, ssid) = match text.split_once('-') {
+            Some((callsign, ssid)) => {
+                let ssid = ssid
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|&ssid| ssid <= 15)
+                    .ok_or_else(|| format!("SSID of {} is not 0 to 15", text))?;
+                (callsign, ssid)
+            }
+            None => (text, 0),
+        };
+        if !is_callsign(callsign) {
+            return Err(format!(
+                "callsign of {} is not 1 to 6 letters and digits",
+                text
+            ));
+        }
+        Ok(Address {
+            callsign: callsign.to_ascii_uppercase(),
+            ssid,
+        })
+    }
+}
+
+fn is_callsign(text: &str) -> bool {
+    !text.is_empty()
+        && text.len() <= CALLSIGN_LEN
+        && text.bytes().all(|c| c.is_ascii_alphanumeric())
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ssid {
+            0 => write!(f, "{}", self.callsign),
+            ssid => write!(f, "{}-{}", self.callsign, ssid),
+        }
+    }
+}
+
+impl Address {
+    // The address field: the callsign's characters shifted left a bit and padded with
+    // spaces, then the SSID octet with its command/response bit and, on the last address
+    // of the frame, the extension bit.
+    fn encode(&self, command: bool, last: bool, out: &mut Vec<u8>) {
+        let padded = format!("{:<width$}", self.callsign, width = CALLSIGN_LEN);
+        out.extend(padded.bytes().map(|c| c << 1));
+        out.push((command as u8) << 7 | 0b0110_0000 | self.ssid << 1 | last as u8);
+    }
+
+    // Parses the address field, returning it and whether it is the frame's last.
+    fn decode(field: &[u8]) -> Result<(Self, bool), String> {
+        let callsign: String = field[..CALLSIGN_LEN]
+            .iter()
+            .map(|&c| (c >> 1) as char)
+            .collect::<String>()
+            .trim_end()
+            .to_string();
+        // Checked as it is, as a '-' in it would read as the start of an SSID.
+        if !is_callsign(&callsign) {
+            return Err(format!(
+                "callsign {:?} is not 1 to 6 letters and digits",
+                callsign
+            ));
+        }
+        let ssid = field[CALLSIGN_LEN];
+        Ok((
+            Address {
+                callsign: callsign.to_ascii_uppercase(),
+                ssid: (ssid >> 1) & 0x0F,
+            },
+            ssid & 1 == 1,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UiFrame<'a> {
+    pub destination: Address,
+    pub source: Address,
+    pub info: &'a [u8],
+}
+
+impl<'a> UiFrame<'a> {
+    // Appends the frame, as a command with no digipeater path and no layer 3 protocol.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        self.destination.encode(true, false, out);
+        self.source.encode(false, true, out);
+        out.push(UI);
+        out.push(NO_LAYER_3);
+        out.extend_from_slice(self.info);
+    }
+
+    // Parses a frame as the TNC hands it over, without its FCS. Digipeater addresses are
+    // skipped, and frames other than UI frames refused.
+    pub fn decode(frame: &'a [u8]) -> Result<Self, String> {
+        let mut addresses = Vec::new();
+        let mut offset = 0;
+        loop {
+            let Some(field) = frame.get(offset..offset + ADDRESS_LEN) else {
+                return Err(format!(
+                    "frame of {} octets ends in its addresses",
+                    frame.len()
+                ));
+            };
+            let (address, last) = Address::decode(field)?;
+            addresses.push(address);
+            offset += ADDRESS_LEN;
+            if last {
+                break;
+            }
+            if addresses.len() == 2 + MAX_DIGIPEATERS {
+                return Err("frame has more than 8 digipeaters".to_string());
+            }
+        }
+        if addresses.len() < 2 {
+            return Err("frame has no source address".to_string());
+        }
+        // The poll/final bit may be set on a UI frame.
+        match frame.get(offset) {
+            Some(&control) if control & !0x10 == UI => {}
+            Some(&control) => return Err(format!("control field {:#04x} is not UI", control)),
+            None => return Err("frame has no control field".to_string()),
+        }
+        if frame.len() < offset + 2 {
+            return Err("frame has no protocol identifier".to_string());
+        }
+        let mut addresses = addresses.into_iter();
+        Ok(UiFrame {
+            destination: addresses.next().expect("two addresses checked"),
+            source: addresses.next().expect("two addresses checked"),
+            info: &frame[offset + 2..],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // CQ from N0CALL-7 carrying "Hi", as the framer sends it.
+    const FRAME: [u8; 18] = [
+        // CQ, shifted and padded with shifted spaces; a command, so C is set; not last.
+        0x86, 0xa2, 0x40, 0x40, 0x40, 0x40, 0xe0, //
+        // N0CALL; SSID 7 in bits 1 to 4, C clear, and the end-of-address bit set.
+        0x9c, 0x60, 0x86, 0x82, 0x98, 0x98, 0x6f, //
+        0x03, 0xf0, b'H', b'i',
+    ];
+
+    fn address(text: &str) -> Address {
+        text.parse().unwrap()
+    }
+
+    fn frame() -> UiFrame<'static> {
+        UiFrame {
+            destination: address("CQ"),
+            source: address("N0CALL-7"),
+            info: b"Hi",
+        }
+    }
+
+    #[test]
+    fn encodes_a_known_frame() {
+        let mut out = Vec::new();
+        frame().encode(&mut out);
+        assert_eq!(out, FRAME);
+    }
+
+    #[test]
+    fn decodes_a_known_frame() {
+        assert_eq!(UiFrame::decode(&FRAME).unwrap(), frame());
+    }
+
+    #[test]
+    fn round_trips_every_ssid_and_callsign_length() {
+        for (callsign, ssid) in [("A", 0), ("AB1", 1), ("N0CALL", 15), ("VK2ABC", 8)] {
+            let source = address(&format!("{}-{}", callsign, ssid));
+            let frame = UiFrame {
+                destination: address("APRS"),
+                source: source.clone(),
+                info: &[0x00, 0xc0, 0xdb, 0xff],
+            };
+            let mut out = Vec::new();
+            frame.encode(&mut out);
+            assert_eq!(out[13], 0x60 | ssid << 1 | 1, "{}", source);
+            assert_eq!(UiFrame::decode(&out).unwrap(), frame);
+        }
+    }
+
+    #[test]
+    fn parses_and_writes_addresses() {
+        assert_eq!(address("n0call-0").to_string(), "N0CALL");
+        assert_eq!(address("N0CALL-15").to_string(), "N0CALL-15");
+        for bad in ["", "N0CALL-16", "N0CALL-", "TOOLONG", "N0-CALL", "N0CALL-x"] {
+            assert!(bad.parse::<Address>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn skips_digipeaters() {
+        // The source no longer ends the addresses; WIDE1-1 does, with its has-been-repeated
+        // bit set.
+        let mut bytes = FRAME[..14].to_vec();
+        bytes[13] &= !1;
+        bytes.extend([0xae, 0x92, 0x88, 0x8a, 0x62, 0x40, 0xe3]);
+        bytes.extend(&FRAME[14..]);
+        assert_eq!(UiFrame::decode(&bytes).unwrap(), frame());
+    }
+
+    #[test]
+    fn takes_the_poll_bit_and_an_empty_info_field() {
+        let mut bytes = FRAME[..16].to_vec();
+        bytes[14] |= 0x10;
+        let frame = UiFrame::decode(&bytes).unwrap();
+        assert!(frame.info.is_empty());
+    }
+
+    #[test]
+    fn refuses_frames_it_cannot_take() {
+        let altered = |at: usize, byte: u8| {
+            let mut bytes = FRAME.to_vec();
+            bytes[at] = byte;
+            bytes
+        };
+        // Every address continuing past the end of the frame.
+        let endless: Vec<u8> = FRAME[..7].repeat(11);
+        let cases = [
+            (FRAME[..10].to_vec(), "ends in its addresses"),
+            (altered(6, 0xe1), "no source address"),
+            (endless, "more than 8 digipeaters"),
+            // An I frame.
+            (altered(14, 0x00), "not UI"),
+            (FRAME[..14].to_vec(), "no control field"),
+            (FRAME[..15].to_vec(), "no protocol identifier"),
+            (altered(0, b'-' << 1), "callsign \"-Q\""),
+        ];
+        for (bytes, error) in cases {
+            let e = UiFrame::decode(&bytes).unwrap_err();
+            assert!(e.contains(error), "{:02x?}: {}", bytes, e);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// KISS, the framing between a host and its TNC over a serial line: each frame goes between
+// FEND octets, led by a type octet of the TNC port in its high nibble and the command in
+// its low one, with FEND and FESC in the data escaped.
+const FEND: u8 = 0xC0;
+const FESC: u8 = 0xDB;
+const TFEND: u8 = 0xDC;
+const TFESC: u8 = 0xDD;
+// Command of a frame to send on the air, or received from it.
+const DATA_FRAME: u8 = 0x00;
+
+// Highest TNC port a KISS frame addresses.
+pub const MAX_PORT: u8 = 15;
+
+// Longest frame the decoder holds; a longer one is line noise, and dropped.
+const MAX_FRAME_LEN: usize = 4096;
+
+// Appends the data frame to send on the port. A leading FEND flushes whatever line noise
+// the TNC may have received before it.
+pub fn encode(port: u8, frame: &[u8], out: &mut Vec<u8>) {
+    out.push(FEND);
+    out.push(port << 4 | DATA_FRAME);
+    for &byte in frame {
+        match byte {
+            FEND => out.extend_from_slice(&[FESC, TFEND]),
+            FESC => out.extend_from_slice(&[FESC, TFESC]),
+            byte => out.push(byte),
+        }
+    }
+    out.push(FEND);
+}
+
+// Takes the data frames out of the octets the TNC sends, as they arrive.
+#[derive(Default)]
+pub struct Decoder {
+    frame: Vec<u8>,
+    escaped: bool,
+    // Whether the frame being received has grown too long to keep.
+    overrun: bool,
+}
+
+impl Decoder {
+    // Takes the next octet, returning the TNC port and the data of a data frame it ends.
+    // Frames of other commands, empty frames, and overlong ones are dropped.
+    pub fn push(&mut self, byte: u8) -> Option<(u8, Vec<u8>)> {
+        if byte == FEND {
+            let frame = std::mem::take(&mut self.frame);
+            let overrun = std::mem::take(&mut self.overrun);
+            self.escaped = false;
+            let (&kind, data) = frame.split_first()?;
+            if overrun || kind & 0x0F != DATA_FRAME || data.is_empty() {
+                return None;
+            }
+            return Some((kind >> 4, data.to_vec()));
+        }
+        let byte = match (std::mem::take(&mut self.escaped), byte) {
+            (false, FESC) => {
+                self.escaped = true;
+                return None;
+            }
+            (true, TFEND) => FEND,
+            (true, TFESC) => FESC,
+            (_, byte) => byte,
+        };
+        if self.frame.len() == MAX_FRAME_LEN {
+            self.overrun = true;
+        } else {
+            self.frame.push(byte);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        let mut decoder = Decoder::default();
+        bytes
+            .iter()
+            .filter_map(|&byte| decoder.push(byte))
+            .collect()
+    }
+
+    #[test]
+    fn escapes_fend_and_fesc() {
+        let mut out = Vec::new();
+        encode(3, &[0x01, FEND, FESC, TFEND, TFESC], &mut out);
+        assert_eq!(
+            out,
+            [0xc0, 0x30, 0x01, 0xdb, 0xdc, 0xdb, 0xdd, 0xdc, 0xdd, 0xc0]
+        );
+        assert_eq!(decode(&out), [(3, vec![0x01, FEND, FESC, TFEND, TFESC])]);
+    }
+
+    #[test]
+    fn round_trips_frames_back_to_back() {
+        let frames: Vec<(u8, Vec<u8>)> = vec![
+            (0, (0..=255).collect()),
+            (MAX_PORT, vec![FEND; 3]),
+            (1, vec![FESC]),
+        ];
+        let mut out = Vec::new();
+        for (port, frame) in &frames {
+            encode(*port, frame, &mut out);
+        }
+        assert_eq!(decode(&out), frames);
+    }
+
+    #[test]
+    fn drops_other_commands_and_empty_frames() {
+        // TXDELAY on port 0, an empty data frame, repeated FENDs, then a data frame.
+        let bytes = [
+            0xc0, 0x01, 0x32, 0xc0, 0xc0, 0x00, 0xc0, 0xc0, 0xc0, 0x10, 0xaa, 0xc0,
+        ];
+        assert_eq!(decode(&bytes), [(1, vec![0xaa])]);
+    }
+
+    #[test]
+    fn drops_an_overlong_frame() {
+        let mut bytes = vec![FEND, 0x00];
+        bytes.extend(std::iter::repeat_n(0x55, MAX_FRAME_LEN));
+        bytes.push(FEND);
+        encode(0, &[0x55], &mut bytes);
+        assert_eq!(decode(&bytes), [(0, vec![0x55])]);
+    }
+}
@@ -0,0 +1,7 @@
+// AX.25 UI frames over a KISS TNC, shared by the framer on board and the decoder on the
+// ground.
+pub mod ax25;
+pub mod kiss;
+pub mod serial;
+
+pub use ax25::{Address, UiFrame};
@@ -0,0 +1,181 @@
+use ax25_framer::ax25::DEFAULT_MAX_INFO_LEN;
+use ax25_framer::{Address, UiFrame, kiss, serial};
+use clap::Parser;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use std::collections::HashSet;
+use std::process::ExitCode;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+// Frames held for the TNC while it is slow or unplugged; samples arriving once it is full
+// are dropped.
+const QUEUE: usize = 64;
+
+#[derive(Parser)]
+#[command(
+    about = "Sends downlink packets as AX.25 UI frames through a KISS TNC on a serial port, for an amateur-band radio"
+)]
+struct Args {
+    /// Serial port of the TNC, e.g. /dev/ttyUSB0.
+    #[arg(long, value_name = "PATH")]
+    device: String,
+    #[arg(long, default_value_t = 9600)]
+    baud: u32,
+    /// Callsign to send from, e.g. N0CALL-11.
+    #[arg(long)]
+    callsign: Address,
+    /// Callsign to send to.
+    #[arg(long, default_value = "CQ")]
+    destination: Address,
+    /// Key expression of the packets to send, one frame per sample. The CCSDS framer's
+    /// packets by default.
+    #[arg(long, default_value = keys::ccsds_packets())]
+    key: String,
+    /// TNC port to send on, for TNCs with more than one radio.
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(..=kiss::MAX_PORT as i64))]
+    tnc_port: u8,
+    /// Most bytes of a packet a frame carries; larger packets are dropped.
+    #[arg(long, default_value_t = DEFAULT_MAX_INFO_LEN)]
+    max_info: usize,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct Ax25Framer {
+    device: String,
+    baud: u32,
+    source: Address,
+    destination: Address,
+    key: String,
+    tnc_port: u8,
+    max_info: usize,
+    frames: Option<mpsc::Sender<Vec<u8>>>,
+    writer: Option<JoinHandle<()>>,
+    // Samples dropped since the TNC last took one.
+    dropped: u64,
+    // Keys whose packets are too large to send, reported once each.
+    oversize: HashSet<String>,
+}
+
+// Writes the frames to the TNC, opening its port again whenever writing fails; the frame
+// being written then is lost.
+fn spawn_writer(device: String, baud: u32, mut frames: mpsc::Receiver<Vec<u8>>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let mut port = serial::open(&device, baud).await;
+            loop {
+                let Some(frame) = frames.recv().await else {
+                    return;
+                };
+                if let Err(e) = port.write_all(&frame).await {
+                    warn!("Lost the TNC on {}: {}", device, e);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+impl Ax25Framer {
+    fn on_sample(&mut self, sample: &Sample) {
+        let Some(frames) = &self.frames else {
+            return;
+        };
+        if sample.kind() == SampleKind::Delete {
+            return;
+        }
+        let info = sample.payload().to_bytes();
+        if info.len() > self.max_info {
+            let key = sample.key_expr().as_str();
+            if self.oversize.insert(key.to_string()) {
+                warn!(
+                    "Dropping samples of {}: {} bytes are over --max-info {}",
+                    key,
+                    info.len(),
+                    self.max_info
+                );
+            }
+            return;
+        }
+        let mut frame = Vec::with_capacity(info.len() + 16);
+        UiFrame {
+            destination: self.destination.clone(),
+            source: self.source.clone(),
+            info: &info,
+        }
+        .encode(&mut frame);
+        let mut escaped = Vec::with_capacity(frame.len() + 8);
+        kiss::encode(self.tnc_port, &frame, &mut escaped);
+        match frames.try_send(escaped) {
+            Ok(()) if self.dropped > 0 => {
+                info!(
+                    "Sending to the TNC again after dropping {} samples",
+                    self.dropped
+                );
+                self.dropped = 0;
+            }
+            Ok(()) => {}
+            Err(_) => {
+                if self.dropped == 0 {
+                    warn!("Dropping samples until the TNC takes them again");
+                }
+                self.dropped += 1;
+            }
+        }
+    }
+}
+
+impl Node for Ax25Framer {
+    const NAME: &'static str = "ax25_framer";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let (frames, receiver) = mpsc::channel(QUEUE);
+        self.writer = Some(spawn_writer(self.device.clone(), self.baud, receiver));
+        self.frames = Some(frames);
+        ctx.subscribe(&self.key).await?;
+        info!(
+            "Sending {} as {} to {} on TNC port {}",
+            self.key, self.source, self.destination, self.tnc_port
+        );
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(&sample),
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(writer) = self.writer.take() {
+            writer.abort();
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = Ax25Framer {
+        device: args.device,
+        baud: args.baud,
+        source: args.callsign,
+        destination: args.destination,
+        key: args.key,
+        tnc_port: args.tnc_port,
+        max_info: args.max_info,
+        frames: None,
+        writer: None,
+        dropped: 0,
+        oversize: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,35 @@
+// The serial port of the TNC, opened again whenever it fails, e.g. when a USB TNC is
+// unplugged and plugged back in.
+use std::time::Duration;
+use tokio::time::sleep;
+use tokio_serial::{SerialPortBuilderExt, SerialStream};
+use tracing::{info, warn};
+
+// How long to wait before opening the port again after it failed.
+pub const RETRY: Duration = Duration::from_secs(1);
+
+// Opens the port at the baud rate, 8 data bits, no parity, and 1 stop bit, as KISS TNCs
+// take by default, retrying until it opens. Only the first failure in a row is reported.
+pub async fn open(device: &str, baud: u32) -> SerialStream {
+    let mut failed = false;
+    loop {
+        match tokio_serial::new(device, baud).open_native_async() {
+            Ok(port) => {
+                info!("Opened TNC on {} at {} baud", device, baud);
+                return port;
+            }
+            Err(e) => {
+                if !failed {
+                    warn!(
+                        "Cannot open TNC on {}: {}; retrying every {} s",
+                        device,
+                        e,
+                        RETRY.as_secs()
+                    );
+                    failed = true;
+                }
+                sleep(RETRY).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+mod traffic;
+
+use clap::Parser;
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensors_rs::sensors;
+use std::io::{IsTerminal, Write};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::error;
+use traffic::{KeyRate, Traffic};
+use zenoh::pubsub::{Publisher, Subscriber};
+
+#[derive(Parser)]
+#[command(
+    about = "Tallies the message and byte rates of every key on the bus and publishes them on stats/bus as a live table"
+)]
+struct Args {
+    /// Key expression to tally.
+    #[arg(long, default_value = "**")]
+    key: String,
+    /// Window rates are taken over, in milliseconds.
+    #[arg(long, default_value_t = 5000)]
+    window_ms: u64,
+    /// Period of the table and of stats/bus, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    report_ms: u64,
+    /// Keys printed in the table, busiest first; stats/bus has all of them.
+    #[arg(long, default_value_t = 20)]
+    top: usize,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct BusMonitor {
+    key: String,
+    window: Duration,
+    report_period: Duration,
+    top: usize,
+    // Tallied from the subscriber's callback, so samples never queue up for a step.
+    traffic: Arc<Mutex<Traffic>>,
+    subscriber: Option<Subscriber<()>>,
+    publisher: Option<Publisher<'static>>,
+}
+
+impl BusMonitor {
+    async fn report(&self, name: &str) -> zenoh::Result<()> {
+        let rates = self.traffic.lock().unwrap().window(Instant::now());
+        print_table(&rates, self.top);
+        if let Some(publisher) = &self.publisher {
+            publisher.put(encode(name, self.window, &rates)).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Node for BusMonitor {
+    const NAME: &'static str = "bus_monitor";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let key = keys::bus_stats();
+        let publisher = ctx
+            .session()
+            .declare_publisher(key)
+            .encoding(encoding::flatbuffer("sensors.BusStats"))
+            .qos(ctx.qos(), key)
+            .await?;
+        self.publisher = Some(publisher);
+        // Declared directly rather than through the context, so samples are counted as
+        // they arrived, compressed, unreadable or corrupted alike.
+        let traffic = self.traffic.clone();
+        let subscriber = ctx
+            .session()
+            .declare_subscriber(&self.key)
+            .callback(move |sample| {
+                let bytes = sample.payload().len() + sample.attachment().map_or(0, |a| a.len());
+                traffic
+                    .lock()
+                    .unwrap()
+                    .record(sample.key_expr().as_str(), bytes, Instant::now());
+            })
+            .await?;
+        self.subscriber = Some(subscriber);
+        ctx.add_timer(self.report_period);
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Timer(_) => self.report(ctx.name()).await,
+            Event::Sample(..) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(subscriber) = self.subscriber.take() {
+            subscriber.undeclare().await?;
+        }
+        if let Some(publisher) = self.publisher.take() {
+            publisher.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+// A rate in bytes per second with a decimal unit prefix, e.g. 12.3 kB/s.
+fn bytes_per_s(rate: f64) -> String {
+    match rate {
+        r if r >= 1e9 => format!("{:.1} GB/s", r / 1e9),
+        r if r >= 1e6 => format!("{:.1} MB/s", r / 1e6),
+        r if r >= 1e3 => format!("{:.1} kB/s", r / 1e3),
+        r => format!("{:.0} B/s", r),
+    }
+}
+
+// Prints the busiest keys and the total to stdout, over the previous table on a terminal.
+fn print_table(rates: &[KeyRate], top: usize) {
+    let total_messages: f64 = rates.iter().map(|rate| rate.messages_per_s).sum();
+    let total_bytes: f64 = rates.iter().map(|rate| rate.bytes_per_s).sum();
+    let width = rates
+        .iter()
+        .take(top)
+        .map(|rate| rate.key.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    let mut out = std::io::stdout().lock();
+    if std::io::stdout().is_terminal() {
+        let _ = write!(out, "\x1b[2J\x1b[H");
+    }
+    let _ = writeln!(
+        out,
+        "{:<width$} {:>10} {:>12} {:>6}",
+        "KEY", "MSG/S", "BYTES/S", "SHARE"
+    );
+    for rate in rates.iter().take(top) {
+        let share = if total_bytes > 0.0 {
+            rate.bytes_per_s / total_bytes * 100.0
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            out,
+            "{:<width$} {:>10.1} {:>12} {:>5.1}%",
+            rate.key,
+            rate.messages_per_s,
+            bytes_per_s(rate.bytes_per_s),
+            share
+        );
+    }
+    if rates.len() > top {
+        let _ = writeln!(out, "... {} more keys", rates.len() - top);
+    }
+    let _ = writeln!(
+        out,
+        "{:<width$} {:>10.1} {:>12}\n",
+        "TOTAL",
+        total_messages,
+        bytes_per_s(total_bytes)
+    );
+    let _ = out.flush();
+}
+
+fn encode(name: &str, window: Duration, rates: &[KeyRate]) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(1024);
+    let keys: Vec<_> = rates
+        .iter()
+        .map(|rate| {
+            let key = builder.create_string(&rate.key);
+            sensors::KeyTraffic::create(
+                &mut builder,
+                &sensors::KeyTrafficArgs {
+                    key: Some(key),
+                    messages: rate.messages,
+                    bytes: rate.bytes,
+                    messages_per_s: rate.messages_per_s as f32,
+                    bytes_per_s: rate.bytes_per_s as f32,
+                },
+            )
+        })
+        .collect();
+    let keys = builder.create_vector(&keys);
+    let node = builder.create_string(name);
+    let stats = sensors::BusStats::create(
+        &mut builder,
+        &sensors::BusStatsArgs {
+            timestamp_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_micros() as u64)
+                .unwrap_or(0),
+            node: Some(node),
+            window_ms: window.as_millis() as u64,
+            keys: Some(keys),
+        },
+    );
+    builder.finish(stats, None);
+    builder.finished_data().to_vec()
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if args.window_ms == 0 || args.report_ms == 0 {
+        error!("--window-ms and --report-ms must be positive");
+        return ExitCode::FAILURE;
+    }
+    let window = Duration::from_millis(args.window_ms);
+    let node = BusMonitor {
+        key: args.key.clone(),
+        window,
+        report_period: Duration::from_millis(args.report_ms),
+        top: args.top,
+        traffic: Arc::new(Mutex::new(Traffic::new(window))),
+        subscriber: None,
+        publisher: None,
+    };
+    node_framework::run(node, &args.node).await
+}
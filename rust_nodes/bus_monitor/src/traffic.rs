@@ -0,0 +1,96 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+// Slots a window is tallied in. The window slides by a slot at a time, so rates lag by at
+// most a tenth of it.
+const SLOTS: u32 = 10;
+
+// What arrived on one key during one slot, numbered from when tallying started.
+struct Slot {
+    index: u64,
+    messages: u64,
+    bytes: u64,
+}
+
+// Traffic on one key over the window, busiest keys first.
+pub struct KeyRate {
+    pub key: String,
+    pub messages: u64,
+    pub bytes: u64,
+    pub messages_per_s: f64,
+    pub bytes_per_s: f64,
+}
+
+// Tallies the samples and bytes received per key over a sliding window.
+pub struct Traffic {
+    started: Instant,
+    slot: Duration,
+    keys: HashMap<String, VecDeque<Slot>>,
+}
+
+impl Traffic {
+    pub fn new(window: Duration) -> Self {
+        Traffic {
+            started: Instant::now(),
+            slot: (window / SLOTS).max(Duration::from_millis(1)),
+            keys: HashMap::new(),
+        }
+    }
+
+    fn index(&self, at: Instant) -> u64 {
+        (at.saturating_duration_since(self.started).as_nanos() / self.slot.as_nanos()) as u64
+    }
+
+    // Counts a sample of the given size received on key at the given time.
+    pub fn record(&mut self, key: &str, bytes: usize, at: Instant) {
+        let index = self.index(at);
+        // Only a key seen for the first time costs an allocation.
+        let slots = match self.keys.get_mut(key) {
+            Some(slots) => slots,
+            None => self.keys.entry(key.to_string()).or_default(),
+        };
+        match slots.back_mut() {
+            Some(slot) if slot.index == index => {
+                slot.messages += 1;
+                slot.bytes += bytes as u64;
+            }
+            _ => slots.push_back(Slot {
+                index,
+                messages: 1,
+                bytes: bytes as u64,
+            }),
+        }
+    }
+
+    // The traffic of every key with samples in the window ending now, busiest by bytes
+    // first, forgetting what fell out of the window. Rates are taken over the part of the
+    // window since tallying started.
+    pub fn window(&mut self, now: Instant) -> Vec<KeyRate> {
+        let current = self.index(now);
+        let oldest = (current + 1).saturating_sub(SLOTS as u64);
+        let elapsed = now.saturating_duration_since(self.started).as_nanos();
+        let covered = elapsed.saturating_sub(self.slot.as_nanos() * oldest as u128);
+        let covered = Duration::from_nanos(covered as u64).max(Duration::from_millis(1));
+        let mut rates = Vec::with_capacity(self.keys.len());
+        self.keys.retain(|key, slots| {
+            while slots.front().is_some_and(|slot| slot.index < oldest) {
+                slots.pop_front();
+            }
+            if slots.is_empty() {
+                return false;
+            }
+            let messages = slots.iter().map(|slot| slot.messages).sum();
+            let bytes = slots.iter().map(|slot| slot.bytes).sum();
+            rates.push(KeyRate {
+                key: key.clone(),
+                messages,
+                bytes,
+                messages_per_s: messages as f64 / covered.as_secs_f64(),
+                bytes_per_s: bytes as f64 / covered.as_secs_f64(),
+            });
+            true
+        });
+        rates.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.key.cmp(&b.key)));
+        rates
+    }
+}
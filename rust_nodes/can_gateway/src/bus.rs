@@ -0,0 +1,35 @@
+// The CAN interface, opened again whenever it fails, e.g. when a USB adapter is unplugged
+// and plugged back in, or the interface is taken down.
+use socketcan::tokio::CanSocket;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+// How long to wait before opening the interface again after it failed.
+pub const RETRY: Duration = Duration::from_secs(1);
+
+// Opens a raw socket on the interface, retrying until it opens. Only the first failure in
+// a row is reported.
+pub async fn open(interface: &str) -> CanSocket {
+    let mut failed = false;
+    loop {
+        match CanSocket::open(interface) {
+            Ok(socket) => {
+                info!("Opened {}", interface);
+                return socket;
+            }
+            Err(e) => {
+                if !failed {
+                    warn!(
+                        "Cannot open {}: {}; retrying every {} s",
+                        interface,
+                        e,
+                        RETRY.as_secs()
+                    );
+                    failed = true;
+                }
+                sleep(RETRY).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use common::config;
+use sensor_decode::MessageType;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use zenoh::key_expr::KeyExpr;
+
+// Bytes in a classic CAN frame.
+pub const MAX_FRAME_LEN: usize = 8;
+const MAX_STANDARD_ID: u32 = 0x7FF;
+const MAX_EXTENDED_ID: u32 = 0x1FFF_FFFF;
+
+// Byte order of a signal, as a DBC file's @1 and @0.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteOrder {
+    // Intel: start_bit is the signal's least significant bit, and it runs up from there.
+    #[default]
+    LittleEndian,
+    // Motorola: start_bit is the signal's most significant bit, and it runs down through
+    // each byte and on into the next.
+    BigEndian,
+}
+
+// One value of a frame: bits of the frame, or a constant.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SignalConfig {
+    // What the signal holds: a channel of the table received, as sensor_decode names them,
+    // or the path to the value in the samples sent as telemetry_json decodes them, with /
+    // between nested fields, e.g. position.
+    pub channel: String,
+    // Bits of the frame the signal takes, counting bit 0 as the least significant bit of
+    // byte 0, as DBC files do.
+    pub start_bit: Option<u32>,
+    pub length: Option<u32>,
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    #[serde(default)]
+    pub signed: bool,
+    // The signal's bits hold (value - offset) / scale, rounded.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+    // Constant the signal holds rather than a value from the bus, e.g. a sensor id. A
+    // received constant takes no bits; a sent one takes its bits like any other signal.
+    pub value: Option<f64>,
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+impl SignalConfig {
+    // Positions of the signal's bits in the frame, least significant first.
+    pub fn bits(&self) -> Vec<u32> {
+        let (Some(start), Some(length)) = (self.start_bit, self.length) else {
+            return Vec::new();
+        };
+        match self.byte_order {
+            ByteOrder::LittleEndian => (start..start + length).collect(),
+            ByteOrder::BigEndian => {
+                let mut bits = Vec::with_capacity(length as usize);
+                let mut bit = start;
+                for _ in 0..length {
+                    bits.push(bit);
+                    // Down through the byte, then on from the top of the next one.
+                    bit = if bit % 8 == 0 { bit + 15 } else { bit - 1 };
+                }
+                bits.reverse();
+                bits
+            }
+        }
+    }
+
+    // Checks the signal of a frame of frame_len bytes, sent or received.
+    fn validate(&self, message: &str, frame_len: usize, sent: bool) -> Result<(), String> {
+        let name = format!("{} {}", message, self.channel);
+        match (self.start_bit, self.length) {
+            (Some(start), Some(length)) => {
+                if start >= 64 || !(1..=64).contains(&length) {
+                    return Err(format!(
+                        "{}: start_bit must be 0 to 63 and length 1 to 64 bits",
+                        name
+                    ));
+                }
+                if self.bits().iter().any(|&bit| bit as usize >= frame_len * 8) {
+                    return Err(format!(
+                        "{}: runs past the end of a {}-byte frame",
+                        name, frame_len
+                    ));
+                }
+                if !sent && self.value.is_some() {
+                    return Err(format!(
+                        "{}: give either start_bit and length, or value",
+                        name
+                    ));
+                }
+            }
+            (None, None) if !sent && self.value.is_some() => {}
+            (None, None) if !sent => {
+                return Err(format!(
+                    "{}: give either start_bit and length, or value",
+                    name
+                ));
+            }
+            _ => return Err(format!("{}: give start_bit and length", name)),
+        }
+        if self.value.is_some_and(|value| !value.is_finite()) {
+            return Err(format!("{}: value must be finite", name));
+        }
+        if !(self.scale.is_finite() && self.scale != 0.0 && self.offset.is_finite()) {
+            return Err(format!(
+                "{}: scale must be finite and non-zero, and offset finite",
+                name
+            ));
+        }
+        Ok(())
+    }
+}
+
+// A CAN frame published as a sensors table on a device key.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReceiveConfig {
+    pub id: u32,
+    // Whether the id is a 29-bit extended one rather than an 11-bit standard one.
+    #[serde(default)]
+    pub extended: bool,
+    // Key to publish on, e.g. devices/power0.
+    pub key: String,
+    // Sensors table to publish, one sensor_decode reads, e.g. sensors.Power.
+    pub table: String,
+    // One signal per channel of the table.
+    pub signals: Vec<SignalConfig>,
+}
+
+impl ReceiveConfig {
+    pub fn message_type(&self) -> MessageType {
+        MessageType::ALL
+            .into_iter()
+            .find(|kind| kind.table() == self.table)
+            .expect("table checked by validate")
+    }
+}
+
+// Samples on a key sent as a CAN frame.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SendConfig {
+    // Key whose samples to send, e.g. cmd/actuators/fin0; a key, not a key expression.
+    pub key: String,
+    pub id: u32,
+    #[serde(default)]
+    pub extended: bool,
+    // Bytes in the frame, bits no signal takes sent as zeros.
+    #[serde(default = "default_frame_len")]
+    pub length: usize,
+    pub signals: Vec<SignalConfig>,
+}
+
+fn default_frame_len() -> usize {
+    MAX_FRAME_LEN
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub receive: Vec<ReceiveConfig>,
+    #[serde(default)]
+    pub send: Vec<SendConfig>,
+}
+
+// How an id is named in messages.
+pub fn describe(id: u32, extended: bool) -> String {
+    if extended {
+        format!("extended id {:#010x}", id)
+    } else {
+        format!("id {:#05x}", id)
+    }
+}
+
+fn validate_id(id: u32, extended: bool) -> Result<(), String> {
+    let max = if extended {
+        MAX_EXTENDED_ID
+    } else {
+        MAX_STANDARD_ID
+    };
+    if id > max {
+        return Err(format!("{} is over {:#x}", describe(id, extended), max));
+    }
+    Ok(())
+}
+
+fn validate_key(key: &str) -> Result<(), String> {
+    let key_expr = KeyExpr::new(key).map_err(|e| format!("{}: {}", key, e))?;
+    if key_expr.is_wild() {
+        return Err(format!("{} is not a single key", key));
+    }
+    Ok(())
+}
+
+// Checks that no two of the signals share a bit of the frame.
+fn validate_overlap(message: &str, signals: &[SignalConfig]) -> Result<(), String> {
+    let mut taken = 0u64;
+    for signal in signals {
+        for bit in signal.bits() {
+            if taken & 1 << bit != 0 {
+                return Err(format!(
+                    "{} {}: bit {} is taken by another signal",
+                    message, signal.channel, bit
+                ));
+            }
+            taken |= 1 << bit;
+        }
+    }
+    Ok(())
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.receive.is_empty() && self.send.is_empty() {
+            return Err("nothing to receive or send configured".to_string());
+        }
+        let mut ids = HashSet::new();
+        for message in &self.receive {
+            let name = describe(message.id, message.extended);
+            validate_id(message.id, message.extended)?;
+            if !ids.insert((message.id, message.extended)) {
+                return Err(format!("{} appears more than once", name));
+            }
+            validate_key(&message.key)?;
+            let Some(kind) = MessageType::ALL
+                .into_iter()
+                .find(|kind| kind.table() == message.table)
+            else {
+                return Err(format!("{}: unknown table '{}'", name, message.table));
+            };
+            for signal in &message.signals {
+                signal.validate(&name, MAX_FRAME_LEN, false)?;
+                if !kind.channels().contains(&signal.channel.as_str()) {
+                    return Err(format!(
+                        "{}: {} has no channel '{}'",
+                        name, message.table, signal.channel
+                    ));
+                }
+            }
+            for channel in kind.channels() {
+                let given = message
+                    .signals
+                    .iter()
+                    .filter(|signal| signal.channel == *channel)
+                    .count();
+                if given != 1 {
+                    return Err(format!(
+                        "{}: give channel '{}' of {} once, not {} times",
+                        name, channel, message.table, given
+                    ));
+                }
+            }
+            validate_overlap(&name, &message.signals)?;
+        }
+        for message in &self.send {
+            let name = describe(message.id, message.extended);
+            validate_id(message.id, message.extended)?;
+            // The gateway would otherwise take the frames it sends for received ones, as
+            // the kernel loops them back.
+            if !ids.insert((message.id, message.extended)) {
+                return Err(format!("{} appears more than once", name));
+            }
+            validate_key(&message.key)?;
+            if message.length > MAX_FRAME_LEN {
+                return Err(format!("{}: frames carry at most 8 bytes", name));
+            }
+            if message.signals.is_empty() {
+                return Err(format!("{}: no signals configured", name));
+            }
+            for signal in &message.signals {
+                signal.validate(&name, message.length, true)?;
+            }
+            validate_overlap(&name, &message.signals)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,343 @@
+mod bus;
+mod config;
+mod signal;
+
+use clap::Parser;
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use common::sequence::SequencedPublisher;
+use config::{Config, ReceiveConfig, SendConfig, SignalConfig};
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensor_decode::MessageType;
+use serde_json::Value;
+use socketcan::tokio::CanSocket;
+use socketcan::{CanFrame, EmbeddedFrame, ExtendedId, Id, SocketOptions, StandardId};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+use zenoh::liveliness::LivelinessToken;
+use zenoh::sample::{Sample, SampleKind};
+
+#[derive(Parser)]
+#[command(
+    about = "Publishes the CAN frames of its config as sensor readings on their device keys, and sends samples on its command keys as CAN frames"
+)]
+struct Args {
+    /// Frames to receive and send, and how their signals are laid out (TOML or JSON).
+    config: PathBuf,
+    /// SocketCAN interface of the bus, e.g. can0 or vcan0.
+    #[arg(long, default_value = "can0")]
+    interface: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// A frame id, with whether it is extended.
+type FrameId = (u32, bool);
+
+// A received frame and where its readings go.
+struct Receiver {
+    key: String,
+    kind: MessageType,
+    // One signal per channel of kind, in the order sensor_decode gives the channels.
+    signals: Vec<SignalConfig>,
+    publisher: SequencedPublisher<'static>,
+}
+
+struct CanGateway {
+    interface: String,
+    receive: Vec<ReceiveConfig>,
+    // Frames to send, by the key whose samples fill them.
+    send: HashMap<String, Vec<SendConfig>>,
+    socket: Option<CanSocket>,
+    // Whether sending is failing, so only the first failure in a row is reported.
+    failing: bool,
+    reader: Option<JoinHandle<()>>,
+    tokens: Vec<LivelinessToken>,
+    // Channels missing from the samples of a key, and keys whose samples cannot be
+    // decoded, reported once each.
+    reported: HashSet<String>,
+}
+
+fn frame_id(id: Id) -> FrameId {
+    match id {
+        Id::Standard(id) => (id.as_raw() as u32, false),
+        Id::Extended(id) => (id.as_raw(), true),
+    }
+}
+
+// Reads frames off the bus and publishes the readings of those configured, opening the
+// interface again whenever reading fails. Frames of other ids, remote frames, and error
+// frames are ignored.
+fn spawn_reader(interface: String, receivers: HashMap<FrameId, Receiver>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // Ids whose frames are too short for their signals, reported once each.
+        let mut short = HashSet::new();
+        loop {
+            let socket = bus::open(&interface).await;
+            loop {
+                let frame = match socket.read_frame().await {
+                    Ok(CanFrame::Data(frame)) => frame,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        warn!("Lost {}: {}", interface, e);
+                        break;
+                    }
+                };
+                let id = frame_id(frame.id());
+                let Some(receiver) = receivers.get(&id) else {
+                    continue;
+                };
+                let values: Option<Vec<f64>> = receiver
+                    .signals
+                    .iter()
+                    .map(|signal| signal::decode(signal, frame.data()))
+                    .collect();
+                let Some(values) = values else {
+                    if short.insert(id) {
+                        warn!(
+                            "Dropping frames of {}: {} bytes are too short for its signals",
+                            config::describe(id.0, id.1),
+                            frame.data().len()
+                        );
+                    }
+                    continue;
+                };
+                let payload = sensor_decode::encode(receiver.kind, &values);
+                if let Err(e) = receiver.publisher.put(payload).await {
+                    error!("Failed to publish on {}: {}", receiver.key, e);
+                }
+            }
+            sleep(bus::RETRY).await;
+        }
+    })
+}
+
+// The signal's value from a sample decoded by telemetry_json, or None if the sample does
+// not have it.
+fn value(signal: &SignalConfig, sample: &Value) -> Result<Option<f64>, String> {
+    if let Some(value) = signal.value {
+        return Ok(Some(value));
+    }
+    match sample.pointer(&format!("/{}", signal.channel)) {
+        Some(Value::Number(number)) => Ok(number.as_f64()),
+        Some(Value::Bool(flag)) => Ok(Some(*flag as u8 as f64)),
+        Some(Value::Null) | None => Ok(None),
+        Some(other) => Err(format!("{} is not a number", other)),
+    }
+}
+
+impl CanGateway {
+    fn on_sample(&mut self, sample: &Sample) -> Vec<CanFrame> {
+        if sample.kind() == SampleKind::Delete {
+            return Vec::new();
+        }
+        let key = sample.key_expr().as_str();
+        let decoded = match telemetry_json::decode(sample) {
+            Ok(Some(decoded)) => decoded,
+            Ok(None) => {
+                if self.reported.insert(key.to_string()) {
+                    warn!(
+                        "Not sending samples of {}: no conversion for {}",
+                        key,
+                        sample.encoding()
+                    );
+                }
+                return Vec::new();
+            }
+            Err(e) => {
+                warn!("Dropping a sample of {}: {}", key, e);
+                return Vec::new();
+            }
+        };
+        let mut frames = Vec::new();
+        for message in self.send.get(key).into_iter().flatten() {
+            let mut data = vec![0u8; message.length];
+            let mut complete = true;
+            for signal in &message.signals {
+                let problem = match value(signal, &decoded) {
+                    Ok(Some(value)) => {
+                        signal::encode(signal, value, &mut data);
+                        continue;
+                    }
+                    Ok(None) => format!("{} is missing", signal.channel),
+                    Err(e) => format!("{}: {}", signal.channel, e),
+                };
+                complete = false;
+                if self.reported.insert(format!("{} {}", key, signal.channel)) {
+                    warn!(
+                        "Not sending {} for samples of {}: {}",
+                        config::describe(message.id, message.extended),
+                        key,
+                        problem
+                    );
+                }
+            }
+            if !complete {
+                continue;
+            }
+            let id: Id = if message.extended {
+                ExtendedId::new(message.id)
+                    .expect("id checked by validate")
+                    .into()
+            } else {
+                StandardId::new(message.id as u16)
+                    .expect("id checked by validate")
+                    .into()
+            };
+            frames.push(CanFrame::new(id, &data).expect("length checked by validate"));
+        }
+        frames
+    }
+
+    // Sends the frames, opening a socket first if there is none. The socket only sends, so
+    // it takes no frames off the bus.
+    async fn send(&mut self, frames: Vec<CanFrame>) {
+        for frame in frames {
+            if self.socket.is_none() {
+                match CanSocket::open(&self.interface)
+                    .and_then(|socket| socket.set_filter_drop_all().map(|_| socket))
+                {
+                    Ok(socket) => self.socket = Some(socket),
+                    Err(e) => {
+                        self.fail(format!("cannot open {}: {}", self.interface, e));
+                        return;
+                    }
+                }
+            }
+            let socket = self.socket.as_ref().expect("opened above");
+            match socket.write_frame(frame).await {
+                Ok(()) => {
+                    if self.failing {
+                        info!("Sending on {} again", self.interface);
+                        self.failing = false;
+                    }
+                }
+                Err(e) => {
+                    // Opened again for the next frame, in case the interface went away.
+                    self.socket = None;
+                    self.fail(format!("{}", e));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn fail(&mut self, problem: String) {
+        if !self.failing {
+            warn!("Failed to send on {}: {}", self.interface, problem);
+            self.failing = true;
+        }
+    }
+}
+
+impl Node for CanGateway {
+    const NAME: &'static str = "can_gateway";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let mut receivers = HashMap::new();
+        for message in &self.receive {
+            let kind = message.message_type();
+            let signals = kind
+                .channels()
+                .iter()
+                .map(|channel| {
+                    message
+                        .signals
+                        .iter()
+                        .find(|signal| signal.channel == *channel)
+                        .expect("channels checked by validate")
+                        .clone()
+                })
+                .collect();
+            let publisher = ctx
+                .session()
+                .declare_publisher(message.key.clone())
+                .encoding(encoding::flatbuffer(kind.table()))
+                .qos(ctx.qos(), &message.key)
+                .await?;
+            // Announces the device on its key for as long as the gateway runs, so fusion
+            // --discover reads it.
+            let token = ctx
+                .session()
+                .liveliness()
+                .declare_token(message.key.clone())
+                .await?;
+            self.tokens.push(token);
+            receivers.insert(
+                (message.id, message.extended),
+                Receiver {
+                    key: message.key.clone(),
+                    kind,
+                    signals,
+                    publisher: ctx.sequenced(publisher),
+                },
+            );
+        }
+        for key in self.send.keys() {
+            ctx.subscribe(key).await?;
+        }
+        if !receivers.is_empty() {
+            self.reader = Some(spawn_reader(self.interface.clone(), receivers));
+        }
+        info!(
+            "Receiving {} and sending {} frames on {}",
+            self.receive.len(),
+            self.send.values().map(Vec::len).sum::<usize>(),
+            self.interface
+        );
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => {
+                let frames = self.on_sample(&sample);
+                self.send(frames).await;
+            }
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+        for token in self.tokens.drain(..) {
+            token.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let config = match Config::load(&args.config) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Invalid CAN mapping: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut send: HashMap<String, Vec<SendConfig>> = HashMap::new();
+    for message in config.send {
+        send.entry(message.key.clone()).or_default().push(message);
+    }
+    let node = CanGateway {
+        interface: args.interface,
+        receive: config.receive,
+        send,
+        socket: None,
+        failing: false,
+        reader: None,
+        tokens: Vec::new(),
+        reported: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
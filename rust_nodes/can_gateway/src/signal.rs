@@ -0,0 +1,52 @@
+// Packing signals into CAN frames and taking them back out, as a DBC file lays them out.
+use crate::config::SignalConfig;
+
+// The signal's value in the frame, or None if the frame is too short to hold it. A
+// constant signal takes its value whatever the frame holds.
+pub fn decode(signal: &SignalConfig, data: &[u8]) -> Option<f64> {
+    if let Some(value) = signal.value {
+        return Some(value);
+    }
+    let bits = signal.bits();
+    let mut raw = 0u64;
+    for (i, &bit) in bits.iter().enumerate() {
+        let byte = data.get(bit as usize / 8)?;
+        raw |= ((byte >> (bit % 8)) & 1) as u64 * (1 << i);
+    }
+    let length = bits.len() as u32;
+    let raw = if signal.signed && length < 64 && raw >> (length - 1) & 1 == 1 {
+        // Sign-extends the raw value from its top bit.
+        (raw | !0 << length) as i64 as f64
+    } else if signal.signed {
+        raw as i64 as f64
+    } else {
+        raw as f64
+    };
+    Some(raw * signal.scale + signal.offset)
+}
+
+// Writes the value into the signal's bits of the frame, rounded and saturated to what the
+// bits hold.
+pub fn encode(signal: &SignalConfig, value: f64, data: &mut [u8]) {
+    let bits = signal.bits();
+    let length = bits.len() as u32;
+    let scaled = ((value - signal.offset) / signal.scale).round();
+    let raw = if signal.signed {
+        let max = (i64::MAX >> (64 - length)) as f64;
+        let min = (i64::MIN >> (64 - length)) as f64;
+        // NaN becomes 0.
+        scaled.clamp(min, max) as i64 as u64
+    } else {
+        let max = (u64::MAX >> (64 - length)) as f64;
+        scaled.clamp(0.0, max) as u64
+    };
+    for (i, &bit) in bits.iter().enumerate() {
+        let byte = &mut data[bit as usize / 8];
+        let mask = 1 << (bit % 8);
+        if raw >> i & 1 == 1 {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+    }
+}
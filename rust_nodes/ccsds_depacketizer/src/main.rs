@@ -0,0 +1,192 @@
+use ccsds_framer::packet;
+use ccsds_framer::{Config, SpacePacket};
+use clap::Parser;
+use common::qos::PublisherBuilderQosExt;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use zenoh::pubsub::Publisher;
+use zenoh::sample::{Sample, SampleKind};
+use zenoh::time::{NTP64, Timestamp};
+
+// How often the packets missed since the last report are reported.
+const REPORT_PERIOD: Duration = Duration::from_secs(10);
+
+#[derive(Parser)]
+#[command(
+    about = "Takes the telemetry out of CCSDS Space Packets on the ground and republishes it under ground/ by APID, counting the packets missed"
+)]
+struct Args {
+    /// APID of every key sent down (TOML or JSON); the framer's config. Defaults to the
+    /// framer's default.
+    config: Option<PathBuf>,
+    /// Key the packets arrive on.
+    #[arg(long, default_value = keys::ccsds_packets())]
+    packets: String,
+    /// Prefix to republish the telemetry under, followed by its key.
+    #[arg(long, value_name = "KEY_PREFIX", default_value = keys::GROUND)]
+    prefix: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// One APID being republished.
+struct Output {
+    publisher: Publisher<'static>,
+    // Sequence count of the packet expected next.
+    expected: u16,
+}
+
+struct CcsdsDepacketizer {
+    config: Config,
+    packets: String,
+    prefix: String,
+    outputs: HashMap<u16, Output>,
+    // Packets missed since the last report, by APID.
+    missed: BTreeMap<u16, u64>,
+    // APIDs not in the config, reported once each.
+    unknown: HashSet<u16>,
+}
+
+impl CcsdsDepacketizer {
+    // Republishes every packet of the sample, which holds one or more back to back.
+    async fn on_sample(&mut self, ctx: &Context, sample: &Sample) -> zenoh::Result<()> {
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let bytes = sample.payload().to_bytes();
+        let mut rest = &bytes[..];
+        while !rest.is_empty() {
+            match SpacePacket::decode(rest) {
+                Ok((packet, len)) => {
+                    self.republish(ctx, &packet).await?;
+                    rest = &rest[len..];
+                }
+                Err(e) => {
+                    warn!("Dropping {} octets of {}: {}", rest.len(), self.packets, e);
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn republish(&mut self, ctx: &Context, packet: &SpacePacket<'_>) -> zenoh::Result<()> {
+        let Some(config) = self.config.apid(packet.apid) else {
+            if self.unknown.insert(packet.apid) {
+                warn!("Dropping packets of APID {}: not configured", packet.apid);
+            }
+            return Ok(());
+        };
+        let output = match self.outputs.get_mut(&packet.apid) {
+            Some(output) => {
+                let missed = packet::missed(output.expected, packet.sequence_count);
+                if missed > 0 {
+                    *self.missed.entry(packet.apid).or_default() += missed as u64;
+                }
+                output
+            }
+            None => {
+                let key = format!("{}/{}", self.prefix, config.key);
+                let publisher = ctx
+                    .session()
+                    .declare_publisher(key.clone())
+                    .encoding(config.encoding())
+                    .qos(ctx.qos(), &key)
+                    .await?;
+                info!("Receiving APID {} as {}", packet.apid, key);
+                self.outputs.entry(packet.apid).or_insert(Output {
+                    publisher,
+                    expected: packet.sequence_count,
+                })
+            }
+        };
+        output.expected = packet::next(packet.sequence_count);
+        // Stamped with when the sample was published on board, so the ground plots it at
+        // that time rather than when it came down.
+        let id = *ctx.session().new_timestamp().get_id();
+        let timestamp = Timestamp::new(NTP64::from(packet.time), id);
+        output
+            .publisher
+            .put(packet.data.to_vec())
+            .timestamp(timestamp)
+            .await
+    }
+
+    fn report(&mut self) {
+        if self.missed.is_empty() {
+            return;
+        }
+        let total: u64 = self.missed.values().sum();
+        let by_apid: Vec<String> = self
+            .missed
+            .iter()
+            .map(|(apid, missed)| format!("{} of APID {}", missed, apid))
+            .collect();
+        warn!(
+            "Missed {} packets in the last {} s: {}",
+            total,
+            REPORT_PERIOD.as_secs(),
+            by_apid.join(", ")
+        );
+        self.missed.clear();
+    }
+}
+
+impl Node for CcsdsDepacketizer {
+    const NAME: &'static str = "ccsds_depacketizer";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.subscribe(&self.packets).await?;
+        ctx.add_timer(REPORT_PERIOD);
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(ctx, &sample).await,
+            Event::Timer(_) => {
+                self.report();
+                Ok(())
+            }
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        self.report();
+        for (_, output) in self.outputs.drain() {
+            output.publisher.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let config = match &args.config {
+        Some(path) => match Config::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid CCSDS config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Config::default(),
+    };
+    let node = CcsdsDepacketizer {
+        config,
+        packets: args.packets,
+        prefix: args.prefix.trim_end_matches('/').to_string(),
+        outputs: HashMap::new(),
+        missed: BTreeMap::new(),
+        unknown: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
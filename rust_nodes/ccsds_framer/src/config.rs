@@ -0,0 +1,99 @@
+use crate::packet::MAX_APID;
+use common::config;
+use common::encoding;
+use serde::Deserialize;
+use std::path::Path;
+use zenoh::bytes::Encoding;
+use zenoh::key_expr::KeyExpr;
+
+// The telemetry sent down when no config file is given: the flight phase, fused state,
+// and flight events, and every sensor sensor_sim publishes by default.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+// The APID the samples of a key are sent on, and what the ground tags them as.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PacketConfig {
+    pub apid: u16,
+    // Key of the telemetry, without the downlink prefix, e.g. state/fused; a key, not a
+    // key expression, so the ground knows which key each APID's samples are of.
+    pub key: String,
+    // FlatBuffers table the payloads are, which the ground tags with its schema version.
+    pub table: Option<String>,
+    // Any other encoding the payloads are published with.
+    pub encoding: Option<String>,
+}
+
+impl PacketConfig {
+    // The encoding the ground republishes the payloads with: untagged if the config gives
+    // none, so sensor decoders go by the key.
+    pub fn encoding(&self) -> Encoding {
+        match (&self.table, &self.encoding) {
+            (Some(table), _) => encoding::flatbuffer(table),
+            (None, Some(rendered)) => Encoding::from(rendered.as_str()),
+            (None, None) => Encoding::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    pub packets: Vec<PacketConfig>,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // The packets of an APID, if it is configured.
+    pub fn apid(&self, apid: u16) -> Option<&PacketConfig> {
+        self.packets.iter().find(|packet| packet.apid == apid)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.packets.is_empty() {
+            return Err("no packets configured".to_string());
+        }
+        for (i, packet) in self.packets.iter().enumerate() {
+            let key = &packet.key;
+            let key_expr = KeyExpr::new(key.as_str()).map_err(|e| format!("{}: {}", key, e))?;
+            if key_expr.is_wild() {
+                return Err(format!("{}: not a single key", key));
+            }
+            if packet.apid > MAX_APID {
+                return Err(format!(
+                    "{}: APID {} is above {}",
+                    key, packet.apid, MAX_APID
+                ));
+            }
+            if packet.table.is_some() && packet.encoding.is_some() {
+                return Err(format!("{}: give one of table and encoding", key));
+            }
+            let earlier = &self.packets[..i];
+            if earlier.iter().any(|other| &other.key == key) {
+                return Err(format!("{} configured twice", key));
+            }
+            if let Some(other) = earlier.iter().find(|other| other.apid == packet.apid) {
+                return Err(format!(
+                    "{} and {} both on APID {}",
+                    other.key, key, packet.apid
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let config: Config =
+            toml::from_str(DEFAULT_CONFIG).expect("Built-in CCSDS config is invalid.");
+        config
+            .validate()
+            .expect("Built-in CCSDS config is invalid.");
+        config
+    }
+}
@@ -0,0 +1,7 @@
+// CCSDS Space Packet encapsulation of downlink telemetry, shared by the framer on board
+// and the depacketizer on the ground, which read the same APID config.
+pub mod config;
+pub mod packet;
+
+pub use config::{Config, PacketConfig};
+pub use packet::SpacePacket;
@@ -0,0 +1,184 @@
+use ccsds_framer::packet::{self, MAX_DATA_LEN};
+use ccsds_framer::{Config, PacketConfig, SpacePacket};
+use clap::Parser;
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+use zenoh::bytes::Encoding;
+use zenoh::pubsub::Publisher;
+use zenoh::sample::{Sample, SampleKind};
+
+#[derive(Parser)]
+#[command(
+    about = "Wraps downlink telemetry into CCSDS Space Packets on the APIDs of its config, for the radio"
+)]
+struct Args {
+    /// APID of every key to send down (TOML or JSON). Defaults to the flight phase, fused
+    /// state, flight events, and sensor_sim's sensors.
+    config: Option<PathBuf>,
+    /// Prefix the configured keys are taken from, the downsampler's by default.
+    #[arg(long, value_name = "KEY_PREFIX", default_value = keys::DOWNLINK)]
+    prefix: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// The packets of one configured key.
+struct Stream {
+    config: PacketConfig,
+    sequence_count: u16,
+    // Whether a sample published with another encoding than the config gives, or too
+    // large for a packet, was reported.
+    mismatch_reported: bool,
+    oversize_reported: bool,
+}
+
+impl Stream {
+    // Whether the sample is published with the encoding the ground will tag it with.
+    fn encoding_matches(&self, sample: &Encoding) -> bool {
+        match (&self.config.table, &self.config.encoding) {
+            (Some(table), _) => encoding::flatbuffer_table(sample).as_ref() == Some(table),
+            (None, Some(rendered)) => sample.to_string() == *rendered,
+            (None, None) => true,
+        }
+    }
+}
+
+struct CcsdsFramer {
+    config: Option<Config>,
+    prefix: String,
+    streams: HashMap<SubscriptionId, Stream>,
+    publisher: Option<Publisher<'static>>,
+}
+
+// When the sample was published, by its Zenoh timestamp, else now.
+fn sample_time(sample: &Sample) -> Duration {
+    sample
+        .timestamp()
+        .map_or_else(SystemTime::now, |t| t.get_time().to_system_time())
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+impl CcsdsFramer {
+    async fn on_sample(&mut self, id: SubscriptionId, sample: &Sample) -> zenoh::Result<()> {
+        let (Some(stream), Some(publisher)) = (self.streams.get_mut(&id), &self.publisher) else {
+            return Ok(());
+        };
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = &stream.config.key;
+        if !stream.mismatch_reported && !stream.encoding_matches(sample.encoding()) {
+            warn!(
+                "{} is published as {}, which the ground will not tag it as",
+                key,
+                sample.encoding()
+            );
+            stream.mismatch_reported = true;
+        }
+        let data = sample.payload().to_bytes();
+        if data.len() > MAX_DATA_LEN {
+            if !stream.oversize_reported {
+                warn!(
+                    "Dropping samples of {}: {} bytes do not fit in a packet of {}",
+                    key,
+                    data.len(),
+                    MAX_DATA_LEN
+                );
+                stream.oversize_reported = true;
+            }
+            return Ok(());
+        }
+        let mut bytes = Vec::with_capacity(
+            packet::PRIMARY_HEADER_LEN + packet::SECONDARY_HEADER_LEN + data.len(),
+        );
+        SpacePacket {
+            apid: stream.config.apid,
+            sequence_count: stream.sequence_count,
+            time: sample_time(sample),
+            data: &data,
+        }
+        .encode(&mut bytes);
+        stream.sequence_count = packet::next(stream.sequence_count);
+        publisher.put(bytes).await
+    }
+}
+
+impl Node for CcsdsFramer {
+    const NAME: &'static str = "ccsds_framer";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let config = self.config.take().unwrap_or_default();
+        let publisher = ctx
+            .session()
+            .declare_publisher(keys::ccsds_packets())
+            .encoding(Encoding::APPLICATION_OCTET_STREAM)
+            .qos(ctx.qos(), keys::ccsds_packets())
+            .await?;
+        self.publisher = Some(publisher);
+        let prefix = self.prefix.trim_end_matches('/');
+        for packet in config.packets {
+            let id = ctx.subscribe(&format!("{}/{}", prefix, packet.key)).await?;
+            self.streams.insert(
+                id,
+                Stream {
+                    config: packet,
+                    sequence_count: 0,
+                    mismatch_reported: false,
+                    oversize_reported: false,
+                },
+            );
+        }
+        info!(
+            "Framing {} keys under {} on {}",
+            self.streams.len(),
+            prefix,
+            keys::ccsds_packets()
+        );
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(id, sample) => self.on_sample(id, &sample).await,
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(publisher) = self.publisher.take() {
+            publisher.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let config = match &args.config {
+        Some(path) => match Config::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid CCSDS config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Config::default(),
+    };
+    let node = CcsdsFramer {
+        config: Some(config),
+        prefix: args.prefix,
+        streams: HashMap::new(),
+        publisher: None,
+    };
+    node_framework::run(node, &args.node).await
+}
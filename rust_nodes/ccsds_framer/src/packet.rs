@@ -0,0 +1,244 @@
+// CCSDS Space Packets (CCSDS 133.0-B-2) of telemetry: a 6-octet primary header, a
+// secondary header holding when the sample was published, and the sample's payload as the
+// user data. Packets are never segmented, so a sample must fit in one.
+//
+// The secondary header is a CCSDS Unsegmented Time Code (CCSDS 301.0-B-4) without its
+// P-field: 4 octets of whole seconds and 2 of fractions of 2^-16 s, from the Unix epoch
+// as the mission-defined epoch.
+use std::time::Duration;
+
+pub const PRIMARY_HEADER_LEN: usize = 6;
+pub const SECONDARY_HEADER_LEN: usize = 6;
+
+// Highest APID telemetry is sent on; 0x7FF is reserved for idle packets.
+pub const MAX_APID: u16 = 0x7FE;
+
+// Sequence counts run from 0 to SEQUENCE_COUNTS - 1 per APID, and start over.
+pub const SEQUENCE_COUNTS: u16 = 1 << 14;
+
+// Most payload a packet carries: its data field holds at most 65536 octets, the secondary
+// header among them.
+pub const MAX_DATA_LEN: usize = 65536 - SECONDARY_HEADER_LEN;
+
+const VERSION: u8 = 0;
+// Sequence flags of a packet holding all of its user data.
+const UNSEGMENTED: u8 = 0b11;
+const FRACTIONS_PER_SECOND: f64 = 65536.0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpacePacket<'a> {
+    pub apid: u16,
+    pub sequence_count: u16,
+    // When the sample was published, since the Unix epoch.
+    pub time: Duration,
+    pub data: &'a [u8],
+}
+
+impl<'a> SpacePacket<'a> {
+    // Appends the packet to out. The APID, sequence count, and data length are the
+    // caller's to keep in range; the time is cut to the 2^-16 s the header holds.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        debug_assert!(self.apid <= MAX_APID && self.sequence_count < SEQUENCE_COUNTS);
+        debug_assert!(self.data.len() <= MAX_DATA_LEN);
+        // Version, a telemetry packet (type 0) with a secondary header, and the APID.
+        let identification = (VERSION as u16) << 13 | 1 << 11 | self.apid;
+        let sequence = (UNSEGMENTED as u16) << 14 | self.sequence_count;
+        // The length of the data field, less one.
+        let length = (SECONDARY_HEADER_LEN + self.data.len() - 1) as u16;
+        out.extend_from_slice(&identification.to_be_bytes());
+        out.extend_from_slice(&sequence.to_be_bytes());
+        out.extend_from_slice(&length.to_be_bytes());
+        let seconds = self.time.as_secs() as u32;
+        let fraction = (self.time.subsec_nanos() as f64 * 1e-9 * FRACTIONS_PER_SECOND) as u16;
+        out.extend_from_slice(&seconds.to_be_bytes());
+        out.extend_from_slice(&fraction.to_be_bytes());
+        out.extend_from_slice(self.data);
+    }
+
+    // Parses the packet at the start of bytes, returning it and the octets it takes, so
+    // several packets sent back to back are parsed one after the other.
+    pub fn decode(bytes: &'a [u8]) -> Result<(Self, usize), String> {
+        if bytes.len() < PRIMARY_HEADER_LEN {
+            return Err(format!(
+                "{} octets are too few for a primary header",
+                bytes.len()
+            ));
+        }
+        let identification = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let sequence = u16::from_be_bytes([bytes[2], bytes[3]]);
+        let length = u16::from_be_bytes([bytes[4], bytes[5]]) as usize + 1;
+        let version = (identification >> 13) as u8;
+        if version != VERSION {
+            return Err(format!("packet version {} is not a Space Packet", version));
+        }
+        if identification & 1 << 12 != 0 {
+            return Err("a telecommand packet is not telemetry".to_string());
+        }
+        if identification & 1 << 11 == 0 {
+            return Err("packet has no secondary header".to_string());
+        }
+        if (sequence >> 14) as u8 != UNSEGMENTED {
+            return Err("segmented packets are not reassembled".to_string());
+        }
+        if length < SECONDARY_HEADER_LEN {
+            return Err(format!(
+                "data field of {} octets has no room for the secondary header",
+                length
+            ));
+        }
+        let end = PRIMARY_HEADER_LEN + length;
+        if bytes.len() < end {
+            return Err(format!(
+                "packet of {} octets is cut short at {}",
+                end,
+                bytes.len()
+            ));
+        }
+        let header = &bytes[PRIMARY_HEADER_LEN..PRIMARY_HEADER_LEN + SECONDARY_HEADER_LEN];
+        let seconds = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let fraction = u16::from_be_bytes([header[4], header[5]]);
+        let time = Duration::from_secs(seconds as u64)
+            + Duration::from_secs_f64(fraction as f64 / FRACTIONS_PER_SECOND);
+        let packet = SpacePacket {
+            apid: identification & 0x7FF,
+            sequence_count: sequence & (SEQUENCE_COUNTS - 1),
+            time,
+            data: &bytes[PRIMARY_HEADER_LEN + SECONDARY_HEADER_LEN..end],
+        };
+        Ok((packet, end))
+    }
+}
+
+// Packets missed between the one expected next and the one received, going by their
+// sequence counts: 0 if it is the one expected. A packet repeated or received out of
+// order shows as most of a lap of the counts missed.
+pub fn missed(expected: u16, received: u16) -> u16 {
+    received.wrapping_sub(expected) & (SEQUENCE_COUNTS - 1)
+}
+
+// The sequence count after count.
+pub fn next(count: u16) -> u16 {
+    (count + 1) & (SEQUENCE_COUNTS - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(packet: &SpacePacket) -> Vec<u8> {
+        let mut out = Vec::new();
+        packet.encode(&mut out);
+        out
+    }
+
+    #[test]
+    fn encodes_known_headers() {
+        let packet = SpacePacket {
+            apid: 0,
+            sequence_count: 0,
+            time: Duration::ZERO,
+            data: &[0x01],
+        };
+        assert_eq!(
+            encode(&packet),
+            [0x08, 0x00, 0xc0, 0x00, 0x00, 0x06, 0, 0, 0, 0, 0, 0, 0x01]
+        );
+        // The highest APID and sequence count fill their fields without spilling into
+        // the flags around them.
+        let packet = SpacePacket {
+            apid: MAX_APID,
+            sequence_count: SEQUENCE_COUNTS - 1,
+            time: Duration::from_millis(1_700_000_000_500),
+            data: &[0xaa, 0xbb],
+        };
+        assert_eq!(
+            encode(&packet),
+            [
+                0x0f, 0xfe, 0xff, 0xff, 0x00, 0x07, 0x65, 0x53, 0xf1, 0x00, 0x80, 0x00, 0xaa, 0xbb
+            ]
+        );
+    }
+
+    #[test]
+    fn cuts_the_time_to_the_fraction() {
+        let packet = SpacePacket {
+            apid: 1,
+            sequence_count: 1,
+            time: Duration::new(1, 999_999_999),
+            data: &[],
+        };
+        let bytes = encode(&packet);
+        assert_eq!(&bytes[6..12], [0, 0, 0, 1, 0xff, 0xff]);
+        let (decoded, _) = SpacePacket::decode(&bytes).unwrap();
+        assert!(packet.time - decoded.time < Duration::from_secs_f64(1.0 / 65536.0));
+    }
+
+    #[test]
+    fn round_trips_packets_back_to_back() {
+        let packets = [
+            SpacePacket {
+                apid: MAX_APID,
+                sequence_count: SEQUENCE_COUNTS - 1,
+                time: Duration::from_secs(u32::MAX as u64),
+                data: &[0xff; 300],
+            },
+            SpacePacket {
+                apid: 0x155,
+                sequence_count: 0x2aaa,
+                time: Duration::from_secs_f64(1_760_000_000.25),
+                data: &[0x00],
+            },
+        ];
+        let mut bytes = Vec::new();
+        for packet in &packets {
+            packet.encode(&mut bytes);
+        }
+        let (first, len) = SpacePacket::decode(&bytes).unwrap();
+        assert_eq!(first, packets[0]);
+        assert_eq!(len, PRIMARY_HEADER_LEN + SECONDARY_HEADER_LEN + 300);
+        let (second, rest) = SpacePacket::decode(&bytes[len..]).unwrap();
+        assert_eq!(second, packets[1]);
+        assert_eq!(len + rest, bytes.len());
+    }
+
+    #[test]
+    fn refuses_packets_it_cannot_take() {
+        let mut good = Vec::new();
+        SpacePacket {
+            apid: 7,
+            sequence_count: 0,
+            time: Duration::ZERO,
+            data: &[1, 2],
+        }
+        .encode(&mut good);
+        let altered = |at: usize, byte: u8| {
+            let mut bytes = good.clone();
+            bytes[at] = byte;
+            bytes
+        };
+        let cases = [
+            (good[..5].to_vec(), "too few"),
+            (altered(0, 0x28), "version"),
+            (altered(0, 0x18), "telecommand"),
+            (altered(0, 0x00), "no secondary header"),
+            (altered(2, 0x40), "segmented"),
+            (altered(5, 0x04), "no room"),
+            (good[..good.len() - 1].to_vec(), "cut short"),
+        ];
+        for (bytes, error) in cases {
+            let e = SpacePacket::decode(&bytes).unwrap_err();
+            assert!(e.contains(error), "{:02x?}: {}", bytes, e);
+        }
+    }
+
+    #[test]
+    fn wraps_the_sequence_count() {
+        assert_eq!(next(0), 1);
+        assert_eq!(next(SEQUENCE_COUNTS - 1), 0);
+        assert_eq!(missed(SEQUENCE_COUNTS - 1, 0), 1);
+        assert_eq!(missed(SEQUENCE_COUNTS - 2, 1), 3);
+        assert_eq!(missed(0, 0), 0);
+        // A packet repeated.
+        assert_eq!(missed(5, 4), SEQUENCE_COUNTS - 1);
+    }
+}
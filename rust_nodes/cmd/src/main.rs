@@ -0,0 +1,84 @@
+use clap::Parser;
+use command::{Key, Reply, Sender};
+use common::ZenohArgs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(about = "Sends a command to a node on cmd/<node>/<command> and prints its reply")]
+struct Args {
+    /// Name of the node, as announced on @/alive/<NAME>.
+    node: String,
+    /// Command to send, e.g. restart.
+    command: String,
+    /// Command arguments as NAME=VALUE.
+    #[arg(value_parser = parse_arg)]
+    args: Vec<(String, String)>,
+    /// How long each attempt waits for the node to be reachable and to reply, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    timeout_ms: u64,
+    /// Attempts before giving up. Retries reuse the sequence number, so the node carries
+    /// the command out at most once.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    attempts: u32,
+    /// File holding the secret to sign the command with, for nodes that check signatures.
+    #[arg(long, value_name = "PATH", env = "COMMAND_KEY")]
+    command_key: Option<PathBuf>,
+    #[command(flatten)]
+    zenoh: ZenohArgs,
+}
+
+fn parse_arg(arg: &str) -> Result<(String, String), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", arg))?;
+    Ok((name.to_string(), value.to_string()))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let key = match args.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let session = match args.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut sender = Sender::new(&session, &args.node)
+        .timeout(Duration::from_millis(args.timeout_ms))
+        .attempts(args.attempts)
+        .key(key);
+    let result = sender.send(&args.command, &args.args).await;
+    let status = match result {
+        Ok(Reply {
+            result: Ok(message),
+            ..
+        }) => {
+            println!("ack: {}", message);
+            ExitCode::SUCCESS
+        }
+        Ok(Reply {
+            result: Err(nack), ..
+        }) => {
+            eprintln!("nack: {}", nack);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    };
+    if let Err(e) = session.close().await {
+        eprintln!("Failed to close Zenoh session: {}", e);
+    }
+    status
+}
@@ -0,0 +1,100 @@
+use crate::{Nack, NackReason};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// How far a command's timestamp may be from the receiver's clock, covering the sender's
+// retries and clock differences between hosts. Older commands are refused as replays.
+pub const MAX_AGE: Duration = Duration::from_secs(10);
+
+// The attachment of a signed command: the sending time in microseconds since the epoch,
+// little-endian, followed by the HMAC-SHA256 of that time, the key expression, and the
+// payload.
+const TIMESTAMP_LEN: usize = 8;
+const SIGNATURE_LEN: usize = 32;
+
+// Secret shared by the senders and the nodes that check their commands.
+#[derive(Clone)]
+pub struct Key(Vec<u8>);
+
+impl Key {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Key(secret.into())
+    }
+
+    // Reads the secret from a file, ignoring surrounding whitespace.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let secret = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read command key {}: {}", path.display(), e))?;
+        let secret = secret.trim();
+        if secret.is_empty() {
+            return Err(format!("command key {} is empty", path.display()));
+        }
+        Ok(Key::new(secret))
+    }
+
+    fn mac(&self, timestamp: &[u8], key_expr: &str, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts keys of any length");
+        mac.update(timestamp);
+        mac.update(key_expr.as_bytes());
+        // Separates the key expression from the payload, so no two messages sign alike.
+        mac.update(&[0]);
+        mac.update(payload);
+        mac
+    }
+
+    // Signs a command sent now on key_expr; the result goes in the query's attachment.
+    pub fn sign(&self, key_expr: &str, payload: &[u8]) -> Vec<u8> {
+        let timestamp = now_us().to_le_bytes();
+        let signature = self
+            .mac(&timestamp, key_expr, payload)
+            .finalize()
+            .into_bytes();
+        let mut attachment = timestamp.to_vec();
+        attachment.extend_from_slice(&signature);
+        attachment
+    }
+
+    // Checks the attachment of a command received on key_expr.
+    pub fn verify(
+        &self,
+        key_expr: &str,
+        payload: &[u8],
+        attachment: Option<&[u8]>,
+    ) -> Result<(), Nack> {
+        let unauthorized = |message: &str| Err(Nack::new(NackReason::Unauthorized, message));
+        let Some(attachment) = attachment else {
+            return unauthorized("command is not signed");
+        };
+        if attachment.len() != TIMESTAMP_LEN + SIGNATURE_LEN {
+            return unauthorized("malformed signature");
+        }
+        let (timestamp, signature) = attachment.split_at(TIMESTAMP_LEN);
+        if self
+            .mac(timestamp, key_expr, payload)
+            .verify_slice(signature)
+            .is_err()
+        {
+            return unauthorized("invalid signature");
+        }
+        let mut sent = [0; TIMESTAMP_LEN];
+        sent.copy_from_slice(timestamp);
+        let age_us = now_us().abs_diff(u64::from_le_bytes(sent));
+        if age_us > MAX_AGE.as_micros() as u64 {
+            return unauthorized(&format!(
+                "stale command, signed {:.1} s off this node's clock",
+                age_us as f64 / 1e6
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn now_us() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0)
+}
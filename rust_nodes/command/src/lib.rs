@@ -0,0 +1,228 @@
+// Commands sent to nodes as queries on cmd/<node>/<command>, and the replies they answer
+// with, encoded as sensors::Command and sensors::CommandReply. Nodes receive them through
+// node_framework's command handler.
+mod auth;
+mod sender;
+mod senders;
+
+pub use auth::{Key, MAX_AGE};
+pub use sender::{SendError, Sender};
+pub use senders::Senders;
+
+use sensors_rs::sensors;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Command {
+    pub id: String,
+    pub sequence: u64,
+    pub args: Vec<(String, String)>,
+    pub sender: String,
+}
+
+impl Command {
+    pub fn new(id: impl Into<String>, sequence: u64) -> Self {
+        Command {
+            id: id.into(),
+            sequence,
+            args: Vec::new(),
+            sender: String::new(),
+        }
+    }
+
+    pub fn with_sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = sender.into();
+        self
+    }
+
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.args.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Parses a required argument, refusing the command if it is missing or malformed.
+    pub fn parse<T>(&self, name: &str) -> Result<T, Nack>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.arg(name).ok_or_else(|| {
+            Nack::new(
+                NackReason::InvalidArgs,
+                format!("missing argument {}", name),
+            )
+        })?;
+        value.parse().map_err(|e| {
+            Nack::new(
+                NackReason::InvalidArgs,
+                format!("invalid {} '{}': {}", name, value, e),
+            )
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+        let id = builder.create_string(&self.id);
+        let args: Vec<_> = self
+            .args
+            .iter()
+            .map(|(name, value)| {
+                let name = builder.create_string(name);
+                let value = builder.create_string(value);
+                sensors::CommandArg::create(
+                    &mut builder,
+                    &sensors::CommandArgArgs {
+                        name: Some(name),
+                        value: Some(value),
+                    },
+                )
+            })
+            .collect();
+        let args = builder.create_vector(&args);
+        let sender = builder.create_string(&self.sender);
+        let command = sensors::Command::create(
+            &mut builder,
+            &sensors::CommandArgs {
+                id: Some(id),
+                sequence: self.sequence,
+                args: Some(args),
+                sender: Some(sender),
+            },
+        );
+        builder.finish(command, None);
+        builder.finished_data().to_vec()
+    }
+
+    pub fn decode(payload: &[u8]) -> Result<Self, String> {
+        let command = flatbuffers::root::<sensors::Command>(payload).map_err(|e| e.to_string())?;
+        Ok(Command {
+            id: command.id().unwrap_or_default().to_string(),
+            sequence: command.sequence(),
+            args: command
+                .args()
+                .into_iter()
+                .flatten()
+                .map(|arg| {
+                    (
+                        arg.name().unwrap_or_default().to_string(),
+                        arg.value().unwrap_or_default().to_string(),
+                    )
+                })
+                .collect(),
+            sender: command.sender().unwrap_or_default().to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NackReason {
+    // No command of that name is registered by the node.
+    UnknownCommand,
+    // The payload is not a sensors::Command, or its id does not match the key.
+    Malformed,
+    InvalidArgs,
+    // The node understood the command but will not carry it out in its current state.
+    Rejected,
+    // Carrying out the command went wrong.
+    Failed,
+    // The command's signature is missing, invalid, or too old.
+    Unauthorized,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Nack {
+    pub reason: NackReason,
+    pub message: String,
+}
+
+impl Nack {
+    pub fn new(reason: NackReason, message: impl Into<String>) -> Self {
+        Nack {
+            reason,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Nack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}: {}", self.reason, self.message)
+    }
+}
+
+// A node's answer to the command with the given sequence number: an ack with a message
+// for the operator, or a nack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub sequence: u64,
+    pub result: Result<String, Nack>,
+}
+
+impl Reply {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+        let (status, reason, message) = match &self.result {
+            Ok(message) => (
+                sensors::CommandStatus::Ack,
+                sensors::NackReason::None,
+                message,
+            ),
+            Err(nack) => (
+                sensors::CommandStatus::Nack,
+                match nack.reason {
+                    NackReason::UnknownCommand => sensors::NackReason::UnknownCommand,
+                    NackReason::Malformed => sensors::NackReason::Malformed,
+                    NackReason::InvalidArgs => sensors::NackReason::InvalidArgs,
+                    NackReason::Rejected => sensors::NackReason::Rejected,
+                    NackReason::Failed => sensors::NackReason::Failed,
+                    NackReason::Unauthorized => sensors::NackReason::Unauthorized,
+                },
+                &nack.message,
+            ),
+        };
+        let message = builder.create_string(message);
+        let reply = sensors::CommandReply::create(
+            &mut builder,
+            &sensors::CommandReplyArgs {
+                sequence: self.sequence,
+                status,
+                reason,
+                message: Some(message),
+            },
+        );
+        builder.finish(reply, None);
+        builder.finished_data().to_vec()
+    }
+
+    pub fn decode(payload: &[u8]) -> Result<Self, String> {
+        let reply =
+            flatbuffers::root::<sensors::CommandReply>(payload).map_err(|e| e.to_string())?;
+        let message = reply.message().unwrap_or_default().to_string();
+        let result = match reply.status() {
+            sensors::CommandStatus::Ack => Ok(message),
+            _ => Err(Nack::new(
+                match reply.reason() {
+                    sensors::NackReason::UnknownCommand => NackReason::UnknownCommand,
+                    sensors::NackReason::Malformed => NackReason::Malformed,
+                    sensors::NackReason::InvalidArgs => NackReason::InvalidArgs,
+                    sensors::NackReason::Rejected => NackReason::Rejected,
+                    sensors::NackReason::Unauthorized => NackReason::Unauthorized,
+                    _ => NackReason::Failed,
+                },
+                message,
+            )),
+        };
+        Ok(Reply {
+            sequence: reply.sequence(),
+            result,
+        })
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{Command, Key, Reply};
+use common::encoding;
+use keyspace::keys;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::time::timeout;
+use zenoh::Session;
+use zenoh::sample::Locality;
+
+#[derive(Debug, Error)]
+pub enum SendError {
+    // No node answers on the command's key, so the command was never delivered.
+    #[error("nothing answers on {0}")]
+    Unreachable(String),
+    // Every attempt went unanswered. The command may or may not have been carried out.
+    #[error("no reply on {key} after {attempts} attempts")]
+    NoReply { key: String, attempts: u32 },
+    // The node answered with something other than a sensors::CommandReply.
+    #[error("malformed reply: {0}")]
+    Malformed(String),
+    #[error(transparent)]
+    Zenoh(#[from] zenoh::Error),
+}
+
+// Sends commands to one node and waits for their replies, retrying unanswered ones. Every
+// command gets the next sequence number and keeps it across its retries, so a node that
+// got an earlier attempt answers with that attempt's outcome instead of carrying the
+// command out again.
+pub struct Sender {
+    session: Session,
+    node: String,
+    name: String,
+    next_sequence: u64,
+    timeout: Duration,
+    attempts: u32,
+    key: Option<Key>,
+}
+
+impl Sender {
+    // Sends as the session's id, waiting 1 s for each of at most 3 attempts.
+    pub fn new(session: &Session, node: &str) -> Self {
+        Sender {
+            session: session.clone(),
+            node: node.to_string(),
+            name: session.zid().to_string(),
+            // Counting from the current time keeps a restarted sender with a fixed name
+            // from reusing sequence numbers the node still remembers.
+            next_sequence: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_micros() as u64)
+                .unwrap_or(0),
+            timeout: Duration::from_secs(1),
+            attempts: 3,
+            key: None,
+        }
+    }
+
+    // Name the node tells senders apart by when detecting duplicates.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    // How long each attempt waits for the node to be reachable and to reply.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // Signs every command with the key, for nodes that check signatures.
+    pub fn key(mut self, key: Option<Key>) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    pub async fn send(
+        &mut self,
+        command: &str,
+        args: &[(String, String)],
+    ) -> Result<Reply, SendError> {
+        let mut command = Command::new(command, self.next_sequence).with_sender(&self.name);
+        command.args = args.to_vec();
+        self.next_sequence += 1;
+
+        let key = keys::command(&self.node, &command.id);
+        let querier = self
+            .session
+            .declare_querier(key.clone())
+            .allowed_destination(Locality::Any)
+            .timeout(self.timeout)
+            .await?;
+        let listener = querier.matching_listener().await?;
+        let payload = command.encode();
+        let mut delivered = false;
+        for _ in 0..self.attempts {
+            // A fresh session has not discovered the node yet, and a node being
+            // restarted is gone for a while; a query sent meanwhile reaches nobody.
+            if !querier.matching_status().await?.matching() {
+                let matched = timeout(self.timeout, async {
+                    while let Ok(status) = listener.recv_async().await {
+                        if status.matching() {
+                            return true;
+                        }
+                    }
+                    false
+                })
+                .await;
+                if matched != Ok(true) {
+                    continue;
+                }
+            }
+            delivered = true;
+
+            // Signed per attempt, so retries stay within the node's MAX_AGE.
+            let attachment = self.key.as_ref().map(|k| k.sign(&key, &payload));
+            let replies = querier
+                .get()
+                .payload(payload.clone())
+                .encoding(encoding::flatbuffer("sensors.Command"))
+                .attachment(attachment)
+                .await?;
+            while let Ok(reply) = replies.recv_async().await {
+                let sample = match reply.into_result() {
+                    Ok(sample) => sample,
+                    Err(e) => {
+                        let message = e.payload().to_bytes();
+                        return Err(SendError::Malformed(
+                            String::from_utf8_lossy(&message).into_owned(),
+                        ));
+                    }
+                };
+                let reply =
+                    Reply::decode(&sample.payload().to_bytes()).map_err(SendError::Malformed)?;
+                if reply.sequence == command.sequence {
+                    return Ok(reply);
+                }
+            }
+        }
+        if delivered {
+            Err(SendError::NoReply {
+                key,
+                attempts: self.attempts,
+            })
+        } else {
+            Err(SendError::Unreachable(key))
+        }
+    }
+}
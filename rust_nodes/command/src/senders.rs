@@ -0,0 +1,50 @@
+use crate::{Key, Reply, SendError, Sender};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use zenoh::Session;
+
+// Commands to any node, through a Sender per node made when the node is first commanded.
+// Each node sees a single sender counting up its sequence numbers, and is sent one command
+// at a time, while commands to different nodes go out together.
+pub struct Senders {
+    session: Session,
+    timeout: Duration,
+    key: Option<Key>,
+    senders: Mutex<HashMap<String, Arc<tokio::sync::Mutex<Sender>>>>,
+}
+
+impl Senders {
+    // Senders waiting timeout for each attempt and signing with the key, if given.
+    pub fn new(session: &Session, timeout: Duration, key: Option<Key>) -> Self {
+        Senders {
+            session: session.clone(),
+            timeout,
+            key,
+            senders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn send(
+        &self,
+        node: &str,
+        command: &str,
+        args: &[(String, String)],
+    ) -> Result<Reply, SendError> {
+        let sender = self
+            .senders
+            .lock()
+            .unwrap()
+            .entry(node.to_string())
+            .or_insert_with(|| {
+                Arc::new(tokio::sync::Mutex::new(
+                    Sender::new(&self.session, node)
+                        .timeout(self.timeout)
+                        .key(self.key.clone()),
+                ))
+            })
+            .clone();
+        let mut sender = sender.lock().await;
+        sender.send(command, args).await
+    }
+}
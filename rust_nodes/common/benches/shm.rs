@@ -0,0 +1,124 @@
+use common::shm::ShmPool;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::net::TcpListener;
+use tokio::runtime::Runtime;
+use zenoh::bytes::ZBytes;
+use zenoh::handlers::FifoChannelHandler;
+use zenoh::pubsub::{Publisher, Subscriber};
+use zenoh::sample::Sample;
+
+const KEY: &str = "bench/shm";
+
+// From a few sensor readings to a camera frame.
+const SIZES: [usize; 5] = [256, 1024, 4096, 65536, 1 << 20];
+
+// Room for a few of the largest payloads in flight.
+const POOL_SIZE: usize = 8 << 20;
+
+// A session on loopback only: no scouting, so nothing else on the network answers.
+async fn open(listen: &[&str], connect: &[&str]) -> zenoh::Session {
+    let mut config = zenoh::Config::default();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    config
+        .insert_json5("listen/endpoints", &serde_json::to_string(listen).unwrap())
+        .unwrap();
+    config
+        .insert_json5(
+            "connect/endpoints",
+            &serde_json::to_string(connect).unwrap(),
+        )
+        .unwrap();
+    config
+        .insert_json5("transport/shared_memory/mode", "\"init\"")
+        .unwrap();
+    zenoh::open(config).await.unwrap()
+}
+
+// A publishing and a subscribing session on one host, as co-located nodes are, connected
+// over TCP.
+async fn sessions() -> (
+    zenoh::Session,
+    zenoh::Session,
+    Publisher<'static>,
+    Subscriber<FifoChannelHandler<Sample>>,
+) {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let endpoint = format!("tcp/127.0.0.1:{}", port);
+    let publishing = open(&[&endpoint], &[]).await;
+    let subscribing = open(&[], &[&endpoint]).await;
+    let subscriber = subscribing.declare_subscriber(KEY).await.unwrap();
+    let publisher = publishing.declare_publisher(KEY).await.unwrap();
+    // Samples put before the sessions have exchanged their declarations go nowhere.
+    loop {
+        publisher.put(vec![0u8]).await.unwrap();
+        if let Ok(Some(_)) = subscriber.recv_timeout(std::time::Duration::from_millis(100)) {
+            break;
+        }
+    }
+    while let Ok(Some(_)) = subscriber.try_recv() {}
+    (publishing, subscribing, publisher, subscriber)
+}
+
+// Publishes one payload and reads it on the subscriber, end to end.
+async fn round(
+    publisher: &Publisher<'static>,
+    subscriber: &Subscriber<FifoChannelHandler<Sample>>,
+    payload: ZBytes,
+) -> Sample {
+    publisher.put(payload).await.unwrap();
+    let sample = subscriber.recv_async().await.unwrap();
+    black_box(sample.payload().to_bytes().len());
+    sample
+}
+
+// One sample of every size published as bytes, serialized through the transport, and
+// from a shared-memory buffer, of which the subscriber only receives a reference. Both
+// copy the payload once, as a node encoding into its own buffer does.
+fn shm(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (publishing, subscribing, publisher, subscriber) = runtime.block_on(sessions());
+    let pool = ShmPool::new(POOL_SIZE).unwrap();
+
+    let probe = pool.alloc(&[0u8; 64]).unwrap();
+    let sample = runtime.block_on(round(&publisher, &subscriber, probe));
+    assert!(
+        sample.payload().as_shm().is_some(),
+        "the subscriber received a copy rather than the shared-memory buffer"
+    );
+
+    for size in SIZES {
+        let payload = vec![0x5au8; size];
+        let mut group = c.benchmark_group(format!("publish_{}", size));
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(BenchmarkId::from_parameter("network"), |b| {
+            b.iter(|| {
+                let bytes = ZBytes::from(payload.clone());
+                runtime.block_on(round(&publisher, &subscriber, bytes))
+            })
+        });
+        group.bench_function(BenchmarkId::from_parameter("shm"), |b| {
+            b.iter(|| {
+                let bytes = pool.alloc(&payload).unwrap();
+                runtime.block_on(round(&publisher, &subscriber, bytes))
+            })
+        });
+        group.finish();
+    }
+
+    runtime.block_on(async {
+        publisher.undeclare().await.unwrap();
+        subscriber.undeclare().await.unwrap();
+        subscribing.close().await.unwrap();
+        publishing.close().await.unwrap();
+    });
+}
+
+criterion_group!(benches, shm);
+criterion_main!(benches);
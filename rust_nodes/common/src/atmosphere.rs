@@ -0,0 +1,51 @@
+// The ISA standard atmosphere up to 20 km: a troposphere cooling at a constant lapse rate
+// up to the tropopause at 11 km, and an isothermal layer above. Altitudes are geopotential
+// meters above mean sea level, pressures pascals, and temperatures kelvin.
+
+pub const SEA_LEVEL_PRESSURE: f64 = 101_325.0;
+pub const SEA_LEVEL_TEMPERATURE: f64 = 288.15;
+const LAPSE_RATE: f64 = 0.0065;
+const TROPOPAUSE: f64 = 11_000.0;
+const GRAVITY: f64 = 9.80665;
+// Specific gas constant of dry air, J/(kg K).
+const GAS_CONSTANT: f64 = 287.053;
+
+// Exponent of the troposphere's pressure-temperature relation.
+const EXPONENT: f64 = GRAVITY / (GAS_CONSTANT * LAPSE_RATE);
+const TROPOPAUSE_TEMPERATURE: f64 = SEA_LEVEL_TEMPERATURE - LAPSE_RATE * TROPOPAUSE;
+
+pub fn temperature(altitude: f64) -> f64 {
+    SEA_LEVEL_TEMPERATURE - LAPSE_RATE * altitude.min(TROPOPAUSE)
+}
+
+pub fn pressure(altitude: f64) -> f64 {
+    let troposphere =
+        |h: f64| SEA_LEVEL_PRESSURE * (1.0 - LAPSE_RATE * h / SEA_LEVEL_TEMPERATURE).powf(EXPONENT);
+    if altitude <= TROPOPAUSE {
+        return troposphere(altitude);
+    }
+    let above = altitude - TROPOPAUSE;
+    troposphere(TROPOPAUSE) * (-GRAVITY * above / (GAS_CONSTANT * TROPOPAUSE_TEMPERATURE)).exp()
+}
+
+// Altitude at which the standard atmosphere has the given pressure, the inverse of
+// pressure(). NaN for pressures that are not positive.
+pub fn altitude(pressure: f64) -> f64 {
+    if pressure.is_nan() || pressure <= 0.0 {
+        return f64::NAN;
+    }
+    let tropopause_pressure = self::pressure(TROPOPAUSE);
+    if pressure >= tropopause_pressure {
+        let ratio = (pressure / SEA_LEVEL_PRESSURE).powf(1.0 / EXPONENT);
+        return SEA_LEVEL_TEMPERATURE * (1.0 - ratio) / LAPSE_RATE;
+    }
+    TROPOPAUSE
+        - GAS_CONSTANT * TROPOPAUSE_TEMPERATURE / GRAVITY * (pressure / tropopause_pressure).ln()
+}
+
+// Height above the point where the pressure is reference, e.g. the pad's pressure measured
+// before launch. Referencing the ground takes out the day's deviation of the
+// sea-level pressure from the standard, and a barometer's offset, to first order.
+pub fn height_above(pressure: f64, reference: f64) -> f64 {
+    altitude(pressure) - altitude(reference)
+}
@@ -0,0 +1,13 @@
+// The rotation of an attitude given as roll, pitch and yaw in radians, the yaw applied
+// first, as a unit quaternion [x, y, z, w], the form visualization tools take it in.
+pub fn quaternion(roll: f64, pitch: f64, yaw: f64) -> [f64; 4] {
+    let (sr, cr) = (roll / 2.0).sin_cos();
+    let (sp, cp) = (pitch / 2.0).sin_cos();
+    let (sy, cy) = (yaw / 2.0).sin_cos();
+    [
+        sr * cp * cy - cr * sp * sy,
+        cr * sp * cy + sr * cp * sy,
+        cr * cp * sy - sr * sp * cy,
+        cr * cp * cy + sr * sp * sy,
+    ]
+}
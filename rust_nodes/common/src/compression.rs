@@ -0,0 +1,143 @@
+use std::io::Read;
+use std::str::FromStr;
+use zenoh::bytes::Encoding;
+use zenoh::key_expr::{KeyExpr, OwnedKeyExpr};
+use zenoh::sample::{Sample, SampleBuilder, SampleBuilderPut};
+
+// Encoding parameter naming the codec of a compressed payload, appended to the encoding it
+// had before, e.g. "application/x-flatbuffers;schema=sensors.IMU;version=2;compression=zstd",
+// so subscribers know to decompress it and generic tools still find the schema.
+const PARAMETER: &str = ";compression=";
+
+// Payloads shorter than this go uncompressed: the codecs' framing would eat what they save.
+pub const MIN_PAYLOAD: usize = 128;
+
+// Most a payload may decompress to, so a corrupted or hostile sample cannot exhaust memory.
+const MAX_DECOMPRESSED: usize = 64 << 20;
+
+// Zstandard's default level, which compresses telemetry well at a few hundred MB/s.
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Codec {
+    // Compresses best, for bulk telemetry and log transfer on a narrow link.
+    Zstd,
+    // Compresses less but several times faster, for high rates.
+    Lz4,
+}
+
+impl Codec {
+    pub fn name(self) -> &'static str {
+        match self {
+            Codec::Zstd => "zstd",
+            Codec::Lz4 => "lz4",
+        }
+    }
+
+    fn named(name: &str) -> Option<Self> {
+        match name {
+            "zstd" => Some(Codec::Zstd),
+            "lz4" => Some(Codec::Lz4),
+            _ => None,
+        }
+    }
+}
+
+// A --compress rule: payloads published on the keys key_expr matches are compressed with
+// codec.
+#[derive(Clone, Debug)]
+pub struct CompressionRule {
+    pub key_expr: OwnedKeyExpr,
+    pub codec: Codec,
+}
+
+impl FromStr for CompressionRule {
+    type Err = String;
+
+    // Parses KEY_EXPR=CODEC, e.g. downlink/**=zstd.
+    fn from_str(rule: &str) -> Result<Self, String> {
+        let (key_expr, codec) = rule
+            .rsplit_once('=')
+            .ok_or_else(|| format!("expected KEY_EXPR=CODEC, got '{}'", rule))?;
+        let key_expr = OwnedKeyExpr::try_from(key_expr.to_string())
+            .map_err(|e| format!("{}: {}", key_expr, e))?;
+        let codec = Codec::named(codec)
+            .ok_or_else(|| format!("unknown codec '{}', expected zstd or lz4", codec))?;
+        Ok(CompressionRule { key_expr, codec })
+    }
+}
+
+// The codec of the first rule matching key, if any.
+pub fn codec(rules: &[CompressionRule], key: &KeyExpr<'_>) -> Option<Codec> {
+    rules
+        .iter()
+        .find(|rule| rule.key_expr.includes(key))
+        .map(|rule| rule.codec)
+}
+
+// The payload compressed with codec and its encoding tagged with the codec, or None if it
+// is too short to gain from it or does not get shorter.
+pub fn compress(codec: Codec, payload: &[u8], encoding: &Encoding) -> Option<(Vec<u8>, Encoding)> {
+    if payload.len() < MIN_PAYLOAD {
+        return None;
+    }
+    let compressed = match codec {
+        Codec::Zstd => zstd::bulk::compress(payload, ZSTD_LEVEL).ok()?,
+        Codec::Lz4 => lz4_flex::compress_prepend_size(payload),
+    };
+    if compressed.len() >= payload.len() {
+        return None;
+    }
+    let encoding = Encoding::from(format!("{}{}{}", encoding, PARAMETER, codec.name()));
+    Some((compressed, encoding))
+}
+
+// The sample with its payload decompressed and its encoding as it was before compression,
+// or the sample as it is if its encoding names no codec.
+pub fn decompress(sample: Sample) -> Result<Sample, String> {
+    let rendered = sample.encoding().to_string();
+    let Some((original, name)) = rendered.rsplit_once(PARAMETER) else {
+        return Ok(sample);
+    };
+    let codec =
+        Codec::named(name).ok_or_else(|| format!("compressed with unknown codec '{}'", name))?;
+    let compressed = sample.payload().to_bytes();
+    let payload = match codec {
+        Codec::Zstd => {
+            let mut payload = Vec::new();
+            zstd::stream::read::Decoder::new(&compressed[..])
+                .and_then(|decoder| {
+                    decoder
+                        .take(MAX_DECOMPRESSED as u64 + 1)
+                        .read_to_end(&mut payload)
+                })
+                .map_err(|e| format!("cannot decompress zstd payload: {}", e))?;
+            payload
+        }
+        Codec::Lz4 => {
+            let size = compressed
+                .get(..4)
+                .map(|size| u32::from_le_bytes(size.try_into().unwrap()) as usize)
+                .ok_or("lz4 payload is too short")?;
+            if size > MAX_DECOMPRESSED {
+                return Err(format!("lz4 payload decompresses to {} bytes", size));
+            }
+            lz4_flex::decompress_size_prepended(&compressed)
+                .map_err(|e| format!("cannot decompress lz4 payload: {}", e))?
+        }
+    };
+    // Lets go of the sample's payload, so the sample can be rebuilt.
+    drop(compressed);
+    if payload.len() > MAX_DECOMPRESSED {
+        return Err(format!(
+            "payload decompresses to more than {} bytes",
+            MAX_DECOMPRESSED
+        ));
+    }
+    let encoding = Encoding::from(original.to_string());
+    let sample = SampleBuilder::<SampleBuilderPut>::try_from(sample)
+        .map_err(|e| e.to_string())?
+        .payload(payload)
+        .encoding(encoding);
+    Ok(sample.into())
+}
@@ -0,0 +1,31 @@
+// Node configs, read from a file whose extension says what it is written in.
+use serde::de::DeserializeOwned;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// Why a config file could not be loaded.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("cannot read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("{}: expected a .toml or .json file", .0.display())]
+    Format(PathBuf),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+}
+
+// Loads a config from a TOML file, or from a JSON one if its extension is .json.
+pub fn load_toml<T: DeserializeOwned>(path: &Path) -> Result<T, ConfigError> {
+    let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => Ok(serde_json::from_str(&text)?),
+        Some("toml") => Ok(toml::from_str(&text)?),
+        _ => Err(ConfigError::Format(path.to_path_buf())),
+    }
+}
@@ -0,0 +1,37 @@
+// CRC-32C (Castagnoli), the checksum of iSCSI and ext4, here guarding payloads end to end
+// across links whose transport checksums are not enough, such as RF bridges.
+const POLYNOMIAL: u32 = 0x82f6_3b78;
+
+const TABLE: [u32; 256] = table();
+
+const fn table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+pub fn checksum(data: &[u8]) -> u32 {
+    update(0, data)
+}
+
+// Extends the checksum of some data with the bytes that follow it, for data checksummed
+// in pieces as it is written; update(checksum(a), b) is the checksum of a then b.
+pub fn update(crc: u32, data: &[u8]) -> u32 {
+    !data.iter().fold(!crc, |crc, &byte| {
+        TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
@@ -0,0 +1,58 @@
+use zenoh::bytes::Encoding;
+
+// Version of the sensors schema this build publishes, carried in every flatbuffer()
+// encoding. It is bumped only for changes FlatBuffers' own evolution cannot absorb: adding
+// a field at the end of a table, or a new table, needs no bump. Nodes read every version
+// from OLDEST_SCHEMA_VERSION up, so during a rolling upgrade subscribers are upgraded
+// before the publishers they read.
+//   1: samples carry no version, and devices/temp a raw little-endian f32.
+//   2: devices/temp carries a sensors.Temperature.
+pub const SCHEMA_VERSION: u32 = 2;
+pub const OLDEST_SCHEMA_VERSION: u32 = 1;
+
+// Mime type of FlatBuffers payloads; the table and schema version follow as parameters.
+pub const FLATBUFFERS: &str = "application/x-flatbuffers";
+const TABLE: &str = "schema=";
+const VERSION: &str = "version=";
+
+// FlatBuffers payloads are tagged as application/x-flatbuffers with the fully qualified
+// table name, e.g. "application/x-flatbuffers;schema=sensors.IMU;version=2", so generic
+// tools can pick a decoder without knowing the key.
+pub fn flatbuffer(table: &str) -> Encoding {
+    Encoding::from(format!(
+        "{};{}{};{}{}",
+        FLATBUFFERS, TABLE, table, VERSION, SCHEMA_VERSION
+    ))
+}
+
+// Table name of a payload tagged with flatbuffer(), if it is one. Payloads of earlier
+// nodes and logs, tagged as octet streams whose Zenoh schema is the table name, e.g.
+// "application/octet-stream;sensors.IMU", are recognized too.
+pub fn flatbuffer_table(encoding: &Encoding) -> Option<String> {
+    let rendered = encoding.to_string();
+    let mut parts = rendered.split(';');
+    let table = match parts.next()? {
+        FLATBUFFERS => parts.find_map(|part| part.strip_prefix(TABLE))?,
+        mime if mime == Encoding::APPLICATION_OCTET_STREAM.to_string() => parts.next()?,
+        _ => return None,
+    };
+    (!table.is_empty()).then(|| table.to_string())
+}
+
+// Schema version a payload was published with: 1 for payloads that carry none, and None
+// for a version that is not a number.
+pub fn schema_version(encoding: &Encoding) -> Option<u32> {
+    let rendered = encoding.to_string();
+    match rendered
+        .split(';')
+        .find_map(|part| part.strip_prefix(VERSION))
+    {
+        Some(version) => version.parse().ok(),
+        None => Some(1),
+    }
+}
+
+// Whether this build can decode payloads of a schema version.
+pub fn readable(version: u32) -> bool {
+    (OLDEST_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&version)
+}
@@ -0,0 +1,19 @@
+// Helpers shared by every node in the workspace.
+pub mod atmosphere;
+pub mod attitude;
+pub mod compression;
+pub mod config;
+pub mod crc32c;
+pub mod encoding;
+pub mod matching;
+pub mod publication_cache;
+pub mod qos;
+pub mod sequence;
+#[cfg(feature = "shm")]
+pub mod shm;
+pub mod shutdown;
+pub mod trace;
+pub mod zenoh_config;
+
+pub use shutdown::shutdown_signal;
+pub use zenoh_config::{ZenohArgs, ZenohConfigError};
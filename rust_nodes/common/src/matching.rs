@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use zenoh::matching::MatchingStatus;
+use zenoh::pubsub::Publisher;
+use zenoh_ext::AdvancedPublisher;
+
+// Whether a publisher has subscribers to publish to, so a node can stop encoding and
+// sending samples nobody receives and start again as soon as a subscriber appears. A
+// listener on the publisher keeps it up to date until the publisher is undeclared.
+#[derive(Clone)]
+pub struct Matching {
+    matching: Arc<AtomicBool>,
+}
+
+impl Matching {
+    // For a publisher that publishes regardless of subscribers.
+    pub fn always() -> Self {
+        Matching {
+            matching: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    pub async fn watch(publisher: &Publisher<'_>) -> zenoh::Result<Self> {
+        let matching = Self::unknown();
+        publisher
+            .matching_listener()
+            .callback(matching.listener())
+            .background()
+            .await?;
+        // Read after the listener is up, so no change falls between the two.
+        matching.set(publisher.matching_status().await?.matching());
+        Ok(matching)
+    }
+
+    // Like watch, for a publisher declared through sequence::cached.
+    pub async fn watch_advanced(publisher: &AdvancedPublisher<'_>) -> zenoh::Result<Self> {
+        let matching = Self::unknown();
+        publisher
+            .matching_listener()
+            .callback(matching.listener())
+            .background()
+            .await?;
+        matching.set(publisher.matching_status().await?.matching());
+        Ok(matching)
+    }
+
+    fn unknown() -> Self {
+        Matching {
+            matching: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn listener(&self) -> impl Fn(MatchingStatus) + Send + Sync + 'static {
+        let matching = self.clone();
+        move |status| matching.set(status.matching())
+    }
+
+    fn set(&self, matching: bool) {
+        self.matching.store(matching, Ordering::Relaxed);
+    }
+
+    pub fn matching(&self) -> bool {
+        self.matching.load(Ordering::Relaxed)
+    }
+}
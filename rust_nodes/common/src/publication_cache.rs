@@ -0,0 +1,34 @@
+use zenoh::Session;
+use zenoh_ext::SessionExt;
+
+// Keeps the last samples published from a session on every key matching a key expression
+// and answers plain queries on those keys with them, attachments included, so a node that
+// starts late reads the last values with a get() instead of waiting for the next sample.
+// The publishers of sequence::cached keep samples only for advanced subscribers, which a
+// get() does not reach; zenoh-ext deprecates this cache in their favour, hence the allows.
+pub struct PublicationCache {
+    #[allow(deprecated)]
+    cache: zenoh_ext::PublicationCache,
+}
+
+impl PublicationCache {
+    // Caches up to history samples per key. The session must have been opened with
+    // ZenohArgs::timestamping set, as replies are ordered by their timestamps.
+    #[allow(deprecated)]
+    pub async fn declare(
+        session: &Session,
+        key_expr: &str,
+        history: usize,
+    ) -> zenoh::Result<PublicationCache> {
+        let cache = session
+            .declare_publication_cache(key_expr.to_string())
+            .history(history)
+            .await?;
+        Ok(PublicationCache { cache })
+    }
+
+    #[allow(deprecated)]
+    pub async fn undeclare(self) -> zenoh::Result<()> {
+        self.cache.undeclare().await
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Deserialize;
+use zenoh::key_expr::KeyExpr;
+use zenoh::pubsub::PublisherBuilder;
+use zenoh::qos::{CongestionControl, Priority, Reliability};
+
+// Ordered from the most urgent.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityConfig {
+    RealTime,
+    InteractiveHigh,
+    InteractiveLow,
+    DataHigh,
+    Data,
+    DataLow,
+    Background,
+}
+
+impl From<PriorityConfig> for Priority {
+    fn from(priority: PriorityConfig) -> Self {
+        match priority {
+            PriorityConfig::RealTime => Priority::RealTime,
+            PriorityConfig::InteractiveHigh => Priority::InteractiveHigh,
+            PriorityConfig::InteractiveLow => Priority::InteractiveLow,
+            PriorityConfig::DataHigh => Priority::DataHigh,
+            PriorityConfig::Data => Priority::Data,
+            PriorityConfig::DataLow => Priority::DataLow,
+            PriorityConfig::Background => Priority::Background,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CongestionConfig {
+    // Wait for room in the transmission queue, so no sample is lost.
+    Block,
+    // Drop the sample when the queue is full, so the publisher never stalls.
+    Drop,
+}
+
+impl From<CongestionConfig> for CongestionControl {
+    fn from(congestion: CongestionConfig) -> Self {
+        match congestion {
+            CongestionConfig::Block => CongestionControl::Block,
+            CongestionConfig::Drop => CongestionControl::Drop,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReliabilityConfig {
+    Reliable,
+    BestEffort,
+}
+
+impl From<ReliabilityConfig> for Reliability {
+    fn from(reliability: ReliabilityConfig) -> Self {
+        match reliability {
+            ReliabilityConfig::Reliable => Reliability::Reliable,
+            ReliabilityConfig::BestEffort => Reliability::BestEffort,
+        }
+    }
+}
+
+// QoS of the publishers on the keys a key expression matches. Settings left out are taken
+// from the next class that matches, and those no class sets stay as the node declares
+// them.
+#[derive(Clone, Debug, Deserialize)]
+pub struct QosClass {
+    pub key: String,
+    pub priority: Option<PriorityConfig>,
+    pub congestion: Option<CongestionConfig>,
+    pub reliability: Option<ReliabilityConfig>,
+    // Send samples right away rather than batched with others.
+    pub express: Option<bool>,
+}
+
+// Classes every node falls back on after its configured ones: state and commands preempt
+// sensor data, which preempts events, and heartbeats and statistics go last, so bulk
+// telemetry never delays what the vehicle is flown on. What the downsampler republishes
+// for the downlink goes last too. Congestion and reliability are left to the nodes, which
+// choose them by what a lost sample costs.
+const DEFAULT_CLASSES: [(&str, PriorityConfig, bool); 9] = [
+    ("state/**", PriorityConfig::RealTime, true),
+    ("cmd/**", PriorityConfig::RealTime, true),
+    ("fdir/**", PriorityConfig::InteractiveHigh, true),
+    ("devices/**", PriorityConfig::DataHigh, false),
+    ("events/**", PriorityConfig::InteractiveLow, false),
+    ("alarms/**", PriorityConfig::InteractiveLow, false),
+    ("heartbeat/**", PriorityConfig::DataLow, false),
+    ("stats/**", PriorityConfig::Background, false),
+    ("downlink/**", PriorityConfig::Background, false),
+];
+
+// The QoS section of a node config: [[qos]] entries, tried in order before the defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct QosConfig {
+    classes: Vec<QosClass>,
+}
+
+impl QosConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for class in &self.classes {
+            KeyExpr::new(class.key.as_str()).map_err(|e| format!("qos {}: {}", class.key, e))?;
+        }
+        Ok(())
+    }
+
+    // Every class matching key, configured ones first.
+    fn matching(&self, key: &str) -> Vec<QosClass> {
+        let Ok(key) = KeyExpr::new(key) else {
+            return Vec::new();
+        };
+        let defaults = DEFAULT_CLASSES
+            .iter()
+            .map(|&(class, priority, express)| QosClass {
+                key: class.to_string(),
+                priority: Some(priority),
+                congestion: None,
+                reliability: None,
+                express: Some(express),
+            });
+        self.classes
+            .iter()
+            .cloned()
+            .chain(defaults)
+            .filter(|class| {
+                KeyExpr::new(class.key.as_str()).is_ok_and(|class| class.includes(&key))
+            })
+            .collect()
+    }
+
+    // The QoS key is published with, as the first matching class to set each part of it.
+    pub fn resolve(&self, key: &str) -> QosClass {
+        let mut resolved = QosClass {
+            key: key.to_string(),
+            priority: None,
+            congestion: None,
+            reliability: None,
+            express: None,
+        };
+        for class in self.matching(key) {
+            resolved.priority = resolved.priority.or(class.priority);
+            resolved.congestion = resolved.congestion.or(class.congestion);
+            resolved.reliability = resolved.reliability.or(class.reliability);
+            resolved.express = resolved.express.or(class.express);
+        }
+        resolved
+    }
+}
+
+// Declares publishers with the QoS a node config gives their key.
+pub trait PublisherBuilderQosExt {
+    // Applies the QoS of key, the key the publisher is declared on, over what the node
+    // chose so far.
+    fn qos(self, qos: &QosConfig, key: &str) -> Self;
+}
+
+impl PublisherBuilderQosExt for PublisherBuilder<'_, '_> {
+    fn qos(mut self, qos: &QosConfig, key: &str) -> Self {
+        let qos = qos.resolve(key);
+        if let Some(priority) = qos.priority {
+            self = self.priority(priority.into());
+        }
+        if let Some(congestion) = qos.congestion {
+            self = self.congestion_control(congestion.into());
+        }
+        if let Some(reliability) = qos.reliability {
+            self = self.reliability(reliability.into());
+        }
+        if let Some(express) = qos.express {
+            self = self.express(express);
+        }
+        self
+    }
+}
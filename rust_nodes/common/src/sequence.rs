@@ -0,0 +1,302 @@
+use crate::compression::{self, Codec};
+use crate::crc32c;
+use crate::trace::{self, TraceContext};
+use std::collections::BTreeMap;
+#[cfg(feature = "shm")]
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use zenoh::bytes::{Encoding, ZBytes};
+use zenoh::key_expr::KeyExpr;
+use zenoh::pubsub::{Publisher, PublisherBuilder};
+use zenoh::sample::Sample;
+use zenoh_ext::{
+    AdvancedPublisher, AdvancedPublisherBuilder, AdvancedPublisherBuilderExt, CacheConfig,
+    MissDetectionConfig,
+};
+
+const NUMBER_LEN: usize = 8;
+const CRC_LEN: usize = 4;
+
+// The attachment of a sequenced sample, little-endian: the u64 sequence number, counting
+// from 0 by one per sample on its publisher, then the publishing node's name as UTF-8.
+// When the publisher checks integrity or the sample was published in a traced span, a NUL
+// follows, then the u32 CRC32C of the payload if checked, then the TraceContext if traced,
+// told apart by their length; node names come from the command line, so they cannot
+// contain a NUL themselves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sequence {
+    pub number: u64,
+    pub node: String,
+    pub crc32c: Option<u32>,
+    pub trace: Option<TraceContext>,
+}
+
+impl Sequence {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut attachment = self.number.to_le_bytes().to_vec();
+        attachment.extend_from_slice(self.node.as_bytes());
+        if self.crc32c.is_some() || self.trace.is_some() {
+            attachment.push(0);
+        }
+        if let Some(crc) = self.crc32c {
+            attachment.extend_from_slice(&crc.to_le_bytes());
+        }
+        if let Some(trace) = &self.trace {
+            trace.encode_into(&mut attachment);
+        }
+        attachment
+    }
+
+    pub fn decode(attachment: &[u8]) -> Option<Self> {
+        if attachment.len() < NUMBER_LEN {
+            return None;
+        }
+        let (number, rest) = attachment.split_at(NUMBER_LEN);
+        let (node, tail) = match rest.iter().position(|&byte| byte == 0) {
+            Some(end) => (&rest[..end], &rest[end + 1..]),
+            None => (rest, &[][..]),
+        };
+        let (crc32c, trace) = match tail.len() {
+            0 => (None, None),
+            CRC_LEN => (Some(tail), None),
+            TraceContext::LEN => (None, Some(tail)),
+            len if len == CRC_LEN + TraceContext::LEN => {
+                let (crc, trace) = tail.split_at(CRC_LEN);
+                (Some(crc), Some(trace))
+            }
+            _ => return None,
+        };
+        Some(Sequence {
+            number: u64::from_le_bytes(number.try_into().unwrap()),
+            node: String::from_utf8(node.to_vec()).ok()?,
+            crc32c: crc32c.map(|crc| u32::from_le_bytes(crc.try_into().unwrap())),
+            trace: trace.and_then(TraceContext::decode),
+        })
+    }
+
+    // The sequence of a sample from a SequencedPublisher, if it is one.
+    pub fn of(sample: &Sample) -> Option<Self> {
+        Self::decode(&sample.attachment()?.to_bytes())
+    }
+
+    // Whether payload is what was published: always, if the publisher sent no CRC32C.
+    pub fn verify(&self, payload: &[u8]) -> bool {
+        self.crc32c
+            .is_none_or(|crc| crc == crc32c::checksum(payload))
+    }
+}
+
+// Samples a cached publisher keeps for advanced subscribers to fetch.
+pub const CACHED_SAMPLES: usize = 16;
+
+// How often a cached publisher repeats the number of its last sample, so advanced
+// subscribers notice when that one went missing too.
+const HEARTBEAT_PERIOD: Duration = Duration::from_secs(1);
+
+// Turns a publisher into one that keeps its last CACHED_SAMPLES samples for advanced
+// subscribers to fetch, both as history when they subscribe and to recover the samples
+// they missed. It announces itself through liveliness, so subscribers declared before it
+// fetch its history too.
+pub fn cached<'a, 'b, 'c>(
+    builder: PublisherBuilder<'a, 'b>,
+) -> AdvancedPublisherBuilder<'a, 'b, 'c> {
+    builder
+        .cache(CacheConfig::default().max_samples(CACHED_SAMPLES))
+        .sample_miss_detection(MissDetectionConfig::default().heartbeat(HEARTBEAT_PERIOD))
+        .publisher_detection()
+}
+
+enum Inner<'a> {
+    Plain(Publisher<'a>),
+    Cached(AdvancedPublisher<'a>),
+}
+
+// Numbers every sample it publishes, so subscribers can tell when samples went missing.
+pub struct SequencedPublisher<'a> {
+    publisher: Inner<'a>,
+    node: String,
+    next: AtomicU64,
+    crc32c: bool,
+    compression: Option<Codec>,
+    #[cfg(feature = "shm")]
+    shm: Option<Arc<crate::shm::ShmPool>>,
+}
+
+impl<'a> SequencedPublisher<'a> {
+    // Wraps a declared publisher; node is the name samples are attributed to.
+    pub fn new(publisher: Publisher<'a>, node: &str) -> Self {
+        Self::wrap(Inner::Plain(publisher), node)
+    }
+
+    // Wraps a publisher declared through cached(). The sequence numbers are cached with the
+    // samples, so samples an advanced subscriber recovers fill the gap they left.
+    pub fn cached(publisher: AdvancedPublisher<'a>, node: &str) -> Self {
+        Self::wrap(Inner::Cached(publisher), node)
+    }
+
+    fn wrap(publisher: Inner<'a>, node: &str) -> Self {
+        SequencedPublisher {
+            publisher,
+            node: node.to_string(),
+            next: AtomicU64::new(0),
+            crc32c: false,
+            compression: None,
+            #[cfg(feature = "shm")]
+            shm: None,
+        }
+    }
+
+    // Also attaches the CRC32C of every payload when enabled, so subscribers can tell
+    // corrupted payloads from good ones.
+    pub fn with_crc32c(mut self, enabled: bool) -> Self {
+        self.crc32c = enabled;
+        self
+    }
+
+    // Compresses payloads with the codec, if one is given, tagging the encoding of those it
+    // shortens with it.
+    pub fn with_compression(mut self, codec: Option<Codec>) -> Self {
+        self.compression = codec;
+        self
+    }
+
+    pub fn key_expr(&self) -> &KeyExpr<'a> {
+        match &self.publisher {
+            Inner::Plain(publisher) => publisher.key_expr(),
+            Inner::Cached(publisher) => publisher.key_expr(),
+        }
+    }
+
+    fn encoding(&self) -> &Encoding {
+        match &self.publisher {
+            Inner::Plain(publisher) => publisher.encoding(),
+            Inner::Cached(publisher) => publisher.encoding(),
+        }
+    }
+
+    // Publishes large payloads from the shared-memory pool, if one is given.
+    #[cfg(feature = "shm")]
+    pub fn with_shm(mut self, pool: Option<Arc<crate::shm::ShmPool>>) -> Self {
+        self.shm = pool;
+        self
+    }
+
+    // Publishes the payload with the publisher's encoding, the next sequence number and the
+    // context of the span it is published in, if traced, and counts it in
+    // zenoh_samples_published_total. The CRC32C is taken of the payload as
+    // sent, compressed or not, which subscribers check before they decompress it.
+    pub async fn put(&self, payload: impl Into<ZBytes>) -> zenoh::Result<()> {
+        let mut payload = payload.into();
+        let mut encoding = self.encoding().clone();
+        if let Some((compressed, tagged)) = self
+            .compression
+            .and_then(|codec| compression::compress(codec, &payload.to_bytes(), &encoding))
+        {
+            payload = compressed.into();
+            encoding = tagged;
+        }
+        let sequence = Sequence {
+            number: self.next.fetch_add(1, Ordering::Relaxed),
+            node: self.node.clone(),
+            crc32c: self.crc32c.then(|| crc32c::checksum(&payload.to_bytes())),
+            trace: trace::current(),
+        };
+        #[cfg(feature = "shm")]
+        let payload = match &self.shm {
+            Some(pool) => pool.copy(payload),
+            None => payload,
+        };
+        match &self.publisher {
+            Inner::Plain(publisher) => {
+                publisher
+                    .put(payload)
+                    .encoding(encoding)
+                    .attachment(sequence.encode())
+                    .await?
+            }
+            Inner::Cached(publisher) => {
+                publisher
+                    .put(payload)
+                    .encoding(encoding)
+                    .attachment(sequence.encode())
+                    .await?
+            }
+        }
+        metrics::counter!("zenoh_samples_published_total", "key" => self.key_expr().to_string())
+            .increment(1);
+        Ok(())
+    }
+
+    pub async fn undeclare(self) -> zenoh::Result<()> {
+        match self.publisher {
+            Inner::Plain(publisher) => publisher.undeclare().await,
+            Inner::Cached(publisher) => publisher.undeclare().await,
+        }
+    }
+}
+
+// What a subscriber saw of one publisher's samples on one key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    pub received: u64,
+    // Sequence numbers skipped between samples received.
+    pub dropped: u64,
+    // Times the numbers went back, i.e. the publisher restarted.
+    pub restarts: u32,
+    // Samples received whose payload failed its CRC32C.
+    pub corrupted: u64,
+}
+
+// Tracks the sequence numbers of samples received, per key and publishing node. A number
+// at or below the last one seen means the publisher restarted and counts on from there,
+// rather than as a drop.
+#[derive(Default)]
+pub struct DropTracker {
+    streams: BTreeMap<(String, String), (u64, StreamStats)>,
+}
+
+impl DropTracker {
+    // Records a sample received on key and returns how many samples before it were
+    // dropped.
+    pub fn record(&mut self, key: &str, sequence: &Sequence) -> u64 {
+        let stream = (key.to_string(), sequence.node.clone());
+        let Some((last, stats)) = self.streams.get_mut(&stream) else {
+            let stats = StreamStats {
+                received: 1,
+                ..Default::default()
+            };
+            self.streams.insert(stream, (sequence.number, stats));
+            return 0;
+        };
+        let dropped = if sequence.number > *last {
+            sequence.number - *last - 1
+        } else {
+            stats.restarts += 1;
+            0
+        };
+        *last = sequence.number;
+        stats.received += 1;
+        stats.dropped += dropped;
+        dropped
+    }
+
+    // Counts a sample recorded on key whose payload failed its CRC32C.
+    pub fn corrupted(&mut self, key: &str, sequence: &Sequence) {
+        let stream = (key.to_string(), sequence.node.clone());
+        if let Some((_, stats)) = self.streams.get_mut(&stream) {
+            stats.corrupted += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+
+    // Every stream seen, as key, publishing node and statistics, ordered by key.
+    pub fn streams(&self) -> impl Iterator<Item = (&str, &str, &StreamStats)> {
+        self.streams
+            .iter()
+            .map(|((key, node), (_, stats))| (key.as_str(), node.as_str(), stats))
+    }
+}
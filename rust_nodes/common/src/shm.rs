@@ -0,0 +1,49 @@
+use zenoh::Wait;
+use zenoh::bytes::ZBytes;
+use zenoh::shm::{GarbageCollect, PosixShmProviderBackend, ShmProvider, ShmProviderBuilder};
+
+// Payloads smaller than this are published as they are: below it, handing a subscriber a
+// shared-memory buffer costs more than copying the bytes through the transport, as
+// benches/shm.rs shows.
+pub const MIN_PAYLOAD: usize = 4096;
+
+// A shared-memory segment payloads are copied into before they are published, so the
+// transport only carries a reference to them to subscribers on the same host that have
+// shared memory enabled. Subscribers elsewhere receive the bytes as usual.
+pub struct ShmPool {
+    provider: ShmProvider<PosixShmProviderBackend>,
+}
+
+impl ShmPool {
+    // Maps a segment of size bytes, which bounds the payloads in flight at once.
+    pub fn new(size: usize) -> zenoh::Result<Self> {
+        let provider = ShmProviderBuilder::default_backend(size).wait()?;
+        Ok(ShmPool { provider })
+    }
+
+    // The payload in a shared-memory buffer, or as it is if it is below MIN_PAYLOAD. A
+    // payload the segment has no room for even after reclaiming the buffers subscribers
+    // released goes out through the transport rather than wait for one.
+    pub fn copy(&self, payload: ZBytes) -> ZBytes {
+        if payload.len() < MIN_PAYLOAD {
+            return payload;
+        }
+        if let Some(buffer) = self.alloc(&payload.to_bytes()) {
+            return buffer;
+        }
+        payload
+    }
+
+    // A shared-memory buffer holding bytes, whatever their length, or None if the segment
+    // has no room for it.
+    pub fn alloc(&self, bytes: &[u8]) -> Option<ZBytes> {
+        let mut buffer = self
+            .provider
+            .alloc(bytes.len())
+            .with_policy::<GarbageCollect>()
+            .wait()
+            .ok()?;
+        buffer.copy_from_slice(bytes);
+        Some(buffer.into())
+    }
+}
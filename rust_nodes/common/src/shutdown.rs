@@ -0,0 +1,10 @@
+use tokio::signal::unix::{SignalKind, signal};
+
+// Resolves once the process receives SIGINT (ctrl-c) or SIGTERM.
+pub async fn shutdown_signal() {
+    let mut sigterm = signal(SignalKind::terminate()).expect("Failed to install SIGTERM handler.");
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = sigterm.recv() => {}
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::OnceLock;
+
+const TRACE_ID_LEN: usize = 16;
+const SPAN_ID_LEN: usize = 8;
+
+// The W3C trace context of the span a sample was published in: the trace's ID, the span's
+// ID and the trace flags, of which bit 0 says whether the trace is sampled. Sequenced
+// publishers attach it, so the nodes a sample flows through can continue its trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; TRACE_ID_LEN],
+    pub span_id: [u8; SPAN_ID_LEN],
+    pub flags: u8,
+}
+
+impl TraceContext {
+    // Length of the context as encoded.
+    pub const LEN: usize = TRACE_ID_LEN + SPAN_ID_LEN + 1;
+
+    pub fn encode_into(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.trace_id);
+        bytes.extend_from_slice(&self.span_id);
+        bytes.push(self.flags);
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+        let (trace_id, rest) = bytes.split_at(TRACE_ID_LEN);
+        let (span_id, flags) = rest.split_at(SPAN_ID_LEN);
+        Some(TraceContext {
+            trace_id: trace_id.try_into().unwrap(),
+            span_id: span_id.try_into().unwrap(),
+            flags: flags[0],
+        })
+    }
+}
+
+// Where the context of the current span comes from, set by whatever exports the spans.
+static CURRENT: OnceLock<fn() -> Option<TraceContext>> = OnceLock::new();
+
+// Sets where current() takes the context of the current span from. Only the first call
+// sets it.
+pub fn set_current(current: fn() -> Option<TraceContext>) {
+    let _ = CURRENT.set(current);
+}
+
+// The context of the span the caller is in, if spans are exported and it is in one.
+pub fn current() -> Option<TraceContext> {
+    CURRENT.get().and_then(|current| current())
+}
@@ -0,0 +1,99 @@
+use clap::ValueEnum;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Mode {
+    Peer,
+    Client,
+}
+
+// Why a session could not be set up from the options.
+#[derive(Debug, Error)]
+pub enum ZenohConfigError {
+    #[error("invalid Zenoh config: {0}")]
+    Config(zenoh::Error),
+    #[error("failed to open Zenoh session: {0}")]
+    Open(zenoh::Error),
+    #[cfg(feature = "shm")]
+    #[error("failed to map a {size} byte shared-memory pool: {error}")]
+    ShmPool { size: u64, error: zenoh::Error },
+}
+
+// Zenoh session options accepted by every node. The session config is loaded from the
+// JSON5 file given by --zenoh-config or ZENOH_CONFIG and falls back to Zenoh's defaults;
+// the remaining options override individual settings on top of it.
+#[derive(clap::Args, Debug)]
+pub struct ZenohArgs {
+    /// JSON5 Zenoh config file.
+    #[arg(long, value_name = "PATH", env = "ZENOH_CONFIG")]
+    pub zenoh_config: Option<PathBuf>,
+    /// Zenoh session mode [default: the config file's mode, or peer].
+    #[arg(long, value_enum)]
+    pub mode: Option<Mode>,
+    /// Endpoint to connect to, e.g. tcp/192.168.1.10:7447. May be repeated.
+    #[arg(long, value_name = "ENDPOINT")]
+    pub connect: Vec<String>,
+    /// Endpoint to listen on. May be repeated.
+    #[arg(long, value_name = "ENDPOINT")]
+    pub listen: Vec<String>,
+    /// Size in bytes of the shared-memory pool large payloads are published from, so
+    /// subscribers on the same host receive them without a copy through the transport.
+    /// Published as usual if not given.
+    #[cfg(feature = "shm")]
+    #[arg(long, value_name = "BYTES", value_parser = clap::value_parser!(u64).range(1..))]
+    pub shm_pool: Option<u64>,
+    // Whether the session timestamps what it publishes, which a PublicationCache needs.
+    // Set by the nodes that declare one rather than given on the command line.
+    #[arg(skip)]
+    pub timestamping: bool,
+}
+
+impl ZenohArgs {
+    pub fn config(&self) -> zenoh::Result<zenoh::Config> {
+        let mut config = match &self.zenoh_config {
+            Some(path) => zenoh::Config::from_file(path)?,
+            None => zenoh::Config::default(),
+        };
+        if let Some(mode) = self.mode {
+            let mode = match mode {
+                Mode::Peer => "peer",
+                Mode::Client => "client",
+            };
+            config.insert_json5("mode", &serde_json::to_string(mode)?)?;
+        }
+        if !self.connect.is_empty() {
+            config.insert_json5("connect/endpoints", &serde_json::to_string(&self.connect)?)?;
+        }
+        if !self.listen.is_empty() {
+            config.insert_json5("listen/endpoints", &serde_json::to_string(&self.listen)?)?;
+        }
+        if self.timestamping {
+            config.insert_json5("timestamping/enabled", "true")?;
+        }
+        // Set up shared memory when the session opens rather than on the first buffer, so
+        // the first sample published does not pay for it.
+        #[cfg(feature = "shm")]
+        if self.shm_pool.is_some() {
+            config.insert_json5("transport/shared_memory/mode", "\"init\"")?;
+        }
+        Ok(config)
+    }
+
+    // The shared-memory pool --shm-pool asks for, if any.
+    #[cfg(feature = "shm")]
+    pub fn shm_pool(&self) -> Result<Option<crate::shm::ShmPool>, ZenohConfigError> {
+        let Some(size) = self.shm_pool else {
+            return Ok(None);
+        };
+        crate::shm::ShmPool::new(size as usize)
+            .map(Some)
+            .map_err(|error| ZenohConfigError::ShmPool { size, error })
+    }
+
+    // Opens a session with the resolved config.
+    pub async fn open(&self) -> Result<zenoh::Session, ZenohConfigError> {
+        let config = self.config().map_err(ZenohConfigError::Config)?;
+        zenoh::open(config).await.map_err(ZenohConfigError::Open)
+    }
+}
@@ -0,0 +1,124 @@
+use sensors_rs::sensors;
+use serde::Deserialize;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// A scalar of the fused state a control law can act on. Attitude angles are in radians,
+// rates in rad/s, and vertical quantities in m, m/s, and m/s², positive up.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Roll,
+    Pitch,
+    Yaw,
+    RollRate,
+    PitchRate,
+    YawRate,
+    Altitude,
+    VerticalVelocity,
+    VerticalAcceleration,
+}
+
+const COUNT: usize = 9;
+
+impl Channel {
+    pub fn name(self) -> &'static str {
+        match self {
+            Channel::Roll => "roll",
+            Channel::Pitch => "pitch",
+            Channel::Yaw => "yaw",
+            Channel::RollRate => "roll_rate",
+            Channel::PitchRate => "pitch_rate",
+            Channel::YawRate => "yaw_rate",
+            Channel::Altitude => "altitude",
+            Channel::VerticalVelocity => "vertical_velocity",
+            Channel::VerticalAcceleration => "vertical_acceleration",
+        }
+    }
+
+    // Whether errors on the channel wrap around at ±π.
+    pub fn is_angle(self) -> bool {
+        matches!(self, Channel::Roll | Channel::Pitch | Channel::Yaw)
+    }
+
+    // Error of a measurement against a setpoint, the short way around for angles.
+    pub fn error(self, setpoint: f32, measured: f32) -> f32 {
+        let error = setpoint - measured;
+        if !self.is_angle() {
+            return error;
+        }
+        let tau = std::f32::consts::TAU;
+        (error + std::f32::consts::PI).rem_euclid(tau) - std::f32::consts::PI
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// The channels of one fused state, None where fusion flagged the estimate invalid.
+pub struct Snapshot([Option<f32>; COUNT]);
+
+impl Snapshot {
+    pub fn decode(state: &sensors::FusedState) -> Self {
+        let vec3 = |v: Option<&sensors::Vec3>, valid: bool| match v {
+            Some(v) if valid => [Some(v.x()), Some(v.y()), Some(v.z())],
+            _ => [None; 3],
+        };
+        let [roll, pitch, yaw] = vec3(state.attitude(), state.attitude_valid());
+        let [roll_rate, pitch_rate, yaw_rate] =
+            vec3(state.angular_rate(), state.angular_rate_valid());
+        let [_, _, vertical_velocity] = vec3(state.velocity(), state.velocity_valid());
+        let [_, _, vertical_acceleration] = vec3(state.acceleration(), state.acceleration_valid());
+        let altitude = state.altitude_valid().then(|| state.altitude());
+        Snapshot([
+            roll,
+            pitch,
+            yaw,
+            roll_rate,
+            pitch_rate,
+            yaw_rate,
+            altitude,
+            vertical_velocity,
+            vertical_acceleration,
+        ])
+    }
+
+    pub fn get(&self, channel: Channel) -> Option<f32> {
+        self.0[channel as usize]
+    }
+}
+
+// The latest valid value of every channel and when it arrived. Fusion's validity flags
+// can flicker from one state to the next as an uncertainty hovers around its threshold,
+// so a channel is only given up on once it has gone without a valid value for a while.
+pub struct Latest([Option<(f32, Instant)>; COUNT]);
+
+impl Latest {
+    pub fn new() -> Self {
+        Latest([None; COUNT])
+    }
+
+    pub fn update(&mut self, snapshot: &Snapshot, now: Instant) {
+        for (latest, value) in self.0.iter_mut().zip(snapshot.0) {
+            if let Some(value) = value {
+                *latest = Some((value, now));
+            }
+        }
+    }
+
+    // The channels valid within timeout of now.
+    pub fn fresh(&self, now: Instant, timeout: Duration) -> Snapshot {
+        Snapshot(self.0.map(|latest| {
+            latest
+                .filter(|&(_, received)| now - received <= timeout)
+                .map(|(value, _)| value)
+        }))
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = [None; COUNT];
+    }
+}
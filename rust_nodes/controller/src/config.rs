@@ -0,0 +1,228 @@
+use crate::channel::Channel;
+use common::config;
+use common::qos::QosConfig;
+use sensors_rs::sensors;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use zenoh::key_expr::OwnedKeyExpr;
+
+// Control law used when no config file is given: rate damping on four fins.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+// One PID loop driving an axis from the error of a state channel to its setpoint.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PidConfig {
+    // Name of the axis the loop drives, e.g. roll; the mixer spreads it over actuators.
+    pub axis: String,
+    pub state: Channel,
+    #[serde(default)]
+    pub setpoint: f32,
+    pub kp: f32,
+    #[serde(default)]
+    pub ki: f32,
+    #[serde(default)]
+    pub kd: f32,
+    // Channel measuring the state's rate of change. The derivative term damps it directly
+    // if given, or the state differentiated between fused states otherwise.
+    pub rate: Option<Channel>,
+    // Bound on the integral term, in output units, so it does not wind up while the
+    // output saturates.
+    pub integral_limit: f32,
+    // Bound on the loop's output.
+    pub output_limit: f32,
+}
+
+// A linear-quadratic regulator: axis outputs u = -K (x - setpoint) over a state vector x,
+// with K computed offline.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LqrConfig {
+    pub states: Vec<Channel>,
+    // One per state; zero if left out.
+    #[serde(default)]
+    pub setpoint: Vec<f32>,
+    pub axes: Vec<String>,
+    // K, one row per axis and one column per state.
+    pub gains: Vec<Vec<f32>>,
+    // Bound on each axis output.
+    pub output_limit: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LawConfig {
+    Pid { loops: Vec<PidConfig> },
+    Lqr(LqrConfig),
+}
+
+impl LawConfig {
+    pub fn axes(&self) -> Vec<&str> {
+        match self {
+            LawConfig::Pid { loops } => loops.iter().map(|l| l.axis.as_str()).collect(),
+            LawConfig::Lqr(lqr) => lqr.axes.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+// How one actuator's command is made up from the axis outputs: their sum, weighted.
+#[derive(Clone, Debug, Deserialize)]
+pub struct MixConfig {
+    pub actuator: String,
+    pub axes: BTreeMap<String, f32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    // Flight phases the law runs in, from state/phase. Absent means always, regardless
+    // of the phase.
+    pub phases: Option<Vec<String>>,
+    pub law: LawConfig,
+    pub mixer: Vec<MixConfig>,
+    #[serde(default)]
+    pub qos: QosConfig,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // The flight phases the law runs in, or None if it always runs.
+    pub fn phases(&self) -> Option<Vec<sensors::FlightPhase>> {
+        self.phases
+            .as_ref()
+            .map(|names| names.iter().filter_map(|name| flight_phase(name)).collect())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.qos.validate()?;
+        for name in self.phases.iter().flatten() {
+            if flight_phase(name).is_none() {
+                return Err(format!("unknown flight phase {}", name));
+            }
+        }
+        match &self.law {
+            LawConfig::Pid { loops } => validate_pid(loops)?,
+            LawConfig::Lqr(lqr) => validate_lqr(lqr)?,
+        }
+        let axes = self.law.axes();
+        if self.mixer.is_empty() {
+            return Err("no actuators in the mixer".to_string());
+        }
+        for (i, mix) in self.mixer.iter().enumerate() {
+            let name = &mix.actuator;
+            match OwnedKeyExpr::try_from(name.clone()) {
+                Ok(key_expr) if !key_expr.is_wild() && !name.contains('/') => {}
+                _ => return Err(format!("invalid actuator name '{}'", name)),
+            }
+            if self.mixer[..i].iter().any(|other| &other.actuator == name) {
+                return Err(format!("actuator {} mixed twice", name));
+            }
+            for (axis, weight) in &mix.axes {
+                if !axes.contains(&axis.as_str()) {
+                    return Err(format!("{}: no control law drives axis {}", name, axis));
+                }
+                if !weight.is_finite() {
+                    return Err(format!("{}: weight of axis {} must be finite", name, axis));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn flight_phase(name: &str) -> Option<sensors::FlightPhase> {
+    sensors::FlightPhase::ENUM_VALUES
+        .iter()
+        .copied()
+        .find(|phase| {
+            phase
+                .variant_name()
+                .is_some_and(|variant| variant.eq_ignore_ascii_case(name))
+        })
+}
+
+fn validate_pid(loops: &[PidConfig]) -> Result<(), String> {
+    if loops.is_empty() {
+        return Err("no PID loops configured".to_string());
+    }
+    for (i, pid) in loops.iter().enumerate() {
+        let axis = &pid.axis;
+        if loops[..i].iter().any(|other| &other.axis == axis) {
+            return Err(format!("axis {} driven by two loops", axis));
+        }
+        let values = [
+            pid.setpoint,
+            pid.kp,
+            pid.ki,
+            pid.kd,
+            pid.integral_limit,
+            pid.output_limit,
+        ];
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err(format!("{}: gains and limits must be finite", axis));
+        }
+        if pid.integral_limit < 0.0 || pid.output_limit <= 0.0 {
+            return Err(format!(
+                "{}: output_limit must be positive and integral_limit not negative",
+                axis
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_lqr(lqr: &LqrConfig) -> Result<(), String> {
+    if lqr.states.is_empty() || lqr.axes.is_empty() {
+        return Err("LQR needs at least one state and one axis".to_string());
+    }
+    for (i, channel) in lqr.states.iter().enumerate() {
+        if lqr.states[..i].contains(channel) {
+            return Err(format!("LQR state {} listed twice", channel));
+        }
+    }
+    for (i, axis) in lqr.axes.iter().enumerate() {
+        if lqr.axes[..i].contains(axis) {
+            return Err(format!("LQR axis {} listed twice", axis));
+        }
+    }
+    if lqr.setpoint.len() > lqr.states.len() {
+        return Err("LQR has more setpoints than states".to_string());
+    }
+    if lqr.gains.len() != lqr.axes.len()
+        || lqr.gains.iter().any(|row| row.len() != lqr.states.len())
+    {
+        return Err(format!(
+            "LQR gains must be {} rows (axes) of {} (states)",
+            lqr.axes.len(),
+            lqr.states.len()
+        ));
+    }
+    let mut values = lqr
+        .gains
+        .iter()
+        .flatten()
+        .chain(&lqr.setpoint)
+        .chain([&lqr.output_limit]);
+    if values.any(|v| !v.is_finite()) {
+        return Err("LQR gains and limits must be finite".to_string());
+    }
+    if lqr.output_limit <= 0.0 {
+        return Err("LQR output_limit must be positive".to_string());
+    }
+    Ok(())
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let config: Config =
+            toml::from_str(DEFAULT_CONFIG).expect("Built-in controller config is invalid.");
+        config
+            .validate()
+            .expect("Built-in controller config is invalid.");
+        config
+    }
+}
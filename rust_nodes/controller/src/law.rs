@@ -0,0 +1,126 @@
+use crate::channel::{Channel, Snapshot};
+use crate::config::{LawConfig, LqrConfig, PidConfig};
+
+pub struct Pid {
+    config: PidConfig,
+    // Integral term, in output units.
+    integral: f32,
+    // Previous measurement and the fused state time it was taken at, for differentiating
+    // it when no rate channel is given.
+    last: Option<(f64, f32)>,
+    derivative: f32,
+}
+
+impl Pid {
+    fn new(config: PidConfig) -> Self {
+        Pid {
+            config,
+            integral: 0.0,
+            last: None,
+            derivative: 0.0,
+        }
+    }
+
+    fn update(&mut self, snapshot: &Snapshot, time: f64, dt: f32) -> Result<f32, Channel> {
+        let config = &self.config;
+        let measured = snapshot.get(config.state).ok_or(config.state)?;
+        let error = config.state.error(config.setpoint, measured);
+        // The derivative is taken on the measurement rather than the error, so a setpoint
+        // change does not kick the output.
+        let rate = match config.rate {
+            Some(channel) => snapshot.get(channel).ok_or(channel)?,
+            None => {
+                match self.last {
+                    Some((last_time, last)) if time > last_time => {
+                        self.derivative =
+                            config.state.error(measured, last) / (time - last_time) as f32;
+                    }
+                    Some(_) => {}
+                    None => self.derivative = 0.0,
+                }
+                self.last = Some((time, measured));
+                self.derivative
+            }
+        };
+        self.integral = (self.integral + config.ki * error * dt)
+            .clamp(-config.integral_limit, config.integral_limit);
+        let output = config.kp * error + self.integral - config.kd * rate;
+        Ok(output.clamp(-config.output_limit, config.output_limit))
+    }
+
+    fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last = None;
+        self.derivative = 0.0;
+    }
+}
+
+pub struct Lqr {
+    config: LqrConfig,
+}
+
+impl Lqr {
+    fn update(&self, snapshot: &Snapshot) -> Result<Vec<f32>, Channel> {
+        let config = &self.config;
+        let errors = config
+            .states
+            .iter()
+            .enumerate()
+            .map(|(i, &channel)| {
+                let measured = snapshot.get(channel).ok_or(channel)?;
+                let setpoint = config.setpoint.get(i).copied().unwrap_or(0.0);
+                Ok(channel.error(setpoint, measured))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // u = -K (x - setpoint) = K (setpoint - x).
+        Ok(config
+            .gains
+            .iter()
+            .map(|row| {
+                let u: f32 = row.iter().zip(&errors).map(|(k, e)| k * e).sum();
+                u.clamp(-config.output_limit, config.output_limit)
+            })
+            .collect())
+    }
+}
+
+pub enum Law {
+    Pid(Vec<Pid>),
+    Lqr(Lqr),
+}
+
+impl Law {
+    pub fn new(config: LawConfig) -> Self {
+        match config {
+            LawConfig::Pid { loops } => Law::Pid(loops.into_iter().map(Pid::new).collect()),
+            LawConfig::Lqr(config) => Law::Lqr(Lqr { config }),
+        }
+    }
+
+    // Names of the axes the law drives, in the order update returns their outputs.
+    pub fn axes(&self) -> Vec<&str> {
+        match self {
+            Law::Pid(loops) => loops.iter().map(|pid| pid.config.axis.as_str()).collect(),
+            Law::Lqr(lqr) => lqr.config.axes.iter().map(String::as_str).collect(),
+        }
+    }
+
+    // Runs the law on a fused state taken at time, dt seconds after the previous update.
+    // Fails with the first channel it needs that fusion flagged invalid.
+    pub fn update(&mut self, snapshot: &Snapshot, time: f64, dt: f32) -> Result<Vec<f32>, Channel> {
+        match self {
+            Law::Pid(loops) => loops
+                .iter_mut()
+                .map(|pid| pid.update(snapshot, time, dt))
+                .collect(),
+            Law::Lqr(lqr) => lqr.update(snapshot),
+        }
+    }
+
+    // Forgets integrated and differentiated state, e.g. while the law is not running.
+    pub fn reset(&mut self) {
+        if let Law::Pid(loops) = self {
+            loops.iter_mut().for_each(Pid::reset);
+        }
+    }
+}
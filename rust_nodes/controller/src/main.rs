@@ -0,0 +1,283 @@
+mod channel;
+mod config;
+mod law;
+
+use channel::{Latest, Snapshot};
+use clap::Parser;
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use common::sequence::SequencedPublisher;
+use config::Config;
+use keyspace::keys;
+use law::Law;
+use node_framework::trace::{self, TraceContext};
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensors_rs::sensors;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{Instrument, Span, error, info, info_span};
+use zenoh::sample::Sample;
+
+#[derive(Parser)]
+#[command(
+    about = "Runs a PID or LQR control law on state/fused at a fixed rate and publishes actuator commands on cmd/actuators/*"
+)]
+struct Args {
+    /// Control law and mixer (TOML or JSON). Defaults to rate damping on four fins during
+    /// BOOST and COAST.
+    config: Option<PathBuf>,
+    /// Rate the law runs and commands are published at.
+    #[arg(long, default_value_t = 50.0)]
+    rate_hz: f64,
+    /// Hold once no fused state, or no valid estimate of a channel the law needs, has
+    /// arrived for this long, in milliseconds.
+    #[arg(long, default_value_t = 200)]
+    state_timeout_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// One actuator's command as a weighted sum of axis outputs, by index into the law's axes.
+struct Mix {
+    actuator: String,
+    weights: Vec<(usize, f32)>,
+    publisher: Option<SequencedPublisher<'static>>,
+}
+
+struct Controller {
+    period: Duration,
+    timeout: Duration,
+    law: Law,
+    mixer: Vec<Mix>,
+    // Phases the law runs in, or None for always.
+    phases: Option<Vec<sensors::FlightPhase>>,
+    phase: Option<sensors::FlightPhase>,
+    // Time of the latest fused state, in seconds, and when it arrived.
+    state: Option<(f64, Instant)>,
+    // Context of the span the latest fused state was published in, if it was traced and
+    // no update has continued its trace yet.
+    trace: Option<TraceContext>,
+    channels: Latest,
+    last_update: Option<Instant>,
+    // Why the law is not running, if it is not; printed when it changes.
+    holding: Option<String>,
+    qos: QosConfig,
+}
+
+impl Controller {
+    fn on_sample(&mut self, sample: &Sample, now: Instant) -> zenoh::Result<()> {
+        let payload = sample.payload().to_bytes();
+        if sample.key_expr().as_str() == keys::phase() {
+            let state = flatbuffers::root::<sensors::PhaseState>(&payload)
+                .map_err(|e| format!("malformed phase state: {}", e))?;
+            self.phase = Some(state.phase());
+        } else {
+            let fused = flatbuffers::root::<sensors::FusedState>(&payload)
+                .map_err(|e| format!("malformed fused state: {}", e))?;
+            let time = fused.timestamp_us() as f64 / 1e6;
+            // Fused time going back, e.g. a replay restarting, leaves nothing to go on.
+            if self.state.is_some_and(|(last, _)| time < last) {
+                self.channels.clear();
+            }
+            self.channels.update(&Snapshot::decode(&fused), now);
+            self.state = Some((time, now));
+            self.trace = trace::of(sample);
+        }
+        Ok(())
+    }
+
+    // Why the law cannot run now, if it cannot.
+    fn hold_reason(&self, now: Instant) -> Option<String> {
+        if let Some(phases) = &self.phases {
+            match self.phase {
+                None => return Some("no flight phase yet".to_string()),
+                Some(phase) if !phases.contains(&phase) => {
+                    return Some(format!(
+                        "phase {}",
+                        phase.variant_name().unwrap_or("unknown").to_uppercase()
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        match &self.state {
+            None => Some("no fused state yet".to_string()),
+            Some((_, received)) if now - *received > self.timeout => {
+                Some(format!("no fused state for {:?}", self.timeout))
+            }
+            Some(_) => None,
+        }
+    }
+
+    fn hold(&mut self, reason: String) {
+        if self.holding.as_ref() != Some(&reason) {
+            info!("Holding: {}", reason);
+            self.holding = Some(reason);
+        }
+        self.law.reset();
+    }
+
+    async fn run(&mut self, now: Instant) -> zenoh::Result<()> {
+        let dt = match self.last_update {
+            Some(last) => (now - last).as_secs_f32(),
+            None => self.period.as_secs_f32(),
+        };
+        self.last_update = Some(now);
+        if let Some(reason) = self.hold_reason(now) {
+            self.hold(reason);
+            return Ok(());
+        }
+        let Some((time, _)) = self.state else {
+            return Ok(());
+        };
+        let snapshot = self.channels.fresh(now, self.timeout);
+        let outputs = match self.law.update(&snapshot, time, dt) {
+            Ok(outputs) => outputs,
+            Err(channel) => {
+                self.hold(format!("no valid {} for {:?}", channel, self.timeout));
+                return Ok(());
+            }
+        };
+        if self.holding.take().is_some() {
+            info!("Controlling");
+        }
+
+        let timestamp_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_micros() as u64)
+            .unwrap_or(0);
+        for mix in &self.mixer {
+            let position = mix
+                .weights
+                .iter()
+                .map(|&(axis, weight)| weight * outputs[axis])
+                .sum();
+            let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(32);
+            let command = sensors::ActuatorCommand::create(
+                &mut builder,
+                &sensors::ActuatorCommandArgs {
+                    timestamp_us,
+                    position,
+                },
+            );
+            builder.finish(command, None);
+            if let Some(publisher) = &mix.publisher {
+                publisher.put(builder.finished_data().to_vec()).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Node for Controller {
+    const NAME: &'static str = "controller";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.set_qos(self.qos.clone());
+        for mix in &mut self.mixer {
+            let key = keys::actuator_command(&mix.actuator);
+            let publisher = ctx
+                .session()
+                .declare_publisher(key.clone())
+                .encoding(encoding::flatbuffer("sensors.ActuatorCommand"))
+                .qos(ctx.qos(), &key)
+                .await?;
+            mix.publisher = Some(ctx.sequenced(publisher));
+        }
+        ctx.subscribe(keys::fused_state()).await?;
+        if self.phases.is_some() {
+            ctx.subscribe(keys::phase()).await?;
+        }
+        ctx.add_timer(self.period);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let now = Instant::now();
+        match event {
+            // The first update after a traced fused state continues its trace, so the
+            // commands it publishes carry it on to the actuators.
+            Event::Timer(_) => {
+                let span = match self.trace.take() {
+                    Some(parent) => {
+                        let span = info_span!("control");
+                        trace::continue_from(&span, parent);
+                        span
+                    }
+                    None => Span::none(),
+                };
+                self.run(now).instrument(span).await?
+            }
+            Event::Sample(_, sample) => self.on_sample(&sample, now)?,
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        for mix in &mut self.mixer {
+            if let Some(publisher) = mix.publisher.take() {
+                publisher.undeclare().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if !args.rate_hz.is_finite() || args.rate_hz <= 0.0 {
+        error!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    let config = match &args.config {
+        Some(path) => match Config::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid controller config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Config::default(),
+    };
+
+    let phases = config.phases();
+    let law = Law::new(config.law);
+    let axes = law.axes();
+    let mixer = config
+        .mixer
+        .into_iter()
+        .map(|mix| Mix {
+            actuator: mix.actuator,
+            // Validation made sure every mixed axis is one the law drives.
+            weights: mix
+                .axes
+                .iter()
+                .filter_map(|(axis, &weight)| {
+                    let index = axes.iter().position(|name| name == axis)?;
+                    Some((index, weight))
+                })
+                .collect(),
+            publisher: None,
+        })
+        .collect();
+
+    let node = Controller {
+        period: Duration::from_secs_f64(1.0 / args.rate_hz),
+        timeout: Duration::from_millis(args.state_timeout_ms),
+        law,
+        mixer,
+        phases,
+        phase: None,
+        state: None,
+        trace: None,
+        channels: Latest::new(),
+        last_update: None,
+        holding: None,
+        qos: config.qos,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,162 @@
+mod telemetry;
+mod ui;
+
+use clap::Parser;
+use common::{ZenohArgs, compression};
+use keyspace::keys;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use telemetry::Telemetry;
+use zenoh::Session;
+use zenoh::pubsub::Subscriber;
+use zenoh::sample::Sample;
+
+#[derive(Parser)]
+#[command(
+    about = "Shows the flight state, sensors, nodes and alarms on the bus live in the terminal"
+)]
+struct Args {
+    /// Period the screen is redrawn and the trends sampled at, in milliseconds.
+    #[arg(long, default_value_t = 200, value_parser = clap::value_parser!(u64).range(1..))]
+    refresh_ms: u64,
+    /// Samples of each trend kept, so it spans this many refresh periods at most.
+    #[arg(long, default_value_t = 600)]
+    history: usize,
+    /// Show a sensor, node or the fused state as stale after this long without a sample,
+    /// in milliseconds.
+    #[arg(long, default_value_t = 3000)]
+    stale_ms: u64,
+    #[command(flatten)]
+    zenoh: ZenohArgs,
+}
+
+type Handler = fn(&mut Telemetry, &Sample) -> Result<(), String>;
+
+// Hands the samples on key to handler, decompressed. Samples that cannot be read are only
+// counted, as there is nowhere to log them while the console is drawn.
+async fn subscribe(
+    session: &Session,
+    key: &str,
+    telemetry: &Arc<Mutex<Telemetry>>,
+    handler: Handler,
+) -> zenoh::Result<Subscriber<()>> {
+    let telemetry = telemetry.clone();
+    session
+        .declare_subscriber(key.to_string())
+        .callback(move |sample| {
+            let mut telemetry = telemetry.lock().unwrap();
+            let result = compression::decompress(sample).and_then(|s| handler(&mut telemetry, &s));
+            if result.is_err() {
+                telemetry.malformed += 1;
+            }
+        })
+        .await
+}
+
+async fn declare(
+    session: &Session,
+    telemetry: &Arc<Mutex<Telemetry>>,
+) -> zenoh::Result<(Vec<Subscriber<()>>, Subscriber<()>)> {
+    let handlers: [(&str, Handler); 8] = [
+        (keys::all_devices(), Telemetry::on_sensor),
+        (keys::fused_state(), Telemetry::on_fused),
+        (keys::phase(), Telemetry::on_phase),
+        (keys::all_heartbeats(), Telemetry::on_heartbeat),
+        (keys::all_stats(), Telemetry::on_stats),
+        (keys::all_alarms(), Telemetry::on_alarm),
+        (keys::all_crashes(), Telemetry::on_crash),
+        (keys::all_overruns(), Telemetry::on_overrun),
+    ];
+    let mut subscribers = Vec::with_capacity(handlers.len());
+    for (key, handler) in handlers {
+        subscribers.push(subscribe(session, key, telemetry, handler).await?);
+    }
+    // With history, so nodes already up are listed from the start.
+    let alive = {
+        let telemetry = telemetry.clone();
+        session
+            .liveliness()
+            .declare_subscriber(keys::all_alive())
+            .history(true)
+            .callback(move |sample| {
+                let _ = telemetry.lock().unwrap().on_alive(&sample);
+            })
+            .await?
+    };
+    Ok((subscribers, alive))
+}
+
+// Whether a key asking to quit was pressed since the last call. Waits for none.
+fn quit_pressed() -> std::io::Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Press
+            && (matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                || key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let session = match args.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let telemetry = Arc::new(Mutex::new(Telemetry::new(args.history)));
+    let (subscribers, alive) = match declare(&session, &telemetry).await {
+        Ok(subscribers) => subscribers,
+        Err(e) => {
+            eprintln!("Failed to subscribe: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let stale = Duration::from_millis(args.stale_ms);
+    // Restores the terminal on a panic too.
+    let mut terminal = match ratatui::try_init() {
+        Ok(terminal) => terminal,
+        Err(e) => {
+            eprintln!("Cannot draw on this terminal: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut refresh = tokio::time::interval(Duration::from_millis(args.refresh_ms));
+    refresh.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let result = loop {
+        refresh.tick().await;
+        match quit_pressed() {
+            Ok(false) => {}
+            Ok(true) => break Ok(()),
+            Err(e) => break Err(e),
+        }
+        let mut telemetry = telemetry.lock().unwrap();
+        telemetry.sample_histories();
+        if let Err(e) = terminal.draw(|frame| ui::draw(frame, &telemetry, stale)) {
+            break Err(e);
+        }
+    };
+    ratatui::restore();
+    for subscriber in subscribers {
+        let _ = subscriber.undeclare().await;
+    }
+    let _ = alive.undeclare().await;
+    let _ = session.close().await;
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to draw: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use keyspace::keys;
+use sensor_decode::MessageType;
+use sensors_rs::sensors;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+use zenoh::sample::{Sample, SampleKind};
+
+// Events kept for the event list, newest first.
+const EVENTS: usize = 100;
+// Period sensor rates are counted over.
+const RATE_PERIOD: Duration = Duration::from_secs(1);
+
+// The latest fused state and when it arrived.
+pub struct Fused {
+    pub received: Instant,
+    pub attitude: Option<[f32; 3]>,
+    pub angular_rate: Option<[f32; 3]>,
+    pub altitude: Option<f32>,
+    pub velocity: Option<[f32; 3]>,
+    pub acceleration: Option<[f32; 3]>,
+}
+
+pub struct Phase {
+    pub phase: String,
+    pub since_s: f64,
+    pub max_altitude: f32,
+}
+
+// One sensor key: its latest values, named by its type's channels, and how often it
+// publishes.
+pub struct Sensor {
+    pub kind: MessageType,
+    pub values: Vec<f64>,
+    pub received: Instant,
+    pub rate_hz: f64,
+    counted: u64,
+    counting_since: Instant,
+}
+
+// One node, from its liveliness token, heartbeats and stats on stats/<node>.
+pub struct NodeStatus {
+    pub alive: bool,
+    pub heartbeat: Option<Instant>,
+    pub uptime_s: f64,
+    pub errors: u64,
+    pub mean_cycle_us: f32,
+    pub cpu_percent: Option<f32>,
+    pub rss_bytes: Option<u64>,
+}
+
+pub struct ActiveAlarm {
+    pub severity: sensors::AlarmSeverity,
+    pub kind: sensors::AlarmKind,
+    pub value: f64,
+    pub limit: f64,
+    pub since: Instant,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warning,
+    Critical,
+}
+
+pub struct LogEntry {
+    pub at: Instant,
+    pub level: Level,
+    pub text: String,
+    // Times the event happened in a row, as the last one; an overrunning node overruns
+    // every tick.
+    pub count: u64,
+}
+
+// Latest values of one quantity at the dashboard's refresh rate, oldest first; None where
+// nothing was known.
+pub struct History {
+    capacity: usize,
+    values: VecDeque<Option<f64>>,
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        History {
+            capacity,
+            values: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, value: Option<f64>) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    // The last count values, oldest first.
+    pub fn last(&self, count: usize) -> impl Iterator<Item = Option<f64>> + '_ {
+        self.values
+            .iter()
+            .skip(self.values.len().saturating_sub(count))
+            .copied()
+    }
+}
+
+// Everything the dashboard shows, updated from the subscribers' callbacks.
+pub struct Telemetry {
+    pub fused: Option<Fused>,
+    pub phase: Option<Phase>,
+    pub altitude: History,
+    pub vertical_speed: History,
+    pub acceleration: History,
+    pub sensors: BTreeMap<String, Sensor>,
+    pub nodes: BTreeMap<String, NodeStatus>,
+    // Keyed by the alarm's key, alarms/<sensor key>/<channel>.
+    pub alarms: BTreeMap<String, ActiveAlarm>,
+    pub events: VecDeque<LogEntry>,
+    // Message and byte rates of the whole bus, as bus_monitor last saw them.
+    pub bus: Option<(f64, f64)>,
+    // Samples that could not be decoded, since the dashboard started.
+    pub malformed: u64,
+}
+
+impl Telemetry {
+    pub fn new(history: usize) -> Self {
+        Telemetry {
+            fused: None,
+            phase: None,
+            altitude: History::new(history),
+            vertical_speed: History::new(history),
+            acceleration: History::new(history),
+            sensors: BTreeMap::new(),
+            nodes: BTreeMap::new(),
+            alarms: BTreeMap::new(),
+            events: VecDeque::with_capacity(EVENTS),
+            bus: None,
+            malformed: 0,
+        }
+    }
+
+    fn log(&mut self, level: Level, text: String) {
+        if let Some(last) = self.events.front_mut().filter(|last| last.text == text) {
+            last.at = Instant::now();
+            last.count += 1;
+            return;
+        }
+        if self.events.len() == EVENTS {
+            self.events.pop_back();
+        }
+        self.events.push_front(LogEntry {
+            at: Instant::now(),
+            level,
+            text,
+            count: 1,
+        });
+    }
+
+    fn node(&mut self, name: &str) -> &mut NodeStatus {
+        self.nodes
+            .entry(name.to_string())
+            .or_insert_with(|| NodeStatus {
+                alive: false,
+                heartbeat: None,
+                uptime_s: 0.0,
+                errors: 0,
+                mean_cycle_us: 0.0,
+                cpu_percent: None,
+                rss_bytes: None,
+            })
+    }
+
+    // Adds the latest fused values to the sparklines' histories, once per refresh, so
+    // they share a time axis whatever rate fusion publishes at.
+    pub fn sample_histories(&mut self) {
+        let fused = self.fused.as_ref();
+        let altitude = fused.and_then(|f| f.altitude).map(f64::from);
+        let vertical_speed = fused.and_then(|f| f.velocity).map(|v| v[2] as f64);
+        let acceleration = fused
+            .and_then(|f| f.acceleration)
+            .map(|a| (a.iter().map(|a| a * a).sum::<f32>() as f64).sqrt());
+        self.altitude.push(altitude);
+        self.vertical_speed.push(vertical_speed);
+        self.acceleration.push(acceleration);
+    }
+
+    pub fn on_sensor(&mut self, sample: &Sample) -> Result<(), String> {
+        let key = sample.key_expr().as_str();
+        // Actuator positions share the prefix but are not readings.
+        let Some(kind) = MessageType::detect(key, sample.encoding()) else {
+            return Ok(());
+        };
+        let values = sensor_decode::values(kind, sample.encoding(), sample.payload())?;
+        let now = Instant::now();
+        let sensor = self
+            .sensors
+            .entry(key.to_string())
+            .or_insert_with(|| Sensor {
+                kind,
+                values: Vec::new(),
+                received: now,
+                rate_hz: 0.0,
+                counted: 0,
+                counting_since: now,
+            });
+        sensor.kind = kind;
+        sensor.values = values;
+        sensor.received = now;
+        sensor.counted += 1;
+        let counted_for = now.duration_since(sensor.counting_since);
+        if counted_for >= RATE_PERIOD {
+            sensor.rate_hz = sensor.counted as f64 / counted_for.as_secs_f64();
+            sensor.counted = 0;
+            sensor.counting_since = now;
+        }
+        Ok(())
+    }
+
+    pub fn on_fused(&mut self, sample: &Sample) -> Result<(), String> {
+        let payload = sample.payload().to_bytes();
+        let state = flatbuffers::root::<sensors::FusedState>(&payload)
+            .map_err(|e| format!("malformed fused state: {}", e))?;
+        let vec3 = |v: Option<&sensors::Vec3>, valid: bool| {
+            v.filter(|_| valid).map(|v| [v.x(), v.y(), v.z()])
+        };
+        self.fused = Some(Fused {
+            received: Instant::now(),
+            attitude: vec3(state.attitude(), state.attitude_valid()),
+            angular_rate: vec3(state.angular_rate(), state.angular_rate_valid()),
+            altitude: state.altitude_valid().then(|| state.altitude()),
+            velocity: vec3(state.velocity(), state.velocity_valid()),
+            acceleration: vec3(state.acceleration(), state.acceleration_valid()),
+        });
+        Ok(())
+    }
+
+    pub fn on_phase(&mut self, sample: &Sample) -> Result<(), String> {
+        let payload = sample.payload().to_bytes();
+        let state = flatbuffers::root::<sensors::PhaseState>(&payload)
+            .map_err(|e| format!("malformed phase: {}", e))?;
+        let phase = phase_name(state.phase());
+        if self.phase.as_ref().is_some_and(|p| p.phase != phase) {
+            let reason = state.reason().unwrap_or_default();
+            self.log(Level::Info, format!("phase {} ({})", phase, reason));
+        }
+        self.phase = Some(Phase {
+            phase,
+            since_s: state.since_s(),
+            max_altitude: state.max_altitude(),
+        });
+        Ok(())
+    }
+
+    pub fn on_alive(&mut self, sample: &Sample) -> Result<(), String> {
+        let key = sample.key_expr().as_str();
+        let name = key.strip_prefix(keys::ALIVE).unwrap_or(key);
+        let name = name.trim_start_matches('/').to_string();
+        let alive = sample.kind() == SampleKind::Put;
+        self.node(&name).alive = alive;
+        let level = if alive { Level::Info } else { Level::Warning };
+        let state = if alive { "up" } else { "down" };
+        self.log(level, format!("{} {}", name, state));
+        Ok(())
+    }
+
+    pub fn on_heartbeat(&mut self, sample: &Sample) -> Result<(), String> {
+        let payload = sample.payload().to_bytes();
+        let heartbeat = flatbuffers::root::<sensors::Heartbeat>(&payload)
+            .map_err(|e| format!("malformed heartbeat: {}", e))?;
+        let node = self.node(heartbeat.node().unwrap_or_default());
+        node.heartbeat = Some(Instant::now());
+        node.uptime_s = heartbeat.uptime_s();
+        node.errors = heartbeat.errors();
+        node.mean_cycle_us = heartbeat.mean_cycle_us();
+        Ok(())
+    }
+
+    // Node statistics on stats/<node> and the bus totals on stats/bus; the other
+    // statistics under stats/ are not shown.
+    pub fn on_stats(&mut self, sample: &Sample) -> Result<(), String> {
+        let key = sample.key_expr().as_str();
+        let payload = sample.payload().to_bytes();
+        if key == keys::bus_stats() {
+            let stats = flatbuffers::root::<sensors::BusStats>(&payload)
+                .map_err(|e| format!("malformed bus stats: {}", e))?;
+            let keys = stats.keys().unwrap_or_default();
+            let messages = keys.iter().map(|k| k.messages_per_s() as f64).sum();
+            let bytes = keys.iter().map(|k| k.bytes_per_s() as f64).sum();
+            self.bus = Some((messages, bytes));
+            return Ok(());
+        }
+        if key == keys::drop_stats() || key == keys::parse_stats() {
+            return Ok(());
+        }
+        let stats = flatbuffers::root::<sensors::NodeStats>(&payload)
+            .map_err(|e| format!("malformed node stats on {}: {}", key, e))?;
+        let node = self.node(stats.node().unwrap_or_default());
+        node.cpu_percent = Some(stats.cpu_percent());
+        node.rss_bytes = Some(stats.rss_bytes());
+        Ok(())
+    }
+
+    pub fn on_alarm(&mut self, sample: &Sample) -> Result<(), String> {
+        let key = sample.key_expr().as_str();
+        let name = key.strip_prefix(keys::ALARMS).unwrap_or(key);
+        let name = name.trim_start_matches('/').to_string();
+        let payload = sample.payload().to_bytes();
+        let alarm = flatbuffers::root::<sensors::Alarm>(&payload)
+            .map_err(|e| format!("malformed alarm: {}", e))?;
+        let level = match alarm.severity() {
+            sensors::AlarmSeverity::Critical => Level::Critical,
+            sensors::AlarmSeverity::Warning => Level::Warning,
+            _ => Level::Info,
+        };
+        if alarm.active() {
+            // A repeat of an active alarm keeps the time it was raised.
+            let since = self
+                .alarms
+                .get(&name)
+                .map_or_else(Instant::now, |active| active.since);
+            if !self.alarms.contains_key(&name) {
+                self.log(
+                    level,
+                    format!(
+                        "alarm {} {:.3} (limit {:.3})",
+                        name,
+                        alarm.value(),
+                        alarm.limit()
+                    ),
+                );
+            }
+            self.alarms.insert(
+                name,
+                ActiveAlarm {
+                    severity: alarm.severity(),
+                    kind: alarm.kind(),
+                    value: alarm.value(),
+                    limit: alarm.limit(),
+                    since,
+                },
+            );
+        } else if self.alarms.remove(&name).is_some() {
+            self.log(Level::Info, format!("cleared {}", name));
+        }
+        Ok(())
+    }
+
+    pub fn on_crash(&mut self, sample: &Sample) -> Result<(), String> {
+        let payload = sample.payload().to_bytes();
+        let crash = flatbuffers::root::<sensors::CrashReport>(&payload)
+            .map_err(|e| format!("malformed crash report: {}", e))?;
+        self.log(
+            Level::Critical,
+            format!(
+                "{} crashed: {} at {}",
+                crash.node().unwrap_or_default(),
+                crash.message().unwrap_or_default(),
+                crash.location().unwrap_or_default()
+            ),
+        );
+        Ok(())
+    }
+
+    pub fn on_overrun(&mut self, sample: &Sample) -> Result<(), String> {
+        let payload = sample.payload().to_bytes();
+        let overrun = flatbuffers::root::<sensors::DeadlineOverrun>(&payload)
+            .map_err(|e| format!("malformed overrun: {}", e))?;
+        self.log(
+            Level::Warning,
+            format!(
+                "{} overran its {} us timer {}",
+                overrun.node().unwrap_or_default(),
+                overrun.period_us(),
+                overrun.timer()
+            ),
+        );
+        Ok(())
+    }
+}
+
+fn phase_name(phase: sensors::FlightPhase) -> String {
+    match phase.variant_name() {
+        Some(name) => name.to_string(),
+        None => format!("phase {}", phase.0),
+    }
+}
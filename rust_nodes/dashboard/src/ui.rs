@@ -0,0 +1,396 @@
+use crate::telemetry::{History, Level, NodeStatus, Telemetry};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, List, ListItem, Paragraph, Row, Sparkline, Table};
+use sensors_rs::sensors;
+use std::time::{Duration, Instant};
+
+// Sensor values shown per key; the type's first channels, leaving out timestamps.
+const SENSOR_VALUES: usize = 3;
+
+fn level_style(level: Level) -> Style {
+    match level {
+        Level::Info => Style::default(),
+        Level::Warning => Style::default().fg(Color::Yellow),
+        Level::Critical => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    }
+}
+
+// How long ago, in the largest whole unit, e.g. 3s or 12m.
+fn age(since: Instant, now: Instant) -> String {
+    let age = now.saturating_duration_since(since);
+    match age.as_secs() {
+        0 => format!("{}ms", age.as_millis()),
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h", s / 3600),
+    }
+}
+
+fn vec3(v: Option<[f32; 3]>, labels: [&str; 3], scale: f32, precision: usize) -> String {
+    match v {
+        Some(v) => labels
+            .iter()
+            .zip(v)
+            .map(|(label, v)| format!("{} {:>8.*}", label, precision, v * scale))
+            .collect::<Vec<_>>()
+            .join("  "),
+        None => "invalid".to_string(),
+    }
+}
+
+// Draws the whole console: a status line, the flight state and its trends and the sensors
+// on the left, the nodes, active alarms and recent events on the right.
+pub fn draw(frame: &mut Frame, telemetry: &Telemetry, stale: Duration) {
+    let now = Instant::now();
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body);
+    let [flight, trends, sensors] = Layout::vertical([
+        Constraint::Length(8),
+        Constraint::Length(12),
+        Constraint::Min(0),
+    ])
+    .areas(left);
+    let [nodes, alarms, events] = Layout::vertical([
+        Constraint::Percentage(45),
+        Constraint::Length(8),
+        Constraint::Min(0),
+    ])
+    .areas(right);
+    draw_header(frame, header, telemetry, now, stale);
+    draw_flight(frame, flight, telemetry, now, stale);
+    draw_trends(frame, trends, telemetry);
+    draw_sensors(frame, sensors, telemetry, now, stale);
+    draw_nodes(frame, nodes, telemetry, now, stale);
+    draw_alarms(frame, alarms, telemetry, now);
+    draw_events(frame, events, telemetry, now);
+    let mut status = vec![Span::raw(" q quit")];
+    if telemetry.malformed > 0 {
+        status.push(Span::styled(
+            format!("  {} malformed samples", telemetry.malformed),
+            level_style(Level::Warning),
+        ));
+    }
+    frame.render_widget(Line::from(status), footer);
+}
+
+fn draw_header(
+    frame: &mut Frame,
+    area: Rect,
+    telemetry: &Telemetry,
+    now: Instant,
+    stale: Duration,
+) {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut spans = vec![Span::styled(
+        " DASHBOARD ",
+        bold.add_modifier(Modifier::REVERSED),
+    )];
+    match &telemetry.phase {
+        Some(phase) => spans.push(Span::styled(
+            format!(
+                "  {} for {:.0} s",
+                phase.phase.to_uppercase(),
+                phase.since_s
+            ),
+            bold,
+        )),
+        None => spans.push(Span::raw("  no phase")),
+    }
+    let fused = match &telemetry.fused {
+        Some(fused) if now.saturating_duration_since(fused.received) < stale => {
+            Span::raw(format!("  fused {} ago", age(fused.received, now)))
+        }
+        Some(fused) => Span::styled(
+            format!("  fused STALE {}", age(fused.received, now)),
+            level_style(Level::Critical),
+        ),
+        None => Span::styled("  no fused state", level_style(Level::Warning)),
+    };
+    spans.push(fused);
+    let active = telemetry.alarms.len();
+    if active > 0 {
+        spans.push(Span::styled(
+            format!("  {} active alarms", active),
+            level_style(Level::Critical),
+        ));
+    }
+    if let Some((messages, bytes)) = telemetry.bus {
+        spans.push(Span::raw(format!(
+            "  bus {:.0} msg/s {:.1} kB/s",
+            messages,
+            bytes / 1e3
+        )));
+    }
+    frame.render_widget(Line::from(spans), area);
+}
+
+fn draw_flight(
+    frame: &mut Frame,
+    area: Rect,
+    telemetry: &Telemetry,
+    now: Instant,
+    stale: Duration,
+) {
+    let block = Block::bordered().title(" Flight ");
+    let Some(fused) = &telemetry.fused else {
+        frame.render_widget(Paragraph::new("waiting for state/fused").block(block), area);
+        return;
+    };
+    let style = if now.saturating_duration_since(fused.received) < stale {
+        Style::default()
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let altitude = match fused.altitude {
+        Some(altitude) => format!("{:>10.2} m", altitude),
+        None => "invalid".to_string(),
+    };
+    let max_altitude = telemetry
+        .phase
+        .as_ref()
+        .map(|phase| format!("   max {:.1} m", phase.max_altitude))
+        .unwrap_or_default();
+    let lines = vec![
+        Line::from(format!("Altitude  {}{}", altitude, max_altitude)),
+        Line::from(format!(
+            "Velocity  {} m/s",
+            vec3(fused.velocity, ["E", "N", "U"], 1.0, 2)
+        )),
+        Line::from(format!(
+            "Accel     {} m/s²",
+            vec3(fused.acceleration, ["E", "N", "U"], 1.0, 2)
+        )),
+        Line::from(format!(
+            "Attitude  {} °",
+            vec3(
+                fused.attitude,
+                ["R", "P", "Y"],
+                180.0 / std::f32::consts::PI,
+                1
+            )
+        )),
+        Line::from(format!(
+            "Rate      {} rad/s",
+            vec3(fused.angular_rate, ["X", "Y", "Z"], 1.0, 3)
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).style(style).block(block), area);
+}
+
+fn draw_trends(frame: &mut Frame, area: Rect, telemetry: &Telemetry) {
+    let [altitude, vertical_speed, acceleration] =
+        Layout::vertical([Constraint::Length(4); 3]).areas(area);
+    draw_sparkline(frame, altitude, "Altitude", "m", &telemetry.altitude);
+    draw_sparkline(
+        frame,
+        vertical_speed,
+        "Vertical speed",
+        "m/s",
+        &telemetry.vertical_speed,
+    );
+    draw_sparkline(
+        frame,
+        acceleration,
+        "|Accel|",
+        "m/s²",
+        &telemetry.acceleration,
+    );
+}
+
+// A sparkline of the history spanning the area's width, scaled from its lowest to its
+// highest value, so negative values show as well.
+fn draw_sparkline(frame: &mut Frame, area: Rect, name: &str, unit: &str, history: &History) {
+    let width = area.width.saturating_sub(2) as usize;
+    let values: Vec<Option<f64>> = history.last(width).collect();
+    let known = || values.iter().flatten().copied();
+    let min = known().fold(f64::INFINITY, f64::min);
+    let max = known().fold(f64::NEG_INFINITY, f64::max);
+    let title = match values.last().copied().flatten() {
+        Some(latest) => format!(
+            " {} {:.2} {} [{:.1} .. {:.1}] ",
+            name, latest, unit, min, max
+        ),
+        None => format!(" {} ", name),
+    };
+    let span = (max - min).max(f64::EPSILON);
+    // Bars of 1..=100, so the lowest value still shows as a bar and an unknown one as none.
+    let bars: Vec<Option<u64>> = values
+        .iter()
+        .map(|v| v.map(|v| 1 + ((v - min) / span * 99.0).round() as u64))
+        .collect();
+    let sparkline = Sparkline::default()
+        .block(Block::bordered().title(title))
+        .data(&bars)
+        .max(100)
+        .style(Style::default().fg(Color::Cyan));
+    frame.render_widget(sparkline, area);
+}
+
+fn draw_sensors(
+    frame: &mut Frame,
+    area: Rect,
+    telemetry: &Telemetry,
+    now: Instant,
+    stale: Duration,
+) {
+    let rows = telemetry.sensors.iter().map(|(key, sensor)| {
+        let values = sensor
+            .kind
+            .channels()
+            .iter()
+            .zip(&sensor.values)
+            .filter(|(channel, _)| **channel != "timestamp_us")
+            .take(SENSOR_VALUES)
+            .map(|(channel, value)| format!("{} {:.3}", channel, value))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let style = if now.saturating_duration_since(sensor.received) < stale {
+            Style::default()
+        } else {
+            level_style(Level::Warning)
+        };
+        Row::new([
+            Cell::from(key.as_str()),
+            Cell::from(format!("{:>6.1}", sensor.rate_hz)),
+            Cell::from(format!("{:>5}", age(sensor.received, now))),
+            Cell::from(values),
+        ])
+        .style(style)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(22),
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Min(0),
+        ],
+    )
+    .header(
+        Row::new(["KEY", "    HZ", "  AGE", "VALUES"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::bordered().title(" Sensors "));
+    frame.render_widget(table, area);
+}
+
+fn node_state(node: &NodeStatus, now: Instant, stale: Duration) -> (&'static str, Style) {
+    if !node.alive {
+        return ("down", level_style(Level::Critical));
+    }
+    match node.heartbeat {
+        Some(at) if now.saturating_duration_since(at) >= stale => {
+            ("stale", level_style(Level::Warning))
+        }
+        _ => ("up", Style::default().fg(Color::Green)),
+    }
+}
+
+fn draw_nodes(frame: &mut Frame, area: Rect, telemetry: &Telemetry, now: Instant, stale: Duration) {
+    let rows = telemetry.nodes.iter().map(|(name, node)| {
+        let (state, style) = node_state(node, now, stale);
+        Row::new([
+            Cell::from(name.as_str()),
+            Cell::from(state).style(style),
+            Cell::from(format!("{:>6.0}", node.uptime_s)),
+            Cell::from(node.heartbeat.map(|at| age(at, now)).unwrap_or_default()),
+            Cell::from(
+                node.cpu_percent
+                    .map(|cpu| format!("{:>5.1}", cpu))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(
+                node.rss_bytes
+                    .map(|rss| format!("{:>6.1}", rss as f64 / 1e6))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(format!("{:>7.0}", node.mean_cycle_us)),
+            Cell::from(format!("{:>6}", node.errors)).style(if node.errors > 0 {
+                level_style(Level::Warning)
+            } else {
+                Style::default()
+            }),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(14),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ],
+    )
+    .header(
+        Row::new([
+            "NODE", "STATE", " UP s", "HB", " CPU%", "RSS MB", "CYCLEus", "ERRORS",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::bordered().title(" Nodes "));
+    frame.render_widget(table, area);
+}
+
+fn draw_alarms(frame: &mut Frame, area: Rect, telemetry: &Telemetry, now: Instant) {
+    let items: Vec<ListItem> = telemetry
+        .alarms
+        .iter()
+        .map(|(name, alarm)| {
+            let (label, level) = match alarm.severity {
+                sensors::AlarmSeverity::Critical => ("CRIT", Level::Critical),
+                sensors::AlarmSeverity::Warning => ("WARN", Level::Warning),
+                _ => ("INFO", Level::Info),
+            };
+            let limit = match alarm.kind {
+                sensors::AlarmKind::BelowMin => "<",
+                sensors::AlarmKind::AboveMax => ">",
+                _ => "rate >",
+            };
+            ListItem::new(format!(
+                "{} {} {:.3} {} {:.3} for {}",
+                label,
+                name,
+                alarm.value,
+                limit,
+                alarm.limit,
+                age(alarm.since, now)
+            ))
+            .style(level_style(level))
+        })
+        .collect();
+    let title = format!(" Alarms ({}) ", items.len());
+    frame.render_widget(List::new(items).block(Block::bordered().title(title)), area);
+}
+
+fn draw_events(frame: &mut Frame, area: Rect, telemetry: &Telemetry, now: Instant) {
+    let items: Vec<ListItem> = telemetry
+        .events
+        .iter()
+        .take(area.height as usize)
+        .map(|event| {
+            let count = match event.count {
+                1 => String::new(),
+                count => format!(" (x{})", count),
+            };
+            ListItem::new(format!("{:>5} {}{}", age(event.at, now), event.text, count))
+                .style(level_style(event.level))
+        })
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title(" Events ")),
+        area,
+    );
+}
@@ -0,0 +1,186 @@
+// Rows as InfluxDB line protocol, written to its v2 HTTP write endpoint, which InfluxDB 3
+// and InfluxDB 1.8 and later serve as well; 1.x takes DATABASE/RETENTION_POLICY as the
+// bucket and USER:PASSWORD as the token. Each row is a point of the measurement tagged
+// with its key, with a field per value: members of objects and elements of arrays are
+// named by their path, e.g. attitude.x or sensors.0.key, nulls are left out, and a value
+// that is not an object is the field "value".
+use crate::writer::{Error, Row};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Client, StatusCode, Url};
+use serde_json::Value;
+use std::error::Error as _;
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+// How long a write may take before it is tried again.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Influx {
+    client: Client,
+    base: Url,
+    url: Url,
+    token: Option<String>,
+    measurement: String,
+}
+
+impl Influx {
+    pub fn new(
+        mut base: Url,
+        org: &str,
+        bucket: &str,
+        token: Option<String>,
+        measurement: &str,
+    ) -> Result<Self, String> {
+        // Joined onto as a directory, so an InfluxDB behind a path prefix keeps it.
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        let mut url = base.join("api/v2/write").map_err(|e| e.to_string())?;
+        url.query_pairs_mut()
+            .append_pair("org", org)
+            .append_pair("bucket", bucket)
+            .append_pair("precision", "ns");
+        let client = Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(Influx {
+            client,
+            base,
+            url,
+            token,
+            measurement: measurement.to_string(),
+        })
+    }
+
+    pub async fn write(&mut self, rows: &[Row]) -> Result<(), Error> {
+        let body = lines(&self.measurement, rows);
+        if body.is_empty() {
+            return Ok(());
+        }
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(body);
+        if let Some(token) = &self.token {
+            request = request.header(AUTHORIZATION, format!("Token {}", token));
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Unavailable(describe(e)))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let text = response.text().await.unwrap_or_default();
+        let message = format!("{}: {}", status, text.trim());
+        if status.is_server_error()
+            || status == StatusCode::TOO_MANY_REQUESTS
+            || status == StatusCode::REQUEST_TIMEOUT
+        {
+            Err(Error::Unavailable(message))
+        } else {
+            Err(Error::Refused(message))
+        }
+    }
+}
+
+impl fmt::Display for Influx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "InfluxDB at {}", self.base)
+    }
+}
+
+// Reqwest's errors say what failed, e.g. sending the request, and why only in their sources.
+fn describe(e: reqwest::Error) -> String {
+    let e = e.without_url();
+    let mut text = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        let _ = write!(text, ": {}", cause);
+        source = cause.source();
+    }
+    text
+}
+
+// The rows as lines of line protocol, leaving out those without a field.
+fn lines(measurement: &str, rows: &[Row]) -> String {
+    let mut out = String::new();
+    let mut fields = Vec::new();
+    for row in rows {
+        fields.clear();
+        match &row.value {
+            Value::Object(_) | Value::Array(_) => flatten("", &row.value, &mut fields),
+            value => flatten("value", value, &mut fields),
+        }
+        if fields.is_empty() {
+            continue;
+        }
+        let _ = write!(out, "{},key={} ", measurement, escape(&row.key));
+        for (i, (name, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{}={}", escape(name), value);
+        }
+        let _ = writeln!(out, " {}", row.time.as_nanos());
+    }
+    out
+}
+
+fn flatten(name: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    let member = |key: &str| {
+        if name.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", name, key)
+        }
+    };
+    match value {
+        Value::Null => {}
+        Value::Bool(value) => fields.push((name.to_string(), value.to_string())),
+        // InfluxDB fixes a field's type at its first write, so integers are written as
+        // integers, and floats without a suffix even when they are whole.
+        Value::Number(number) => {
+            let value = if let Some(value) = number.as_i64() {
+                format!("{}i", value)
+            } else if let Some(value) = number.as_u64() {
+                format!("{}u", value)
+            } else {
+                number.to_string()
+            };
+            fields.push((name.to_string(), value));
+        }
+        Value::String(text) => {
+            let text = text
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            fields.push((name.to_string(), format!("\"{}\"", text)));
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                flatten(&member(&i.to_string()), value, fields);
+            }
+        }
+        Value::Object(members) => {
+            for (key, value) in members {
+                flatten(&member(key), value, fields);
+            }
+        }
+    }
+}
+
+// Tag values and field names escape commas, equals signs, and spaces with a backslash.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, ',' | '=' | ' ') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,111 @@
+// The task writing the queued rows to the database. Rows are written in batches, each once
+// it holds the batch size or its first row has waited the flush interval. A batch the
+// database cannot take for now, because it is unreachable, overloaded, or timed out, is
+// written again after a backoff that doubles from MIN_BACKOFF up to MAX_BACKOFF; the
+// queue fills up meanwhile, and the node drops the samples after it. A batch the database
+// refuses would be refused again, so it is dropped.
+use crate::influx::Influx;
+use crate::postgres::Timescale;
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, sleep, timeout_at};
+use tracing::{debug, info, warn};
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+// A decoded sample, as written to the database.
+pub struct Row {
+    pub key: String,
+    // Since the Unix epoch.
+    pub time: Duration,
+    pub value: Value,
+}
+
+pub enum Error {
+    // The batch may be written again later.
+    Unavailable(String),
+    // The database refused the batch.
+    Refused(String),
+}
+
+pub enum Database {
+    Influx(Influx),
+    Timescale(Timescale),
+}
+
+impl Database {
+    async fn write(&mut self, rows: &[Row]) -> Result<(), Error> {
+        match self {
+            Database::Influx(influx) => influx.write(rows).await,
+            Database::Timescale(timescale) => timescale.write(rows).await,
+        }
+    }
+}
+
+impl fmt::Display for Database {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Database::Influx(influx) => influx.fmt(f),
+            Database::Timescale(timescale) => timescale.fmt(f),
+        }
+    }
+}
+
+// Writes the rows until the queue is closed and emptied.
+pub fn spawn(
+    mut database: Database,
+    mut rows: mpsc::Receiver<Row>,
+    batch_size: usize,
+    flush: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut batch = Vec::with_capacity(batch_size);
+        while let Some(row) = rows.recv().await {
+            batch.push(row);
+            let deadline = Instant::now() + flush;
+            while batch.len() < batch_size {
+                match timeout_at(deadline, rows.recv()).await {
+                    Ok(Some(row)) => batch.push(row),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+            write(&mut database, &batch).await;
+            batch.clear();
+        }
+    })
+}
+
+async fn write(database: &mut Database, batch: &[Row]) {
+    let mut backoff = MIN_BACKOFF;
+    let mut failed = false;
+    loop {
+        match database.write(batch).await {
+            Ok(()) => {
+                if failed {
+                    info!("Writing to {} again", database);
+                }
+                debug!("Wrote {} rows", batch.len());
+                return;
+            }
+            Err(Error::Refused(e)) => {
+                warn!("{} refused {} rows: {}", database, batch.len(), e);
+                return;
+            }
+            Err(Error::Unavailable(e)) => {
+                warn!(
+                    "{}: {}; writing again in {} s",
+                    database,
+                    e,
+                    backoff.as_secs()
+                );
+                failed = true;
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use common::config;
+use common::qos::{PriorityConfig, QosConfig};
+use keyspace::keys;
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+use zenoh::key_expr::KeyExpr;
+
+// Streams used when no config file is given: events in full, the fused state and sensors
+// at downlink rates, within 64 kbit/s.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+// How many of a stream's samples go down.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rate {
+    // Every Nth sample, starting with the first.
+    Every(u64),
+    // A sample at most once per period.
+    Period(Duration),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct StreamConfig {
+    // Key expression of the samples to republish; each key it matches is downsampled on
+    // its own.
+    pub key: String,
+    // Republish every Nth sample.
+    pub every: Option<u64>,
+    // Republish at most this many samples per second.
+    pub rate_hz: Option<f64>,
+    // Order the stream's samples take the downlink in when it has a budget.
+    #[serde(default = "default_priority")]
+    pub priority: PriorityConfig,
+    // Whether samples the budget has no room for wait for a later frame rather than being
+    // dropped.
+    #[serde(default)]
+    pub defer: bool,
+}
+
+fn default_priority() -> PriorityConfig {
+    PriorityConfig::Data
+}
+
+impl StreamConfig {
+    pub fn rate(&self) -> Rate {
+        match (self.every, self.rate_hz) {
+            (Some(every), _) => Rate::Every(every),
+            (None, Some(rate_hz)) => Rate::Period(Duration::from_secs_f64(1.0 / rate_hz)),
+            (None, None) => unreachable!("validated to have a rate"),
+        }
+    }
+}
+
+// What the downlink carries. Without a budget, samples go down as soon as they are kept.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BudgetConfig {
+    // Bits per second of payload and key.
+    pub bps: u64,
+    // Most bytes of deferred samples held while the downlink is saturated; beyond it the
+    // oldest of the least urgent are dropped.
+    #[serde(default = "default_backlog_bytes")]
+    pub backlog_bytes: usize,
+}
+
+fn default_backlog_bytes() -> usize {
+    1 << 20
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    pub streams: Vec<StreamConfig>,
+    pub budget: Option<BudgetConfig>,
+    #[serde(default)]
+    pub qos: QosConfig,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.qos.validate()?;
+        if self.streams.is_empty() {
+            return Err("no streams configured".to_string());
+        }
+        if self.budget.as_ref().is_some_and(|budget| budget.bps == 0) {
+            return Err("budget.bps must be positive".to_string());
+        }
+        let downlink = KeyExpr::new(keys::all_downlink()).expect("checked by key!");
+        for (i, stream) in self.streams.iter().enumerate() {
+            let key = &stream.key;
+            let key_expr = KeyExpr::new(key.as_str()).map_err(|e| format!("{}: {}", key, e))?;
+            // The downsampler would receive what it republishes and republish it again.
+            if key_expr.intersects(&downlink) {
+                return Err(format!("{}: cannot downsample {}", key, keys::DOWNLINK));
+            }
+            if self.streams[..i].iter().any(|other| &other.key == key) {
+                return Err(format!("stream {} configured twice", key));
+            }
+            match (stream.every, stream.rate_hz) {
+                (Some(every), None) if every > 0 => {}
+                (None, Some(rate_hz)) if rate_hz.is_finite() && rate_hz > 0.0 => {}
+                (Some(_), Some(_)) | (None, None) => {
+                    return Err(format!("{}: give one of every and rate_hz", key));
+                }
+                _ => return Err(format!("{}: every and rate_hz must be positive", key)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let config: Config =
+            toml::from_str(DEFAULT_CONFIG).expect("Built-in downsampler config is invalid.");
+        config
+            .validate()
+            .expect("Built-in downsampler config is invalid.");
+        config
+    }
+}
@@ -0,0 +1,46 @@
+use crate::config::Rate;
+use std::time::Instant;
+
+// Part of a period a sample may arrive early and still be kept, so the jitter of a source
+// publishing at a multiple of the rate does not make every other sample it keeps miss the
+// period, halving the rate.
+const SLACK: f64 = 0.1;
+
+// Decides which samples of one key are republished.
+pub struct Decimator {
+    rate: Rate,
+    // Samples seen since the first, for Rate::Every.
+    seen: u64,
+    // When the latest sample kept arrived, for Rate::Period.
+    last: Option<Instant>,
+}
+
+impl Decimator {
+    pub fn new(rate: Rate) -> Self {
+        Decimator {
+            rate,
+            seen: 0,
+            last: None,
+        }
+    }
+
+    // Whether the sample that arrived at now is kept.
+    pub fn keep(&mut self, now: Instant) -> bool {
+        match self.rate {
+            Rate::Every(every) => {
+                let keep = self.seen.is_multiple_of(every);
+                self.seen += 1;
+                keep
+            }
+            Rate::Period(period) => {
+                let keep = self
+                    .last
+                    .is_none_or(|last| now - last >= period.mul_f64(1.0 - SLACK));
+                if keep {
+                    self.last = Some(now);
+                }
+                keep
+            }
+        }
+    }
+}
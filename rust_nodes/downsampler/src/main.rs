@@ -0,0 +1,307 @@
+mod config;
+mod decimator;
+mod scheduler;
+
+use clap::Parser;
+use command::NackReason;
+use common::qos::{PriorityConfig, PublisherBuilderQosExt, QosConfig};
+use common::sequence::SequencedPublisher;
+use config::{Config, Rate};
+use decimator::Decimator;
+use keyspace::keys;
+use node_framework::{CommandRequest, Context, Event, Node, NodeArgs, SubscriptionId};
+use scheduler::{Pending, Scheduler};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use zenoh::key_expr::OwnedKeyExpr;
+use zenoh::sample::{Sample, SampleKind};
+
+#[derive(Parser)]
+#[command(
+    about = "Republishes telemetry under downlink/ at the reduced rates and within the bit-rate budget of its config, reloaded on a reload command"
+)]
+struct Args {
+    /// Streams to downsample (TOML or JSON). Defaults to the flight phase, events, and
+    /// alarms in full, the fused state at 10 Hz, and the sensors at 1 Hz, within 64 kbit/s.
+    config: Option<PathBuf>,
+    /// Period the downlink is scheduled at when the config gives a budget, in milliseconds.
+    #[arg(long, default_value_t = 100)]
+    frame_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// A configured stream, as samples are matched against it.
+#[derive(Clone, PartialEq)]
+struct Stream {
+    key: OwnedKeyExpr,
+    rate: Rate,
+    priority: PriorityConfig,
+    defer: bool,
+}
+
+// One key being republished, with the samples of it already seen.
+struct Output {
+    // The stream the key is downsampled by.
+    stream: Stream,
+    decimator: Decimator,
+    publisher: SequencedPublisher<'static>,
+}
+
+struct Downsampler {
+    config_path: Option<PathBuf>,
+    // Config the node starts from, until init applies it.
+    config: Option<Config>,
+    frame: Duration,
+    // The configured streams, in config order.
+    streams: Vec<Stream>,
+    // Key expression of every subscription so far. The Context cannot undeclare a single
+    // subscription, so a stream dropped by a reload stays subscribed and its samples are
+    // ignored.
+    subscriptions: HashMap<SubscriptionId, String>,
+    outputs: HashMap<String, Output>,
+    // Samples waiting for the downlink, if the config gives it a budget.
+    scheduler: Option<Scheduler>,
+    // Samples dropped since the downlink saturated, while it is.
+    saturated: Option<u64>,
+    qos: QosConfig,
+}
+
+impl Downsampler {
+    // The first stream matching key, if any.
+    fn stream(&self, key: &str) -> Option<&Stream> {
+        let key = OwnedKeyExpr::try_from(key).ok()?;
+        self.streams.iter().find(|stream| stream.key.includes(&key))
+    }
+
+    // Starts downsampling the streams of config within its budget, subscribing to the new
+    // streams. Keys whose stream changed start over; keys no stream matches any more are
+    // no longer republished.
+    async fn apply(&mut self, ctx: &mut Context, config: Config) -> zenoh::Result<()> {
+        self.streams = config
+            .streams
+            .iter()
+            .map(|stream| {
+                Ok(Stream {
+                    key: OwnedKeyExpr::try_from(stream.key.clone())?,
+                    rate: stream.rate(),
+                    priority: stream.priority,
+                    defer: stream.defer,
+                })
+            })
+            .collect::<zenoh::Result<_>>()?;
+        for stream in &config.streams {
+            if !self.subscriptions.values().any(|key| key == &stream.key) {
+                let id = ctx.subscribe(&stream.key).await?;
+                self.subscriptions.insert(id, stream.key.clone());
+            }
+        }
+        let keys: Vec<String> = self.outputs.keys().cloned().collect();
+        for key in keys {
+            match self.stream(&key).cloned() {
+                Some(stream) => {
+                    let output = self.outputs.get_mut(&key).expect("key just listed");
+                    if output.stream != stream {
+                        output.decimator = Decimator::new(stream.rate);
+                        output.stream = stream;
+                    }
+                }
+                None => {
+                    let output = self.outputs.remove(&key).expect("key just listed");
+                    output.publisher.undeclare().await?;
+                }
+            }
+        }
+        match config.budget {
+            Some(budget) => {
+                if let Some(scheduler) = &mut self.scheduler {
+                    scheduler.set_budget(budget);
+                } else {
+                    self.scheduler = Some(Scheduler::new(budget, self.frame));
+                }
+            }
+            None => {
+                // What was waiting for room goes down now.
+                if let Some(mut scheduler) = self.scheduler.take() {
+                    for pending in scheduler.drain() {
+                        self.publish(pending).await?;
+                    }
+                }
+                self.saturated = None;
+            }
+        }
+        Ok(())
+    }
+
+    // Republishes the sample if its key is due one, or queues it for the downlink if it
+    // has a budget. Subscriptions whose key expressions overlap each receive it, so it is
+    // only taken from the one of its stream.
+    async fn on_sample(
+        &mut self,
+        ctx: &Context,
+        id: SubscriptionId,
+        sample: &Sample,
+    ) -> zenoh::Result<()> {
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = sample.key_expr().as_str();
+        let subscription = &self.subscriptions[&id];
+        if let Some(output) = self.outputs.get(key) {
+            if output.stream.key.as_str() != subscription {
+                return Ok(());
+            }
+        } else {
+            let Some(stream) = self.stream(key).cloned() else {
+                return Ok(());
+            };
+            if stream.key.as_str() != subscription {
+                return Ok(());
+            }
+            let downlink = keys::downlink(key);
+            let publisher = ctx
+                .session()
+                .declare_publisher(downlink.clone())
+                .encoding(sample.encoding().clone())
+                .qos(ctx.qos(), &downlink)
+                .await?;
+            let output = Output {
+                decimator: Decimator::new(stream.rate),
+                stream,
+                // Numbered afresh, so the ground counts what the downlink lost rather than
+                // the samples left out here.
+                publisher: ctx.sequenced(publisher),
+            };
+            self.outputs.insert(key.to_string(), output);
+        }
+        let output = self.outputs.get_mut(key).expect("inserted above");
+        if !output.decimator.keep(Instant::now()) {
+            return Ok(());
+        }
+        let pending = Pending::new(key, sample.payload().clone(), output.stream.defer);
+        match &mut self.scheduler {
+            Some(scheduler) => {
+                scheduler.push(output.stream.priority, pending);
+                Ok(())
+            }
+            None => output.publisher.put(pending.payload).await,
+        }
+    }
+
+    // Sends the samples the budget has room for this frame.
+    async fn on_frame(&mut self) -> zenoh::Result<()> {
+        let Some(scheduler) = &mut self.scheduler else {
+            return Ok(());
+        };
+        let (sent, shortfall) = scheduler.frame();
+        for pending in sent {
+            self.publish(pending).await?;
+        }
+        match self.saturated {
+            None if shortfall.dropped > 0 || shortfall.deferred > 0 => {
+                info!("Downlink saturated, deferring and dropping the least urgent samples");
+                self.saturated = Some(shortfall.dropped);
+            }
+            Some(dropped) if shortfall.dropped == 0 && shortfall.deferred == 0 => {
+                info!("Downlink clear after dropping {} samples", dropped);
+                self.saturated = None;
+            }
+            Some(dropped) => self.saturated = Some(dropped + shortfall.dropped),
+            None => {}
+        }
+        Ok(())
+    }
+
+    async fn publish(&self, pending: Pending) -> zenoh::Result<()> {
+        // The key's output is gone if a reload dropped its stream.
+        match self.outputs.get(&pending.key) {
+            Some(output) => output.publisher.put(pending.payload).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn reload(&mut self, ctx: &mut Context, request: CommandRequest) -> zenoh::Result<()> {
+        let Some(path) = self.config_path.clone() else {
+            return request
+                .nack(NackReason::Rejected, "started without a config file")
+                .await;
+        };
+        let config = match Config::load(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Not reloading {}: {}", path.display(), e);
+                return request.nack(NackReason::Failed, e).await;
+            }
+        };
+        let streams = config.streams.len();
+        self.apply(ctx, config).await?;
+        info!("Reloaded {} streams from {}", streams, path.display());
+        request.ack(format!("{} streams", streams)).await
+    }
+}
+
+impl Node for Downsampler {
+    const NAME: &'static str = "downsampler";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.set_qos(self.qos.clone());
+        let config = self.config.take().unwrap_or_default();
+        self.apply(ctx, config).await?;
+        ctx.register_command("reload").await?;
+        // Runs without a budget too, so a reload can give the downlink one.
+        ctx.add_timer(self.frame);
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(id, sample) => self.on_sample(ctx, id, &sample).await,
+            Event::Timer(_) => self.on_frame().await,
+            Event::Command(request) => self.reload(ctx, request).await,
+            Event::Query(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        for (_, output) in self.outputs.drain() {
+            output.publisher.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if args.frame_ms == 0 {
+        error!("--frame-ms must be positive");
+        return ExitCode::FAILURE;
+    }
+    let config = match &args.config {
+        Some(path) => match Config::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid downsampler config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Config::default(),
+    };
+
+    let node = Downsampler {
+        config_path: args.config,
+        qos: config.qos.clone(),
+        config: Some(config),
+        frame: Duration::from_millis(args.frame_ms),
+        streams: Vec::new(),
+        subscriptions: HashMap::new(),
+        outputs: HashMap::new(),
+        scheduler: None,
+        saturated: None,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,138 @@
+use crate::config::BudgetConfig;
+use common::qos::PriorityConfig;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+use zenoh::bytes::ZBytes;
+
+// A sample kept for the downlink, waiting for room on it.
+pub struct Pending {
+    // Key the sample arrived on, not yet under downlink/.
+    pub key: String,
+    pub payload: ZBytes,
+    defer: bool,
+}
+
+impl Pending {
+    pub fn new(key: &str, payload: ZBytes, defer: bool) -> Self {
+        Pending {
+            key: key.to_string(),
+            payload,
+            defer,
+        }
+    }
+
+    // What the sample costs on the link, roughly: its key and payload, leaving out the
+    // framing.
+    fn size(&self) -> usize {
+        self.key.len() + self.payload.len()
+    }
+}
+
+// What was left out of the downlink in one frame.
+pub struct Shortfall {
+    // Samples dropped at the end of the frame or to bound the backlog.
+    pub dropped: u64,
+    // Deferred samples still waiting.
+    pub deferred: usize,
+}
+
+// Sends kept samples down frame by frame within a bit-rate budget: the most urgent first
+// and, within a priority, in the order they were kept. What a frame has no room for is
+// dropped, unless its stream defers it to a later frame.
+pub struct Scheduler {
+    budget: BudgetConfig,
+    frame: Duration,
+    // Bytes the link can still take. A sample larger than what was left takes it below
+    // zero, and later frames pay the excess back, so samples larger than a frame still go
+    // down without the budget being exceeded on average.
+    credit: f64,
+    queues: BTreeMap<PriorityConfig, VecDeque<Pending>>,
+    // Bytes of the samples queued.
+    backlog: usize,
+    // Dropped to bound the backlog since the last frame.
+    dropped: u64,
+}
+
+impl Scheduler {
+    pub fn new(budget: BudgetConfig, frame: Duration) -> Self {
+        Scheduler {
+            budget,
+            frame,
+            credit: 0.0,
+            queues: BTreeMap::new(),
+            backlog: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn set_budget(&mut self, budget: BudgetConfig) {
+        self.budget = budget;
+    }
+
+    // Queues a sample for the next frame. While the backlog is over its bound, the oldest
+    // samples of the least urgent priority are dropped to make room.
+    pub fn push(&mut self, priority: PriorityConfig, pending: Pending) {
+        self.backlog += pending.size();
+        self.queues.entry(priority).or_default().push_back(pending);
+        while self.backlog > self.budget.backlog_bytes {
+            let Some(mut queue) = self.queues.last_entry() else {
+                break;
+            };
+            if let Some(oldest) = queue.get_mut().pop_front() {
+                self.backlog -= oldest.size();
+                self.dropped += 1;
+            }
+            if queue.get().is_empty() {
+                queue.remove();
+            }
+        }
+    }
+
+    // The samples sent this frame, in order, and what was left out.
+    pub fn frame(&mut self) -> (Vec<Pending>, Shortfall) {
+        let per_frame = self.budget.bps as f64 / 8.0 * self.frame.as_secs_f64();
+        self.credit = (self.credit + per_frame).min(per_frame);
+        let mut sent = Vec::new();
+        while self.credit > 0.0 {
+            let Some(mut queue) = self.queues.first_entry() else {
+                break;
+            };
+            let pending = queue
+                .get_mut()
+                .pop_front()
+                .expect("empty queues are removed");
+            if queue.get().is_empty() {
+                queue.remove();
+            }
+            self.backlog -= pending.size();
+            self.credit -= pending.size() as f64;
+            sent.push(pending);
+        }
+
+        let mut shortfall = Shortfall {
+            dropped: std::mem::take(&mut self.dropped),
+            deferred: 0,
+        };
+        for queue in self.queues.values_mut() {
+            queue.retain(|pending| {
+                if !pending.defer {
+                    self.backlog -= pending.size();
+                    shortfall.dropped += 1;
+                }
+                pending.defer
+            });
+            shortfall.deferred += queue.len();
+        }
+        self.queues.retain(|_, queue| !queue.is_empty());
+        (sent, shortfall)
+    }
+
+    // Every sample queued, most urgent first, for sending without a budget.
+    pub fn drain(&mut self) -> Vec<Pending> {
+        self.backlog = 0;
+        std::mem::take(&mut self.queues)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+}
@@ -0,0 +1,261 @@
+use crate::vehicle::Vehicle;
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use tracing::info;
+
+pub const GRAVITY: f64 = 9.81;
+// Exponential atmosphere.
+const SEA_LEVEL_DENSITY: f64 = 1.225;
+const SCALE_HEIGHT: f64 = 8500.0;
+// Below this airspeed aerodynamic forces are negligible and the angle of attack undefined.
+const MIN_AIRSPEED: f64 = 1.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    // Waiting on the rail for launch.
+    Pad,
+    // Sliding along the rail; motion is constrained to the rail axis.
+    Rail,
+    // Unconstrained 6-DOF flight.
+    Free,
+    // Back on the ground and at rest.
+    Landed,
+}
+
+// Rigid-body state. Position and velocity are east-north-up relative to the pad, attitude
+// rotates body vectors into that frame, and angular rate is in the body frame.
+#[derive(Clone, Copy, Debug)]
+pub struct BodyState {
+    pub position: Vector3<f64>,
+    pub velocity: Vector3<f64>,
+    pub attitude: Quaternion<f64>,
+    pub angular_rate: Vector3<f64>,
+    // Impulse delivered so far, which sets how much propellant has burned.
+    pub impulse: f64,
+}
+
+// Time derivative of a BodyState.
+struct Derivative {
+    velocity: Vector3<f64>,
+    acceleration: Vector3<f64>,
+    attitude: Quaternion<f64>,
+    angular_accel: Vector3<f64>,
+    thrust: f64,
+}
+
+impl BodyState {
+    fn advanced(&self, d: &Derivative, h: f64) -> BodyState {
+        BodyState {
+            position: self.position + d.velocity * h,
+            velocity: self.velocity + d.acceleration * h,
+            attitude: self.attitude + d.attitude * h,
+            angular_rate: self.angular_rate + d.angular_accel * h,
+            impulse: self.impulse + d.thrust * h,
+        }
+    }
+}
+
+// Rail-launched vehicle integrated with fixed-step RK4. Forces are thrust along the body z
+// axis, gravity, axial drag, and a normal force at the center of pressure that weathercocks
+// the vehicle into the airflow; moments add pitch, yaw, and roll damping plus a fin-cant
+// roll moment. Wind is steady and horizontal; aerodynamic forces follow the velocity
+// relative to the air.
+pub struct Simulation {
+    vehicle: Vehicle,
+    rail_length: f64,
+    rail_axis: Vector3<f64>,
+    // East-north-up wind velocity in m/s.
+    wind: Vector3<f64>,
+    pub phase: Phase,
+    // Seconds since launch.
+    pub time: f64,
+    pub state: BodyState,
+    // World-frame kinematic acceleration and thrust at the current state.
+    pub acceleration: Vector3<f64>,
+    pub thrust: f64,
+    // Multiplier on the thrust curve, for motors that underperform or cut out.
+    pub thrust_scale: f64,
+}
+
+impl Simulation {
+    pub fn new(
+        vehicle: Vehicle,
+        rail_length: f64,
+        elevation: f64,
+        azimuth: f64,
+        wind: Vector3<f64>,
+    ) -> Self {
+        let rail_axis = Vector3::new(
+            elevation.cos() * azimuth.cos(),
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+        );
+        let attitude = UnitQuaternion::rotation_between(&Vector3::z(), &rail_axis)
+            .unwrap_or_else(UnitQuaternion::identity);
+        Simulation {
+            vehicle,
+            rail_length,
+            rail_axis,
+            wind,
+            phase: Phase::Pad,
+            time: 0.0,
+            state: BodyState {
+                position: Vector3::zeros(),
+                velocity: Vector3::zeros(),
+                attitude: attitude.into_inner(),
+                angular_rate: Vector3::zeros(),
+                impulse: 0.0,
+            },
+            acceleration: Vector3::zeros(),
+            thrust: 0.0,
+            thrust_scale: 1.0,
+        }
+    }
+
+    // Ignites the motor. Has no effect once the vehicle has left the pad.
+    pub fn launch(&mut self) {
+        if self.phase == Phase::Pad {
+            self.phase = Phase::Rail;
+        }
+    }
+
+    pub fn attitude(&self) -> UnitQuaternion<f64> {
+        UnitQuaternion::new_normalize(self.state.attitude)
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.vehicle.mass(self.state.impulse)
+    }
+
+    // What an ideal body-fixed accelerometer reads: kinematic acceleration minus gravity,
+    // in the body frame.
+    pub fn specific_force(&self) -> Vector3<f64> {
+        self.attitude().inverse() * (self.acceleration + Vector3::new(0.0, 0.0, GRAVITY))
+    }
+
+    // What an ideal body-fixed magnetometer reads: Earth's field in the body frame.
+    pub fn magnetic_field(&self) -> Vector3<f64> {
+        self.attitude().inverse() * Vector3::from(sim_common::devices::MAGNETIC_FIELD)
+    }
+
+    // Advances the simulation by dt seconds.
+    pub fn step(&mut self, dt: f64) {
+        if matches!(self.phase, Phase::Rail | Phase::Free) {
+            let (s, t) = (self.state, self.time);
+            let k1 = self.derivative(&s, t);
+            let k2 = self.derivative(&s.advanced(&k1, dt / 2.0), t + dt / 2.0);
+            let k3 = self.derivative(&s.advanced(&k2, dt / 2.0), t + dt / 2.0);
+            let k4 = self.derivative(&s.advanced(&k3, dt), t + dt);
+            let sum = Derivative {
+                velocity: k1.velocity + (k2.velocity + k3.velocity) * 2.0 + k4.velocity,
+                acceleration: k1.acceleration
+                    + (k2.acceleration + k3.acceleration) * 2.0
+                    + k4.acceleration,
+                attitude: k1.attitude + (k2.attitude + k3.attitude) * 2.0 + k4.attitude,
+                angular_accel: k1.angular_accel
+                    + (k2.angular_accel + k3.angular_accel) * 2.0
+                    + k4.angular_accel,
+                thrust: k1.thrust + (k2.thrust + k3.thrust) * 2.0 + k4.thrust,
+            };
+            self.state = s.advanced(&sum, dt / 6.0);
+            self.state.attitude = self.attitude().into_inner();
+        }
+        self.time += dt;
+        self.update_phase();
+
+        match self.phase {
+            Phase::Rail | Phase::Free => {
+                let d = self.derivative(&self.state, self.time);
+                self.acceleration = d.acceleration;
+                self.thrust = d.thrust;
+            }
+            Phase::Pad | Phase::Landed => {
+                self.acceleration = Vector3::zeros();
+                self.thrust = 0.0;
+            }
+        }
+    }
+
+    fn update_phase(&mut self) {
+        let next = match self.phase {
+            Phase::Rail if self.state.position.norm() >= self.rail_length => Phase::Free,
+            Phase::Free if self.state.position.z <= 0.0 && self.state.velocity.z < 0.0 => {
+                self.state.position.z = 0.0;
+                self.state.velocity = Vector3::zeros();
+                self.state.angular_rate = Vector3::zeros();
+                Phase::Landed
+            }
+            phase => phase,
+        };
+        if next != self.phase {
+            info!(
+                "{:?} -> {:?} at t={:.2}s, altitude {:.1} m",
+                self.phase, next, self.time, self.state.position.z
+            );
+            self.phase = next;
+        }
+    }
+
+    fn derivative(&self, s: &BodyState, t: f64) -> Derivative {
+        let v = &self.vehicle;
+        let r = UnitQuaternion::new_normalize(s.attitude);
+        let mass = v.mass(s.impulse);
+        let thrust = v.thrust.thrust(t) * self.thrust_scale;
+
+        let mut force = r * Vector3::new(0.0, 0.0, thrust);
+        let mut moment = Vector3::zeros();
+        let air_velocity = s.velocity - self.wind;
+        let speed = air_velocity.norm();
+        if speed > MIN_AIRSPEED {
+            let density = SEA_LEVEL_DENSITY * (-s.position.z.max(0.0) / SCALE_HEIGHT).exp();
+            let qbar_area = 0.5 * density * speed * speed * v.reference_area();
+            force -= air_velocity / speed * qbar_area * v.drag_coefficient;
+
+            // Small-angle normal force opposing the body-frame crossflow, applied at the
+            // center of pressure.
+            let airflow = r.inverse() * air_velocity;
+            let crossflow = Vector3::new(airflow.x, airflow.y, 0.0);
+            let normal = -crossflow / speed * qbar_area * v.normal_force_slope;
+            force += r * normal;
+            moment += Vector3::new(0.0, 0.0, -v.static_margin).cross(&normal);
+
+            let w = s.angular_rate;
+            let pitch = qbar_area * v.length * v.pitch_damping * v.length / (2.0 * speed);
+            moment += Vector3::new(w.x, w.y, 0.0) * pitch;
+            let d = v.diameter;
+            moment.z += qbar_area * d * (v.roll_forcing + v.roll_damping * w.z * d / (2.0 * speed));
+        }
+        let mut acceleration = force / mass + Vector3::new(0.0, 0.0, -GRAVITY);
+
+        let w = s.angular_rate;
+        let (angular_accel, attitude) = match self.phase {
+            Phase::Rail => {
+                // The rail carries all lateral load and keeps the vehicle from sliding back
+                // down before thrust exceeds weight.
+                let along = acceleration.dot(&self.rail_axis);
+                let along = if along < 0.0 && s.velocity.dot(&self.rail_axis) <= 0.0 {
+                    0.0
+                } else {
+                    along
+                };
+                acceleration = self.rail_axis * along;
+                (Vector3::zeros(), Quaternion::from_imag(Vector3::zeros()))
+            }
+            _ => {
+                // Euler's rotation equations with principal inertia.
+                let inertia = Vector3::from(v.inertia);
+                let iw = inertia.component_mul(&w);
+                let angular_accel = (moment - w.cross(&iw)).component_div(&inertia);
+                let attitude = s.attitude * Quaternion::from_imag(w) * 0.5;
+                (angular_accel, attitude)
+            }
+        };
+
+        Derivative {
+            velocity: s.velocity,
+            acceleration,
+            attitude,
+            angular_accel,
+            thrust,
+        }
+    }
+}
@@ -0,0 +1,277 @@
+mod dynamics;
+mod truth;
+mod vehicle;
+
+use clap::Parser;
+use common::matching::Matching;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use common::{encoding, shutdown_signal};
+use dynamics::{Phase, Simulation};
+use keyspace::keys;
+use nalgebra::{UnitQuaternion, Vector3};
+use node_framework::{Heartbeat, LogControl, NodeArgs, NodeError};
+use sim_common::{FlightClock, SensorArgs, SensorInputs, SimError};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{error, info, warn};
+use vehicle::{ThrustCurve, Vehicle};
+
+// Upper bound on the physics steps run per tick, so after a stall the simulation catches up
+// with the wall clock over several ticks instead of starving shutdown and truth publishing.
+const MAX_STEPS_PER_TICK: u32 = 1000;
+
+#[derive(Parser)]
+#[command(
+    about = "Simulates a 6-DOF rocket flight, publishing sensor samples on devices/* and truth on sim/truth"
+)]
+struct Args {
+    /// Thrust curve as a CSV file of "time_s,thrust_n" lines. Defaults to a built-in 3 s burn.
+    #[arg(long)]
+    thrust_curve: Option<PathBuf>,
+    /// Length of the launch rail in meters.
+    #[arg(long, default_value_t = 3.0)]
+    rail_length_m: f64,
+    /// Rail elevation above the horizon in degrees.
+    #[arg(long, default_value_t = 85.0)]
+    elevation_deg: f64,
+    /// Rail azimuth in degrees, counterclockwise from east.
+    #[arg(long, default_value_t = 30.0)]
+    azimuth_deg: f64,
+    /// Steady horizontal wind in m/s, as east and north components, comma separated.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "EAST,NORTH",
+        default_values_t = [0.0, 0.0],
+        allow_negative_numbers = true
+    )]
+    wind_mps: Vec<f64>,
+    /// Rotation of the sensor board relative to the body axes in degrees, as roll, pitch,
+    /// and yaw, comma separated. The IMUs, gyroscopes, and magnetometers measure in the
+    /// rotated frame, while fusion assumes they are aligned with the body.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "ROLL,PITCH,YAW",
+        default_values_t = [0.0, 0.0, 0.0],
+        allow_negative_numbers = true
+    )]
+    misalignment_deg: Vec<f64>,
+    /// Physics integration rate in Hz.
+    #[arg(long, default_value_t = 1000.0)]
+    physics_rate_hz: f64,
+    /// Rate truth is published at in Hz.
+    #[arg(long, default_value_t = 100.0)]
+    truth_rate_hz: f64,
+    /// Key expression the truth state is published on.
+    #[arg(long, default_value = keys::sim_truth())]
+    truth_key: String,
+    /// Launch this many seconds after startup instead of waiting for a launch command.
+    #[arg(long)]
+    launch_after_s: Option<f64>,
+    #[command(flatten)]
+    sensors: SensorArgs,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// The sensors' ideal readings, with the body-frame vectors seen in the frame of a sensor
+// board rotated by mounting.
+fn sensor_inputs(sim: &Simulation, mounting: &UnitQuaternion<f64>) -> SensorInputs {
+    let board = mounting.inverse();
+    SensorInputs {
+        specific_force: (board * sim.specific_force()).into(),
+        angular_rate: (board * sim.state.angular_rate).into(),
+        altitude: sim.state.position.z,
+        magnetic_field: (board * sim.magnetic_field()).into(),
+        position: sim.state.position.into(),
+        velocity: sim.state.velocity.into(),
+    }
+}
+
+fn build_vehicle(args: &Args) -> Result<Vehicle, String> {
+    let mut vehicle = Vehicle::default();
+    if let Some(path) = &args.thrust_curve {
+        vehicle.thrust = ThrustCurve::load(path)?;
+    }
+    for (name, rate) in [
+        ("--physics-rate-hz", args.physics_rate_hz),
+        ("--truth-rate-hz", args.truth_rate_hz),
+    ] {
+        if rate.is_nan() || rate <= 0.0 {
+            return Err(format!("{} must be positive", name));
+        }
+    }
+    for (name, values, count) in [
+        ("--wind-mps", &args.wind_mps, 2),
+        ("--misalignment-deg", &args.misalignment_deg, 3),
+    ] {
+        if values.len() != count || values.iter().any(|v| !v.is_finite()) {
+            return Err(format!("{} takes {} finite values", name, count));
+        }
+    }
+    args.sensors.validate()?;
+    Ok(vehicle)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    match run(args).await {
+        Ok(status) => status,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Flies the simulation until shutdown. Fails if it cannot start; failures in the teardown
+// are reported and make the status a failure.
+async fn run(mut args: Args) -> Result<ExitCode, SimError> {
+    let vehicle = build_vehicle(&args).map_err(SimError::Args)?;
+
+    args.node.zenoh.timestamping = args.sensors.publication_cache.is_some();
+    let session = args.node.zenoh.open().await.map_err(NodeError::from)?;
+    node_framework::crash::install(&session, args.node.name("dynamics_sim"), &args.node);
+    let mut sim = Simulation::new(
+        vehicle,
+        args.rail_length_m,
+        args.elevation_deg.to_radians(),
+        args.azimuth_deg.to_radians(),
+        Vector3::new(args.wind_mps[0], args.wind_mps[1], 0.0),
+    );
+    let [roll, pitch, yaw] = [0, 1, 2].map(|i| args.misalignment_deg[i].to_radians());
+    let mounting = UnitQuaternion::from_euler_angles(roll, pitch, yaw);
+    let clock = FlightClock::default();
+    let launch_subscriber = clock
+        .listen(&session)
+        .await
+        .map_err(NodeError::declare("launch subscriber"))?;
+    // Commanded thrust scale, which the next physics tick picks up.
+    let thrust_scale = Arc::new(Mutex::new(1.0));
+    let commanded_scale = thrust_scale.clone();
+    let thrust_subscriber = session
+        .declare_subscriber(keys::sim_thrust_scale())
+        .callback(move |sample| {
+            let scale = sample
+                .payload()
+                .try_to_string()
+                .ok()
+                .and_then(|text| text.trim().parse::<f64>().ok());
+            match scale {
+                Some(scale) if scale.is_finite() && scale >= 0.0 => {
+                    info!("Thrust scaled by {}", scale);
+                    *commanded_scale.lock().unwrap() = scale;
+                }
+                _ => warn!("Ignoring invalid thrust scale on {}", sample.key_expr()),
+            }
+        })
+        .await
+        .map_err(NodeError::declare("thrust scale subscriber"))?;
+    let truth_publisher = session
+        .declare_publisher(args.truth_key.clone())
+        .encoding(encoding::flatbuffer("sensors.SimTruth"))
+        .qos(&QosConfig::default(), &args.truth_key)
+        .await
+        .map_err(NodeError::declare("truth publisher"))?;
+    // Truth is only encoded while something subscribes to it.
+    let truth_matching = if args.sensors.always_publishes(&args.truth_key) {
+        Matching::always()
+    } else {
+        Matching::watch(&truth_publisher)
+            .await
+            .map_err(NodeError::declare("truth matching listener"))?
+    };
+
+    // The sensor tasks sample the state left by the latest physics step.
+    let inputs = Arc::new(Mutex::new(sensor_inputs(&sim, &mounting)));
+    let sensor_inputs_source = inputs.clone();
+    let tasks = args.sensors.spawn(
+        &session,
+        args.node.name("dynamics_sim"),
+        args.node.crc32c,
+        Arc::new(move || *sensor_inputs_source.lock().unwrap()),
+    );
+
+    let name = args.node.name("dynamics_sim");
+    let heartbeat = Heartbeat::start(&session, name, args.node.heartbeat_period())
+        .await
+        .map_err(|source| NodeError::Start {
+            node: name.to_string(),
+            source,
+        })?;
+    let _log_control = LogControl::start(&session, name, &args.node).await?;
+
+    match args.launch_after_s {
+        Some(delay) => clock.launch_after(Duration::from_secs_f64(delay.max(0.0))),
+        None => info!("Waiting for launch command on '{}'.", keys::launch()),
+    }
+
+    let step = 1.0 / args.physics_rate_hz;
+    let mut physics = interval(Duration::from_secs_f64(step));
+    physics.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut truth = interval(Duration::from_secs_f64(1.0 / args.truth_rate_hz));
+    truth.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            _ = physics.tick() => {
+                let started = Instant::now();
+                // Simulated time follows the wall clock since launch in fixed steps.
+                if let Some(elapsed) = clock.elapsed() {
+                    if sim.phase == Phase::Pad {
+                        sim.launch();
+                    }
+                    sim.thrust_scale = *thrust_scale.lock().unwrap();
+                    let mut steps = 0;
+                    while sim.time + step <= elapsed && steps < MAX_STEPS_PER_TICK {
+                        sim.step(step);
+                        steps += 1;
+                    }
+                    *inputs.lock().unwrap() = sensor_inputs(&sim, &mounting);
+                }
+                heartbeat.record(started.elapsed(), true);
+            }
+            _ = truth.tick(), if truth_matching.matching() => {
+                if let Err(e) = truth_publisher.put(truth::encode(&sim)).await {
+                    error!("Failed to publish truth: {}", e);
+                }
+            }
+        }
+    }
+
+    info!("Shutting down dynamics_sim...");
+    for task in &tasks {
+        task.abort();
+    }
+    let mut status = ExitCode::SUCCESS;
+    if let Err(e) = heartbeat.stop().await {
+        error!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = truth_publisher.undeclare().await {
+        error!("Failed to undeclare truth publisher: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = thrust_subscriber.undeclare().await {
+        error!("Failed to undeclare thrust scale subscriber: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = launch_subscriber.undeclare().await {
+        error!("Failed to undeclare launch subscriber: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = session.close().await {
+        error!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    Ok(status)
+}
@@ -0,0 +1,41 @@
+use crate::dynamics::Simulation;
+use nalgebra::Vector3;
+use sensors_rs::sensors;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Serializes the simulation state into a finished sensors::SimTruth FlatBuffer stamped
+// with the current wall-clock time.
+pub fn encode(sim: &Simulation) -> Vec<u8> {
+    let timestamp_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0);
+    let (roll, pitch, yaw) = sim.attitude().euler_angles();
+
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(160);
+    let position = vec3(&sim.state.position);
+    let velocity = vec3(&sim.state.velocity);
+    let acceleration = vec3(&sim.acceleration);
+    let attitude = vec3(&Vector3::new(roll, pitch, yaw));
+    let angular_rate = vec3(&sim.state.angular_rate);
+    let truth = sensors::SimTruth::create(
+        &mut builder,
+        &sensors::SimTruthArgs {
+            timestamp_us,
+            flight_time_s: sim.time as f32,
+            position: Some(&position),
+            velocity: Some(&velocity),
+            acceleration: Some(&acceleration),
+            attitude: Some(&attitude),
+            angular_rate: Some(&angular_rate),
+            mass: sim.mass() as f32,
+            thrust: sim.thrust as f32,
+        },
+    );
+    builder.finish(truth, None);
+    builder.finished_data().to_vec()
+}
+
+fn vec3(v: &Vector3<f64>) -> sensors::Vec3 {
+    sensors::Vec3::new(v.x as f32, v.y as f32, v.z as f32)
+}
@@ -0,0 +1,144 @@
+use std::path::Path;
+
+// Thrust over time since ignition, linearly interpolated between samples and zero outside
+// them.
+#[derive(Clone, Debug)]
+pub struct ThrustCurve {
+    points: Vec<(f64, f64)>,
+}
+
+impl ThrustCurve {
+    // Loads a curve from a CSV file of "time_s,thrust_n" lines. Blank lines and lines
+    // starting with '#' are ignored.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let mut points = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parsed = line.split_once(',').and_then(|(t, f)| {
+                Some((t.trim().parse::<f64>().ok()?, f.trim().parse::<f64>().ok()?))
+            });
+            match parsed {
+                Some(point) => points.push(point),
+                None => {
+                    return Err(format!(
+                        "{}:{}: expected \"time_s,thrust_n\"",
+                        path.display(),
+                        n + 1
+                    ));
+                }
+            }
+        }
+        Self::new(points)
+    }
+
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self, String> {
+        if points.len() < 2 {
+            return Err("thrust curve needs at least two points".to_string());
+        }
+        if points.windows(2).any(|w| w[1].0 <= w[0].0) {
+            return Err("thrust curve times must be strictly increasing".to_string());
+        }
+        if points
+            .iter()
+            .any(|&(t, f)| t < 0.0 || f.is_nan() || f < 0.0)
+        {
+            return Err("thrust curve times and thrusts must be non-negative".to_string());
+        }
+        Ok(ThrustCurve { points })
+    }
+
+    pub fn thrust(&self, t: f64) -> f64 {
+        let i = self.points.partition_point(|&(ti, _)| ti <= t);
+        if i == 0 || i == self.points.len() {
+            return 0.0;
+        }
+        let (t0, f0) = self.points[i - 1];
+        let (t1, f1) = self.points[i];
+        f0 + (f1 - f0) * (t - t0) / (t1 - t0)
+    }
+
+    pub fn total_impulse(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|w| (w[1].0 - w[0].0) * (w[0].1 + w[1].1) / 2.0)
+            .sum()
+    }
+}
+
+impl Default for ThrustCurve {
+    // Roughly a high-power K motor: 2 kN spike settling to 1.5 kN over a 3 s burn.
+    fn default() -> Self {
+        ThrustCurve::new(vec![
+            (0.0, 0.0),
+            (0.1, 2000.0),
+            (0.5, 1800.0),
+            (2.5, 1500.0),
+            (3.0, 0.0),
+        ])
+        .expect("Built-in thrust curve is invalid.")
+    }
+}
+
+// Mass, inertia, and aerodynamic properties of the simulated vehicle. Lengths are in
+// meters, with the body z axis pointing from tail to nose.
+#[derive(Clone, Debug)]
+pub struct Vehicle {
+    pub dry_mass: f64,
+    // Burned in proportion to the delivered impulse, so it is gone at burnout.
+    pub propellant_mass: f64,
+    // Principal moments of inertia about the body x, y, and z axes in kg m^2.
+    pub inertia: [f64; 3],
+    pub length: f64,
+    pub diameter: f64,
+    pub drag_coefficient: f64,
+    // Normal force coefficient slope per radian of angle of attack.
+    pub normal_force_slope: f64,
+    // Distance the center of pressure sits behind the center of mass.
+    pub static_margin: f64,
+    // Pitch/yaw and roll damping moment coefficients (negative is damping).
+    pub pitch_damping: f64,
+    pub roll_damping: f64,
+    // Roll moment coefficient from fin cant, which spins the vehicle up in flight.
+    pub roll_forcing: f64,
+    pub thrust: ThrustCurve,
+}
+
+impl Vehicle {
+    pub fn reference_area(&self) -> f64 {
+        std::f64::consts::PI * self.diameter * self.diameter / 4.0
+    }
+
+    pub fn mass(&self, burned_impulse: f64) -> f64 {
+        let total = self.thrust.total_impulse();
+        let burned = if total > 0.0 {
+            (burned_impulse / total).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.dry_mass + self.propellant_mass * (1.0 - burned)
+    }
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        Vehicle {
+            dry_mass: 20.0,
+            propellant_mass: 5.0,
+            inertia: [10.0, 10.0, 0.05],
+            length: 2.5,
+            diameter: 0.11,
+            drag_coefficient: 0.5,
+            normal_force_slope: 10.0,
+            static_margin: 0.3,
+            pitch_damping: -20.0,
+            roll_damping: -5.0,
+            roll_forcing: 0.002,
+            thrust: ThrustCurve::default(),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Launch,
+    Apogee,
+    Landing,
+}
+
+// What each event is detected on. Accelerations and velocities are vertical, positive
+// up, and accelerations kinematic.
+pub struct Config {
+    // Launch: vertical acceleration above this, in m/s², sustained over launch_window_s.
+    pub launch_acceleration: f32,
+    pub launch_window_s: f64,
+    // Apogee: vertical velocity positive over the first half of apogee_window_s and
+    // negative over the second.
+    pub apogee_window_s: f64,
+    // Landing: acceleration and speed below these, in m/s² and m/s, sustained over
+    // landing_window_s. Fusion only observes the vertical acceleration well, so the
+    // horizontal is left out.
+    pub landing_acceleration: f32,
+    pub landing_velocity: f32,
+    pub landing_window_s: f64,
+    // Share of a window's states that must bear an event out for it to be detected.
+    pub min_confidence: f32,
+}
+
+// The fields of one fused state the detector looks at, time in seconds.
+#[derive(Clone, Copy)]
+pub struct State {
+    pub time: f64,
+    pub altitude: Option<f32>,
+    pub vertical_velocity: Option<f32>,
+    pub vertical_acceleration: Option<f32>,
+}
+
+pub struct Detection {
+    pub kind: Kind,
+    // When the event happened, by fused state time.
+    pub time: f64,
+    pub confidence: f32,
+    pub altitude: Option<f32>,
+}
+
+// Detects launch, apogee, and landing in that order, then waits for the next launch.
+pub struct Detector {
+    config: Config,
+    next: Kind,
+    // The states within the current event's window, oldest first.
+    window: VecDeque<State>,
+    // Whether the window has filled once, i.e. states older than it were seen.
+    full: bool,
+}
+
+impl Detector {
+    pub fn new(config: Config) -> Self {
+        Detector {
+            config,
+            next: Kind::Launch,
+            window: VecDeque::new(),
+            full: false,
+        }
+    }
+
+    fn window_s(&self) -> f64 {
+        match self.next {
+            Kind::Launch => self.config.launch_window_s,
+            Kind::Apogee => self.config.apogee_window_s,
+            Kind::Landing => self.config.landing_window_s,
+        }
+    }
+
+    pub fn update(&mut self, state: State) -> Option<Detection> {
+        let start = state.time - self.window_s();
+        // A state older than the latest one means fused time went back, e.g. a replay
+        // restarted; what came before tells nothing about what follows.
+        if self
+            .window
+            .back()
+            .is_some_and(|last| last.time > state.time)
+        {
+            self.window.clear();
+            self.full = false;
+        }
+        self.window.push_back(state);
+        while self
+            .window
+            .front()
+            .is_some_and(|oldest| oldest.time < start)
+        {
+            self.window.pop_front();
+            self.full = true;
+        }
+        if !self.full {
+            return None;
+        }
+        let detection = match self.next {
+            Kind::Launch => self.launch(),
+            Kind::Apogee => self.apogee(start + self.window_s() / 2.0),
+            Kind::Landing => self.landing(),
+        }
+        .filter(|detection| detection.confidence >= self.config.min_confidence)?;
+        self.next = match self.next {
+            Kind::Launch => Kind::Apogee,
+            Kind::Apogee => Kind::Landing,
+            Kind::Landing => Kind::Launch,
+        };
+        self.window.clear();
+        self.full = false;
+        Some(detection)
+    }
+
+    // Share of states that bear an event out, counting those lacking the estimates as not.
+    fn share(states: &VecDeque<State>, bears_out: impl Fn(&State) -> bool) -> f32 {
+        if states.is_empty() {
+            return 0.0;
+        }
+        states.iter().filter(|state| bears_out(state)).count() as f32 / states.len() as f32
+    }
+
+    fn launch(&self) -> Option<Detection> {
+        let threshold = self.config.launch_acceleration;
+        let accelerating =
+            |state: &State| state.vertical_acceleration.is_some_and(|a| a > threshold);
+        // Dated to the first state of the window already accelerating hard.
+        let first = self.window.iter().find(|state| accelerating(state))?;
+        Some(Detection {
+            kind: Kind::Launch,
+            time: first.time,
+            confidence: Self::share(&self.window, accelerating),
+            altitude: first.altitude,
+        })
+    }
+
+    fn apogee(&self, middle: f64) -> Option<Detection> {
+        let turned = |state: &State| match state.vertical_velocity {
+            Some(v) if state.time < middle => v > 0.0,
+            Some(v) => v < 0.0,
+            None => false,
+        };
+        // Dated to the last sign change of the vertical velocity, interpolated between the
+        // states on either side, and placed at the highest altitude seen around it.
+        let crossing = self
+            .window
+            .iter()
+            .zip(self.window.iter().skip(1))
+            .filter_map(|(a, b)| match (a.vertical_velocity, b.vertical_velocity) {
+                (Some(va), Some(vb)) if va > 0.0 && vb <= 0.0 => {
+                    Some(a.time + (b.time - a.time) * (va / (va - vb)) as f64)
+                }
+                _ => None,
+            })
+            .next_back()?;
+        let altitude = self
+            .window
+            .iter()
+            .filter_map(|state| state.altitude)
+            .reduce(f32::max);
+        Some(Detection {
+            kind: Kind::Apogee,
+            time: crossing,
+            confidence: Self::share(&self.window, turned),
+            altitude,
+        })
+    }
+
+    fn landing(&self) -> Option<Detection> {
+        let config = &self.config;
+        let quiet = |state: &State| match (state.vertical_acceleration, state.vertical_velocity) {
+            (Some(a), Some(v)) => {
+                a.abs() < config.landing_acceleration && v.abs() < config.landing_velocity
+            }
+            _ => false,
+        };
+        // Dated to the start of the quiet window.
+        let first = self.window.front()?;
+        Some(Detection {
+            kind: Kind::Landing,
+            time: first.time,
+            confidence: Self::share(&self.window, quiet),
+            altitude: self.window.back().and_then(|state| state.altitude),
+        })
+    }
+}
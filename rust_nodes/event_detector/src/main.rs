@@ -0,0 +1,191 @@
+mod detector;
+
+use clap::Parser;
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use detector::{Config, Detection, Detector, Kind, State};
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensors_rs::sensors;
+use std::process::ExitCode;
+use tracing::{error, info};
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+use zenoh::sample::Sample;
+
+#[derive(Parser)]
+#[command(
+    about = "Detects launch, apogee, and landing in the fused state and publishes them on events/flight"
+)]
+struct Args {
+    /// Launch once the vertical acceleration stays above this, in m/s²...
+    #[arg(long, default_value_t = 20.0)]
+    launch_acceleration: f32,
+    /// ...over this long, in milliseconds.
+    #[arg(long, default_value_t = 300)]
+    launch_window_ms: u64,
+    /// Apogee once the vertical velocity has been positive and then negative over this
+    /// long, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    apogee_window_ms: u64,
+    /// Landing once the vertical acceleration stays below this, in m/s²...
+    #[arg(long, default_value_t = 1.0)]
+    landing_acceleration: f32,
+    /// ...and the vertical speed below this, in m/s...
+    #[arg(long, default_value_t = 1.0)]
+    landing_velocity: f32,
+    /// ...over this long, in milliseconds.
+    #[arg(long, default_value_t = 3000)]
+    landing_window_ms: u64,
+    /// Share of the fused states in a window that must bear an event out, from 0 to 1.
+    #[arg(long, default_value_t = 0.8)]
+    min_confidence: f32,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+fn name(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Launch => "Launch",
+        Kind::Apogee => "Apogee",
+        Kind::Landing => "Landing",
+    }
+}
+
+struct EventDetector {
+    detector: Detector,
+    publisher: Option<Publisher<'static>>,
+}
+
+impl EventDetector {
+    fn on_fused(&mut self, sample: &Sample) -> zenoh::Result<Option<(Detection, u64)>> {
+        let payload = sample.payload().to_bytes();
+        let fused = flatbuffers::root::<sensors::FusedState>(&payload)
+            .map_err(|e| format!("malformed fused state: {}", e))?;
+        let state = State {
+            time: fused.timestamp_us() as f64 / 1e6,
+            altitude: fused.altitude_valid().then(|| fused.altitude()),
+            vertical_velocity: fused
+                .velocity()
+                .filter(|_| fused.velocity_valid())
+                .map(|velocity| velocity.z()),
+            vertical_acceleration: fused
+                .acceleration()
+                .filter(|_| fused.acceleration_valid())
+                .map(|acceleration| acceleration.z()),
+        };
+        Ok(self
+            .detector
+            .update(state)
+            .map(|detection| (detection, fused.timestamp_us())))
+    }
+
+    async fn publish(&self, detection: &Detection, detected_us: u64) -> zenoh::Result<()> {
+        let latency_s = detected_us as f64 / 1e6 - detection.time;
+        match detection.altitude {
+            Some(altitude) => info!(
+                "{} at {:.1} m, detected {:.2} s later with confidence {:.2}",
+                name(detection.kind),
+                altitude,
+                latency_s,
+                detection.confidence
+            ),
+            None => info!(
+                "{} detected {:.2} s later with confidence {:.2}",
+                name(detection.kind),
+                latency_s,
+                detection.confidence
+            ),
+        }
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(64);
+        let event = sensors::FlightEvent::create(
+            &mut builder,
+            &sensors::FlightEventArgs {
+                timestamp_us: (detection.time * 1e6) as u64,
+                detected_us,
+                kind: match detection.kind {
+                    Kind::Launch => sensors::FlightEventKind::Launch,
+                    Kind::Apogee => sensors::FlightEventKind::Apogee,
+                    Kind::Landing => sensors::FlightEventKind::Landing,
+                },
+                confidence: detection.confidence,
+                altitude: detection.altitude.unwrap_or(f32::NAN),
+            },
+        );
+        builder.finish(event, None);
+        match &self.publisher {
+            Some(publisher) => publisher.put(builder.finished_data().to_vec()).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Node for EventDetector {
+    const NAME: &'static str = "event_detector";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.subscribe(keys::fused_state()).await?;
+        // Each event is published once, so none are dropped under congestion.
+        let publisher = ctx
+            .session()
+            .declare_publisher(keys::flight_events())
+            .encoding(encoding::flatbuffer("sensors.FlightEvent"))
+            .congestion_control(CongestionControl::Block)
+            .qos(ctx.qos(), keys::flight_events())
+            .await?;
+        self.publisher = Some(publisher);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => {
+                if let Some((detection, detected_us)) = self.on_fused(&sample)? {
+                    self.publish(&detection, detected_us).await?;
+                }
+            }
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        match self.publisher.take() {
+            Some(publisher) => publisher.undeclare().await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if !(0.0..=1.0).contains(&args.min_confidence) {
+        error!("--min-confidence must be between 0 and 1");
+        return ExitCode::FAILURE;
+    }
+    let windows = [
+        args.launch_window_ms,
+        args.apogee_window_ms,
+        args.landing_window_ms,
+    ];
+    if windows.contains(&0) {
+        error!("detection windows must be positive");
+        return ExitCode::FAILURE;
+    }
+
+    let node = EventDetector {
+        detector: Detector::new(Config {
+            launch_acceleration: args.launch_acceleration,
+            launch_window_s: args.launch_window_ms as f64 / 1e3,
+            apogee_window_s: args.apogee_window_ms as f64 / 1e3,
+            landing_acceleration: args.landing_acceleration,
+            landing_velocity: args.landing_velocity,
+            landing_window_s: args.landing_window_ms as f64 / 1e3,
+            min_confidence: args.min_confidence,
+        }),
+        publisher: None,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,311 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sensor_decode::MessageType;
+use sensors_rs::sensors;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use zenoh::key_expr::{OwnedKeyExpr, keyexpr};
+
+// Weight of the newest sample in the moving average that noise faults scale deviations
+// from; at 100 Hz the average follows the signal within a few tenths of a second.
+const NOISE_AVERAGE_WEIGHT: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum FaultKind {
+    Bias,
+    Freeze,
+    Dropout,
+    Noise,
+    Spike,
+}
+
+impl FaultKind {
+    // Chance per sample a dropout or spike fires when the command does not give one.
+    pub fn default_probability(self) -> f64 {
+        match self {
+            FaultKind::Spike => 0.05,
+            _ => 1.0,
+        }
+    }
+}
+
+// A fault as commanded, see sensors::FaultCommand.
+#[derive(Clone, Debug)]
+pub struct Fault {
+    pub target: OwnedKeyExpr,
+    pub kind: FaultKind,
+    pub channel: Option<String>,
+    pub magnitude: f64,
+    pub probability: f64,
+    pub duration: Option<Duration>,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} on {}", self.kind, self.target)?;
+        if let Some(channel) = &self.channel {
+            write!(f, " {}", channel)?;
+        }
+        match self.kind {
+            FaultKind::Bias => write!(f, " of {}", self.magnitude)?,
+            FaultKind::Noise => write!(f, " x{}", self.magnitude)?,
+            FaultKind::Spike => write!(f, " of {} at p={}", self.magnitude, self.probability)?,
+            FaultKind::Dropout => write!(f, " at p={}", self.probability)?,
+            FaultKind::Freeze => {}
+        }
+        if let Some(duration) = self.duration {
+            write!(f, " for {} s", duration.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
+
+impl Fault {
+    // A fault on the sensors matching target, checked the same whoever commands it: the
+    // probability defaults by kind and must be within 0 and 1, the channel must be one of
+    // a known sensor type, and the duration must be positive.
+    pub fn new(
+        target: &str,
+        kind: FaultKind,
+        magnitude: f64,
+        probability: Option<f64>,
+        channel: Option<String>,
+        duration_s: Option<f64>,
+    ) -> Result<Self, String> {
+        let probability = probability.unwrap_or(kind.default_probability());
+        if !(0.0..=1.0).contains(&probability) {
+            return Err("probability must be within 0 and 1".to_string());
+        }
+        if let Some(channel) = &channel {
+            let known = MessageType::ALL
+                .iter()
+                .any(|kind| kind.channels().contains(&channel.as_str()));
+            if !known {
+                return Err(format!("unknown channel '{}'", channel));
+            }
+        }
+        let duration = match duration_s {
+            Some(s) if s.is_nan() || s <= 0.0 => {
+                return Err("duration must be positive".to_string());
+            }
+            Some(s) => Some(Duration::from_secs_f64(s)),
+            None => None,
+        };
+        Ok(Fault {
+            target: target_key(target)?,
+            kind,
+            channel,
+            magnitude,
+            probability,
+            duration,
+        })
+    }
+}
+
+// The key expression of the sensors a fault command targets.
+pub fn target_key(target: &str) -> Result<OwnedKeyExpr, String> {
+    OwnedKeyExpr::autocanonize(target.to_string())
+        .map_err(|e| format!("invalid target '{}': {}", target, e))
+}
+
+pub enum Command {
+    Add(Fault),
+    // Removes every fault whose target intersects the key expression.
+    Clear(OwnedKeyExpr),
+}
+
+impl Command {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+        let args = match self {
+            Command::Add(fault) => sensors::FaultCommandArgs {
+                target: Some(builder.create_string(fault.target.as_str())),
+                clear: false,
+                kind: match fault.kind {
+                    FaultKind::Bias => sensors::FaultKind::Bias,
+                    FaultKind::Freeze => sensors::FaultKind::Freeze,
+                    FaultKind::Dropout => sensors::FaultKind::Dropout,
+                    FaultKind::Noise => sensors::FaultKind::Noise,
+                    FaultKind::Spike => sensors::FaultKind::Spike,
+                },
+                channel: fault
+                    .channel
+                    .as_deref()
+                    .map(|channel| builder.create_string(channel)),
+                magnitude: fault.magnitude,
+                probability: fault.probability,
+                duration_s: fault.duration.map_or(0.0, |d| d.as_secs_f64()),
+            },
+            Command::Clear(target) => sensors::FaultCommandArgs {
+                target: Some(builder.create_string(target.as_str())),
+                clear: true,
+                ..Default::default()
+            },
+        };
+        let command = sensors::FaultCommand::create(&mut builder, &args);
+        builder.finish(command, None);
+        builder.finished_data().to_vec()
+    }
+
+    pub fn decode(payload: &[u8]) -> Result<Self, String> {
+        let command =
+            flatbuffers::root::<sensors::FaultCommand>(payload).map_err(|e| e.to_string())?;
+        let target = command.target().unwrap_or_default();
+        let target = OwnedKeyExpr::autocanonize(target.to_string())
+            .map_err(|e| format!("invalid target '{}': {}", target, e))?;
+        if command.clear() {
+            return Ok(Command::Clear(target));
+        }
+        let kind = match command.kind() {
+            sensors::FaultKind::Bias => FaultKind::Bias,
+            sensors::FaultKind::Freeze => FaultKind::Freeze,
+            sensors::FaultKind::Dropout => FaultKind::Dropout,
+            sensors::FaultKind::Noise => FaultKind::Noise,
+            sensors::FaultKind::Spike => FaultKind::Spike,
+            other => return Err(format!("unknown fault kind {:?}", other)),
+        };
+        let probability = command.probability();
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(format!("probability {} is not within 0 and 1", probability));
+        }
+        let duration_s = command.duration_s();
+        if duration_s.is_nan() || duration_s < 0.0 {
+            return Err(format!("duration {} s is negative", duration_s));
+        }
+        Ok(Command::Add(Fault {
+            target,
+            kind,
+            channel: command
+                .channel()
+                .filter(|channel| !channel.is_empty())
+                .map(str::to_string),
+            magnitude: command.magnitude(),
+            probability,
+            duration: (duration_s > 0.0).then(|| Duration::from_secs_f64(duration_s)),
+        }))
+    }
+}
+
+struct Active {
+    fault: Fault,
+    started: Instant,
+    // Per sensor key: the frozen values of a freeze, the moving averages of a noise fault.
+    memory: HashMap<String, Vec<f64>>,
+}
+
+// The active faults, applied in the order they were added.
+pub struct Injector {
+    faults: Vec<Active>,
+    rng: StdRng,
+}
+
+impl Injector {
+    // Dropouts and spikes are drawn from a generator with the given seed, so a scripted
+    // test run corrupts the same samples every time.
+    pub fn new(seed: u64) -> Self {
+        Injector {
+            faults: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn add(&mut self, fault: Fault) {
+        self.faults.push(Active {
+            fault,
+            started: Instant::now(),
+            memory: HashMap::new(),
+        });
+    }
+
+    // Removes the faults on sensors matching the key expression and returns them.
+    pub fn clear(&mut self, target: &keyexpr) -> Vec<Fault> {
+        self.remove(|active| active.fault.target.intersects(target))
+    }
+
+    // Removes the faults whose duration has run out and returns them.
+    pub fn expire(&mut self, now: Instant) -> Vec<Fault> {
+        self.remove(|active| {
+            active
+                .fault
+                .duration
+                .is_some_and(|duration| now.duration_since(active.started) >= duration)
+        })
+    }
+
+    fn remove(&mut self, matches: impl Fn(&Active) -> bool) -> Vec<Fault> {
+        let (removed, kept) = self.faults.drain(..).partition(|active| matches(active));
+        self.faults = kept;
+        removed.into_iter().map(|active| active.fault).collect()
+    }
+
+    // Whether any fault applies to the sensor, i.e. its samples need decoding at all.
+    pub fn affects(&self, key: &keyexpr) -> bool {
+        self.faults
+            .iter()
+            .any(|active| active.fault.target.intersects(key))
+    }
+
+    // Corrupts the values of a sample published on key with every fault on it. Returns
+    // None if the sample is dropped.
+    pub fn apply(
+        &mut self,
+        key: &keyexpr,
+        kind: MessageType,
+        mut values: Vec<f64>,
+    ) -> Option<Vec<f64>> {
+        for active in &mut self.faults {
+            let fault = &active.fault;
+            if !fault.target.intersects(key) {
+                continue;
+            }
+            let channels: Vec<usize> = match &fault.channel {
+                Some(name) => match kind.channels().iter().position(|c| c == name) {
+                    Some(channel) => vec![channel],
+                    None => continue,
+                },
+                None => (0..values.len()).collect(),
+            };
+            match fault.kind {
+                FaultKind::Bias => {
+                    for c in channels {
+                        values[c] += fault.magnitude;
+                    }
+                }
+                FaultKind::Freeze => {
+                    let frozen = active
+                        .memory
+                        .entry(key.to_string())
+                        .or_insert_with(|| values.clone());
+                    for c in channels {
+                        values[c] = frozen[c];
+                    }
+                }
+                FaultKind::Dropout => {
+                    if self.rng.random_bool(fault.probability) {
+                        return None;
+                    }
+                }
+                FaultKind::Noise => {
+                    let average = active
+                        .memory
+                        .entry(key.to_string())
+                        .or_insert_with(|| values.clone());
+                    for c in channels {
+                        let deviation = values[c] - average[c];
+                        values[c] = average[c] + deviation * fault.magnitude;
+                        average[c] += NOISE_AVERAGE_WEIGHT * deviation;
+                    }
+                }
+                FaultKind::Spike => {
+                    if self.rng.random_bool(fault.probability) {
+                        for c in channels {
+                            values[c] += fault.magnitude;
+                        }
+                    }
+                }
+            }
+        }
+        Some(values)
+    }
+}
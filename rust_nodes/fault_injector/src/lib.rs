@@ -0,0 +1,56 @@
+// Fault models applied to sensor samples, and the commands that add and clear them, shared
+// by the injector node and everything that commands it.
+pub mod faults;
+
+use common::encoding;
+use faults::Command;
+use keyspace::keys;
+use std::time::{Duration, Instant};
+use zenoh::query::ConsolidationMode;
+
+// How long send keeps asking before giving up on reaching an injector.
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+const COMMAND_RETRY: Duration = Duration::from_millis(250);
+
+// Sends a command to the injectors as a query on faults/command and returns their replies,
+// which are the acknowledgement. A fresh session may not have discovered the injectors yet,
+// so the query is repeated until one answers or timeout passes.
+pub async fn send(
+    session: &zenoh::Session,
+    command: &Command,
+    timeout: Duration,
+) -> Result<Vec<String>, String> {
+    let payload = command.encode();
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        let replies = session
+            .get(keys::fault_commands())
+            .payload(payload.clone())
+            .encoding(encoding::flatbuffer("sensors.FaultCommand"))
+            .consolidation(ConsolidationMode::None)
+            .timeout(COMMAND_RETRY)
+            .await
+            .map_err(|e| format!("Failed to send fault command: {}", e))?;
+        let mut confirmations = Vec::new();
+        while let Ok(reply) = replies.recv_async().await {
+            match reply.result() {
+                Ok(sample) => confirmations
+                    .push(String::from_utf8_lossy(&sample.payload().to_bytes()).into_owned()),
+                Err(e) => {
+                    return Err(format!(
+                        "Rejected: {}",
+                        String::from_utf8_lossy(&e.payload().to_bytes())
+                    ));
+                }
+            }
+        }
+        if !confirmations.is_empty() {
+            return Ok(confirmations);
+        }
+        tokio::time::sleep(COMMAND_RETRY).await;
+    }
+    Err(format!(
+        "No fault injector answered on {}",
+        keys::fault_commands()
+    ))
+}
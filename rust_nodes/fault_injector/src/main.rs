@@ -0,0 +1,277 @@
+use clap::{Parser, Subcommand};
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use fault_injector::COMMAND_TIMEOUT;
+use fault_injector::faults::{self, Command, Fault, FaultKind, Injector};
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, QueryableId};
+use sensor_decode::MessageType;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use tracing::info;
+use zenoh::bytes::{Encoding, ZBytes};
+use zenoh::key_expr::OwnedKeyExpr;
+use zenoh::pubsub::Publisher;
+use zenoh::query::Query;
+use zenoh::sample::Sample;
+
+// How often faults are checked for having run their duration.
+const EXPIRY_PERIOD: Duration = Duration::from_millis(100);
+
+#[derive(Parser)]
+#[command(
+    about = "Republishes sensor samples from raw/** onto devices/** with commanded faults applied"
+)]
+struct Args {
+    /// Prefix the sensors publish under; samples on <PREFIX>/** are republished without it.
+    #[arg(long, default_value = keys::RAW)]
+    source_prefix: String,
+    /// Seed of the generator deciding which samples dropouts and spikes hit.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[command(subcommand)]
+    command: Option<CommandArgs>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+#[derive(Subcommand)]
+enum CommandArgs {
+    /// Tells the running injectors to add a fault, then exits.
+    Inject {
+        /// Key expression of the sensors to corrupt, e.g. devices/imu1.
+        target: String,
+        #[arg(value_enum)]
+        kind: FaultKind,
+        /// Size of a bias or spike in the channel's units, or the noise multiplier.
+        #[arg(long, default_value_t = 0.0)]
+        magnitude: f64,
+        /// Chance per sample of a dropout or spike; defaults to 1 and 0.05 respectively.
+        #[arg(long)]
+        probability: Option<f64>,
+        /// Only corrupt this channel, e.g. acceleration_z. Defaults to all channels.
+        #[arg(long)]
+        channel: Option<String>,
+        /// Clear the fault after this many seconds instead of keeping it until cleared.
+        #[arg(long)]
+        duration_s: Option<f64>,
+    },
+    /// Tells the running injectors to clear every fault on the matching sensors, then exits.
+    Clear {
+        /// Key expression of the sensors to clear.
+        #[arg(default_value = "**")]
+        target: String,
+    },
+}
+
+impl CommandArgs {
+    fn into_command(self) -> Result<Command, String> {
+        match self {
+            CommandArgs::Inject {
+                target,
+                kind,
+                magnitude,
+                probability,
+                channel,
+                duration_s,
+            } => Fault::new(&target, kind, magnitude, probability, channel, duration_s)
+                .map(Command::Add),
+            CommandArgs::Clear { target } => Ok(Command::Clear(faults::target_key(&target)?)),
+        }
+    }
+}
+
+// Sends a command to the running injectors and prints their confirmations.
+async fn send(node: &NodeArgs, command: Command) -> ExitCode {
+    let session = match node.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let status = match fault_injector::send(&session, &command, COMMAND_TIMEOUT).await {
+        Ok(confirmations) => {
+            for confirmation in confirmations {
+                println!("{}", confirmation);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    };
+    if let Err(e) = session.close().await {
+        eprintln!("Failed to close Zenoh session: {}", e);
+    }
+    status
+}
+
+struct FaultInjector {
+    source_prefix: String,
+    injector: Injector,
+    commands: Option<QueryableId>,
+    // Latest sample republished on each sensor key, for answering sensor queries the way
+    // the simulators do.
+    latest: HashMap<OwnedKeyExpr, (ZBytes, Encoding)>,
+    publishers: HashMap<OwnedKeyExpr, Publisher<'static>>,
+}
+
+impl FaultInjector {
+    async fn on_command(&mut self, query: &Query) -> zenoh::Result<()> {
+        let payload = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+        let reply = match Command::decode(&payload) {
+            Ok(Command::Add(fault)) => {
+                let reply = format!("Injecting {}", fault);
+                self.injector.add(fault);
+                reply
+            }
+            Ok(Command::Clear(target)) => {
+                let cleared = self.injector.clear(&target);
+                for fault in &cleared {
+                    info!("Cleared {}", fault);
+                }
+                format!("Cleared {} faults on {}", cleared.len(), target)
+            }
+            Err(e) => {
+                return query
+                    .reply_err(format!("invalid fault command: {}", e))
+                    .await;
+            }
+        };
+        info!("{}", reply);
+        query
+            .reply(keys::fault_commands(), reply)
+            .encoding(Encoding::TEXT_PLAIN)
+            .await
+    }
+
+    async fn on_sample(
+        &mut self,
+        session: &zenoh::Session,
+        qos: &QosConfig,
+        sample: &Sample,
+    ) -> zenoh::Result<()> {
+        let raw = sample.key_expr().as_str();
+        let Some(key) = raw
+            .strip_prefix(self.source_prefix.as_str())
+            .and_then(|key| key.strip_prefix('/'))
+        else {
+            return Ok(());
+        };
+        let key = OwnedKeyExpr::try_from(key.to_string())?;
+
+        // Samples of sensors without faults, or of types the injector cannot decode, are
+        // passed through untouched. Faulted ones are re-encoded at the current schema version.
+        let kind = MessageType::detect(key.as_str(), sample.encoding());
+        let (payload, encoding) = match kind {
+            Some(kind) if self.injector.affects(&key) => {
+                let values = sensor_decode::values(kind, sample.encoding(), sample.payload())
+                    .map_err(|e| format!("{}: failed to decode {:?}: {}", raw, kind, e))?;
+                match self.injector.apply(&key, kind, values) {
+                    Some(values) => (
+                        sensor_decode::encode(kind, &values),
+                        encoding::flatbuffer(kind.table()),
+                    ),
+                    None => {
+                        self.latest.remove(&key);
+                        return Ok(());
+                    }
+                }
+            }
+            _ => (sample.payload().clone(), sample.encoding().clone()),
+        };
+
+        if !self.publishers.contains_key(&key) {
+            let publisher = session
+                .declare_publisher(key.clone())
+                .qos(qos, &key)
+                .await?;
+            self.publishers.insert(key.clone(), publisher);
+        }
+        self.publishers[&key]
+            .put(payload.clone())
+            .encoding(encoding.clone())
+            .await?;
+        self.latest.insert(key, (payload, encoding));
+        Ok(())
+    }
+
+    async fn on_sensor_query(&self, query: &Query) -> zenoh::Result<()> {
+        for (key, (payload, encoding)) in &self.latest {
+            if query.key_expr().intersects(key) {
+                query
+                    .reply(key, payload.clone())
+                    .encoding(encoding.clone())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Node for FaultInjector {
+    const NAME: &'static str = "fault_injector";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.subscribe(&format!("{}/**", self.source_prefix)).await?;
+        self.commands = Some(ctx.declare_queryable(keys::fault_commands()).await?);
+        ctx.declare_queryable(keys::all_devices()).await?;
+        ctx.add_timer(EXPIRY_PERIOD);
+        info!(
+            "Republishing {}/** on {}, fault commands on {}",
+            self.source_prefix,
+            keys::all_devices(),
+            keys::fault_commands()
+        );
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Timer(_) => {
+                for fault in self.injector.expire(Instant::now()) {
+                    info!("Expired {}", fault);
+                }
+                Ok(())
+            }
+            Event::Query(id, query) if Some(id) == self.commands => self.on_command(&query).await,
+            Event::Query(_, query) => self.on_sensor_query(&query).await,
+            Event::Sample(_, sample) => self.on_sample(ctx.session(), ctx.qos(), &sample).await,
+            Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        for (_, publisher) in self.publishers.drain() {
+            publisher.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    if let Some(command) = args.command {
+        return match command.into_command() {
+            Ok(command) => send(&args.node, command).await,
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    node_framework::logging::init(&args.node.log_level);
+    let node = FaultInjector {
+        source_prefix: args.source_prefix.trim_end_matches('/').to_string(),
+        injector: Injector::new(args.seed),
+        commands: None,
+        latest: HashMap::new(),
+        publishers: HashMap::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,282 @@
+mod tracker;
+
+use clap::{Parser, ValueEnum};
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId, TimerId};
+use sensors_rs::sensors;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use tracker::{Health, Policy, Tracked};
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+use zenoh::sample::Sample;
+
+// How often the fault evidence of every sensor is reevaluated.
+const CHECK_PERIOD: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Parser)]
+#[command(
+    about = "Isolates faulted sensors from fusion and re-admits them once healthy, publishing decisions on fdir/status"
+)]
+struct Args {
+    /// Key expression of the sensors to track.
+    #[arg(long, default_value = keys::all_devices())]
+    key: String,
+    /// Lowest alarm severity counted as a fault.
+    #[arg(long, value_enum, default_value_t = Severity::Warning)]
+    min_severity: Severity,
+    /// Count a sensor as faulted after this long without a sample, in milliseconds.
+    #[arg(long, default_value_t = 500)]
+    silence_ms: u64,
+    /// Isolate a sensor once its fault evidence has persisted this long, in milliseconds.
+    #[arg(long, default_value_t = 200)]
+    isolate_after_ms: u64,
+    /// Re-admit an isolated sensor after this long without fault evidence, in seconds.
+    #[arg(long, default_value_t = 5.0)]
+    recovery_s: f64,
+    /// Period of status publications between health changes, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    publish_period_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+fn severity(severity: sensors::AlarmSeverity) -> Severity {
+    match severity {
+        sensors::AlarmSeverity::Info => Severity::Info,
+        sensors::AlarmSeverity::Warning => Severity::Warning,
+        _ => Severity::Critical,
+    }
+}
+
+struct Fdir {
+    key: String,
+    min_severity: Severity,
+    silence: Duration,
+    policy: Policy,
+    publish_period: Duration,
+    sensors_subscription: Option<SubscriptionId>,
+    alarms_subscription: Option<SubscriptionId>,
+    check_timer: Option<TimerId>,
+    // Every sensor a sample has been seen from.
+    sensors: BTreeMap<String, Tracked>,
+    // Channels with an active alarm of at least --min-severity, by sensor key.
+    alarms: HashMap<String, BTreeSet<String>>,
+    // Sensors fusion currently outvotes.
+    outvoted: HashSet<String>,
+    publisher: Option<Publisher<'static>>,
+}
+
+impl Fdir {
+    fn on_alarm(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        let payload = sample.payload().to_bytes();
+        let alarm = flatbuffers::root::<sensors::Alarm>(&payload)
+            .map_err(|e| format!("malformed alarm on {}: {}", sample.key_expr(), e))?;
+        let source = alarm.source().unwrap_or_default().to_string();
+        let channel = alarm.channel().unwrap_or_default().to_string();
+        let channels = self.alarms.entry(source).or_default();
+        if alarm.active() && severity(alarm.severity()) >= self.min_severity {
+            channels.insert(channel);
+        } else {
+            channels.remove(&channel);
+        }
+        Ok(())
+    }
+
+    fn on_voting(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        let payload = sample.payload().to_bytes();
+        let status = flatbuffers::root::<sensors::VotingStatus>(&payload)
+            .map_err(|e| format!("malformed voting status: {}", e))?;
+        self.outvoted = status
+            .excluded()
+            .map(|keys| keys.iter().map(str::to_string).collect())
+            .unwrap_or_default();
+        Ok(())
+    }
+
+    // Fault evidence currently seen for a sensor.
+    fn causes(&self, key: &str, tracked: &Tracked, now: Instant) -> Vec<String> {
+        let mut causes = Vec::new();
+        if self.outvoted.contains(key) {
+            causes.push("outvoted".to_string());
+        }
+        if now - tracked.last_sample > self.silence {
+            causes.push("silent".to_string());
+        }
+        for channel in self.alarms.get(key).into_iter().flatten() {
+            causes.push(format!("alarm {}", channel));
+        }
+        causes
+    }
+
+    // Reevaluates every sensor and reports those whose health changed. Returns whether any
+    // did.
+    fn check(&mut self, now: Instant) -> bool {
+        let mut changed = false;
+        let keys: Vec<String> = self.sensors.keys().cloned().collect();
+        for key in keys {
+            let causes = self.causes(&key, &self.sensors[&key], now);
+            let Some(tracked) = self.sensors.get_mut(&key) else {
+                continue;
+            };
+            if !tracked.update(causes, &self.policy, now) {
+                continue;
+            }
+            changed = true;
+            match tracked.health {
+                Health::Isolated => info!("{} isolated: {}", key, tracked.causes.join(", ")),
+                Health::Recovering => info!("{} recovering", key),
+                Health::Healthy => info!("{} re-admitted", key),
+            }
+        }
+        changed
+    }
+
+    fn encode(&self, now: Instant) -> Vec<u8> {
+        let timestamp_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_micros() as u64)
+            .unwrap_or(0);
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(512);
+        let sensors: Vec<_> = self
+            .sensors
+            .iter()
+            .map(|(key, tracked)| {
+                let key = builder.create_string(key);
+                let causes: Vec<_> = tracked
+                    .causes
+                    .iter()
+                    .map(|cause| builder.create_string(cause))
+                    .collect();
+                let causes = builder.create_vector(&causes);
+                sensors::SensorStatus::create(
+                    &mut builder,
+                    &sensors::SensorStatusArgs {
+                        key: Some(key),
+                        health: match tracked.health {
+                            Health::Healthy => sensors::SensorHealth::Healthy,
+                            Health::Isolated => sensors::SensorHealth::Isolated,
+                            Health::Recovering => sensors::SensorHealth::Recovering,
+                        },
+                        causes: Some(causes),
+                        since_s: (now - tracked.since).as_secs_f64(),
+                    },
+                )
+            })
+            .collect();
+        let sensors = builder.create_vector(&sensors);
+        let status = sensors::FdirStatus::create(
+            &mut builder,
+            &sensors::FdirStatusArgs {
+                timestamp_us,
+                sensors: Some(sensors),
+            },
+        );
+        builder.finish(status, None);
+        builder.finished_data().to_vec()
+    }
+
+    async fn publish(&self, now: Instant) -> zenoh::Result<()> {
+        match &self.publisher {
+            Some(publisher) => publisher.put(self.encode(now)).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Node for Fdir {
+    const NAME: &'static str = "fdir";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        self.sensors_subscription = Some(ctx.subscribe(&self.key).await?);
+        self.alarms_subscription = Some(ctx.subscribe(keys::all_alarms()).await?);
+        ctx.subscribe(keys::voting_status()).await?;
+        self.check_timer = Some(ctx.add_timer(CHECK_PERIOD));
+        ctx.add_timer(self.publish_period);
+        // Decisions are what fusion acts on, so none are dropped under congestion.
+        let publisher = ctx
+            .session()
+            .declare_publisher(keys::fdir_status())
+            .encoding(encoding::flatbuffer("sensors.FdirStatus"))
+            .congestion_control(CongestionControl::Block)
+            .qos(ctx.qos(), keys::fdir_status())
+            .await?;
+        self.publisher = Some(publisher);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let now = Instant::now();
+        match event {
+            Event::Timer(id) if Some(id) == self.check_timer => {
+                if self.check(now) {
+                    self.publish(now).await?;
+                }
+            }
+            Event::Timer(_) => self.publish(now).await?,
+            Event::Sample(id, sample) if Some(id) == self.sensors_subscription => {
+                self.sensors
+                    .entry(sample.key_expr().to_string())
+                    .or_insert_with(|| Tracked::new(now))
+                    .last_sample = now;
+            }
+            Event::Sample(id, sample) if Some(id) == self.alarms_subscription => {
+                self.on_alarm(&sample)?
+            }
+            Event::Sample(_, sample) => self.on_voting(&sample)?,
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        match self.publisher.take() {
+            Some(publisher) => publisher.undeclare().await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if args.recovery_s.is_nan() || args.recovery_s < 0.0 {
+        error!("--recovery-s must not be negative");
+        return ExitCode::FAILURE;
+    }
+    if args.publish_period_ms == 0 {
+        error!("--publish-period-ms must be positive");
+        return ExitCode::FAILURE;
+    }
+
+    let node = Fdir {
+        key: args.key.clone(),
+        min_severity: args.min_severity,
+        silence: Duration::from_millis(args.silence_ms),
+        policy: Policy {
+            isolate_after: Duration::from_millis(args.isolate_after_ms),
+            recovery: Duration::from_secs_f64(args.recovery_s),
+        },
+        publish_period: Duration::from_millis(args.publish_period_ms),
+        sensors_subscription: None,
+        alarms_subscription: None,
+        check_timer: None,
+        sensors: BTreeMap::new(),
+        alarms: HashMap::new(),
+        outvoted: HashSet::new(),
+        publisher: None,
+    };
+    node_framework::run(node, &args.node).await
+}
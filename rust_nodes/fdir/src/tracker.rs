@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Health {
+    Healthy,
+    Isolated,
+    Recovering,
+}
+
+// How quickly sensors are isolated and re-admitted.
+pub struct Policy {
+    // How long fault evidence must persist before a healthy sensor is isolated, so a single
+    // glitch does not take it out.
+    pub isolate_after: Duration,
+    // How long an isolated sensor must show no fault evidence before it is re-admitted.
+    pub recovery: Duration,
+}
+
+// Health of one sensor and the evidence behind it.
+pub struct Tracked {
+    pub health: Health,
+    pub causes: Vec<String>,
+    // When the sensor entered its current health.
+    pub since: Instant,
+    pub last_sample: Instant,
+    // When the current, unbroken stretch of fault evidence started.
+    faulted_since: Option<Instant>,
+}
+
+impl Tracked {
+    pub fn new(now: Instant) -> Self {
+        Tracked {
+            health: Health::Healthy,
+            causes: Vec::new(),
+            since: now,
+            last_sample: now,
+            faulted_since: None,
+        }
+    }
+
+    // Moves the sensor along healthy -> isolated -> recovering -> healthy given the fault
+    // evidence seen now. Any evidence while recovering isolates the sensor again and restarts
+    // the recovery. Returns whether the health changed.
+    pub fn update(&mut self, causes: Vec<String>, policy: &Policy, now: Instant) -> bool {
+        let faulted = !causes.is_empty();
+        self.causes = causes;
+        let faulted_since = match faulted {
+            true => *self.faulted_since.get_or_insert(now),
+            false => {
+                self.faulted_since = None;
+                now
+            }
+        };
+        let next = match self.health {
+            Health::Healthy if faulted && now - faulted_since >= policy.isolate_after => {
+                Health::Isolated
+            }
+            Health::Isolated if !faulted => Health::Recovering,
+            Health::Recovering if faulted => Health::Isolated,
+            Health::Recovering if now - self.since >= policy.recovery => Health::Healthy,
+            health => health,
+        };
+        if next == self.health {
+            return false;
+        }
+        self.health = next;
+        self.since = now;
+        true
+    }
+}
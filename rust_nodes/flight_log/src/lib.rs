@@ -0,0 +1,31 @@
+// Flight log format written by the recorder and read by the replay node.
+//
+// A log is a header (magic "FLOG", then a little-endian u16 format version) followed by
+// records until the end of the file. Each record is, little-endian:
+//
+//   u64  receive time in nanoseconds since the Unix epoch
+//   u16  key length, then the key expression as UTF-8
+//   u16  encoding length, then the Zenoh encoding as rendered by Encoding's Display
+//   u32  payload length, then the payload bytes
+//
+// Records are appended as they arrive, so a log cut short by a crash or power loss is
+// readable up to its last complete record. See segments.rs for logs split across files.
+mod reader;
+mod segments;
+mod writer;
+
+pub use reader::LogReader;
+pub use segments::{Limits, Manifest, SegmentWriter, segments};
+pub use writer::LogWriter;
+
+pub const MAGIC: &[u8; 4] = b"FLOG";
+pub const VERSION: u16 = 1;
+
+// One sample as received on the bus.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub timestamp_ns: u64,
+    pub key: String,
+    pub encoding: String,
+    pub payload: Vec<u8>,
+}
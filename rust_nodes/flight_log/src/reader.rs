@@ -0,0 +1,107 @@
+use crate::{MAGIC, Record, VERSION};
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+pub struct LogReader<R: Read> {
+    inner: R,
+}
+
+impl LogReader<BufReader<File>> {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        LogReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> LogReader<R> {
+    // Checks the log header; fails if the input is not a log of a supported version.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        inner.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a flight log"));
+        }
+        let version = u16::from_le_bytes(read_array(&mut inner)?);
+        if version != VERSION {
+            return Err(invalid(&format!("unsupported log version {}", version)));
+        }
+        Ok(LogReader { inner })
+    }
+
+    // Reads the next record, or None at the end of the log. A log ending in the middle of
+    // a record yields an UnexpectedEof error.
+    pub fn read(&mut self) -> io::Result<Option<Record>> {
+        let mut timestamp = [0u8; 8];
+        let mut filled = 0;
+        while filled < timestamp.len() {
+            match self.inner.read(&mut timestamp[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(truncated()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let key_len = u16::from_le_bytes(self.read_array()?) as usize;
+        let key = String::from_utf8(self.read_bytes(key_len)?)
+            .map_err(|_| invalid("key is not UTF-8"))?;
+        let encoding_len = u16::from_le_bytes(self.read_array()?) as usize;
+        let encoding = String::from_utf8(self.read_bytes(encoding_len)?)
+            .map_err(|_| invalid("encoding is not UTF-8"))?;
+        let payload_len = u32::from_le_bytes(self.read_array()?) as usize;
+        let payload = self.read_bytes(payload_len)?;
+
+        Ok(Some(Record {
+            timestamp_ns: u64::from_le_bytes(timestamp),
+            key,
+            encoding,
+            payload,
+        }))
+    }
+
+    fn read_array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        read_array(&mut self.inner).map_err(eof_as_truncated)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0u8; len];
+        self.inner
+            .read_exact(&mut bytes)
+            .map_err(eof_as_truncated)?;
+        Ok(bytes)
+    }
+}
+
+impl<R: Read> Iterator for LogReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+fn read_array<const N: usize>(inner: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    inner.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn truncated() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "log ends in a truncated record",
+    )
+}
+
+fn eof_as_truncated(e: io::Error) -> io::Error {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        truncated()
+    } else {
+        e
+    }
+}
@@ -0,0 +1,310 @@
+// A flight log split into segments, for recording to flash that has to be bounded: a
+// directory of logs segment-000000.flog, segment-000001.flog, ... written one after the
+// other. When a segment is closed it is synced to disk and a manifest beside it
+// (segment-000000.manifest) records its length, records, receive-time span, and CRC-32C.
+// A segment without a manifest was still being written when the writer stopped, so it is
+// cut back to its last complete record and sealed the next time the directory is opened.
+use crate::writer::encoded_len;
+use crate::{LogReader, LogWriter, Record};
+use common::crc32c;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const PREFIX: &str = "segment-";
+const EXTENSION: &str = "flog";
+const MANIFEST: &str = "manifest";
+
+// When a SegmentWriter moves on to a new segment, and how much it keeps. A limit left at
+// None does not apply.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Limits {
+    // Largest size of a segment; a record that would take it past this starts a new one.
+    pub segment_bytes: Option<u64>,
+    // Longest span of receive time a segment covers.
+    pub segment_span: Option<Duration>,
+    // Most bytes all segments may take together. The oldest closed segments are deleted
+    // to stay under it whenever a segment is started.
+    pub total_bytes: Option<u64>,
+}
+
+// What a closed segment holds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub bytes: u64,
+    pub records: u64,
+    // Receive times of the first and last records; both 0 in a segment without records.
+    pub first_ns: u64,
+    pub last_ns: u64,
+    // CRC-32C of the whole segment file, header included.
+    pub crc32c: u32,
+}
+
+impl Manifest {
+    // The manifest of a segment, or None if it has not been sealed.
+    pub fn load(segment: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(manifest_path(segment)) {
+            Ok(text) => Manifest::parse(&text)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Reads a segment through to work out its manifest.
+    pub fn compute(segment: &Path) -> io::Result<Self> {
+        let mut manifest = Manifest::default();
+        let mut file = BufReader::new(File::open(segment)?);
+        let mut chunk = vec![0u8; 64 * 1024];
+        loop {
+            let n = file.read(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+            manifest.bytes += n as u64;
+            manifest.crc32c = crc32c::update(manifest.crc32c, &chunk[..n]);
+        }
+        for record in LogReader::open(segment)? {
+            manifest.add(&record?);
+        }
+        Ok(manifest)
+    }
+
+    fn add(&mut self, record: &Record) {
+        if self.records == 0 {
+            self.first_ns = record.timestamp_ns;
+        }
+        self.records += 1;
+        self.last_ns = record.timestamp_ns;
+    }
+
+    // Written to a temporary file first, so a manifest is either whole or missing.
+    fn store(&self, segment: &Path) -> io::Result<()> {
+        let path = manifest_path(segment);
+        let temporary = path.with_extension("manifest.tmp");
+        let mut file = File::create(&temporary)?;
+        file.write_all(self.render().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temporary, &path)
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "bytes = {}\nrecords = {}\nfirst_ns = {}\nlast_ns = {}\ncrc32c = {:#010x}\n",
+            self.bytes, self.records, self.first_ns, self.last_ns, self.crc32c
+        )
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let fields: Vec<(&str, &str)> = text
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .collect();
+        let field = |name: &str| -> Result<u64, String> {
+            let value = fields
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| value)
+                .ok_or_else(|| format!("manifest has no {}", name))?;
+            let parsed = match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => value.parse(),
+            };
+            parsed.map_err(|_| format!("manifest {} '{}' is not a number", name, value))
+        };
+        Ok(Manifest {
+            bytes: field("bytes")?,
+            records: field("records")?,
+            first_ns: field("first_ns")?,
+            last_ns: field("last_ns")?,
+            crc32c: field("crc32c")? as u32,
+        })
+    }
+}
+
+// The segments in dir, oldest first.
+pub fn segments(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(index) = segment_index(&path) {
+            found.push((index, path));
+        }
+    }
+    found.sort();
+    Ok(found.into_iter().map(|(_, path)| path).collect())
+}
+
+fn segment_index(path: &Path) -> Option<u32> {
+    if path.extension()? != EXTENSION {
+        return None;
+    }
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(PREFIX)?
+        .parse()
+        .ok()
+}
+
+fn segment_path(dir: &Path, index: u32) -> PathBuf {
+    dir.join(format!("{}{:06}.{}", PREFIX, index, EXTENSION))
+}
+
+fn manifest_path(segment: &Path) -> PathBuf {
+    segment.with_extension(MANIFEST)
+}
+
+// Keeps the length and CRC-32C of everything written through it.
+struct Checksummed<W> {
+    inner: W,
+    bytes: u64,
+    crc32c: u32,
+}
+
+impl<W: Write> Write for Checksummed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.bytes += n as u64;
+        self.crc32c = crc32c::update(self.crc32c, &buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub struct SegmentWriter {
+    dir: PathBuf,
+    limits: Limits,
+    index: u32,
+    writer: LogWriter<Checksummed<BufWriter<File>>>,
+    manifest: Manifest,
+    recovered: Vec<PathBuf>,
+}
+
+impl SegmentWriter {
+    // Opens the segment directory at dir, creating it if needed, and starts a segment
+    // after any already there. Segments an earlier writer left unsealed are cut back to
+    // their last complete record and sealed first.
+    pub fn open(dir: &Path, limits: Limits) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let existing = segments(dir)?;
+        let mut recovered = Vec::new();
+        for segment in &existing {
+            if Manifest::load(segment)?.is_none() {
+                // Appending cuts off a record the writer stopped in the middle of.
+                drop(LogWriter::append(segment)?);
+                Manifest::compute(segment)?.store(segment)?;
+                recovered.push(segment.clone());
+            }
+        }
+        let index = existing
+            .last()
+            .and_then(|segment| segment_index(segment))
+            .map_or(0, |index| index + 1);
+        let writer = SegmentWriter {
+            dir: dir.to_path_buf(),
+            limits,
+            index,
+            writer: start(dir, index)?,
+            manifest: Manifest::default(),
+            recovered,
+        };
+        writer.enforce_total()?;
+        Ok(writer)
+    }
+
+    // Segments open found unsealed and sealed.
+    pub fn recovered(&self) -> &[PathBuf] {
+        &self.recovered
+    }
+
+    // The segment being written.
+    pub fn path(&self) -> PathBuf {
+        segment_path(&self.dir, self.index)
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        if self.manifest.records > 0 {
+            let bytes = self.writer.get_ref().bytes + encoded_len(record);
+            let span = record.timestamp_ns.saturating_sub(self.manifest.first_ns);
+            let full = self.limits.segment_bytes.is_some_and(|limit| bytes > limit);
+            let old = self
+                .limits
+                .segment_span
+                .is_some_and(|limit| span >= limit.as_nanos() as u64);
+            if full || old {
+                self.rotate()?;
+            }
+        }
+        self.writer.write(record)?;
+        self.manifest.add(record);
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    // Seals the segment being written.
+    pub fn close(mut self) -> io::Result<()> {
+        self.seal()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.seal()?;
+        self.index += 1;
+        self.writer = start(&self.dir, self.index)?;
+        self.manifest = Manifest::default();
+        self.enforce_total()
+    }
+
+    fn seal(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        let written = self.writer.get_ref();
+        written.inner.get_ref().sync_all()?;
+        let manifest = Manifest {
+            bytes: written.bytes,
+            crc32c: written.crc32c,
+            ..self.manifest.clone()
+        };
+        manifest.store(&self.path())
+    }
+
+    // Deletes the oldest sealed segments until all of them fit in the total limit.
+    fn enforce_total(&self) -> io::Result<()> {
+        let Some(limit) = self.limits.total_bytes else {
+            return Ok(());
+        };
+        let current = self.path();
+        let mut sealed = Vec::new();
+        let mut total = self.writer.get_ref().bytes;
+        for segment in segments(&self.dir)? {
+            if segment != current {
+                total += fs::metadata(&segment)?.len();
+                sealed.push(segment);
+            }
+        }
+        for segment in sealed {
+            if total <= limit {
+                break;
+            }
+            total -= fs::metadata(&segment)?.len();
+            fs::remove_file(manifest_path(&segment))?;
+            fs::remove_file(&segment)?;
+        }
+        Ok(())
+    }
+}
+
+fn start(dir: &Path, index: u32) -> io::Result<LogWriter<Checksummed<BufWriter<File>>>> {
+    LogWriter::new(Checksummed {
+        inner: BufWriter::new(File::create(segment_path(dir, index))?),
+        bytes: 0,
+        crc32c: 0,
+    })
+}
@@ -0,0 +1,92 @@
+use crate::{LogReader, MAGIC, Record, VERSION};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const HEADER_LEN: u64 = MAGIC.len() as u64 + 2;
+
+pub struct LogWriter<W: Write> {
+    inner: W,
+}
+
+impl LogWriter<BufWriter<File>> {
+    // Creates or truncates the file at path and writes the log header.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        LogWriter::new(BufWriter::new(File::create(path)?))
+    }
+
+    // Opens the log at path to add records to, or creates it if there is none. A record
+    // cut short at the end of the log, as a crash leaves it, is cut off first so the new
+    // ones follow the last complete record.
+    pub fn append(path: &Path) -> io::Result<Self> {
+        let reader = match LogReader::open(path) {
+            Ok(reader) => reader,
+            // A log cut short in its header holds nothing yet.
+            Err(e)
+                if e.kind() == io::ErrorKind::NotFound
+                    || e.kind() == io::ErrorKind::UnexpectedEof =>
+            {
+                return LogWriter::create(path);
+            }
+            Err(e) => return Err(e),
+        };
+        let mut complete = HEADER_LEN;
+        for record in reader {
+            match record {
+                Ok(record) => complete += encoded_len(&record),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+        }
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        file.set_len(complete)?;
+        file.seek(SeekFrom::End(0))?;
+        Ok(LogWriter {
+            inner: BufWriter::new(file),
+        })
+    }
+}
+
+impl<W: Write> LogWriter<W> {
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(MAGIC)?;
+        inner.write_all(&VERSION.to_le_bytes())?;
+        Ok(LogWriter { inner })
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let key_len = length::<u16>(record.key.len(), "key")?;
+        let encoding_len = length::<u16>(record.encoding.len(), "encoding")?;
+        let payload_len = length::<u32>(record.payload.len(), "payload")?;
+
+        self.inner.write_all(&record.timestamp_ns.to_le_bytes())?;
+        self.inner.write_all(&key_len.to_le_bytes())?;
+        self.inner.write_all(record.key.as_bytes())?;
+        self.inner.write_all(&encoding_len.to_le_bytes())?;
+        self.inner.write_all(record.encoding.as_bytes())?;
+        self.inner.write_all(&payload_len.to_le_bytes())?;
+        self.inner.write_all(&record.payload)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+// Bytes a record takes in the log.
+pub(crate) fn encoded_len(record: &Record) -> u64 {
+    (8 + 2 + record.key.len() + 2 + record.encoding.len() + 4 + record.payload.len()) as u64
+}
+
+fn length<T: TryFrom<usize>>(len: usize, field: &str) -> io::Result<T> {
+    T::try_from(len).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} of {} bytes is too long for a log record", field, len),
+        )
+    })
+}
@@ -0,0 +1,299 @@
+use common::{attitude, encoding};
+use foxglove::Schema;
+use foxglove::schemas::{FrameTransform, LocationFix, Log, Quaternion, Timestamp, Vector3, log};
+use sensor_decode::MessageType;
+use sensors_rs::sensors;
+use serde_json::{Map, Value, json};
+use zenoh::sample::Sample;
+
+// Frames the fused state's transform is published between: the vehicle's body in a
+// local east-north-up world frame whose origin is on the ground below it.
+pub const WORLD_FRAME: &str = "world";
+pub const BODY_FRAME: &str = "body";
+
+// The JSON schemas the bridge advertises, one per kind of JSON message.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum JsonSchema {
+    Sensor(MessageType),
+    FusedState,
+    PhaseState,
+    Heartbeat,
+    // Any other JSON telemetry_json converts, such as the simulator's truth or JSON
+    // published as such on the bus, without a schema.
+    None,
+}
+
+impl JsonSchema {
+    pub fn schema(self) -> Option<Schema> {
+        let (name, properties) = match self {
+            JsonSchema::Sensor(kind) => (
+                kind.table(),
+                kind.channels()
+                    .iter()
+                    .map(|channel| (channel.to_string(), json!({"type": "number"})))
+                    .collect(),
+            ),
+            JsonSchema::FusedState => (
+                "sensors.FusedState",
+                properties(&[
+                    ("timestamp_us", json!({"type": "integer"})),
+                    ("attitude", vec3_schema()),
+                    ("angular_rate", vec3_schema()),
+                    ("altitude", json!({"type": "number"})),
+                    ("velocity", vec3_schema()),
+                    ("acceleration", vec3_schema()),
+                ]),
+            ),
+            JsonSchema::PhaseState => (
+                "sensors.PhaseState",
+                properties(&[
+                    ("timestamp_us", json!({"type": "integer"})),
+                    ("phase", json!({"type": "string"})),
+                    ("previous", json!({"type": "string"})),
+                    ("since_s", json!({"type": "number"})),
+                    ("reason", json!({"type": "string"})),
+                    ("max_altitude", json!({"type": "number"})),
+                ]),
+            ),
+            JsonSchema::Heartbeat => (
+                "sensors.Heartbeat",
+                properties(&[
+                    ("node", json!({"type": "string"})),
+                    ("uptime_s", json!({"type": "number"})),
+                    ("cycles", json!({"type": "integer"})),
+                    ("errors", json!({"type": "integer"})),
+                    ("mean_cycle_us", json!({"type": "number"})),
+                    ("max_cycle_us", json!({"type": "number"})),
+                ]),
+            ),
+            JsonSchema::None => return None,
+        };
+        let schema = json!({
+            "title": name,
+            "type": "object",
+            "properties": Value::Object(properties),
+        });
+        Some(Schema::new(
+            name,
+            "jsonschema",
+            serde_json::to_vec(&schema).unwrap(),
+        ))
+    }
+}
+
+fn properties(fields: &[(&str, Value)]) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect()
+}
+
+fn vec3_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "x": {"type": "number"},
+            "y": {"type": "number"},
+            "z": {"type": "number"},
+        },
+    })
+}
+
+// What a sample is shown as in Foxglove.
+pub enum Output {
+    // JSON on the topic named after the sample's key.
+    Json(JsonSchema, Value),
+    // A GNSS fix, on the key's topic with /fix appended, for the Map panel.
+    Location(LocationFix),
+    // Where the body frame is, for the 3D panel.
+    Transform(FrameTransform),
+    // An alarm, a crash or a node's logged warning or error, for the Log panel.
+    Log(Log),
+}
+
+fn timestamp(us: u64) -> Timestamp {
+    Timestamp::new((us / 1_000_000) as u32, (us % 1_000_000 * 1000) as u32)
+}
+
+// The sample as Foxglove messages, none if the bridge has no conversion for its payload.
+pub fn convert(sample: &Sample) -> Result<Vec<Output>, String> {
+    let key = sample.key_expr().as_str();
+    let encoding = sample.encoding();
+    let table = encoding::flatbuffer_table(encoding).unwrap_or_default();
+    let payload = sample.payload().to_bytes();
+    let malformed = |e: flatbuffers::InvalidFlatbuffer| format!("malformed {}: {}", table, e);
+    let mut outputs = Vec::with_capacity(2);
+    let schema = match table.as_str() {
+        "sensors.Alarm" => {
+            let alarm = flatbuffers::root::<sensors::Alarm>(&payload).map_err(malformed)?;
+            return Ok(vec![Output::Log(alarm_log(alarm))]);
+        }
+        "sensors.LogRecord" => {
+            let record = flatbuffers::root::<sensors::LogRecord>(&payload).map_err(malformed)?;
+            return Ok(vec![Output::Log(record_log(record))]);
+        }
+        "sensors.CrashReport" => {
+            let crash = flatbuffers::root::<sensors::CrashReport>(&payload).map_err(malformed)?;
+            return Ok(vec![Output::Log(crash_log(crash))]);
+        }
+        "sensors.FusedState" => {
+            let state = flatbuffers::root::<sensors::FusedState>(&payload).map_err(malformed)?;
+            outputs.extend(transform(state).map(Output::Transform));
+            JsonSchema::FusedState
+        }
+        "sensors.PhaseState" => JsonSchema::PhaseState,
+        "sensors.Heartbeat" => JsonSchema::Heartbeat,
+        _ => match MessageType::detect(key, encoding) {
+            Some(kind) => {
+                if kind == MessageType::Gnss {
+                    let values = sensor_decode::values(kind, encoding, sample.payload())?;
+                    outputs.extend(location(&values).map(Output::Location));
+                }
+                JsonSchema::Sensor(kind)
+            }
+            None => JsonSchema::None,
+        },
+    };
+    let Some(value) = telemetry_json::decode(sample)? else {
+        return Ok(Vec::new());
+    };
+    outputs.push(Output::Json(schema, value));
+    Ok(outputs)
+}
+
+// Where the body frame is by the fused state, rotated by the attitude and raised by the
+// altitude, if either is valid.
+fn transform(state: sensors::FusedState) -> Option<FrameTransform> {
+    let attitude = state.attitude().filter(|_| state.attitude_valid());
+    let altitude = state.altitude_valid().then(|| state.altitude());
+    if attitude.is_none() && altitude.is_none() {
+        return None;
+    }
+    let rotation = attitude.map_or(
+        Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        },
+        |a| {
+            let [x, y, z, w] = attitude::quaternion(a.x() as f64, a.y() as f64, a.z() as f64);
+            Quaternion { x, y, z, w }
+        },
+    );
+    Some(FrameTransform {
+        timestamp: Some(timestamp(state.timestamp_us())),
+        parent_frame_id: WORLD_FRAME.to_string(),
+        child_frame_id: BODY_FRAME.to_string(),
+        translation: Some(Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: altitude.unwrap_or_default() as f64,
+        }),
+        rotation: Some(rotation),
+    })
+}
+
+// A fix from the values of a GNSS sample, in sensor_decode's channel order, if it has one.
+fn location(values: &[f64]) -> Option<LocationFix> {
+    let [
+        timestamp_us,
+        fix_type,
+        latitude,
+        longitude,
+        altitude,
+        east,
+        north,
+        up,
+        ..,
+    ] = *values
+    else {
+        return None;
+    };
+    if fix_type == 0.0 {
+        return None;
+    }
+    Some(LocationFix {
+        timestamp: Some(timestamp(timestamp_us as u64)),
+        frame_id: BODY_FRAME.to_string(),
+        latitude,
+        longitude,
+        altitude,
+        velocity: Some(Vector3 {
+            x: east,
+            y: north,
+            z: up,
+        }),
+        ..Default::default()
+    })
+}
+
+fn alarm_log(alarm: sensors::Alarm) -> Log {
+    let level = match (alarm.active(), alarm.severity()) {
+        (false, _) => log::Level::Info,
+        (true, sensors::AlarmSeverity::Critical) => log::Level::Error,
+        (true, sensors::AlarmSeverity::Warning) => log::Level::Warning,
+        (true, _) => log::Level::Info,
+    };
+    let limit = match alarm.kind() {
+        sensors::AlarmKind::BelowMin => "below",
+        sensors::AlarmKind::AboveMax => "above",
+        _ => "changing faster than",
+    };
+    let channel = alarm.channel().unwrap_or_default();
+    let message = if alarm.active() {
+        format!(
+            "{} {:.3} {} {:.3}",
+            channel,
+            alarm.value(),
+            limit,
+            alarm.limit()
+        )
+    } else {
+        format!("{} back within limits", channel)
+    };
+    Log {
+        timestamp: Some(timestamp(alarm.timestamp_us())),
+        level: level as i32,
+        message,
+        name: alarm.source().unwrap_or_default().to_string(),
+        ..Default::default()
+    }
+}
+
+fn record_log(record: sensors::LogRecord) -> Log {
+    let level = match record.level() {
+        sensors::LogLevel::Error => log::Level::Error,
+        sensors::LogLevel::Warn => log::Level::Warning,
+        sensors::LogLevel::Info => log::Level::Info,
+        _ => log::Level::Debug,
+    };
+    Log {
+        timestamp: Some(timestamp(record.timestamp_us())),
+        level: level as i32,
+        message: record.message().unwrap_or_default().to_string(),
+        name: format!(
+            "{} {}",
+            record.node().unwrap_or_default(),
+            record.target().unwrap_or_default()
+        ),
+        ..Default::default()
+    }
+}
+
+fn crash_log(crash: sensors::CrashReport) -> Log {
+    // Raised at file:line:column.
+    let location = crash.location().unwrap_or_default();
+    let (file, line) = match location.rsplitn(3, ':').collect::<Vec<_>>()[..] {
+        [_, line, file] => (file, line.parse().unwrap_or_default()),
+        _ => (location, 0),
+    };
+    Log {
+        timestamp: Some(timestamp(crash.timestamp_us())),
+        level: log::Level::Fatal as i32,
+        message: format!("crashed: {}", crash.message().unwrap_or_default()),
+        name: crash.node().unwrap_or_default().to_string(),
+        file: file.to_string(),
+        line,
+    }
+}
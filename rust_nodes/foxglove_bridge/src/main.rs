@@ -0,0 +1,168 @@
+mod convert;
+
+use clap::Parser;
+use convert::{JsonSchema, Output};
+use foxglove::schemas::{FrameTransform, LocationFix, Log};
+use foxglove::{Channel, ChannelBuilder, RawChannel, WebSocketServer, WebSocketServerHandle};
+use node_framework::{Context, Event, Node, NodeArgs};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::sync::Arc;
+use tracing::{debug, info};
+use zenoh::sample::{Sample, SampleKind};
+
+// Topics of the messages not named after a key.
+const TRANSFORM_TOPIC: &str = "/tf";
+const LOG_TOPIC: &str = "/log";
+
+#[derive(Parser)]
+#[command(
+    about = "Serves the bus over the Foxglove WebSocket protocol, for live visualization in Foxglove"
+)]
+struct Args {
+    /// Key expression to bridge. May be repeated.
+    #[arg(long, default_values_t = ["**".to_string()])]
+    key: Vec<String>,
+    /// Address to serve the Foxglove WebSocket protocol on.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8765")]
+    ws_addr: SocketAddr,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct FoxgloveBridge {
+    keys: Vec<String>,
+    addr: SocketAddr,
+    // The channels advertised to Foxglove and the server they are served on.
+    context: Arc<foxglove::Context>,
+    server: Option<WebSocketServerHandle>,
+    // Channels are advertised on the first sample of their key, by topic.
+    json: HashMap<String, Arc<RawChannel>>,
+    locations: HashMap<String, Channel<LocationFix>>,
+    transforms: Channel<FrameTransform>,
+    log: Channel<Log>,
+    // Keys whose samples are not bridged, reported once each.
+    skipped: HashSet<String>,
+}
+
+impl FoxgloveBridge {
+    fn new(keys: Vec<String>, addr: SocketAddr) -> Self {
+        let context = foxglove::Context::new();
+        FoxgloveBridge {
+            keys,
+            addr,
+            transforms: ChannelBuilder::new(TRANSFORM_TOPIC)
+                .context(&context)
+                .build(),
+            log: ChannelBuilder::new(LOG_TOPIC).context(&context).build(),
+            context,
+            server: None,
+            json: HashMap::new(),
+            locations: HashMap::new(),
+            skipped: HashSet::new(),
+        }
+    }
+
+    fn on_sample(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = sample.key_expr().as_str();
+        let outputs = convert::convert(sample).map_err(|e| format!("{}: {}", key, e))?;
+        if outputs.is_empty() {
+            if self.skipped.insert(key.to_string()) {
+                debug!(
+                    "Not bridging {}: no conversion for {}",
+                    key,
+                    sample.encoding()
+                );
+            }
+            return Ok(());
+        }
+        let topic = format!("/{}", key);
+        for output in outputs {
+            match output {
+                Output::Json(schema, value) => {
+                    let channel = match self.json.get(&topic) {
+                        Some(channel) => channel,
+                        None => {
+                            let channel = self.advertise(&topic, schema)?;
+                            self.json.entry(topic.clone()).or_insert(channel)
+                        }
+                    };
+                    // Serializing is skipped while no client is subscribed.
+                    if channel.has_sinks() {
+                        channel.log(&serde_json::to_vec(&value)?);
+                    }
+                }
+                Output::Location(fix) => self
+                    .locations
+                    .entry(topic.clone())
+                    .or_insert_with(|| {
+                        ChannelBuilder::new(format!("{}/fix", topic))
+                            .context(&self.context)
+                            .build()
+                    })
+                    .log(&fix),
+                Output::Transform(transform) => self.transforms.log(&transform),
+                Output::Log(log) => self.log.log(&log),
+            }
+        }
+        Ok(())
+    }
+
+    fn advertise(&self, topic: &str, schema: JsonSchema) -> zenoh::Result<Arc<RawChannel>> {
+        let channel = ChannelBuilder::new(topic)
+            .message_encoding("json")
+            .schema(schema.schema())
+            .context(&self.context)
+            .build_raw()?;
+        Ok(channel)
+    }
+}
+
+impl Node for FoxgloveBridge {
+    const NAME: &'static str = "foxglove_bridge";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let server = WebSocketServer::new()
+            .name(ctx.name())
+            .bind(self.addr.ip().to_string(), self.addr.port())
+            .context(&self.context)
+            .start()
+            .await?;
+        info!(
+            "Serving Foxglove on ws://{}:{}",
+            self.addr.ip(),
+            server.port()
+        );
+        self.server = Some(server);
+        for key in &self.keys {
+            ctx.subscribe(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(&sample),
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(server) = self.server.take() {
+            server.stop().wait().await;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = FoxgloveBridge::new(args.key.clone(), args.ws_addr);
+    node_framework::run(node, &args.node).await
+}
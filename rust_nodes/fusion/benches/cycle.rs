@@ -0,0 +1,159 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use fusion::cache::SensorCache;
+use fusion::filter::{Ekf, EkfConfig, Filter, Reading};
+use fusion::parse::{AltitudeParser, GyroParser, ImuParser, SensorParser, contiguous};
+use sensors_rs::readings::{AltitudeReading, GyroReading, ImuReading, Reading as _};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use zenoh::bytes::ZBytes;
+
+// Counts every allocation, so the cycle can be checked to make none.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Imu,
+    Gyro,
+    Altitude,
+}
+
+// The sensors of config/default.toml: three IMUs, two gyros, and four altimeters, as key,
+// kind, and index into the measurement array.
+const SENSORS: [(&str, Kind, usize); 9] = [
+    ("devices/imu0", Kind::Imu, 0),
+    ("devices/imu1", Kind::Imu, 3),
+    ("devices/imu2", Kind::Imu, 6),
+    ("devices/gyro0", Kind::Gyro, 9),
+    ("devices/gyro1", Kind::Gyro, 12),
+    ("devices/altitude0", Kind::Altitude, 15),
+    ("devices/altitude1", Kind::Altitude, 16),
+    ("devices/altitude2", Kind::Altitude, 17),
+    ("devices/altitude3", Kind::Altitude, 18),
+];
+
+struct Loop {
+    cache: SensorCache,
+    values: [f32; 19],
+    scratch: Vec<u8>,
+    ekf: Ekf,
+}
+
+impl Loop {
+    // A loop whose cache holds one sample of every sensor, each received in one piece or,
+    // if fragmented, split in two as a payload reassembled by Zenoh is.
+    fn new(fragmented: bool) -> Self {
+        let imu = ImuReading {
+            acceleration: [0.12, -0.05, 9.81],
+        }
+        .encode();
+        let gyro = GyroReading {
+            omega: [0.01, 0.02, -0.03],
+        }
+        .encode();
+        let altitude = AltitudeReading { altitude: 12.5 }.encode();
+        let mut cache = SensorCache::default();
+        for (key, kind, _) in SENSORS {
+            let payload = match kind {
+                Kind::Imu => &imu,
+                Kind::Gyro => &gyro,
+                Kind::Altitude => &altitude,
+            };
+            cache.insert(key, zbytes(payload, fragmented));
+        }
+        Loop {
+            cache,
+            values: [0.0; 19],
+            scratch: Vec::new(),
+            ekf: Ekf::new(EkfConfig::default()),
+        }
+    }
+
+    // One 100 Hz cycle from the cache to the estimate, as fusion runs it in subscribe mode.
+    fn cycle(&mut self) {
+        for (key, kind, index) in SENSORS {
+            if let Some(payload) = self.cache.latest(key) {
+                let parser = match kind {
+                    Kind::Imu => ImuParser::parse,
+                    Kind::Gyro => GyroParser::parse,
+                    Kind::Altitude => AltitudeParser::parse,
+                };
+                let _ = parser(
+                    contiguous(payload, &mut self.scratch),
+                    &mut self.values,
+                    index,
+                );
+            }
+        }
+        self.ekf.propagate(0.01);
+        for (_, kind, i) in SENSORS {
+            let v = &self.values;
+            let reading = match kind {
+                Kind::Imu => Reading::Imu([v[i], v[i + 1], v[i + 2]]),
+                Kind::Gyro => Reading::Gyro([v[i], v[i + 1], v[i + 2]]),
+                Kind::Altitude => Reading::Altitude(v[i]),
+            };
+            self.ekf.update(&reading);
+        }
+        black_box(self.ekf.estimate());
+    }
+}
+
+fn zbytes(payload: &[u8], fragmented: bool) -> ZBytes {
+    if !fragmented {
+        return ZBytes::from(payload.to_vec());
+    }
+    let (head, tail) = payload.split_at(payload.len() / 2);
+    let mut writer = ZBytes::writer();
+    writer.append(ZBytes::from(head.to_vec()));
+    writer.append(ZBytes::from(tail.to_vec()));
+    writer.finish()
+}
+
+// Runs the cycle once to size the scratch buffer, then fails the bench if further cycles
+// allocate at all.
+fn assert_allocation_free(name: &str, fusion: &mut Loop) {
+    fusion.cycle();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..100 {
+        fusion.cycle();
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(
+        allocations, 0,
+        "{} allocated {} times in 100 cycles",
+        name, allocations
+    );
+}
+
+fn cycle(c: &mut Criterion) {
+    for (name, fragmented) in [("cycle", false), ("cycle_fragmented", true)] {
+        let mut fusion = Loop::new(fragmented);
+        assert_allocation_free(name, &mut fusion);
+        c.bench_function(name, |b| b.iter(|| fusion.cycle()));
+    }
+}
+
+criterion_group!(benches, cycle);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use fusion::filter::{Ekf, EkfConfig, Filter, Reading};
+use std::hint::black_box;
+
+// One cycle of the loop at 100 Hz with the sensors of config/default.toml: three IMUs, two
+// gyros, and four altimeters.
+fn step(ekf: &mut Ekf) {
+    ekf.propagate(0.01);
+    for _ in 0..3 {
+        ekf.update(black_box(&Reading::Imu([0.12, -0.05, 9.81])));
+    }
+    for _ in 0..2 {
+        ekf.update(black_box(&Reading::Gyro([0.01, 0.02, -0.03])));
+    }
+    for _ in 0..4 {
+        ekf.update(black_box(&Reading::Altitude(12.5)));
+    }
+    black_box(ekf.estimate());
+}
+
+fn ekf(c: &mut Criterion) {
+    c.bench_function("ekf_propagate", |b| {
+        let mut ekf = Ekf::new(EkfConfig::default());
+        b.iter(|| ekf.propagate(black_box(0.01)))
+    });
+    c.bench_function("ekf_update_imu", |b| {
+        let mut ekf = Ekf::new(EkfConfig::default());
+        b.iter(|| ekf.update(black_box(&Reading::Imu([0.12, -0.05, 9.81]))))
+    });
+    c.bench_function("ekf_step", |b| {
+        let mut ekf = Ekf::new(EkfConfig::default());
+        b.iter(|| step(&mut ekf))
+    });
+}
+
+criterion_group!(benches, ekf);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use fusion::state::FusedState;
+use sensors_rs::readings::{AltitudeReading, GyroReading, ImuReading, Reading};
+use std::hint::black_box;
+
+// Building the sensor tables, as publishers do for every sample, and the fused state
+// fusion publishes every cycle.
+fn encode(c: &mut Criterion) {
+    let imu = ImuReading {
+        acceleration: [0.12, -0.05, 9.81],
+    };
+    let gyro = GyroReading {
+        omega: [0.01, 0.02, -0.03],
+    };
+    let altitude = AltitudeReading { altitude: 1234.5 };
+    let state = FusedState {
+        attitude: [0.01, -0.02, 1.57],
+        angular_rate: [0.01, 0.02, -0.03],
+        velocity: [1.0, 2.0, 150.0],
+        acceleration: [0.1, 0.2, 30.0],
+        altitude: 1234.5,
+        attitude_valid: true,
+        angular_rate_valid: true,
+        velocity_valid: true,
+        acceleration_valid: true,
+        altitude_valid: true,
+    };
+
+    c.bench_function("encode_imu", |b| b.iter(|| black_box(&imu).encode()));
+    c.bench_function("encode_gyro", |b| b.iter(|| black_box(&gyro).encode()));
+    c.bench_function("encode_altitude", |b| {
+        b.iter(|| black_box(&altitude).encode())
+    });
+    c.bench_function("encode_fused_state", |b| {
+        b.iter(|| black_box(&state).encode(black_box(1_700_000_000_000_000)))
+    });
+}
+
+criterion_group!(benches, encode);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use fusion::parse::{AltitudeParser, GyroParser, ImuParser, SensorParser};
+use sensors_rs::readings::{AltitudeReading, GyroReading, ImuReading, Reading};
+use std::hint::black_box;
+
+// Decoding runs once per sensor per cycle of the 100 Hz loop.
+fn parse(c: &mut Criterion) {
+    let imu = ImuReading {
+        acceleration: [0.12, -0.05, 9.81],
+    }
+    .encode();
+    let gyro = GyroReading {
+        omega: [0.01, 0.02, -0.03],
+    }
+    .encode();
+    let altitude = AltitudeReading { altitude: 1234.5 }.encode();
+    let mut meas = [0.0; 3];
+
+    c.bench_function("parse_imu", |b| {
+        b.iter(|| ImuParser::parse(black_box(&imu), &mut meas, 0))
+    });
+    c.bench_function("parse_gyro", |b| {
+        b.iter(|| GyroParser::parse(black_box(&gyro), &mut meas, 0))
+    });
+    c.bench_function("parse_altitude", |b| {
+        b.iter(|| AltitudeParser::parse(black_box(&altitude), &mut meas, 0))
+    });
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
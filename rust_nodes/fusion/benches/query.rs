@@ -0,0 +1,145 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use futures::future::join_all;
+use sensors_rs::readings::{ImuReading, Reading as _};
+use std::net::TcpListener;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use zenoh::Wait;
+use zenoh::query::{ConsolidationMode, Querier, Queryable};
+
+// The sensors of config/default.toml, as fusion queries them every cycle in query mode.
+const KEYS: [&str; 9] = [
+    "devices/imu0",
+    "devices/imu1",
+    "devices/imu2",
+    "devices/gyro0",
+    "devices/gyro1",
+    "devices/altitude0",
+    "devices/altitude1",
+    "devices/altitude2",
+    "devices/altitude3",
+];
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+// A session on loopback only: no scouting, so nothing else on the network answers.
+async fn open(listen: &[&str], connect: &[&str]) -> zenoh::Session {
+    let mut config = zenoh::Config::default();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+    config
+        .insert_json5("listen/endpoints", &serde_json::to_string(listen).unwrap())
+        .unwrap();
+    config
+        .insert_json5(
+            "connect/endpoints",
+            &serde_json::to_string(connect).unwrap(),
+        )
+        .unwrap();
+    zenoh::open(config).await.unwrap()
+}
+
+// Sensors answering every query with an IMU reading from their own session, and the
+// session fusion queries them from, connected to it over TCP.
+async fn sessions() -> (zenoh::Session, zenoh::Session, Vec<Queryable<()>>) {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let endpoint = format!("tcp/127.0.0.1:{}", port);
+    let sensors = open(&[&endpoint], &[]).await;
+    let payload = ImuReading {
+        acceleration: [0.12, -0.05, 9.81],
+    }
+    .encode();
+    let mut queryables = Vec::new();
+    for key in KEYS {
+        let payload = payload.clone();
+        let queryable = sensors
+            .declare_queryable(key)
+            .callback(move |query| {
+                query
+                    .reply(query.key_expr().clone(), payload.clone())
+                    .wait()
+                    .unwrap();
+            })
+            .await
+            .unwrap();
+        queryables.push(queryable);
+    }
+    let fusion = open(&[], &[&endpoint]).await;
+    // Queries sent before the sessions have exchanged their declarations go unanswered.
+    loop {
+        let replies = fusion.get(KEYS[0]).await.unwrap();
+        if replies.recv_async().await.is_ok() {
+            break;
+        }
+    }
+    (sensors, fusion, queryables)
+}
+
+async fn session_get(session: &zenoh::Session, key: &str) {
+    let replies = session
+        .get(key)
+        .consolidation(ConsolidationMode::Latest)
+        .timeout(TIMEOUT)
+        .await
+        .unwrap();
+    replies.recv_async().await.unwrap();
+}
+
+async fn querier_get(querier: &Querier<'static>) {
+    let replies = querier.get().await.unwrap();
+    replies.recv_async().await.unwrap();
+}
+
+// One query, and one cycle's worth of concurrent queries, issued with session.get and with
+// queriers declared up front.
+fn query(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (sensors, fusion, queryables) = runtime.block_on(sessions());
+    let queriers: Vec<Querier<'static>> = runtime.block_on(async {
+        let mut queriers = Vec::new();
+        for key in KEYS {
+            let querier = fusion
+                .declare_querier(key)
+                .consolidation(ConsolidationMode::Latest)
+                .timeout(TIMEOUT)
+                .await
+                .unwrap();
+            queriers.push(querier);
+        }
+        queriers
+    });
+
+    for count in [1, KEYS.len()] {
+        let mut group = c.benchmark_group(format!("query_{}", count));
+        group.bench_function(BenchmarkId::from_parameter("session_get"), |b| {
+            b.iter(|| {
+                runtime.block_on(join_all(
+                    KEYS[..count].iter().map(|key| session_get(&fusion, key)),
+                ))
+            })
+        });
+        group.bench_function(BenchmarkId::from_parameter("querier_get"), |b| {
+            b.iter(|| runtime.block_on(join_all(queriers[..count].iter().map(querier_get))))
+        });
+        group.finish();
+    }
+
+    runtime.block_on(async {
+        for querier in queriers {
+            querier.undeclare().await.unwrap();
+        }
+        for queryable in queryables {
+            queryable.undeclare().await.unwrap();
+        }
+        fusion.close().await.unwrap();
+        sensors.close().await.unwrap();
+    });
+}
+
+criterion_group!(benches, query);
+criterion_main!(benches);
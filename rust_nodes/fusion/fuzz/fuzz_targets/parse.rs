@@ -0,0 +1,47 @@
+// Feeds arbitrary payloads to every sensor parser at arbitrary indexes into measurement
+// arrays of arbitrary length. The first bytes of the input pick the parser, the array
+// length, and the index; the rest is the payload.
+#![no_main]
+
+use fusion::parse::{
+    AltitudeParser, BarometerParser, GnssParser, GyroParser, ImuParser, MagnetometerParser,
+    ParseError, SensorParser,
+};
+use libfuzzer_sys::fuzz_target;
+
+type Parser = fn(&[u8], &mut [f32], usize) -> Result<(), ParseError>;
+
+const PARSERS: [(Parser, usize); 6] = [
+    (ImuParser::parse, ImuParser::STRIDE),
+    (GyroParser::parse, GyroParser::STRIDE),
+    (AltitudeParser::parse, AltitudeParser::STRIDE),
+    (BarometerParser::parse, BarometerParser::STRIDE),
+    (MagnetometerParser::parse, MagnetometerParser::STRIDE),
+    (GnssParser::parse, GnssParser::STRIDE),
+];
+
+const UNTOUCHED: f32 = -12345.0;
+
+fuzz_target!(|data: &[u8]| {
+    let [parser, len, idx, payload @ ..] = data else {
+        return;
+    };
+    let (parse, stride) = PARSERS[*parser as usize % PARSERS.len()];
+    let len = *len as usize % 32;
+    // Past the end as often as not, and now and then at the very end of the index range.
+    let idx = match idx {
+        255 => usize::MAX,
+        idx => *idx as usize % 48,
+    };
+    let mut meas = vec![UNTOUCHED; len];
+    let result = parse(payload, &mut meas, idx);
+    let fits = idx.checked_add(stride).is_some_and(|end| end <= len);
+    let written = |i: usize| result.is_ok() && i >= idx && i < idx + stride;
+    assert_eq!(
+        matches!(result, Err(ParseError::OutOfBounds { .. })),
+        !fits
+    );
+    for (i, &value) in meas.iter().enumerate() {
+        assert!(written(i) || value == UNTOUCHED, "entry {} written", i);
+    }
+});
@@ -0,0 +1,92 @@
+use crate::config::BarometerConfig;
+use common::atmosphere;
+use tracing::info;
+
+struct Barometer {
+    key: String,
+    index: usize,
+    // Pressure at the pad, in pascals, that heights are taken above.
+    reference: Option<f64>,
+    // Readings summed towards the reference while it is being taken.
+    sum: f64,
+    count: u32,
+}
+
+// Converts barometer pressures to height above the pad with the ISA standard atmosphere.
+// Each barometer's ground reference is the mean of its first readings, or the configured
+// pressure; while it is being taken the barometer's readings are not fused.
+pub struct Barometers {
+    samples: u32,
+    reference_pressure: Option<f64>,
+    barometers: Vec<Barometer>,
+}
+
+impl Barometers {
+    // Takes the key and measurement index of every barometer.
+    pub fn new(config: &BarometerConfig, barometers: Vec<(String, usize)>) -> Self {
+        let mut converter = Barometers {
+            samples: config.calibration_samples,
+            reference_pressure: config.reference_pressure,
+            barometers: Vec::with_capacity(barometers.len()),
+        };
+        for (key, index) in barometers {
+            converter.add(key, index);
+        }
+        converter
+    }
+
+    // Adds a barometer, which takes its ground reference like the others.
+    pub fn add(&mut self, key: String, index: usize) {
+        self.barometers.push(Barometer {
+            key,
+            index,
+            reference: self.reference_pressure,
+            sum: 0.0,
+            count: 0,
+        });
+    }
+
+    // Replaces the pressure of every barometer refreshed this cycle with its height above
+    // the pad, or marks it invalid while the reference is still being taken.
+    pub fn convert(&mut self, values: &mut [f32], valid: &mut [bool]) {
+        for barometer in &mut self.barometers {
+            let i = barometer.index;
+            if !valid[i] {
+                continue;
+            }
+            let pressure = values[i] as f64;
+            let height = match barometer.reference {
+                Some(reference) => atmosphere::height_above(pressure, reference),
+                None => {
+                    if pressure.is_finite() && pressure > 0.0 {
+                        barometer.sum += pressure;
+                        barometer.count += 1;
+                    }
+                    if barometer.count >= self.samples {
+                        let reference = barometer.sum / barometer.count as f64;
+                        info!(
+                            "Barometer {}: ground reference {:.1} Pa",
+                            barometer.key, reference
+                        );
+                        barometer.reference = Some(reference);
+                    }
+                    f64::NAN
+                }
+            };
+            values[i] = height as f32;
+            if !height.is_finite() {
+                valid[i..i + 2].fill(false);
+            }
+        }
+    }
+
+    // Sets every ground reference to the given pad pressure, or has each barometer take
+    // its own again from its next readings.
+    pub fn calibrate(&mut self, pressure: Option<f64>) {
+        for barometer in &mut self.barometers {
+            barometer.reference = pressure;
+            barometer.sum = 0.0;
+            barometer.count = 0;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use zenoh::bytes::ZBytes;
+
+// Latest payload per sensor key, fed from the node's sensor subscriptions as samples are
+// stepped, so reading the cache never touches the network.
+#[derive(Default)]
+pub struct SensorCache {
+    latest: HashMap<String, ZBytes>,
+}
+
+impl SensorCache {
+    // Replaces the payload of a key seen before in place, so only the first sample on a key
+    // allocates.
+    pub fn insert(&mut self, key: &str, payload: ZBytes) {
+        match self.latest.get_mut(key) {
+            Some(latest) => *latest = payload,
+            None => {
+                self.latest.insert(key.to_string(), payload);
+            }
+        }
+    }
+
+    // Forgets the payload of a key, e.g. of a sensor that went away, so it is not read
+    // again before the sensor publishes anew.
+    pub fn remove(&mut self, key: &str) {
+        self.latest.remove(key);
+    }
+
+    // Returns the most recent payload received on the key, if any. It is borrowed, as even
+    // a reference-counted clone allocates for a payload received in fragments.
+    pub fn latest(&self, key: &str) -> Option<&ZBytes> {
+        self.latest.get(key)
+    }
+}
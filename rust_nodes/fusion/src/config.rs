@@ -0,0 +1,434 @@
+use common::config;
+use common::qos::QosConfig;
+use fusion::parse::{
+    AltitudeParser, BarometerParser, GnssParser, GyroParser, ImuParser, MagnetometerParser,
+    SensorParser,
+};
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+use zenoh::query::{ConsolidationMode, QueryTarget};
+
+// Topology used when no config file is given: 3 IMUs, 2 gyroscopes, 4 altimeters, and
+// no barometer, magnetometer, or GNSS receiver.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+// Why a sensor topology could not be loaded or planned.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error(transparent)]
+    Load(#[from] config::ConfigError),
+    // A value the topology cannot be run with.
+    #[error("{0}")]
+    Invalid(String),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    Imu,
+    Gyro,
+    Altitude,
+    Barometer,
+    Magnetometer,
+    Gnss,
+}
+
+impl SensorKind {
+    pub const ALL: [SensorKind; 6] = [
+        SensorKind::Imu,
+        SensorKind::Gyro,
+        SensorKind::Altitude,
+        SensorKind::Barometer,
+        SensorKind::Magnetometer,
+        SensorKind::Gnss,
+    ];
+
+    // Name used in the config and in published statuses.
+    pub fn name(self) -> &'static str {
+        match self {
+            SensorKind::Imu => "imu",
+            SensorKind::Gyro => "gyro",
+            SensorKind::Altitude => "altitude",
+            SensorKind::Barometer => "barometer",
+            SensorKind::Magnetometer => "magnetometer",
+            SensorKind::Gnss => "gnss",
+        }
+    }
+
+    // Number of measurement vector entries a sensor of this kind occupies, as its parser
+    // writes them.
+    pub fn stride(self) -> usize {
+        match self {
+            SensorKind::Imu => ImuParser::STRIDE,
+            SensorKind::Gyro => GyroParser::STRIDE,
+            SensorKind::Altitude => AltitudeParser::STRIDE,
+            SensorKind::Barometer => BarometerParser::STRIDE,
+            SensorKind::Magnetometer => MagnetometerParser::STRIDE,
+            SensorKind::Gnss => GnssParser::STRIDE,
+        }
+    }
+
+    // Number of leading entries voting compares: all of them but a barometer's temperature
+    // and a GNSS fix's quality.
+    pub fn voted(self) -> usize {
+        match self {
+            SensorKind::Barometer => 1,
+            SensorKind::Gnss => 4,
+            kind => kind.stride(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SensorConfig {
+    #[serde(rename = "type")]
+    pub kind: SensorKind,
+    // Full key expression the sensor is queried or subscribed on.
+    pub key: String,
+    // Expected update rate. Sensors are not polled more often than this.
+    pub rate_hz: f32,
+    // First entry of the measurement vector the sensor's values are written to.
+    pub index: usize,
+    // Magnetometers only: calibration applied to every reading, see Calibration.
+    pub hard_iron: Option<[f32; 3]>,
+    pub soft_iron: Option<[[f32; 3]; 3]>,
+}
+
+impl SensorConfig {
+    pub fn calibration(&self) -> Option<Calibration> {
+        if self.hard_iron.is_none() && self.soft_iron.is_none() {
+            return None;
+        }
+        Some(Calibration {
+            hard_iron: self.hard_iron.unwrap_or([0.0; 3]),
+            soft_iron: self.soft_iron.unwrap_or([
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+            ]),
+        })
+    }
+}
+
+// Magnetometer calibration: the hard-iron offset is the field of magnetized parts of the
+// airframe, subtracted from the reading, and the soft-iron matrix undoes the distortion of
+// nearby soft metal, applied after. Both are in the sensor's body frame, the offset in
+// microtesla.
+#[derive(Clone, Copy, Debug)]
+pub struct Calibration {
+    pub hard_iron: [f32; 3],
+    pub soft_iron: [[f32; 3]; 3],
+}
+
+impl Calibration {
+    // Calibrates a raw reading in place.
+    pub fn apply(&self, field: &mut [f32]) {
+        let raw: [f32; 3] = std::array::from_fn(|i| field[i] - self.hard_iron[i]);
+        for (value, row) in field.iter_mut().zip(&self.soft_iron) {
+            *value = row.iter().zip(&raw).map(|(m, b)| m * b).sum();
+        }
+    }
+}
+
+// Largest distance a reading may be from the median of its kind before it is outvoted.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct VotingConfig {
+    pub imu: f32,
+    pub gyro: f32,
+    pub altitude: f32,
+    pub barometer: f32,
+    pub magnetometer: f32,
+    pub gnss: f32,
+}
+
+impl VotingConfig {
+    pub fn threshold(&self, kind: SensorKind) -> f32 {
+        match kind {
+            SensorKind::Imu => self.imu,
+            SensorKind::Gyro => self.gyro,
+            SensorKind::Altitude => self.altitude,
+            SensorKind::Barometer => self.barometer,
+            SensorKind::Magnetometer => self.magnetometer,
+            SensorKind::Gnss => self.gnss,
+        }
+    }
+
+    pub fn set_threshold(&mut self, kind: SensorKind, threshold: f32) {
+        match kind {
+            SensorKind::Imu => self.imu = threshold,
+            SensorKind::Gyro => self.gyro = threshold,
+            SensorKind::Altitude => self.altitude = threshold,
+            SensorKind::Barometer => self.barometer = threshold,
+            SensorKind::Magnetometer => self.magnetometer = threshold,
+            SensorKind::Gnss => self.gnss = threshold,
+        }
+    }
+}
+
+impl Default for VotingConfig {
+    fn default() -> Self {
+        VotingConfig {
+            imu: 5.0,
+            gyro: 0.5,
+            altitude: 20.0,
+            barometer: 20.0,
+            magnetometer: 10.0,
+            gnss: 20.0,
+        }
+    }
+}
+
+// How replies from several sources or samples of a key are merged, see Zenoh's
+// ConsolidationMode.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Consolidation {
+    Auto,
+    None,
+    Monotonic,
+    #[default]
+    Latest,
+}
+
+impl Consolidation {
+    pub fn mode(self) -> ConsolidationMode {
+        match self {
+            Consolidation::Auto => ConsolidationMode::Auto,
+            Consolidation::None => ConsolidationMode::None,
+            Consolidation::Monotonic => ConsolidationMode::Monotonic,
+            Consolidation::Latest => ConsolidationMode::Latest,
+        }
+    }
+}
+
+// Which of the queryables matching a key answer a query, see Zenoh's QueryTarget.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Target {
+    #[default]
+    BestMatching,
+    All,
+    AllComplete,
+}
+
+impl Target {
+    pub fn target(self) -> QueryTarget {
+        match self {
+            Target::BestMatching => QueryTarget::BestMatching,
+            Target::All => QueryTarget::All,
+            Target::AllComplete => QueryTarget::AllComplete,
+        }
+    }
+}
+
+// How the sensors of one kind are queried in query mode.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryOptions {
+    // How long a query waits for its reply, in milliseconds. Absent, --query-timeout-ms.
+    pub timeout_ms: Option<u64>,
+    pub consolidation: Consolidation,
+    pub target: Target,
+}
+
+impl QueryOptions {
+    pub fn timeout(&self, default: Duration) -> Duration {
+        self.timeout_ms.map_or(default, Duration::from_millis)
+    }
+}
+
+// Query options of every sensor kind, so slow sensors can be given longer deadlines
+// without holding up the fast ones.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryConfig {
+    pub imu: QueryOptions,
+    pub gyro: QueryOptions,
+    pub altitude: QueryOptions,
+    pub barometer: QueryOptions,
+    pub magnetometer: QueryOptions,
+    pub gnss: QueryOptions,
+}
+
+impl QueryConfig {
+    pub fn options(&self, kind: SensorKind) -> QueryOptions {
+        match kind {
+            SensorKind::Imu => self.imu,
+            SensorKind::Gyro => self.gyro,
+            SensorKind::Altitude => self.altitude,
+            SensorKind::Barometer => self.barometer,
+            SensorKind::Magnetometer => self.magnetometer,
+            SensorKind::Gnss => self.gnss,
+        }
+    }
+}
+
+// Where barometer heights are measured from.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BarometerConfig {
+    // Pressure at the pad in pascals. Absent, each barometer takes the mean of its first
+    // calibration_samples readings, so fusion should start on the pad.
+    pub reference_pressure: Option<f64>,
+    pub calibration_samples: u32,
+}
+
+impl Default for BarometerConfig {
+    fn default() -> Self {
+        BarometerConfig {
+            reference_pressure: None,
+            calibration_samples: 50,
+        }
+    }
+}
+
+// Which GNSS fixes are fused and where their altitude is measured from.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct GnssConfig {
+    // Altitude of the pad above mean sea level, in meters, that fixes are converted to
+    // altitude above the pad against. Absent, each receiver's first usable fix is taken
+    // against the altitude fused at that moment, so receivers should acquire on the pad.
+    pub site_altitude: Option<f64>,
+    // Fixes with fewer satellites, or a larger horizontal or vertical DOP, are not fused.
+    pub min_satellites: u8,
+    pub max_dop: f32,
+    // A receiver counts as having lost its fix once it has gone this long without a new
+    // usable one, in seconds.
+    pub fix_timeout_s: f32,
+}
+
+impl Default for GnssConfig {
+    fn default() -> Self {
+        GnssConfig {
+            site_altitude: None,
+            min_satellites: 5,
+            max_dop: 5.0,
+            fix_timeout_s: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FusionConfig {
+    pub sensors: Vec<SensorConfig>,
+    #[serde(default)]
+    pub voting: VotingConfig,
+    // Earth's magnetic field at the launch site, east-north-up in microtesla, that
+    // magnetometer headings are taken against. Only its direction matters.
+    #[serde(default = "default_magnetic_field")]
+    pub magnetic_field: [f64; 3],
+    #[serde(default)]
+    pub barometer: BarometerConfig,
+    #[serde(default)]
+    pub gnss: GnssConfig,
+    #[serde(default)]
+    pub query: QueryConfig,
+    #[serde(default)]
+    pub qos: QosConfig,
+}
+
+// Matches the simulators: 20 µT north and 45 µT down.
+fn default_magnetic_field() -> [f64; 3] {
+    [0.0, 20.0, -45.0]
+}
+
+impl FusionConfig {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let config: FusionConfig = config::load_toml(path)?;
+        config.validate().map_err(ConfigError::Invalid)?;
+        Ok(config)
+    }
+
+    // Size of the measurement vector needed to hold every configured sensor.
+    pub fn measurement_len(&self) -> usize {
+        self.sensors
+            .iter()
+            .map(|s| s.index + s.kind.stride())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.qos.validate()?;
+        for kind in SensorKind::ALL {
+            let threshold = self.voting.threshold(kind);
+            if threshold.is_nan() || threshold <= 0.0 {
+                return Err(format!(
+                    "voting threshold of {} must be positive",
+                    kind.name()
+                ));
+            }
+        }
+        let [east, north, up] = self.magnetic_field;
+        if ![east, north, up].iter().all(|v| v.is_finite()) || east.hypot(north) == 0.0 {
+            return Err("magnetic_field must be finite with a horizontal component".to_string());
+        }
+        for kind in SensorKind::ALL {
+            if self.query.options(kind).timeout_ms == Some(0) {
+                return Err(format!("query.{}.timeout_ms must be positive", kind.name()));
+            }
+        }
+        let barometer = &self.barometer;
+        if barometer
+            .reference_pressure
+            .is_some_and(|pressure| !pressure.is_finite() || pressure <= 0.0)
+        {
+            return Err("barometer.reference_pressure must be positive".to_string());
+        }
+        if barometer.calibration_samples == 0 {
+            return Err("barometer.calibration_samples must be positive".to_string());
+        }
+        let gnss = &self.gnss;
+        if gnss
+            .site_altitude
+            .is_some_and(|altitude| !altitude.is_finite())
+        {
+            return Err("gnss.site_altitude must be finite".to_string());
+        }
+        if gnss.max_dop.is_nan() || gnss.max_dop <= 0.0 {
+            return Err("gnss.max_dop must be positive".to_string());
+        }
+        if gnss.fix_timeout_s.is_nan() || gnss.fix_timeout_s <= 0.0 {
+            return Err("gnss.fix_timeout_s must be positive".to_string());
+        }
+        let mut owner: Vec<Option<&str>> = vec![None; self.measurement_len()];
+        for sensor in &self.sensors {
+            if sensor.rate_hz.is_nan() || sensor.rate_hz <= 0.0 {
+                return Err(format!("{}: rate_hz must be positive", sensor.key));
+            }
+            if let Some(calibration) = sensor.calibration() {
+                if sensor.kind != SensorKind::Magnetometer {
+                    return Err(format!(
+                        "{}: only magnetometers take hard_iron and soft_iron",
+                        sensor.key
+                    ));
+                }
+                let values = calibration.soft_iron.iter().flatten();
+                if !values.chain(&calibration.hard_iron).all(|v| v.is_finite()) {
+                    return Err(format!("{}: calibration must be finite", sensor.key));
+                }
+            }
+            for slot in &mut owner[sensor.index..sensor.index + sensor.kind.stride()] {
+                if let Some(other) = slot {
+                    return Err(format!(
+                        "{} overlaps {} in the measurement vector",
+                        sensor.key, other
+                    ));
+                }
+                *slot = Some(&sensor.key);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for FusionConfig {
+    fn default() -> Self {
+        toml::from_str(DEFAULT_CONFIG).expect("Built-in fusion config is invalid.")
+    }
+}
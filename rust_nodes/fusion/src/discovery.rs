@@ -0,0 +1,58 @@
+use crate::config::SensorKind;
+use sensors_rs::sensors;
+
+// The kind of sensor a device key names by its last chunk, as the simulators name them:
+// devices/imu0, devices/baro1, and so on.
+pub fn kind_of(key: &str) -> Option<SensorKind> {
+    let device = key.rsplit('/').next()?;
+    match device.trim_end_matches(|c: char| c.is_ascii_digit()) {
+        "imu" => Some(SensorKind::Imu),
+        "gyro" => Some(SensorKind::Gyro),
+        "altitude" => Some(SensorKind::Altitude),
+        "baro" => Some(SensorKind::Barometer),
+        "mag" => Some(SensorKind::Magnetometer),
+        "gnss" => Some(SensorKind::Gnss),
+        _ => None,
+    }
+}
+
+// Rate an unconfigured sensor is read at once discovered, the simulators' default for its
+// kind.
+pub fn rate_hz(kind: SensorKind) -> f32 {
+    match kind {
+        SensorKind::Barometer => 50.0,
+        SensorKind::Gnss => 10.0,
+        _ => 100.0,
+    }
+}
+
+// Serializes a finished sensors::ActiveSensors FlatBuffer from the key, kind, and first
+// measurement index of every sensor read.
+pub fn encode(timestamp_us: u64, active: &[(&str, SensorKind, usize)]) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(256);
+    let active: Vec<_> = active
+        .iter()
+        .map(|&(key, kind, index)| {
+            let key = builder.create_string(key);
+            let kind = builder.create_string(kind.name());
+            sensors::ActiveSensor::create(
+                &mut builder,
+                &sensors::ActiveSensorArgs {
+                    key: Some(key),
+                    kind: Some(kind),
+                    index: index as u32,
+                },
+            )
+        })
+        .collect();
+    let active = builder.create_vector(&active);
+    let status = sensors::ActiveSensors::create(
+        &mut builder,
+        &sensors::ActiveSensorsArgs {
+            timestamp_us,
+            sensors: Some(active),
+        },
+    );
+    builder.finish(status, None);
+    builder.finished_data().to_vec()
+}
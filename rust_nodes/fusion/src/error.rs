@@ -0,0 +1,55 @@
+use crate::config::ConfigError;
+use flatbuffers::InvalidFlatbuffer;
+use fusion::parse::ParseError;
+use std::time::Duration;
+use thiserror::Error;
+
+// Why fusion could not start, or could not handle an event. Errors of a cycle carry the
+// number of the cycle they happened in.
+#[derive(Debug, Error)]
+pub enum FusionError {
+    #[error("invalid fusion config: {0}")]
+    Config(#[from] ConfigError),
+    #[error("unknown {variable} '{value}', expected 'query' or 'subscribe'")]
+    Mode {
+        variable: &'static str,
+        value: String,
+    },
+    #[error("fusion cycle before init")]
+    NotInitialized,
+    #[error("malformed clock tick: {0}")]
+    MalformedClock(#[from] InvalidFlatbuffer),
+    #[error("cycle {cycle}: failed to publish {what}: {source}")]
+    Publish {
+        what: &'static str,
+        cycle: u64,
+        source: zenoh::Error,
+    },
+}
+
+impl FusionError {
+    // For map_err on the publication of what in the given cycle.
+    pub fn publish(what: &'static str, cycle: u64) -> impl FnOnce(zenoh::Error) -> FusionError {
+        move |source| FusionError::Publish {
+            what,
+            cycle,
+            source,
+        }
+    }
+}
+
+// Why a sensor read in a cycle gave no values.
+#[derive(Debug, Error)]
+pub enum SensorError {
+    // The query received no reply within its timeout.
+    #[error("no sample found after {} ms", .0.as_millis())]
+    Timeout(Duration),
+    // The query or its reply failed.
+    #[error("error in query: {0}")]
+    Query(zenoh::Error),
+    // The cache has not received a sample on the key yet.
+    #[error("no sample received yet")]
+    Empty,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
@@ -0,0 +1,58 @@
+use sensors_rs::sensors;
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+// How long the exclusions are kept without a status from the FDIR manager, which publishes
+// at least once a second while it runs.
+const STATUS_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Sensors the FDIR manager has taken out of the measurement model, as last published on
+// fdir/status.
+#[derive(Default)]
+pub struct Exclusions {
+    keys: BTreeSet<String>,
+    received: Option<Instant>,
+}
+
+impl Exclusions {
+    // Replaces the exclusions with the isolated and recovering sensors of a status.
+    // Returns whether they changed.
+    pub fn update(&mut self, payload: &[u8], now: Instant) -> Result<bool, String> {
+        let status = flatbuffers::root::<sensors::FdirStatus>(payload)
+            .map_err(|e| format!("malformed FDIR status: {}", e))?;
+        let keys: BTreeSet<String> = status
+            .sensors()
+            .into_iter()
+            .flatten()
+            .filter(|sensor| sensor.health() != sensors::SensorHealth::Healthy)
+            .filter_map(|sensor| sensor.key().map(str::to_string))
+            .collect();
+        self.received = Some(now);
+        let changed = keys != self.keys;
+        self.keys = keys;
+        Ok(changed)
+    }
+
+    // Clears the exclusions if the FDIR manager has gone quiet, so a crashed manager cannot
+    // keep sensors out for good. Returns whether any were cleared.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let stale = self
+            .received
+            .is_some_and(|received| now - received > STATUS_TIMEOUT);
+        if !stale {
+            return false;
+        }
+        self.received = None;
+        let cleared = !self.keys.is_empty();
+        self.keys.clear();
+        cleared
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+}
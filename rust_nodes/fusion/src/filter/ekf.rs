@@ -0,0 +1,317 @@
+use super::{Filter, Reading};
+use crate::state::FusedState;
+use nalgebra::{Rotation3, SMatrix, SVector, Vector3};
+use std::f64::consts::PI;
+
+// State layout. Attitude is ZYX Euler angles (roll, pitch, yaw) of the body frame relative
+// to a local east-north-up frame, with the body z axis along the vehicle's long axis so a
+// vehicle standing on the pad sits at zero attitude. Body rates and world-frame kinematic
+// acceleration are carried as random-walk states and observed by the gyros and IMUs.
+const N: usize = 13;
+const ATT: usize = 0;
+const RATE: usize = 3;
+const VEL: usize = 6;
+const ALT: usize = 9;
+const ACC: usize = 10;
+
+const GRAVITY: f64 = 9.81;
+// Step used for the numerical Jacobians of the nonlinear models.
+const JACOBIAN_EPS: f64 = 1e-5;
+// Smallest share of a magnetometer reading that must lie in the levelled horizontal plane
+// for a heading to be taken from it.
+const MIN_HORIZONTAL_FIELD: f64 = 0.1;
+// Headings farther from the predicted yaw than this many standard deviations of the
+// innovation are taken for a disturbed or miscalibrated field rather than a turn, and left
+// out; fused regardless, they would drag the tilt along through its correlation with yaw.
+const HEADING_GATE: f64 = 5.0;
+
+type State = SVector<f64, N>;
+type Covariance = SMatrix<f64, N, N>;
+
+// Noise parameters of the filter, expressed as standard deviations.
+#[derive(Clone, Debug)]
+pub struct EkfConfig {
+    // Process noise spectral densities.
+    pub attitude_noise: f64,
+    pub rate_noise: f64,
+    pub velocity_noise: f64,
+    pub altitude_noise: f64,
+    // Horizontal acceleration is kept tight: without horizontal aiding it is only separable
+    // from tilt by assuming the vehicle mostly accelerates along the vertical.
+    pub horizontal_accel_noise: f64,
+    pub vertical_accel_noise: f64,
+    // Measurement noise of a single sensor.
+    pub imu_sigma: f64,
+    pub gyro_sigma: f64,
+    pub altitude_sigma: f64,
+    // Noise of the heading taken from a magnetometer, in radians.
+    pub magnetometer_sigma: f64,
+    // Reference magnetic field, east-north-up; only its direction is used.
+    pub magnetic_field: [f64; 3],
+    // Noise of GNSS altitude and velocity at a DOP of 1, scaled by the fix's DOP.
+    pub gnss_altitude_sigma: f64,
+    pub gnss_velocity_sigma: f64,
+    // An estimate is flagged valid once its standard deviation drops below these.
+    pub attitude_valid_sigma: f64,
+    pub rate_valid_sigma: f64,
+    pub velocity_valid_sigma: f64,
+    pub acceleration_valid_sigma: f64,
+    pub altitude_valid_sigma: f64,
+}
+
+impl Default for EkfConfig {
+    // Measurement noise matches the mock sensor covariances in python_nodes/.
+    fn default() -> Self {
+        EkfConfig {
+            attitude_noise: 1e-3,
+            rate_noise: 1.0,
+            velocity_noise: 0.1,
+            altitude_noise: 0.1,
+            horizontal_accel_noise: 0.5,
+            vertical_accel_noise: 10.0,
+            imu_sigma: 10.0,
+            gyro_sigma: 0.1,
+            altitude_sigma: 1.0,
+            magnetometer_sigma: 0.05,
+            magnetic_field: [0.0, 20.0, -45.0],
+            gnss_altitude_sigma: 2.0,
+            gnss_velocity_sigma: 0.2,
+            attitude_valid_sigma: 0.1,
+            rate_valid_sigma: 0.1,
+            velocity_valid_sigma: 2.0,
+            acceleration_valid_sigma: 5.0,
+            altitude_valid_sigma: 5.0,
+        }
+    }
+}
+
+pub struct Ekf {
+    config: EkfConfig,
+    x: State,
+    p: Covariance,
+    // Whether a magnetometer heading was fused, making yaw observable.
+    heading_aided: bool,
+}
+
+impl Ekf {
+    pub fn new(config: EkfConfig) -> Self {
+        let mut p = Covariance::zeros();
+        for (range, var) in [
+            (ATT..ATT + 3, 1.0),
+            (RATE..RATE + 3, 1.0),
+            (VEL..VEL + 3, 100.0),
+            (ALT..ALT + 1, 100.0),
+            (ACC..ACC + 2, 1.0),
+            (ACC + 2..ACC + 3, 100.0),
+        ] {
+            for i in range {
+                p[(i, i)] = var;
+            }
+        }
+        Ekf {
+            config,
+            x: State::zeros(),
+            p,
+            heading_aided: false,
+        }
+    }
+
+    // Generic EKF correction for a measurement model h with per-axis noise sigma.
+    fn correct<const M: usize>(
+        &mut self,
+        z: SVector<f64, M>,
+        sigma: f64,
+        h: fn(&State) -> SVector<f64, M>,
+    ) {
+        let jac = jacobian(h, &self.x);
+        let r = SMatrix::<f64, M, M>::identity() * sigma * sigma;
+        let s = jac * self.p * jac.transpose() + r;
+        let Some(s_inv) = s.try_inverse() else {
+            return;
+        };
+        let k = self.p * jac.transpose() * s_inv;
+        let innovation = z - h(&self.x);
+        self.x += k * innovation;
+        // Joseph form keeps the covariance symmetric and positive definite.
+        let i_kh = Covariance::identity() - k * jac;
+        self.p = i_kh * self.p * i_kh.transpose() + k * r * k.transpose();
+        self.x[ATT] = wrap_angle(self.x[ATT]);
+        self.x[ATT + 2] = wrap_angle(self.x[ATT + 2]);
+    }
+
+    // Yaw at which the reference field, seen at the estimated roll and pitch, points the
+    // way the body-frame field b does. None when b is too close to the levelled vertical
+    // for its direction in the horizontal plane to mean anything.
+    fn heading(&self, b: [f32; 3]) -> Option<f64> {
+        let b = Vector3::new(b[0] as f64, b[1] as f64, b[2] as f64);
+        let level = Rotation3::from_euler_angles(self.x[ATT], self.x[ATT + 1], 0.0) * b;
+        let horizontal = level.x.hypot(level.y);
+        if !horizontal.is_finite() || horizontal <= MIN_HORIZONTAL_FIELD * level.norm() {
+            return None;
+        }
+        let [east, north, _] = self.config.magnetic_field;
+        Some(north.atan2(east) - level.y.atan2(level.x))
+    }
+
+    fn sigma(&self, i: usize) -> f64 {
+        self.p[(i, i)].sqrt()
+    }
+}
+
+impl Filter for Ekf {
+    fn propagate(&mut self, dt: f32) {
+        let dt = dt as f64;
+        if dt <= 0.0 {
+            return;
+        }
+        let f = jacobian(|x| transition(x, dt), &self.x);
+        self.x = transition(&self.x, dt);
+        self.x[ATT] = wrap_angle(self.x[ATT]);
+        self.x[ATT + 2] = wrap_angle(self.x[ATT + 2]);
+
+        let c = &self.config;
+        let mut q = Covariance::zeros();
+        for (range, sigma) in [
+            (ATT..ATT + 3, c.attitude_noise),
+            (RATE..RATE + 3, c.rate_noise),
+            (VEL..VEL + 3, c.velocity_noise),
+            (ALT..ALT + 1, c.altitude_noise),
+            (ACC..ACC + 2, c.horizontal_accel_noise),
+            (ACC + 2..ACC + 3, c.vertical_accel_noise),
+        ] {
+            for i in range {
+                q[(i, i)] = sigma * sigma * dt;
+            }
+        }
+        self.p = f * self.p * f.transpose() + q;
+    }
+
+    fn update(&mut self, reading: &Reading) {
+        match *reading {
+            Reading::Imu(f) => {
+                let z = Vector3::new(f[0] as f64, f[1] as f64, f[2] as f64);
+                self.correct(z, self.config.imu_sigma, specific_force);
+            }
+            Reading::Gyro(w) => {
+                let z = Vector3::new(w[0] as f64, w[1] as f64, w[2] as f64);
+                self.correct(z, self.config.gyro_sigma, |x| {
+                    x.fixed_rows::<3>(RATE).into()
+                });
+            }
+            Reading::Altitude(h) => {
+                let z = SVector::<f64, 1>::new(h as f64);
+                self.correct(z, self.config.altitude_sigma, |x| {
+                    SVector::<f64, 1>::new(x[ALT])
+                });
+            }
+            Reading::Magnetometer(b) => {
+                let Some(heading) = self.heading(b) else {
+                    return;
+                };
+                // Observed as a yaw measurement with the innovation wrapped, so the filter
+                // turns the short way around.
+                let yaw = self.x[ATT + 2];
+                let innovation = wrap_angle(heading - yaw);
+                let sigma = self.config.magnetometer_sigma;
+                let spread = (self.p[(ATT + 2, ATT + 2)] + sigma * sigma).sqrt();
+                if innovation.abs() > HEADING_GATE * spread {
+                    return;
+                }
+                let z = SVector::<f64, 1>::new(yaw + innovation);
+                self.correct(z, sigma, |x| SVector::<f64, 1>::new(x[ATT + 2]));
+                self.heading_aided = true;
+            }
+            Reading::Gnss {
+                altitude,
+                velocity,
+                hdop,
+                vdop,
+            } => {
+                let c = &self.config;
+                let (hdop, vdop) = (hdop as f64, vdop as f64);
+                let (altitude_sigma, velocity_sigma) =
+                    (c.gnss_altitude_sigma, c.gnss_velocity_sigma);
+                let horizontal = SVector::<f64, 2>::new(velocity[0] as f64, velocity[1] as f64);
+                self.correct(horizontal, velocity_sigma * hdop, |x| {
+                    x.fixed_rows::<2>(VEL).into()
+                });
+                let vertical = SVector::<f64, 1>::new(velocity[2] as f64);
+                self.correct(vertical, velocity_sigma * vdop, |x| {
+                    SVector::<f64, 1>::new(x[VEL + 2])
+                });
+                let z = SVector::<f64, 1>::new(altitude as f64);
+                self.correct(z, altitude_sigma * vdop, |x| SVector::<f64, 1>::new(x[ALT]));
+            }
+        }
+    }
+
+    fn estimate(&self) -> FusedState {
+        let c = &self.config;
+        let v3 = |i: usize| [self.x[i] as f32, self.x[i + 1] as f32, self.x[i + 2] as f32];
+        FusedState {
+            attitude: v3(ATT),
+            angular_rate: v3(RATE),
+            velocity: v3(VEL),
+            acceleration: v3(ACC),
+            altitude: self.x[ALT] as f32,
+            // Yaw is unobservable without a heading reference, so until a magnetometer
+            // provides one only tilt gates validity.
+            attitude_valid: (ATT..ATT + if self.heading_aided { 3 } else { 2 })
+                .all(|i| self.sigma(i) < c.attitude_valid_sigma),
+            angular_rate_valid: (RATE..RATE + 3).all(|i| self.sigma(i) < c.rate_valid_sigma),
+            velocity_valid: self.sigma(VEL + 2) < c.velocity_valid_sigma,
+            // Horizontal acceleration is held near zero by its tight process noise rather
+            // than observed, so as with velocity only the vertical gates validity.
+            acceleration_valid: self.sigma(ACC + 2) < c.acceleration_valid_sigma,
+            altitude_valid: self.sigma(ALT) < c.altitude_valid_sigma,
+        }
+    }
+}
+
+// Constant-rate, constant-acceleration motion model integrated over dt.
+fn transition(x: &State, dt: f64) -> State {
+    let (roll, pitch) = (x[ATT], x[ATT + 1]);
+    let (p, q, r) = (x[RATE], x[RATE + 1], x[RATE + 2]);
+    let (sr, cr) = roll.sin_cos();
+    // Clamp away from gimbal lock; the vehicle never flies horizontal in this frame.
+    let cp = pitch.cos().max(1e-3);
+    let tp = pitch.sin() / cp;
+
+    let mut next = *x;
+    next[ATT] += (p + sr * tp * q + cr * tp * r) * dt;
+    next[ATT + 1] += (cr * q - sr * r) * dt;
+    next[ATT + 2] += (sr / cp * q + cr / cp * r) * dt;
+    for i in 0..3 {
+        next[VEL + i] += x[ACC + i] * dt;
+    }
+    next[ALT] += x[VEL + 2] * dt + 0.5 * x[ACC + 2] * dt * dt;
+    next
+}
+
+// Specific force seen by a body-fixed accelerometer: kinematic acceleration minus gravity,
+// rotated into the body frame.
+fn specific_force(x: &State) -> SVector<f64, 3> {
+    let body_to_world = Rotation3::from_euler_angles(x[ATT], x[ATT + 1], x[ATT + 2]);
+    let accel = Vector3::new(x[ACC], x[ACC + 1], x[ACC + 2]);
+    body_to_world.inverse() * (accel - Vector3::new(0.0, 0.0, -GRAVITY))
+}
+
+// Central-difference Jacobian of f around x.
+fn jacobian<const M: usize>(
+    f: impl Fn(&State) -> SVector<f64, M>,
+    x: &State,
+) -> SMatrix<f64, M, N> {
+    let mut jac = SMatrix::<f64, M, N>::zeros();
+    for j in 0..N {
+        let mut hi = *x;
+        let mut lo = *x;
+        hi[j] += JACOBIAN_EPS;
+        lo[j] -= JACOBIAN_EPS;
+        jac.set_column(j, &((f(&hi) - f(&lo)) / (2.0 * JACOBIAN_EPS)));
+    }
+    jac
+}
+
+fn wrap_angle(a: f64) -> f64 {
+    (a + PI).rem_euclid(2.0 * PI) - PI
+}
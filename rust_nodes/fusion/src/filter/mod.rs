@@ -0,0 +1,34 @@
+mod ekf;
+
+pub use ekf::{Ekf, EkfConfig};
+
+use crate::state::FusedState;
+
+// A single decoded sensor sample handed to a filter's measurement update.
+#[derive(Clone, Copy, Debug)]
+pub enum Reading {
+    // Body-frame specific force [m/s^2].
+    Imu([f32; 3]),
+    // Body-frame angular rate [rad/s].
+    Gyro([f32; 3]),
+    // Altitude above the launch site [m].
+    Altitude(f32),
+    // Calibrated body-frame magnetic field [µT].
+    Magnetometer([f32; 3]),
+    // GNSS fix: altitude above the launch site [m] and east-north-up velocity [m/s], with
+    // the horizontal and vertical dilution of precision scaling their noise.
+    Gnss {
+        altitude: f32,
+        velocity: [f32; 3],
+        hdop: f32,
+        vdop: f32,
+    },
+}
+
+// State estimator driven by the fusion loop: propagate once per cycle by the elapsed time,
+// then apply one update per fresh sensor reading, then read out the estimate.
+pub trait Filter {
+    fn propagate(&mut self, dt: f32);
+    fn update(&mut self, reading: &Reading);
+    fn estimate(&self) -> FusedState;
+}
@@ -0,0 +1,170 @@
+use crate::config::GnssConfig;
+use sensors_rs::sensors;
+use std::fmt;
+use std::time::Duration;
+use tracing::info;
+
+// Offsets into a GNSS receiver's measurement entries, see SensorKind::stride.
+const ALTITUDE: usize = 0;
+const VELOCITY: usize = 1;
+const FIX_TYPE: usize = 4;
+const HDOP: usize = 5;
+const VDOP: usize = 6;
+const SATELLITES: usize = 7;
+const STRIDE: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    // No usable fix yet since startup.
+    Acquiring,
+    Fixed,
+    Lost,
+}
+
+struct Receiver {
+    key: String,
+    index: usize,
+    status: Status,
+    // Entries of the last fix fused, and the loop time it was read at.
+    last_fix: Option<([f32; STRIDE], Duration)>,
+    // Altitude of the pad above mean sea level as this receiver measures it.
+    datum: Option<f64>,
+}
+
+// Decides which GNSS fixes the filter sees. A fix is fused once, if it is a 3D fix with
+// enough satellites and small enough DOPs, with its altitude converted from mean sea level
+// to above the pad; everything else is marked invalid, leaving the barometric altitude to
+// carry the estimate. Receivers are reported when they first acquire, lose, and regain a
+// usable fix.
+pub struct GnssScreen {
+    config: GnssConfig,
+    receivers: Vec<Receiver>,
+}
+
+impl GnssScreen {
+    // Takes the key and first measurement index of every GNSS receiver.
+    pub fn new(config: GnssConfig, receivers: Vec<(String, usize)>) -> Self {
+        let mut screen = GnssScreen {
+            receivers: Vec::with_capacity(receivers.len()),
+            config,
+        };
+        for (key, index) in receivers {
+            screen.add(key, index);
+        }
+        screen
+    }
+
+    // Adds a receiver, acquiring until its first usable fix.
+    pub fn add(&mut self, key: String, index: usize) {
+        self.receivers.push(Receiver {
+            key,
+            index,
+            status: Status::Acquiring,
+            last_fix: None,
+            datum: self.config.site_altitude,
+        });
+    }
+
+    // Screens the fixes refreshed this cycle at loop time now. A receiver's datum is
+    // learned, if not configured, from its first usable fix against the fused altitude.
+    pub fn screen(
+        &mut self,
+        values: &mut [f32],
+        valid: &mut [bool],
+        now: Duration,
+        fused_altitude: f32,
+    ) {
+        let timeout = Duration::from_secs_f32(self.config.fix_timeout_s);
+        for receiver in &mut self.receivers {
+            let range = receiver.index..receiver.index + STRIDE;
+            let fix: [f32; STRIDE] = std::array::from_fn(|i| values[receiver.index + i]);
+            // Receivers publish every fix once, so one identical to the last fused is the
+            // same fix read again, e.g. from the cache of a receiver that went quiet.
+            let repeated = receiver.last_fix.is_some_and(|(last, _)| last == fix);
+            let fresh = valid[receiver.index] && !repeated;
+            match fresh.then(|| unusable(&self.config, &fix)) {
+                Some(None) => {
+                    let datum = *receiver
+                        .datum
+                        .get_or_insert(fix[ALTITUDE] as f64 - fused_altitude as f64);
+                    let satellites = fix[SATELLITES];
+                    match receiver.status {
+                        Status::Acquiring => info!(
+                            "GNSS {}: fix acquired ({} satellites), pad at {:.1} m",
+                            receiver.key, satellites, datum
+                        ),
+                        Status::Lost => info!(
+                            "GNSS {}: fix regained ({} satellites)",
+                            receiver.key, satellites
+                        ),
+                        Status::Fixed => {}
+                    }
+                    receiver.status = Status::Fixed;
+                    receiver.last_fix = Some((fix, now));
+                    values[receiver.index + ALTITUDE] = (fix[ALTITUDE] as f64 - datum) as f32;
+                    continue;
+                }
+                Some(Some(reason)) if receiver.status == Status::Fixed => {
+                    info!("GNSS {}: fix lost ({})", receiver.key, reason);
+                    receiver.status = Status::Lost;
+                }
+                _ => {
+                    let stale = receiver
+                        .last_fix
+                        .is_some_and(|(_, read)| now.saturating_sub(read) > timeout);
+                    if receiver.status == Status::Fixed && stale {
+                        info!(
+                            "GNSS {}: fix lost (no new fix for {:?})",
+                            receiver.key, timeout
+                        );
+                        receiver.status = Status::Lost;
+                    }
+                }
+            }
+            valid[range].fill(false);
+        }
+    }
+}
+
+// Why a fix cannot be fused. Reasons are only formatted when reported, so screening does
+// not allocate.
+enum Unusable {
+    NoFix,
+    Fix2D,
+    Satellites(f32),
+    Dop(f32, f32),
+    NoSolution,
+}
+
+impl fmt::Display for Unusable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unusable::NoFix => write!(f, "no fix"),
+            Unusable::Fix2D => write!(f, "2D fix"),
+            Unusable::Satellites(satellites) => write!(f, "{} satellites", satellites),
+            Unusable::Dop(hdop, vdop) => write!(f, "DOP {:.1}/{:.1}", hdop, vdop),
+            Unusable::NoSolution => write!(f, "no solution"),
+        }
+    }
+}
+
+// Why a fix cannot be fused, if it cannot.
+fn unusable(config: &GnssConfig, fix: &[f32; STRIDE]) -> Option<Unusable> {
+    if fix[FIX_TYPE] != sensors::GnssFixType::Fix3D.0 as f32 {
+        return Some(match fix[FIX_TYPE] {
+            t if t == sensors::GnssFixType::Fix2D.0 as f32 => Unusable::Fix2D,
+            _ => Unusable::NoFix,
+        });
+    }
+    if fix[SATELLITES] < config.min_satellites as f32 {
+        return Some(Unusable::Satellites(fix[SATELLITES]));
+    }
+    let dop = fix[HDOP].max(fix[VDOP]);
+    if !(fix[HDOP] > 0.0 && fix[VDOP] > 0.0 && dop <= config.max_dop) {
+        return Some(Unusable::Dop(fix[HDOP], fix[VDOP]));
+    }
+    if !fix[ALTITUDE..VELOCITY + 3].iter().all(|v| v.is_finite()) {
+        return Some(Unusable::NoSolution);
+    }
+    None
+}
@@ -0,0 +1,189 @@
+// Golden-output regression test of the estimator: replays the checked-in flight log
+// testdata/flight.flog through the fusion loop as fusion --clock sim runs on
+// replay --clock-period-ms 10, and compares every cycle's fused state with the trace in
+// testdata/flight_fused.csv. A change to the estimator that moves its output fails the
+// test; if the change is intended, regenerate the trace with
+//
+//     UPDATE_GOLDEN=1 cargo test -p fusion golden
+//
+// and review the difference in the CSV before committing it. The log is 5 s of the
+// default topology's sensors from sensor_sim: 1.2 s on the pad, then boost and coast.
+use crate::{Args, Fusion, FusionConfig, Source, read_cache};
+use clap::Parser;
+use flight_log::LogReader;
+use fusion::cache::SensorCache;
+use fusion::filter::Filter;
+use fusion::state::FusedState;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+use zenoh::bytes::ZBytes;
+
+const LOG: &str = "flight.flog";
+const TRACE: &str = "flight_fused.csv";
+const CLOCK_PERIOD_NS: u64 = 10_000_000;
+// How far a value may be from the trace: enough for floating-point differences between
+// platforms and compilers, far less than any change to the filter moves it.
+const ABSOLUTE_TOLERANCE: f32 = 1e-3;
+const RELATIVE_TOLERANCE: f32 = 1e-5;
+
+// A file in testdata/: under Bazel, in the runfiles directory TESTDATA names, as the
+// crate's directory is gone by the time the test runs.
+fn testdata(name: &str) -> PathBuf {
+    std::env::var_os("TESTDATA")
+        .map_or_else(
+            || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata"),
+            PathBuf::from,
+        )
+        .join(name)
+}
+
+const COLUMNS: [&str; 19] = [
+    "time_s",
+    "roll",
+    "pitch",
+    "yaw",
+    "roll_rate",
+    "pitch_rate",
+    "yaw_rate",
+    "altitude",
+    "velocity_x",
+    "velocity_y",
+    "velocity_z",
+    "acceleration_x",
+    "acceleration_y",
+    "acceleration_z",
+    "attitude_valid",
+    "angular_rate_valid",
+    "altitude_valid",
+    "velocity_valid",
+    "acceleration_valid",
+];
+
+// The values of a cycle's state in the order of COLUMNS after time_s, with the validity
+// of each.
+fn values(state: &FusedState) -> Vec<(f32, bool)> {
+    let mut values = Vec::with_capacity(COLUMNS.len() - 1);
+    let vec3 = |values: &mut Vec<(f32, bool)>, v: [f32; 3], valid: bool| {
+        values.extend(v.map(|x| (x, valid)));
+    };
+    vec3(&mut values, state.attitude, state.attitude_valid);
+    vec3(&mut values, state.angular_rate, state.angular_rate_valid);
+    values.push((state.altitude, state.altitude_valid));
+    vec3(&mut values, state.velocity, state.velocity_valid);
+    vec3(&mut values, state.acceleration, state.acceleration_valid);
+    for valid in [
+        state.attitude_valid,
+        state.angular_rate_valid,
+        state.altitude_valid,
+        state.velocity_valid,
+        state.acceleration_valid,
+    ] {
+        values.push((valid as u8 as f32, true));
+    }
+    values
+}
+
+// Runs fusion over the log, returning each cycle's time since the first tick and state.
+fn replay() -> Vec<(Duration, FusedState)> {
+    let args = Args::parse_from(["fusion", "--clock", "sim"]);
+    let mut fusion = Fusion::new(
+        &args,
+        &FusionConfig::default(),
+        Source::Cache(SensorCache::default()),
+    )
+    .expect("Default topology is invalid.");
+    let mut states = Vec::new();
+    let mut first_tick = None;
+    let mut next_tick = None;
+    let mut cycle = |fusion: &mut Fusion, tick_ns: u64| {
+        let time = Duration::from_nanos(tick_ns - *first_tick.get_or_insert(tick_ns));
+        let Source::Cache(cache) = &fusion.source else {
+            unreachable!();
+        };
+        read_cache(cache, &mut fusion.plan, &mut fusion.measurement, time);
+        fusion.fuse(time);
+        states.push((time, fusion.filter.estimate()));
+    };
+
+    for record in LogReader::open(&testdata(LOG)).expect("Cannot open the golden log.") {
+        let record = record.expect("Golden log is corrupt.");
+        // Ticks go out as replay sends them: each after every sample stamped before it.
+        let mut tick = *next_tick.get_or_insert(record.timestamp_ns + CLOCK_PERIOD_NS);
+        while tick < record.timestamp_ns {
+            cycle(&mut fusion, tick);
+            tick += CLOCK_PERIOD_NS;
+        }
+        next_tick = Some(tick);
+        let Source::Cache(cache) = &mut fusion.source else {
+            unreachable!();
+        };
+        cache.insert(&record.key, ZBytes::from(record.payload));
+    }
+    if let Some(tick) = next_tick {
+        cycle(&mut fusion, tick);
+    }
+    states
+}
+
+fn render(states: &[(Duration, FusedState)]) -> String {
+    let mut csv = COLUMNS.join(",");
+    csv.push('\n');
+    for (time, state) in states {
+        let _ = write!(csv, "{:.2}", time.as_secs_f64());
+        for (value, _) in values(state) {
+            let _ = write!(csv, ",{}", value);
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+#[test]
+fn fused_output_matches_golden_trace() {
+    let states = replay();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(testdata(TRACE), render(&states)).expect("Cannot write the golden trace.");
+        return;
+    }
+    let trace = std::fs::read_to_string(testdata(TRACE)).expect("Cannot read the golden trace.");
+    let mut rows = trace.lines();
+    assert_eq!(
+        rows.next(),
+        Some(COLUMNS.join(",").as_str()),
+        "trace columns"
+    );
+    let rows: Vec<&str> = rows.collect();
+    assert_eq!(rows.len(), states.len(), "cycles run");
+
+    let mut mismatches = Vec::new();
+    for (row, (time, state)) in rows.iter().zip(&states) {
+        let expected: Vec<f32> = row
+            .split(',')
+            .skip(1)
+            .map(|value| value.parse().expect("Golden trace holds a non-number."))
+            .collect();
+        for (i, ((actual, valid), expected)) in values(state).into_iter().zip(expected).enumerate()
+        {
+            let tolerance = ABSOLUTE_TOLERANCE + RELATIVE_TOLERANCE * expected.abs();
+            // A NaN never agrees. Fields fusion flags invalid carry no information, whatever
+            // their value.
+            let agrees = (actual - expected).abs() <= tolerance;
+            if valid && !agrees {
+                mismatches.push(format!(
+                    "{:.2} s {}: {} instead of {}",
+                    time.as_secs_f64(),
+                    COLUMNS[i + 1],
+                    actual,
+                    expected
+                ));
+            }
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "{} values differ from the golden trace, first:\n{}",
+        mismatches.len(),
+        mismatches[..mismatches.len().min(20)].join("\n")
+    );
+}
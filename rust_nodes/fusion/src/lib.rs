@@ -0,0 +1,6 @@
+// The fusion node's sensor cache, sample parsing, and state estimation, kept apart from
+// the node itself so benches/ can drive the hot loop's pieces directly.
+pub mod cache;
+pub mod filter;
+pub mod parse;
+pub mod state;
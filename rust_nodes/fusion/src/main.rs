@@ -1,120 +1,1280 @@
-use futures::StreamExt;
+mod baro;
+mod config;
+mod discovery;
+mod error;
+mod fdir;
+mod gnss;
+#[cfg(test)]
+mod golden;
+mod voting;
+
+use baro::Barometers;
+use clap::{Parser, ValueEnum};
+use command::NackReason;
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use common::sequence::SequencedPublisher;
+use config::{
+    Calibration, ConfigError, FusionConfig, QueryConfig, QueryOptions, SensorConfig, SensorKind,
+};
+use error::{FusionError, SensorError};
+use fdir::Exclusions;
+use flatbuffers::FlatBufferBuilder;
+use fusion::cache::SensorCache;
+use fusion::filter::{Ekf, EkfConfig, Filter, Reading};
+use fusion::parse::{
+    self, AltitudeParser, BarometerParser, GnssParser, GyroParser, ImuParser, MagnetometerParser,
+    ParseCounts, ParseError, SensorParser, contiguous,
+};
+use futures::future::join_all;
+use gnss::GnssScreen;
+use keyspace::keys;
+use node_framework::trace::{self, TraceContext};
+use node_framework::{
+    Bus, CommandId, CommandRequest, Context, Event, GetOptions, Node, NodeArgs, ParamId,
+    SubscriptionId, TimerId, ZenohBus,
+};
 use sensors_rs::sensors;
-use std::thread::sleep;
-use std::time::Duration;
-use tokio;
-use zenoh;
-use zenoh::query::ConsolidationMode;
-
-// 3 IMUs, 2 gyroscopes, 4 altimeters
-const N_FLOATS: usize = 3 * 3 + 2 * 3 + 4 * 1;
-const CLOCK_PER: u64 = 10; // ms
-const IMU_KEYS: [&str; 3] = ["imu0", "imu1", "imu2"];
-const GYRO_KEYS: [&str; 2] = ["gyro0", "gyro1"];
-const ALT_KEYS: [&str; 4] = ["altitude0", "altitude1", "altitude2", "altitude3"];
-const BASE_SENSOR_KEY: &str = "devices/";
-
-async fn query_latest_value(session: &zenoh::Session, key: &str) -> Option<zenoh::sample::Sample> {
-    let res = session
-        .get(key)
-        .consolidation(ConsolidationMode::Latest)
-        .timeout(Duration::from_millis(50))
-        .await;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{Instrument, debug, debug_span, error, info, info_span, warn};
+use voting::Voter;
+use zenoh::bytes::ZBytes;
+use zenoh::key_expr::KeyExpr;
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+use zenoh::sample::SampleKind;
+
+// Selects how sensor values are acquired: "query" (default) or "subscribe".
+const MODE_ENV: &str = "FUSION_MODE";
+// Values the voting thresholds can be set to at runtime, in each kind's units.
+const THRESHOLD_RANGE: std::ops::RangeInclusive<f64> = 0.001..=1000.0;
+// Vertical speed above which barometer calibration is refused, in m/s: re-referencing in
+// flight would throw the altitude off by the height flown.
+const CALIBRATION_MAX_SPEED: f32 = 1.0;
+
+// What paces the fusion loop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ClockMode {
+    // Fixed --period-ms grid in real time.
+    Wall,
+    // One cycle per tick received on --clock-key, e.g. from replay --clock-period-ms.
+    Sim,
+}
+
+// Latest sensor values and which of them were refreshed this cycle.
+struct Measurement {
+    values: Vec<f32>,
+    valid: Vec<bool>,
+    // Where fragmented payloads are joined for parsing.
+    scratch: Vec<u8>,
+}
+
+#[derive(Parser)]
+#[command(about = "Fuses sensor samples into a state estimate published on state/fused")]
+struct Args {
+    /// Sensor topology config (TOML or JSON). Defaults to the built-in topology.
+    config: Option<PathBuf>,
+    /// Fusion loop period in milliseconds.
+    #[arg(long, default_value_t = 10)]
+    period_ms: u64,
+    /// Timeout of each sensor query in milliseconds.
+    #[arg(long, default_value_t = 50)]
+    query_timeout_ms: u64,
+    /// Key expression the fused state is published on.
+    #[arg(long, default_value = keys::fused_state())]
+    state_key: String,
+    /// Run on the wall clock, or step once per simulation clock tick. The sim clock
+    /// always reads sensors through subscriptions.
+    #[arg(long, value_enum, default_value_t = ClockMode::Wall)]
+    clock: ClockMode,
+    /// Key expression simulation clock ticks are received on.
+    #[arg(long, default_value = keys::sim_clock())]
+    clock_key: String,
+    /// Read the sensors whose liveliness tokens are alive under devices/**, as they appear
+    /// and disappear. Configured sensors are read while their token is alive; others are
+    /// added after them, with the kind their key names and that kind's default rate.
+    #[arg(long)]
+    discover: bool,
+    /// Period of the per-sensor parse statistics on stats/parse in milliseconds, published
+    /// from the first payload that failed to parse on.
+    #[arg(long, default_value_t = 5000)]
+    parse_stats_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+type PayloadParser = fn(&[u8], &mut [f32], usize) -> Result<(), ParseError>;
+
+// One entry of the parse plan built from the config at startup, or added as a sensor is
+// discovered: where a sensor is read from, how its payload is decoded, and when it is next
+// due to be read.
+struct PlannedSensor {
+    kind: SensorKind,
+    key: String,
+    index: usize,
+    parser: PayloadParser,
+    calibration: Option<Calibration>,
+    // How the sensor is queried in query mode.
+    query: QueryOptions,
+    period: Duration,
+    // Loop time at which the sensor is next read.
+    next_due: Duration,
+    // Timeout accounting, reported whenever a query to the sensor times out.
+    timeouts: u64,
+    consecutive_misses: u32,
+    // How its payloads parsed, for stats/parse.
+    parse_counts: ParseCounts,
+    // Whether its last payload failed to parse, so a run of failures is reported once.
+    parse_failing: bool,
+    // Whether the sensor is read. Discovered sensors are only while their token is alive.
+    active: bool,
+}
+
+fn build_plan(config: &FusionConfig, active: bool) -> Result<Vec<PlannedSensor>, ConfigError> {
+    config
+        .sensors
+        .iter()
+        .map(|sensor| plan_sensor(sensor, &config.query, active))
+        .collect()
+}
+
+fn plan_sensor(
+    sensor: &SensorConfig,
+    query: &QueryConfig,
+    active: bool,
+) -> Result<PlannedSensor, ConfigError> {
+    KeyExpr::try_from(sensor.key.as_str()).map_err(|e| {
+        ConfigError::Invalid(format!("{}: invalid key expression: {}", sensor.key, e))
+    })?;
+    Ok(PlannedSensor {
+        kind: sensor.kind,
+        key: sensor.key.clone(),
+        index: sensor.index,
+        parser: match sensor.kind {
+            SensorKind::Imu => ImuParser::parse,
+            SensorKind::Gyro => GyroParser::parse,
+            SensorKind::Altitude => AltitudeParser::parse,
+            SensorKind::Barometer => BarometerParser::parse,
+            SensorKind::Magnetometer => MagnetometerParser::parse,
+            SensorKind::Gnss => GnssParser::parse,
+        },
+        calibration: sensor.calibration(),
+        query: query.options(sensor.kind),
+        period: Duration::from_secs_f32(1.0 / sensor.rate_hz),
+        next_due: Duration::ZERO,
+        timeouts: 0,
+        consecutive_misses: 0,
+        parse_counts: ParseCounts::default(),
+        parse_failing: false,
+        active,
+    })
+}
+
+// Where refresh_meas reads sensor payloads from. Query issues a get() on the bus per key
+// every cycle, with the sensor kind's query options and the given timeout where they set
+// none; Cache reads the latest values received on the node's sensor subscriptions.
+enum Source {
+    Query(Duration),
+    Cache(SensorCache),
+}
+
+async fn query_latest_value(
+    bus: &impl Bus,
+    sensor: &PlannedSensor,
+    timeout: Duration,
+) -> Result<ZBytes, SensorError> {
+    let options = GetOptions {
+        timeout: sensor.query.timeout(timeout),
+        consolidation: sensor.query.consolidation.mode(),
+        target: sensor.query.target.target(),
+    };
+    let start = Instant::now();
+    let span = debug_span!("query", key = %sensor.key);
+    match bus.get(&sensor.key, &options).instrument(span).await {
+        Ok(Some(payload)) => Ok(payload),
+        Ok(None) => Err(SensorError::Timeout(start.elapsed())),
+        Err(e) => Err(SensorError::Query(e)),
+    }
+}
+
+// Queries the latest payloads of the given sensors concurrently, so slow or dead sensors
+// cost at most the longest query timeout per cycle instead of one per sensor, then parses
+// them into the measurement array.
+async fn query_and_parse(
+    bus: &impl Bus,
+    timeout: Duration,
+    mut sensors: Vec<&mut PlannedSensor>,
+    measurement: &mut Measurement,
+) {
+    let results = join_all(
+        sensors
+            .iter()
+            .map(|sensor| query_latest_value(bus, sensor, timeout)),
+    )
+    .await;
+
+    for (sensor, result) in sensors.iter_mut().zip(results) {
+        parse_into(sensor, result.as_ref(), measurement);
+    }
+}
 
-    return match res {
-        Ok(res) => match res.into_stream().next().await {
-            Some(reply) => match reply.into_result() {
-                Ok(sample) => Some(sample),
+// Parses a sensor's payload into the measurement array at the sensor's index. Parsing and
+// population in the measurement array is defined by the sensor's parser, which says why a
+// payload could not be parsed; every outcome is counted for stats/parse. The matching
+// entries of the validity mask are set accordingly. Decoded values of calibrated sensors
+// are calibrated in place, so voting compares them as the filter sees them.
+fn parse_into(
+    sensor: &mut PlannedSensor,
+    result: Result<&ZBytes, &SensorError>,
+    measurement: &mut Measurement,
+) {
+    let parsed = match result {
+        Ok(payload) => {
+            sensor.consecutive_misses = 0;
+            let payload = contiguous(payload, &mut measurement.scratch);
+            let result = (sensor.parser)(payload, &mut measurement.values, sensor.index);
+            sensor.parse_counts.record(&result);
+            if result.is_err() {
+                metrics::counter!(
+                    "fusion_parse_errors_total",
+                    "sensor" => sensor.key.clone(),
+                    "reason" => ParseCounts::outcome(&result)
+                )
+                .increment(1);
+            }
+            match result {
+                Ok(()) => {
+                    sensor.parse_failing = false;
+                    if let Some(calibration) = &sensor.calibration {
+                        let range = sensor.index..sensor.index + sensor.kind.stride();
+                        calibration.apply(&mut measurement.values[range]);
+                    }
+                    true
+                }
                 Err(e) => {
-                    eprintln!("Error in sample for key {}: {}", key, e);
-                    None
+                    let first = !sensor.parse_failing;
+                    sensor.parse_failing = true;
+                    if first {
+                        report_sensor_error(sensor, &SensorError::Parse(e));
+                    }
+                    false
                 }
-            },
-            None => {
-                eprintln!("No sample found for key {}", key);
-                None
             }
-        },
+        }
         Err(e) => {
-            eprintln!("Error in query for key {}: {}", key, e);
-            None
+            sensor.consecutive_misses += 1;
+            if let SensorError::Timeout(_) = e {
+                sensor.timeouts += 1;
+            }
+            report_sensor_error(sensor, e);
+            false
         }
     };
+    measurement.valid[sensor.index..sensor.index + sensor.kind.stride()].fill(parsed);
 }
 
-// Queries a list of sensor keys of homogeneous sensor type and parses the payloads
-// into the measurement array at the given base index. Parsing and population in the
-// measurement array is defined by the parser function.
-async fn query_and_parse<F>(
-    session: &zenoh::Session,
-    keys: &[&str],
-    measurement: &mut [f32],
-    mut base: usize,
-    stride: usize,
-    parser: F,
-) -> usize
-where
-    F: Fn(&[u8], &mut [f32], usize),
-{
-    for key in keys.iter() {
-        let full_key = format!("{}{}", BASE_SENSOR_KEY, key);
-        let sample = query_latest_value(session, &full_key).await;
-        if let Some(sample) = sample {
-            let payload = sample.payload().to_bytes();
-            parser(&payload, measurement, base);
-        }
-        base += stride;
+// Reports why a sensor gave no values in a cycle, the one place sensor errors are logged.
+// Timeouts come with the sensor's miss counts; an empty cache is the normal state before
+// the first sample and is not reported. Parse failures are reported by the caller only at
+// the start of a run of them.
+fn report_sensor_error(sensor: &PlannedSensor, error: &SensorError) {
+    match error {
+        SensorError::Empty => {}
+        SensorError::Timeout(_) => warn!(
+            "key {}: {} ({} consecutive misses, {} timeouts total)",
+            sensor.key, error, sensor.consecutive_misses, sensor.timeouts
+        ),
+        _ => warn!("key {}: {}", sensor.key, error),
     }
-    base
 }
 
-fn parse_imu(payload: &[u8], meas: &mut [f32], idx: usize) {
-    if let Ok(imu_data) = flatbuffers::root::<sensors::IMU>(payload) {
-        if let Some(accel) = imu_data.acceleration() {
-            meas[idx] = accel.x();
-            meas[idx + 1] = accel.y();
-            meas[idx + 2] = accel.z();
+// Refreshes the measurement array with the latest values from the sensors that are due
+// this cycle. Entries of sensors that were not read or did not report keep their previous
+// value but are marked invalid.
+async fn refresh_meas(
+    source: &Source,
+    bus: &impl Bus,
+    plan: &mut [PlannedSensor],
+    measurement: &mut Measurement,
+    now: Duration,
+) {
+    match source {
+        Source::Query(timeout) => {
+            measurement.valid.fill(false);
+            let due = due_sensors(plan, now).collect();
+            query_and_parse(bus, *timeout, due, measurement).await
         }
+        Source::Cache(cache) => read_cache(cache, plan, measurement, now),
+    }
+}
+
+// Refreshes the measurement array from the cache like refresh_meas. Cached payloads are
+// parsed where they are, so a cycle in subscription mode does not allocate.
+fn read_cache(
+    cache: &SensorCache,
+    plan: &mut [PlannedSensor],
+    measurement: &mut Measurement,
+    now: Duration,
+) {
+    measurement.valid.fill(false);
+    for sensor in due_sensors(plan, now) {
+        parse_into(
+            sensor,
+            cache.latest(&sensor.key).ok_or(&SensorError::Empty),
+            measurement,
+        );
+    }
+}
+
+// The active sensors due to be read at loop time now, each scheduled for its next read as
+// it is taken.
+fn due_sensors(
+    plan: &mut [PlannedSensor],
+    now: Duration,
+) -> impl Iterator<Item = &mut PlannedSensor> {
+    plan.iter_mut()
+        .filter(move |sensor| sensor.active && now >= sensor.next_due)
+        .map(move |sensor| {
+            sensor.next_due = now + sensor.period;
+            &mut *sensor
+        })
+}
+
+// Feeds every sensor refreshed this cycle to the filter as a measurement update.
+fn update_filter(filter: &mut impl Filter, plan: &[PlannedSensor], measurement: &Measurement) {
+    let values = &measurement.values;
+
+    for sensor in plan.iter().filter(|s| measurement.valid[s.index]) {
+        let i = sensor.index;
+        let reading = match sensor.kind {
+            SensorKind::Imu => Reading::Imu([values[i], values[i + 1], values[i + 2]]),
+            SensorKind::Gyro => Reading::Gyro([values[i], values[i + 1], values[i + 2]]),
+            // Barometer pressures have been converted to height by then.
+            SensorKind::Altitude | SensorKind::Barometer => Reading::Altitude(values[i]),
+            SensorKind::Magnetometer => {
+                Reading::Magnetometer([values[i], values[i + 1], values[i + 2]])
+            }
+            SensorKind::Gnss => Reading::Gnss {
+                altitude: values[i],
+                velocity: [values[i + 1], values[i + 2], values[i + 3]],
+                hdop: values[i + 5],
+                vdop: values[i + 6],
+            },
+        };
+        filter.update(&reading);
+    }
+}
+
+// Picks the source selected by FUSION_MODE. The sim clock always reads from the cache.
+fn select_source(query_timeout: Duration, clock: ClockMode) -> Result<Source, FusionError> {
+    let mode = std::env::var(MODE_ENV);
+    let mode = match clock {
+        ClockMode::Sim => Ok("subscribe"),
+        ClockMode::Wall => mode.as_deref(),
+    };
+    match mode {
+        Ok("subscribe") => Ok(Source::Cache(SensorCache::default())),
+        Ok("query") | Err(_) => Ok(Source::Query(query_timeout)),
+        Ok(other) => Err(FusionError::Mode {
+            variable: MODE_ENV,
+            value: other.to_string(),
+        }),
     }
 }
 
-fn parse_gyro(payload: &[u8], meas: &mut [f32], idx: usize) {
-    if let Ok(gyro_data) = flatbuffers::root::<sensors::Gyro>(payload) {
-        meas[idx] = gyro_data.omega_x();
-        meas[idx + 1] = gyro_data.omega_y();
-        meas[idx + 2] = gyro_data.omega_z();
+// Loads the sensor topology from the given config file, falling back to the built-in
+// default topology.
+fn load_config(path: Option<&PathBuf>) -> Result<FusionConfig, ConfigError> {
+    match path {
+        Some(path) => FusionConfig::load(path),
+        None => Ok(FusionConfig::default()),
     }
 }
 
-fn parse_altitude(payload: &[u8], meas: &mut [f32], idx: usize) {
-    if let Ok(altitude_data) = flatbuffers::root::<sensors::Altitude>(payload) {
-        meas[idx] = altitude_data.altitude();
+fn wall_time_us() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+// Time of one cycle, relative to the first, and the timestamp its output is stamped with.
+struct Cycle {
+    time: Duration,
+    timestamp_us: u64,
+}
+
+// The measurement array as logged every cycle at debug level, formatted field by field
+// into the logger's own buffer, so logging it allocates nothing of its own.
+struct Echo<'a>(&'a [f32]);
+
+impl fmt::Display for Echo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, x) in self.0.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(f, "{}{:6.2}", separator, x)?;
+        }
+        Ok(())
+    }
+}
+
+// Generic over the bus sensors are queried on, so the tests below can run cycles on a
+// MockBus.
+struct Fusion<B = ZenohBus> {
+    period: Duration,
+    parse_stats_period: Duration,
+    // Timers of the wall clock cycles and of stats/parse.
+    cycle_timer: Option<TimerId>,
+    parse_stats_timer: Option<TimerId>,
+    state_key: String,
+    clock: ClockMode,
+    clock_key: String,
+    plan: Vec<PlannedSensor>,
+    // Query options sensors added on discovery are planned with.
+    query: QueryConfig,
+    qos: QosConfig,
+    discover: bool,
+    discovery_subscription: Option<SubscriptionId>,
+    source: Source,
+    // Set in init from the node's session.
+    bus: Option<B>,
+    measurement: Measurement,
+    // What the filter is built from, again on reset.
+    ekf_config: EkfConfig,
+    filter: Ekf,
+    voter: Voter,
+    barometers: Barometers,
+    gnss: GnssScreen,
+    calibrate_command: Option<CommandId>,
+    // Voting threshold parameters, param/<node>/voting/<kind>.
+    thresholds: Vec<(ParamId<f64>, SensorKind)>,
+    exclusions: Exclusions,
+    fdir_subscription: Option<SubscriptionId>,
+    // Sensor key each sensor subscription feeds in the cache.
+    sensor_subscriptions: HashMap<SubscriptionId, String>,
+    clock_subscription: Option<SubscriptionId>,
+    // When the wall clock started, and the time of the first sim clock tick.
+    started: Instant,
+    first_tick: Option<u64>,
+    // Cycles run so far, which errors and reports are numbered by.
+    cycles: u64,
+    // Contexts of the traced sensor samples received since the last cycle, oldest first.
+    traces: Vec<TraceContext>,
+    last_cycle: Option<Duration>,
+    publisher: Option<SequencedPublisher<'static>>,
+    // The fused state is encoded into, reused every cycle.
+    builder: FlatBufferBuilder<'static>,
+    voting_publisher: Option<Publisher<'static>>,
+    sensors_publisher: Option<Publisher<'static>>,
+    parse_stats_publisher: Option<Publisher<'static>>,
+}
+
+impl<B: Bus> Fusion<B> {
+    fn new(args: &Args, config: &FusionConfig, source: Source) -> Result<Self, ConfigError> {
+        let ekf_config = EkfConfig {
+            magnetic_field: config.magnetic_field,
+            ..Default::default()
+        };
+        Ok(Fusion {
+            period: Duration::from_millis(args.period_ms),
+            parse_stats_period: Duration::from_millis(args.parse_stats_ms),
+            cycle_timer: None,
+            parse_stats_timer: None,
+            state_key: args.state_key.clone(),
+            clock: args.clock,
+            clock_key: args.clock_key.clone(),
+            plan: build_plan(config, !args.discover)?,
+            query: config.query.clone(),
+            qos: config.qos.clone(),
+            discover: args.discover,
+            discovery_subscription: None,
+            source,
+            bus: None,
+            measurement: Measurement {
+                values: vec![0.0_f32; config.measurement_len()],
+                valid: vec![false; config.measurement_len()],
+                scratch: Vec::new(),
+            },
+            filter: Ekf::new(ekf_config.clone()),
+            ekf_config,
+            voter: Voter::new(
+                config.voting.clone(),
+                config.sensors.iter().map(|s| (s.kind, s.index)).collect(),
+            ),
+            barometers: Barometers::new(
+                &config.barometer,
+                config
+                    .sensors
+                    .iter()
+                    .filter(|s| s.kind == SensorKind::Barometer)
+                    .map(|s| (s.key.clone(), s.index))
+                    .collect(),
+            ),
+            calibrate_command: None,
+            gnss: GnssScreen::new(
+                config.gnss.clone(),
+                config
+                    .sensors
+                    .iter()
+                    .filter(|s| s.kind == SensorKind::Gnss)
+                    .map(|s| (s.key.clone(), s.index))
+                    .collect(),
+            ),
+            thresholds: Vec::new(),
+            exclusions: Exclusions::default(),
+            fdir_subscription: None,
+            sensor_subscriptions: HashMap::new(),
+            clock_subscription: None,
+            started: Instant::now(),
+            first_tick: None,
+            cycles: 0,
+            traces: Vec::new(),
+            last_cycle: None,
+            publisher: None,
+            builder: FlatBufferBuilder::with_capacity(128),
+            voting_publisher: None,
+            sensors_publisher: None,
+            parse_stats_publisher: None,
+        })
+    }
+
+    // Declares the subscription the sensor at a plan position is read through in
+    // subscription mode. Query mode declares nothing: the bus declares a key's querier on
+    // its first query.
+    async fn connect(&mut self, ctx: &mut Context, position: usize) -> zenoh::Result<()> {
+        if let Source::Cache(_) = self.source {
+            let key = &self.plan[position].key;
+            let id = ctx.subscribe_advanced(key, 1).await?;
+            self.sensor_subscriptions.insert(id, key.clone());
+        }
+        Ok(())
+    }
+
+    // Reads the sensor on a key whose liveliness token appeared, planning it first if it is
+    // not configured, or stops reading it once its token went away.
+    async fn discovered(&mut self, ctx: &mut Context, key: &str, alive: bool) -> zenoh::Result<()> {
+        let position = match self.plan.iter().position(|sensor| sensor.key == key) {
+            Some(position) => position,
+            None if !alive => return Ok(()),
+            None => {
+                let Some(kind) = discovery::kind_of(key) else {
+                    warn!("Ignoring {}: its name is not that of a sensor kind", key);
+                    return Ok(());
+                };
+                self.add_sensor(ctx, key, kind).await?
+            }
+        };
+        let sensor = &mut self.plan[position];
+        if sensor.active == alive {
+            return Ok(());
+        }
+        sensor.active = alive;
+        if alive {
+            info!("Sensor {} appeared, reading it", key);
+        } else {
+            info!("Sensor {} disappeared", key);
+            if let Source::Cache(cache) = &mut self.source {
+                cache.remove(key);
+            }
+            if self.voter.forget(position) {
+                self.report_vote(wall_time_us()).await?;
+            }
+        }
+        Ok(self.report_sensors().await?)
+    }
+
+    // Plans an unconfigured sensor after the end of the measurement vector, at its kind's
+    // default rate, and connects it. Returns its plan position.
+    async fn add_sensor(
+        &mut self,
+        ctx: &mut Context,
+        key: &str,
+        kind: SensorKind,
+    ) -> zenoh::Result<usize> {
+        let index = self.measurement.values.len();
+        let config = SensorConfig {
+            kind,
+            key: key.to_string(),
+            rate_hz: discovery::rate_hz(kind),
+            index,
+            hard_iron: None,
+            soft_iron: None,
+        };
+        self.plan.push(plan_sensor(&config, &self.query, false)?);
+        let len = index + kind.stride();
+        self.measurement.values.resize(len, 0.0);
+        self.measurement.valid.resize(len, false);
+        self.voter.add(kind, index);
+        match kind {
+            SensorKind::Barometer => self.barometers.add(key.to_string(), index),
+            SensorKind::Gnss => self.gnss.add(key.to_string(), index),
+            _ => {}
+        }
+        let position = self.plan.len() - 1;
+        self.connect(ctx, position).await?;
+        Ok(position)
+    }
+
+    // Refreshes the measurement for the cycle, runs the filter over it and publishes the
+    // resulting state, all within a span numbering the cycle. The span continues the trace
+    // of the oldest traced sample received since the last cycle and links the others, so
+    // the state is traced back to the samples it was fused from.
+    async fn run_cycle(&mut self, cycle: Cycle) -> Result<(), FusionError> {
+        self.cycles += 1;
+        let span = info_span!("cycle", n = self.cycles);
+        let mut traces = self.traces.drain(..);
+        if let Some(parent) = traces.next() {
+            trace::continue_from(&span, parent);
+        }
+        for other in traces {
+            trace::link(&span, other);
+        }
+        self.fuse_cycle(cycle).instrument(span).await
+    }
+
+    async fn fuse_cycle(&mut self, cycle: Cycle) -> Result<(), FusionError> {
+        let Some(bus) = &self.bus else {
+            return Err(FusionError::NotInitialized);
+        };
+        refresh_meas(
+            &self.source,
+            bus,
+            &mut self.plan,
+            &mut self.measurement,
+            cycle.time,
+        )
+        .await;
+        let voted = self.fuse(cycle.time);
+        debug!("{}", Echo(&self.measurement.values));
+        if voted {
+            self.report_vote(cycle.timestamp_us).await?;
+        }
+
+        let state = self.filter.estimate();
+        if let Some(publisher) = &self.publisher {
+            // Zenoh takes ownership of what it publishes, so this copy is the one allocation
+            // the state costs.
+            let payload = state.encode_into(&mut self.builder, cycle.timestamp_us);
+            publisher
+                .put(payload.to_vec())
+                .await
+                .map_err(FusionError::publish("fused state", self.cycles))?;
+        }
+        Ok(())
+    }
+
+    // Screens and votes on the measurement refreshed for the cycle at loop time, then runs
+    // the filter over it. Returns whether the voting status changed. Does not allocate, which
+    // the tests below check.
+    fn fuse(&mut self, time: Duration) -> bool {
+        self.barometers
+            .convert(&mut self.measurement.values, &mut self.measurement.valid);
+        self.gnss.screen(
+            &mut self.measurement.values,
+            &mut self.measurement.valid,
+            time,
+            self.filter.estimate().altitude,
+        );
+        let voted = self
+            .voter
+            .vote(&self.measurement.values, &mut self.measurement.valid);
+        if self.exclusions.expire(Instant::now()) {
+            info!("FDIR status lost, re-admitting all sensors");
+        }
+        for sensor in &self.plan {
+            if self.exclusions.contains(&sensor.key) {
+                self.measurement.valid[sensor.index..sensor.index + sensor.kind.stride()]
+                    .fill(false);
+            }
+        }
+
+        let dt = self
+            .last_cycle
+            .map_or(Duration::ZERO, |last| time.saturating_sub(last));
+        self.filter.propagate(dt.as_secs_f32());
+        self.last_cycle = Some(time);
+        update_filter(&mut self.filter, &self.plan, &self.measurement);
+        voted
+    }
+
+    // Has the barometers take their ground reference again, or sets it to the pad pressure
+    // given in pascals as the pressure argument.
+    async fn calibrate(&mut self, request: CommandRequest) -> zenoh::Result<()> {
+        let pressure = match request.command.arg("pressure") {
+            None => None,
+            Some(_) => match request.command.parse::<f64>("pressure") {
+                Ok(pressure) if pressure.is_finite() && pressure > 0.0 => Some(pressure),
+                Ok(pressure) => {
+                    let message = format!("pressure must be positive, got {}", pressure);
+                    return request.nack(NackReason::InvalidArgs, message).await;
+                }
+                Err(nack) => return request.reply(Err(nack)).await,
+            },
+        };
+        if !self.plan.iter().any(|s| s.kind == SensorKind::Barometer) {
+            return request
+                .nack(NackReason::Rejected, "no barometers configured")
+                .await;
+        }
+        let state = self.filter.estimate();
+        if state.velocity_valid && state.velocity[2].abs() > CALIBRATION_MAX_SPEED {
+            let message = format!("moving at {:.1} m/s vertically", state.velocity[2]);
+            return request.nack(NackReason::Rejected, message).await;
+        }
+        self.barometers.calibrate(pressure);
+        let message = match pressure {
+            Some(pressure) => format!("ground reference set to {:.1} Pa", pressure),
+            None => "taking ground reference".to_string(),
+        };
+        info!("Barometers: {} by {}", message, request.command.sender);
+        request.ack(message).await
+    }
+
+    // Publishes the sensors read and where their values sit.
+    async fn report_sensors(&self) -> Result<(), FusionError> {
+        let active: Vec<(&str, SensorKind, usize)> = self
+            .plan
+            .iter()
+            .filter(|sensor| sensor.active)
+            .map(|sensor| (sensor.key.as_str(), sensor.kind, sensor.index))
+            .collect();
+        if let Some(publisher) = &self.sensors_publisher {
+            publisher
+                .put(discovery::encode(wall_time_us(), &active))
+                .await
+                .map_err(FusionError::publish("active sensors", self.cycles))?;
+        }
+        Ok(())
+    }
+
+    // Publishes how every sensor's payloads parsed so far, once any of them failed to,
+    // under the name the node runs as.
+    async fn report_parse_stats(&self, node: &str) -> Result<(), FusionError> {
+        if self
+            .plan
+            .iter()
+            .all(|sensor| sensor.parse_counts.failed() == 0)
+        {
+            return Ok(());
+        }
+        let counts: Vec<(&str, ParseCounts)> = self
+            .plan
+            .iter()
+            .map(|sensor| (sensor.key.as_str(), sensor.parse_counts))
+            .collect();
+        if let Some(publisher) = &self.parse_stats_publisher {
+            publisher
+                .put(parse::encode_stats(wall_time_us(), node, &counts))
+                .await
+                .map_err(FusionError::publish("parse statistics", self.cycles))?;
+        }
+        Ok(())
+    }
+
+    // Logs and publishes the sensors voting currently leaves out.
+    async fn report_vote(&self, timestamp_us: u64) -> Result<(), FusionError> {
+        let excluded: Vec<&str> = self
+            .voter
+            .excluded()
+            .map(|position| self.plan[position].key.as_str())
+            .collect();
+        let disagreeing = self.voter.disagreeing();
+        let names: Vec<&str> = disagreeing.iter().map(|kind| kind.name()).collect();
+        info!(
+            "Voting: excluded [{}], disagreeing [{}]",
+            excluded.join(", "),
+            names.join(", ")
+        );
+        if let Some(publisher) = &self.voting_publisher {
+            publisher
+                .put(voting::encode(timestamp_us, &excluded, disagreeing))
+                .await
+                .map_err(FusionError::publish("voting status", self.cycles))?;
+        }
+        Ok(())
     }
 }
 
-// Refreshes the measurement array with the latest values queried from the sensors.
-async fn refresh_meas(session: &zenoh::Session, measurement: &mut [f32; N_FLOATS]) {
-    let mut base = 0;
+impl Node for Fusion {
+    const NAME: &'static str = "fusion";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.set_qos(self.qos.clone());
+        metrics::describe_counter!(
+            "fusion_parse_errors_total",
+            "Sensor payloads fusion could not parse, by sensor key and reason."
+        );
+        self.bus = Some(ctx.bus());
+        for position in 0..self.plan.len() {
+            self.connect(ctx, position).await?;
+        }
+        self.fdir_subscription = Some(ctx.subscribe(keys::fdir_status()).await?);
+        ctx.register_command("reset").await?;
+        self.calibrate_command = Some(ctx.register_command("calibrate").await?);
+        for kind in SensorKind::ALL {
+            let name = format!("voting/{}", kind.name());
+            let threshold = self.voter.config().threshold(kind) as f64;
+            let id = ctx
+                .declare_param(&name, threshold, Some(THRESHOLD_RANGE))
+                .await?;
+            self.thresholds.push((id, kind));
+        }
+        match self.clock {
+            ClockMode::Sim => self.clock_subscription = Some(ctx.subscribe(&self.clock_key).await?),
+            ClockMode::Wall => self.cycle_timer = Some(ctx.add_timer(self.period)),
+        }
+        self.parse_stats_timer = Some(ctx.add_timer(self.parse_stats_period));
+        // Sim-clock runs are meant to be recorded and compared, so no output may be dropped
+        // when the loop runs faster than the network.
+        let congestion = match self.clock {
+            ClockMode::Sim => CongestionControl::Block,
+            ClockMode::Wall => CongestionControl::Drop,
+        };
+        let publisher = ctx
+            .session()
+            .declare_publisher(self.state_key.clone())
+            .encoding(encoding::flatbuffer("sensors.FusedState"))
+            .congestion_control(congestion)
+            .qos(ctx.qos(), &self.state_key)
+            .await?;
+        self.publisher = Some(ctx.sequenced(publisher));
+        // Status changes are rare and each one matters, so they are never dropped.
+        let voting_publisher = ctx
+            .session()
+            .declare_publisher(keys::voting_status())
+            .encoding(encoding::flatbuffer("sensors.VotingStatus"))
+            .congestion_control(CongestionControl::Block)
+            .qos(ctx.qos(), keys::voting_status())
+            .await?;
+        self.voting_publisher = Some(voting_publisher);
+        let sensors_publisher = ctx
+            .session()
+            .declare_publisher(keys::active_sensors())
+            .encoding(encoding::flatbuffer("sensors.ActiveSensors"))
+            .congestion_control(CongestionControl::Block)
+            .qos(ctx.qos(), keys::active_sensors())
+            .await?;
+        self.sensors_publisher = Some(sensors_publisher);
+        let parse_stats_publisher = ctx
+            .session()
+            .declare_publisher(keys::parse_stats())
+            .encoding(encoding::flatbuffer("sensors.ParseStats"))
+            .qos(ctx.qos(), keys::parse_stats())
+            .await?;
+        self.parse_stats_publisher = Some(parse_stats_publisher);
+        self.report_sensors().await?;
+        // Tokens alive already arrive first, so sensors present at startup are read from the
+        // first cycles.
+        if self.discover {
+            self.discovery_subscription =
+                Some(ctx.subscribe_liveliness(keys::all_devices()).await?);
+        }
+        self.started = Instant::now();
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let cycle = match event {
+            Event::Timer(id) if Some(id) == self.parse_stats_timer => {
+                return Ok(self.report_parse_stats(ctx.name()).await?);
+            }
+            Event::Timer(id) if Some(id) == self.cycle_timer => Cycle {
+                time: self.started.elapsed(),
+                timestamp_us: wall_time_us(),
+            },
+            Event::Timer(_) => return Ok(()),
+            // Ticks and sensor samples are stepped in arrival order, so the cache holds
+            // exactly the samples published before the tick no matter how far the loop lags
+            // behind. This holds as long as both come from one publishing session, as they
+            // do from replay.
+            Event::Sample(id, sample) if Some(id) == self.clock_subscription => {
+                let payload = contiguous(sample.payload(), &mut self.measurement.scratch);
+                let time_ns = flatbuffers::root::<sensors::Clock>(payload)
+                    .map_err(FusionError::MalformedClock)?
+                    .time_ns();
+                let first = *self.first_tick.get_or_insert(time_ns);
+                Cycle {
+                    time: Duration::from_nanos(time_ns.saturating_sub(first)),
+                    timestamp_us: time_ns / 1000,
+                }
+            }
+            Event::Sample(id, sample) if Some(id) == self.discovery_subscription => {
+                let alive = sample.kind() == SampleKind::Put;
+                return self
+                    .discovered(ctx, sample.key_expr().as_str(), alive)
+                    .await;
+            }
+            Event::Sample(id, sample) if Some(id) == self.fdir_subscription => {
+                let changed = self
+                    .exclusions
+                    .update(&sample.payload().to_bytes(), Instant::now())?;
+                if changed {
+                    let keys: Vec<&str> = self.exclusions.keys().collect();
+                    info!("FDIR: excluding [{}]", keys.join(", "));
+                }
+                return Ok(());
+            }
+            Event::Sample(id, sample) => {
+                let key = self.sensor_subscriptions.get(&id);
+                if let (Some(key), Source::Cache(cache)) = (key, &mut self.source) {
+                    cache.insert(key, sample.payload().clone());
+                    self.traces.extend(trace::of(&sample));
+                }
+                return Ok(());
+            }
+            Event::Query(..) => return Ok(()),
+            Event::Param(changed) => {
+                for &(id, kind) in &self.thresholds {
+                    if changed.is(id) {
+                        let threshold = ctx.param(id) as f32;
+                        self.voter.config_mut().set_threshold(kind, threshold);
+                    }
+                }
+                return Ok(());
+            }
+            Event::Command(request) if Some(request.id) == self.calibrate_command => {
+                return self.calibrate(request).await;
+            }
+            Event::Command(request) => {
+                self.filter = Ekf::new(self.ekf_config.clone());
+                self.last_cycle = None;
+                info!("Filter reset by command");
+                return request.ack("filter reset").await;
+            }
+        };
+        Ok(self.run_cycle(cycle).await?)
+    }
 
-    base = query_and_parse(session, &IMU_KEYS, measurement, base, 3, parse_imu).await;
-    base = query_and_parse(session, &GYRO_KEYS, measurement, base, 3, parse_gyro).await;
-    query_and_parse(session, &ALT_KEYS, measurement, base, 1, parse_altitude).await;
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        // Dropping the bus undeclares the queriers it declared.
+        self.bus = None;
+        if let Some(publisher) = self.voting_publisher.take() {
+            publisher.undeclare().await?;
+        }
+        if let Some(publisher) = self.sensors_publisher.take() {
+            publisher.undeclare().await?;
+        }
+        if let Some(publisher) = self.parse_stats_publisher.take() {
+            publisher.undeclare().await?;
+        }
+        match self.publisher.take() {
+            Some(publisher) => publisher.undeclare().await,
+            None => Ok(()),
+        }
+    }
 }
 
-fn echo_meas(measurement: &[f32; N_FLOATS]) {
-    println!("{}", measurement.map(|x| format!("{:6.2}", x)).join(", "));
+// The node the arguments describe.
+fn fusion(args: &Args) -> Result<Fusion, FusionError> {
+    let config = load_config(args.config.as_ref())?;
+    let query_timeout = Duration::from_millis(args.query_timeout_ms);
+    let source = select_source(query_timeout, args.clock)?;
+    Ok(Fusion::new(args, &config, source)?)
 }
 
 #[tokio::main]
-async fn main() {
-    let session = zenoh::open(zenoh::Config::default())
-        .await
-        .expect("Failed to open Zenoh session.");
-
-    let mut measurement = [0.0_f32; N_FLOATS];
-    loop {
-        refresh_meas(&session, &mut measurement).await;
-        echo_meas(&measurement);
-        sleep(Duration::from_millis(CLOCK_PER));
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    match fusion(&args) {
+        Ok(node) => node_framework::run(node, &args.node).await,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use node_framework::MockBus;
+    use sensors_rs::readings::{
+        AltitudeReading, BarometerReading, FixType, GnssReading, GyroReading, ImuReading,
+        MagnetometerReading, Reading as _,
+    };
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    // Counts allocations per thread, so those of the harness's other threads do not count
+    // against the cycle under test.
+    struct Counting;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    fn count() {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count();
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            count();
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    // Sensors of every kind the default topology leaves out.
+    const EXTRA_SENSORS: &str = r#"
+        [[sensors]]
+        type = "barometer"
+        key = "devices/barometer0"
+        rate_hz = 100.0
+        index = 19
+
+        [[sensors]]
+        type = "barometer"
+        key = "devices/barometer1"
+        rate_hz = 100.0
+        index = 21
+
+        [[sensors]]
+        type = "barometer"
+        key = "devices/barometer2"
+        rate_hz = 100.0
+        index = 23
+
+        [[sensors]]
+        type = "magnetometer"
+        key = "devices/magnetometer0"
+        rate_hz = 100.0
+        index = 25
+        hard_iron = [1.0, -2.0, 0.5]
+
+        [[sensors]]
+        type = "gnss"
+        key = "devices/gnss0"
+        rate_hz = 10.0
+        index = 28
+    "#;
+
+    // Cycles spent taking the barometer references and acquiring the GNSS fix, which are
+    // reported as they happen.
+    const WARM_UP: u32 = 60;
+
+    // Splits every other payload in two, as Zenoh delivers a payload reassembled from
+    // fragments.
+    fn zbytes(payload: Vec<u8>, fragmented: bool) -> ZBytes {
+        if !fragmented {
+            return ZBytes::from(payload);
+        }
+        let (head, tail) = payload.split_at(payload.len() / 2);
+        let mut writer = ZBytes::writer();
+        writer.append(ZBytes::from(head.to_vec()));
+        writer.append(ZBytes::from(tail.to_vec()));
+        writer.finish()
+    }
+
+    // A sample of a vehicle standing still at the given altitude, as of the given cycle.
+    // GNSS fixes change every cycle, so none is taken for a repeat of the last.
+    fn steady(kind: SensorKind, altitude: f32, step: u32) -> Vec<u8> {
+        match kind {
+            SensorKind::Imu => ImuReading {
+                acceleration: [0.0, 0.0, 9.81],
+            }
+            .encode(),
+            SensorKind::Gyro => GyroReading { omega: [0.0; 3] }.encode(),
+            SensorKind::Altitude => AltitudeReading { altitude }.encode(),
+            SensorKind::Barometer => BarometerReading {
+                pressure: 101_325.0,
+                temperature: 15.0,
+            }
+            .encode(),
+            SensorKind::Magnetometer => MagnetometerReading {
+                field: [21.0, -2.0, -44.5],
+            }
+            .encode(),
+            SensorKind::Gnss => GnssReading {
+                timestamp_us: step as u64 * 10_000,
+                fix_type: FixType::Fix3D,
+                altitude: 100.0 + step as f32 * 0.001,
+                velocity: Some([0.0; 3]),
+                hdop: 0.9,
+                vdop: 1.4,
+                satellites: 9,
+                ..Default::default()
+            }
+            .encode(),
+        }
+    }
+
+    // Fills the cache with a steady vehicle's samples of the given cycle.
+    fn receive(cache: &mut SensorCache, plan: &[PlannedSensor], step: u32) {
+        for (position, sensor) in plan.iter().enumerate() {
+            let payload = steady(sensor.kind, 0.0, step);
+            let fragmented = (position as u32 + step) % 2 == 1;
+            cache.insert(&sensor.key, zbytes(payload, fragmented));
+        }
+    }
+
+    #[test]
+    fn subscription_cycle_does_not_allocate() {
+        let mut config = FusionConfig::default();
+        let extra: FusionConfig = toml::from_str(EXTRA_SENSORS).unwrap();
+        config.sensors.extend(extra.sensors);
+        let args = Args::parse_from(["fusion"]);
+        let mut fusion: Fusion = Fusion::new(&args, &config, Source::Cache(SensorCache::default()))
+            .expect("Test topology is invalid.");
+
+        for step in 0..WARM_UP + 100 {
+            let time = Duration::from_millis(step as u64 * 10);
+            let Source::Cache(cache) = &mut fusion.source else {
+                unreachable!();
+            };
+            receive(cache, &fusion.plan, step);
+
+            let before = ALLOCATIONS.with(Cell::get);
+            read_cache(cache, &mut fusion.plan, &mut fusion.measurement, time);
+            let voted = fusion.fuse(time);
+            let allocations = ALLOCATIONS.with(Cell::get) - before;
+
+            if step >= WARM_UP {
+                assert_eq!(allocations, 0, "cycle {} allocated", step);
+                assert!(!voted, "cycle {} changed the voting status", step);
+            }
+        }
+        // GNSS is read every tenth cycle, so only the last cycle's other sensors are fresh.
+        let stale = fusion
+            .plan
+            .iter()
+            .filter(|sensor| sensor.kind == SensorKind::Gnss)
+            .flat_map(|sensor| sensor.index..sensor.index + sensor.kind.stride());
+        let mut expected = vec![true; fusion.measurement.valid.len()];
+        for i in stale {
+            expected[i] = false;
+        }
+        assert_eq!(fusion.measurement.valid, expected);
+    }
+
+    // Fusion on the default topology in query mode, on a mock bus where every sensor answers
+    // as a vehicle standing at 100 m.
+    fn on_mock_bus() -> (Fusion<MockBus>, MockBus) {
+        let args = Args::parse_from(["fusion"]);
+        let source = Source::Query(Duration::from_millis(50));
+        let mut fusion = Fusion::new(&args, &FusionConfig::default(), source)
+            .expect("Default topology is invalid.");
+        let bus = MockBus::new();
+        for sensor in &fusion.plan {
+            bus.answer(&sensor.key, steady(sensor.kind, 100.0, 0));
+        }
+        fusion.bus = Some(bus.clone());
+        (fusion, bus)
+    }
+
+    async fn run_cycles(fusion: &mut Fusion<MockBus>, steps: std::ops::Range<u32>) {
+        for step in steps {
+            let time = Duration::from_millis(step as u64 * 10);
+            let cycle = Cycle {
+                time,
+                timestamp_us: time.as_micros() as u64,
+            };
+            fusion.run_cycle(cycle).await.expect("Cycle failed.");
+        }
+    }
+
+    fn sensor<'a>(fusion: &'a Fusion<MockBus>, key: &str) -> &'a PlannedSensor {
+        fusion.plan.iter().find(|sensor| sensor.key == key).unwrap()
+    }
+
+    #[tokio::test]
+    async fn query_cycle_reads_every_sensor() {
+        let (mut fusion, _bus) = on_mock_bus();
+        run_cycles(&mut fusion, 0..1).await;
+        assert!(fusion.measurement.valid.iter().all(|&valid| valid));
+        assert_eq!(fusion.measurement.values[0..3], [0.0, 0.0, 9.81]);
+        assert_eq!(fusion.measurement.values[15..19], [100.0; 4]);
+    }
+
+    #[tokio::test]
+    async fn silent_sensor_is_invalid_until_it_answers_again() {
+        let (mut fusion, bus) = on_mock_bus();
+        bus.silence("devices/altitude3");
+        run_cycles(&mut fusion, 0..3).await;
+        let silent = sensor(&fusion, "devices/altitude3");
+        assert_eq!((silent.consecutive_misses, silent.timeouts), (3, 3));
+        let mut expected = vec![true; fusion.measurement.valid.len()];
+        expected[silent.index] = false;
+        assert_eq!(fusion.measurement.valid, expected);
+
+        bus.answer("devices/altitude3", steady(SensorKind::Altitude, 100.0, 0));
+        run_cycles(&mut fusion, 3..4).await;
+        let silent = sensor(&fusion, "devices/altitude3");
+        assert_eq!((silent.consecutive_misses, silent.timeouts), (0, 3));
+        assert!(fusion.measurement.valid.iter().all(|&valid| valid));
+    }
+
+    #[tokio::test]
+    async fn undecodable_payload_is_invalid() {
+        let (mut fusion, bus) = on_mock_bus();
+        bus.answer("devices/imu1", vec![0xff; 7]);
+        run_cycles(&mut fusion, 0..1).await;
+        let imu = sensor(&fusion, "devices/imu1");
+        // It answered, so it did not miss.
+        assert_eq!(imu.consecutive_misses, 0);
+        let mut expected = vec![true; fusion.measurement.valid.len()];
+        expected[imu.index..imu.index + 3].fill(false);
+        assert_eq!(fusion.measurement.valid, expected);
+    }
+
+    #[tokio::test]
+    async fn parse_failures_are_counted_per_sensor() {
+        let (mut fusion, bus) = on_mock_bus();
+        bus.answer("devices/imu1", vec![0xff; 7]);
+        run_cycles(&mut fusion, 0..2).await;
+        bus.answer("devices/imu1", steady(SensorKind::Imu, 100.0, 0));
+        run_cycles(&mut fusion, 2..3).await;
+        let failing = sensor(&fusion, "devices/imu1");
+        assert_eq!(
+            (failing.parse_counts.parsed, failing.parse_counts.invalid),
+            (1, 2)
+        );
+        assert!(!failing.parse_failing);
+        let healthy = sensor(&fusion, "devices/imu0");
+        assert_eq!(healthy.parse_counts.parsed, 3);
+        assert_eq!(healthy.parse_counts.failed(), 0);
+    }
+
+    #[tokio::test]
+    async fn voting_leaves_out_a_disagreeing_altimeter() {
+        let (mut fusion, bus) = on_mock_bus();
+        bus.answer("devices/altitude2", steady(SensorKind::Altitude, 500.0, 0));
+        run_cycles(&mut fusion, 0..1).await;
+        let position = fusion
+            .plan
+            .iter()
+            .position(|sensor| sensor.key == "devices/altitude2")
+            .unwrap();
+        assert_eq!(fusion.voter.excluded().collect::<Vec<_>>(), [position]);
+        assert!(!fusion.measurement.valid[fusion.plan[position].index]);
+    }
+
+    #[tokio::test]
+    async fn filter_settles_on_the_altimeters() {
+        let (mut fusion, _bus) = on_mock_bus();
+        run_cycles(&mut fusion, 0..300).await;
+        let state = fusion.filter.estimate();
+        assert!(state.altitude_valid);
+        assert!(
+            (state.altitude - 100.0).abs() < 1.0,
+            "altitude {} after 3 s at 100 m",
+            state.altitude
+        );
     }
 }
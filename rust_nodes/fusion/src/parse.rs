@@ -0,0 +1,419 @@
+use flatbuffers::InvalidFlatbuffer;
+use sensors_rs::sensors;
+use thiserror::Error;
+use zenoh::bytes::ZBytes;
+
+// Decoders of sensor payloads into the fusion loop's measurement array: each writes its
+// kind's values from idx on, and leaves the array untouched if it returns an error.
+// ParseCounts keeps the outcomes per sensor for stats/parse.
+
+// Why a payload was not parsed.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ParseError {
+    // The kind's entries from index on do not fit in the measurement array.
+    #[error("{stride} entries from index {index} do not fit in {len} measurements")]
+    OutOfBounds {
+        index: usize,
+        stride: usize,
+        len: usize,
+    },
+    // The payload is not a valid flatbuffer of the kind's table.
+    #[error("undecodable payload: {0}")]
+    Invalid(#[from] InvalidFlatbuffer),
+    // The table decoded but lacks a field the kind needs.
+    #[error("no {0} in the payload")]
+    MissingField(&'static str),
+}
+
+// The stride entries of the measurement array from index on.
+fn entries(meas: &mut [f32], index: usize, stride: usize) -> Result<&mut [f32], ParseError> {
+    let len = meas.len();
+    index
+        .checked_add(stride)
+        .and_then(|end| meas.get_mut(index..end))
+        .ok_or(ParseError::OutOfBounds { index, stride, len })
+}
+
+// The payload as one slice for the parsers, without allocating: a payload received in one
+// piece is borrowed as it is, and the pieces of a fragmented one are joined in scratch,
+// which keeps its capacity from one payload to the next.
+pub fn contiguous<'a>(payload: &'a ZBytes, scratch: &'a mut Vec<u8>) -> &'a [u8] {
+    let mut slices = payload.slices();
+    match (slices.next(), slices.next()) {
+        (None, _) => &[],
+        (Some(slice), None) => slice,
+        (Some(_), Some(_)) => {
+            scratch.clear();
+            for slice in payload.slices() {
+                scratch.extend_from_slice(slice);
+            }
+            scratch
+        }
+    }
+}
+
+// A sensor kind's payload decoder. decode is given exactly the kind's STRIDE entries and
+// writes them only once the whole payload decoded; parse finds those entries in the
+// measurement array first, so no index can write outside them.
+pub trait SensorParser {
+    // Number of measurement array entries the kind's values take.
+    const STRIDE: usize;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError>;
+
+    fn parse(payload: &[u8], meas: &mut [f32], idx: usize) -> Result<(), ParseError> {
+        Self::decode(payload, entries(meas, idx, Self::STRIDE)?)
+    }
+}
+
+pub struct ImuParser;
+
+impl SensorParser for ImuParser {
+    const STRIDE: usize = 3;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError> {
+        let accel = flatbuffers::root::<sensors::IMU>(payload)?
+            .acceleration()
+            .ok_or(ParseError::MissingField("acceleration"))?;
+        out.copy_from_slice(&[accel.x(), accel.y(), accel.z()]);
+        Ok(())
+    }
+}
+
+pub struct GyroParser;
+
+impl SensorParser for GyroParser {
+    const STRIDE: usize = 3;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError> {
+        let gyro = flatbuffers::root::<sensors::Gyro>(payload)?;
+        out.copy_from_slice(&[gyro.omega_x(), gyro.omega_y(), gyro.omega_z()]);
+        Ok(())
+    }
+}
+
+pub struct AltitudeParser;
+
+impl SensorParser for AltitudeParser {
+    const STRIDE: usize = 1;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError> {
+        out[0] = flatbuffers::root::<sensors::Altitude>(payload)?.altitude();
+        Ok(())
+    }
+}
+
+// Pressure, converted to height in place later, and temperature.
+pub struct BarometerParser;
+
+impl SensorParser for BarometerParser {
+    const STRIDE: usize = 2;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError> {
+        let barometer = flatbuffers::root::<sensors::Barometer>(payload)?;
+        out.copy_from_slice(&[barometer.pressure(), barometer.temperature()]);
+        Ok(())
+    }
+}
+
+pub struct MagnetometerParser;
+
+impl SensorParser for MagnetometerParser {
+    const STRIDE: usize = 3;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError> {
+        let field = flatbuffers::root::<sensors::Magnetometer>(payload)?
+            .field()
+            .ok_or(ParseError::MissingField("field"))?;
+        out.copy_from_slice(&[field.x(), field.y(), field.z()]);
+        Ok(())
+    }
+}
+
+// Altitude, east, north, and up velocity, then fix type, HDOP, VDOP, and satellite count.
+// Every field of a fix is written, usable or not; GnssScreen decides which are fused. A fix
+// without a velocity solution gets NaN velocity.
+pub struct GnssParser;
+
+impl SensorParser for GnssParser {
+    const STRIDE: usize = 8;
+
+    fn decode(payload: &[u8], out: &mut [f32]) -> Result<(), ParseError> {
+        let fix = flatbuffers::root::<sensors::GnssFix>(payload)?;
+        let velocity = fix
+            .velocity()
+            .map_or([f32::NAN; 3], |v| [v.x(), v.y(), v.z()]);
+        out.copy_from_slice(&[
+            fix.altitude(),
+            velocity[0],
+            velocity[1],
+            velocity[2],
+            fix.fix_type().0 as f32,
+            fix.hdop(),
+            fix.vdop(),
+            fix.satellites() as f32,
+        ]);
+        Ok(())
+    }
+}
+
+// How the payloads of one sensor parsed, by outcome.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseCounts {
+    pub parsed: u64,
+    pub invalid: u64,
+    pub missing_field: u64,
+    pub out_of_bounds: u64,
+}
+
+impl ParseCounts {
+    pub fn record(&mut self, result: &Result<(), ParseError>) {
+        let count = match result {
+            Ok(()) => &mut self.parsed,
+            Err(ParseError::Invalid(_)) => &mut self.invalid,
+            Err(ParseError::MissingField(_)) => &mut self.missing_field,
+            Err(ParseError::OutOfBounds { .. }) => &mut self.out_of_bounds,
+        };
+        *count += 1;
+    }
+
+    // The outcome a result is counted as, named as in SensorParseStats.
+    pub fn outcome(result: &Result<(), ParseError>) -> &'static str {
+        match result {
+            Ok(()) => "parsed",
+            Err(ParseError::Invalid(_)) => "invalid",
+            Err(ParseError::MissingField(_)) => "missing_field",
+            Err(ParseError::OutOfBounds { .. }) => "out_of_bounds",
+        }
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.invalid + self.missing_field + self.out_of_bounds
+    }
+}
+
+// A ParseStats of the given sensors' counts, published by node on stats/parse.
+pub fn encode_stats(timestamp_us: u64, node: &str, sensors: &[(&str, ParseCounts)]) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(256);
+    let sensors: Vec<_> = sensors
+        .iter()
+        .map(|&(key, counts)| {
+            let key = builder.create_string(key);
+            sensors::SensorParseStats::create(
+                &mut builder,
+                &sensors::SensorParseStatsArgs {
+                    key: Some(key),
+                    parsed: counts.parsed,
+                    invalid: counts.invalid,
+                    missing_field: counts.missing_field,
+                    out_of_bounds: counts.out_of_bounds,
+                },
+            )
+        })
+        .collect();
+    let sensors = builder.create_vector(&sensors);
+    let node = builder.create_string(node);
+    let stats = sensors::ParseStats::create(
+        &mut builder,
+        &sensors::ParseStatsArgs {
+            timestamp_us,
+            node: Some(node),
+            sensors: Some(sensors),
+        },
+    );
+    builder.finish(stats, None);
+    builder.finished_data().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use sensors_rs::readings::{
+        AltitudeReading, BarometerReading, GyroReading, ImuReading, MagnetometerReading,
+        Reading as _,
+    };
+
+    type Parser = fn(&[u8], &mut [f32], usize) -> Result<(), ParseError>;
+
+    // Every parser with the number of entries it writes.
+    const PARSERS: [(Parser, usize); 6] = [
+        (ImuParser::parse, ImuParser::STRIDE),
+        (GyroParser::parse, GyroParser::STRIDE),
+        (AltitudeParser::parse, AltitudeParser::STRIDE),
+        (BarometerParser::parse, BarometerParser::STRIDE),
+        (MagnetometerParser::parse, MagnetometerParser::STRIDE),
+        (GnssParser::parse, GnssParser::STRIDE),
+    ];
+
+    // What the measurement array is filled with, to see which entries a parser wrote.
+    const UNTOUCHED: f32 = -12345.0;
+
+    fn index() -> impl Strategy<Value = usize> {
+        prop_oneof![8 => 0..40usize, 1 => Just(usize::MAX), 1 => Just(usize::MAX - 1)]
+    }
+
+    fn finite() -> impl Strategy<Value = f32> {
+        -1e6f32..1e6f32
+    }
+
+    proptest! {
+        // Whatever the payload and index, a parser writes inside its own entries only, and
+        // nothing at all when it fails.
+        #[test]
+        fn arbitrary_input_stays_in_bounds(
+            parser in 0..PARSERS.len(),
+            payload in prop::collection::vec(any::<u8>(), 0..256),
+            len in 0..32usize,
+            idx in index(),
+        ) {
+            let (parse, stride) = PARSERS[parser];
+            let mut meas = vec![UNTOUCHED; len];
+            let fits = idx.checked_add(stride).is_some_and(|end| end <= len);
+            match parse(&payload, &mut meas, idx) {
+                Ok(()) => {
+                    prop_assert!(fits);
+                    for (i, &value) in meas.iter().enumerate() {
+                        if i < idx || i >= idx + stride {
+                            prop_assert_eq!(value, UNTOUCHED);
+                        }
+                    }
+                }
+                Err(e) => {
+                    prop_assert_eq!(
+                        matches!(e, ParseError::OutOfBounds { .. }),
+                        !fits
+                    );
+                    prop_assert!(meas.iter().all(|&value| value == UNTOUCHED));
+                }
+            }
+        }
+
+        // A payload cut short fails to parse without writing anything.
+        #[test]
+        fn truncated_payloads_are_rejected(
+            acceleration in prop::array::uniform3(finite()),
+            cut in 0.0..1.0f64,
+        ) {
+            let payload = ImuReading { acceleration }.encode();
+            let cut = (payload.len() as f64 * cut) as usize;
+            let mut meas = [UNTOUCHED; 3];
+            if ImuParser::parse(&payload[..cut], &mut meas, 0).is_err() {
+                prop_assert_eq!(meas, [UNTOUCHED; 3]);
+            }
+        }
+
+        #[test]
+        fn imu_round_trips(
+            acceleration in prop::array::uniform3(finite()),
+            idx in 0..16usize,
+        ) {
+            let mut meas = [0.0; 19];
+            ImuParser::parse(&ImuReading { acceleration }.encode(), &mut meas, idx).unwrap();
+            prop_assert_eq!(&meas[idx..idx + 3], &acceleration);
+        }
+
+        #[test]
+        fn gyro_round_trips(omega in prop::array::uniform3(finite()), idx in 0..16usize) {
+            let mut meas = [0.0; 19];
+            GyroParser::parse(&GyroReading { omega }.encode(), &mut meas, idx).unwrap();
+            prop_assert_eq!(&meas[idx..idx + 3], &omega);
+        }
+
+        #[test]
+        fn altitude_round_trips(altitude in finite(), idx in 0..19usize) {
+            let mut meas = [0.0; 19];
+            let payload = AltitudeReading { altitude }.encode();
+            AltitudeParser::parse(&payload, &mut meas, idx).unwrap();
+            prop_assert_eq!(meas[idx], altitude);
+        }
+
+        #[test]
+        fn barometer_round_trips(
+            pressure in finite(),
+            temperature in finite(),
+            idx in 0..17usize,
+        ) {
+            let mut meas = [0.0; 19];
+            let payload = BarometerReading { pressure, temperature }.encode();
+            BarometerParser::parse(&payload, &mut meas, idx).unwrap();
+            prop_assert_eq!(&meas[idx..idx + 2], &[pressure, temperature]);
+        }
+
+        #[test]
+        fn magnetometer_round_trips(
+            field in prop::array::uniform3(finite()),
+            idx in 0..16usize,
+        ) {
+            let mut meas = [0.0; 19];
+            let payload = MagnetometerReading { field }.encode();
+            MagnetometerParser::parse(&payload, &mut meas, idx).unwrap();
+            prop_assert_eq!(&meas[idx..idx + 3], &field);
+        }
+    }
+
+    #[test]
+    fn index_past_the_end_is_out_of_bounds() {
+        let payload = ImuReading {
+            acceleration: [0.0, 0.0, 9.81],
+        }
+        .encode();
+        let mut meas = [0.0; 4];
+        assert_eq!(
+            ImuParser::parse(&payload, &mut meas, 2),
+            Err(ParseError::OutOfBounds {
+                index: 2,
+                stride: 3,
+                len: 4
+            })
+        );
+    }
+
+    #[test]
+    fn imu_without_acceleration_is_missing_a_field() {
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let imu = sensors::IMU::create(&mut builder, &sensors::IMUArgs::default());
+        builder.finish(imu, None);
+        let mut meas = [UNTOUCHED; 3];
+        assert_eq!(
+            ImuParser::parse(builder.finished_data(), &mut meas, 0),
+            Err(ParseError::MissingField("acceleration"))
+        );
+        assert_eq!(meas, [UNTOUCHED; 3]);
+    }
+
+    #[test]
+    fn counts_are_kept_by_outcome_and_encoded() {
+        let payload = AltitudeReading { altitude: 12.0 }.encode();
+        let mut meas = [0.0; 2];
+        let mut counts = ParseCounts::default();
+        for (payload, idx) in [
+            (&payload[..], 0),
+            (&payload[..], 1),
+            (&payload[..], 2),
+            (&[][..], 0),
+        ] {
+            counts.record(&AltitudeParser::parse(payload, &mut meas, idx));
+        }
+        assert_eq!(
+            counts,
+            ParseCounts {
+                parsed: 2,
+                invalid: 1,
+                missing_field: 0,
+                out_of_bounds: 1,
+            }
+        );
+        assert_eq!(counts.failed(), 2);
+
+        let bytes = encode_stats(7, "fusion", &[("devices/altimeter0", counts)]);
+        let stats = flatbuffers::root::<sensors::ParseStats>(&bytes).unwrap();
+        assert_eq!((stats.timestamp_us(), stats.node()), (7, Some("fusion")));
+        let sensor = stats.sensors().unwrap().get(0);
+        assert_eq!(sensor.key(), Some("devices/altimeter0"));
+        assert_eq!(
+            (sensor.parsed(), sensor.invalid(), sensor.out_of_bounds()),
+            (2, 1, 1)
+        );
+    }
+}
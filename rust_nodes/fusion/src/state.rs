@@ -0,0 +1,62 @@
+use flatbuffers::FlatBufferBuilder;
+use sensors_rs::sensors;
+
+// Snapshot of the fused vehicle state for one cycle, mirroring sensors::FusedState.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FusedState {
+    pub attitude: [f32; 3],
+    pub angular_rate: [f32; 3],
+    pub velocity: [f32; 3],
+    pub acceleration: [f32; 3],
+    pub altitude: f32,
+    pub attitude_valid: bool,
+    pub angular_rate_valid: bool,
+    pub velocity_valid: bool,
+    pub acceleration_valid: bool,
+    pub altitude_valid: bool,
+}
+
+impl FusedState {
+    // Serializes the state into a finished sensors::FusedState FlatBuffer with the given
+    // timestamp in microseconds since the Unix epoch.
+    pub fn encode(&self, timestamp_us: u64) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::with_capacity(128);
+        self.encode_into(&mut builder, timestamp_us).to_vec()
+    }
+
+    // Like encode, but into a builder kept by the caller, which is reset first and keeps its
+    // buffer from one state to the next.
+    pub fn encode_into<'b>(
+        &self,
+        builder: &'b mut FlatBufferBuilder<'static>,
+        timestamp_us: u64,
+    ) -> &'b [u8] {
+        builder.reset();
+        let attitude = vec3(self.attitude);
+        let angular_rate = vec3(self.angular_rate);
+        let velocity = vec3(self.velocity);
+        let acceleration = vec3(self.acceleration);
+        let state = sensors::FusedState::create(
+            builder,
+            &sensors::FusedStateArgs {
+                timestamp_us,
+                attitude: Some(&attitude),
+                angular_rate: Some(&angular_rate),
+                altitude: self.altitude,
+                attitude_valid: self.attitude_valid,
+                angular_rate_valid: self.angular_rate_valid,
+                altitude_valid: self.altitude_valid,
+                velocity: Some(&velocity),
+                velocity_valid: self.velocity_valid,
+                acceleration: Some(&acceleration),
+                acceleration_valid: self.acceleration_valid,
+            },
+        );
+        builder.finish(state, None);
+        builder.finished_data()
+    }
+}
+
+fn vec3(v: [f32; 3]) -> sensors::Vec3 {
+    sensors::Vec3::new(v[0], v[1], v[2])
+}
@@ -0,0 +1,182 @@
+use crate::config::{SensorKind, VotingConfig};
+use sensors_rs::sensors;
+
+// Cross-compares redundant sensors of the same kind. With three or more fresh readings,
+// every reading farther than the kind's threshold from their per-axis median is outvoted,
+// so one faulted sensor out of three cannot pull the estimate. When the outliers are not
+// a strict minority, or only two readings are fresh and they disagree, there is no telling
+// which are wrong; nothing is outvoted and the kind is reported as disagreeing instead.
+// Verdicts are kept until a sensor is voted on again, so sensors read less often than the
+// loop runs do not flap in and out of the status.
+pub struct Voter {
+    config: VotingConfig,
+    // Kind and first measurement index of every sensor, in plan order.
+    sensors: Vec<(SensorKind, usize)>,
+    outvoted: Vec<bool>,
+    // Kinds whose fresh readings last had no majority to outvote against.
+    disagreeing: Vec<SensorKind>,
+    // Buffers reused from one vote to the next, sized up front so voting does not
+    // allocate: the plan position and index of every fresh reading of a kind, one axis of
+    // those readings, and their per-axis median.
+    fresh: Vec<(usize, usize)>,
+    axis: Vec<f32>,
+    median: Vec<f32>,
+}
+
+impl Voter {
+    pub fn new(config: VotingConfig, sensors: Vec<(SensorKind, usize)>) -> Self {
+        let axes = SensorKind::ALL.iter().map(|kind| kind.voted()).max();
+        Voter {
+            config,
+            outvoted: vec![false; sensors.len()],
+            disagreeing: Vec::with_capacity(SensorKind::ALL.len()),
+            fresh: Vec::with_capacity(sensors.len()),
+            axis: Vec::with_capacity(sensors.len()),
+            median: Vec::with_capacity(axes.unwrap_or(0)),
+            sensors,
+        }
+    }
+
+    // Adds a sensor after the last, at the next plan position. The buffers grow with it, so
+    // voting still does not allocate.
+    pub fn add(&mut self, kind: SensorKind, index: usize) {
+        self.sensors.push((kind, index));
+        self.outvoted.push(false);
+        let sensors = self.sensors.len();
+        self.fresh.reserve(sensors - self.fresh.len());
+        self.axis.reserve(sensors - self.axis.len());
+    }
+
+    // Clears the verdict on the sensor at a plan position, for one no longer read. Returns
+    // whether it had been outvoted.
+    pub fn forget(&mut self, position: usize) -> bool {
+        std::mem::replace(&mut self.outvoted[position], false)
+    }
+
+    pub fn config(&self) -> &VotingConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut VotingConfig {
+        &mut self.config
+    }
+
+    // Votes on the readings refreshed this cycle and marks those of outvoted sensors invalid.
+    // Returns whether the set of excluded sensors or disagreeing kinds changed.
+    pub fn vote(&mut self, values: &[f32], valid: &mut [bool]) -> bool {
+        let mut changed = false;
+        for kind in SensorKind::ALL {
+            let voted = kind.voted();
+            let reading = |i: usize| &values[i..i + voted];
+            self.fresh.clear();
+            self.fresh.extend(
+                self.sensors
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (k, i))| *k == kind && valid[*i])
+                    .map(|(position, (_, i))| (position, *i)),
+            );
+            if self.fresh.len() < 2 {
+                continue;
+            }
+            let threshold = self.config.threshold(kind);
+
+            let disagree = if self.fresh.len() == 2 {
+                for &(position, _) in &self.fresh {
+                    changed |= std::mem::replace(&mut self.outvoted[position], false);
+                }
+                distance(reading(self.fresh[0].1), reading(self.fresh[1].1)) > threshold
+            } else {
+                self.median.clear();
+                for axis in 0..voted {
+                    self.axis.clear();
+                    self.axis
+                        .extend(self.fresh.iter().map(|&(_, i)| reading(i)[axis]));
+                    self.median.push(median(&mut self.axis));
+                }
+                let outlier = |i: usize| distance(reading(i), &self.median) > threshold;
+                // Without a strict majority agreeing, there is nothing to outvote against.
+                let count = self.fresh.iter().filter(|&&(_, i)| outlier(i)).count();
+                let consensus = count * 2 < self.fresh.len();
+                for &(position, i) in &self.fresh {
+                    let outvoted = consensus && outlier(i);
+                    changed |=
+                        std::mem::replace(&mut self.outvoted[position], outvoted) != outvoted;
+                }
+                !consensus
+            };
+            if disagree != self.disagreeing.contains(&kind) {
+                changed = true;
+                self.disagreeing.retain(|k| *k != kind);
+                if disagree {
+                    self.disagreeing.push(kind);
+                }
+            }
+        }
+        self.disagreeing
+            .sort_unstable_by_key(|k| SensorKind::ALL.iter().position(|other| other == k));
+
+        for (position, &(kind, i)) in self.sensors.iter().enumerate() {
+            if self.outvoted[position] {
+                valid[i..i + kind.stride()].fill(false);
+            }
+        }
+        changed
+    }
+
+    // Plan positions of the sensors currently outvoted.
+    pub fn excluded(&self) -> impl Iterator<Item = usize> + '_ {
+        self.outvoted
+            .iter()
+            .enumerate()
+            .filter(|(_, outvoted)| **outvoted)
+            .map(|(position, _)| position)
+    }
+
+    pub fn disagreeing(&self) -> &[SensorKind] {
+        &self.disagreeing
+    }
+}
+
+// Serializes a finished sensors::VotingStatus FlatBuffer.
+pub fn encode(timestamp_us: u64, excluded: &[&str], disagreeing: &[SensorKind]) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+    let excluded: Vec<_> = excluded
+        .iter()
+        .map(|key| builder.create_string(key))
+        .collect();
+    let excluded = builder.create_vector(&excluded);
+    let disagreeing: Vec<_> = disagreeing
+        .iter()
+        .map(|kind| builder.create_string(kind.name()))
+        .collect();
+    let disagreeing = builder.create_vector(&disagreeing);
+    let status = sensors::VotingStatus::create(
+        &mut builder,
+        &sensors::VotingStatusArgs {
+            timestamp_us,
+            excluded: Some(excluded),
+            disagreeing: Some(disagreeing),
+        },
+    );
+    builder.finish(status, None);
+    builder.finished_data().to_vec()
+}
+
+fn median(values: &mut [f32]) -> f32 {
+    values.sort_unstable_by(f32::total_cmp);
+    let n = values.len();
+    if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / 2.0
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
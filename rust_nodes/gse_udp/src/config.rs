@@ -0,0 +1,204 @@
+use common::config;
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+use zenoh::key_expr::KeyExpr;
+
+// Most payload a UDP datagram carries over IPv4.
+const MAX_FRAME_BYTES: usize = 65507;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteOrder {
+    // Network order, LabVIEW's default for Flatten To String and Unflatten From String.
+    #[default]
+    Big,
+    Little,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    // Spare bytes, sent as zeros.
+    Pad,
+}
+
+impl FieldType {
+    // Bytes a field of the type takes, but for padding, which takes its own count.
+    pub fn size(self) -> usize {
+        match self {
+            FieldType::U8 | FieldType::I8 => 1,
+            FieldType::U16 | FieldType::I16 => 2,
+            FieldType::U32 | FieldType::I32 | FieldType::F32 => 4,
+            FieldType::U64 | FieldType::I64 | FieldType::F64 => 8,
+            FieldType::Pad => 0,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, FieldType::F32 | FieldType::F64)
+    }
+}
+
+// Values the node fills in itself rather than taking from a channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Generated {
+    // Frames sent before this one, wrapping around at the size of the field.
+    Counter,
+    // When the frame is sent, in microseconds since the Unix epoch.
+    TimeUs,
+}
+
+// One field of the frame. Exactly one of a key and channel, a constant value, or a
+// generated source gives what it holds, except for padding, which holds nothing.
+#[derive(Clone, Debug, Deserialize)]
+pub struct FieldConfig {
+    // Name to report the field by; the key and channel, or its position, if not given.
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: FieldType,
+    // Key of the samples to take the channel from; a key, not a key expression.
+    pub key: Option<String>,
+    // Path to the value in the sample's payload as telemetry_json decodes it, with /
+    // between nested fields, e.g. altitude or attitude/x.
+    pub channel: Option<String>,
+    // Constant sent in every frame, e.g. a sync word.
+    pub value: Option<f64>,
+    pub source: Option<Generated>,
+    // A channel value is sent as value * scale + offset, rounded for integer fields.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+    // Sent for a channel that has no value yet, or whose value is stale; NaN for float
+    // fields and 0 for integer fields if not given.
+    pub missing: Option<f64>,
+    // Length of padding, in bytes.
+    pub bytes: Option<usize>,
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+impl FieldConfig {
+    // The name the field is reported by, given its position in the frame.
+    pub fn describe(&self, index: usize) -> String {
+        match (&self.name, &self.key, &self.channel) {
+            (Some(name), _, _) => name.clone(),
+            (None, Some(key), Some(channel)) => format!("{}:{}", key, channel),
+            _ => format!("field {}", index),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self.kind {
+            FieldType::Pad => self.bytes.unwrap_or(0),
+            kind => kind.size(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    // Frames sent per second.
+    pub rate_hz: f64,
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    // Age past which a channel's value is stale and sent as missing; values never go
+    // stale if not given.
+    pub stale_ms: Option<u64>,
+    // The frame's fields, in the order they are packed, with nothing between them.
+    pub fields: Vec<FieldConfig>,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn period(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.rate_hz)
+    }
+
+    // Bytes in every frame.
+    pub fn frame_size(&self) -> usize {
+        self.fields.iter().map(FieldConfig::size).sum()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !(self.rate_hz.is_finite() && self.rate_hz > 0.0) {
+            return Err("rate_hz must be positive".to_string());
+        }
+        if self.fields.is_empty() {
+            return Err("no fields configured".to_string());
+        }
+        for (i, field) in self.fields.iter().enumerate() {
+            let name = field.describe(i);
+            if field.kind == FieldType::Pad {
+                if field.bytes.unwrap_or(0) == 0 {
+                    return Err(format!("{}: padding needs a positive bytes", name));
+                }
+                if field.key.is_some()
+                    || field.channel.is_some()
+                    || field.value.is_some()
+                    || field.source.is_some()
+                {
+                    return Err(format!("{}: padding holds no value", name));
+                }
+                continue;
+            }
+            if field.bytes.is_some() {
+                return Err(format!("{}: bytes is only for padding", name));
+            }
+            let sources = [
+                field.key.is_some() || field.channel.is_some(),
+                field.value.is_some(),
+                field.source.is_some(),
+            ];
+            if sources.into_iter().filter(|&given| given).count() != 1 {
+                return Err(format!(
+                    "{}: give one of key and channel, value, or source",
+                    name
+                ));
+            }
+            if let Some(key) = &field.key {
+                let key_expr =
+                    KeyExpr::new(key.as_str()).map_err(|e| format!("{}: {}", name, e))?;
+                if key_expr.is_wild() {
+                    return Err(format!("{}: {} is not a single key", name, key));
+                }
+                if field.channel.as_deref().is_none_or(str::is_empty) {
+                    return Err(format!("{}: a key needs a channel", name));
+                }
+            } else if field.channel.is_some() {
+                return Err(format!("{}: a channel needs a key", name));
+            }
+            if !(field.scale.is_finite() && field.offset.is_finite()) {
+                return Err(format!("{}: scale and offset must be finite", name));
+            }
+        }
+        let size = self.frame_size();
+        if size > MAX_FRAME_BYTES {
+            return Err(format!(
+                "frame of {} bytes does not fit in a datagram of {}",
+                size, MAX_FRAME_BYTES
+            ));
+        }
+        Ok(())
+    }
+}
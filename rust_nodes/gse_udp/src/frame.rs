@@ -0,0 +1,195 @@
+// The frame the GSE is sent: the configured fields packed back to back, each holding the
+// latest value of its channel, a constant, or a value the node fills in.
+use crate::config::{ByteOrder, Config, FieldType, Generated};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+enum Source {
+    // The channel at this JSON pointer into the decoded payload of the field's key.
+    Channel(String),
+    Constant(f64),
+    Generated(Generated),
+    Pad(usize),
+}
+
+struct Field {
+    name: String,
+    kind: FieldType,
+    source: Source,
+    scale: f64,
+    offset: f64,
+    missing: f64,
+    // The channel's latest value and when it arrived, if it has one.
+    latest: Option<(f64, Instant)>,
+}
+
+pub struct Frame {
+    fields: Vec<Field>,
+    byte_order: ByteOrder,
+    stale: Option<Duration>,
+    // Indices of the channel fields of each key.
+    channels: HashMap<String, Vec<usize>>,
+    size: usize,
+    // Frames packed so far.
+    packed: u64,
+}
+
+impl Frame {
+    pub fn new(config: &Config) -> Self {
+        let mut channels: HashMap<String, Vec<usize>> = HashMap::new();
+        let fields = config
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let source = match (&field.key, &field.channel, field.value, field.source) {
+                    (Some(key), Some(channel), _, _) => {
+                        channels.entry(key.clone()).or_default().push(i);
+                        Source::Channel(format!("/{}", channel.trim_matches('/')))
+                    }
+                    (_, _, Some(value), _) => Source::Constant(value),
+                    (_, _, _, Some(generated)) => Source::Generated(generated),
+                    _ => Source::Pad(field.size()),
+                };
+                let missing =
+                    field
+                        .missing
+                        .unwrap_or(if field.kind.is_float() { f64::NAN } else { 0.0 });
+                Field {
+                    name: field.describe(i),
+                    kind: field.kind,
+                    source,
+                    scale: field.scale,
+                    offset: field.offset,
+                    missing,
+                    latest: None,
+                }
+            })
+            .collect();
+        Frame {
+            fields,
+            byte_order: config.byte_order,
+            stale: config.stale_ms.map(Duration::from_millis),
+            channels,
+            size: config.frame_size(),
+            packed: 0,
+        }
+    }
+
+    // Keys the frame takes channels from.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.channels.keys().map(String::as_str)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // Takes the channels of key from its decoded payload. A channel the payload leaves
+    // out (as the fused state does its fields that are not valid), holds null in, or
+    // holds something other than a number or a bool in is missing until the next sample;
+    // the last of these are returned, by field.
+    pub fn update(&mut self, key: &str, value: &Value, now: Instant) -> Vec<(String, String)> {
+        let mut problems = Vec::new();
+        let Some(indices) = self.channels.get(key) else {
+            return problems;
+        };
+        for &i in indices {
+            let field = &mut self.fields[i];
+            let Source::Channel(pointer) = &field.source else {
+                continue;
+            };
+            let channel = match value.pointer(pointer) {
+                Some(Value::Number(number)) => number.as_f64(),
+                Some(Value::Bool(flag)) => Some(*flag as u8 as f64),
+                Some(Value::Null) | None => None,
+                Some(other) => {
+                    problems.push((field.name.clone(), format!("{} is not a number", other)));
+                    None
+                }
+            };
+            field.latest = channel.map(|channel| (channel * field.scale + field.offset, now));
+        }
+        problems
+    }
+
+    // Packs the next frame, sent at time_us since the Unix epoch.
+    pub fn pack(&mut self, now: Instant, time_us: u64) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(self.size);
+        for field in &self.fields {
+            match &field.source {
+                Source::Channel(_) => {
+                    let value = match field.latest {
+                        Some((value, at))
+                            if self
+                                .stale
+                                .is_none_or(|stale| now.duration_since(at) <= stale) =>
+                        {
+                            value
+                        }
+                        _ => field.missing,
+                    };
+                    push_float(&mut frame, field.kind, value, self.byte_order);
+                }
+                Source::Constant(value) => {
+                    push_float(&mut frame, field.kind, *value, self.byte_order)
+                }
+                Source::Generated(Generated::Counter) => {
+                    push_int(&mut frame, field.kind, self.packed, self.byte_order)
+                }
+                Source::Generated(Generated::TimeUs) => {
+                    push_int(&mut frame, field.kind, time_us, self.byte_order)
+                }
+                Source::Pad(bytes) => frame.resize(frame.len() + bytes, 0),
+            }
+        }
+        self.packed += 1;
+        frame
+    }
+}
+
+macro_rules! push {
+    ($frame:expr, $value:expr, $order:expr) => {
+        match $order {
+            ByteOrder::Big => $frame.extend_from_slice(&$value.to_be_bytes()),
+            ByteOrder::Little => $frame.extend_from_slice(&$value.to_le_bytes()),
+        }
+    };
+}
+
+// Integer fields take the value rounded, and saturated at their range, with NaN as 0.
+fn push_float(frame: &mut Vec<u8>, kind: FieldType, value: f64, order: ByteOrder) {
+    let rounded = value.round();
+    match kind {
+        FieldType::U8 => push!(frame, rounded as u8, order),
+        FieldType::I8 => push!(frame, rounded as i8, order),
+        FieldType::U16 => push!(frame, rounded as u16, order),
+        FieldType::I16 => push!(frame, rounded as i16, order),
+        FieldType::U32 => push!(frame, rounded as u32, order),
+        FieldType::I32 => push!(frame, rounded as i32, order),
+        FieldType::U64 => push!(frame, rounded as u64, order),
+        FieldType::I64 => push!(frame, rounded as i64, order),
+        FieldType::F32 => push!(frame, value as f32, order),
+        FieldType::F64 => push!(frame, value, order),
+        FieldType::Pad => {}
+    }
+}
+
+// Integer fields take the value wrapped around at their size, so a counter counts up from
+// 0 again once it overflows.
+fn push_int(frame: &mut Vec<u8>, kind: FieldType, value: u64, order: ByteOrder) {
+    match kind {
+        FieldType::U8 => push!(frame, value as u8, order),
+        FieldType::I8 => push!(frame, value as i8, order),
+        FieldType::U16 => push!(frame, value as u16, order),
+        FieldType::I16 => push!(frame, value as i16, order),
+        FieldType::U32 => push!(frame, value as u32, order),
+        FieldType::I32 => push!(frame, value as i32, order),
+        FieldType::U64 => push!(frame, value, order),
+        FieldType::I64 => push!(frame, value as i64, order),
+        FieldType::F32 => push!(frame, value as f32, order),
+        FieldType::F64 => push!(frame, value as f64, order),
+        FieldType::Pad => {}
+    }
+}
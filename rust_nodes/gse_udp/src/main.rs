@@ -0,0 +1,154 @@
+mod config;
+mod frame;
+
+use clap::Parser;
+use config::Config;
+use frame::Frame;
+use node_framework::{Context, Event, Node, NodeArgs};
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tracing::{debug, error, info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+#[derive(Parser)]
+#[command(
+    about = "Packs decoded channels into the fixed binary frame of its config and sends it to legacy GSE over UDP at a fixed rate"
+)]
+struct Args {
+    /// Frame layout and rate (TOML or JSON).
+    config: PathBuf,
+    /// Address of the GSE to send the frames to.
+    #[arg(long, value_name = "ADDR")]
+    gse_addr: SocketAddr,
+    /// Address to send from, for GSE that only takes frames from a known port; any port
+    /// on every interface if not given.
+    #[arg(long, value_name = "ADDR")]
+    bind_addr: Option<SocketAddr>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct GseUdp {
+    frame: Frame,
+    period: Duration,
+    rate_hz: f64,
+    addr: SocketAddr,
+    bind_addr: Option<SocketAddr>,
+    socket: Option<UdpSocket>,
+    // Frames that could not be sent since the last one that was, while sending fails.
+    failing: Option<u64>,
+    // Fields whose channel could not be taken, reported once each.
+    reported: HashSet<String>,
+    // Keys whose samples cannot be decoded, reported once each.
+    skipped: HashSet<String>,
+}
+
+impl GseUdp {
+    fn on_sample(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = sample.key_expr().as_str();
+        let Some(value) = telemetry_json::decode(sample).map_err(|e| format!("{}: {}", key, e))?
+        else {
+            if self.skipped.insert(key.to_string()) {
+                debug!(
+                    "Not taking channels from {}: no conversion for {}",
+                    key,
+                    sample.encoding()
+                );
+            }
+            return Ok(());
+        };
+        for (field, problem) in self.frame.update(key, &value, Instant::now()) {
+            if self.reported.insert(field.clone()) {
+                warn!("Sending {} as missing: {}", field, problem);
+            }
+        }
+        Ok(())
+    }
+
+    async fn on_tick(&mut self) -> zenoh::Result<()> {
+        let Some(socket) = &self.socket else {
+            return Ok(());
+        };
+        let time_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_micros() as u64);
+        let frame = self.frame.pack(Instant::now(), time_us);
+        match (socket.send_to(&frame, self.addr).await, self.failing) {
+            (Ok(_), Some(failed)) => {
+                info!("Sending to the GSE again after {} frames failed", failed);
+                self.failing = None;
+            }
+            (Ok(_), None) => {}
+            (Err(e), None) => {
+                warn!("Cannot send frames to udp://{}: {}", self.addr, e);
+                self.failing = Some(1);
+            }
+            (Err(_), Some(failed)) => self.failing = Some(failed + 1),
+        }
+        Ok(())
+    }
+}
+
+impl Node for GseUdp {
+    const NAME: &'static str = "gse_udp";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let local = self.bind_addr.unwrap_or(match self.addr {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        });
+        self.socket = Some(UdpSocket::bind(local).await?);
+        info!(
+            "Sending {}-byte frames to udp://{} at {} Hz",
+            self.frame.size(),
+            self.addr,
+            self.rate_hz
+        );
+        let keys: Vec<String> = self.frame.keys().map(str::to_string).collect();
+        for key in keys {
+            ctx.subscribe(&key).await?;
+        }
+        ctx.add_timer(self.period);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(&sample),
+            Event::Timer(_) => self.on_tick().await,
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let config = match Config::load(&args.config) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Invalid GSE frame config: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let node = GseUdp {
+        frame: Frame::new(&config),
+        period: config.period(),
+        rate_hz: config.rate_hz,
+        addr: args.gse_addr,
+        bind_addr: args.bind_addr,
+        socket: None,
+        failing: None,
+        reported: HashSet::new(),
+        skipped: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,103 @@
+use common::config;
+use sensor_decode::MessageType;
+use serde::Deserialize;
+use std::path::Path;
+use zenoh::key_expr::KeyExpr;
+
+// Limits used when no config file is given.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitKind {
+    Min,
+    Max,
+    // Bound on the absolute rate of change per second.
+    Rate,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LimitConfig {
+    // Key expression of the sensors the limit applies to.
+    pub key: String,
+    pub channel: String,
+    pub kind: LimitKind,
+    pub limit: f64,
+    // How far back inside the limit a value must be for an active alarm to clear.
+    #[serde(default)]
+    pub hysteresis: f64,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HealthConfig {
+    pub limits: Vec<LimitConfig>,
+}
+
+impl HealthConfig {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: HealthConfig = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let types = [
+            MessageType::Imu,
+            MessageType::Gyro,
+            MessageType::Altitude,
+            MessageType::Barometer,
+            MessageType::Magnetometer,
+            MessageType::Gnss,
+            MessageType::Temperature,
+            MessageType::Power,
+        ];
+        for limit in &self.limits {
+            KeyExpr::new(limit.key.as_str()).map_err(|e| format!("{}: {}", limit.key, e))?;
+            if !types
+                .iter()
+                .any(|t| t.channels().contains(&limit.channel.as_str()))
+            {
+                return Err(format!(
+                    "{}: unknown channel '{}'",
+                    limit.key, limit.channel
+                ));
+            }
+            if !limit.limit.is_finite() || limit.hysteresis.is_nan() || limit.hysteresis < 0.0 {
+                return Err(format!(
+                    "{} {}: limit must be finite and hysteresis non-negative",
+                    limit.key, limit.channel
+                ));
+            }
+            if limit.kind == LimitKind::Rate && limit.limit <= 0.0 {
+                return Err(format!(
+                    "{} {}: rate limits must be positive",
+                    limit.key, limit.channel
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        let config: HealthConfig =
+            toml::from_str(DEFAULT_CONFIG).expect("Built-in health config is invalid.");
+        config
+            .validate()
+            .expect("Built-in health config is invalid.");
+        config
+    }
+}
@@ -0,0 +1,45 @@
+use crate::config::{LimitConfig, LimitKind, Severity};
+use zenoh::key_expr::KeyExpr;
+
+// A configured limit with its key expression parsed.
+pub struct Limit {
+    pub key: KeyExpr<'static>,
+    pub channel: String,
+    pub kind: LimitKind,
+    pub limit: f64,
+    pub hysteresis: f64,
+    pub severity: Severity,
+}
+
+impl Limit {
+    pub fn new(config: &LimitConfig) -> Self {
+        Limit {
+            key: KeyExpr::new(config.key.clone()).expect("Limit key was validated."),
+            channel: config.channel.clone(),
+            kind: config.kind,
+            limit: config.limit,
+            hysteresis: config.hysteresis,
+            severity: config.severity,
+        }
+    }
+
+    // Whether the alarm is active for this value, given whether it already was. An active
+    // alarm only clears once the value is back inside the limit by the hysteresis, so a
+    // value hovering at the limit does not toggle it every sample. Rate limits are given
+    // the absolute rate.
+    pub fn violated(&self, value: f64, active: bool) -> bool {
+        let margin = if active { self.hysteresis } else { 0.0 };
+        match self.kind {
+            LimitKind::Min => value < self.limit + margin,
+            LimitKind::Max | LimitKind::Rate => value > self.limit - margin,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self.kind {
+            LimitKind::Min => "min",
+            LimitKind::Max => "max",
+            LimitKind::Rate => "max rate",
+        }
+    }
+}
@@ -0,0 +1,182 @@
+mod config;
+mod limits;
+
+use clap::Parser;
+use common::encoding;
+use config::{HealthConfig, LimitKind, Severity};
+use keyspace::keys;
+use limits::Limit;
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensor_decode::MessageType;
+use sensors_rs::sensors;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use zenoh::qos::CongestionControl;
+
+#[derive(Parser)]
+#[command(about = "Checks sensor samples against limits and publishes alarms on alarms/**")]
+struct Args {
+    /// Limits to check (TOML or JSON). Defaults to the built-in limits.
+    config: Option<PathBuf>,
+    /// Key expression of the sensors to check.
+    #[arg(long, default_value = keys::all_devices())]
+    key: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+fn encode_alarm(source: &str, limit: &Limit, value: f64, active: bool) -> Vec<u8> {
+    let timestamp_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0);
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+    let source = builder.create_string(source);
+    let channel = builder.create_string(&limit.channel);
+    let alarm = sensors::Alarm::create(
+        &mut builder,
+        &sensors::AlarmArgs {
+            timestamp_us,
+            source: Some(source),
+            channel: Some(channel),
+            kind: match limit.kind {
+                LimitKind::Min => sensors::AlarmKind::BelowMin,
+                LimitKind::Max => sensors::AlarmKind::AboveMax,
+                LimitKind::Rate => sensors::AlarmKind::RateOfChange,
+            },
+            severity: match limit.severity {
+                Severity::Info => sensors::AlarmSeverity::Info,
+                Severity::Warning => sensors::AlarmSeverity::Warning,
+                Severity::Critical => sensors::AlarmSeverity::Critical,
+            },
+            active,
+            value,
+            limit: limit.limit,
+        },
+    );
+    builder.finish(alarm, None);
+    builder.finished_data().to_vec()
+}
+
+// Prints an alarm being raised or cleared and publishes it on its alarm key.
+async fn report(
+    session: &zenoh::Session,
+    source: &str,
+    limit: &Limit,
+    value: f64,
+    active: bool,
+) -> zenoh::Result<()> {
+    info!(
+        "{} {:?} {} {}: {:.3} ({} {})",
+        if active { "ALARM" } else { "clear" },
+        limit.severity,
+        source,
+        limit.channel,
+        value,
+        limit.describe(),
+        limit.limit
+    );
+    // Alarms are rare and each one matters, so none are dropped under congestion.
+    session
+        .put(
+            keys::alarm(source, &limit.channel),
+            encode_alarm(source, limit, value, active),
+        )
+        .encoding(encoding::flatbuffer("sensors.Alarm"))
+        .congestion_control(CongestionControl::Block)
+        .await
+}
+
+struct Health {
+    key: String,
+    limits: Vec<Limit>,
+    // Latest values of each sensor and when they arrived. Rates of change are taken
+    // between arrivals, so they are only meaningful for samples played in real time.
+    latest: HashMap<String, (Instant, Vec<f64>)>,
+    // Active alarms by sensor key and limit index.
+    active: HashSet<(String, usize)>,
+}
+
+impl Node for Health {
+    const NAME: &'static str = "health";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.subscribe(&self.key).await?;
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let Event::Sample(_, sample) = event else {
+            return Ok(());
+        };
+        let key = sample.key_expr();
+        let Some(kind) = MessageType::detect(key.as_str(), sample.encoding()) else {
+            return Ok(());
+        };
+        let values = sensor_decode::values(kind, sample.encoding(), sample.payload())
+            .map_err(|e| format!("{}: failed to decode {:?}: {}", key, kind, e))?;
+        let now = Instant::now();
+        let previous = self.latest.insert(key.to_string(), (now, values.clone()));
+
+        for (i, limit) in self.limits.iter().enumerate() {
+            if !limit.key.includes(key) {
+                continue;
+            }
+            let Some(channel) = kind.channels().iter().position(|c| *c == limit.channel) else {
+                continue;
+            };
+            let value = match (limit.kind, &previous) {
+                (LimitKind::Rate, Some((then, before))) => {
+                    let dt = now.duration_since(*then).as_secs_f64();
+                    if dt <= 0.0 {
+                        continue;
+                    }
+                    ((values[channel] - before[channel]) / dt).abs()
+                }
+                (LimitKind::Rate, None) => continue,
+                (LimitKind::Min | LimitKind::Max, _) => values[channel],
+            };
+
+            let id = (key.to_string(), i);
+            let was_active = self.active.contains(&id);
+            let active = limit.violated(value, was_active);
+            if active == was_active {
+                continue;
+            }
+            if active {
+                self.active.insert(id);
+            } else {
+                self.active.remove(&id);
+            }
+            report(ctx.session(), key.as_str(), limit, value, active).await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let config = match &args.config {
+        Some(path) => match HealthConfig::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid health config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => HealthConfig::default(),
+    };
+
+    let node = Health {
+        key: args.key.clone(),
+        limits: config.limits.iter().map(Limit::new).collect(),
+        latest: HashMap::new(),
+        active: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,135 @@
+use clap::Parser;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId};
+use sensors_rs::sensors;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+// How often heartbeat ages are checked against --timeout-ms.
+const CHECK_PERIOD: Duration = Duration::from_millis(250);
+
+#[derive(Parser)]
+#[command(about = "Reports nodes coming up, going down, or stalling on the bus")]
+struct Args {
+    /// Report a live node as stale after this long without a heartbeat, in milliseconds.
+    #[arg(long, default_value_t = 3000)]
+    timeout_ms: u64,
+    /// Also print the loop statistics of every heartbeat received.
+    #[arg(long)]
+    stats: bool,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// What is known about one node holding a liveliness token.
+struct NodeStatus {
+    // When the token appeared or the last heartbeat arrived, whichever is later.
+    last_seen: Instant,
+    stale: bool,
+}
+
+struct HealthMonitor {
+    timeout: Duration,
+    stats: bool,
+    alive: Option<SubscriptionId>,
+    nodes: HashMap<String, NodeStatus>,
+}
+
+impl HealthMonitor {
+    fn on_liveliness(&mut self, sample: &Sample) {
+        let key = sample.key_expr().as_str();
+        let name = key.strip_prefix(keys::ALIVE).unwrap_or(key);
+        let name = name.trim_start_matches('/').to_string();
+        match sample.kind() {
+            SampleKind::Put => {
+                info!("{} up", name);
+                self.nodes.insert(
+                    name,
+                    NodeStatus {
+                        last_seen: Instant::now(),
+                        stale: false,
+                    },
+                );
+            }
+            SampleKind::Delete => {
+                info!("{} down", name);
+                self.nodes.remove(&name);
+            }
+        }
+    }
+
+    fn on_heartbeat(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        let payload = sample.payload().to_bytes();
+        let heartbeat = flatbuffers::root::<sensors::Heartbeat>(&payload)
+            .map_err(|e| format!("malformed heartbeat on {}: {}", sample.key_expr(), e))?;
+        let name = heartbeat.node().unwrap_or_default();
+        if self.stats {
+            info!(
+                "{:<16} up {:.0} s, {} cycles, {} errors, cycle mean {:.0} us, max {:.0} us",
+                name,
+                heartbeat.uptime_s(),
+                heartbeat.cycles(),
+                heartbeat.errors(),
+                heartbeat.mean_cycle_us(),
+                heartbeat.max_cycle_us()
+            );
+        }
+        // Heartbeats of nodes whose token is not known yet are ignored; the token
+        // brings them in.
+        if let Some(status) = self.nodes.get_mut(name) {
+            if status.stale {
+                info!("{} resumed", name);
+            }
+            status.last_seen = Instant::now();
+            status.stale = false;
+        }
+        Ok(())
+    }
+
+    fn check_stale(&mut self) {
+        for (name, status) in &mut self.nodes {
+            let age = status.last_seen.elapsed();
+            if !status.stale && age > self.timeout {
+                warn!("{} stale: no heartbeat for {} ms", name, age.as_millis());
+                status.stale = true;
+            }
+        }
+    }
+}
+
+impl Node for HealthMonitor {
+    const NAME: &'static str = "health_monitor";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        self.alive = Some(ctx.subscribe_liveliness(keys::all_alive()).await?);
+        ctx.subscribe(keys::all_heartbeats()).await?;
+        ctx.add_timer(CHECK_PERIOD);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Timer(_) => self.check_stale(),
+            Event::Sample(id, sample) if Some(id) == self.alive => self.on_liveliness(&sample),
+            Event::Sample(_, sample) => self.on_heartbeat(&sample)?,
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = HealthMonitor {
+        timeout: Duration::from_millis(args.timeout_ms),
+        stats: args.stats,
+        alive: None,
+        nodes: HashMap::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,197 @@
+// Harness for tests that run the real node binaries together: a graph of child processes
+// around a Zenoh router of the test's own, which the tests observe and which is torn down
+// at the end with every node required to shut down cleanly.
+mod node;
+
+pub use node::{NodeProcess, program};
+
+use keyspace::keys;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::{Instant, sleep, timeout};
+use zenoh::sample::Sample;
+
+const ALIVE_POLL: Duration = Duration::from_millis(100);
+
+// One isolated node graph. The router listens on a free localhost port with multicast and
+// gossip scouting off and the nodes connect to it as clients, so tests running at the same
+// time, and anything else on the host or network, never meet.
+pub struct Graph {
+    session: zenoh::Session,
+    endpoint: String,
+    nodes: Vec<NodeProcess>,
+    dir: PathBuf,
+}
+
+impl Graph {
+    pub async fn new() -> Self {
+        // The port is free now; the router takes it straight away.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("No free localhost port.")
+            .port();
+        let endpoint = format!("tcp/127.0.0.1:{}", port);
+        let mut config = zenoh::Config::default();
+        for (key, value) in [
+            ("mode", "\"router\"".to_string()),
+            ("listen/endpoints", format!("[\"{}\"]", endpoint)),
+            ("scouting/multicast/enabled", "false".to_string()),
+            ("scouting/gossip/enabled", "false".to_string()),
+        ] {
+            config
+                .insert_json5(key, &value)
+                .expect("Invalid router config.");
+        }
+        let session = zenoh::open(config)
+            .await
+            .unwrap_or_else(|e| panic!("Cannot listen on {}: {}", endpoint, e));
+        let dir =
+            std::env::temp_dir().join(format!("integration_tests-{}-{}", std::process::id(), port));
+        std::fs::create_dir_all(&dir).expect("Cannot create the scratch directory.");
+        Graph {
+            session,
+            endpoint,
+            nodes: Vec::new(),
+            dir,
+        }
+    }
+
+    // The test's own session, on the router.
+    pub fn session(&self) -> &zenoh::Session {
+        &self.session
+    }
+
+    // Scratch directory for files the nodes write, removed with the graph.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // Starts a workspace binary connected to the graph's router.
+    pub fn start(&mut self, name: &str, args: &[&str]) -> &NodeProcess {
+        self.start_with_env(name, args, &[])
+    }
+
+    pub fn start_with_env(
+        &mut self,
+        name: &str,
+        args: &[&str],
+        env: &[(&str, &str)],
+    ) -> &NodeProcess {
+        let mut all: Vec<String> = ["--mode", "client", "--connect", &self.endpoint]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        all.extend(args.iter().map(|arg| arg.to_string()));
+        let node = NodeProcess::spawn(name, &all, env)
+            .unwrap_or_else(|e| panic!("Cannot start {}: {}", name, e));
+        self.nodes.push(node);
+        self.nodes.last().unwrap()
+    }
+
+    pub fn node(&self, name: &str) -> &NodeProcess {
+        self.nodes
+            .iter()
+            .find(|node| node.name == name)
+            .unwrap_or_else(|| panic!("{} was not started", name))
+    }
+
+    // Waits until the node's liveliness token on @/alive/<name> is up.
+    pub async fn wait_alive(&self, name: &str, within: Duration) {
+        let deadline = Instant::now() + within;
+        while Instant::now() < deadline {
+            let replies = self
+                .session
+                .liveliness()
+                .get(keys::alive(name))
+                .timeout(ALIVE_POLL)
+                .await
+                .expect("Liveliness query failed.");
+            if replies.recv_async().await.is_ok() {
+                return;
+            }
+            sleep(ALIVE_POLL).await;
+        }
+        panic!("{} did not come up within {:?}", name, within);
+    }
+
+    // Waits for a sample on key_expr that accept takes, returning it.
+    pub async fn wait_for_sample(
+        &self,
+        key_expr: &str,
+        within: Duration,
+        accept: impl Fn(&Sample) -> bool,
+    ) -> Sample {
+        let subscriber = self
+            .session
+            .declare_subscriber(key_expr)
+            .await
+            .expect("Cannot subscribe.");
+        let deadline = Instant::now() + within;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            match timeout(left, subscriber.recv_async()).await {
+                Ok(Ok(sample)) if accept(&sample) => return sample,
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => panic!("Subscriber on {} closed: {}", key_expr, e),
+                Err(_) => panic!("No sample on {} within {:?}", key_expr, within),
+            }
+        }
+    }
+
+    // Stops one node, which must exit successfully, and takes it out of the graph.
+    pub async fn stop(&mut self, name: &str) {
+        let index = self
+            .nodes
+            .iter()
+            .position(|node| node.name == name)
+            .unwrap_or_else(|| panic!("{} was not started", name));
+        let mut node = self.nodes.remove(index);
+        match node.stop().await {
+            Ok(status) if status.success() => {}
+            Ok(status) => panic!(
+                "{} exited with {}:\n{}",
+                name,
+                status,
+                node.output().join("\n")
+            ),
+            Err(e) => panic!("{}:\n{}", e, node.output().join("\n")),
+        }
+    }
+
+    // Stops every node, the last started first, and closes the router. Every node must
+    // still have been running and must exit successfully; the output of those that did
+    // not is in the panic message.
+    pub async fn shutdown(mut self) {
+        let mut failures = Vec::new();
+        while let Some(mut node) = self.nodes.pop() {
+            let early = node.exited();
+            let result = node.stop().await;
+            let failure = match (early, result) {
+                (Some(status), _) => Some(format!("exited early with {}", status)),
+                (None, Ok(status)) if status.success() => None,
+                (None, Ok(status)) => Some(format!("exited with {}", status)),
+                (None, Err(e)) => Some(e),
+            };
+            if let Some(failure) = failure {
+                failures.push(format!(
+                    "{} {}:\n{}",
+                    node.name,
+                    failure,
+                    node.output().join("\n")
+                ));
+            }
+        }
+        if let Err(e) = self.session.close().await {
+            failures.push(format!("router did not close: {}", e));
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+    }
+}
+
+impl Drop for Graph {
+    // Nodes left running are killed as their processes drop.
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
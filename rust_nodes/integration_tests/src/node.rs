@@ -0,0 +1,140 @@
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::time::{Instant, sleep, timeout};
+
+// How long a node has to shut down on SIGTERM before it is killed.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+const OUTPUT_POLL: Duration = Duration::from_millis(50);
+
+// Environment the tests' own shell may carry that would take the nodes out of the graph or
+// change how they run.
+const CLEARED_ENV: &[&str] = &[
+    "ZENOH_CONFIG",
+    "COMMAND_KEY",
+    "PARAM_DIR",
+    "FUSION_MODE",
+    "RUST_LOG",
+];
+
+// The executable of a workspace binary: the path in <NAME>_BIN if set, as Bazel passes its
+// runfiles, else the one cargo built next to the test executable.
+pub fn program(name: &str) -> PathBuf {
+    if let Some(path) = std::env::var_os(format!("{}_BIN", name.to_uppercase())) {
+        return PathBuf::from(path);
+    }
+    // Test executables live in target/<profile>/deps, binaries in target/<profile>.
+    let path = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.parent()?.join(name)))
+        .unwrap_or_else(|| PathBuf::from(name));
+    assert!(
+        path.is_file(),
+        "{} is not built; run `cargo build --workspace` before the integration tests",
+        path.display()
+    );
+    path
+}
+
+// A node running as a child process, with everything it prints collected line by line.
+pub struct NodeProcess {
+    pub name: String,
+    child: Child,
+    output: Arc<Mutex<Vec<String>>>,
+}
+
+impl NodeProcess {
+    pub(crate) fn spawn(
+        name: &str,
+        args: &[String],
+        env: &[(&str, &str)],
+    ) -> std::io::Result<Self> {
+        let mut command = Command::new(program(name));
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for key in CLEARED_ENV {
+            command.env_remove(key);
+        }
+        command.envs(env.iter().copied());
+        let mut child = command.spawn()?;
+        let output = Arc::new(Mutex::new(Vec::new()));
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(collect(stdout, output.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(collect(stderr, output.clone()));
+        }
+        Ok(NodeProcess {
+            name: name.to_string(),
+            child,
+            output,
+        })
+    }
+
+    // Every line printed so far, stdout and stderr interleaved as they arrived.
+    pub fn output(&self) -> Vec<String> {
+        self.output.lock().unwrap().clone()
+    }
+
+    // Waits until the node prints a line containing text.
+    pub async fn wait_for_output(&self, text: &str, within: Duration) {
+        let deadline = Instant::now() + within;
+        loop {
+            if self.output().iter().any(|line| line.contains(text)) {
+                return;
+            }
+            if Instant::now() >= deadline {
+                panic!(
+                    "{} did not print '{}' within {:?}; it printed:\n{}",
+                    self.name,
+                    text,
+                    within,
+                    self.output().join("\n")
+                );
+            }
+            sleep(OUTPUT_POLL).await;
+        }
+    }
+
+    // Whether the process has exited, without waiting for it.
+    pub fn exited(&mut self) -> Option<ExitStatus> {
+        self.child.try_wait().ok().flatten()
+    }
+
+    // Asks the node to shut down with SIGTERM, as the supervisor does, and returns how it
+    // exited. A node still running after the grace period is killed, which is an error.
+    pub async fn stop(&mut self) -> Result<ExitStatus, String> {
+        if let Some(status) = self.exited() {
+            return Ok(status);
+        }
+        if let Some(pid) = self.child.id() {
+            let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
+        }
+        match timeout(STOP_GRACE, self.child.wait()).await {
+            Ok(status) => status.map_err(|e| format!("cannot wait for {}: {}", self.name, e)),
+            Err(_) => {
+                let _ = self.child.kill().await;
+                Err(format!(
+                    "{} did not shut down within {:?} of SIGTERM",
+                    self.name, STOP_GRACE
+                ))
+            }
+        }
+    }
+}
+
+async fn collect(stream: impl AsyncRead + Unpin, output: Arc<Mutex<Vec<String>>>) {
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        output.lock().unwrap().push(line);
+    }
+}
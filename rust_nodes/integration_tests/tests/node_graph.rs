@@ -0,0 +1,79 @@
+// End-to-end runs of the node binaries on an isolated bus. Build them first with
+// `cargo build --workspace`.
+use flight_log::LogReader;
+use integration_tests::Graph;
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use zenoh::sample::Sample;
+
+const START: Duration = Duration::from_secs(10);
+const FLIGHT: Duration = Duration::from_secs(20);
+
+// Altitude of a fused state, if fusion flagged it valid.
+fn altitude(sample: &Sample) -> Option<f32> {
+    let bytes = sample.payload().to_bytes();
+    let state = flatbuffers::root::<sensors::FusedState>(&bytes).ok()?;
+    state.altitude_valid().then_some(state.altitude())
+}
+
+// Simulated sensors launch a second in, and fusion tracks the climb.
+async fn fly(graph: &mut Graph) {
+    graph.start("sensor_sim", &["--launch-after-s", "1"]);
+    graph.start("fusion", &[]);
+    graph.wait_alive("sensor_sim", START).await;
+    graph.wait_alive("fusion", START).await;
+    graph
+        .wait_for_sample(keys::fused_state(), START, |sample| {
+            altitude(sample).is_some()
+        })
+        .await;
+    graph
+        .wait_for_sample(keys::fused_state(), FLIGHT, |sample| {
+            altitude(sample).is_some_and(|h| h > 50.0)
+        })
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fusion_tracks_simulated_flight() {
+    let mut graph = Graph::new().await;
+    fly(&mut graph).await;
+    graph.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recorder_logs_sensors_and_state() {
+    let mut graph = Graph::new().await;
+    let log = graph.dir().join("flight.flog");
+    let output = log.to_str().unwrap().to_string();
+    graph.start("recorder", &["-o", &output, "--flush-ms", "100"]);
+    graph.wait_alive("recorder", START).await;
+    fly(&mut graph).await;
+    // The log is complete once the recorder has closed it.
+    graph.stop("recorder").await;
+
+    let mut keys = BTreeSet::new();
+    for record in LogReader::open(&log).expect("No log written.") {
+        keys.insert(record.expect("Malformed log.").key);
+    }
+    for key in [keys::imu(0), keys::fused_state().to_string()] {
+        assert!(keys.contains(&key), "{} not in the log: {:?}", key, keys);
+    }
+    graph.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn health_monitor_sees_nodes_come_and_go() {
+    let mut graph = Graph::new().await;
+    graph.start("health_monitor", &[]);
+    graph.wait_alive("health_monitor", START).await;
+    graph.start("fusion", &[]);
+    let monitor = graph.node("health_monitor");
+    monitor.wait_for_output("fusion up", START).await;
+    graph.stop("fusion").await;
+    let monitor = graph.node("health_monitor");
+    monitor.wait_for_output("fusion down", START).await;
+    graph.shutdown().await;
+}
@@ -0,0 +1,332 @@
+use crate::key;
+
+// Prefix under which every sensor publishes or answers queries.
+pub const DEVICES: &str = key!("devices");
+// Prefix of estimator outputs.
+pub const STATE: &str = key!("state");
+// Prefix of simulator outputs.
+pub const SIM: &str = key!("sim");
+// Prefix of node heartbeats.
+pub const HEARTBEAT: &str = key!("heartbeat");
+// Prefix of node liveliness tokens.
+pub const ALIVE: &str = key!("@/alive");
+// Prefix of operational events.
+pub const EVENTS: &str = key!("events");
+// Prefix of sensor limit alarms.
+pub const ALARMS: &str = key!("alarms");
+// Prefix of command queryables.
+pub const CMD: &str = key!("cmd");
+// Prefix of fault detection, isolation and recovery decisions.
+pub const FDIR: &str = key!("fdir");
+// Prefix of node parameters.
+pub const PARAM: &str = key!("param");
+// Prefix simulated sensors publish under when their samples go through fault_injector.
+pub const RAW: &str = key!("raw");
+// Prefix the downsampler republishes telemetry under, at the rates the downlink carries.
+pub const DOWNLINK: &str = key!("downlink");
+// Prefix of node statistics.
+pub const STATS: &str = key!("stats");
+// Prefix of CCSDS Space Packets, as the framer hands them to the radio.
+pub const CCSDS: &str = key!("ccsds");
+// Prefix the ground republishes the telemetry it depacketizes under, followed by its key,
+// e.g. ground/state/fused.
+pub const GROUND: &str = key!("ground");
+
+pub fn imu(id: u32) -> String {
+    format!("{}/imu{}", DEVICES, id)
+}
+
+pub fn gyro(id: u32) -> String {
+    format!("{}/gyro{}", DEVICES, id)
+}
+
+pub fn altitude(id: u32) -> String {
+    format!("{}/altitude{}", DEVICES, id)
+}
+
+pub fn barometer(id: u32) -> String {
+    format!("{}/baro{}", DEVICES, id)
+}
+
+pub fn magnetometer(id: u32) -> String {
+    format!("{}/mag{}", DEVICES, id)
+}
+
+pub fn gnss(id: u32) -> String {
+    format!("{}/gnss{}", DEVICES, id)
+}
+
+// A power board, such as one can_gateway publishes from the CAN bus.
+pub fn power(id: u32) -> String {
+    format!("{}/power{}", DEVICES, id)
+}
+
+pub const fn temp() -> &'static str {
+    key!("devices/temp")
+}
+
+// Matches every sensor key.
+pub const fn all_devices() -> &'static str {
+    key!("devices/**")
+}
+
+// Matches every estimator output.
+pub const fn all_state() -> &'static str {
+    key!("state/**")
+}
+
+// Achieved position of an actuator, published by the actuator node.
+pub fn actuator(name: &str) -> String {
+    format!("{}/actuators/{}", DEVICES, name)
+}
+
+// Position commands for an actuator. These are published samples, unlike the queries on
+// cmd/<node>/<command>, so they can go out at control rate.
+pub fn actuator_command(name: &str) -> String {
+    format!("{}/actuators/{}", CMD, name)
+}
+
+// Matches every actuator's position commands.
+pub const fn all_actuator_commands() -> &'static str {
+    key!("cmd/actuators/**")
+}
+
+// Matches every simulator output.
+pub const fn all_sim() -> &'static str {
+    key!("sim/**")
+}
+
+pub const fn fused_state() -> &'static str {
+    key!("state/fused")
+}
+
+// Flight phase published by mission_manager.
+pub const fn phase() -> &'static str {
+    key!("state/phase")
+}
+
+// Sensors fusion currently outvotes.
+pub const fn voting_status() -> &'static str {
+    key!("state/voting")
+}
+
+// Sensors fusion currently reads.
+pub const fn active_sensors() -> &'static str {
+    key!("state/sensors")
+}
+
+// Simulation time that drives fusion in simulated-time mode.
+pub const fn sim_clock() -> &'static str {
+    key!("sim/clock")
+}
+
+// Ground truth published by dynamics_sim.
+pub const fn sim_truth() -> &'static str {
+    key!("sim/truth")
+}
+
+// Multiplier dynamics_sim applies to its thrust curve from when it receives one, as a
+// decimal number in text, e.g. "0" to cut the motor out.
+pub const fn sim_thrust_scale() -> &'static str {
+    key!("sim/thrust_scale")
+}
+
+// Launch command consumed by the simulated sensors; a payload of "s" starts the flight.
+pub const fn launch() -> &'static str {
+    key!("launch")
+}
+
+pub fn heartbeat(node: &str) -> String {
+    format!("{}/{}", HEARTBEAT, node)
+}
+
+// Matches every node heartbeat.
+pub const fn all_heartbeats() -> &'static str {
+    key!("heartbeat/**")
+}
+
+// Drop statistics of the sequenced samples every node receives.
+pub const fn drop_stats() -> &'static str {
+    key!("stats/drops")
+}
+
+// Parse outcomes of fusion's sensor payloads, per sensor.
+pub const fn parse_stats() -> &'static str {
+    key!("stats/parse")
+}
+
+// Message and byte rates per key, as bus_monitor sees them.
+pub const fn bus_stats() -> &'static str {
+    key!("stats/bus")
+}
+
+// Resource usage, timer jitter and queue depths of a node, e.g. stats/fusion.
+pub fn node_stats(node: &str) -> String {
+    format!("{}/{}", STATS, node)
+}
+
+// Matches every node's statistics and the bus-wide ones, e.g. stats/drops.
+pub const fn all_stats() -> &'static str {
+    key!("stats/*")
+}
+
+// Liveliness token a node holds for as long as it runs.
+pub fn alive(node: &str) -> String {
+    format!("{}/{}", ALIVE, node)
+}
+
+// Matches every node liveliness token.
+pub const fn all_alive() -> &'static str {
+    key!("@/alive/**")
+}
+
+// Process starts, exits, and restarts reported by the supervisor.
+pub const fn supervisor_events() -> &'static str {
+    key!("events/supervisor")
+}
+
+// Launch, apogee, and landing as detected by event_detector.
+pub const fn flight_events() -> &'static str {
+    key!("events/flight")
+}
+
+// Arming, disarming, and firing of commands that must be armed first.
+pub const fn arming_events() -> &'static str {
+    key!("events/arming")
+}
+
+// Crash report a node publishes when it panics, e.g. events/crash/fusion.
+pub fn crash(node: &str) -> String {
+    format!("{}/crash/{}", EVENTS, node)
+}
+
+// Matches the crash reports of every node.
+pub const fn all_crashes() -> &'static str {
+    key!("events/crash/*")
+}
+
+// Deadline overruns of a node's timers, e.g. events/overrun/fusion.
+pub fn overrun(node: &str) -> String {
+    format!("{}/overrun/{}", EVENTS, node)
+}
+
+// Matches the deadline overruns of every node.
+pub const fn all_overruns() -> &'static str {
+    key!("events/overrun/*")
+}
+
+// Alarm about one channel of a sensor, e.g. alarms/devices/imu0/acceleration_z.
+pub fn alarm(source: &str, channel: &str) -> String {
+    format!("{}/{}/{}", ALARMS, source, channel)
+}
+
+// Matches every alarm.
+pub const fn all_alarms() -> &'static str {
+    key!("alarms/**")
+}
+
+// Fault injection commands consumed by fault_injector.
+pub const fn fault_commands() -> &'static str {
+    key!("faults/command")
+}
+
+// Health of every sensor as decided by the FDIR manager.
+pub const fn fdir_status() -> &'static str {
+    key!("fdir/status")
+}
+
+// Where the downsampler republishes the samples it keeps of key, e.g.
+// downlink/state/fused.
+pub fn downlink(key: &str) -> String {
+    format!("{}/{}", DOWNLINK, key)
+}
+
+// Matches everything the downsampler republishes.
+pub const fn all_downlink() -> &'static str {
+    key!("downlink/**")
+}
+
+// Prefix store_forward replays what it stored while the ground was out of contact under,
+// e.g. downlink/backlog/state/fused.
+pub const BACKLOG: &str = key!("downlink/backlog");
+
+pub fn backlog(key: &str) -> String {
+    format!("{}/{}", BACKLOG, key)
+}
+
+// Matches everything store_forward replays.
+pub const fn all_backlog() -> &'static str {
+    key!("downlink/backlog/**")
+}
+
+// Space Packets ccsds_framer builds of the downlink, one per sample, as a radio would
+// carry them to the ground.
+pub const fn ccsds_packets() -> &'static str {
+    key!("ccsds/tm")
+}
+
+// Space Packets ax25_decoder received over the radio on the ground; a separate key from
+// the ones sent, so a decoder on the same Zenoh network as the framer does not loop them
+// back up.
+pub const fn ccsds_received() -> &'static str {
+    key!("ccsds/rx")
+}
+
+// Queryable latency_test's responder echoes the requester's pings on.
+pub const fn latency_echo() -> &'static str {
+    key!("latency/echo")
+}
+
+// What throughput_test's publisher floods its subscriber with.
+pub const fn throughput_data() -> &'static str {
+    key!("throughput/data")
+}
+
+// Queryable throughput_test's subscriber reports what it received of each step on.
+pub const fn throughput_report() -> &'static str {
+    key!("throughput/report")
+}
+
+// Queryable a node answers one command on, e.g. cmd/supervisor/restart.
+pub fn command(node: &str, command: &str) -> String {
+    format!("{}/{}/{}", CMD, node, command)
+}
+
+// Matches every command of a node.
+pub fn node_commands(node: &str) -> String {
+    format!("{}/{}/*", CMD, node)
+}
+
+// Where a node serves one parameter and publishes its changes, e.g. param/fusion/voting/imu.
+pub fn param(node: &str, name: &str) -> String {
+    format!("{}/{}/{}", PARAM, node, name)
+}
+
+// Matches every parameter of a node.
+pub fn node_params(node: &str) -> String {
+    format!("{}/{}/**", PARAM, node)
+}
+
+// Prefix of the recorder's log queryable, e.g. log/devices/imu0?start=..&end=.. for what
+// it recorded on devices/imu0.
+pub const LOG: &str = key!("log");
+
+pub fn log(key: &str) -> String {
+    format!("{}/{}", LOG, key)
+}
+
+// Matches everything the recorder answers from its log.
+pub const fn all_log() -> &'static str {
+    key!("log/**")
+}
+
+// Where a node publishes the warnings and errors it logs, e.g. log/fusion. The recorder's
+// queryable shares the prefix but only answers queries, never samples.
+pub fn node_log(node: &str) -> String {
+    format!("{}/{}", LOG, node)
+}
+
+// Matches the logs of every node.
+pub const fn all_node_logs() -> &'static str {
+    key!("log/*")
+}
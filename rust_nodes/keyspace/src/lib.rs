@@ -0,0 +1,52 @@
+// Key expressions used on the bus, so nodes never spell key strings by hand.
+pub mod keys;
+
+// A key expression spelled out in the source, checked by checked() when the crate is
+// compiled, so a malformed static key fails the build instead of the first declaration.
+#[macro_export]
+macro_rules! key {
+    ($key:expr) => {
+        const { $crate::checked($key) }
+    };
+}
+
+// Returns the key if it is a key expression in the canonical form Zenoh matches on, and
+// panics otherwise: non-empty chunks between single slashes, wildcards only as whole `*`
+// or `**` chunks with no wildcard chunk right after a `**`, and none of the characters
+// `#`, `?`, and `$` that Zenoh reserves. Called through key! this fails at compile time.
+pub const fn checked(key: &'static str) -> &'static str {
+    let bytes = key.as_bytes();
+    assert!(!bytes.is_empty(), "key expressions cannot be empty");
+    let mut start = 0;
+    let mut stars = 0;
+    let mut after_double = false;
+    let mut i = 0;
+    while i <= bytes.len() {
+        if i == bytes.len() || bytes[i] == b'/' {
+            let len = i - start;
+            assert!(len > 0, "key expressions cannot have empty chunks");
+            assert!(
+                stars == 0 || (stars == len && len <= 2),
+                "wildcards must be whole `*` or `**` chunks"
+            );
+            assert!(
+                !(after_double && stars > 0),
+                "a wildcard chunk after `**` is not canonical"
+            );
+            after_double = stars == 2;
+            start = i + 1;
+            stars = 0;
+        } else {
+            let c = bytes[i];
+            assert!(
+                c != b'#' && c != b'?' && c != b'$',
+                "key expressions cannot contain `#`, `?`, or `$`"
+            );
+            if c == b'*' {
+                stars += 1;
+            }
+        }
+        i += 1;
+    }
+    key
+}
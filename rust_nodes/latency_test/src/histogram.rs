@@ -0,0 +1,104 @@
+use std::fmt;
+use std::time::Duration;
+
+// Sub-buckets per power of two: every bucket spans at most 1/SUB_BUCKETS of its lower
+// bound, so quantiles read from it are within 12.5% of the exact ones.
+const SUB_BITS: u32 = 3;
+const SUB_BUCKETS: u64 = 1 << SUB_BITS;
+
+// Bucket a round trip of us microseconds falls into: one per microsecond below
+// SUB_BUCKETS, then SUB_BUCKETS per power of two.
+fn bucket(us: u64) -> usize {
+    if us < SUB_BUCKETS {
+        return us as usize;
+    }
+    let exponent = 63 - us.leading_zeros();
+    let sub = (us >> (exponent - SUB_BITS)) & (SUB_BUCKETS - 1);
+    (SUB_BUCKETS * (exponent - SUB_BITS + 1) as u64 + sub) as usize
+}
+
+// Smallest round trip in microseconds that falls into a bucket.
+fn lower_bound(bucket: usize) -> u64 {
+    let bucket = bucket as u64;
+    if bucket < SUB_BUCKETS {
+        return bucket;
+    }
+    let exponent = bucket / SUB_BUCKETS + SUB_BITS as u64 - 1;
+    (SUB_BUCKETS + bucket % SUB_BUCKETS) << (exponent - SUB_BITS as u64)
+}
+
+// Round trip times, with the exact minimum, mean and maximum and log-linear buckets for
+// quantiles.
+#[derive(Default)]
+pub struct Histogram {
+    counts: Vec<u64>,
+    count: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl Histogram {
+    pub fn record(&mut self, rtt: Duration) {
+        let bucket = bucket(rtt.as_micros() as u64);
+        if self.counts.len() <= bucket {
+            self.counts.resize(bucket + 1, 0);
+        }
+        self.counts[bucket] += 1;
+        self.min = if self.count == 0 {
+            rtt
+        } else {
+            self.min.min(rtt)
+        };
+        self.max = self.max.max(rtt);
+        self.count += 1;
+        self.total += rtt;
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64)
+    }
+
+    // Upper bound of the bucket holding the q quantile, capped at the maximum.
+    pub fn quantile(&self, q: f64) -> Duration {
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_micros(lower_bound(bucket + 1)).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    // The non-empty buckets, as their lower and upper bound in microseconds and count.
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, u64, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(bucket, count)| (lower_bound(bucket), lower_bound(bucket + 1), *count))
+    }
+}
+
+// One line of statistics: count, min, mean, p99 and max in microseconds.
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.count == 0 {
+            return write!(f, "no replies");
+        }
+        write!(
+            f,
+            "{} replies, rtt min {:.1} mean {:.1} p99 {:.1} max {:.1} us",
+            self.count,
+            self.min.as_secs_f64() * 1e6,
+            self.mean().as_secs_f64() * 1e6,
+            self.quantile(0.99).as_secs_f64() * 1e6,
+            self.max.as_secs_f64() * 1e6
+        )
+    }
+}
@@ -0,0 +1,225 @@
+mod histogram;
+
+use clap::Parser;
+use common::shutdown_signal;
+use histogram::Histogram;
+use keyspace::keys;
+use node_framework::{Heartbeat, NodeArgs};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{error, info, warn};
+use zenoh::key_expr::KeyExpr;
+
+// Every ping starts with its sequence number and the time it was sent, in nanoseconds since
+// the requester started, both little-endian u64s; the rest is zero padding up to the size.
+const HEADER_LEN: usize = 16;
+
+// Width of the longest bar of the final histogram.
+const BAR_WIDTH: u64 = 50;
+
+#[derive(Parser)]
+#[command(
+    about = "Sends timestamped queries to latency_pong and reports round trip time statistics"
+)]
+struct Args {
+    /// Key expression of the echo queryable.
+    #[arg(long, default_value = keys::latency_echo())]
+    key: String,
+    /// Size of each ping in bytes, including its 16-byte header.
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u64).range(HEADER_LEN as u64..))]
+    size_bytes: u64,
+    /// Pings sent per second.
+    #[arg(long, default_value_t = 10.0)]
+    rate_hz: f64,
+    /// Stop after this many pings. Pings go on until Ctrl-C if this is not given.
+    #[arg(long)]
+    count: Option<u64>,
+    /// Milliseconds after which a ping without a reply counts as lost.
+    #[arg(long, default_value_t = 1000)]
+    timeout_ms: u64,
+    /// Period of the statistics lines in milliseconds.
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    report_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// What became of one ping.
+enum Outcome {
+    Reply(Duration),
+    // No reply came within the timeout, or the reply did not echo the ping.
+    Lost,
+}
+
+fn ping(sequence: u64, sent_ns: u64, size: usize) -> Vec<u8> {
+    let mut payload = vec![0; size];
+    payload[..8].copy_from_slice(&sequence.to_le_bytes());
+    payload[8..HEADER_LEN].copy_from_slice(&sent_ns.to_le_bytes());
+    payload
+}
+
+// Sends one ping and waits for the first reply, which must echo it byte for byte.
+async fn send(
+    session: zenoh::Session,
+    key: KeyExpr<'static>,
+    payload: Vec<u8>,
+    timeout: Duration,
+    started: Instant,
+) -> Outcome {
+    let replies = match session
+        .get(&key)
+        .payload(payload.clone())
+        .timeout(timeout)
+        .await
+    {
+        Ok(replies) => replies,
+        Err(e) => {
+            warn!("Failed to send ping: {}", e);
+            return Outcome::Lost;
+        }
+    };
+    let Ok(reply) = replies.recv_async().await else {
+        return Outcome::Lost;
+    };
+    let Ok(sample) = reply.result() else {
+        return Outcome::Lost;
+    };
+    let echo = sample.payload().to_bytes();
+    if *echo != *payload {
+        return Outcome::Lost;
+    }
+    let sent_ns = u64::from_le_bytes(echo[8..HEADER_LEN].try_into().unwrap());
+    Outcome::Reply(started.elapsed() - Duration::from_nanos(sent_ns))
+}
+
+fn print_histogram(histogram: &Histogram) {
+    let largest = histogram
+        .buckets()
+        .map(|(_, _, count)| count)
+        .max()
+        .unwrap_or(0);
+    for (lower, upper, count) in histogram.buckets() {
+        let bar = (count * BAR_WIDTH).div_ceil(largest) as usize;
+        println!(
+            "{:>8} - {:<8} us {:>8} {}",
+            lower,
+            upper,
+            count,
+            "#".repeat(bar)
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if !args.rate_hz.is_finite() || args.rate_hz <= 0.0 {
+        error!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    // Parsed once, so every ping reuses it instead of validating the key again.
+    let key = match KeyExpr::try_from(args.key.clone()) {
+        Ok(key) => key,
+        Err(e) => {
+            error!("Invalid --key '{}': {}", args.key, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let session = match args.node.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let heartbeat = Heartbeat::start(
+        &session,
+        args.node.name("latency_ping"),
+        args.node.heartbeat_period(),
+    )
+    .await
+    .expect("Failed to start heartbeat.");
+    info!(
+        "Pinging '{}' with {} bytes at {} Hz.",
+        args.key, args.size_bytes, args.rate_hz
+    );
+
+    let started = Instant::now();
+    let timeout = Duration::from_millis(args.timeout_ms);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut pings = interval(Duration::from_secs_f64(1.0 / args.rate_hz));
+    pings.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut report = interval(Duration::from_millis(args.report_ms));
+    report.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    report.tick().await;
+
+    let mut sent: u64 = 0;
+    let mut outstanding: u64 = 0;
+    let mut lost: u64 = 0;
+    let mut window_lost: u64 = 0;
+    let mut window = Histogram::default();
+    let mut total = Histogram::default();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        let done = args.count.is_some_and(|count| sent >= count);
+        if done && outstanding == 0 {
+            break;
+        }
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            Some(outcome) = rx.recv() => {
+                let handled = Instant::now();
+                outstanding -= 1;
+                let replied = match outcome {
+                    Outcome::Reply(rtt) => {
+                        window.record(rtt);
+                        total.record(rtt);
+                        true
+                    }
+                    Outcome::Lost => {
+                        window_lost += 1;
+                        lost += 1;
+                        false
+                    }
+                };
+                heartbeat.record(handled.elapsed(), replied);
+            }
+            _ = report.tick() => {
+                info!("{}, {} lost", window, window_lost);
+                window = Histogram::default();
+                window_lost = 0;
+            }
+            _ = pings.tick(), if !done => {
+                let sent_ns = started.elapsed().as_nanos() as u64;
+                let payload = ping(sent, sent_ns, args.size_bytes as usize);
+                let tx = tx.clone();
+                let task = send(session.clone(), key.clone(), payload, timeout, started);
+                tokio::spawn(async move {
+                    let _ = tx.send(task.await);
+                });
+                sent += 1;
+                outstanding += 1;
+            }
+        }
+    }
+
+    info!("Shutting down latency_ping...");
+    println!("{} pings sent: {}, {} lost", sent, total, lost);
+    print_histogram(&total);
+    let mut status = ExitCode::SUCCESS;
+    if let Err(e) = heartbeat.stop().await {
+        error!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = session.close().await {
+        error!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    status
+}
@@ -0,0 +1,86 @@
+use clap::Parser;
+use common::shutdown_signal;
+use keyspace::keys;
+use node_framework::{Heartbeat, NodeArgs};
+use std::process::ExitCode;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use zenoh::Wait;
+
+#[derive(Parser)]
+#[command(about = "Answers latency_ping's queries with their own payload")]
+struct Args {
+    /// Key expression of the echo queryable.
+    #[arg(long, default_value = keys::latency_echo())]
+    key: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let session = match args.node.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Queries are answered from Zenoh's callback rather than handed to the main loop, so
+    // the round trip measured is Zenoh's own; the main loop only keeps the heartbeat.
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let queryable = session
+        .declare_queryable(&args.key)
+        .callback(move |query| {
+            let started = Instant::now();
+            let payload = query.payload().cloned().unwrap_or_default();
+            let replied = query.reply(query.key_expr().clone(), payload).wait();
+            if let Err(e) = &replied {
+                warn!("Failed to answer ping: {}", e);
+            }
+            let _ = tx.send((started.elapsed(), replied.is_ok()));
+        })
+        .await
+        .expect("Failed to declare echo queryable.");
+    let heartbeat = Heartbeat::start(
+        &session,
+        args.node.name("latency_pong"),
+        args.node.heartbeat_period(),
+    )
+    .await
+    .expect("Failed to start heartbeat.");
+    info!("Echoing queries on '{}'.", args.key);
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            echo = rx.recv() => match echo {
+                Some((elapsed, ok)) => heartbeat.record(elapsed, ok),
+                None => break,
+            },
+        }
+    }
+
+    info!("Shutting down latency_pong...");
+    let mut status = ExitCode::SUCCESS;
+    if let Err(e) = heartbeat.stop().await {
+        error!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = queryable.undeclare().await {
+        error!("Failed to undeclare echo queryable: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = session.close().await {
+        error!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    status
+}
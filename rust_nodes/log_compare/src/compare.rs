@@ -0,0 +1,85 @@
+use log_export::channels::Recording;
+use log_export::resample::{self, Interpolation};
+use std::f64::consts::{PI, TAU};
+
+// Where a run's time starts for alignment: the first sample on the key given, or else the
+// first record of its log.
+pub fn origin(recording: &Recording, align: Option<&str>) -> Result<u64, String> {
+    let Some(key) = align else {
+        return Ok(recording.start_ns);
+    };
+    recording
+        .streams
+        .get(key)
+        .and_then(|stream| stream.timestamps_ns.first().copied())
+        .ok_or_else(|| format!("no samples on {}", key))
+}
+
+// Resamples both runs onto grids of rate_hz times counted from their origins, over the
+// span of time both cover, so the values at an index are at the same time into each run.
+// Returns the number of times compared.
+pub fn align(
+    baseline: &mut Recording,
+    candidate: &mut Recording,
+    origins: (u64, u64),
+    rate_hz: f64,
+) -> usize {
+    let span = |recording: &Recording, origin: u64| {
+        recording
+            .timeline()
+            .last()
+            .map_or(0, |&last| last.saturating_sub(origin))
+    };
+    let offsets = resample::grid(
+        0,
+        span(baseline, origins.0).min(span(candidate, origins.1)),
+        rate_hz,
+    );
+    for (recording, origin) in [(baseline, origins.0), (candidate, origins.1)] {
+        let grid: Vec<u64> = offsets.iter().map(|offset| origin + offset).collect();
+        resample::onto(recording, &grid, Interpolation::Linear);
+    }
+    offsets.len()
+}
+
+// How a channel of the candidate run differs from the baseline.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    // Times both runs have a value at.
+    pub compared: usize,
+    // Times only one of them has a value at, such as an estimate valid in one run only.
+    pub one_sided: usize,
+    // Of the difference, candidate minus baseline, at the times compared.
+    pub mean: f64,
+    pub rms: f64,
+    pub max_abs: f64,
+}
+
+// Compares two aligned series, taking differences of angles the short way around.
+pub fn stats(baseline: &[f64], candidate: &[f64], angle: bool) -> Stats {
+    let mut stats = Stats::default();
+    let (mut sum, mut sum_squares) = (0.0, 0.0);
+    for (&expected, &actual) in baseline.iter().zip(candidate) {
+        match (expected.is_nan(), actual.is_nan()) {
+            (true, true) => continue,
+            (false, false) => {}
+            _ => {
+                stats.one_sided += 1;
+                continue;
+            }
+        }
+        let mut difference = actual - expected;
+        if angle {
+            difference = (difference + PI).rem_euclid(TAU) - PI;
+        }
+        stats.compared += 1;
+        sum += difference;
+        sum_squares += difference * difference;
+        stats.max_abs = stats.max_abs.max(difference.abs());
+    }
+    if stats.compared > 0 {
+        stats.mean = sum / stats.compared as f64;
+        stats.rms = (sum_squares / stats.compared as f64).sqrt();
+    }
+    stats
+}
@@ -0,0 +1,82 @@
+use common::config;
+use serde::Deserialize;
+use std::path::Path;
+use zenoh::key_expr::{KeyExpr, OwnedKeyExpr};
+
+// Tolerances used when no config file is given.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Tolerance {
+    // Key expression of the streams the tolerance applies to.
+    pub key: String,
+    // The channel it applies to; every channel of the streams if left out.
+    #[serde(default)]
+    pub channel: Option<String>,
+    // Bound on the root mean square of the difference from the baseline.
+    #[serde(default)]
+    pub rms: Option<f64>,
+    // Bound on the largest magnitude of the difference.
+    #[serde(default)]
+    pub max_abs: Option<f64>,
+}
+
+impl Tolerance {
+    pub fn applies(&self, key: &str, channel: &str) -> bool {
+        let (Ok(expr), Ok(key)) = (KeyExpr::new(self.key.as_str()), KeyExpr::new(key)) else {
+            return false;
+        };
+        expr.intersects(&key) && self.channel.as_deref().is_none_or(|c| c == channel)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CompareConfig {
+    pub tolerances: Vec<Tolerance>,
+}
+
+impl CompareConfig {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: CompareConfig = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for tolerance in &self.tolerances {
+            let key = &tolerance.key;
+            OwnedKeyExpr::new(key.as_str()).map_err(|e| format!("{}: {}", key, e))?;
+            let bounds = [tolerance.rms, tolerance.max_abs];
+            if bounds.iter().all(Option::is_none) {
+                return Err(format!("{}: give rms, max_abs, or both", key));
+            }
+            if bounds
+                .iter()
+                .flatten()
+                .any(|bound| !(bound.is_finite() && *bound >= 0.0))
+            {
+                return Err(format!("{}: tolerances must be non-negative", key));
+            }
+        }
+        Ok(())
+    }
+
+    // The tolerance a channel is held to, if any.
+    pub fn tolerance(&self, key: &str, channel: &str) -> Option<&Tolerance> {
+        self.tolerances
+            .iter()
+            .find(|tolerance| tolerance.applies(key, channel))
+    }
+}
+
+impl Default for CompareConfig {
+    fn default() -> Self {
+        let config: CompareConfig =
+            toml::from_str(DEFAULT_CONFIG).expect("Built-in compare config is invalid.");
+        config
+            .validate()
+            .expect("Built-in compare config is invalid.");
+        config
+    }
+}
@@ -0,0 +1,193 @@
+mod compare;
+mod config;
+
+use clap::Parser;
+use compare::Stats;
+use config::CompareConfig;
+use log_export::channels::{self, Recording};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use zenoh::key_expr::OwnedKeyExpr;
+
+#[derive(Parser)]
+#[command(about = "Compares the channels of two recorded runs and fails on regressions")]
+struct Args {
+    /// Flight log of the reference run, or directory of its segments.
+    baseline: PathBuf,
+    /// Flight log of the run under test, or directory of its segments.
+    candidate: PathBuf,
+    /// TOML or JSON file of tolerances; defaults to the built-in config/default.toml.
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Rate of the grid, in Hz, both runs are resampled onto to be compared.
+    #[arg(long, default_value_t = 100.0)]
+    rate_hz: f64,
+    /// Key whose first sample marks the start of each run. Defaults to the first record of
+    /// each log.
+    #[arg(long)]
+    align_key: Option<String>,
+    /// Key expression to compare; repeatable. Defaults to every key.
+    #[arg(long = "key")]
+    keys: Vec<String>,
+}
+
+fn load(path: &Path, keys: &[OwnedKeyExpr]) -> Result<Recording, String> {
+    let logs =
+        channels::logs(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    if logs.is_empty() {
+        return Err(format!("{} holds no log segments", path.display()));
+    }
+    channels::read(&logs, keys)
+}
+
+// What a channel of the baseline came to in the candidate.
+enum Verdict {
+    Pass,
+    Untoleranced,
+    Regression(String),
+}
+
+fn report(name: &str, stats: &Stats, verdict: &Verdict) {
+    let verdict = match verdict {
+        Verdict::Pass => "ok".to_string(),
+        Verdict::Untoleranced => "-".to_string(),
+        Verdict::Regression(why) => format!("REGRESSION: {}", why),
+    };
+    println!(
+        "{:<40} {:>8} {:>8} {:>12.6} {:>12.6} {:>12.6}  {}",
+        name, stats.compared, stats.one_sided, stats.mean, stats.rms, stats.max_abs, verdict
+    );
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if !(args.rate_hz.is_finite() && args.rate_hz > 0.0) {
+        eprintln!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    let config = match &args.config {
+        Some(path) => match CompareConfig::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Invalid compare config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => CompareConfig::default(),
+    };
+    let mut keys = Vec::with_capacity(args.keys.len());
+    for key in &args.keys {
+        match OwnedKeyExpr::new(key.as_str()) {
+            Ok(key) => keys.push(key),
+            Err(e) => {
+                eprintln!("Invalid key expression {}: {}", key, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let (mut baseline, mut candidate) =
+        match (load(&args.baseline, &keys), load(&args.candidate, &keys)) {
+            (Ok(baseline), Ok(candidate)) => (baseline, candidate),
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+    let align = args.align_key.as_deref();
+    let origins = match (
+        compare::origin(&baseline, align),
+        compare::origin(&candidate, align),
+    ) {
+        (Ok(baseline), Ok(candidate)) => (baseline, candidate),
+        (Err(e), _) => {
+            eprintln!("{}: {}", args.baseline.display(), e);
+            return ExitCode::FAILURE;
+        }
+        (_, Err(e)) => {
+            eprintln!("{}: {}", args.candidate.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let times = compare::align(&mut baseline, &mut candidate, origins, args.rate_hz);
+    println!(
+        "Compared {} times at {} Hz ({:.3} s)",
+        times,
+        args.rate_hz,
+        times as f64 / args.rate_hz
+    );
+    println!(
+        "{:<40} {:>8} {:>8} {:>12} {:>12} {:>12}  verdict",
+        "channel", "compared", "one-side", "mean", "rms", "max_abs"
+    );
+
+    let mut regressions = 0;
+    for (key, expected) in &baseline.streams {
+        let actual = candidate.streams.get(key);
+        for (i, &channel) in expected.channels().iter().enumerate() {
+            let name = format!("{}/{}", key, channel);
+            let tolerance = config.tolerance(key, channel);
+            let (stats, verdict) = match actual {
+                None => (
+                    Stats::default(),
+                    Verdict::Regression("missing from candidate".to_string()),
+                ),
+                Some(actual) if actual.kind != expected.kind => (
+                    Stats::default(),
+                    Verdict::Regression(format!(
+                        "{} in candidate, {} in baseline",
+                        actual.kind.table(),
+                        expected.kind.table()
+                    )),
+                ),
+                Some(actual) => {
+                    let angle = expected.kind.is_angle(channel);
+                    let stats = compare::stats(&expected.values[i], &actual.values[i], angle);
+                    let verdict = match tolerance {
+                        None => Verdict::Untoleranced,
+                        Some(_) if stats.compared == 0 && stats.one_sided > 0 => {
+                            Verdict::Regression("valid in one run only".to_string())
+                        }
+                        Some(tolerance) => {
+                            let mut exceeded = Vec::new();
+                            if let Some(rms) = tolerance.rms.filter(|&rms| stats.rms > rms) {
+                                exceeded.push(format!("rms {:.6} > {}", stats.rms, rms));
+                            }
+                            if let Some(max) = tolerance.max_abs.filter(|&max| stats.max_abs > max)
+                            {
+                                exceeded.push(format!("max_abs {:.6} > {}", stats.max_abs, max));
+                            }
+                            if exceeded.is_empty() {
+                                Verdict::Pass
+                            } else {
+                                Verdict::Regression(exceeded.join(", "))
+                            }
+                        }
+                    };
+                    (stats, verdict)
+                }
+            };
+            // Channels nothing holds to a tolerance only inform, even when missing.
+            let verdict = match (tolerance, verdict) {
+                (None, Verdict::Regression(_)) => Verdict::Untoleranced,
+                (_, verdict) => verdict,
+            };
+            if matches!(verdict, Verdict::Regression(_)) {
+                regressions += 1;
+            }
+            report(&name, &stats, &verdict);
+        }
+    }
+    for key in candidate.streams.keys() {
+        if !baseline.streams.contains_key(key) {
+            println!("{} is new in the candidate", key);
+        }
+    }
+
+    if regressions > 0 {
+        println!("{} channels regressed", regressions);
+        ExitCode::FAILURE
+    } else {
+        println!("No regressions");
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,226 @@
+use crate::state;
+use common::encoding;
+use flight_log::LogReader;
+use sensor_decode::MessageType;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use zenoh::bytes::{Encoding, ZBytes};
+use zenoh::key_expr::{KeyExpr, OwnedKeyExpr};
+
+// What the samples of a key are: a sensor reading, or the fused or true state of the
+// vehicle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Sensor(MessageType),
+    Fused,
+    Truth,
+}
+
+impl Kind {
+    fn detect(key: &str, encoding: &Encoding) -> Option<Self> {
+        match encoding::flatbuffer_table(encoding).as_deref() {
+            Some(state::FUSED_STATE) => Some(Kind::Fused),
+            Some(state::SIM_TRUTH) => Some(Kind::Truth),
+            _ => MessageType::detect(key, encoding).map(Kind::Sensor),
+        }
+    }
+
+    pub fn table(self) -> &'static str {
+        match self {
+            Kind::Sensor(kind) => kind.table(),
+            Kind::Fused => state::FUSED_STATE,
+            Kind::Truth => state::SIM_TRUTH,
+        }
+    }
+
+    pub fn channels(self) -> &'static [&'static str] {
+        match self {
+            Kind::Sensor(kind) => kind.channels(),
+            Kind::Fused => state::FUSED_CHANNELS,
+            Kind::Truth => state::TRUTH_CHANNELS,
+        }
+    }
+
+    // Whether the named channel is an angle, so differences in it wrap around at ±π.
+    pub fn is_angle(self, channel: &str) -> bool {
+        matches!(self, Kind::Fused | Kind::Truth) && state::ANGLES.contains(&channel)
+    }
+
+    fn values(self, encoding: &Encoding, payload: Vec<u8>) -> Result<Vec<f64>, String> {
+        match self {
+            Kind::Sensor(kind) => sensor_decode::values(kind, encoding, &ZBytes::from(payload)),
+            Kind::Fused => state::fused(&payload),
+            Kind::Truth => state::truth(&payload),
+        }
+    }
+}
+
+// The decoded samples of one key.
+pub struct Stream {
+    pub kind: Kind,
+    pub timestamps_ns: Vec<u64>,
+    // One series per channel of kind, each with a value per timestamp.
+    pub values: Vec<Vec<f64>>,
+}
+
+impl Stream {
+    pub fn channels(&self) -> &'static [&'static str] {
+        self.kind.channels()
+    }
+
+    // Puts the samples in order of receive time, which a wall clock stepped back during
+    // the recording can leave them out of.
+    fn sort(&mut self) {
+        if self.timestamps_ns.is_sorted() {
+            return;
+        }
+        let mut order: Vec<usize> = (0..self.timestamps_ns.len()).collect();
+        order.sort_by_key(|&i| self.timestamps_ns[i]);
+        self.timestamps_ns = order.iter().map(|&i| self.timestamps_ns[i]).collect();
+        for series in &mut self.values {
+            *series = order.iter().map(|&i| series[i]).collect();
+        }
+    }
+
+    // The values of a channel at each of the times, None at times the stream has no
+    // sample at. The times must be in order; of samples received at the same time, the
+    // last is taken.
+    pub fn aligned<'a>(
+        &'a self,
+        channel: usize,
+        times_ns: &'a [u64],
+    ) -> impl Iterator<Item = Option<f64>> + 'a {
+        let mut next = 0;
+        times_ns.iter().map(move |&time| {
+            while next < self.timestamps_ns.len() && self.timestamps_ns[next] < time {
+                next += 1;
+            }
+            let mut found = None;
+            while next < self.timestamps_ns.len() && self.timestamps_ns[next] == time {
+                found = Some(self.values[channel][next]);
+                next += 1;
+            }
+            found
+        })
+    }
+}
+
+// What a set of logs holds that can be exported.
+#[derive(Default)]
+pub struct Recording {
+    // Receive time of the first record, decoded or not; times are exported from it.
+    pub start_ns: u64,
+    pub streams: BTreeMap<String, Stream>,
+    // Records that were not decoded, by key, with why.
+    pub skipped: BTreeMap<String, (u64, String)>,
+}
+
+impl Recording {
+    // Receive times of all samples of every stream, in order and each once.
+    pub fn timeline(&self) -> Vec<u64> {
+        let mut times: Vec<u64> = self
+            .streams
+            .values()
+            .flat_map(|stream| stream.timestamps_ns.iter().copied())
+            .collect();
+        times.sort_unstable();
+        times.dedup();
+        times
+    }
+
+    // Seconds since the start of the recording.
+    pub fn seconds(&self, timestamp_ns: u64) -> f64 {
+        timestamp_ns.saturating_sub(self.start_ns) as f64 / 1e9
+    }
+}
+
+// The logs at path: the segments of a segmented log directory, oldest first, or else the
+// single log file.
+pub fn logs(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        flight_log::segments(path)
+    } else {
+        Ok(vec![path.to_path_buf()])
+    }
+}
+
+// Reads and decodes every record of the logs on a key matching one of the key expressions,
+// or on any key if there are none.
+pub fn read(logs: &[PathBuf], keys: &[OwnedKeyExpr]) -> Result<Recording, String> {
+    let mut recording = Recording::default();
+    let mut first = true;
+    for path in logs {
+        let reader =
+            LogReader::open(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        for record in reader {
+            let record = match record {
+                Ok(record) => record,
+                // Logs cut short by a crash end in a partial record; export what is there.
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    eprintln!("{}: {}", path.display(), e);
+                    break;
+                }
+                Err(e) => return Err(format!("{}: {}", path.display(), e)),
+            };
+            if first {
+                recording.start_ns = record.timestamp_ns;
+                first = false;
+            }
+            if !keys.is_empty() {
+                let Ok(key) = KeyExpr::new(record.key.as_str()) else {
+                    continue;
+                };
+                if !keys.iter().any(|expr| expr.intersects(&key)) {
+                    continue;
+                }
+            }
+            let encoding = Encoding::from(record.encoding.as_str());
+            let Some(kind) = Kind::detect(&record.key, &encoding) else {
+                skip(&mut recording, &record.key, record.encoding.clone());
+                continue;
+            };
+            let values = match kind.values(&encoding, record.payload) {
+                Ok(values) => values,
+                Err(e) => {
+                    skip(
+                        &mut recording,
+                        &record.key,
+                        format!("{}: {}", kind.table(), e),
+                    );
+                    continue;
+                }
+            };
+            let known = recording.streams.get(&record.key).map(|stream| stream.kind);
+            if let Some(known) = known.filter(|&known| known != kind) {
+                let reason = format!("{} on a {} key", kind.table(), known.table());
+                skip(&mut recording, &record.key, reason);
+                continue;
+            }
+            let stream = recording
+                .streams
+                .entry(record.key.clone())
+                .or_insert_with(|| Stream {
+                    kind,
+                    timestamps_ns: Vec::new(),
+                    values: vec![Vec::new(); kind.channels().len()],
+                });
+            stream.timestamps_ns.push(record.timestamp_ns);
+            for (series, value) in stream.values.iter_mut().zip(values) {
+                series.push(value);
+            }
+        }
+    }
+    for stream in recording.streams.values_mut() {
+        stream.sort();
+    }
+    Ok(recording)
+}
+
+fn skip(recording: &mut Recording, key: &str, reason: String) {
+    let entry = recording
+        .skipped
+        .entry(key.to_string())
+        .or_insert((0, reason));
+    entry.0 += 1;
+}
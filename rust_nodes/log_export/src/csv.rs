@@ -0,0 +1,31 @@
+use log_export::channels::Recording;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Writes a CSV per key into dir, named after the key with its slashes as underscores
+// (devices_imu0.csv): the receive time in nanoseconds since the Unix epoch and in seconds
+// since the start of the recording, then a column per channel. Returns the files written.
+pub fn write(dir: &Path, recording: &Recording) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::with_capacity(recording.streams.len());
+    for (key, stream) in &recording.streams {
+        let path = dir.join(format!("{}.csv", key.replace('/', "_")));
+        let mut file = BufWriter::new(File::create(&path)?);
+        write!(file, "timestamp_ns,time_s")?;
+        for channel in stream.channels() {
+            write!(file, ",{}", channel)?;
+        }
+        writeln!(file)?;
+        for (i, &timestamp_ns) in stream.timestamps_ns.iter().enumerate() {
+            write!(file, "{},{}", timestamp_ns, recording.seconds(timestamp_ns))?;
+            for series in &stream.values {
+                write!(file, ",{}", series[i])?;
+            }
+            writeln!(file)?;
+        }
+        file.flush()?;
+        written.push(path);
+    }
+    Ok(written)
+}
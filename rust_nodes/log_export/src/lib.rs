@@ -0,0 +1,4 @@
+// Decoding of recorded flights into channels, shared by log_export and log_compare.
+pub mod channels;
+pub mod resample;
+mod state;
@@ -0,0 +1,218 @@
+mod csv;
+mod mat;
+mod parquet;
+mod thrift;
+
+use clap::{Parser, ValueEnum};
+use log_export::channels::{self, Recording};
+use log_export::resample::{self, Interpolation};
+use mat::{MatWriter, Value};
+use parquet::ParquetWriter;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use zenoh::key_expr::OwnedKeyExpr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    // A CSV per key in a directory.
+    Csv,
+    // One Parquet file with every channel on a common timeline.
+    Parquet,
+    // A MATLAB MAT-file with a struct per key.
+    Mat,
+}
+
+#[derive(Parser)]
+#[command(
+    about = "Decodes the sensor and state samples of a recorded flight log into CSV, Parquet, or MAT-files"
+)]
+struct Args {
+    /// Flight log written by the recorder, or directory of its segments.
+    log: PathBuf,
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+    /// Directory of CSV files, or Parquet or MAT-file to write. Defaults to the log's path
+    /// with its extension replaced by -csv, .parquet, or .mat.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Key expression to export; repeatable. Defaults to every key.
+    #[arg(long = "key")]
+    keys: Vec<String>,
+    /// Resample every channel onto one grid of this rate, in Hz, from the first sample of
+    /// the export to the last.
+    #[arg(long)]
+    rate_hz: Option<f64>,
+    /// How channels are valued between their samples when resampling.
+    #[arg(long, value_enum, default_value_t = Interpolation::Hold)]
+    interpolation: Interpolation,
+}
+
+// Writes a Parquet file with a row per receive time of any sample: timestamp_ns and time_s
+// as in the CSV files, then a column <key>/<channel> per channel, null in the rows of
+// other samples.
+fn write_parquet(path: &Path, recording: &Recording) -> io::Result<()> {
+    let timeline = recording.timeline();
+    let mut parquet =
+        ParquetWriter::new(BufWriter::new(File::create(path)?), timeline.len() as u64)?;
+    let timestamps: Vec<i64> = timeline.iter().map(|&t| t as i64).collect();
+    parquet.int64("timestamp_ns", &timestamps)?;
+    let seconds: Vec<f64> = timeline.iter().map(|&t| recording.seconds(t)).collect();
+    parquet.double("time_s", &seconds)?;
+    for (key, stream) in &recording.streams {
+        for (i, channel) in stream.channels().iter().enumerate() {
+            let name = format!("{}/{}", key, channel);
+            parquet.optional_double(&name, stream.aligned(i, &timeline))?;
+        }
+    }
+    parquet.finish()?;
+    Ok(())
+}
+
+// Writes a MAT-file with the streams nested in structs by key, so devices/imu0 loads as
+// devices.imu0: a struct of time_s and timestamp_ns as in the CSV files, then a column
+// vector per channel, all of one length. Each top-level struct is its own variable.
+fn write_mat(path: &Path, recording: &Recording) -> io::Result<()> {
+    let mut variables = Vec::new();
+    for (key, stream) in &recording.streams {
+        let seconds = stream
+            .timestamps_ns
+            .iter()
+            .map(|&t| recording.seconds(t))
+            .collect();
+        let timestamps = stream.timestamps_ns.iter().map(|&t| t as i64).collect();
+        let mut fields = vec![
+            ("time_s".to_string(), Value::Double(seconds)),
+            ("timestamp_ns".to_string(), Value::Int64(timestamps)),
+        ];
+        for (channel, series) in stream.channels().iter().zip(&stream.values) {
+            fields.push((mat::identifier(channel), Value::Double(series.clone())));
+        }
+        let path: Vec<String> = key.split('/').map(mat::identifier).collect();
+        insert(&mut variables, &path, fields).map_err(|name| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} and another key both export as {}", key, name),
+            )
+        })?;
+    }
+    let mut mat = MatWriter::new(BufWriter::new(File::create(path)?))?;
+    for (name, value) in &variables {
+        mat.variable(name, value)?;
+    }
+    mat.finish()?;
+    Ok(())
+}
+
+// Adds the fields of a key's struct at path into the nested structs, merging them with
+// the structs of keys under it. Returns the name in conflict if path already holds a
+// channel.
+fn insert(
+    structs: &mut Vec<(String, Value)>,
+    path: &[String],
+    fields: Vec<(String, Value)>,
+) -> Result<(), String> {
+    let (name, rest) = path.split_first().expect("keys have a chunk");
+    let index = match structs.iter().position(|(field, _)| field == name) {
+        Some(index) => index,
+        None => {
+            structs.push((name.clone(), Value::Struct(Vec::new())));
+            structs.len() - 1
+        }
+    };
+    let Value::Struct(members) = &mut structs[index].1 else {
+        return Err(name.clone());
+    };
+    if !rest.is_empty() {
+        return insert(members, rest, fields);
+    }
+    for (field, value) in fields {
+        if members.iter().any(|(member, _)| *member == field) {
+            return Err(format!("{}.{}", name, field));
+        }
+        members.push((field, value));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if args
+        .rate_hz
+        .is_some_and(|rate| !(rate.is_finite() && rate > 0.0))
+    {
+        eprintln!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    let mut keys = Vec::with_capacity(args.keys.len());
+    for key in &args.keys {
+        match OwnedKeyExpr::new(key.as_str()) {
+            Ok(key) => keys.push(key),
+            Err(e) => {
+                eprintln!("Invalid key expression {}: {}", key, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let logs = match channels::logs(&args.log) {
+        Ok(logs) if !logs.is_empty() => logs,
+        Ok(_) => {
+            eprintln!("{} holds no log segments", args.log.display());
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", args.log.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut recording = match channels::read(&logs, &keys) {
+        Ok(recording) => recording,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    for (key, (count, reason)) in &recording.skipped {
+        println!("Skipped {} samples on {}: {}", count, key, reason);
+    }
+    if let Some(rate_hz) = args.rate_hz {
+        let times = resample::resample(&mut recording, rate_hz, args.interpolation);
+        println!("Resampled onto {} times at {} Hz", times, rate_hz);
+    }
+
+    let output = args.output.clone().unwrap_or_else(|| match args.format {
+        Format::Csv => {
+            let stem = args.log.file_stem().unwrap_or_default().to_string_lossy();
+            args.log.with_file_name(format!("{}-csv", stem))
+        }
+        Format::Parquet => args.log.with_extension("parquet"),
+        Format::Mat => args.log.with_extension("mat"),
+    });
+    let written = match args.format {
+        Format::Csv => csv::write(&output, &recording).map(|files| files.len()),
+        Format::Parquet => write_parquet(&output, &recording).map(|()| 1),
+        Format::Mat => write_mat(&output, &recording).map(|()| 1),
+    };
+    match written {
+        Ok(files) => {
+            let samples: usize = recording
+                .streams
+                .values()
+                .map(|stream| stream.timestamps_ns.len())
+                .sum();
+            println!(
+                "Exported {} samples of {} keys to {} ({} files)",
+                samples,
+                recording.streams.len(),
+                output.display(),
+                files
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to write {}: {}", output.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
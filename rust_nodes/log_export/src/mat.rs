@@ -0,0 +1,257 @@
+// A MATLAB Level 5 MAT-file of uncompressed variables: column vectors of doubles or int64s
+// and structs of them, which load() in any MATLAB since 5 (and scipy.io.loadmat) reads.
+use std::io::{self, Write};
+
+// Data types of elements.
+const MI_INT8: u32 = 1;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_DOUBLE: u32 = 9;
+const MI_INT64: u32 = 12;
+const MI_MATRIX: u32 = 14;
+// Classes of arrays.
+const MX_STRUCT: u32 = 2;
+const MX_DOUBLE: u32 = 6;
+const MX_INT64: u32 = 14;
+
+// Longest name MATLAB accepts for a variable or field.
+const NAME_MAX: usize = 63;
+
+pub enum Value {
+    Double(Vec<f64>),
+    Int64(Vec<i64>),
+    // Fields in order, each with a name unique in the struct.
+    Struct(Vec<(String, Value)>),
+}
+
+// A valid MATLAB name made from any text: a letter, then letters, digits, and
+// underscores.
+pub fn identifier(text: &str) -> String {
+    let mut name: String = text
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, 'x');
+    }
+    name.truncate(NAME_MAX);
+    name
+}
+
+pub struct MatWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> MatWriter<W> {
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        let mut text = format!(
+            "MATLAB 5.0 MAT-file, Platform: {}, Created by: log_export",
+            std::env::consts::OS
+        )
+        .into_bytes();
+        text.resize(116, b' ');
+        inner.write_all(&text)?;
+        // No subsystem data.
+        inner.write_all(&[0; 8])?;
+        inner.write_all(&0x0100u16.to_le_bytes())?;
+        inner.write_all(b"IM")?;
+        Ok(MatWriter { inner })
+    }
+
+    // Writes a variable; name must be an identifier().
+    pub fn variable(&mut self, name: &str, value: &Value) -> io::Result<()> {
+        let element = matrix(name, value)?;
+        self.inner.write_all(&element)
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+// A miMATRIX element: array flags, dimensions, name, then the data of its class.
+fn matrix(name: &str, value: &Value) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let (class, rows) = match value {
+        Value::Double(values) => (MX_DOUBLE, values.len()),
+        Value::Int64(values) => (MX_INT64, values.len()),
+        Value::Struct(_) => (MX_STRUCT, 1),
+    };
+    element(
+        &mut body,
+        MI_UINT32,
+        &[class.to_le_bytes(), [0; 4]].concat(),
+    )?;
+    let rows = i32::try_from(rows).map_err(|_| too_large(name))?;
+    element(
+        &mut body,
+        MI_INT32,
+        &[rows.to_le_bytes(), 1i32.to_le_bytes()].concat(),
+    )?;
+    element(&mut body, MI_INT8, name.as_bytes())?;
+    match value {
+        Value::Double(values) => {
+            let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            element(&mut body, MI_DOUBLE, &data)?;
+        }
+        Value::Int64(values) => {
+            let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            element(&mut body, MI_INT64, &data)?;
+        }
+        Value::Struct(fields) => {
+            // Field names are written null-padded to a common length, given first as a
+            // small element: type and size packed into one word, the length in the next.
+            let length = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0) + 1;
+            body.extend_from_slice(&(MI_INT32 | (4 << 16)).to_le_bytes());
+            body.extend_from_slice(&(length as i32).to_le_bytes());
+            let mut names = vec![0u8; length * fields.len()];
+            for (i, (name, _)) in fields.iter().enumerate() {
+                names[i * length..i * length + name.len()].copy_from_slice(name.as_bytes());
+            }
+            element(&mut body, MI_INT8, &names)?;
+            // Field values are arrays without a name.
+            for (_, value) in fields {
+                body.extend_from_slice(&matrix("", value)?);
+            }
+        }
+    }
+    let mut bytes = Vec::with_capacity(body.len() + 8);
+    element(&mut bytes, MI_MATRIX, &body)?;
+    Ok(bytes)
+}
+
+// Appends an element: its type, its size in bytes, then its data padded to 8 bytes.
+fn element(out: &mut Vec<u8>, kind: u32, data: &[u8]) -> io::Result<()> {
+    let size = u32::try_from(data.len()).map_err(|_| too_large("element"))?;
+    out.extend_from_slice(&kind.to_le_bytes());
+    out.extend_from_slice(&size.to_le_bytes());
+    out.extend_from_slice(data);
+    out.resize(out.len().next_multiple_of(8), 0);
+    Ok(())
+}
+
+fn too_large(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{} is too large for a MAT-file", name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(variables: &[(&str, Value)]) -> Vec<u8> {
+        let mut writer = MatWriter::new(Vec::new()).unwrap();
+        for (name, value) in variables {
+            writer.variable(name, value).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    // A tag of a type and size, as little-endian words.
+    fn tag(kind: u32, size: u32) -> Vec<u8> {
+        [kind.to_le_bytes(), size.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn writes_the_header() {
+        let file = write(&[]);
+        assert_eq!(file.len(), 128);
+        assert!(file.starts_with(b"MATLAB 5.0 MAT-file, Platform: "));
+        // The text is padded with spaces.
+        let text = String::from_utf8_lossy(&file[..116]);
+        assert!(
+            text.trim_end().ends_with(", Created by: log_export"),
+            "{}",
+            text
+        );
+        assert_eq!(&file[116..124], [0; 8], "subsystem data offset");
+        assert_eq!(&file[124..126], [0x00, 0x01], "version");
+        assert_eq!(&file[126..128], b"IM", "little-endian");
+    }
+
+    #[test]
+    fn writes_a_struct_as_matlab_does() {
+        let file = write(&[(
+            "s",
+            Value::Struct(vec![
+                ("x".to_string(), Value::Double(vec![1.0])),
+                ("n".to_string(), Value::Int64(vec![-2])),
+            ]),
+        )]);
+        let field = |class: u32, data: Vec<u8>| {
+            [
+                tag(MI_MATRIX, 56),
+                tag(MI_UINT32, 8),
+                vec![class as u8, 0, 0, 0, 0, 0, 0, 0],
+                tag(MI_INT32, 8),
+                vec![1, 0, 0, 0, 1, 0, 0, 0],
+                // No name.
+                tag(MI_INT8, 0),
+                data,
+            ]
+            .concat()
+        };
+        let expected = [
+            tag(MI_MATRIX, 200),
+            // Array flags: the class, then no nonzero count.
+            tag(MI_UINT32, 8),
+            vec![2, 0, 0, 0, 0, 0, 0, 0],
+            // Dimensions: 1 by 1.
+            tag(MI_INT32, 8),
+            vec![1, 0, 0, 0, 1, 0, 0, 0],
+            // The name, padded to 8 bytes.
+            tag(MI_INT8, 1),
+            vec![b's', 0, 0, 0, 0, 0, 0, 0],
+            // The length of each field name, the longest and a null, as a small element.
+            vec![5, 0, 4, 0, 2, 0, 0, 0],
+            tag(MI_INT8, 4),
+            vec![b'x', 0, b'n', 0, 0, 0, 0, 0],
+            field(
+                MX_DOUBLE,
+                [tag(MI_DOUBLE, 8), vec![0, 0, 0, 0, 0, 0, 0xf0, 0x3f]].concat(),
+            ),
+            field(
+                MX_INT64,
+                [
+                    tag(MI_INT64, 8),
+                    vec![0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                ]
+                .concat(),
+            ),
+        ]
+        .concat();
+        assert_eq!(&file[128..], expected);
+    }
+
+    #[test]
+    fn pads_elements_to_8_bytes() {
+        for (len, padded) in [(0, 8), (1, 16), (7, 16), (8, 16), (9, 24)] {
+            let mut out = Vec::new();
+            element(&mut out, MI_INT8, &vec![b'a'; len]).unwrap();
+            assert_eq!(out.len(), padded, "{} bytes", len);
+            assert_eq!(&out[..8], tag(MI_INT8, len as u32));
+            assert!(out[8 + len..].iter().all(|&b| b == 0));
+        }
+        // Every variable starts on a boundary, whatever its length.
+        let file = write(&[
+            ("a", Value::Double(vec![0.5; 3])),
+            ("long_name", Value::Int64(vec![])),
+        ]);
+        let first = u32::from_le_bytes(file[132..136].try_into().unwrap()) as usize;
+        assert_eq!(first % 8, 0);
+        let second = &file[136 + first..];
+        assert_eq!(&second[..4], MI_MATRIX.to_le_bytes());
+        assert_eq!(second.len() % 8, 0);
+    }
+
+    #[test]
+    fn makes_identifiers() {
+        assert_eq!(identifier("imu0"), "imu0");
+        assert_eq!(identifier("state/fused"), "state_fused");
+        assert_eq!(identifier("0x"), "x0x");
+        assert_eq!(identifier(&"a".repeat(70)).len(), NAME_MAX);
+    }
+}
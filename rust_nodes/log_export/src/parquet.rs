@@ -0,0 +1,357 @@
+// A Parquet file of one row group, written a column at a time. Each column chunk is a
+// single uncompressed data page of PLAIN values, with RLE definition levels for columns
+// that may be null: the plainest layout of the format, which every Parquet reader
+// (pyarrow, Polars, DuckDB) reads.
+use crate::thrift::Encoder;
+use std::io::{self, Write};
+
+const MAGIC: &[u8; 4] = b"PAR1";
+
+// Physical types.
+const INT64: i32 = 2;
+const DOUBLE: i32 = 5;
+// Field repetition types.
+const REQUIRED: i32 = 0;
+const OPTIONAL: i32 = 1;
+// Encodings.
+const PLAIN: i32 = 0;
+const RLE: i32 = 3;
+const UNCOMPRESSED: i32 = 0;
+const DATA_PAGE: i32 = 0;
+
+struct Column {
+    name: String,
+    kind: i32,
+    repetition: i32,
+    offset: u64,
+    size: u64,
+}
+
+pub struct ParquetWriter<W: Write> {
+    inner: W,
+    written: u64,
+    rows: u64,
+    columns: Vec<Column>,
+}
+
+impl<W: Write> ParquetWriter<W> {
+    // Starts a file whose columns all have the given number of rows.
+    pub fn new(mut inner: W, rows: u64) -> io::Result<Self> {
+        inner.write_all(MAGIC)?;
+        Ok(ParquetWriter {
+            inner,
+            written: MAGIC.len() as u64,
+            rows,
+            columns: Vec::new(),
+        })
+    }
+
+    pub fn int64(&mut self, name: &str, values: &[i64]) -> io::Result<()> {
+        let data = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.column(name, INT64, values.len() as u64, None, data)
+    }
+
+    pub fn double(&mut self, name: &str, values: &[f64]) -> io::Result<()> {
+        let data = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.column(name, DOUBLE, values.len() as u64, None, data)
+    }
+
+    // A column of doubles with a value or a null in each row.
+    pub fn optional_double(
+        &mut self,
+        name: &str,
+        values: impl IntoIterator<Item = Option<f64>>,
+    ) -> io::Result<()> {
+        let mut levels = Levels::default();
+        let mut data = Vec::new();
+        let mut count = 0;
+        for value in values {
+            levels.push(value.is_some());
+            if let Some(value) = value {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            count += 1;
+        }
+        self.column(name, DOUBLE, count, Some(levels.finish()), data)
+    }
+
+    // Writes the file metadata; the file is unreadable until then.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut metadata = Encoder::new();
+        metadata
+            .i32(1, 1)
+            .struct_list(2, &self.schema(), |encoder, element| match element {
+                None => {
+                    encoder
+                        .binary(4, b"schema")
+                        .i32(5, self.columns.len() as i32);
+                }
+                Some(column) => {
+                    encoder
+                        .i32(1, column.kind)
+                        .i32(3, column.repetition)
+                        .binary(4, column.name.as_bytes());
+                }
+            })
+            .i64(3, self.rows as i64)
+            .struct_list(4, &[()], |encoder, _| {
+                let total: u64 = self.columns.iter().map(|column| column.size).sum();
+                encoder
+                    .struct_list(1, &self.columns, |encoder, column| {
+                        encoder.i64(2, column.offset as i64).structure(3, |meta| {
+                            meta.i32(1, column.kind)
+                                .i32_list(2, &[PLAIN, RLE])
+                                .binary_list(3, &[column.name.as_bytes()])
+                                .i32(4, UNCOMPRESSED)
+                                .i64(5, self.rows as i64)
+                                .i64(6, column.size as i64)
+                                .i64(7, column.size as i64)
+                                .i64(9, column.offset as i64);
+                        });
+                    })
+                    .i64(2, total as i64)
+                    .i64(3, self.rows as i64);
+            })
+            .binary(6, b"log_export")
+            .stop();
+        let metadata = metadata.finish();
+        self.inner.write_all(&metadata)?;
+        self.inner
+            .write_all(&(metadata.len() as u32).to_le_bytes())?;
+        self.inner.write_all(MAGIC)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    // The schema's elements: the root, then one per column.
+    fn schema(&self) -> Vec<Option<&Column>> {
+        std::iter::once(None)
+            .chain(self.columns.iter().map(Some))
+            .collect()
+    }
+
+    fn column(
+        &mut self,
+        name: &str,
+        kind: i32,
+        count: u64,
+        levels: Option<Vec<u8>>,
+        data: Vec<u8>,
+    ) -> io::Result<()> {
+        if count != self.rows {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("column {} has {} rows, not {}", name, count, self.rows),
+            ));
+        }
+        let repetition = if levels.is_some() { OPTIONAL } else { REQUIRED };
+        let mut page = Vec::with_capacity(data.len() + 4);
+        if let Some(levels) = levels {
+            page.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+            page.extend_from_slice(&levels);
+        }
+        page.extend_from_slice(&data);
+        let page_len = i32::try_from(page.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("column {} is too large for one page", name),
+            )
+        })?;
+
+        let mut header = Encoder::new();
+        header
+            .i32(1, DATA_PAGE)
+            .i32(2, page_len)
+            .i32(3, page_len)
+            .structure(5, |data_page| {
+                data_page
+                    .i32(1, count as i32)
+                    .i32(2, PLAIN)
+                    .i32(3, RLE)
+                    .i32(4, RLE);
+            })
+            .stop();
+        let header = header.finish();
+        self.inner.write_all(&header)?;
+        self.inner.write_all(&page)?;
+
+        let size = (header.len() + page.len()) as u64;
+        self.columns.push(Column {
+            name: name.to_string(),
+            kind,
+            repetition,
+            offset: self.written,
+            size,
+        });
+        self.written += size;
+        Ok(())
+    }
+}
+
+// Definition levels of a column of maximum level 1, RLE-encoded as runs of present or
+// null values.
+#[derive(Default)]
+struct Levels {
+    bytes: Vec<u8>,
+    run: Option<(bool, u64)>,
+}
+
+impl Levels {
+    fn push(&mut self, present: bool) {
+        match &mut self.run {
+            Some((value, len)) if *value == present => *len += 1,
+            _ => {
+                self.flush();
+                self.run = Some((present, 1));
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.flush();
+        self.bytes
+    }
+
+    // A run is its length shifted left once, as a varint, then the level in one byte.
+    fn flush(&mut self) {
+        let Some((present, len)) = self.run.take() else {
+            return;
+        };
+        let mut header = len << 1;
+        while header >= 0x80 {
+            self.bytes.push((header as u8) | 0x80);
+            header >>= 7;
+        }
+        self.bytes.push(header as u8);
+        self.bytes.push(present as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thrift::tests::{Value, read};
+
+    fn file() -> Vec<u8> {
+        let mut writer = ParquetWriter::new(Vec::new(), 3).unwrap();
+        writer.int64("time_us", &[0, 10_000, 20_000]).unwrap();
+        writer.double("altitude", &[1.5, -2.0, 812.25]).unwrap();
+        writer
+            .optional_double("pressure", [Some(101_325.0), None, None])
+            .unwrap();
+        writer.finish().unwrap()
+    }
+
+    // The file metadata, found by the length before the closing magic.
+    fn footer(file: &[u8]) -> Value {
+        let (rest, magic) = file.split_at(file.len() - 4);
+        assert_eq!(magic, MAGIC);
+        let (rest, len) = rest.split_at(rest.len() - 4);
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        let mut metadata = &rest[rest.len() - len..];
+        let value = read(&mut metadata);
+        assert!(metadata.is_empty(), "bytes after the metadata");
+        value
+    }
+
+    #[test]
+    fn starts_and_ends_with_the_magic() {
+        let file = file();
+        assert_eq!(&file[..4], b"PAR1");
+        assert_eq!(&file[file.len() - 4..], b"PAR1");
+    }
+
+    #[test]
+    fn describes_the_columns_in_the_footer() {
+        let metadata = footer(&file());
+        assert_eq!(metadata.field(1).int(), 1, "version");
+        assert_eq!(metadata.field(3).int(), 3, "num_rows");
+        assert_eq!(metadata.field(6).binary(), b"log_export", "created_by");
+
+        let schema = metadata.field(2).list();
+        assert_eq!(schema[0].field(4).binary(), b"schema");
+        assert_eq!(schema[0].field(5).int(), 3, "num_children");
+        for (element, (name, kind, repetition)) in schema[1..].iter().zip([
+            ("time_us", INT64, REQUIRED),
+            ("altitude", DOUBLE, REQUIRED),
+            ("pressure", DOUBLE, OPTIONAL),
+        ]) {
+            assert_eq!(element.field(1).int(), kind as i64, "{}", name);
+            assert_eq!(element.field(3).int(), repetition as i64, "{}", name);
+            assert_eq!(element.field(4).binary(), name.as_bytes());
+        }
+
+        let row_groups = metadata.field(4).list();
+        assert_eq!(row_groups.len(), 1);
+        assert_eq!(row_groups[0].field(3).int(), 3, "num_rows");
+        let columns = row_groups[0].field(1).list();
+        let sizes: i64 = columns
+            .iter()
+            .map(|column| column.field(3).field(7).int())
+            .sum();
+        assert_eq!(row_groups[0].field(2).int(), sizes, "total_byte_size");
+        for (column, name) in columns.iter().zip(["time_us", "altitude", "pressure"]) {
+            let meta = column.field(3);
+            assert_eq!(meta.field(3).list(), [Value::Binary(name.into())]);
+            assert_eq!(meta.field(4).int(), UNCOMPRESSED as i64, "codec");
+            assert_eq!(meta.field(5).int(), 3, "num_values");
+            assert_eq!(meta.field(9).int(), column.field(2).int());
+        }
+    }
+
+    #[test]
+    fn points_each_column_at_its_page() {
+        let file = file();
+        let metadata = footer(&file);
+        let columns = metadata.field(4).list()[0].field(1).list();
+        // The columns follow the magic back to back.
+        assert_eq!(columns[0].field(2).int(), 4);
+        let mut pages = Vec::new();
+        for column in columns {
+            let meta = column.field(3);
+            let start = meta.field(9).int() as usize;
+            let chunk = &file[start..start + meta.field(7).int() as usize];
+            let mut rest = chunk;
+            let header = read(&mut rest);
+            assert_eq!(header.field(1).int(), DATA_PAGE as i64);
+            assert_eq!(header.field(2).int(), rest.len() as i64);
+            assert_eq!(header.field(3).int(), rest.len() as i64);
+            let data_page = header.field(5);
+            assert_eq!(data_page.field(1).int(), 3, "num_values");
+            assert_eq!(data_page.field(2).int(), PLAIN as i64);
+            pages.push(rest.to_vec());
+        }
+        let int64s: Vec<u8> = [0i64, 10_000, 20_000]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        assert_eq!(pages[0], int64s);
+        let doubles: Vec<u8> = [1.5f64, -2.0, 812.25]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        assert_eq!(pages[1], doubles);
+        // Levels of one present and two null, as runs of 1 and 2 after their length, and
+        // only the present value.
+        let mut optional = vec![4, 0, 0, 0, 0x02, 1, 0x04, 0];
+        optional.extend(101_325.0f64.to_le_bytes());
+        assert_eq!(pages[2], optional);
+    }
+
+    #[test]
+    fn runs_definition_levels_past_a_byte() {
+        let mut levels = Levels::default();
+        for i in 0..200 {
+            levels.push(i < 100);
+        }
+        // Runs of 100, so a header of 200 in two bytes.
+        assert_eq!(levels.finish(), [0xc8, 0x01, 1, 0xc8, 0x01, 0]);
+    }
+
+    #[test]
+    fn refuses_a_column_of_another_length() {
+        let mut writer = ParquetWriter::new(Vec::new(), 3).unwrap();
+        let e = writer.double("altitude", &[1.0, 2.0]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+    }
+}
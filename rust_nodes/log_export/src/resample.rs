@@ -0,0 +1,185 @@
+use crate::channels::Recording;
+use clap::ValueEnum;
+
+// How a channel is valued between its samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Interpolation {
+    // The last sample at or before the time, held until the end of the recording.
+    Hold,
+    // The straight line between the samples either side; NaN after the last sample.
+    Linear,
+}
+
+// Moves every stream onto one grid of rate_hz times, from the first sample of any stream
+// to the last, so all channels have a value at every time. Channels are NaN at times
+// before their first sample. Returns the number of times on the grid.
+pub fn resample(recording: &mut Recording, rate_hz: f64, interpolation: Interpolation) -> usize {
+    let timeline = recording.timeline();
+    let (Some(&first), Some(&last)) = (timeline.first(), timeline.last()) else {
+        return 0;
+    };
+    let grid = grid(first, last, rate_hz);
+    onto(recording, &grid, interpolation);
+    grid.len()
+}
+
+// Times rate_hz apart from first up to last.
+pub fn grid(first: u64, last: u64, rate_hz: f64) -> Vec<u64> {
+    let step_ns = 1e9 / rate_hz;
+    (0u64..)
+        .map(|k| first + (k as f64 * step_ns).round() as u64)
+        .take_while(|&time| time <= last)
+        .collect()
+}
+
+// Replaces the samples of every stream with its values at the times of grid, which must
+// be in order. Channels are NaN at times before their first sample.
+pub fn onto(recording: &mut Recording, grid: &[u64], interpolation: Interpolation) {
+    for stream in recording.streams.values_mut() {
+        let times = &stream.timestamps_ns;
+        for series in &mut stream.values {
+            *series = values_at(times, series, grid, interpolation);
+        }
+        stream.timestamps_ns = grid.to_vec();
+    }
+}
+
+// The values of a series sampled at times (in order) at each time of grid (in order).
+fn values_at(
+    times: &[u64],
+    series: &[f64],
+    grid: &[u64],
+    interpolation: Interpolation,
+) -> Vec<f64> {
+    let mut values = Vec::with_capacity(grid.len());
+    // The last sample at or before the grid time.
+    let mut at = None;
+    for &time in grid {
+        let mut next = at.map_or(0, |i| i + 1);
+        while next < times.len() && times[next] <= time {
+            at = Some(next);
+            next += 1;
+        }
+        let value = match (at, interpolation) {
+            (None, _) => f64::NAN,
+            (Some(i), Interpolation::Hold) => series[i],
+            (Some(i), Interpolation::Linear) if times[i] == time => series[i],
+            (Some(i), Interpolation::Linear) if next < times.len() => {
+                let fraction = (time - times[i]) as f64 / (times[next] - times[i]) as f64;
+                series[i] + fraction * (series[next] - series[i])
+            }
+            (Some(_), Interpolation::Linear) => f64::NAN,
+        };
+        values.push(value);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::{Kind, Stream};
+
+    const MS: u64 = 1_000_000;
+
+    // Compares as bits, so NaN matches NaN.
+    fn assert_values(actual: &[f64], expected: &[f64]) {
+        let bits = |values: &[f64]| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        assert_eq!(
+            bits(actual),
+            bits(expected),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn recording(streams: &[(&str, &[u64], &[f64])]) -> Recording {
+        let mut recording = Recording::default();
+        for &(key, times, values) in streams {
+            let stream = Stream {
+                kind: Kind::Fused,
+                timestamps_ns: times.to_vec(),
+                values: vec![values.to_vec()],
+            };
+            recording.streams.insert(key.to_string(), stream);
+        }
+        recording
+    }
+
+    #[test]
+    fn grids_from_the_first_sample_to_the_last() {
+        assert_eq!(
+            grid(5 * MS, 35 * MS, 100.0),
+            [5 * MS, 15 * MS, 25 * MS, 35 * MS]
+        );
+        // The last time is left out when it falls between grid times.
+        assert_eq!(grid(0, 29 * MS, 100.0), [0, 10 * MS, 20 * MS]);
+        assert_eq!(grid(7, 7, 100.0), [7]);
+    }
+
+    #[test]
+    fn keeps_the_first_and_last_sample() {
+        let times = [0, 10 * MS, 20 * MS];
+        let series = [1.0, 2.0, 4.0];
+        for interpolation in [Interpolation::Hold, Interpolation::Linear] {
+            let values = values_at(&times, &series, &times, interpolation);
+            assert_values(&values, &series);
+        }
+    }
+
+    #[test]
+    fn is_nan_before_the_first_sample_and_after_the_last_unless_held() {
+        let times = [10 * MS, 20 * MS];
+        let series = [1.0, 2.0];
+        let grid = [0, 10 * MS, 20 * MS, 30 * MS];
+        let hold = values_at(&times, &series, &grid, Interpolation::Hold);
+        assert_values(&hold, &[f64::NAN, 1.0, 2.0, 2.0]);
+        let linear = values_at(&times, &series, &grid, Interpolation::Linear);
+        assert_values(&linear, &[f64::NAN, 1.0, 2.0, f64::NAN]);
+    }
+
+    #[test]
+    fn bridges_a_gap_longer_than_the_period() {
+        let times = [0, 50 * MS];
+        let series = [0.0, 10.0];
+        let grid = grid(0, 50 * MS, 100.0);
+        let hold = values_at(&times, &series, &grid, Interpolation::Hold);
+        assert_values(&hold, &[0.0, 0.0, 0.0, 0.0, 0.0, 10.0]);
+        let linear = values_at(&times, &series, &grid, Interpolation::Linear);
+        assert_values(&linear, &[0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+    }
+
+    #[test]
+    fn takes_the_last_of_samples_at_the_same_time() {
+        let times = [0, 10 * MS, 10 * MS, 20 * MS];
+        let series = [0.0, 1.0, 3.0, 5.0];
+        let grid = [0, 10 * MS, 15 * MS, 20 * MS];
+        let hold = values_at(&times, &series, &grid, Interpolation::Hold);
+        assert_values(&hold, &[0.0, 3.0, 3.0, 5.0]);
+        let linear = values_at(&times, &series, &grid, Interpolation::Linear);
+        assert_values(&linear, &[0.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn puts_every_stream_on_one_grid() {
+        let mut recording = recording(&[
+            (
+                "fast",
+                &[0, 10 * MS, 20 * MS, 30 * MS],
+                &[0.0, 1.0, 2.0, 3.0],
+            ),
+            ("slow", &[15 * MS], &[7.0]),
+        ]);
+        assert_eq!(resample(&mut recording, 50.0, Interpolation::Hold), 2);
+        for stream in recording.streams.values() {
+            assert_eq!(stream.timestamps_ns, [0, 20 * MS]);
+        }
+        assert_values(&recording.streams["fast"].values[0], &[0.0, 2.0]);
+        assert_values(&recording.streams["slow"].values[0], &[f64::NAN, 7.0]);
+        assert_eq!(
+            resample(&mut Recording::default(), 50.0, Interpolation::Hold),
+            0
+        );
+    }
+}
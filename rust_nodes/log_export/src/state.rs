@@ -0,0 +1,87 @@
+// The estimator and simulator state tables a recording holds next to its sensor samples,
+// decoded into channels like sensor_decode's so fused state and ground truth export
+// alongside the sensors. Attitude follows controller's channel names, and vectors are
+// east-north-up.
+use sensors_rs::sensors;
+
+pub const FUSED_STATE: &str = "sensors.FusedState";
+pub const SIM_TRUTH: &str = "sensors.SimTruth";
+
+// Channels that wrap around at ±π.
+pub const ANGLES: &[&str] = &["roll", "pitch", "yaw"];
+
+pub const FUSED_CHANNELS: &[&str] = &[
+    "roll",
+    "pitch",
+    "yaw",
+    "roll_rate",
+    "pitch_rate",
+    "yaw_rate",
+    "altitude",
+    "velocity_east",
+    "velocity_north",
+    "velocity_up",
+    "acceleration_east",
+    "acceleration_north",
+    "acceleration_up",
+];
+
+pub const TRUTH_CHANNELS: &[&str] = &[
+    "flight_time",
+    "position_east",
+    "position_north",
+    "position_up",
+    "velocity_east",
+    "velocity_north",
+    "velocity_up",
+    "acceleration_east",
+    "acceleration_north",
+    "acceleration_up",
+    "roll",
+    "pitch",
+    "yaw",
+    "roll_rate",
+    "pitch_rate",
+    "yaw_rate",
+    "mass",
+    "thrust",
+];
+
+fn vec3(v: Option<&sensors::Vec3>, valid: bool) -> [f64; 3] {
+    match v {
+        Some(v) if valid => [v.x() as f64, v.y() as f64, v.z() as f64],
+        _ => [f64::NAN; 3],
+    }
+}
+
+// The values of FUSED_CHANNELS, NaN where fusion flagged the estimate invalid.
+pub fn fused(bytes: &[u8]) -> Result<Vec<f64>, String> {
+    let state = flatbuffers::root::<sensors::FusedState>(bytes).map_err(|e| e.to_string())?;
+    let altitude = if state.altitude_valid() {
+        state.altitude() as f64
+    } else {
+        f64::NAN
+    };
+    let mut values = Vec::with_capacity(FUSED_CHANNELS.len());
+    values.extend(vec3(state.attitude(), state.attitude_valid()));
+    values.extend(vec3(state.angular_rate(), state.angular_rate_valid()));
+    values.push(altitude);
+    values.extend(vec3(state.velocity(), state.velocity_valid()));
+    values.extend(vec3(state.acceleration(), state.acceleration_valid()));
+    Ok(values)
+}
+
+// The values of TRUTH_CHANNELS.
+pub fn truth(bytes: &[u8]) -> Result<Vec<f64>, String> {
+    let truth = flatbuffers::root::<sensors::SimTruth>(bytes).map_err(|e| e.to_string())?;
+    let mut values = Vec::with_capacity(TRUTH_CHANNELS.len());
+    values.push(truth.flight_time_s() as f64);
+    values.extend(vec3(truth.position(), true));
+    values.extend(vec3(truth.velocity(), true));
+    values.extend(vec3(truth.acceleration(), true));
+    values.extend(vec3(truth.attitude(), true));
+    values.extend(vec3(truth.angular_rate(), true));
+    values.push(truth.mass() as f64);
+    values.push(truth.thrust() as f64);
+    Ok(values)
+}
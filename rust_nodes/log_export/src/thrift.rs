@@ -0,0 +1,357 @@
+// Just enough of Thrift's compact protocol to write Parquet's page headers and file
+// metadata: structs of integer, binary, struct, and list fields.
+
+const I32: u8 = 5;
+const I64: u8 = 6;
+const BINARY: u8 = 8;
+const LIST: u8 = 9;
+const STRUCT: u8 = 12;
+
+pub struct Encoder {
+    bytes: Vec<u8>,
+    // Id of the last field written in each struct being written, innermost last; the
+    // compact protocol encodes field ids as deltas from it.
+    last_field: Vec<i16>,
+}
+
+impl Encoder {
+    pub fn new() -> Self {
+        Encoder {
+            bytes: Vec::new(),
+            last_field: vec![0],
+        }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn i32(&mut self, id: i16, value: i32) -> &mut Self {
+        self.field(id, I32);
+        self.varint(zigzag(value as i64));
+        self
+    }
+
+    pub fn i64(&mut self, id: i16, value: i64) -> &mut Self {
+        self.field(id, I64);
+        self.varint(zigzag(value));
+        self
+    }
+
+    pub fn binary(&mut self, id: i16, value: &[u8]) -> &mut Self {
+        self.field(id, BINARY);
+        self.varint(value.len() as u64);
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    // Writes a struct field whose own fields are written by body.
+    pub fn structure(&mut self, id: i16, body: impl FnOnce(&mut Self)) -> &mut Self {
+        self.field(id, STRUCT);
+        self.nested(body);
+        self
+    }
+
+    pub fn i32_list(&mut self, id: i16, values: &[i32]) -> &mut Self {
+        self.list_header(id, I32, values.len());
+        for &value in values {
+            self.varint(zigzag(value as i64));
+        }
+        self
+    }
+
+    pub fn binary_list(&mut self, id: i16, values: &[&[u8]]) -> &mut Self {
+        self.list_header(id, BINARY, values.len());
+        for value in values {
+            self.varint(value.len() as u64);
+            self.bytes.extend_from_slice(value);
+        }
+        self
+    }
+
+    // Writes a list of structs, each written by body.
+    pub fn struct_list<T>(
+        &mut self,
+        id: i16,
+        items: &[T],
+        mut body: impl FnMut(&mut Self, &T),
+    ) -> &mut Self {
+        self.list_header(id, STRUCT, items.len());
+        for item in items {
+            self.nested(|encoder| body(encoder, item));
+        }
+        self
+    }
+
+    // Ends the outermost struct.
+    pub fn stop(&mut self) {
+        self.bytes.push(0);
+    }
+
+    fn nested(&mut self, body: impl FnOnce(&mut Self)) {
+        self.last_field.push(0);
+        body(self);
+        self.bytes.push(0);
+        self.last_field.pop();
+    }
+
+    fn field(&mut self, id: i16, kind: u8) {
+        let last = self.last_field.last_mut().expect("always one struct");
+        let delta = id - *last;
+        *last = id;
+        if (1..=15).contains(&delta) {
+            self.bytes.push(((delta as u8) << 4) | kind);
+        } else {
+            self.bytes.push(kind);
+            self.varint(zigzag(id as i64));
+        }
+    }
+
+    fn list_header(&mut self, id: i16, kind: u8, len: usize) {
+        self.field(id, LIST);
+        if len < 15 {
+            self.bytes.push(((len as u8) << 4) | kind);
+        } else {
+            self.bytes.push(0xf0 | kind);
+            self.varint(len as u64);
+        }
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // A value read back by the compact protocol's rules, independently of the encoder.
+    #[derive(Debug, PartialEq)]
+    pub enum Value {
+        Int(i64),
+        Binary(Vec<u8>),
+        List(Vec<Value>),
+        Struct(Vec<(i16, Value)>),
+    }
+
+    impl Value {
+        pub fn int(&self) -> i64 {
+            match self {
+                Value::Int(value) => *value,
+                value => panic!("{:?} is not an integer", value),
+            }
+        }
+
+        pub fn binary(&self) -> &[u8] {
+            match self {
+                Value::Binary(value) => value,
+                value => panic!("{:?} is not binary", value),
+            }
+        }
+
+        pub fn list(&self) -> &[Value] {
+            match self {
+                Value::List(values) => values,
+                value => panic!("{:?} is not a list", value),
+            }
+        }
+
+        // The struct's field of the id.
+        pub fn field(&self, id: i16) -> &Value {
+            match self {
+                Value::Struct(fields) => fields
+                    .iter()
+                    .find_map(|(field, value)| (*field == id).then_some(value))
+                    .unwrap_or_else(|| panic!("no field {} in {:?}", id, fields)),
+                value => panic!("{:?} is not a struct", value),
+            }
+        }
+    }
+
+    // Reads a struct off the front of the bytes, through its stop byte.
+    pub fn read(bytes: &mut &[u8]) -> Value {
+        let mut fields = Vec::new();
+        let mut last = 0;
+        loop {
+            let header = byte(bytes);
+            if header == 0 {
+                return Value::Struct(fields);
+            }
+            let id = match header >> 4 {
+                0 => unzigzag(varint(bytes)) as i16,
+                delta => last + delta as i16,
+            };
+            last = id;
+            fields.push((id, value(bytes, header & 0x0f)));
+        }
+    }
+
+    fn value(bytes: &mut &[u8], kind: u8) -> Value {
+        match kind {
+            I32 | I64 => Value::Int(unzigzag(varint(bytes))),
+            BINARY => {
+                let len = varint(bytes) as usize;
+                let (value, rest) = bytes.split_at(len);
+                *bytes = rest;
+                Value::Binary(value.to_vec())
+            }
+            LIST => {
+                let header = byte(bytes);
+                let len = match header >> 4 {
+                    15 => varint(bytes) as usize,
+                    len => len as usize,
+                };
+                Value::List((0..len).map(|_| value(bytes, header & 0x0f)).collect())
+            }
+            STRUCT => read(bytes),
+            kind => panic!("unexpected type {}", kind),
+        }
+    }
+
+    fn byte(bytes: &mut &[u8]) -> u8 {
+        let (&byte, rest) = bytes.split_first().expect("truncated");
+        *bytes = rest;
+        byte
+    }
+
+    fn varint(bytes: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = byte(bytes);
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte < 0x80 {
+                return value;
+            }
+        }
+        panic!("varint too long");
+    }
+
+    fn unzigzag(value: u64) -> i64 {
+        ((value >> 1) as i64) ^ -((value & 1) as i64)
+    }
+
+    fn encode(body: impl FnOnce(&mut Encoder)) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        body(&mut encoder);
+        encoder.stop();
+        encoder.finish()
+    }
+
+    #[test]
+    fn zigzags_integers() {
+        for (value, encoded) in [
+            (0, 0),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (i32::MAX as i64, 0xffff_fffe),
+            (i32::MIN as i64, 0xffff_ffff),
+            (i64::MIN, u64::MAX),
+        ] {
+            assert_eq!(zigzag(value), encoded, "{}", value);
+            assert_eq!(unzigzag(encoded), value, "{}", value);
+        }
+    }
+
+    #[test]
+    fn writes_varints_least_significant_group_first() {
+        for (value, bytes) in [
+            (63, &[0x16, 0x7e, 0x00][..]),
+            (64, &[0x16, 0x80, 0x01, 0x00]),
+            (300, &[0x16, 0xd8, 0x04, 0x00]),
+            (
+                -1_000_000_000_000,
+                &[0x16, 0xff, 0xbf, 0xa8, 0xca, 0x9a, 0x3a, 0x00],
+            ),
+        ] {
+            assert_eq!(
+                encode(|e| {
+                    e.i64(1, value);
+                }),
+                bytes,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn writes_field_ids_as_deltas() {
+        let bytes = encode(|e| {
+            e.i32(1, 0).i32(16, 0).i32(40, 0).i32(2, 0);
+        });
+        assert_eq!(
+            bytes,
+            [
+                0x15, 0x00, // delta 1
+                0xf5, 0x00, // delta 15, the largest in the header
+                0x05, 0x50, 0x00, // delta 24, so the id in full, zigzagged
+                0x05, 0x04, 0x00, // a smaller id, in full
+                0x00,
+            ]
+        );
+        let fields: Vec<_> = [1, 16, 40, 2].map(|id| (id, Value::Int(0))).into();
+        assert_eq!(read(&mut bytes.as_slice()), Value::Struct(fields));
+    }
+
+    #[test]
+    fn counts_deltas_within_each_struct() {
+        let bytes = encode(|e| {
+            e.i32(2, 1)
+                .structure(3, |s| {
+                    s.binary(4, b"ab");
+                })
+                .i32(4, -1);
+        });
+        assert_eq!(
+            bytes,
+            [
+                0x25, 0x02, 0x1c, 0x48, 0x02, b'a', b'b', 0x00, 0x15, 0x01, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_list_lengths_in_the_header_or_after_it() {
+        let short = encode(|e| {
+            e.i32_list(1, &[1, -1]);
+        });
+        assert_eq!(short, [0x19, 0x25, 0x02, 0x01, 0x00]);
+        let values: Vec<i32> = (0..15).collect();
+        let long = encode(|e| {
+            e.i32_list(1, &values);
+        });
+        assert_eq!(&long[..3], [0x19, 0xf5, 0x0f]);
+        let read = read(&mut long.as_slice());
+        let ints: Vec<i64> = read.field(1).list().iter().map(Value::int).collect();
+        assert_eq!(ints, (0..15).collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn round_trips_structs_in_lists() {
+        let items = [(1, b"x".as_slice()), (2, b"yz".as_slice())];
+        let bytes = encode(|e| {
+            e.struct_list(2, &items, |e, &(n, name)| {
+                e.i64(1, n).binary_list(3, &[name]);
+            })
+            .binary(6, b"end");
+        });
+        let mut rest = bytes.as_slice();
+        let value = read(&mut rest);
+        assert!(rest.is_empty());
+        let list = value.field(2).list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[1].field(1).int(), 2);
+        assert_eq!(list[1].field(3).list()[0].binary(), b"yz");
+        assert_eq!(value.field(6).binary(), b"end");
+    }
+}
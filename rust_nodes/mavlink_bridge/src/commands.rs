@@ -0,0 +1,169 @@
+// COMMAND_LONGs from the ground station, sent to the nodes as the commands the config maps
+// them to and answered with a COMMAND_ACK once the node replies. Commands the config does
+// not map are answered unsupported. A station retransmits a command it got no ack for
+// with its confirmation counted up; a retransmission is answered with the outcome of the
+// first, or not at all while that is still running, rather than carried out again.
+use crate::config::{CommandConfig, Config};
+use command::{Key, NackReason, Reply, SendError, Senders};
+use mavlink::MavHeader;
+use mavlink::dialects::common::{COMMAND_ACK_DATA, COMMAND_LONG_DATA, MavMessage, MavResult};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use zenoh::Session;
+
+// A command from one component of one station: its system id, component id, and MAV_CMD.
+type Origin = (u8, u8, u32);
+
+pub struct Commands {
+    system_id: u8,
+    component_id: u8,
+    mappings: HashMap<u32, CommandConfig>,
+    outbound: mpsc::Sender<MavMessage>,
+    senders: Senders,
+    // Outcome of the latest command of each origin, None while it runs.
+    latest: Mutex<HashMap<Origin, Option<MavResult>>>,
+    // Stations heard from, reported once each.
+    stations: Mutex<HashSet<(u8, u8)>>,
+}
+
+impl Commands {
+    pub fn new(
+        system_id: u8,
+        component_id: u8,
+        config: Config,
+        session: &Session,
+        timeout: Duration,
+        key: Option<Key>,
+        outbound: mpsc::Sender<MavMessage>,
+    ) -> Self {
+        Commands {
+            system_id,
+            component_id,
+            mappings: config
+                .command
+                .into_iter()
+                .map(|command| (command.mav_cmd, command))
+                .collect(),
+            outbound,
+            senders: Senders::new(session, timeout, key),
+            latest: Mutex::new(HashMap::new()),
+            stations: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn on_message(self: &Arc<Self>, header: MavHeader, message: MavMessage) {
+        match message {
+            MavMessage::HEARTBEAT(_) => {
+                let station = (header.system_id, header.component_id);
+                if self.stations.lock().unwrap().insert(station) {
+                    info!(
+                        "Heard system {} component {}",
+                        header.system_id, header.component_id
+                    );
+                }
+            }
+            MavMessage::COMMAND_LONG(command) => self.on_command(header, command),
+            _ => {}
+        }
+    }
+
+    fn on_command(self: &Arc<Self>, header: MavHeader, data: COMMAND_LONG_DATA) {
+        // 0 addresses every system, or every component of one.
+        if (data.target_system != 0 && data.target_system != self.system_id)
+            || (data.target_component != 0 && data.target_component != self.component_id)
+        {
+            return;
+        }
+        let origin = (header.system_id, header.component_id, data.command as u32);
+        {
+            let mut latest = self.latest.lock().unwrap();
+            match latest.get(&origin) {
+                Some(None) => return,
+                Some(&Some(result)) if data.confirmation > 0 => {
+                    self.ack(&data, result);
+                    return;
+                }
+                _ => {}
+            }
+            latest.insert(origin, None);
+        }
+        let Some(mapping) = self.mappings.get(&(data.command as u32)).cloned() else {
+            debug!(
+                "Refusing {:?} from system {}: not mapped to a command",
+                data.command, header.system_id
+            );
+            self.finish(origin, &data, MavResult::MAV_RESULT_UNSUPPORTED);
+            return;
+        };
+        let commands = self.clone();
+        tokio::spawn(async move {
+            let args = mapping.args([
+                data.param1,
+                data.param2,
+                data.param3,
+                data.param4,
+                data.param5,
+                data.param6,
+                data.param7,
+            ]);
+            let reply = commands
+                .senders
+                .send(&mapping.node, &mapping.command, &args)
+                .await;
+            let result = outcome(&reply);
+            match &reply {
+                Ok(Reply {
+                    result: Ok(message),
+                    ..
+                }) => info!(
+                    "{:?} from system {}: {}/{}: {}",
+                    data.command, header.system_id, mapping.node, mapping.command, message
+                ),
+                Ok(Reply { result: Err(e), .. }) => warn!(
+                    "{:?} from system {}: {}/{} refused: {}",
+                    data.command, header.system_id, mapping.node, mapping.command, e
+                ),
+                Err(e) => warn!(
+                    "{:?} from system {}: {}/{} failed: {}",
+                    data.command, header.system_id, mapping.node, mapping.command, e
+                ),
+            }
+            commands.finish(origin, &data, result);
+        });
+    }
+
+    fn finish(&self, origin: Origin, data: &COMMAND_LONG_DATA, result: MavResult) {
+        self.latest.lock().unwrap().insert(origin, Some(result));
+        self.ack(data, result);
+    }
+
+    fn ack(&self, data: &COMMAND_LONG_DATA, result: MavResult) {
+        let ack = MavMessage::COMMAND_ACK(COMMAND_ACK_DATA {
+            command: data.command,
+            result,
+        });
+        if self.outbound.try_send(ack).is_err() {
+            warn!("Dropping the ack of {:?}: the link is full", data.command);
+        }
+    }
+}
+
+fn outcome(reply: &Result<Reply, SendError>) -> MavResult {
+    match reply {
+        Ok(Reply { result: Ok(_), .. }) => MavResult::MAV_RESULT_ACCEPTED,
+        Ok(Reply {
+            result: Err(nack), ..
+        }) => match nack.reason {
+            NackReason::UnknownCommand => MavResult::MAV_RESULT_UNSUPPORTED,
+            NackReason::Rejected => MavResult::MAV_RESULT_TEMPORARILY_REJECTED,
+            NackReason::Failed => MavResult::MAV_RESULT_FAILED,
+            NackReason::Malformed | NackReason::InvalidArgs | NackReason::Unauthorized => {
+                MavResult::MAV_RESULT_DENIED
+            }
+        },
+        Err(_) => MavResult::MAV_RESULT_FAILED,
+    }
+}
@@ -0,0 +1,91 @@
+use common::config;
+use mavlink::dialects::common::MavCmd;
+use num_traits::FromPrimitive;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+// A MAVLink command carried out as a command of a node.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CommandConfig {
+    // MAV_CMD number the ground station sends in a COMMAND_LONG, e.g. 31010 for
+    // MAV_CMD_USER_1.
+    pub mav_cmd: u32,
+    // Node to send the command to, and the command, as cmd takes them.
+    pub node: String,
+    pub command: String,
+    // Arguments sent whatever the COMMAND_LONG holds.
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+    // Arguments taken from the COMMAND_LONG's params, by param number, 1 to 7. A param
+    // the station leaves NaN is not sent.
+    #[serde(default)]
+    pub params: BTreeMap<String, u8>,
+}
+
+impl CommandConfig {
+    // The command's arguments, given the COMMAND_LONG's seven params.
+    pub fn args(&self, params: [f32; 7]) -> Vec<(String, String)> {
+        let mut args: Vec<(String, String)> = self
+            .args
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        for (name, &param) in &self.params {
+            let value = params[param as usize - 1];
+            if !value.is_nan() {
+                args.push((name.clone(), value.to_string()));
+            }
+        }
+        args
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub command: Vec<CommandConfig>,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        for command in &self.command {
+            let name = format!("MAV_CMD {}", command.mav_cmd);
+            // COMMAND_LONGs of numbers the common dialect does not define cannot be read.
+            if MavCmd::from_u32(command.mav_cmd).is_none() {
+                return Err(format!("{} is not in the common dialect", name));
+            }
+            if !seen.insert(command.mav_cmd) {
+                return Err(format!("{} appears more than once", name));
+            }
+            for (what, value) in [("node", &command.node), ("command", &command.command)] {
+                if value.is_empty() || value.contains(['/', '*', '$', '?', '#']) {
+                    return Err(format!("{}: '{}' is not a {} name", name, value, what));
+                }
+            }
+            for (arg, &param) in &command.params {
+                if !(1..=7).contains(&param) {
+                    return Err(format!(
+                        "{}: {} takes param {}, not 1 to 7",
+                        name, arg, param
+                    ));
+                }
+                if command.args.contains_key(arg) {
+                    return Err(format!(
+                        "{}: {} is given both a value and a param",
+                        name, arg
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+// The link to the ground control station, over UDP or a serial telemetry radio. Messages
+// to send come in on a channel and go out as MAVLink 2 with the next sequence number;
+// messages received go to a handler. A serial port is opened again whenever it fails, e.g.
+// when a USB radio is unplugged and plugged back in.
+use mavlink::dialects::common::MavMessage;
+use mavlink::error::MessageReadError;
+use mavlink::{AsyncMavlinkReader, MavHeader, MavlinkReader};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+use tokio_serial::{SerialPortBuilderExt, SerialStream};
+use tracing::{info, warn};
+
+// How long to wait before opening the port again after it failed.
+const RETRY: Duration = Duration::from_secs(1);
+
+// Largest datagram taken; MAVLink 2 frames are at most 280 bytes, and a datagram may hold
+// several.
+const MAX_DATAGRAM: usize = 65536;
+
+pub enum Endpoint {
+    // Sends to the station's address from the socket, and takes what comes back to it.
+    Udp { gcs: SocketAddr, socket: UdpSocket },
+    Serial { device: String, baud: u32 },
+}
+
+pub struct Link {
+    pub endpoint: Endpoint,
+    // System and component id the messages are sent from.
+    pub system_id: u8,
+    pub component_id: u8,
+}
+
+// Frames messages with the link's ids and a sequence number counting up across them.
+struct Framer {
+    system_id: u8,
+    component_id: u8,
+    sequence: u8,
+}
+
+impl Framer {
+    fn frame(&mut self, message: &MavMessage) -> Vec<u8> {
+        let header = MavHeader {
+            system_id: self.system_id,
+            component_id: self.component_id,
+            sequence: self.sequence,
+        };
+        self.sequence = self.sequence.wrapping_add(1);
+        let mut bytes = Vec::with_capacity(64);
+        mavlink::write_v2_msg(&mut bytes, header, message).expect("writing to a Vec");
+        bytes
+    }
+}
+
+impl Link {
+    // Runs the link until outbound closes, handing every message received to on_message.
+    pub async fn run<F>(self, mut outbound: mpsc::Receiver<MavMessage>, on_message: F)
+    where
+        F: Fn(MavHeader, MavMessage) + Clone + Send + 'static,
+    {
+        let mut framer = Framer {
+            system_id: self.system_id,
+            component_id: self.component_id,
+            sequence: 0,
+        };
+        match self.endpoint {
+            Endpoint::Udp { gcs, socket } => {
+                run_udp(gcs, socket, &mut framer, &mut outbound, on_message).await
+            }
+            Endpoint::Serial { device, baud } => {
+                run_serial(&device, baud, &mut framer, &mut outbound, on_message).await
+            }
+        }
+    }
+}
+
+async fn run_udp<F>(
+    gcs: SocketAddr,
+    socket: UdpSocket,
+    framer: &mut Framer,
+    outbound: &mut mpsc::Receiver<MavMessage>,
+    on_message: F,
+) where
+    F: Fn(MavHeader, MavMessage),
+{
+    info!("Sending MAVLink to {} over UDP", gcs);
+    // Whether sending is failing, so only the first failure in a row is reported.
+    let mut failing = false;
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+    loop {
+        tokio::select! {
+            message = outbound.recv() => {
+                let Some(message) = message else { return };
+                match socket.send_to(&framer.frame(&message), gcs).await {
+                    Ok(_) if failing => {
+                        info!("Sending to {} again", gcs);
+                        failing = false;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        if !failing {
+                            warn!("Failed to send to {}: {}", gcs, e);
+                            failing = true;
+                        }
+                    }
+                }
+            }
+            received = socket.recv_from(&mut buf) => {
+                let len = match received {
+                    Ok((len, _)) => len,
+                    Err(e) => {
+                        warn!("Failed to receive from {}: {}", gcs, e);
+                        continue;
+                    }
+                };
+                // A datagram holds whole messages; frames that fail their checksum or
+                // are not of the common dialect are skipped.
+                let mut reader = MavlinkReader::new(&buf[..len]);
+                loop {
+                    match reader.read_any_message::<MavMessage>() {
+                        Ok((header, message)) => on_message(header, message),
+                        Err(MessageReadError::Parse(_)) => continue,
+                        Err(MessageReadError::Io(_)) => break,
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn open(device: &str, baud: u32) -> SerialStream {
+    let mut failed = false;
+    loop {
+        match tokio_serial::new(device, baud).open_native_async() {
+            Ok(port) => {
+                info!("Opened {} at {} baud", device, baud);
+                return port;
+            }
+            Err(e) => {
+                if !failed {
+                    warn!(
+                        "Cannot open {}: {}; retrying every {} s",
+                        device,
+                        e,
+                        RETRY.as_secs()
+                    );
+                    failed = true;
+                }
+                sleep(RETRY).await;
+            }
+        }
+    }
+}
+
+async fn run_serial<F>(
+    device: &str,
+    baud: u32,
+    framer: &mut Framer,
+    outbound: &mut mpsc::Receiver<MavMessage>,
+    on_message: F,
+) where
+    F: Fn(MavHeader, MavMessage) + Clone + Send + 'static,
+{
+    loop {
+        let (read, mut write) = tokio::io::split(open(device, baud).await);
+        // Read in a task of its own, as a read cancelled part way through a frame would
+        // lose it.
+        let on_message = on_message.clone();
+        let device_name = device.to_string();
+        let mut receiver = tokio::spawn(async move {
+            let mut reader = AsyncMavlinkReader::new(read);
+            loop {
+                match reader.read_any_message::<MavMessage>().await {
+                    Ok((header, message)) => on_message(header, message),
+                    Err(MessageReadError::Parse(_)) => continue,
+                    Err(MessageReadError::Io(e)) => {
+                        warn!("Lost {}: {}", device_name, e);
+                        return;
+                    }
+                }
+            }
+        });
+        loop {
+            tokio::select! {
+                message = outbound.recv() => {
+                    let Some(message) = message else {
+                        receiver.abort();
+                        return;
+                    };
+                    if let Err(e) = write.write_all(&framer.frame(&message)).await {
+                        warn!("Lost {}: {}", device, e);
+                        break;
+                    }
+                }
+                _ = &mut receiver => break,
+            }
+        }
+        receiver.abort();
+        sleep(RETRY).await;
+    }
+}
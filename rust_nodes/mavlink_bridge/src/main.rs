@@ -0,0 +1,300 @@
+mod commands;
+mod config;
+mod link;
+mod telemetry;
+
+use clap::{ArgGroup, Parser};
+use command::Key;
+use commands::Commands;
+use config::Config;
+use keyspace::keys;
+use link::{Endpoint, Link};
+use mavlink::dialects::common::MavMessage;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId, TimerId};
+use sensors_rs::readings::{GnssReading, PowerReading, Reading};
+use sensors_rs::sensors;
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use telemetry::{Fused, Telemetry};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+// Messages held for the link while it is slow or down; messages arriving once it is full
+// are dropped.
+const QUEUE: usize = 64;
+
+// How often the heartbeat, GNSS fix, and battery are sent.
+const SLOW_PERIOD: Duration = Duration::from_secs(1);
+
+#[derive(Parser)]
+#[command(
+    about = "Sends the fused state, GNSS fix, and battery to a MAVLink ground control station such as QGroundControl, over UDP or a serial radio, and carries out the commands it sends",
+    group(ArgGroup::new("link").required(true).args(["udp", "serial"]))
+)]
+struct Args {
+    /// Address of the ground station to send to over UDP, e.g. 127.0.0.1:14550, where
+    /// QGroundControl listens.
+    #[arg(long, value_name = "ADDR")]
+    udp: Option<SocketAddr>,
+    /// Address to send from over UDP, which the station replies to; any port on every
+    /// interface if not given.
+    #[arg(long, value_name = "ADDR", requires = "udp")]
+    bind_addr: Option<SocketAddr>,
+    /// Serial port of a telemetry radio to the ground station, e.g. /dev/ttyUSB0.
+    #[arg(long, value_name = "PATH")]
+    serial: Option<String>,
+    #[arg(long, default_value_t = 57600, requires = "serial")]
+    baud: u32,
+    /// MAVLink system id of the vehicle.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..))]
+    system_id: u8,
+    /// MAVLink component id the bridge sends as, 1 for the autopilot.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..))]
+    component_id: u8,
+    /// Times a second the attitude, position, and HUD are sent.
+    #[arg(long, default_value_t = 10.0)]
+    rate_hz: f64,
+    /// Key of the fused state to send.
+    #[arg(long, default_value = keys::fused_state())]
+    fused_key: String,
+    /// Key of the GNSS receiver whose fix is sent.
+    #[arg(long, default_value_t = keys::gnss(0))]
+    gnss_key: String,
+    /// Key of the power board whose reading is sent as the battery.
+    #[arg(long, default_value_t = keys::power(0))]
+    power_key: String,
+    /// MAVLink commands to carry out and the node commands they map to (TOML or JSON).
+    /// Every command is answered unsupported if not given.
+    #[arg(long, value_name = "PATH")]
+    commands: Option<PathBuf>,
+    /// How long each attempt of a command waits for the node to be reachable and to
+    /// reply, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    command_timeout_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct MavlinkBridge {
+    gcs: Option<SocketAddr>,
+    bind_addr: Option<SocketAddr>,
+    serial: Option<String>,
+    baud: u32,
+    system_id: u8,
+    component_id: u8,
+    period: Duration,
+    fused_key: String,
+    gnss_key: String,
+    power_key: String,
+    config: Config,
+    command_timeout: Duration,
+    command_key: Option<Key>,
+    messages: Option<mpsc::Sender<MavMessage>>,
+    link: Option<JoinHandle<()>>,
+    fast: Option<TimerId>,
+    fused: Option<SubscriptionId>,
+    gnss: Option<SubscriptionId>,
+    power: Option<SubscriptionId>,
+    telemetry: Telemetry,
+    started: Instant,
+    // Messages dropped since the link last took one.
+    dropped: u64,
+    // Keys whose samples cannot be read, reported once each.
+    reported: HashSet<String>,
+}
+
+impl MavlinkBridge {
+    fn on_sample(&mut self, id: SubscriptionId, sample: &Sample) {
+        if sample.kind() == SampleKind::Delete {
+            return;
+        }
+        let payload = sample.payload().to_bytes();
+        let read = if Some(id) == self.fused {
+            flatbuffers::root::<sensors::FusedState>(&payload)
+                .map(|state| self.telemetry.fused = Some(Fused::from(state)))
+                .map_err(|e| e.to_string())
+        } else if Some(id) == self.gnss {
+            GnssReading::decode(&payload)
+                .map(|fix| self.telemetry.gnss = Some(fix))
+                .map_err(|e| e.to_string())
+        } else if Some(id) == self.power {
+            PowerReading::decode(&payload)
+                .map(|power| self.telemetry.power = Some(power))
+                .map_err(|e| e.to_string())
+        } else {
+            Ok(())
+        };
+        let key = sample.key_expr().as_str();
+        match read {
+            Err(e) if self.reported.insert(key.to_string()) => {
+                warn!("Cannot read the samples of {}: {}", key, e);
+            }
+            _ => {}
+        }
+    }
+
+    fn send(&mut self, messages: Vec<MavMessage>) {
+        let Some(link) = &self.messages else {
+            return;
+        };
+        for message in messages {
+            match link.try_send(message) {
+                Ok(()) if self.dropped > 0 => {
+                    info!(
+                        "Sending to the ground station again after dropping {} messages",
+                        self.dropped
+                    );
+                    self.dropped = 0;
+                }
+                Ok(()) => {}
+                Err(_) => {
+                    if self.dropped == 0 {
+                        warn!("Dropping messages until the link takes them again");
+                    }
+                    self.dropped += 1;
+                }
+            }
+        }
+    }
+
+    fn on_timer(&mut self, id: TimerId) {
+        if Some(id) == self.fast {
+            let time_boot_ms = self.started.elapsed().as_millis() as u32;
+            let messages = self.telemetry.fast(time_boot_ms);
+            self.send(messages);
+        } else {
+            let mut messages = vec![self.telemetry.heartbeat()];
+            messages.extend(self.telemetry.slow());
+            self.send(messages);
+        }
+    }
+}
+
+impl Node for MavlinkBridge {
+    const NAME: &'static str = "mavlink_bridge";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let endpoint = match (self.gcs, self.serial.take()) {
+            (Some(gcs), _) => {
+                let local = self.bind_addr.unwrap_or(match gcs {
+                    SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+                    SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+                });
+                Endpoint::Udp {
+                    gcs,
+                    socket: UdpSocket::bind(local).await?,
+                }
+            }
+            (None, Some(device)) => Endpoint::Serial {
+                device,
+                baud: self.baud,
+            },
+            (None, None) => unreachable!("clap requires --udp or --serial"),
+        };
+        let (messages, outbound) = mpsc::channel(QUEUE);
+        let commands = Arc::new(Commands::new(
+            self.system_id,
+            self.component_id,
+            std::mem::take(&mut self.config),
+            ctx.session(),
+            self.command_timeout,
+            self.command_key.take(),
+            messages.clone(),
+        ));
+        let link = Link {
+            endpoint,
+            system_id: self.system_id,
+            component_id: self.component_id,
+        };
+        self.link = Some(tokio::spawn(link.run(outbound, move |header, message| {
+            commands.on_message(header, message)
+        })));
+        self.messages = Some(messages);
+        self.fused = Some(ctx.subscribe(&self.fused_key).await?);
+        self.gnss = Some(ctx.subscribe(&self.gnss_key).await?);
+        self.power = Some(ctx.subscribe(&self.power_key).await?);
+        self.fast = Some(ctx.add_timer(self.period));
+        ctx.add_timer(SLOW_PERIOD);
+        self.started = Instant::now();
+        info!(
+            "Sending as system {} component {}",
+            self.system_id, self.component_id
+        );
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(id, sample) => self.on_sample(id, &sample),
+            Event::Timer(id) => self.on_timer(id),
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(link) = self.link.take() {
+            link.abort();
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if !(args.rate_hz.is_finite() && args.rate_hz > 0.0) {
+        error!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    let config = match args.commands.as_deref().map(Config::load).transpose() {
+        Ok(config) => config.unwrap_or_default(),
+        Err(e) => {
+            error!("Invalid MAVLink command mapping: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    // Commands are signed with the secret the bridge checks the commands it receives
+    // against, the one the other nodes check them against too.
+    let command_key = match args.node.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let node = MavlinkBridge {
+        gcs: args.udp,
+        bind_addr: args.bind_addr,
+        serial: args.serial,
+        baud: args.baud,
+        system_id: args.system_id,
+        component_id: args.component_id,
+        period: Duration::from_secs_f64(1.0 / args.rate_hz),
+        fused_key: args.fused_key,
+        gnss_key: args.gnss_key,
+        power_key: args.power_key,
+        config,
+        command_timeout: Duration::from_millis(args.command_timeout_ms),
+        command_key,
+        messages: None,
+        link: None,
+        fast: None,
+        fused: None,
+        gnss: None,
+        power: None,
+        telemetry: Telemetry::default(),
+        started: Instant::now(),
+        dropped: 0,
+        reported: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
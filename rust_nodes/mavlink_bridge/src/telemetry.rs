@@ -0,0 +1,191 @@
+// The latest fused state, GNSS fix, and power board reading, as the MAVLink messages a
+// ground control station shows. Velocities go from the bus's east-north-up to MAVLink's
+// north-east-down; attitude is sent as fusion estimates it. Values out of a field's range
+// saturate.
+use mavlink::dialects::common::{
+    ATTITUDE_DATA, BATTERY_STATUS_DATA, GLOBAL_POSITION_INT_DATA, GPS_RAW_INT_DATA, GpsFixType,
+    HEARTBEAT_DATA, MavAutopilot, MavBatteryFunction, MavBatteryType, MavMessage, MavModeFlag,
+    MavState, MavSysStatusSensor, MavType, SYS_STATUS_DATA, VFR_HUD_DATA,
+};
+use sensors_rs::readings::{FixType, GnssReading, PowerReading};
+use sensors_rs::sensors;
+
+// Unknown, for the unsigned fields MAVLink gives a value for it.
+const UNKNOWN_U16: u16 = u16::MAX;
+
+// What the fused state holds valid.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fused {
+    // Roll, pitch, and yaw in radians.
+    pub attitude: Option<[f32; 3]>,
+    pub angular_rate: Option<[f32; 3]>,
+    pub altitude: Option<f32>,
+    // East-north-up in m/s.
+    pub velocity: Option<[f32; 3]>,
+}
+
+impl From<sensors::FusedState<'_>> for Fused {
+    fn from(state: sensors::FusedState<'_>) -> Self {
+        let vec3 = |v: Option<&sensors::Vec3>| v.map(|v| [v.x(), v.y(), v.z()]);
+        Fused {
+            attitude: vec3(state.attitude()).filter(|_| state.attitude_valid()),
+            angular_rate: vec3(state.angular_rate()).filter(|_| state.angular_rate_valid()),
+            altitude: Some(state.altitude()).filter(|_| state.altitude_valid()),
+            velocity: vec3(state.velocity()).filter(|_| state.velocity_valid()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Telemetry {
+    pub fused: Option<Fused>,
+    pub gnss: Option<GnssReading>,
+    pub power: Option<PowerReading>,
+}
+
+// North, east, and down from east, north, and up.
+fn ned(enu: [f32; 3]) -> [f32; 3] {
+    [enu[1], enu[0], -enu[2]]
+}
+
+// Degrees from north, clockwise, of the horizontal part of an east-north-up velocity.
+fn course(enu: [f32; 3]) -> f32 {
+    enu[0].atan2(enu[1]).to_degrees().rem_euclid(360.0)
+}
+
+impl Telemetry {
+    // Active once the fused state has arrived, in standby until then.
+    pub fn heartbeat(&self) -> MavMessage {
+        MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            custom_mode: 0,
+            mavtype: MavType::MAV_TYPE_ROCKET,
+            autopilot: MavAutopilot::MAV_AUTOPILOT_GENERIC,
+            base_mode: MavModeFlag::empty(),
+            system_status: if self.fused.is_some() {
+                MavState::MAV_STATE_ACTIVE
+            } else {
+                MavState::MAV_STATE_STANDBY
+            },
+            mavlink_version: 3,
+        })
+    }
+
+    // The messages sent at the fast rate: attitude, position, and the HUD, each once what
+    // it is made of has arrived.
+    pub fn fast(&self, time_boot_ms: u32) -> Vec<MavMessage> {
+        let mut messages = Vec::new();
+        let fused = self.fused.unwrap_or_default();
+        let velocity = fused
+            .velocity
+            .or_else(|| self.gnss.as_ref().and_then(|gnss| gnss.velocity));
+        if let Some([roll, pitch, yaw]) = fused.attitude {
+            let [rollspeed, pitchspeed, yawspeed] = fused.angular_rate.unwrap_or_default();
+            messages.push(MavMessage::ATTITUDE(ATTITUDE_DATA {
+                time_boot_ms,
+                roll,
+                pitch,
+                yaw,
+                rollspeed,
+                pitchspeed,
+                yawspeed,
+            }));
+        }
+        if let Some(gnss) = self
+            .gnss
+            .as_ref()
+            .filter(|gnss| gnss.fix_type != FixType::NoFix)
+        {
+            let [vn, ve, vd] = ned(velocity.unwrap_or_default());
+            messages.push(MavMessage::GLOBAL_POSITION_INT(GLOBAL_POSITION_INT_DATA {
+                time_boot_ms,
+                lat: (gnss.latitude_deg * 1e7).round() as i32,
+                lon: (gnss.longitude_deg * 1e7).round() as i32,
+                alt: (gnss.altitude * 1000.0).round() as i32,
+                relative_alt: (fused.altitude.unwrap_or(0.0) * 1000.0).round() as i32,
+                vx: (vn * 100.0).round() as i16,
+                vy: (ve * 100.0).round() as i16,
+                vz: (vd * 100.0).round() as i16,
+                hdg: UNKNOWN_U16,
+            }));
+        }
+        if fused.altitude.is_some() || velocity.is_some() {
+            let velocity = velocity.unwrap_or_default();
+            messages.push(MavMessage::VFR_HUD(VFR_HUD_DATA {
+                airspeed: 0.0,
+                groundspeed: velocity[0].hypot(velocity[1]),
+                alt: fused.altitude.unwrap_or(0.0),
+                climb: velocity[2],
+                heading: course(velocity).round() as i16 % 360,
+                throttle: 0,
+            }));
+        }
+        messages
+    }
+
+    // The messages sent once a second: the GNSS fix and the battery.
+    pub fn slow(&self) -> Vec<MavMessage> {
+        let mut messages = Vec::new();
+        if let Some(gnss) = &self.gnss {
+            let velocity = gnss.velocity.unwrap_or_default();
+            messages.push(MavMessage::GPS_RAW_INT(GPS_RAW_INT_DATA {
+                time_usec: gnss.timestamp_us,
+                lat: (gnss.latitude_deg * 1e7).round() as i32,
+                lon: (gnss.longitude_deg * 1e7).round() as i32,
+                alt: (gnss.altitude * 1000.0).round() as i32,
+                eph: (gnss.hdop * 100.0).round() as u16,
+                epv: (gnss.vdop * 100.0).round() as u16,
+                vel: (velocity[0].hypot(velocity[1]) * 100.0).round() as u16,
+                cog: match gnss.velocity {
+                    Some(velocity) => (course(velocity) * 100.0).round() as u16 % 36000,
+                    None => UNKNOWN_U16,
+                },
+                fix_type: match gnss.fix_type {
+                    FixType::NoFix => GpsFixType::GPS_FIX_TYPE_NO_FIX,
+                    FixType::Fix2D => GpsFixType::GPS_FIX_TYPE_2D_FIX,
+                    FixType::Fix3D => GpsFixType::GPS_FIX_TYPE_3D_FIX,
+                },
+                satellites_visible: gnss.satellites,
+            }));
+        }
+        // The battery's voltage in mV and current in units of 10 mA, or MAVLink's unknown.
+        let (voltage, current) = match &self.power {
+            Some(power) => (
+                (power.voltage * 1000.0).round() as u16,
+                (power.current * 100.0).round() as i16,
+            ),
+            None => (UNKNOWN_U16, -1),
+        };
+        messages.push(MavMessage::SYS_STATUS(SYS_STATUS_DATA {
+            onboard_control_sensors_present: MavSysStatusSensor::empty(),
+            onboard_control_sensors_enabled: MavSysStatusSensor::empty(),
+            onboard_control_sensors_health: MavSysStatusSensor::empty(),
+            load: 0,
+            voltage_battery: voltage,
+            current_battery: current,
+            drop_rate_comm: 0,
+            errors_comm: 0,
+            errors_count1: 0,
+            errors_count2: 0,
+            errors_count3: 0,
+            errors_count4: 0,
+            battery_remaining: -1,
+        }));
+        if let Some(power) = &self.power {
+            // The whole pack as one cell, as the board measures no cells.
+            let mut voltages = [UNKNOWN_U16; 10];
+            voltages[0] = voltage;
+            messages.push(MavMessage::BATTERY_STATUS(BATTERY_STATUS_DATA {
+                current_consumed: -1,
+                energy_consumed: -1,
+                temperature: (power.temperature * 100.0).round() as i16,
+                voltages,
+                current_battery: current,
+                id: 0,
+                battery_function: MavBatteryFunction::MAV_BATTERY_FUNCTION_ALL,
+                mavtype: MavBatteryType::MAV_BATTERY_TYPE_UNKNOWN,
+                battery_remaining: -1,
+            }));
+        }
+        messages
+    }
+}
@@ -0,0 +1,224 @@
+use crate::schema::Scalar;
+use ccsds_framer::packet::MAX_APID;
+use common::config;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+// An argument the telecommand carries, packed big-endian after the ones before it.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ArgumentConfig {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: Scalar,
+}
+
+// A telecommand carried out as a command of a node.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CommandConfig {
+    // APID the mission control system sends the telecommand on.
+    pub apid: u16,
+    // Name the dictionary gives the telecommand; <node>_<command> if not given.
+    pub name: Option<String>,
+    // Node to send the command to, and the command, as cmd takes them.
+    pub node: String,
+    pub command: String,
+    // Arguments sent whatever the telecommand holds.
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+    // Arguments the telecommand carries, in the order it packs them.
+    #[serde(default)]
+    pub arguments: Vec<ArgumentConfig>,
+}
+
+impl CommandConfig {
+    pub fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{}_{}", self.node, self.command))
+    }
+
+    // Octets of the arguments the telecommand carries.
+    pub fn arguments_len(&self) -> usize {
+        self.arguments
+            .iter()
+            .map(|argument| argument.kind.size())
+            .sum()
+    }
+
+    // Octets of the telecommand's data field: its arguments, or one spare octet if it has
+    // none, as a Space Packet's data field is never empty.
+    pub fn data_len(&self) -> usize {
+        self.arguments_len().max(1)
+    }
+
+    // The command's arguments, given the telecommand's user data: numbers as their
+    // decimal text, booleans as true or false.
+    pub fn args(&self, data: &[u8]) -> Result<Vec<(String, String)>, String> {
+        if data.len() < self.arguments_len() {
+            return Err(format!(
+                "{} octets are too few for arguments of {}",
+                data.len(),
+                self.arguments_len()
+            ));
+        }
+        let mut args: Vec<(String, String)> = self
+            .args
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let mut at = 0;
+        for argument in &self.arguments {
+            let bytes = &data[at..at + argument.kind.size()];
+            at += bytes.len();
+            let value = match argument.kind {
+                Scalar::Bool => (bytes[0] != 0).to_string(),
+                Scalar::I8 => (bytes[0] as i8).to_string(),
+                Scalar::U8 => bytes[0].to_string(),
+                Scalar::I16 => i16::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::U16 => u16::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::I32 => i32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::U32 => u32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::I64 => i64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::U64 => u64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::F32 => f32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+                Scalar::F64 => f64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+            };
+            args.push((argument.name.clone(), value));
+        }
+        Ok(args)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Config {
+    // APID of the telemetry packet reporting how each telecommand went; not reported if
+    // not given.
+    pub reply_apid: Option<u16>,
+    #[serde(default)]
+    pub command: Vec<CommandConfig>,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // The telecommand of an APID, if it is configured.
+    pub fn apid(&self, apid: u16) -> Option<&CommandConfig> {
+        self.command.iter().find(|command| command.apid == apid)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(apid) = self.reply_apid.filter(|&apid| apid > MAX_APID) {
+            return Err(format!("reply_apid {} is above {}", apid, MAX_APID));
+        }
+        let mut apids = HashSet::new();
+        let mut names = HashSet::new();
+        for command in &self.command {
+            let name = command.name();
+            if command.apid > MAX_APID {
+                return Err(format!(
+                    "{}: APID {} is above {}",
+                    name, command.apid, MAX_APID
+                ));
+            }
+            if !apids.insert(command.apid) {
+                return Err(format!("APID {} configured twice", command.apid));
+            }
+            if !is_name(&name) {
+                return Err(format!("{}: names are letters, digits, and '_'", name));
+            }
+            if !names.insert(name.clone()) {
+                return Err(format!("{} configured twice", name));
+            }
+            for (what, value) in [("node", &command.node), ("command", &command.command)] {
+                if value.is_empty() || value.contains(['/', '*', '$', '?', '#']) {
+                    return Err(format!("{}: '{}' is not a {} name", name, value, what));
+                }
+            }
+            let mut arguments = HashSet::new();
+            for argument in &command.arguments {
+                if !is_name(&argument.name) {
+                    return Err(format!(
+                        "{}: argument names are letters, digits, and '_', not {}",
+                        name, argument.name
+                    ));
+                }
+                if !arguments.insert(&argument.name) {
+                    return Err(format!("{}: {} is given twice", name, argument.name));
+                }
+                if command.args.contains_key(&argument.name) {
+                    return Err(format!(
+                        "{}: {} is given both a value and a type",
+                        name, argument.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Whether a name is one both dictionaries take as it is.
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_limits() -> CommandConfig {
+        let config: Config = toml::from_str(
+            r#"
+            [[command]]
+            apid = 911
+            name = "set_limits"
+            node = "limits"
+            command = "set"
+            args = { unit = "m" }
+            arguments = [
+              { name = "enabled", type = "bool" },
+              { name = "low", type = "int16" },
+              { name = "high", type = "float64" },
+            ]
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        config.command[0].clone()
+    }
+
+    #[test]
+    fn reads_arguments_big_endian() {
+        let command = set_limits();
+        assert_eq!(command.data_len(), 11);
+        let data = [[1, 0xff, 0x38].as_slice(), &2.5f64.to_be_bytes()].concat();
+        let args = command.args(&data).unwrap();
+        let expected = [
+            ("unit", "m"),
+            ("enabled", "true"),
+            ("low", "-200"),
+            ("high", "2.5"),
+        ];
+        assert_eq!(
+            args,
+            expected.map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+        assert!(command.args(&data[..10]).is_err());
+    }
+
+    #[test]
+    fn carries_a_spare_octet_without_arguments() {
+        let mut command = set_limits();
+        command.arguments.clear();
+        assert_eq!(command.data_len(), 1);
+        assert_eq!(command.name(), "set_limits");
+        command.name = None;
+        assert_eq!(command.name(), "limits_set");
+    }
+}
@@ -0,0 +1,79 @@
+use clap::{Parser, ValueEnum};
+use mcs_adapter::{Config, Dictionary, Schema, openc3, xtce};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    // An XTCE space system, for Yamcs.
+    Xtce,
+    // COSMOS command and telemetry definitions, for OpenC3.
+    Openc3,
+}
+
+#[derive(Parser)]
+#[command(
+    about = "Writes the telemetry and telecommand dictionary of mcs_adapter, as XTCE for Yamcs or command and telemetry definitions for OpenC3"
+)]
+struct Args {
+    /// Dictionary to write.
+    #[arg(long, value_enum)]
+    format: Format,
+    /// APID of every key, as ccsds_framer reads it (TOML or JSON). Defaults to
+    /// ccsds_framer's.
+    #[arg(long, value_name = "PATH")]
+    packets: Option<PathBuf>,
+    /// Telecommands and the node commands they are sent as (TOML or JSON), as
+    /// mcs_adapter reads them. No telecommands if not given.
+    #[arg(long, value_name = "PATH")]
+    commands: Option<PathBuf>,
+    /// Name of the XTCE space system, or of the OpenC3 target.
+    #[arg(long, default_value = "ROCKET")]
+    name: String,
+    /// File to write the dictionary to; standard output if not given.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let packets = match args.packets.as_deref().map(ccsds_framer::Config::load) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            eprintln!("Invalid CCSDS config: {}", e);
+            return ExitCode::FAILURE;
+        }
+        None => ccsds_framer::Config::default(),
+    };
+    let commands = match args.commands.as_deref().map(Config::load).transpose() {
+        Ok(config) => config.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Invalid command config: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dictionary = match Dictionary::new(&Schema::sensors(), &packets, &commands) {
+        Ok(dictionary) => dictionary,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    for key in &dictionary.skipped {
+        eprintln!("Leaving out {}: not a FlatBuffers table", key);
+    }
+    let text = match args.format {
+        Format::Xtce => xtce::generate(&dictionary, &args.name),
+        Format::Openc3 => openc3::generate(&dictionary, &args.name),
+    };
+    match &args.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, text) {
+                eprintln!("Cannot write {}: {}", path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", text),
+    }
+    ExitCode::SUCCESS
+}
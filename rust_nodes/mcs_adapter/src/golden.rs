@@ -0,0 +1,61 @@
+// Golden-output test of the dictionaries: generates the XTCE and OpenC3 dictionaries of
+// testdata/schema.fbs, packets.toml, and commands.toml, and compares them with
+// testdata/dictionary.xml and dictionary.txt. A change to the layout or either generator
+// that moves its output fails the test; if the change is intended, regenerate them with
+//
+//     UPDATE_GOLDEN=1 cargo test -p mcs_adapter golden
+//
+// and review the difference before committing it, and check Yamcs and OpenC3 still load
+// them.
+use crate::{Config, Dictionary, Schema, openc3, xtce};
+use std::path::PathBuf;
+
+const NAME: &str = "ROCKET";
+
+// A file in testdata/: under Bazel, in the runfiles directory TESTDATA names, as the
+// crate's directory is gone by the time the test runs.
+fn testdata(name: &str) -> PathBuf {
+    std::env::var_os("TESTDATA")
+        .map_or_else(
+            || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata"),
+            PathBuf::from,
+        )
+        .join(name)
+}
+
+fn dictionary() -> Dictionary {
+    let text = std::fs::read_to_string(testdata("schema.fbs")).expect("Cannot read the schema.");
+    let schema = Schema::parse(&text).expect("The schema is invalid.");
+    let packets = ccsds_framer::Config::load(&testdata("packets.toml")).unwrap();
+    let commands = Config::load(&testdata("commands.toml")).unwrap();
+    Dictionary::new(&schema, &packets, &commands).unwrap()
+}
+
+fn compare(generated: &str, golden: &str) {
+    let path = testdata(golden);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, generated).expect("Cannot write the golden dictionary.");
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).expect("Cannot read the golden dictionary.");
+    // Compared line by line, so a failure points at the first line that moved.
+    for (i, (line, expected)) in generated.lines().zip(expected.lines()).enumerate() {
+        assert_eq!(line, expected, "{} line {}", golden, i + 1);
+    }
+    assert_eq!(generated, expected, "{}", golden);
+}
+
+#[test]
+fn leaves_out_packets_without_a_table() {
+    assert_eq!(dictionary().skipped, ["test/text"]);
+}
+
+#[test]
+fn golden_xtce() {
+    compare(&xtce::generate(&dictionary(), NAME), "dictionary.xml");
+}
+
+#[test]
+fn golden_openc3() {
+    compare(&openc3::generate(&dictionary(), NAME), "dictionary.txt");
+}
@@ -0,0 +1,460 @@
+// How the adapter lays out the telemetry packets it sends and the telecommands it takes,
+// which the dictionaries describe. A telemetry packet is a CCSDS Space Packet as the
+// framer sends them, whose user data is the table's scalar fields, enums, and struct
+// members, each big-endian and packed one after the other in the order the schema
+// declares them, in place of the FlatBuffer. Strings and vectors are left out, as they
+// have no fixed place.
+use crate::config::{self, CommandConfig};
+use crate::schema::{FieldType, Scalar, Schema, Table};
+use std::collections::HashSet;
+
+// Octets before the parameters of a telemetry packet: the primary and secondary headers.
+pub const TM_HEADER_LEN: usize = 12;
+// Octets before the arguments of a telecommand: the primary header only.
+pub const TC_HEADER_LEN: usize = 6;
+
+// How a telecommand went, as the reply packet gives it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Accepted,
+    // No telecommand is configured on the APID, or the node has no such command.
+    UnknownCommand,
+    // The telecommand is too short for its arguments or has a secondary header, or the
+    // node could not read the command.
+    Malformed,
+    InvalidArgs,
+    Rejected,
+    Failed,
+    Unauthorized,
+    // The node could not be reached or did not answer; it may have carried it out.
+    NoReply,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 8] = [
+        Outcome::Accepted,
+        Outcome::UnknownCommand,
+        Outcome::Malformed,
+        Outcome::InvalidArgs,
+        Outcome::Rejected,
+        Outcome::Failed,
+        Outcome::Unauthorized,
+        Outcome::NoReply,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Accepted => "ACCEPTED",
+            Outcome::UnknownCommand => "UNKNOWN_COMMAND",
+            Outcome::Malformed => "MALFORMED",
+            Outcome::InvalidArgs => "INVALID_ARGS",
+            Outcome::Rejected => "REJECTED",
+            Outcome::Failed => "FAILED",
+            Outcome::Unauthorized => "UNAUTHORIZED",
+            Outcome::NoReply => "NO_REPLY",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    // The field's name, with the member's after it for struct fields, e.g. attitude_x.
+    pub name: String,
+    pub scalar: Scalar,
+    // Names of the values an enum or bool takes; empty for numbers.
+    pub states: Vec<(String, i64)>,
+    // Name of the enum the states are of; None for bools and numbers.
+    pub enumeration: Option<String>,
+}
+
+// Where in the FlatBuffer a parameter's value is.
+#[derive(Clone, Debug)]
+enum Location {
+    // A scalar or enum field, by its slot, and the value it has when left out.
+    Field { slot: usize, default: f64 },
+    // A member of a struct field, by the field's slot and the member's offset into it.
+    Member { slot: usize, offset: usize },
+}
+
+#[derive(Clone, Debug)]
+pub struct Source {
+    pub key: String,
+    // The FlatBuffers table the samples are, e.g. sensors.FusedState.
+    pub table: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct TelemetryPacket {
+    pub apid: u16,
+    // The key with '/' as '_', e.g. state_fused.
+    pub name: String,
+    // Where the packet's samples are taken from; None for the command replies, which the
+    // adapter sends itself.
+    pub source: Option<Source>,
+    pub doc: String,
+    pub parameters: Vec<Parameter>,
+    locations: Vec<Location>,
+}
+
+impl TelemetryPacket {
+    fn new(apid: u16, source: Source, schema: &Schema, table: &Table) -> Result<Self, String> {
+        let mut parameters = Vec::new();
+        let mut locations = Vec::new();
+        for (slot, field) in table.fields.iter().enumerate() {
+            match &field.kind {
+                FieldType::Scalar(scalar) => {
+                    let states = if *scalar == Scalar::Bool {
+                        vec![("FALSE".to_string(), 0), ("TRUE".to_string(), 1)]
+                    } else {
+                        Vec::new()
+                    };
+                    parameters.push(Parameter {
+                        name: field.name.clone(),
+                        scalar: *scalar,
+                        states,
+                        enumeration: None,
+                    });
+                    locations.push(Location::Field {
+                        slot,
+                        default: field.default,
+                    });
+                }
+                FieldType::Enum(name) => {
+                    let kind = &schema.enums[name];
+                    parameters.push(Parameter {
+                        name: field.name.clone(),
+                        scalar: kind.underlying,
+                        states: kind.variants.clone(),
+                        enumeration: Some(name.clone()),
+                    });
+                    locations.push(Location::Field {
+                        slot,
+                        default: field.default,
+                    });
+                }
+                FieldType::Struct(name) => {
+                    for (member, scalar, offset) in &schema.structs[name].fields {
+                        parameters.push(Parameter {
+                            name: format!("{}_{}", field.name, member),
+                            scalar: *scalar,
+                            states: Vec::new(),
+                            enumeration: None,
+                        });
+                        locations.push(Location::Member {
+                            slot,
+                            offset: *offset,
+                        });
+                    }
+                }
+                FieldType::Offset => {}
+            }
+        }
+        if parameters.is_empty() {
+            return Err(format!(
+                "{}: {} has no fixed-size fields",
+                source.key, source.table
+            ));
+        }
+        Ok(TelemetryPacket {
+            apid,
+            name: source.key.replace('/', "_"),
+            source: Some(source),
+            doc: table.doc.clone(),
+            parameters,
+            locations,
+        })
+    }
+
+    // The packet carrying how each telecommand went.
+    fn reply(apid: u16) -> Self {
+        let number = |name: &str, scalar| Parameter {
+            name: name.to_string(),
+            scalar,
+            states: Vec::new(),
+            enumeration: None,
+        };
+        let outcomes = Outcome::ALL
+            .iter()
+            .map(|outcome| (outcome.name().to_string(), *outcome as i64))
+            .collect();
+        TelemetryPacket {
+            apid,
+            name: "command_reply".to_string(),
+            source: None,
+            doc: "Sent once for every telecommand the adapter receives: its APID and \
+                  sequence count, and how the node it was sent to took it."
+                .to_string(),
+            parameters: vec![
+                number("command_apid", Scalar::U16),
+                number("command_sequence", Scalar::U16),
+                Parameter {
+                    name: "outcome".to_string(),
+                    scalar: Scalar::U8,
+                    states: outcomes,
+                    enumeration: Some("CommandOutcome".to_string()),
+                },
+            ],
+            locations: Vec::new(),
+        }
+    }
+
+    // Octets of the parameters.
+    pub fn data_len(&self) -> usize {
+        self.parameters.iter().map(|p| p.scalar.size()).sum()
+    }
+
+    // The packet's user data, given a FlatBuffer of its table.
+    pub fn pack(&self, payload: &[u8]) -> Result<Vec<u8>, String> {
+        let table = RawTable::root(payload)?;
+        let mut data = Vec::with_capacity(self.data_len());
+        for (parameter, location) in self.parameters.iter().zip(&self.locations) {
+            let size = parameter.scalar.size();
+            let value = match location {
+                Location::Field { slot, .. } => table.field(*slot, 0, size)?,
+                Location::Member { slot, offset } => table.field(*slot, *offset, size)?,
+            };
+            match (value, location) {
+                // FlatBuffers are little-endian.
+                (Some(value), _) => data.extend(value.iter().rev()),
+                (None, Location::Field { default, .. }) => {
+                    data.extend(big_endian(parameter.scalar, *default))
+                }
+                // A struct left out reads as zeros.
+                (None, Location::Member { .. }) => data.extend(std::iter::repeat_n(0, size)),
+            }
+        }
+        Ok(data)
+    }
+}
+
+// The user data of a command reply.
+pub fn reply_data(command_apid: u16, command_sequence: u16, outcome: Outcome) -> Vec<u8> {
+    let mut data = Vec::with_capacity(5);
+    data.extend_from_slice(&command_apid.to_be_bytes());
+    data.extend_from_slice(&command_sequence.to_be_bytes());
+    data.push(outcome as u8);
+    data
+}
+
+fn big_endian(scalar: Scalar, value: f64) -> Vec<u8> {
+    match scalar {
+        Scalar::Bool | Scalar::U8 => vec![value as u8],
+        Scalar::I8 => vec![value as i8 as u8],
+        Scalar::I16 => (value as i16).to_be_bytes().to_vec(),
+        Scalar::U16 => (value as u16).to_be_bytes().to_vec(),
+        Scalar::I32 => (value as i32).to_be_bytes().to_vec(),
+        Scalar::U32 => (value as u32).to_be_bytes().to_vec(),
+        Scalar::I64 => (value as i64).to_be_bytes().to_vec(),
+        Scalar::U64 => (value as u64).to_be_bytes().to_vec(),
+        Scalar::F32 => (value as f32).to_be_bytes().to_vec(),
+        Scalar::F64 => value.to_be_bytes().to_vec(),
+    }
+}
+
+// A FlatBuffers table, read without its generated code: its position in the buffer and
+// its vtable's.
+struct RawTable<'a> {
+    buf: &'a [u8],
+    table: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+impl<'a> RawTable<'a> {
+    fn root(buf: &'a [u8]) -> Result<Self, String> {
+        let table = u32::from_le_bytes(read(buf, 0)?) as usize;
+        let soffset = i32::from_le_bytes(read(buf, table)?) as isize;
+        let vtable = usize::try_from(table as isize - soffset)
+            .map_err(|_| "vtable is before the buffer".to_string())?;
+        let vtable_len = u16::from_le_bytes(read(buf, vtable)?) as usize;
+        Ok(RawTable {
+            buf,
+            table,
+            vtable,
+            vtable_len,
+        })
+    }
+
+    // The size octets at offset into the field in slot, None if the table leaves it out.
+    fn field(&self, slot: usize, offset: usize, size: usize) -> Result<Option<&'a [u8]>, String> {
+        let entry = 4 + 2 * slot;
+        if entry + 2 > self.vtable_len {
+            return Ok(None);
+        }
+        let voffset = u16::from_le_bytes(read(self.buf, self.vtable + entry)?) as usize;
+        if voffset == 0 {
+            return Ok(None);
+        }
+        let start = self.table + voffset + offset;
+        self.buf
+            .get(start..start + size)
+            .map(Some)
+            .ok_or_else(|| format!("field {} runs past the end of the buffer", slot))
+    }
+}
+
+fn read<const N: usize>(buf: &[u8], at: usize) -> Result<[u8; N], String> {
+    buf.get(at..at + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("{} octets are too few for the table", buf.len()))
+}
+
+// Everything the adapter sends and takes, in the order the dictionaries list it.
+pub struct Dictionary {
+    pub telemetry: Vec<TelemetryPacket>,
+    pub commands: Vec<CommandConfig>,
+    // Keys left out for not being FlatBuffers tables, which have no layout.
+    pub skipped: Vec<String>,
+}
+
+impl Dictionary {
+    pub fn new(
+        schema: &Schema,
+        packets: &ccsds_framer::Config,
+        commands: &config::Config,
+    ) -> Result<Self, String> {
+        let mut telemetry = Vec::new();
+        let mut skipped = Vec::new();
+        for packet in &packets.packets {
+            let Some(name) = &packet.table else {
+                skipped.push(packet.key.clone());
+                continue;
+            };
+            let table = schema
+                .table(name)
+                .ok_or_else(|| format!("{}: no table {} in the schema", packet.key, name))?;
+            let source = Source {
+                key: packet.key.clone(),
+                table: name.clone(),
+            };
+            telemetry.push(TelemetryPacket::new(packet.apid, source, schema, table)?);
+        }
+        if let Some(apid) = commands.reply_apid {
+            if let Some(packet) = telemetry.iter().find(|packet| packet.apid == apid) {
+                return Err(format!(
+                    "reply_apid {} is the APID of {} too",
+                    apid, packet.name
+                ));
+            }
+            telemetry.push(TelemetryPacket::reply(apid));
+        }
+        let mut names = HashSet::new();
+        for packet in &telemetry {
+            if !names.insert(&packet.name) {
+                return Err(format!("two packets are named {}", packet.name));
+            }
+        }
+        Ok(Dictionary {
+            telemetry,
+            commands: commands.command.clone(),
+            skipped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = include_str!("../testdata/schema.fbs");
+
+    fn sample() -> TelemetryPacket {
+        let schema = Schema::parse(SCHEMA).unwrap();
+        let source = Source {
+            key: "test/sample".to_string(),
+            table: "test.Sample".to_string(),
+        };
+        TelemetryPacket::new(100, source, &schema, schema.table("test.Sample").unwrap()).unwrap()
+    }
+
+    // A FlatBuffer of test.Sample, as flatc's builder lays it out: the root offset, the
+    // vtable of the slots given as (slot, offset into the table), then the table at 24.
+    fn buffer(slots: &[(usize, u16)], fields: &[(usize, &[u8])]) -> Vec<u8> {
+        let vtable_len = 4 + 2 * slots.iter().map(|(slot, _)| slot + 1).max().unwrap_or(0);
+        let mut buf = vec![0u8; 64];
+        buf[0..4].copy_from_slice(&24u32.to_le_bytes());
+        buf[4..6].copy_from_slice(&(vtable_len as u16).to_le_bytes());
+        buf[6..8].copy_from_slice(&40u16.to_le_bytes());
+        for (slot, offset) in slots {
+            buf[8 + 2 * slot..10 + 2 * slot].copy_from_slice(&offset.to_le_bytes());
+        }
+        buf[24..28].copy_from_slice(&(24i32 - 4).to_le_bytes());
+        for (at, bytes) in fields {
+            buf[24 + at..24 + at + bytes.len()].copy_from_slice(bytes);
+        }
+        buf
+    }
+
+    #[test]
+    fn lays_out_the_fixed_size_fields() {
+        let packet = sample();
+        let names: Vec<&str> = packet.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "mode",
+                "position_x",
+                "position_y",
+                "position_z",
+                "count",
+                "valid",
+                "time_us"
+            ]
+        );
+        assert_eq!(packet.data_len(), 1 + 3 * 4 + 2 + 1 + 8);
+        assert_eq!(packet.parameters[0].enumeration.as_deref(), Some("Mode"));
+        assert_eq!(packet.parameters[0].states[0], ("Idle".to_string(), -1));
+        assert_eq!(packet.parameters[5].states.len(), 2);
+    }
+
+    #[test]
+    fn packs_fields_big_endian() {
+        let position = [1.0f32, -2.0, 0.5].map(f32::to_le_bytes).concat();
+        let buf = buffer(
+            &[(0, 28), (1, 16), (6, 8)],
+            &[
+                (8, &0x0102_0304_0506_0708i64.to_le_bytes()),
+                (16, &position),
+                (28, &[0xff]),
+            ],
+        );
+        let expected = [
+            // mode: Idle.
+            vec![0xff],
+            // position, member by member.
+            vec![0x3f, 0x80, 0, 0, 0xc0, 0, 0, 0, 0x3f, 0, 0, 0],
+            // count and valid, left out, as their defaults.
+            vec![0, 7, 0],
+            vec![1, 2, 3, 4, 5, 6, 7, 8],
+        ]
+        .concat();
+        assert_eq!(sample().pack(&buf).unwrap(), expected);
+    }
+
+    #[test]
+    fn packs_fields_past_a_short_vtable_as_left_out() {
+        // Written against an older schema with only mode, in a vtable of one slot.
+        let buf = buffer(&[(0, 4)], &[(4, &[4])]);
+        let mut expected = vec![4];
+        // A struct left out reads as zeros.
+        expected.extend([0; 12]);
+        expected.extend([0, 7, 0]);
+        expected.extend([0; 8]);
+        assert_eq!(sample().pack(&buf).unwrap(), expected);
+    }
+
+    #[test]
+    fn refuses_a_truncated_buffer() {
+        assert!(sample().pack(&[24, 0]).is_err());
+        let buf = buffer(&[(6, 8)], &[]);
+        assert!(sample().pack(&buf[..36]).is_err());
+    }
+
+    #[test]
+    fn packs_a_reply() {
+        assert_eq!(
+            reply_data(911, 0x3fff, Outcome::NoReply),
+            [0x03, 0x8f, 0x3f, 0xff, 7]
+        );
+    }
+}
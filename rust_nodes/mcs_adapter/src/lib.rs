@@ -0,0 +1,15 @@
+// The layout of the CCSDS packets mcs_adapter exchanges with a mission control system, and
+// the XTCE and OpenC3 dictionaries describing it, which mcs_dictionary writes. Both read
+// the same APID and command configs, so the dictionary matches what the adapter sends.
+pub mod config;
+pub mod layout;
+pub mod openc3;
+pub mod schema;
+pub mod xtce;
+
+pub use config::Config;
+pub use layout::Dictionary;
+pub use schema::Schema;
+
+#[cfg(test)]
+mod golden;
@@ -0,0 +1,250 @@
+mod server;
+
+use ccsds_framer::SpacePacket;
+use ccsds_framer::packet::{self, MAX_DATA_LEN};
+use clap::Parser;
+use command::Key;
+use common::encoding;
+use mcs_adapter::layout::{TM_HEADER_LEN, TelemetryPacket};
+use mcs_adapter::{Config, Dictionary, Schema};
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId};
+use server::{Mcs, Server};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+#[derive(Parser)]
+#[command(
+    about = "Streams telemetry to Yamcs or OpenC3 as CCSDS packets of decoded parameters over TCP, and carries out the telecommands they send as node commands"
+)]
+struct Args {
+    /// APID of every key to stream, as ccsds_framer reads it (TOML or JSON). Defaults to
+    /// ccsds_framer's.
+    #[arg(long, value_name = "PATH")]
+    packets: Option<PathBuf>,
+    /// Telecommands and the node commands they are sent as (TOML or JSON). Every
+    /// telecommand is refused if not given.
+    #[arg(long, value_name = "PATH")]
+    commands: Option<PathBuf>,
+    /// Prefix the configured keys are taken from, e.g. ground for ccsds_depacketizer's;
+    /// the keys as configured if not given.
+    #[arg(long, value_name = "KEY_PREFIX")]
+    prefix: Option<String>,
+    /// Address to stream telemetry and take telecommands on, for OpenC3's TCP/IP client
+    /// interface or Yamcs's TcpTmDataLink.
+    #[arg(long, default_value = "127.0.0.1:10015")]
+    tm_addr: SocketAddr,
+    /// Address to only take telecommands on, for Yamcs's TcpTcDataLink.
+    #[arg(long)]
+    tc_addr: Option<SocketAddr>,
+    /// How long each attempt of a command waits for the node to be reachable and to
+    /// reply, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    command_timeout_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// The packets of one configured key.
+struct Stream {
+    packet: TelemetryPacket,
+    table: String,
+    sequence_count: u16,
+    // Whether a sample of another table, or one that could not be packed, was reported.
+    mismatch_reported: bool,
+    error_reported: bool,
+}
+
+struct McsAdapter {
+    telemetry: Vec<TelemetryPacket>,
+    commands: Option<Config>,
+    prefix: Option<String>,
+    tm_addr: SocketAddr,
+    tc_addr: Option<SocketAddr>,
+    command_timeout: Duration,
+    command_key: Option<Key>,
+    streams: HashMap<SubscriptionId, Stream>,
+    mcs: Option<Arc<Mcs>>,
+    servers: Vec<Server>,
+}
+
+// When the sample was published, by its Zenoh timestamp, else now.
+fn sample_time(sample: &Sample) -> Duration {
+    sample
+        .timestamp()
+        .map_or_else(SystemTime::now, |t| t.get_time().to_system_time())
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+impl McsAdapter {
+    fn on_sample(&mut self, id: SubscriptionId, sample: &Sample) {
+        let (Some(stream), Some(mcs)) = (self.streams.get_mut(&id), &self.mcs) else {
+            return;
+        };
+        if sample.kind() == SampleKind::Delete || !mcs.has_clients() {
+            return;
+        }
+        let key = sample.key_expr().as_str();
+        // Untagged payloads are taken to be of the configured table.
+        if let Some(table) =
+            encoding::flatbuffer_table(sample.encoding()).filter(|table| *table != stream.table)
+        {
+            if !stream.mismatch_reported {
+                warn!(
+                    "Dropping samples of {}: published as {}, not {}",
+                    key, table, stream.table
+                );
+                stream.mismatch_reported = true;
+            }
+            return;
+        }
+        let data = match stream.packet.pack(&sample.payload().to_bytes()) {
+            Ok(data) if data.len() <= MAX_DATA_LEN => data,
+            Ok(data) => {
+                if !stream.error_reported {
+                    warn!(
+                        "Dropping samples of {}: {} bytes do not fit in a packet of {}",
+                        key,
+                        data.len(),
+                        MAX_DATA_LEN
+                    );
+                    stream.error_reported = true;
+                }
+                return;
+            }
+            Err(e) => {
+                if !stream.error_reported {
+                    warn!("Dropping malformed samples of {}: {}", key, e);
+                    stream.error_reported = true;
+                }
+                return;
+            }
+        };
+        let mut bytes = Vec::with_capacity(TM_HEADER_LEN + data.len());
+        SpacePacket {
+            apid: stream.packet.apid,
+            sequence_count: stream.sequence_count,
+            time: sample_time(sample),
+            data: &data,
+        }
+        .encode(&mut bytes);
+        stream.sequence_count = packet::next(stream.sequence_count);
+        mcs.send(bytes);
+    }
+}
+
+impl Node for McsAdapter {
+    const NAME: &'static str = "mcs_adapter";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let mcs = Arc::new(Mcs::new(
+            ctx.session(),
+            self.commands.take().unwrap_or_default(),
+            self.command_timeout,
+            self.command_key.take(),
+        ));
+        self.servers
+            .push(Server::start(self.tm_addr, mcs.clone(), true).await?);
+        if let Some(addr) = self.tc_addr {
+            self.servers
+                .push(Server::start(addr, mcs.clone(), false).await?);
+        }
+        self.mcs = Some(mcs);
+        for packet in std::mem::take(&mut self.telemetry) {
+            let Some(source) = packet.source.clone() else {
+                continue;
+            };
+            let key = match &self.prefix {
+                Some(prefix) => format!("{}/{}", prefix.trim_end_matches('/'), source.key),
+                None => source.key,
+            };
+            let id = ctx.subscribe(&key).await?;
+            self.streams.insert(
+                id,
+                Stream {
+                    packet,
+                    table: source.table,
+                    sequence_count: 0,
+                    mismatch_reported: false,
+                    error_reported: false,
+                },
+            );
+        }
+        info!("Streaming {} keys", self.streams.len());
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(id, sample) => self.on_sample(id, &sample),
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        for server in self.servers.drain(..) {
+            server.stop();
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let packets = match args.packets.as_deref().map(ccsds_framer::Config::load) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            error!("Invalid CCSDS config: {}", e);
+            return ExitCode::FAILURE;
+        }
+        None => ccsds_framer::Config::default(),
+    };
+    let commands = match args.commands.as_deref().map(Config::load).transpose() {
+        Ok(config) => config.unwrap_or_default(),
+        Err(e) => {
+            error!("Invalid command config: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dictionary = match Dictionary::new(&Schema::sensors(), &packets, &commands) {
+        Ok(dictionary) => dictionary,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    for key in &dictionary.skipped {
+        warn!("Not streaming {}: not a FlatBuffers table", key);
+    }
+    // Commands are signed with the secret the adapter checks the commands it receives
+    // against, the one the other nodes check them against too.
+    let command_key = match args.node.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let node = McsAdapter {
+        telemetry: dictionary.telemetry,
+        commands: Some(commands),
+        prefix: args.prefix,
+        tm_addr: args.tm_addr,
+        tc_addr: args.tc_addr,
+        command_timeout: Duration::from_millis(args.command_timeout_ms),
+        command_key,
+        streams: HashMap::new(),
+        mcs: None,
+        servers: Vec::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
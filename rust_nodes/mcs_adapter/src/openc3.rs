@@ -0,0 +1,146 @@
+// The dictionary as OpenC3 COSMOS command and telemetry definitions for one target, as a
+// plugin's cmd_tlm file holds them. COSMOS has no inheritance between packets, so every
+// packet spells out its CCSDS header, identified by its APID.
+use crate::config::CommandConfig;
+use crate::layout::{Dictionary, Parameter, TelemetryPacket};
+use crate::schema::Scalar;
+use ccsds_framer::packet::{MAX_APID, SEQUENCE_COUNTS};
+use std::fmt::Write;
+
+// COSMOS descriptions are quoted; the schema's comments only ever hold plain text, but a
+// double quote would end one early.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "'"))
+}
+
+// The bit size and COSMOS data type of a scalar.
+fn data_type(scalar: Scalar) -> (usize, &'static str) {
+    let kind = if scalar.is_float() {
+        "FLOAT"
+    } else if scalar.is_signed() {
+        "INT"
+    } else {
+        "UINT"
+    };
+    (scalar.size() * 8, kind)
+}
+
+// The MIN_ and MAX_ constants COSMOS bounds a parameter of a scalar with.
+fn range(scalar: Scalar) -> (String, String) {
+    if scalar == Scalar::Bool {
+        return ("0".to_string(), "1".to_string());
+    }
+    let (bits, kind) = data_type(scalar);
+    (
+        format!("MIN_{}{}", kind, bits),
+        format!("MAX_{}{}", kind, bits),
+    )
+}
+
+fn states(out: &mut String, parameter: &Parameter) {
+    for (name, value) in &parameter.states {
+        let _ = writeln!(out, "    STATE {} {}", name.to_uppercase(), value);
+    }
+}
+
+pub fn generate(dictionary: &Dictionary, target: &str) -> String {
+    let mut out = String::new();
+    for packet in &dictionary.telemetry {
+        telemetry(&mut out, target, packet);
+        out.push('\n');
+    }
+    for command in &dictionary.commands {
+        command_packet(&mut out, target, command);
+        out.push('\n');
+    }
+    out.pop();
+    out
+}
+
+fn telemetry(out: &mut String, target: &str, packet: &TelemetryPacket) {
+    let _ = write!(
+        out,
+        "TELEMETRY {} {} BIG_ENDIAN",
+        target,
+        packet.name.to_uppercase()
+    );
+    if !packet.doc.is_empty() {
+        let _ = write!(out, " {}", quote(&packet.doc));
+    }
+    out.push('\n');
+    out.push_str("  APPEND_ITEM CCSDS_VERSION 3 UINT \"CCSDS packet version\"\n");
+    out.push_str("  APPEND_ITEM CCSDS_TYPE 1 UINT \"0 for telemetry\"\n");
+    out.push_str("  APPEND_ITEM CCSDS_SHF 1 UINT \"Secondary header flag\"\n");
+    let _ = writeln!(
+        out,
+        "  APPEND_ID_ITEM CCSDS_APID 11 UINT {} \"Application process id\"",
+        packet.apid
+    );
+    out.push_str("  APPEND_ITEM CCSDS_SEQFLAGS 2 UINT \"3 for unsegmented\"\n");
+    out.push_str("  APPEND_ITEM CCSDS_SEQCOUNT 14 UINT \"Sequence count per APID\"\n");
+    out.push_str("  APPEND_ITEM CCSDS_LENGTH 16 UINT \"Data field length less one\"\n");
+    out.push_str("  APPEND_ITEM CCSDS_SECONDS 32 UINT \"Publication time, Unix seconds\"\n");
+    out.push_str("  APPEND_ITEM CCSDS_SUBSECONDS 16 UINT \"Publication time, 2^-16 s\"\n");
+    for parameter in &packet.parameters {
+        let (bits, kind) = data_type(parameter.scalar);
+        let _ = writeln!(
+            out,
+            "  APPEND_ITEM {} {} {}",
+            parameter.name.to_uppercase(),
+            bits,
+            kind
+        );
+        states(out, parameter);
+    }
+}
+
+fn command_packet(out: &mut String, target: &str, command: &CommandConfig) {
+    let _ = writeln!(
+        out,
+        "COMMAND {} {} BIG_ENDIAN {}",
+        target,
+        command.name().to_uppercase(),
+        quote(&format!("Sent to {} as {}", command.node, command.command))
+    );
+    out.push_str("  APPEND_PARAMETER CCSDS_VERSION 3 UINT 0 0 0\n");
+    out.push_str("  APPEND_PARAMETER CCSDS_TYPE 1 UINT 1 1 1\n");
+    out.push_str("  APPEND_PARAMETER CCSDS_SHF 1 UINT 0 0 0\n");
+    let _ = writeln!(
+        out,
+        "  APPEND_ID_PARAMETER CCSDS_APID 11 UINT 0 {} {}",
+        MAX_APID, command.apid
+    );
+    out.push_str("  APPEND_PARAMETER CCSDS_SEQFLAGS 2 UINT 3 3 3\n");
+    let _ = writeln!(
+        out,
+        "  APPEND_PARAMETER CCSDS_SEQCOUNT 14 UINT 0 {} 0",
+        SEQUENCE_COUNTS - 1
+    );
+    let length = command.data_len() - 1;
+    let _ = writeln!(
+        out,
+        "  APPEND_PARAMETER CCSDS_LENGTH 16 UINT {} {} {}",
+        length, length, length
+    );
+    for argument in &command.arguments {
+        let (bits, kind) = data_type(argument.kind);
+        let (min, max) = range(argument.kind);
+        let default = if argument.kind.is_float() { "0.0" } else { "0" };
+        let _ = writeln!(
+            out,
+            "  APPEND_PARAMETER {} {} {} {} {} {}",
+            argument.name.to_uppercase(),
+            bits,
+            kind,
+            min,
+            max,
+            default
+        );
+        if argument.kind == Scalar::Bool {
+            out.push_str("    STATE FALSE 0\n    STATE TRUE 1\n");
+        }
+    }
+    if command.arguments.is_empty() {
+        out.push_str("  APPEND_PARAMETER SPARE 8 UINT 0 0 0\n");
+    }
+}
@@ -0,0 +1,287 @@
+// The tables, structs, and enums of a FlatBuffers schema, read from its text: as much of
+// the grammar as sensors.fbs uses. Fields take their ids in declaration order, which is
+// where a table's vtable gives their offsets.
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Named in configs as FlatBuffers names them, e.g. uint16 or float32.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Scalar {
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+}
+
+impl TryFrom<String> for Scalar {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, String> {
+        Scalar::parse(&name).ok_or_else(|| format!("{} is not a scalar type", name))
+    }
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Scalar::Bool,
+            "byte" | "int8" => Scalar::I8,
+            "ubyte" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "long" | "int64" => Scalar::I64,
+            "ulong" | "uint64" => Scalar::U64,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return None,
+        })
+    }
+
+    pub fn size(self) -> usize {
+        match self {
+            Scalar::Bool | Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::I64 | Scalar::U64 | Scalar::F64 => 8,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, Scalar::F32 | Scalar::F64)
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, Scalar::I8 | Scalar::I16 | Scalar::I32 | Scalar::I64)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Enum {
+    pub underlying: Scalar,
+    // Variant names and values, in declaration order.
+    pub variants: Vec<(String, i64)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Struct {
+    // Field names, types, and offsets into the struct, each aligned to its size.
+    pub fields: Vec<(String, Scalar, usize)>,
+}
+
+#[derive(Clone, Debug)]
+pub enum FieldType {
+    Scalar(Scalar),
+    // Stored as the enum's underlying scalar.
+    Enum(String),
+    // Stored inline in the table.
+    Struct(String),
+    // Strings, vectors, and tables, which are stored behind an offset.
+    Offset,
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub name: String,
+    pub kind: FieldType,
+    // The value of a scalar or enum field the table leaves out.
+    pub default: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Table {
+    // The comment above the table, joined into one line.
+    pub doc: String,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    pub namespace: String,
+    pub enums: HashMap<String, Enum>,
+    pub structs: HashMap<String, Struct>,
+    pub tables: HashMap<String, Table>,
+}
+
+impl Schema {
+    // The sensors schema this build was generated from.
+    pub fn sensors() -> Self {
+        Schema::parse(sensors_rs::SCHEMA).expect("sensors.fbs is a valid schema")
+    }
+
+    // A table by its name qualified with the namespace, e.g. sensors.FusedState, as
+    // encodings carry it.
+    pub fn table(&self, qualified: &str) -> Option<&Table> {
+        let name = qualified.strip_prefix(&self.namespace)?.strip_prefix('.')?;
+        self.tables.get(name)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut schema = Schema::default();
+        let mut doc: Vec<&str> = Vec::new();
+        let mut tables = Vec::new();
+        let mut lines = text.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let line = line.trim();
+            let at = |e: String| format!("line {}: {}", i + 1, e);
+            if let Some(comment) = line.strip_prefix("//") {
+                doc.push(comment.trim());
+                continue;
+            }
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                [] => doc.clear(),
+                ["namespace", name] => {
+                    schema.namespace = name.trim_end_matches(';').to_string();
+                }
+                [
+                    "root_type" | "include" | "attribute" | "file_identifier",
+                    ..,
+                ] => {}
+                ["enum", name, ":", underlying, "{"] => {
+                    let underlying = Scalar::parse(underlying)
+                        .ok_or_else(|| at(format!("no scalar {}", underlying)))?;
+                    let mut variants = Vec::new();
+                    let mut next = 0;
+                    for (i, line) in lines.by_ref() {
+                        let line = strip_comment(line);
+                        if line == "}" {
+                            break;
+                        }
+                        if line.is_empty() {
+                            continue;
+                        }
+                        let variant = line.trim_end_matches(',');
+                        let (variant, value) = match variant.split_once('=') {
+                            Some((variant, value)) => (
+                                variant.trim(),
+                                value
+                                    .trim()
+                                    .parse()
+                                    .map_err(|_| format!("line {}: bad value {}", i + 1, value))?,
+                            ),
+                            None => (variant, next),
+                        };
+                        variants.push((variant.to_string(), value));
+                        next = value + 1;
+                    }
+                    schema.enums.insert(
+                        name.to_string(),
+                        Enum {
+                            underlying,
+                            variants,
+                        },
+                    );
+                    doc.clear();
+                }
+                ["struct", name, "{"] => {
+                    let mut fields = Vec::new();
+                    let mut offset = 0usize;
+                    for (i, line) in lines.by_ref() {
+                        let line = strip_comment(line);
+                        if line == "}" {
+                            break;
+                        }
+                        if line.is_empty() {
+                            continue;
+                        }
+                        let (field_name, kind, _) =
+                            field(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+                        let scalar = Scalar::parse(kind)
+                            .ok_or_else(|| format!("line {}: {} is not a scalar", i + 1, kind))?;
+                        offset = offset.next_multiple_of(scalar.size());
+                        fields.push((field_name.to_string(), scalar, offset));
+                        offset += scalar.size();
+                    }
+                    schema.structs.insert(name.to_string(), Struct { fields });
+                    doc.clear();
+                }
+                ["table", name, "{"] => {
+                    let mut fields = Vec::new();
+                    for (i, line) in lines.by_ref() {
+                        let line = strip_comment(line);
+                        if line == "}" {
+                            break;
+                        }
+                        if line.is_empty() {
+                            continue;
+                        }
+                        let (field_name, kind, default) =
+                            field(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+                        let default = match default {
+                            None => 0.0,
+                            Some("true") => 1.0,
+                            Some("false") => 0.0,
+                            Some(value) => value
+                                .parse()
+                                .map_err(|_| format!("line {}: bad default {}", i + 1, value))?,
+                        };
+                        fields.push((field_name.to_string(), kind.to_string(), default));
+                    }
+                    // Fields are given their kinds once every enum and struct is known.
+                    tables.push((name.to_string(), doc.join(" "), fields));
+                    doc.clear();
+                }
+                _ => return Err(at(format!("cannot read '{}'", line))),
+            }
+        }
+        let names: Vec<String> = tables.iter().map(|(name, _, _)| name.clone()).collect();
+        for (table, doc, fields) in tables {
+            let fields = fields
+                .into_iter()
+                .map(|(name, kind, default)| {
+                    let kind = if let Some(scalar) = Scalar::parse(&kind) {
+                        FieldType::Scalar(scalar)
+                    } else if schema.enums.contains_key(&kind) {
+                        FieldType::Enum(kind)
+                    } else if schema.structs.contains_key(&kind) {
+                        FieldType::Struct(kind)
+                    } else if kind == "string" || kind.starts_with('[') || names.contains(&kind) {
+                        FieldType::Offset
+                    } else {
+                        return Err(format!("{}.{}: no type {}", table, name, kind));
+                    };
+                    Ok(Field {
+                        name,
+                        kind,
+                        default,
+                    })
+                })
+                .collect::<Result<_, String>>()?;
+            schema.tables.insert(table, Table { doc, fields });
+        }
+        Ok(schema)
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split("//").next().unwrap_or_default().trim()
+}
+
+// The name, type, and default of a field declaration, e.g. "valid: bool = true;".
+fn field(line: &str) -> Result<(&str, &str, Option<&str>), String> {
+    let line = line
+        .strip_suffix(';')
+        .ok_or_else(|| format!("'{}' does not end in ;", line))?;
+    // Attributes such as (deprecated) are not used by the schema.
+    if line.contains('(') {
+        return Err(format!("'{}' has attributes", line));
+    }
+    let (name, rest) = line
+        .split_once(':')
+        .ok_or_else(|| format!("'{}' is not a field", line))?;
+    let (kind, default) = match rest.split_once('=') {
+        Some((kind, default)) => (kind.trim(), Some(default.trim())),
+        None => (rest.trim(), None),
+    };
+    Ok((name.trim(), kind, default))
+}
@@ -0,0 +1,244 @@
+// The adapter's TCP servers. A mission control system connects to the telemetry server
+// and is streamed every packet from then on, and may send telecommands back on the same
+// connection, as OpenC3's TCP/IP client interface does. The telecommand server streams
+// nothing, for clients that only send, such as Yamcs's TcpTcDataLink.
+//
+// Telecommands are CCSDS Space Packets without a secondary header, sent back to back.
+// Each is carried out as the node command its APID is configured as, and answered with a
+// command_reply packet to every telemetry client once the node replies.
+use ccsds_framer::SpacePacket;
+use ccsds_framer::packet::{self, SEQUENCE_COUNTS};
+use command::{Key, NackReason, Reply, SendError, Senders};
+use mcs_adapter::Config;
+use mcs_adapter::layout::{Outcome, TC_HEADER_LEN, reply_data};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use zenoh::Session;
+
+// Packets held for each client before a slow one misses some.
+const BACKLOG: usize = 4096;
+
+// What the clients share: the packets to stream, and the senders of the commands they
+// submit.
+pub struct Mcs {
+    commands: Config,
+    packets: broadcast::Sender<Arc<Vec<u8>>>,
+    senders: Senders,
+    reply_sequence: Mutex<u16>,
+}
+
+impl Mcs {
+    pub fn new(session: &Session, commands: Config, timeout: Duration, key: Option<Key>) -> Self {
+        Mcs {
+            commands,
+            packets: broadcast::channel(BACKLOG).0,
+            senders: Senders::new(session, timeout, key),
+            reply_sequence: Mutex::new(0),
+        }
+    }
+
+    // Whether any client is connected to be streamed packets.
+    pub fn has_clients(&self) -> bool {
+        self.packets.receiver_count() > 0
+    }
+
+    pub fn send(&self, packet: Vec<u8>) {
+        // Sending fails only with no client connected.
+        let _ = self.packets.send(Arc::new(packet));
+    }
+
+    async fn command(&self, apid: u16, sequence: u16, data: &[u8]) -> Outcome {
+        let Some(config) = self.commands.apid(apid) else {
+            debug!("Refusing the telecommand on APID {}: not configured", apid);
+            return Outcome::UnknownCommand;
+        };
+        let name = config.name();
+        let args = match config.args(data) {
+            Ok(args) => args,
+            Err(e) => {
+                warn!("{} {}: {}", name, sequence, e);
+                return Outcome::Malformed;
+            }
+        };
+        let reply = self
+            .senders
+            .send(&config.node, &config.command, &args)
+            .await;
+        match &reply {
+            Ok(Reply {
+                result: Ok(message),
+                ..
+            }) => info!(
+                "{} {}: {}/{}: {}",
+                name, sequence, config.node, config.command, message
+            ),
+            Ok(Reply { result: Err(e), .. }) => warn!(
+                "{} {}: {}/{} refused: {}",
+                name, sequence, config.node, config.command, e
+            ),
+            Err(e) => warn!(
+                "{} {}: {}/{} failed: {}",
+                name, sequence, config.node, config.command, e
+            ),
+        }
+        outcome(&reply)
+    }
+
+    fn reply(&self, apid: u16, sequence: u16, outcome: Outcome) {
+        let Some(reply_apid) = self.commands.reply_apid else {
+            return;
+        };
+        let sequence_count = {
+            let mut next = self.reply_sequence.lock().unwrap();
+            let count = *next;
+            *next = packet::next(count);
+            count
+        };
+        let mut bytes = Vec::new();
+        SpacePacket {
+            apid: reply_apid,
+            sequence_count,
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+            data: &reply_data(apid, sequence, outcome),
+        }
+        .encode(&mut bytes);
+        self.send(bytes);
+    }
+}
+
+fn outcome(reply: &Result<Reply, SendError>) -> Outcome {
+    match reply {
+        Ok(Reply { result: Ok(_), .. }) => Outcome::Accepted,
+        Ok(Reply {
+            result: Err(nack), ..
+        }) => match nack.reason {
+            NackReason::UnknownCommand => Outcome::UnknownCommand,
+            NackReason::Malformed => Outcome::Malformed,
+            NackReason::InvalidArgs => Outcome::InvalidArgs,
+            NackReason::Rejected => Outcome::Rejected,
+            NackReason::Failed => Outcome::Failed,
+            NackReason::Unauthorized => Outcome::Unauthorized,
+        },
+        Err(_) => Outcome::NoReply,
+    }
+}
+
+pub struct Server {
+    task: JoinHandle<()>,
+}
+
+impl Server {
+    // Serves clients on addr, streaming them telemetry if telemetry is set.
+    pub async fn start(addr: SocketAddr, mcs: Arc<Mcs>, telemetry: bool) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        info!(
+            "Taking {} on {}",
+            if telemetry {
+                "telemetry clients"
+            } else {
+                "telecommand clients"
+            },
+            listener.local_addr()?
+        );
+        let task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((socket, peer)) => {
+                        tokio::spawn(serve(socket, peer, mcs.clone(), telemetry));
+                    }
+                    Err(e) => warn!("Cannot accept a client: {}", e),
+                }
+            }
+        });
+        Ok(Server { task })
+    }
+
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(socket: TcpStream, peer: SocketAddr, mcs: Arc<Mcs>, telemetry: bool) {
+    info!("Client {} connected", peer);
+    // Subscribed before anything is read, so no packet sent from here on is missed.
+    let packets = telemetry.then(|| mcs.packets.subscribe());
+    let (reader, writer) = socket.into_split();
+    let result = tokio::select! {
+        result = read_telecommands(reader, &mcs) => result,
+        result = write_telemetry(writer, packets, peer) => result,
+    };
+    match result {
+        Ok(()) => info!("Client {} disconnected", peer),
+        Err(e) => warn!("Client {} disconnected: {}", peer, e),
+    }
+}
+
+// Reads telecommands until the client disconnects, carrying each out while reading on.
+async fn read_telecommands(mut reader: OwnedReadHalf, mcs: &Arc<Mcs>) -> Result<(), String> {
+    let mut header = [0; TC_HEADER_LEN];
+    loop {
+        match reader.read_exact(&mut header).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+        let identification = u16::from_be_bytes([header[0], header[1]]);
+        let sequence = u16::from_be_bytes([header[2], header[3]]) & (SEQUENCE_COUNTS - 1);
+        let length = u16::from_be_bytes([header[4], header[5]]) as usize + 1;
+        // Past a header that is not a telecommand's, the stream cannot be followed.
+        if identification >> 13 != 0 || identification & 1 << 12 == 0 {
+            return Err(format!(
+                "{:02x?} is not the header of a telecommand packet",
+                header
+            ));
+        }
+        let mut data = vec![0; length];
+        reader
+            .read_exact(&mut data)
+            .await
+            .map_err(|e| e.to_string())?;
+        let apid = identification & 0x7FF;
+        let mcs = mcs.clone();
+        tokio::spawn(async move {
+            let outcome = if identification & 1 << 11 != 0 {
+                warn!(
+                    "Refusing the telecommand on APID {}: it has a secondary header",
+                    apid
+                );
+                Outcome::Malformed
+            } else {
+                mcs.command(apid, sequence, &data).await
+            };
+            mcs.reply(apid, sequence, outcome);
+        });
+    }
+}
+
+// Streams packets to the client until it disconnects; never returns without packets.
+async fn write_telemetry(
+    mut writer: OwnedWriteHalf,
+    packets: Option<broadcast::Receiver<Arc<Vec<u8>>>>,
+    peer: SocketAddr,
+) -> Result<(), String> {
+    let Some(mut packets) = packets else {
+        return std::future::pending().await;
+    };
+    loop {
+        match packets.recv().await {
+            Ok(packet) => writer.write_all(&packet).await.map_err(|e| e.to_string())?,
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!("Client {} is too slow: missed {} packets", peer, missed);
+            }
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        }
+    }
+}
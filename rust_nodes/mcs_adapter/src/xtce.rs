@@ -0,0 +1,296 @@
+// The dictionary as an XTCE 1.2 space system, as Yamcs loads it. The root space system
+// holds the CCSDS headers, the types, and the telecommands; each telemetry packet is a
+// space system of its own under it, so packets of the same table, e.g. devices_imu0 and
+// devices_imu1, name their parameters alike.
+use crate::layout::{Dictionary, Parameter, TelemetryPacket};
+use crate::schema::Scalar;
+use ccsds_framer::packet::MAX_APID;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const SCALARS: [Scalar; 11] = [
+    Scalar::Bool,
+    Scalar::I8,
+    Scalar::U8,
+    Scalar::I16,
+    Scalar::U16,
+    Scalar::I32,
+    Scalar::U32,
+    Scalar::I64,
+    Scalar::U64,
+    Scalar::F32,
+    Scalar::F64,
+];
+
+// The telemetry headers, as parameter names and sizes in bits.
+const TM_HEADER: [(&str, usize); 9] = [
+    ("ccsds_version", 3),
+    ("ccsds_type", 1),
+    ("ccsds_shf", 1),
+    ("ccsds_apid", 11),
+    ("ccsds_seqflags", 2),
+    ("ccsds_seqcount", 14),
+    ("ccsds_length", 16),
+    ("ccsds_seconds", 32),
+    ("ccsds_subseconds", 16),
+];
+
+// Sizes of the header fields no scalar type has.
+const BIT_FIELDS: [usize; 5] = [1, 2, 3, 11, 14];
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn type_name(scalar: Scalar) -> &'static str {
+    match scalar {
+        Scalar::Bool => "bool",
+        Scalar::I8 => "int8",
+        Scalar::U8 => "uint8",
+        Scalar::I16 => "int16",
+        Scalar::U16 => "uint16",
+        Scalar::I32 => "int32",
+        Scalar::U32 => "uint32",
+        Scalar::I64 => "int64",
+        Scalar::U64 => "uint64",
+        Scalar::F32 => "float32",
+        Scalar::F64 => "float64",
+    }
+}
+
+fn integer_encoding(bits: usize, signed: bool) -> String {
+    format!(
+        r#"<IntegerDataEncoding sizeInBits="{}" encoding="{}"/>"#,
+        bits,
+        if signed { "twosComplement" } else { "unsigned" }
+    )
+}
+
+// The parameter or argument type of a scalar, kind being "Parameter" or "Argument".
+fn scalar_type(out: &mut String, kind: &str, scalar: Scalar) {
+    let name = type_name(scalar);
+    let bits = scalar.size() * 8;
+    let encoding = integer_encoding(bits, scalar.is_signed());
+    let _ = if scalar == Scalar::Bool {
+        writeln!(
+            out,
+            r#"      <Boolean{kind}Type name="{name}">{encoding}</Boolean{kind}Type>"#
+        )
+    } else if scalar.is_float() {
+        writeln!(
+            out,
+            r#"      <Float{kind}Type name="{name}" sizeInBits="{bits}"><FloatDataEncoding sizeInBits="{bits}" encoding="IEEE754_1985"/></Float{kind}Type>"#
+        )
+    } else {
+        writeln!(
+            out,
+            r#"      <Integer{kind}Type name="{name}" signed="{}" sizeInBits="{bits}">{encoding}</Integer{kind}Type>"#,
+            scalar.is_signed()
+        )
+    };
+}
+
+pub fn generate(dictionary: &Dictionary, root: &str) -> String {
+    // Enums by name, with a parameter of each for its states.
+    let enums: BTreeMap<&str, &Parameter> = dictionary
+        .telemetry
+        .iter()
+        .flat_map(|packet| &packet.parameters)
+        .filter_map(|parameter| Some((parameter.enumeration.as_deref()?, parameter)))
+        .collect();
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        r#"<SpaceSystem xmlns="http://www.omg.org/spec/XTCE/20180204" name="{}">"#,
+        root
+    );
+    out.push_str("  <TelemetryMetaData>\n    <ParameterTypeSet>\n");
+    for scalar in SCALARS {
+        scalar_type(&mut out, "Parameter", scalar);
+    }
+    for bits in BIT_FIELDS {
+        let _ = writeln!(
+            out,
+            r#"      <IntegerParameterType name="uint{bits}" signed="false" sizeInBits="{bits}">{}</IntegerParameterType>"#,
+            integer_encoding(bits, false)
+        );
+    }
+    for (name, parameter) in &enums {
+        let _ = writeln!(
+            out,
+            r#"      <EnumeratedParameterType name="{}">{}"#,
+            name,
+            integer_encoding(parameter.scalar.size() * 8, parameter.scalar.is_signed())
+        );
+        out.push_str("        <EnumerationList>\n");
+        for (label, value) in &parameter.states {
+            let _ = writeln!(
+                out,
+                r#"          <Enumeration value="{}" label="{}"/>"#,
+                value,
+                escape(label)
+            );
+        }
+        out.push_str("        </EnumerationList>\n      </EnumeratedParameterType>\n");
+    }
+    out.push_str("    </ParameterTypeSet>\n    <ParameterSet>\n");
+    for (name, bits) in TM_HEADER {
+        let _ = writeln!(
+            out,
+            r#"      <Parameter name="{}" parameterTypeRef="uint{}"/>"#,
+            name, bits
+        );
+    }
+    out.push_str("    </ParameterSet>\n    <ContainerSet>\n");
+    out.push_str("      <SequenceContainer name=\"ccsds_tm\" abstract=\"true\">\n");
+    out.push_str("        <EntryList>\n");
+    for (name, _) in TM_HEADER {
+        let _ = writeln!(
+            out,
+            r#"          <ParameterRefEntry parameterRef="{}"/>"#,
+            name
+        );
+    }
+    out.push_str("        </EntryList>\n      </SequenceContainer>\n");
+    out.push_str("    </ContainerSet>\n  </TelemetryMetaData>\n");
+    commands(&mut out, dictionary);
+    for packet in &dictionary.telemetry {
+        packet_system(&mut out, root, packet);
+    }
+    out.push_str("</SpaceSystem>\n");
+    out
+}
+
+// A telemetry packet's space system, referring to the root's types and header by path.
+fn packet_system(out: &mut String, root: &str, packet: &TelemetryPacket) {
+    let _ = writeln!(out, r#"  <SpaceSystem name="{}">"#, packet.name);
+    if !packet.doc.is_empty() {
+        let _ = writeln!(
+            out,
+            "    <LongDescription>{}</LongDescription>",
+            escape(&packet.doc)
+        );
+    }
+    out.push_str("    <TelemetryMetaData>\n      <ParameterSet>\n");
+    for parameter in &packet.parameters {
+        let kind = match &parameter.enumeration {
+            Some(name) => name.as_str(),
+            None => type_name(parameter.scalar),
+        };
+        let _ = writeln!(
+            out,
+            r#"        <Parameter name="{}" parameterTypeRef="/{}/{}"/>"#,
+            parameter.name, root, kind
+        );
+    }
+    out.push_str("      </ParameterSet>\n      <ContainerSet>\n");
+    let _ = writeln!(out, r#"        <SequenceContainer name="{}">"#, packet.name);
+    out.push_str("          <EntryList>\n");
+    for parameter in &packet.parameters {
+        let _ = writeln!(
+            out,
+            r#"            <ParameterRefEntry parameterRef="{}"/>"#,
+            parameter.name
+        );
+    }
+    out.push_str("          </EntryList>\n");
+    let _ = writeln!(
+        out,
+        r#"          <BaseContainer containerRef="/{}/ccsds_tm">"#,
+        root
+    );
+    out.push_str("            <RestrictionCriteria>\n");
+    let _ = writeln!(
+        out,
+        r#"              <Comparison parameterRef="/{}/ccsds_apid" value="{}"/>"#,
+        root, packet.apid
+    );
+    out.push_str("            </RestrictionCriteria>\n          </BaseContainer>\n");
+    out.push_str("        </SequenceContainer>\n      </ContainerSet>\n");
+    out.push_str("    </TelemetryMetaData>\n  </SpaceSystem>\n");
+}
+
+// The telecommands, each deriving from an abstract one laying out the primary header.
+fn commands(out: &mut String, dictionary: &Dictionary) {
+    out.push_str("  <CommandMetaData>\n    <ArgumentTypeSet>\n");
+    for scalar in SCALARS {
+        scalar_type(out, "Argument", scalar);
+    }
+    let _ = writeln!(
+        out,
+        r#"      <IntegerArgumentType name="apid" signed="false" sizeInBits="11">{}<ValidRangeSet><ValidRange minInclusive="0" maxInclusive="{}"/></ValidRangeSet></IntegerArgumentType>"#,
+        integer_encoding(11, false),
+        MAX_APID
+    );
+    out.push_str("    </ArgumentTypeSet>\n    <MetaCommandSet>\n");
+    out.push_str("      <MetaCommand name=\"ccsds_tc\" abstract=\"true\">\n");
+    out.push_str("        <ArgumentList>\n");
+    out.push_str("          <Argument name=\"ccsds_apid\" argumentTypeRef=\"apid\"/>\n");
+    out.push_str("          <Argument name=\"ccsds_length\" argumentTypeRef=\"uint16\"/>\n");
+    out.push_str("        </ArgumentList>\n");
+    out.push_str("        <CommandContainer name=\"ccsds_tc\">\n          <EntryList>\n");
+    // Version 0, a telecommand (type 1) without a secondary header, then the APID; then
+    // unsegmented with sequence count 0, and the length.
+    out.push_str("            <FixedValueEntry name=\"ccsds_version_type_shf\" binaryValue=\"02\" sizeInBits=\"5\"/>\n");
+    out.push_str("            <ArgumentRefEntry argumentRef=\"ccsds_apid\"/>\n");
+    out.push_str("            <FixedValueEntry name=\"ccsds_sequence\" binaryValue=\"C000\" sizeInBits=\"16\"/>\n");
+    out.push_str("            <ArgumentRefEntry argumentRef=\"ccsds_length\"/>\n");
+    out.push_str("          </EntryList>\n        </CommandContainer>\n      </MetaCommand>\n");
+    for command in &dictionary.commands {
+        let name = command.name();
+        let _ = writeln!(out, r#"      <MetaCommand name="{}">"#, name);
+        let _ = writeln!(
+            out,
+            "        <LongDescription>Sent to {} as {}.</LongDescription>",
+            escape(&command.node),
+            escape(&command.command)
+        );
+        out.push_str("        <BaseMetaCommand metaCommandRef=\"ccsds_tc\">\n");
+        out.push_str("          <ArgumentAssignmentList>\n");
+        let _ = writeln!(
+            out,
+            r#"            <ArgumentAssignment argumentName="ccsds_apid" argumentValue="{}"/>"#,
+            command.apid
+        );
+        // The length of the data field, less one.
+        let _ = writeln!(
+            out,
+            r#"            <ArgumentAssignment argumentName="ccsds_length" argumentValue="{}"/>"#,
+            command.data_len() - 1
+        );
+        out.push_str("          </ArgumentAssignmentList>\n        </BaseMetaCommand>\n");
+        if !command.arguments.is_empty() {
+            out.push_str("        <ArgumentList>\n");
+            for argument in &command.arguments {
+                let _ = writeln!(
+                    out,
+                    r#"          <Argument name="{}" argumentTypeRef="{}"/>"#,
+                    argument.name,
+                    type_name(argument.kind)
+                );
+            }
+            out.push_str("        </ArgumentList>\n");
+        }
+        let _ = writeln!(out, r#"        <CommandContainer name="{}">"#, name);
+        out.push_str("          <EntryList>\n");
+        for argument in &command.arguments {
+            let _ = writeln!(
+                out,
+                r#"            <ArgumentRefEntry argumentRef="{}"/>"#,
+                argument.name
+            );
+        }
+        if command.arguments.is_empty() {
+            out.push_str("            <FixedValueEntry name=\"spare\" binaryValue=\"00\" sizeInBits=\"8\"/>\n");
+        }
+        out.push_str("          </EntryList>\n");
+        out.push_str("          <BaseContainer containerRef=\"ccsds_tc\"/>\n");
+        out.push_str("        </CommandContainer>\n      </MetaCommand>\n");
+    }
+    out.push_str("    </MetaCommandSet>\n  </CommandMetaData>\n");
+}
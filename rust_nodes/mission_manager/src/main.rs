@@ -0,0 +1,239 @@
+mod phase;
+
+use clap::Parser;
+use command::NackReason;
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use keyspace::keys;
+use node_framework::{CommandId, CommandRequest, Context, Event, Node, NodeArgs};
+use phase::{Machine, Observation, Phase, Thresholds};
+use sensors_rs::sensors;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+use zenoh::sample::Sample;
+
+#[derive(Parser)]
+#[command(about = "Tracks the flight phase from the fused state and publishes it on state/phase")]
+struct Args {
+    /// PAD -> BOOST once climbing faster than this, in m/s...
+    #[arg(long, default_value_t = 10.0)]
+    launch_velocity: f32,
+    /// ...and at least this high above the pad, in meters, if the altitude is valid.
+    #[arg(long, default_value_t = 5.0)]
+    launch_altitude: f32,
+    /// BOOST -> COAST once the vertical acceleration falls below this, in m/s².
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    burnout_acceleration: f32,
+    /// APOGEE -> DESCENT once sinking faster than this, in m/s.
+    #[arg(long, default_value_t = 2.0)]
+    descent_velocity: f32,
+    /// DESCENT -> LANDED once the vertical speed stays below this, in m/s...
+    #[arg(long, default_value_t = 1.0)]
+    landed_velocity: f32,
+    /// ...for this long, in seconds.
+    #[arg(long, default_value_t = 3.0)]
+    landed_hold_s: f64,
+    /// How long the other conditions must hold before their transition, in milliseconds.
+    #[arg(long, default_value_t = 200)]
+    hold_ms: u64,
+    /// Period of phase publications between transitions, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    publish_period_ms: u64,
+    /// How long set_phase stays armed after an arm, in seconds.
+    #[arg(long, default_value_t = 10.0)]
+    arm_window_s: f64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+fn flight_phase(phase: Phase) -> sensors::FlightPhase {
+    match phase {
+        Phase::Pad => sensors::FlightPhase::Pad,
+        Phase::Boost => sensors::FlightPhase::Boost,
+        Phase::Coast => sensors::FlightPhase::Coast,
+        Phase::Apogee => sensors::FlightPhase::Apogee,
+        Phase::Descent => sensors::FlightPhase::Descent,
+        Phase::Landed => sensors::FlightPhase::Landed,
+    }
+}
+
+struct MissionManager {
+    machine: Machine,
+    publish_period: Duration,
+    arm_window: Duration,
+    reset_command: Option<CommandId>,
+    publisher: Option<Publisher<'static>>,
+}
+
+impl MissionManager {
+    fn on_fused(&mut self, sample: &Sample) -> zenoh::Result<bool> {
+        let payload = sample.payload().to_bytes();
+        let state = flatbuffers::root::<sensors::FusedState>(&payload)
+            .map_err(|e| format!("malformed fused state: {}", e))?;
+        let observation = Observation {
+            time: state.timestamp_us() as f64 / 1e6,
+            altitude: state.altitude_valid().then(|| state.altitude()),
+            vertical_velocity: state
+                .velocity()
+                .filter(|_| state.velocity_valid())
+                .map(|velocity| velocity.z()),
+        };
+        let previous = self.machine.phase;
+        if !self.machine.update(&observation) {
+            return Ok(false);
+        }
+        info!(
+            "Phase {} -> {}: {}",
+            previous, self.machine.phase, self.machine.reason
+        );
+        Ok(true)
+    }
+
+    // Returns to PAD after a flight. Refused in flight, where set_phase overrides.
+    async fn reset(&mut self, request: CommandRequest) -> zenoh::Result<bool> {
+        let phase = self.machine.phase;
+        if !matches!(phase, Phase::Pad | Phase::Landed) {
+            request
+                .nack(
+                    NackReason::Rejected,
+                    format!("in flight ({}), use set_phase to override", phase),
+                )
+                .await?;
+            return Ok(false);
+        }
+        self.machine.enter(Phase::Pad, "reset commanded");
+        info!("Phase {} -> {}: reset commanded", phase, Phase::Pad);
+        request.ack("phase reset to PAD").await?;
+        Ok(true)
+    }
+
+    // Forces a phase, e.g. when the fused state misses a transition.
+    async fn set_phase(&mut self, request: CommandRequest) -> zenoh::Result<bool> {
+        let phase: Phase = match request.command.parse("phase") {
+            Ok(phase) => phase,
+            Err(nack) => {
+                request.reply(Err(nack)).await?;
+                return Ok(false);
+            }
+        };
+        let previous = self.machine.phase;
+        let reason = format!("set by {}", request.command.sender);
+        info!("Phase {} -> {}: {}", previous, phase, reason);
+        self.machine.enter(phase, reason);
+        request.ack(format!("phase set to {}", phase)).await?;
+        Ok(true)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let timestamp_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_micros() as u64)
+            .unwrap_or(0);
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+        let reason = builder.create_string(&self.machine.reason);
+        let state = sensors::PhaseState::create(
+            &mut builder,
+            &sensors::PhaseStateArgs {
+                timestamp_us,
+                phase: flight_phase(self.machine.phase),
+                previous: flight_phase(self.machine.previous),
+                since_s: self.machine.since_s(),
+                reason: Some(reason),
+                max_altitude: self.machine.max_altitude,
+            },
+        );
+        builder.finish(state, None);
+        builder.finished_data().to_vec()
+    }
+
+    async fn publish(&self) -> zenoh::Result<()> {
+        match &self.publisher {
+            Some(publisher) => publisher.put(self.encode()).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Node for MissionManager {
+    const NAME: &'static str = "mission_manager";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.subscribe(keys::fused_state()).await?;
+        ctx.add_timer(self.publish_period);
+        self.reset_command = Some(ctx.register_command("reset").await?);
+        ctx.register_armed_command("set_phase", self.arm_window)
+            .await?;
+        // Nodes enable pyros and change logging rates on phase changes, so none are
+        // dropped under congestion.
+        let publisher = ctx
+            .session()
+            .declare_publisher(keys::phase())
+            .encoding(encoding::flatbuffer("sensors.PhaseState"))
+            .congestion_control(CongestionControl::Block)
+            .qos(ctx.qos(), keys::phase())
+            .await?;
+        self.publisher = Some(publisher);
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let changed = match event {
+            Event::Timer(_) => true,
+            Event::Sample(_, sample) => self.on_fused(&sample)?,
+            Event::Command(request) if Some(request.id) == self.reset_command => {
+                self.reset(request).await?
+            }
+            Event::Command(request) => self.set_phase(request).await?,
+            Event::Query(..) | Event::Param(_) => false,
+        };
+        if changed {
+            self.publish().await?;
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        match self.publisher.take() {
+            Some(publisher) => publisher.undeclare().await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if args.publish_period_ms == 0 {
+        error!("--publish-period-ms must be positive");
+        return ExitCode::FAILURE;
+    }
+    if args.landed_hold_s.is_nan() || args.landed_hold_s < 0.0 {
+        error!("--landed-hold-s must not be negative");
+        return ExitCode::FAILURE;
+    }
+    if !args.arm_window_s.is_finite() || args.arm_window_s <= 0.0 {
+        error!("--arm-window-s must be positive");
+        return ExitCode::FAILURE;
+    }
+
+    let node = MissionManager {
+        machine: Machine::new(Thresholds {
+            launch_velocity: args.launch_velocity,
+            launch_altitude: args.launch_altitude,
+            burnout_acceleration: args.burnout_acceleration,
+            descent_velocity: args.descent_velocity,
+            landed_velocity: args.landed_velocity,
+            landed_hold_s: args.landed_hold_s,
+            hold_s: args.hold_ms as f64 / 1e3,
+        }),
+        publish_period: Duration::from_millis(args.publish_period_ms),
+        arm_window: Duration::from_secs_f64(args.arm_window_s),
+        reset_command: None,
+        publisher: None,
+    };
+    node_framework::run(node, &args.node).await
+}
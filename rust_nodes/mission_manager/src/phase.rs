@@ -0,0 +1,243 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Pad,
+    Boost,
+    Coast,
+    Apogee,
+    Descent,
+    Landed,
+}
+
+const PHASES: [Phase; 6] = [
+    Phase::Pad,
+    Phase::Boost,
+    Phase::Coast,
+    Phase::Apogee,
+    Phase::Descent,
+    Phase::Landed,
+];
+
+impl Phase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Pad => "PAD",
+            Phase::Boost => "BOOST",
+            Phase::Coast => "COAST",
+            Phase::Apogee => "APOGEE",
+            Phase::Descent => "DESCENT",
+            Phase::Landed => "LANDED",
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PHASES
+            .into_iter()
+            .find(|phase| phase.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = PHASES.iter().map(|phase| phase.name()).collect();
+                format!("expected one of {}", names.join(", "))
+            })
+    }
+}
+
+// Thresholds on the fused state that move the flight from one phase to the next.
+// Velocities are vertical, in m/s, positive up.
+pub struct Thresholds {
+    // PAD -> BOOST: climbing faster than this...
+    pub launch_velocity: f32,
+    // ...and this many meters above the pad, if the altitude is valid.
+    pub launch_altitude: f32,
+    // BOOST -> COAST: vertical acceleration, in m/s², falls below this as thrust ends.
+    pub burnout_acceleration: f32,
+    // APOGEE -> DESCENT: sinking faster than this.
+    pub descent_velocity: f32,
+    // DESCENT -> LANDED: vertical speed stays below this for landed_hold_s.
+    pub landed_velocity: f32,
+    pub landed_hold_s: f64,
+    // How long every other condition must hold before its transition is taken, in
+    // seconds, so a single noisy estimate does not move the flight on.
+    pub hold_s: f64,
+}
+
+// One fused state as the state machine sees it. Time is in seconds, from the state's
+// timestamp.
+pub struct Observation {
+    pub time: f64,
+    pub altitude: Option<f32>,
+    pub vertical_velocity: Option<f32>,
+}
+
+// How quickly the vertical acceleration derived from successive velocities follows them.
+// Differentiating raw estimates is noisy; this averages over about ten states.
+const ACCELERATION_SMOOTHING: f32 = 0.1;
+
+// Pad altitude tracking: how quickly it follows the altitude while on PAD.
+const PAD_SMOOTHING: f32 = 0.05;
+
+pub struct Machine {
+    thresholds: Thresholds,
+    pub phase: Phase,
+    pub previous: Phase,
+    // Fused state time the current phase was entered at, once any state was seen.
+    entered: Option<f64>,
+    pub reason: String,
+    // Time of the latest state seen.
+    pub time: Option<f64>,
+    // Altitude of the pad, averaged while on PAD.
+    pad_altitude: Option<f32>,
+    pub max_altitude: f32,
+    // Previous vertical velocity and the time of its state, for the acceleration.
+    last_velocity: Option<(f64, f32)>,
+    acceleration: Option<f32>,
+    // Since when the condition for leaving the current phase has held.
+    pending_since: Option<f64>,
+}
+
+impl Machine {
+    pub fn new(thresholds: Thresholds) -> Self {
+        Machine {
+            thresholds,
+            phase: Phase::Pad,
+            previous: Phase::Pad,
+            entered: None,
+            reason: "started".to_string(),
+            time: None,
+            pad_altitude: None,
+            max_altitude: 0.0,
+            last_velocity: None,
+            acceleration: None,
+            pending_since: None,
+        }
+    }
+
+    // How long the current phase has lasted, by fused state time.
+    pub fn since_s(&self) -> f64 {
+        match (self.time, self.entered) {
+            (Some(time), Some(entered)) => (time - entered).max(0.0),
+            _ => 0.0,
+        }
+    }
+
+    // Moves to a phase regardless of the fused state, e.g. on command.
+    pub fn enter(&mut self, phase: Phase, reason: impl Into<String>) {
+        if phase == Phase::Pad {
+            self.pad_altitude = None;
+            self.max_altitude = 0.0;
+        }
+        self.previous = self.phase;
+        self.phase = phase;
+        self.entered = self.time;
+        self.reason = reason.into();
+        self.pending_since = None;
+    }
+
+    // Advances the state machine with a fused state. Returns whether the phase changed.
+    pub fn update(&mut self, observation: &Observation) -> bool {
+        self.track(observation);
+        let Some(reason) = self.condition(observation) else {
+            self.pending_since = None;
+            return false;
+        };
+        let hold = match self.phase {
+            Phase::Descent => self.thresholds.landed_hold_s,
+            _ => self.thresholds.hold_s,
+        };
+        let since = *self.pending_since.get_or_insert(observation.time);
+        if observation.time - since < hold {
+            return false;
+        }
+        let next = match self.phase {
+            Phase::Pad => Phase::Boost,
+            Phase::Boost => Phase::Coast,
+            Phase::Coast => Phase::Apogee,
+            Phase::Apogee => Phase::Descent,
+            Phase::Descent | Phase::Landed => Phase::Landed,
+        };
+        self.enter(next, reason);
+        true
+    }
+
+    fn track(&mut self, observation: &Observation) {
+        if self.entered.is_none() {
+            self.entered = Some(observation.time);
+        }
+        self.time = Some(observation.time);
+        if let Some(altitude) = observation.altitude {
+            if self.phase == Phase::Pad {
+                let pad = self.pad_altitude.get_or_insert(altitude);
+                *pad += PAD_SMOOTHING * (altitude - *pad);
+            } else {
+                self.max_altitude = self.max_altitude.max(altitude);
+            }
+        }
+        match (observation.vertical_velocity, self.last_velocity) {
+            (Some(velocity), Some((time, last))) if observation.time > time => {
+                let acceleration = (velocity - last) / (observation.time - time) as f32;
+                let smoothed = self.acceleration.get_or_insert(acceleration);
+                *smoothed += ACCELERATION_SMOOTHING * (acceleration - *smoothed);
+            }
+            _ => {}
+        }
+        if let Some(velocity) = observation.vertical_velocity {
+            self.last_velocity = Some((observation.time, velocity));
+        }
+    }
+
+    // Why the current phase should be left, if it should.
+    fn condition(&self, observation: &Observation) -> Option<String> {
+        let t = &self.thresholds;
+        let velocity = observation.vertical_velocity?;
+        match self.phase {
+            Phase::Pad => {
+                let height = match (observation.altitude, self.pad_altitude) {
+                    (Some(altitude), Some(pad)) => Some(altitude - pad),
+                    _ => None,
+                };
+                if velocity <= t.launch_velocity || height.is_some_and(|h| h <= t.launch_altitude) {
+                    return None;
+                }
+                Some(match height {
+                    Some(height) => format!(
+                        "climbing at {:.1} m/s, {:.1} m above the pad",
+                        velocity, height
+                    ),
+                    None => format!("climbing at {:.1} m/s", velocity),
+                })
+            }
+            Phase::Boost => {
+                let acceleration = self.acceleration?;
+                (acceleration < t.burnout_acceleration)
+                    .then(|| format!("vertical acceleration down to {:.1} m/s²", acceleration))
+            }
+            Phase::Coast => (velocity <= 0.0).then(|| {
+                format!(
+                    "vertical velocity {:.1} m/s at {:.1} m",
+                    velocity, self.max_altitude
+                )
+            }),
+            Phase::Apogee => {
+                (velocity < -t.descent_velocity).then(|| format!("sinking at {:.1} m/s", -velocity))
+            }
+            Phase::Descent => (velocity.abs() < t.landed_velocity).then(|| {
+                format!(
+                    "vertical speed below {} m/s for {} s",
+                    t.landed_velocity, t.landed_hold_s
+                )
+            }),
+            Phase::Landed => None,
+        }
+    }
+}
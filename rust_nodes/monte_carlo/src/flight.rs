@@ -0,0 +1,238 @@
+// What one run's flight came to: the truth dynamics_sim published and the states fusion
+// estimated, reduced to end-state and estimator-error figures.
+use nalgebra::{UnitQuaternion, Vector3};
+use sensors_rs::sensors;
+use serde::Serialize;
+
+struct Truth {
+    timestamp_us: u64,
+    flight_time: f64,
+    position: Vector3<f64>,
+    velocity: Vector3<f64>,
+    attitude: UnitQuaternion<f64>,
+}
+
+// A fused state, with None for the estimates fusion flagged invalid.
+struct Estimate {
+    timestamp_us: u64,
+    altitude: Option<f64>,
+    velocity: Option<Vector3<f64>>,
+    attitude: Option<UnitQuaternion<f64>>,
+}
+
+#[derive(Default)]
+pub struct Flight {
+    truth: Vec<Truth>,
+    estimates: Vec<Estimate>,
+    // Whether the vehicle has been off the ground.
+    airborne: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Outcome {
+    pub landed: bool,
+    // Seconds from launch to landing, or to the last truth if the vehicle never landed.
+    pub flight_time_s: f64,
+    pub apogee_m: f64,
+    pub apogee_time_s: f64,
+    pub max_speed_mps: f64,
+    pub landing_east_m: f64,
+    pub landing_north_m: f64,
+    pub landing_range_m: f64,
+    // Fused states from launch to landing, and the fraction with every estimate valid.
+    pub estimates: usize,
+    pub valid_fraction: f64,
+    pub altitude_error_rms_m: f64,
+    pub altitude_error_max_m: f64,
+    pub velocity_error_rms_mps: f64,
+    pub velocity_error_max_mps: f64,
+    pub attitude_error_rms_deg: f64,
+    pub attitude_error_max_deg: f64,
+    // Highest fused altitude less the true apogee.
+    pub apogee_error_m: f64,
+}
+
+impl Outcome {
+    // The figures summarized across runs.
+    pub fn metrics(&self) -> [(&'static str, f64); 15] {
+        [
+            ("flight_time_s", self.flight_time_s),
+            ("apogee_m", self.apogee_m),
+            ("apogee_time_s", self.apogee_time_s),
+            ("max_speed_mps", self.max_speed_mps),
+            ("landing_east_m", self.landing_east_m),
+            ("landing_north_m", self.landing_north_m),
+            ("landing_range_m", self.landing_range_m),
+            ("valid_fraction", self.valid_fraction),
+            ("altitude_error_rms_m", self.altitude_error_rms_m),
+            ("altitude_error_max_m", self.altitude_error_max_m),
+            ("velocity_error_rms_mps", self.velocity_error_rms_mps),
+            ("velocity_error_max_mps", self.velocity_error_max_mps),
+            ("attitude_error_rms_deg", self.attitude_error_rms_deg),
+            ("attitude_error_max_deg", self.attitude_error_max_deg),
+            ("apogee_error_m", self.apogee_error_m),
+        ]
+    }
+}
+
+// Root-mean-square and largest magnitude of a series of errors; NaN without any.
+#[derive(Default)]
+struct Errors {
+    sum_squares: f64,
+    max: f64,
+    count: usize,
+}
+
+impl Errors {
+    fn add(&mut self, error: f64) {
+        self.sum_squares += error * error;
+        self.max = self.max.max(error.abs());
+        self.count += 1;
+    }
+
+    fn rms(&self) -> f64 {
+        (self.sum_squares / self.count as f64).sqrt()
+    }
+
+    fn max(&self) -> f64 {
+        if self.count == 0 { f64::NAN } else { self.max }
+    }
+}
+
+fn vector(v: Option<&sensors::Vec3>) -> Option<Vector3<f64>> {
+    v.map(|v| Vector3::new(v.x() as f64, v.y() as f64, v.z() as f64))
+}
+
+// Attitude given as roll, pitch, and yaw.
+fn attitude(v: Option<&sensors::Vec3>) -> Option<UnitQuaternion<f64>> {
+    vector(v).map(|v| UnitQuaternion::from_euler_angles(v.x, v.y, v.z))
+}
+
+impl Flight {
+    pub fn truth(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let truth = flatbuffers::root::<sensors::SimTruth>(bytes).map_err(|e| e.to_string())?;
+        let (Some(position), Some(velocity), Some(attitude)) = (
+            vector(truth.position()),
+            vector(truth.velocity()),
+            attitude(truth.attitude()),
+        ) else {
+            return Err("truth without position, velocity, or attitude".to_string());
+        };
+        self.airborne |= position.z > 0.0;
+        self.truth.push(Truth {
+            timestamp_us: truth.timestamp_us(),
+            flight_time: truth.flight_time_s() as f64,
+            position,
+            velocity,
+            attitude,
+        });
+        Ok(())
+    }
+
+    pub fn estimate(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let state = flatbuffers::root::<sensors::FusedState>(bytes).map_err(|e| e.to_string())?;
+        self.estimates.push(Estimate {
+            timestamp_us: state.timestamp_us(),
+            altitude: state.altitude_valid().then_some(state.altitude() as f64),
+            velocity: vector(state.velocity()).filter(|_| state.velocity_valid()),
+            attitude: attitude(state.attitude()).filter(|_| state.attitude_valid()),
+        });
+        Ok(())
+    }
+
+    // Whether the vehicle has come back down and is at rest, as dynamics_sim leaves it on
+    // landing.
+    pub fn landed(&self) -> bool {
+        self.airborne
+            && self
+                .truth
+                .last()
+                .is_some_and(|t| t.position.z <= 0.0 && t.velocity == Vector3::zeros())
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        // Truth from launch on; the first sample after launch may still be at rest on the
+        // rail, so flight_time marks it.
+        let flight = match self.truth.iter().position(|t| t.flight_time > 0.0) {
+            Some(launch) => &self.truth[launch..],
+            None => &[],
+        };
+        let landed = self.landed();
+        let apogee = flight
+            .iter()
+            .max_by(|a, b| a.position.z.total_cmp(&b.position.z));
+        let last = flight.last();
+
+        let mut altitude = Errors::default();
+        let mut velocity = Errors::default();
+        let mut attitude = Errors::default();
+        let mut estimates = 0;
+        let mut valid = 0;
+        let mut highest = f64::NAN;
+        if let (Some(first), Some(last)) = (flight.first(), last) {
+            for estimate in &self.estimates {
+                let time = estimate.timestamp_us;
+                if time < first.timestamp_us || time > last.timestamp_us {
+                    continue;
+                }
+                // The truth samples either side of the estimate, interpolated to its time.
+                let after = flight
+                    .partition_point(|t| t.timestamp_us < time)
+                    .min(flight.len() - 1);
+                let before = &flight[after.saturating_sub(1)];
+                let after = &flight[after];
+                let span = after.timestamp_us.saturating_sub(before.timestamp_us);
+                let fraction = if span == 0 {
+                    0.0
+                } else {
+                    (time.saturating_sub(before.timestamp_us)) as f64 / span as f64
+                };
+                let position = before.position.lerp(&after.position, fraction);
+                let true_velocity = before.velocity.lerp(&after.velocity, fraction);
+                let true_attitude = before.attitude.slerp(&after.attitude, fraction);
+
+                estimates += 1;
+                if let Some(h) = estimate.altitude {
+                    altitude.add(h - position.z);
+                    highest = highest.max(h);
+                }
+                if let Some(v) = estimate.velocity {
+                    velocity.add((v - true_velocity).norm());
+                }
+                if let Some(q) = estimate.attitude {
+                    attitude.add(q.angle_to(&true_attitude).to_degrees());
+                }
+                if estimate.altitude.is_some()
+                    && estimate.velocity.is_some()
+                    && estimate.attitude.is_some()
+                {
+                    valid += 1;
+                }
+            }
+        }
+
+        let landing = last.map_or(Vector3::from_element(f64::NAN), |t| t.position);
+        Outcome {
+            landed,
+            flight_time_s: last.map_or(f64::NAN, |t| t.flight_time),
+            apogee_m: apogee.map_or(f64::NAN, |t| t.position.z),
+            apogee_time_s: apogee.map_or(f64::NAN, |t| t.flight_time),
+            max_speed_mps: flight
+                .iter()
+                .map(|t| t.velocity.norm())
+                .fold(f64::NAN, f64::max),
+            landing_east_m: landing.x,
+            landing_north_m: landing.y,
+            landing_range_m: landing.x.hypot(landing.y),
+            estimates,
+            valid_fraction: valid as f64 / estimates as f64,
+            altitude_error_rms_m: altitude.rms(),
+            altitude_error_max_m: altitude.max(),
+            velocity_error_rms_mps: velocity.rms(),
+            velocity_error_max_mps: velocity.max(),
+            attitude_error_rms_deg: attitude.rms(),
+            attitude_error_max_deg: attitude.max(),
+            apogee_error_m: highest - apogee.map_or(f64::NAN, |t| t.position.z),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+mod flight;
+mod report;
+mod run;
+mod scenario;
+
+use clap::Parser;
+use common::shutdown_signal;
+use report::{Report, RunReport};
+use run::{Settings, Slot};
+use scenario::Scenario;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio::sync::mpsc;
+
+#[derive(Parser)]
+#[command(
+    about = "Flies many randomized closed-loop dynamics_sim and fusion runs and reports end-state and estimator-error statistics"
+)]
+struct Args {
+    /// Number of runs.
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+    /// Runs flown at the same time, each with its own dynamics_sim and fusion.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+    /// Seed the runs are drawn from. Run N draws from SEED + N alone, so a run repeats
+    /// with the same seed whatever the number of runs.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Strongest wind drawn, in m/s. Each run gets a speed uniform up to it from a
+    /// uniformly drawn direction.
+    #[arg(long, default_value_t = 8.0)]
+    max_wind_mps: f64,
+    /// Standard deviation of the sensor board misalignment drawn about each axis, in
+    /// degrees.
+    #[arg(long, default_value_t = 0.5)]
+    misalignment_deg: f64,
+    /// Multiplier applied to every sensor noise model.
+    #[arg(long, default_value_t = 1.0)]
+    noise_scale: f64,
+    /// Seconds on the pad before launch, for fusion to start up and settle.
+    #[arg(long, default_value_t = 5.0)]
+    pad_s: f64,
+    /// Seconds after launch a run is given to land before it counts as failed.
+    #[arg(long, default_value_t = 120.0)]
+    timeout_s: f64,
+    /// Sensor topology config passed to fusion. Defaults to fusion's built-in topology.
+    #[arg(long)]
+    fusion_config: Option<PathBuf>,
+    /// First of the localhost ports the runs' routers listen on, one per job.
+    #[arg(long, default_value_t = 7600)]
+    base_port: u16,
+    /// Directory dynamics_sim and fusion are looked up in before PATH. Defaults to the
+    /// directory of monte_carlo's own executable.
+    #[arg(long)]
+    bin_dir: Option<PathBuf>,
+    /// JSON file the report is written to.
+    #[arg(short, long, default_value = "monte_carlo.json")]
+    output: PathBuf,
+}
+
+fn validate(args: &Args) -> Result<(), String> {
+    for (name, value) in [
+        ("--max-wind-mps", args.max_wind_mps),
+        ("--misalignment-deg", args.misalignment_deg),
+        ("--noise-scale", args.noise_scale),
+        ("--pad-s", args.pad_s),
+    ] {
+        if !(value.is_finite() && value >= 0.0) {
+            return Err(format!("{} must not be negative", name));
+        }
+    }
+    if !(args.timeout_s.is_finite() && args.timeout_s > 0.0) {
+        return Err("--timeout-s must be positive".to_string());
+    }
+    if args.base_port.checked_add(args.jobs - 1).is_none() {
+        return Err("--base-port leaves too few ports for --jobs".to_string());
+    }
+    Ok(())
+}
+
+fn summarize(report: &RunReport) -> String {
+    let scenario = &report.scenario;
+    let [roll, pitch, yaw] = scenario.misalignment_deg;
+    let conditions = format!(
+        "run {}: wind {:.1} m/s, misalignment {:.2}/{:.2}/{:.2} deg",
+        scenario.run,
+        scenario.wind_speed(),
+        roll,
+        pitch,
+        yaw
+    );
+    match (&report.error, &report.outcome) {
+        (Some(e), _) => format!("{}: FAILED: {}", conditions, e),
+        (None, Some(o)) => format!(
+            "{}: apogee {:.1} m, landed {:.1} m out; altitude error rms {:.2} m, velocity \
+             error rms {:.2} m/s, attitude error rms {:.2} deg",
+            conditions,
+            o.apogee_m,
+            o.landing_range_m,
+            o.altitude_error_rms_m,
+            o.velocity_error_rms_mps,
+            o.attitude_error_rms_deg
+        ),
+        (None, None) => conditions,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    if let Err(e) = validate(&args) {
+        eprintln!("{}", e);
+        return ExitCode::FAILURE;
+    }
+    let settings = Arc::new(Settings {
+        bin_dir: args.bin_dir.clone().unwrap_or_else(run::default_bin_dir),
+        pad_s: args.pad_s,
+        timeout_s: args.timeout_s,
+        noise_scale: args.noise_scale,
+        fusion_config: args.fusion_config.clone(),
+    });
+
+    // Every job takes the next run until none are left.
+    let next = Arc::new(AtomicU32::new(0));
+    let (reports, mut received) = mpsc::unbounded_channel();
+    for job in 0..args.jobs {
+        let slot = match Slot::open(args.base_port + job).await {
+            Ok(slot) => slot,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let (next, settings, reports) = (next.clone(), settings.clone(), reports.clone());
+        let (runs, seed, max_wind, misalignment) = (
+            args.runs,
+            args.seed,
+            args.max_wind_mps,
+            args.misalignment_deg,
+        );
+        tokio::spawn(async move {
+            loop {
+                let run = next.fetch_add(1, Ordering::Relaxed);
+                if run >= runs {
+                    break;
+                }
+                let scenario = Scenario::draw(seed, run, max_wind, misalignment);
+                if reports.send(slot.run(&scenario, &settings).await).is_err() {
+                    break;
+                }
+            }
+            slot.close().await;
+        });
+    }
+    drop(reports);
+
+    let mut runs = Vec::with_capacity(args.runs as usize);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            report = received.recv() => match report {
+                Some(report) => {
+                    println!("{}", summarize(&report));
+                    runs.push(report);
+                }
+                None => break,
+            },
+            // Runs in flight are dropped, which kills their nodes; the report covers the
+            // runs that finished.
+            _ = &mut shutdown => {
+                eprintln!("Interrupted after {} of {} runs.", runs.len(), args.runs);
+                break;
+            }
+        }
+    }
+
+    let report = Report::new(runs);
+    println!();
+    report.print();
+    if let Err(e) = report.write(&args.output) {
+        eprintln!("Cannot write {}: {}", args.output.display(), e);
+        return ExitCode::FAILURE;
+    }
+    println!("Report written to {}", args.output.display());
+    let failed = report.failed();
+    if failed > 0 || report.runs.len() < args.runs as usize {
+        eprintln!(
+            "{} of {} runs did not complete.",
+            args.runs as usize - report.runs.len() + failed,
+            args.runs
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,100 @@
+use crate::flight::Outcome;
+use crate::scenario::Scenario;
+use serde::Serialize;
+use std::path::Path;
+
+// One run as the report lists it: its conditions and what came of it, or why it failed.
+#[derive(Serialize)]
+pub struct RunReport {
+    #[serde(flatten)]
+    pub scenario: Scenario,
+    pub error: Option<String>,
+    pub outcome: Option<Outcome>,
+}
+
+// Distribution of one figure over the runs that completed and produced it.
+#[derive(Serialize)]
+pub struct Summary {
+    pub metric: &'static str,
+    pub runs: usize,
+    pub mean: f64,
+    pub std: f64,
+    pub min: f64,
+    // Nearest-rank 95th percentile.
+    pub p95: f64,
+    pub max: f64,
+}
+
+#[derive(Serialize)]
+pub struct Report {
+    pub summary: Vec<Summary>,
+    pub runs: Vec<RunReport>,
+}
+
+impl Summary {
+    fn of(metric: &'static str, mut values: Vec<f64>) -> Self {
+        values.retain(|v| !v.is_nan());
+        values.sort_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let rank = (0.95 * n).ceil() as usize;
+        Summary {
+            metric,
+            runs: values.len(),
+            mean,
+            std: variance.sqrt(),
+            min: values.first().copied().unwrap_or(f64::NAN),
+            p95: values
+                .get(rank.saturating_sub(1))
+                .copied()
+                .unwrap_or(f64::NAN),
+            max: values.last().copied().unwrap_or(f64::NAN),
+        }
+    }
+}
+
+impl Report {
+    // Sorts the runs into order and summarizes every figure over those that completed.
+    pub fn new(mut runs: Vec<RunReport>) -> Self {
+        runs.sort_by_key(|run| run.scenario.run);
+        let completed: Vec<&Outcome> = runs
+            .iter()
+            .filter(|run| run.error.is_none())
+            .filter_map(|run| run.outcome.as_ref())
+            .collect();
+        let summary = match completed.first() {
+            Some(outcome) => (0..outcome.metrics().len())
+                .map(|i| {
+                    let values = completed.iter().map(|o| o.metrics()[i].1).collect();
+                    Summary::of(outcome.metrics()[i].0, values)
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Report { summary, runs }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.runs.iter().filter(|run| run.error.is_some()).count()
+    }
+
+    pub fn print(&self) {
+        println!(
+            "{:<24} {:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "metric", "runs", "mean", "std", "min", "p95", "max"
+        );
+        for s in &self.summary {
+            println!(
+                "{:<24} {:>5} {:>12.4} {:>12.4} {:>12.4} {:>12.4} {:>12.4}",
+                s.metric, s.runs, s.mean, s.std, s.min, s.p95, s.max
+            );
+        }
+    }
+
+    // Writes the report as JSON; figures that could not be computed are null.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json + "\n").map_err(|e| e.to_string())
+    }
+}
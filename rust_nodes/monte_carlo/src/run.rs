@@ -0,0 +1,201 @@
+use crate::flight::Flight;
+use crate::report::RunReport;
+use crate::scenario::Scenario;
+use keyspace::keys;
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::{Child, Command};
+use tokio::time::{sleep, timeout};
+
+// How long a node has to shut down on SIGTERM before it is killed.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+
+// How every run is set up, whichever slot it runs in.
+pub struct Settings {
+    pub bin_dir: PathBuf,
+    // Seconds on the pad before launch, for fusion to start up and settle.
+    pub pad_s: f64,
+    // Seconds after launch a run is given to land.
+    pub timeout_s: f64,
+    pub noise_scale: f64,
+    pub fusion_config: Option<PathBuf>,
+}
+
+impl Settings {
+    // A bare program name in bin_dir, or as PATH finds it.
+    fn program(&self, name: &str) -> PathBuf {
+        let local = self.bin_dir.join(name);
+        if local.is_file() {
+            local
+        } else {
+            PathBuf::from(name)
+        }
+    }
+}
+
+// Where runs take place one after another: a Zenoh router of the runner's own on a port of
+// its own, which the run's nodes connect to as clients. Scouting is off, so concurrent runs
+// on the host never see each other's samples.
+pub struct Slot {
+    session: zenoh::Session,
+    endpoint: String,
+}
+
+impl Slot {
+    pub async fn open(port: u16) -> Result<Self, String> {
+        let endpoint = format!("tcp/127.0.0.1:{}", port);
+        let mut config = zenoh::Config::default();
+        for (key, value) in [
+            ("mode", "\"router\"".to_string()),
+            ("listen/endpoints", format!("[\"{}\"]", endpoint)),
+            ("scouting/multicast/enabled", "false".to_string()),
+            ("scouting/gossip/enabled", "false".to_string()),
+        ] {
+            config
+                .insert_json5(key, &value)
+                .map_err(|e| format!("invalid Zenoh config {}: {}", key, e))?;
+        }
+        let session = zenoh::open(config)
+            .await
+            .map_err(|e| format!("cannot listen on {}: {}", endpoint, e))?;
+        Ok(Slot { session, endpoint })
+    }
+
+    pub async fn close(self) {
+        if let Err(e) = self.session.close().await {
+            eprintln!("Failed to close Zenoh session: {}", e);
+        }
+    }
+
+    // Flies scenario with dynamics_sim and fusion until the vehicle lands, either node
+    // exits, or the timeout passes. The outcome is reported even for a failed run, as far
+    // as it got.
+    pub async fn run(&self, scenario: &Scenario, settings: &Settings) -> RunReport {
+        let mut flight = Flight::default();
+        let error = self.fly(scenario, settings, &mut flight).await.err();
+        RunReport {
+            scenario: scenario.clone(),
+            error,
+            outcome: Some(flight.outcome()),
+        }
+    }
+
+    async fn fly(
+        &self,
+        scenario: &Scenario,
+        settings: &Settings,
+        flight: &mut Flight,
+    ) -> Result<(), String> {
+        let truth = self
+            .session
+            .declare_subscriber(keys::sim_truth())
+            .await
+            .map_err(|e| format!("cannot subscribe to truth: {}", e))?;
+        let fused = self
+            .session
+            .declare_subscriber(keys::fused_state())
+            .await
+            .map_err(|e| format!("cannot subscribe to fused state: {}", e))?;
+
+        let client = ["--mode", "client", "--connect", &self.endpoint];
+        let mut dynamics = Command::new(settings.program("dynamics_sim"));
+        dynamics
+            .args(client)
+            .arg("--launch-after-s")
+            .arg(settings.pad_s.to_string())
+            .arg("--noise-scale")
+            .arg(settings.noise_scale.to_string())
+            .args(scenario.args());
+        let mut fusion = Command::new(settings.program("fusion"));
+        fusion.args(client).env("FUSION_MODE", "subscribe");
+        if let Some(config) = &settings.fusion_config {
+            fusion.arg("--config").arg(config);
+        }
+        let mut dynamics = spawn(&mut dynamics, "dynamics_sim")?;
+        let mut fusion = match spawn(&mut fusion, "fusion") {
+            Ok(fusion) => fusion,
+            Err(e) => {
+                stop(&mut dynamics).await;
+                return Err(e);
+            }
+        };
+
+        let deadline = sleep(Duration::from_secs_f64(settings.pad_s + settings.timeout_s));
+        tokio::pin!(deadline);
+        let result = loop {
+            tokio::select! {
+                sample = truth.recv_async() => {
+                    let Ok(sample) = sample else {
+                        break Err("truth subscriber closed".to_string());
+                    };
+                    if let Err(e) = flight.truth(&sample.payload().to_bytes()) {
+                        break Err(format!("malformed truth: {}", e));
+                    }
+                    if flight.landed() {
+                        break Ok(());
+                    }
+                }
+                sample = fused.recv_async() => {
+                    let Ok(sample) = sample else {
+                        break Err("fused state subscriber closed".to_string());
+                    };
+                    if let Err(e) = flight.estimate(&sample.payload().to_bytes()) {
+                        break Err(format!("malformed fused state: {}", e));
+                    }
+                }
+                status = dynamics.wait() => {
+                    break Err(format!("dynamics_sim exited early: {}", describe(status)));
+                }
+                status = fusion.wait() => {
+                    break Err(format!("fusion exited early: {}", describe(status)));
+                }
+                _ = &mut deadline => {
+                    break Err(format!("not landed {} s after launch", settings.timeout_s));
+                }
+            }
+        };
+
+        stop(&mut fusion).await;
+        stop(&mut dynamics).await;
+        let _ = truth.undeclare().await;
+        let _ = fused.undeclare().await;
+        result
+    }
+}
+
+fn spawn(command: &mut Command, name: &str) -> Result<Child, String> {
+    command
+        .stdout(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("cannot start {}: {}", name, e))
+}
+
+fn describe(status: std::io::Result<std::process::ExitStatus>) -> String {
+    match status {
+        Ok(status) => status.to_string(),
+        Err(e) => format!("cannot wait for process: {}", e),
+    }
+}
+
+// Asks a node to shut down with SIGTERM, killing it if it is still there after the grace
+// period.
+async fn stop(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
+    }
+    if timeout(STOP_GRACE, child.wait()).await.is_err() {
+        let _ = child.kill().await;
+    }
+}
+
+// The directory of the runner's own executable, where the nodes are built next to it.
+pub fn default_bin_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_default()
+}
@@ -0,0 +1,61 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use serde::Serialize;
+use std::f64::consts::TAU;
+
+// The randomized conditions of one run, enough to repeat it by hand with dynamics_sim.
+#[derive(Clone, Debug, Serialize)]
+pub struct Scenario {
+    pub run: u32,
+    // Seed of dynamics_sim's sensor noise.
+    pub noise_seed: u64,
+    // East and north components.
+    pub wind_mps: [f64; 2],
+    // Sensor board rotation as roll, pitch, and yaw.
+    pub misalignment_deg: [f64; 3],
+}
+
+impl Scenario {
+    // Draws run's conditions from a generator seeded with seed + run, so every run can be
+    // repeated alone: the wind's speed uniform up to max_wind and its direction uniform,
+    // and each misalignment axis normal with misalignment_sigma.
+    pub fn draw(seed: u64, run: u32, max_wind: f64, misalignment_sigma: f64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(run as u64));
+        let noise_seed = rng.random();
+        let speed = max_wind * rng.random::<f64>();
+        let direction = TAU * rng.random::<f64>();
+        let misalignment = Normal::new(0.0, misalignment_sigma).ok();
+        let misalignment_deg = [(); 3].map(|_| match &misalignment {
+            Some(normal) => normal.sample(&mut rng),
+            None => 0.0,
+        });
+        Scenario {
+            run,
+            noise_seed,
+            wind_mps: [speed * direction.cos(), speed * direction.sin()],
+            misalignment_deg,
+        }
+    }
+
+    // dynamics_sim options that set the run's conditions.
+    pub fn args(&self) -> Vec<String> {
+        let list = |values: &[f64]| {
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        vec![
+            "--seed".to_string(),
+            self.noise_seed.to_string(),
+            format!("--wind-mps={}", list(&self.wind_mps)),
+            format!("--misalignment-deg={}", list(&self.misalignment_deg)),
+        ]
+    }
+
+    pub fn wind_speed(&self) -> f64 {
+        self.wind_mps[0].hypot(self.wind_mps[1])
+    }
+}
@@ -0,0 +1,149 @@
+// The connection to the MQTT broker, kept up for as long as the node runs. Rumqttc
+// reconnects on the first poll after the connection fails; polls after a failure are
+// spaced by a backoff that doubles from MIN_BACKOFF up to MAX_BACKOFF and starts over once
+// the broker accepts the connection again.
+use command::{Key, Reply, Senders};
+use rumqttc::{AsyncClient, Event, EventLoop, Packet, Publish, QoS};
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{info, warn};
+use zenoh::Session;
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+// Commands taken from MQTT: a message on <topic>/<node>/<command>, with a JSON object of
+// arguments or nothing as its payload, is sent to the node, and its outcome published on
+// <topic>/<node>/<command>/reply as {"ok": true, "message": ...}, or {"ok": false,
+// "reason": ..., "message": ...} with the reason the node refused it, or without one if
+// the command never got an answer.
+pub struct Commands {
+    topic: String,
+    senders: Senders,
+}
+
+impl Commands {
+    pub fn new(topic: String, session: &Session, timeout: Duration, key: Option<Key>) -> Self {
+        Commands {
+            topic,
+            senders: Senders::new(session, timeout, key),
+        }
+    }
+
+    fn filter(&self) -> String {
+        format!("{}/+/+", self.topic)
+    }
+
+    // Sends the command the message carries and publishes its outcome, without holding up
+    // the connection while the node answers.
+    fn on_publish(self: &Arc<Self>, client: &AsyncClient, publish: Publish) {
+        let Some((node, command)) = publish
+            .topic
+            .strip_prefix(&self.topic)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .and_then(|rest| rest.split_once('/'))
+        else {
+            return;
+        };
+        let (node, command) = (node.to_string(), command.to_string());
+        let reply_topic = format!("{}/reply", publish.topic);
+        let commands = self.clone();
+        let client = client.clone();
+        tokio::spawn(async move {
+            let outcome = match args(&publish.payload) {
+                Ok(args) => outcome(commands.senders.send(&node, &command, &args).await),
+                Err(e) => json!({"ok": false, "message": e}),
+            };
+            let payload = outcome.to_string();
+            if let Err(e) = client
+                .publish(reply_topic, QoS::AtLeastOnce, false, payload)
+                .await
+            {
+                warn!("Cannot publish the reply to {}/{}: {}", node, command, e);
+            }
+        });
+    }
+}
+
+// Command arguments as the command subsystem takes them, as text; values that are not
+// strings are sent as their JSON text, e.g. 5 as "5".
+fn args(payload: &[u8]) -> Result<Vec<(String, String)>, String> {
+    if payload.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Vec::new());
+    }
+    let args: Map<String, Value> = serde_json::from_slice(payload)
+        .map_err(|e| format!("expected a JSON object of arguments: {}", e))?;
+    Ok(args
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value,
+                value => value.to_string(),
+            };
+            (name, value)
+        })
+        .collect())
+}
+
+fn outcome(result: Result<Reply, command::SendError>) -> Value {
+    match result {
+        Ok(Reply {
+            result: Ok(message),
+            ..
+        }) => json!({"ok": true, "message": message}),
+        Ok(Reply {
+            result: Err(nack), ..
+        }) => json!({"ok": false, "reason": format!("{:?}", nack.reason), "message": nack.message}),
+        Err(e) => json!({"ok": false, "message": e.to_string()}),
+    }
+}
+
+pub fn spawn(
+    mut events: EventLoop,
+    client: AsyncClient,
+    broker: String,
+    commands: Option<Arc<Commands>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            match events.poll().await {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    info!("Connected to MQTT broker {}", broker);
+                    backoff = MIN_BACKOFF;
+                    // The broker forgets the subscriptions of a clean session when it
+                    // drops, so they are made again on every connection. The request
+                    // waits its turn behind the samples held while the broker was away,
+                    // which this loop is yet to send.
+                    if let Some(commands) = &commands {
+                        let (client, filter) = (client.clone(), commands.filter());
+                        tokio::spawn(async move {
+                            if let Err(e) = client.subscribe(&filter, QoS::AtLeastOnce).await {
+                                warn!("Cannot subscribe to {}: {}", filter, e);
+                            }
+                        });
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    if let Some(commands) = &commands {
+                        commands.on_publish(&client, publish);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(
+                        "MQTT broker {}: {}; reconnecting in {} s",
+                        broker,
+                        e,
+                        backoff.as_secs()
+                    );
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,218 @@
+mod broker;
+
+use broker::Commands;
+use clap::Parser;
+use command::Key;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId};
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use std::collections::{HashMap, HashSet};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+// Messages the client holds while the broker is unreachable or slow; samples arriving
+// once it is full are dropped.
+const QUEUE: usize = 1024;
+
+// A key prefix mirrored to MQTT, and the topic prefix its keys are published under.
+#[derive(Clone, Debug)]
+struct Mapping {
+    prefix: String,
+    topic: String,
+}
+
+impl Mapping {
+    fn topic(&self, key: &str) -> String {
+        format!("{}{}", self.topic, &key[self.prefix.len()..])
+    }
+}
+
+fn parse_mapping(arg: &str) -> Result<Mapping, String> {
+    let (prefix, topic) = arg.split_once('=').unwrap_or((arg, arg));
+    let (prefix, topic) = (prefix.trim_matches('/'), topic.trim_matches('/'));
+    if prefix.is_empty() || topic.is_empty() {
+        return Err(format!("expected KEY_PREFIX[=TOPIC_PREFIX], got '{}'", arg));
+    }
+    if topic.contains(['+', '#']) {
+        return Err(format!("MQTT wildcards are not allowed in '{}'", topic));
+    }
+    Ok(Mapping {
+        prefix: prefix.to_string(),
+        topic: topic.to_string(),
+    })
+}
+
+#[derive(Parser)]
+#[command(
+    about = "Mirrors key prefixes to MQTT topics as JSON, and optionally sends the commands published on MQTT"
+)]
+struct Args {
+    /// Key prefix to mirror, and the topic prefix to publish its keys under, e.g.
+    /// state=vehicle/state; the topic prefix is the key prefix if left out. May be repeated.
+    #[arg(long = "map", value_name = "KEY_PREFIX[=TOPIC_PREFIX]", value_parser = parse_mapping,
+        default_values = [keys::DEVICES, keys::STATE, keys::ALARMS, keys::EVENTS])]
+    mappings: Vec<Mapping>,
+    /// Host name or address of the MQTT broker.
+    #[arg(long, default_value = "127.0.0.1")]
+    broker_host: String,
+    /// Port of the MQTT broker.
+    #[arg(long, default_value_t = 1883)]
+    broker_port: u16,
+    /// Client id to connect to the broker with; the node's name if not given.
+    #[arg(long)]
+    client_id: Option<String>,
+    /// Publish telemetry retained, so clients subscribing later get the latest value of
+    /// every topic at once.
+    #[arg(long)]
+    retain: bool,
+    /// Send the commands published on <TOPIC>/<node>/<command>, with a JSON object of
+    /// arguments or nothing as the payload, and publish their outcome on
+    /// <TOPIC>/<node>/<command>/reply. Commands are not taken if this is not given.
+    #[arg(long, value_name = "TOPIC")]
+    command_topic: Option<String>,
+    /// How long each attempt of a command waits for the node to be reachable and to
+    /// reply, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    command_timeout_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct MqttBridge {
+    mappings: Vec<Mapping>,
+    broker_host: String,
+    broker_port: u16,
+    client_id: Option<String>,
+    retain: bool,
+    command_topic: Option<String>,
+    command_timeout: Duration,
+    command_key: Option<Key>,
+    client: Option<AsyncClient>,
+    connection: Option<JoinHandle<()>>,
+    // The mapping of each subscription's prefix.
+    subscriptions: HashMap<SubscriptionId, Mapping>,
+    // Samples dropped since the client last took one.
+    dropped: u64,
+    // Keys whose samples are not mirrored, reported once each.
+    skipped: HashSet<String>,
+}
+
+impl MqttBridge {
+    fn on_sample(&mut self, id: SubscriptionId, sample: &Sample) -> zenoh::Result<()> {
+        let (Some(client), Some(mapping)) = (&self.client, self.subscriptions.get(&id)) else {
+            return Ok(());
+        };
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = sample.key_expr().as_str();
+        let Some(value) = telemetry_json::decode(sample).map_err(|e| format!("{}: {}", key, e))?
+        else {
+            if self.skipped.insert(key.to_string()) {
+                debug!(
+                    "Not mirroring {}: no conversion for {}",
+                    key,
+                    sample.encoding()
+                );
+            }
+            return Ok(());
+        };
+        let payload = serde_json::to_vec(&value)?;
+        match client.try_publish(mapping.topic(key), QoS::AtMostOnce, self.retain, payload) {
+            Ok(()) if self.dropped > 0 => {
+                info!(
+                    "Mirroring to MQTT again after dropping {} samples",
+                    self.dropped
+                );
+                self.dropped = 0;
+            }
+            Ok(()) => {}
+            Err(e) => {
+                if self.dropped == 0 {
+                    warn!(
+                        "Dropping samples until the MQTT client takes them again: {}",
+                        e
+                    );
+                }
+                self.dropped += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Node for MqttBridge {
+    const NAME: &'static str = "mqtt_bridge";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let client_id = self.client_id.as_deref().unwrap_or(ctx.name());
+        let mut options = MqttOptions::new(client_id, &self.broker_host, self.broker_port);
+        options.set_keep_alive(Duration::from_secs(5));
+        let (client, events) = AsyncClient::new(options, QUEUE);
+        let commands = self.command_topic.as_ref().map(|topic| {
+            Arc::new(Commands::new(
+                topic.trim_matches('/').to_string(),
+                ctx.session(),
+                self.command_timeout,
+                self.command_key.take(),
+            ))
+        });
+        let broker = format!("{}:{}", self.broker_host, self.broker_port);
+        self.connection = Some(broker::spawn(events, client.clone(), broker, commands));
+        self.client = Some(client);
+        for mapping in &self.mappings {
+            let id = ctx.subscribe(&format!("{}/**", mapping.prefix)).await?;
+            self.subscriptions.insert(id, mapping.clone());
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(id, sample) => self.on_sample(id, &sample),
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(connection) = self.connection.take() {
+            connection.abort();
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    // Commands are signed with the secret the bridge checks the commands it receives
+    // against, the one the other nodes check them against too.
+    let command_key = match args.node.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let node = MqttBridge {
+        mappings: args.mappings,
+        broker_host: args.broker_host,
+        broker_port: args.broker_port,
+        client_id: args.client_id,
+        retain: args.retain,
+        command_topic: args.command_topic,
+        command_timeout: Duration::from_millis(args.command_timeout_ms),
+        command_key,
+        client: None,
+        connection: None,
+        subscriptions: HashMap::new(),
+        dropped: 0,
+        skipped: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
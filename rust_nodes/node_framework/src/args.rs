@@ -0,0 +1,86 @@
+use crate::logging;
+use common::ZenohArgs;
+use common::compression::CompressionRule;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Options every node takes: its identity on the bus and its Zenoh session.
+#[derive(clap::Args)]
+pub struct NodeArgs {
+    /// Name announced on @/alive/<NAME> and heartbeat/<NAME>. Defaults to the node type.
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Heartbeat period in milliseconds.
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub heartbeat_ms: u64,
+    /// Period of the drop statistics of received sequenced samples on stats/drops, in
+    /// milliseconds.
+    #[arg(long, default_value_t = 5000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub drop_stats_ms: u64,
+    /// Period of the node's resource usage, timer jitter and queue depths on
+    /// stats/<NAME>, in milliseconds.
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub stats_ms: u64,
+    /// Attach a CRC32C of the payload to every sequenced sample published, so subscribers
+    /// drop and count corrupted payloads instead of decoding them.
+    #[arg(long)]
+    pub crc32c: bool,
+    /// Compress payloads of sequenced samples published on keys matching KEY_EXPR with
+    /// CODEC, zstd or lz4; repeatable, the first matching rule applies. Subscribers
+    /// decompress them whatever their own rules.
+    #[arg(long = "compress", value_name = "KEY_EXPR=CODEC")]
+    pub compress: Vec<CompressionRule>,
+    /// File holding the secret commands must be signed with. Unsigned commands are refused
+    /// once this is set, and accepted if it is not.
+    #[arg(long, value_name = "PATH", env = "COMMAND_KEY")]
+    pub command_key: Option<PathBuf>,
+    /// Directory parameter sets are saved in, as <NAME>.toml, and restored from on start.
+    /// Sets are lost on restart if this is not given.
+    #[arg(long, value_name = "DIR", env = "PARAM_DIR")]
+    pub param_dir: Option<PathBuf>,
+    /// Directory the node writes a crash report to, as <NAME>-<TIMESTAMP_US>.crash, when it
+    /// panics. The report is also published on events/crash/<NAME>.
+    #[arg(long, value_name = "DIR", env = "CRASH_DIR", default_value_os_t = std::env::temp_dir())]
+    pub crash_dir: PathBuf,
+    /// What the node logs: a level, or comma-separated [TARGET=]LEVEL directives such as
+    /// info,fusion=debug. Replaced in flight with the log_level command.
+    #[arg(
+        long,
+        value_name = "FILTER",
+        env = "RUST_LOG",
+        default_value = logging::DEFAULT_FILTER,
+        value_parser = logging::parse_filter
+    )]
+    pub log_level: String,
+    /// Address to serve the node's metrics for Prometheus on, at /metrics, e.g.
+    /// 0.0.0.0:9464. Not served if not given.
+    #[cfg(feature = "prometheus")]
+    #[arg(long, value_name = "ADDR", env = "METRICS_ADDR")]
+    pub metrics_addr: Option<std::net::SocketAddr>,
+    #[command(flatten)]
+    pub zenoh: ZenohArgs,
+}
+
+impl NodeArgs {
+    pub fn name<'a>(&'a self, default: &'a str) -> &'a str {
+        self.name.as_deref().unwrap_or(default)
+    }
+
+    pub fn param_file(&self, name: &str) -> Option<PathBuf> {
+        self.param_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.toml", name)))
+    }
+
+    pub fn heartbeat_period(&self) -> Duration {
+        Duration::from_millis(self.heartbeat_ms)
+    }
+
+    pub fn drop_stats_period(&self) -> Duration {
+        Duration::from_millis(self.drop_stats_ms)
+    }
+
+    pub fn stats_period(&self) -> Duration {
+        Duration::from_millis(self.stats_ms)
+    }
+}
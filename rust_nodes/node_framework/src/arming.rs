@@ -0,0 +1,153 @@
+use command::{Nack, NackReason};
+use common::encoding;
+use keyspace::keys;
+use sensors::ArmingTransition as Transition;
+use sensors_rs::sensors;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+use zenoh::Wait;
+use zenoh::qos::CongestionControl;
+
+// Names of the built-in commands that arm and disarm a node's hazardous commands. Both
+// take the command to arm as command=<name>.
+pub(crate) const ARM: &str = "arm";
+pub(crate) const DISARM: &str = "disarm";
+
+// Arming state of the commands a node registered with Context::register_armed_command.
+// Such a command only reaches step within its window after an arm, and each arm lets it
+// through once.
+pub(crate) struct Arming {
+    session: zenoh::Session,
+    node: String,
+    windows: HashMap<String, Duration>,
+    // Armed commands: when the window closes, and which arm opened it.
+    armed: HashMap<String, (Instant, u64)>,
+    arms: u64,
+}
+
+impl Arming {
+    pub(crate) fn new(session: &zenoh::Session, node: &str) -> Self {
+        Arming {
+            session: session.clone(),
+            node: node.to_string(),
+            windows: HashMap::new(),
+            armed: HashMap::new(),
+            arms: 0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, name: &str, window: Duration) {
+        self.windows.insert(name.to_string(), window);
+    }
+
+    // Whether the node has any commands that must be armed, i.e. answers arm and disarm.
+    pub(crate) fn enabled(&self) -> bool {
+        !self.windows.is_empty()
+    }
+
+    // Opens the command's window, or restarts it if already open. Returns the window and
+    // the arm's number for expire().
+    pub(crate) fn arm(&mut self, name: &str, sender: &str) -> Result<(Duration, u64), Nack> {
+        let Some(&window) = self.windows.get(name) else {
+            return Err(Nack::new(
+                NackReason::InvalidArgs,
+                format!("{} needs no arming", name),
+            ));
+        };
+        self.arms += 1;
+        self.armed
+            .insert(name.to_string(), (Instant::now() + window, self.arms));
+        self.publish(name, Transition::Armed, window, sender);
+        Ok((window, self.arms))
+    }
+
+    // Closes the command's window. Returns whether it was open.
+    pub(crate) fn disarm(&mut self, name: &str, sender: &str) -> Result<bool, Nack> {
+        if !self.windows.contains_key(name) {
+            return Err(Nack::new(
+                NackReason::InvalidArgs,
+                format!("{} needs no arming", name),
+            ));
+        }
+        let armed = self.armed.remove(name).is_some();
+        if armed {
+            self.publish(name, Transition::Disarmed, Duration::ZERO, sender);
+        }
+        Ok(armed)
+    }
+
+    // Lets a received command through if it needs no arming or is armed, closing its
+    // window; refuses it otherwise.
+    pub(crate) fn fire(&mut self, name: &str, sender: &str) -> Result<(), Nack> {
+        if !self.windows.contains_key(name) {
+            return Ok(());
+        }
+        match self.armed.remove(name) {
+            Some((closes, _)) if Instant::now() < closes => {
+                self.publish(name, Transition::Fired, Duration::ZERO, sender);
+                Ok(())
+            }
+            _ => {
+                self.publish(name, Transition::Refused, Duration::ZERO, sender);
+                Err(Nack::new(
+                    NackReason::Rejected,
+                    format!("{} is not armed", name),
+                ))
+            }
+        }
+    }
+
+    // Called once the window of the given arm has passed; closes it unless the command
+    // was fired, disarmed, or armed again meanwhile.
+    pub(crate) fn expire(&mut self, name: &str, arm: u64) {
+        if self.armed.get(name).is_some_and(|&(_, armed)| armed == arm) {
+            self.armed.remove(name);
+            self.publish(name, Transition::Expired, Duration::ZERO, "");
+        }
+    }
+
+    fn publish(&self, name: &str, transition: Transition, window: Duration, sender: &str) {
+        match transition {
+            Transition::Armed => info!(
+                "{} armed for {} s by {}",
+                name,
+                window.as_secs_f64(),
+                sender
+            ),
+            Transition::Expired => info!("{} disarmed, window expired", name),
+            Transition::Refused => warn!("{} refused from {}, not armed", name, sender),
+            Transition::Disarmed => info!("{} disarmed by {}", name, sender),
+            _ => info!("{} fired by {}", name, sender),
+        }
+        let timestamp_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_micros() as u64)
+            .unwrap_or(0);
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+        let node = builder.create_string(&self.node);
+        let command = builder.create_string(name);
+        let sender = builder.create_string(sender);
+        let event = sensors::ArmingEvent::create(
+            &mut builder,
+            &sensors::ArmingEventArgs {
+                timestamp_us,
+                node: Some(node),
+                command: Some(command),
+                transition,
+                window_s: window.as_secs_f64(),
+                sender: Some(sender),
+            },
+        );
+        builder.finish(event, None);
+        let result = self
+            .session
+            .put(keys::arming_events(), builder.finished_data().to_vec())
+            .encoding(encoding::flatbuffer("sensors.ArmingEvent"))
+            .congestion_control(CongestionControl::Block)
+            .wait();
+        if let Err(e) = result {
+            error!("Failed to publish arming event: {}", e);
+        }
+    }
+}
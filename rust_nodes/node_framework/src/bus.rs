@@ -0,0 +1,226 @@
+// The few bus operations node logic needs, behind a trait so the logic can be driven by
+// MockBus in unit tests instead of a Zenoh session. Declarations the runner manages
+// (timers, subscriptions feeding step, queryables, commands) stay on Context.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use zenoh::bytes::ZBytes;
+use zenoh::key_expr::{OwnedKeyExpr, keyexpr};
+use zenoh::pubsub::Subscriber;
+use zenoh::query::{ConsolidationMode, Querier, QueryTarget};
+
+// How a get() is answered. The defaults are Zenoh's own.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetOptions {
+    pub timeout: Duration,
+    pub consolidation: ConsolidationMode,
+    pub target: QueryTarget,
+}
+
+impl Default for GetOptions {
+    fn default() -> Self {
+        GetOptions {
+            timeout: Duration::from_secs(10),
+            consolidation: ConsolidationMode::default(),
+            target: QueryTarget::default(),
+        }
+    }
+}
+
+// A sample received on a subscription.
+#[derive(Clone, Debug)]
+pub struct Message {
+    pub key: String,
+    pub payload: ZBytes,
+}
+
+// Samples on a subscribed key expression, in the order they arrived. Dropping it
+// undeclares the subscription.
+pub struct Subscription {
+    receiver: mpsc::UnboundedReceiver<Message>,
+    // The Zenoh subscriber feeding the receiver, if there is one.
+    _subscriber: Option<Subscriber<()>>,
+}
+
+impl Subscription {
+    // The next sample, or None once the bus is gone.
+    pub async fn recv(&mut self) -> Option<Message> {
+        self.receiver.recv().await
+    }
+
+    // The next sample if one has arrived, without waiting.
+    pub fn try_recv(&mut self) -> Option<Message> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+pub trait Bus {
+    // The first reply to a query on key, or None if nothing answered within the timeout.
+    fn get(
+        &self,
+        key: &str,
+        options: &GetOptions,
+    ) -> impl Future<Output = zenoh::Result<Option<ZBytes>>>;
+
+    fn put(&self, key: &str, payload: ZBytes) -> impl Future<Output = zenoh::Result<()>>;
+
+    fn subscribe(&self, key_expr: &str) -> impl Future<Output = zenoh::Result<Subscription>>;
+}
+
+// The bus of a Zenoh session, from Context::bus.
+pub struct ZenohBus {
+    session: zenoh::Session,
+    // Declared on a key's first get() with that get's options and reused for the key's
+    // later ones, so repeated queries do not set up their routing and options anew.
+    queriers: Mutex<HashMap<String, Arc<Querier<'static>>>>,
+}
+
+impl ZenohBus {
+    pub fn new(session: zenoh::Session) -> Self {
+        ZenohBus {
+            session,
+            queriers: Mutex::default(),
+        }
+    }
+
+    async fn querier(
+        &self,
+        key: &str,
+        options: &GetOptions,
+    ) -> zenoh::Result<Arc<Querier<'static>>> {
+        if let Some(querier) = self.queriers.lock().unwrap().get(key) {
+            return Ok(querier.clone());
+        }
+        let querier = self
+            .session
+            .declare_querier(key.to_string())
+            .consolidation(options.consolidation)
+            .target(options.target)
+            .timeout(options.timeout)
+            .await?;
+        let mut queriers = self.queriers.lock().unwrap();
+        Ok(queriers
+            .entry(key.to_string())
+            .or_insert(Arc::new(querier))
+            .clone())
+    }
+}
+
+impl Bus for ZenohBus {
+    async fn get(&self, key: &str, options: &GetOptions) -> zenoh::Result<Option<ZBytes>> {
+        let started = Instant::now();
+        let replies = self.querier(key, options).await?.get().await?;
+        let reply = replies.recv_async().await;
+        metrics::histogram!("zenoh_query_duration_seconds", "key" => key.to_string())
+            .record(started.elapsed());
+        match reply {
+            Ok(reply) => match reply.into_result() {
+                Ok(sample) => Ok(Some(sample.payload().clone())),
+                Err(e) => Err(format!("error reply: {}", e).into()),
+            },
+            Err(_) => Ok(None),
+        }
+    }
+
+    async fn put(&self, key: &str, payload: ZBytes) -> zenoh::Result<()> {
+        self.session.put(key.to_string(), payload).await
+    }
+
+    async fn subscribe(&self, key_expr: &str) -> zenoh::Result<Subscription> {
+        // Unbounded, as a subscription here is read by the node that declared it, at the
+        // pace it handles the samples.
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let subscriber = self
+            .session
+            .declare_subscriber(key_expr.to_string())
+            .callback(move |sample| {
+                let _ = sender.send(Message {
+                    key: sample.key_expr().to_string(),
+                    payload: sample.payload().clone(),
+                });
+            })
+            .await?;
+        Ok(Subscription {
+            receiver,
+            _subscriber: Some(subscriber),
+        })
+    }
+}
+
+// An in-memory bus for tests. What is put on a key is delivered to the matching
+// subscriptions and answers later gets on the key, as a sensor's queryable answers with its
+// latest sample; a get on a key nothing was put on gets None at once instead of after its
+// timeout. Clones share the same bus.
+#[derive(Clone, Default)]
+pub struct MockBus {
+    inner: Arc<Mutex<MockInner>>,
+}
+
+#[derive(Default)]
+struct MockInner {
+    latest: HashMap<String, ZBytes>,
+    published: Vec<Message>,
+    subscribers: Vec<(OwnedKeyExpr, mpsc::UnboundedSender<Message>)>,
+}
+
+impl MockBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Sets what gets on key are answered with, without publishing it.
+    pub fn answer(&self, key: &str, payload: impl Into<ZBytes>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.latest.insert(key.to_string(), payload.into());
+    }
+
+    // Stops answering gets on key, as a sensor that went silent.
+    pub fn silence(&self, key: &str) {
+        self.inner.lock().unwrap().latest.remove(key);
+    }
+
+    // Everything put on the bus so far, oldest first.
+    pub fn published(&self) -> Vec<Message> {
+        self.inner.lock().unwrap().published.clone()
+    }
+}
+
+impl Bus for MockBus {
+    async fn get(&self, key: &str, _options: &GetOptions) -> zenoh::Result<Option<ZBytes>> {
+        Ok(self.inner.lock().unwrap().latest.get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, payload: ZBytes) -> zenoh::Result<()> {
+        let key_expr = keyexpr::new(key)?;
+        let message = Message {
+            key: key.to_string(),
+            payload,
+        };
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .latest
+            .insert(message.key.clone(), message.payload.clone());
+        inner.published.push(message.clone());
+        // Subscriptions that were dropped are forgotten as they are found.
+        inner.subscribers.retain(|(subscribed, sender)| {
+            !subscribed.intersects(key_expr) || sender.send(message.clone()).is_ok()
+        });
+        Ok(())
+    }
+
+    async fn subscribe(&self, key_expr: &str) -> zenoh::Result<Subscription> {
+        let key_expr = OwnedKeyExpr::try_from(key_expr.to_string())?;
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.inner
+            .lock()
+            .unwrap()
+            .subscribers
+            .push((key_expr, sender));
+        Ok(Subscription {
+            receiver,
+            _subscriber: None,
+        })
+    }
+}
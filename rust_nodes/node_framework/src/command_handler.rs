@@ -0,0 +1,312 @@
+use crate::arming::{self, Arming};
+use crate::context::Event;
+use crate::logging;
+use command::{Command, Key, Nack, NackReason, Reply};
+use common::encoding;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use zenoh::Wait;
+use zenoh::query::{Query, ReplyBuilder, ReplyBuilderPut};
+
+// Identifies a command registered with Context::register_command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CommandId(usize);
+
+// Outcomes remembered per sender for answering retries of commands already received.
+const HISTORY: usize = 64;
+
+// A command received on one of the node's registered commands, answered with ack() or
+// nack(). A request dropped without an answer is nacked as failed, so the sender never
+// waits out its timeout on a node that got the command.
+pub struct CommandRequest {
+    pub id: CommandId,
+    pub command: Command,
+    query: Option<Query>,
+    registry: Registry,
+}
+
+impl CommandRequest {
+    pub async fn reply(mut self, result: Result<String, Nack>) -> zenoh::Result<()> {
+        let Some(query) = self.query.take() else {
+            return Ok(());
+        };
+        let reply = Reply {
+            sequence: self.command.sequence,
+            result,
+        };
+        record(&self.registry, &self.command, &reply);
+        send(&query, &reply).await
+    }
+
+    pub async fn ack(self, message: impl Into<String>) -> zenoh::Result<()> {
+        self.reply(Ok(message.into())).await
+    }
+
+    pub async fn nack(self, reason: NackReason, message: impl Into<String>) -> zenoh::Result<()> {
+        self.reply(Err(Nack::new(reason, message))).await
+    }
+}
+
+impl Drop for CommandRequest {
+    fn drop(&mut self) {
+        if let Some(query) = self.query.take() {
+            let reply = Reply {
+                sequence: self.command.sequence,
+                result: Err(Nack::new(NackReason::Failed, "command was not handled")),
+            };
+            record(&self.registry, &self.command, &reply);
+            let _ = reply_builder(&query, &reply).wait();
+        }
+    }
+}
+
+fn reply_builder<'a>(query: &'a Query, reply: &Reply) -> ReplyBuilder<'a, 'a, ReplyBuilderPut> {
+    query
+        .reply(query.key_expr().clone(), reply.encode())
+        .encoding(encoding::flatbuffer("sensors.CommandReply"))
+}
+
+async fn send(query: &Query, reply: &Reply) -> zenoh::Result<()> {
+    reply_builder(query, reply).await
+}
+
+pub(crate) struct Commands {
+    names: Vec<String>,
+    // The latest sequence numbers received from each sender, with the reply to each, or
+    // None while step has not answered it yet.
+    history: HashMap<String, BTreeMap<u64, Option<Reply>>>,
+    arming: Arming,
+    key: Option<Key>,
+    // Runtime the arming windows are timed on; the queryable's callback runs outside it.
+    runtime: Handle,
+}
+
+// The commands a node registered, shared with the queryable's callback.
+pub(crate) type Registry = Arc<Mutex<Commands>>;
+
+// Must be called from within the node's runtime.
+pub(crate) fn registry(session: &zenoh::Session, node: &str, key: Option<Key>) -> Registry {
+    Arc::new(Mutex::new(Commands {
+        names: Vec::new(),
+        history: HashMap::new(),
+        arming: Arming::new(session, node),
+        key,
+        runtime: Handle::current(),
+    }))
+}
+
+fn lock(registry: &Registry) -> MutexGuard<'_, Commands> {
+    registry.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(crate) fn register(registry: &Registry, name: &str, window: Option<Duration>) -> CommandId {
+    let mut commands = lock(registry);
+    if let Some(window) = window {
+        commands.arming.add(name, window);
+    }
+    commands.names.push(name.to_string());
+    CommandId(commands.names.len() - 1)
+}
+
+// What a received command is for.
+#[derive(Clone, Copy)]
+enum Target {
+    Node(CommandId),
+    Arm,
+    Disarm,
+    LogLevel,
+}
+
+impl Commands {
+    fn target(&self, name: &str) -> Option<Target> {
+        if let Some(id) = self.names.iter().position(|registered| registered == name) {
+            return Some(Target::Node(CommandId(id)));
+        }
+        match name {
+            arming::ARM if self.arming.enabled() => Some(Target::Arm),
+            arming::DISARM if self.arming.enabled() => Some(Target::Disarm),
+            logging::LOG_LEVEL => Some(Target::LogLevel),
+            _ => None,
+        }
+    }
+}
+
+enum Delivery {
+    New,
+    // Already answered; the reply is sent again.
+    Duplicate(Reply),
+    // Still waiting for step; the retry is dropped unanswered and the sender tries again.
+    Pending,
+    // Older than every remembered sequence number, so whether it ran is unknown.
+    Expired,
+}
+
+fn deliver(registry: &Registry, command: &Command) -> Delivery {
+    let mut commands = lock(registry);
+    let history = commands.history.entry(command.sender.clone()).or_default();
+    match history.get(&command.sequence) {
+        Some(Some(reply)) => return Delivery::Duplicate(reply.clone()),
+        Some(None) => return Delivery::Pending,
+        None => {}
+    }
+    let oldest = history.keys().next().copied();
+    if history.len() >= HISTORY && oldest.is_some_and(|oldest| command.sequence < oldest) {
+        return Delivery::Expired;
+    }
+    history.insert(command.sequence, None);
+    while history.len() > HISTORY {
+        history.pop_first();
+    }
+    Delivery::New
+}
+
+// Remembers the reply to a delivered command for answering its retries.
+fn record(registry: &Registry, command: &Command, reply: &Reply) {
+    let mut commands = lock(registry);
+    let outcome = commands
+        .history
+        .get_mut(&command.sender)
+        .and_then(|history| history.get_mut(&command.sequence));
+    if let Some(outcome) = outcome {
+        *outcome = Some(reply.clone());
+    }
+}
+
+fn arm(registry: &Registry, command: &Command) -> Result<String, Nack> {
+    let name: String = command.parse("command")?;
+    let mut commands = lock(registry);
+    let (window, arm) = commands.arming.arm(&name, &command.sender)?;
+    let message = format!("{} armed for {} s", name, window.as_secs_f64());
+    let expiring = registry.clone();
+    commands.runtime.spawn(async move {
+        tokio::time::sleep(window).await;
+        lock(&expiring).arming.expire(&name, arm);
+    });
+    Ok(message)
+}
+
+fn disarm(registry: &Registry, command: &Command) -> Result<String, Nack> {
+    let name: String = command.parse("command")?;
+    match lock(registry).arming.disarm(&name, &command.sender)? {
+        true => Ok(format!("{} disarmed", name)),
+        false => Ok(format!("{} was not armed", name)),
+    }
+}
+
+fn log_level(command: &Command) -> Result<String, Nack> {
+    let directives: String = command.parse("filter")?;
+    let message = logging::set_filter(&directives)?;
+    info!("{} by {}", message, command.sender);
+    Ok(message)
+}
+
+// Called on every query on cmd/<node>/*. Queries for registered commands are queued for
+// step, unless they are not properly signed, must be armed and are not, or are retries of
+// commands already received. Arm, disarm and log_level are answered here, as are unknown
+// commands and malformed payloads.
+pub(crate) fn dispatch(registry: &Registry, events: &mpsc::UnboundedSender<Event>, query: Query) {
+    let name = query
+        .key_expr()
+        .as_str()
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let payload = query
+        .payload()
+        .map(|payload| payload.to_bytes().into_owned())
+        .unwrap_or_default();
+    let decoded = Command::decode(&payload);
+    let sequence = decoded.as_ref().map_or(0, |command| command.sequence);
+    let (target, verified) = {
+        let commands = lock(registry);
+        let attachment = query.attachment().map(|a| a.to_bytes());
+        let verified = match &commands.key {
+            Some(key) => key.verify(query.key_expr().as_str(), &payload, attachment.as_deref()),
+            None => Ok(()),
+        };
+        (commands.target(name), verified)
+    };
+    // Checked first, so unsigned senders learn nothing about the node and cannot fill the
+    // history retries are answered from.
+    if let Err(nack) = verified {
+        warn!("Refused command on {}: {}", query.key_expr(), nack.message);
+        return answer(&query, sequence, Err(nack));
+    }
+
+    let (target, command) = match (target, decoded) {
+        (None, _) => {
+            let nack = Nack::new(
+                NackReason::UnknownCommand,
+                format!("unknown command {}", name),
+            );
+            return answer(&query, sequence, Err(nack));
+        }
+        (Some(_), Err(e)) => {
+            return answer(&query, sequence, Err(Nack::new(NackReason::Malformed, e)));
+        }
+        (Some(_), Ok(command)) if command.id != name => {
+            let nack = Nack::new(
+                NackReason::Malformed,
+                format!("command {} sent on {}", command.id, query.key_expr()),
+            );
+            return answer(&query, sequence, Err(nack));
+        }
+        (Some(target), Ok(command)) => (target, command),
+    };
+
+    match deliver(registry, &command) {
+        Delivery::New => {}
+        Delivery::Duplicate(reply) => {
+            info!(
+                "Repeating reply to {} #{} from {}",
+                command.id, command.sequence, command.sender
+            );
+            let _ = reply_builder(&query, &reply).wait();
+            return;
+        }
+        Delivery::Pending => return,
+        Delivery::Expired => {
+            let nack = Nack::new(
+                NackReason::Rejected,
+                format!(
+                    "sequence {} is older than the last {} commands from {}",
+                    command.sequence, HISTORY, command.sender
+                ),
+            );
+            return answer(&query, sequence, Err(nack));
+        }
+    }
+
+    let result = match target {
+        Target::Arm => arm(registry, &command),
+        Target::Disarm => disarm(registry, &command),
+        Target::LogLevel => log_level(&command),
+        Target::Node(id) => {
+            let fired = lock(registry).arming.fire(name, &command.sender);
+            match fired {
+                Ok(()) => {
+                    let _ = events.send(Event::Command(CommandRequest {
+                        id,
+                        command,
+                        query: Some(query),
+                        registry: registry.clone(),
+                    }));
+                    return;
+                }
+                Err(nack) => Err(nack),
+            }
+        }
+    };
+    let reply = Reply { sequence, result };
+    record(registry, &command, &reply);
+    let _ = reply_builder(&query, &reply).wait();
+}
+
+// Replies to a query not taken up as a command, so nothing to remember for retries.
+fn answer(query: &Query, sequence: u64, result: Result<String, Nack>) {
+    let _ = reply_builder(query, &Reply { sequence, result }).wait();
+}
@@ -0,0 +1,471 @@
+use crate::bus::ZenohBus;
+use crate::command_handler::{self, CommandId, CommandRequest, Registry};
+use crate::node_stats::LoopTiming;
+use crate::param_server::{self, ParamChanged, ParamId, Store};
+use command::Key;
+use common::compression::{self, Codec, CompressionRule};
+use common::encoding;
+use common::qos::QosConfig;
+use common::sequence::{DropTracker, Sequence, SequencedPublisher};
+use keyspace::keys;
+use std::collections::HashSet;
+use std::future::poll_fn;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{Instant, Interval, MissedTickBehavior, interval};
+use tracing::{error, warn};
+use zenoh::key_expr::KeyExpr;
+use zenoh::pubsub::{Publisher, Subscriber};
+use zenoh::query::{Query, Queryable};
+use zenoh::sample::Sample;
+use zenoh_ext::{
+    AdvancedPublisher, AdvancedSubscriber, AdvancedSubscriberBuilderExt, HistoryConfig,
+    RecoveryConfig,
+};
+
+// Identifies a timer added with Context::add_timer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(pub(crate) usize);
+
+// Identifies a subscription declared with Context::subscribe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(usize);
+
+// Identifies a queryable declared with Context::declare_queryable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QueryableId(usize);
+
+enum Subscription {
+    Plain(Subscriber<()>),
+    Advanced(AdvancedSubscriber<()>),
+}
+
+impl Subscription {
+    async fn undeclare(self) -> zenoh::Result<()> {
+        match self {
+            Subscription::Plain(subscriber) => subscriber.undeclare().await,
+            Subscription::Advanced(subscriber) => subscriber.undeclare().await,
+        }
+    }
+}
+
+// What a node is stepped with.
+pub enum Event {
+    Timer(TimerId),
+    Sample(SubscriptionId, Sample),
+    // A query to answer with query.reply(); it is finalized once dropped.
+    Query(QueryableId, Query),
+    Command(CommandRequest),
+    // A parameter was set to a new value; read it with Context::param.
+    Param(ParamChanged),
+}
+
+// The session and the resources the runner manages on a node's behalf.
+pub struct Context {
+    session: zenoh::Session,
+    name: String,
+    timers: Vec<Interval>,
+    subscribers: Vec<(String, Subscription)>,
+    queryables: Vec<(String, Queryable<()>)>,
+    commands: Option<Registry>,
+    params: Option<Store>,
+    // Secret received commands must be signed with, if they are checked.
+    command_key: Option<Key>,
+    // Where parameter sets are saved, if they are.
+    param_file: Option<PathBuf>,
+    events: mpsc::UnboundedSender<Event>,
+    // Sequence numbers of the samples every subscription received.
+    drops: Arc<Mutex<DropTracker>>,
+    // How late timers ticked and how deep the event queue got, for the node's statistics.
+    timing: Arc<Mutex<LoopTiming>>,
+    // Whether sequenced publishers attach a CRC32C of their payloads.
+    crc32c: bool,
+    // Which codec sequenced publishers compress their payloads with, by key.
+    compression: Vec<CompressionRule>,
+    // QoS of the publishers the node and the runner declare.
+    qos: QosConfig,
+    // The pool sequenced publishers publish large payloads from, if the node has one.
+    #[cfg(feature = "shm")]
+    shm: Option<Arc<common::shm::ShmPool>>,
+    stopped: bool,
+}
+
+impl Context {
+    pub(crate) fn new(
+        session: zenoh::Session,
+        name: &str,
+        command_key: Option<Key>,
+        param_file: Option<PathBuf>,
+        crc32c: bool,
+        compression: Vec<CompressionRule>,
+        events: mpsc::UnboundedSender<Event>,
+    ) -> Self {
+        Context {
+            session,
+            name: name.to_string(),
+            timers: Vec::new(),
+            subscribers: Vec::new(),
+            queryables: Vec::new(),
+            commands: None,
+            params: None,
+            command_key,
+            param_file,
+            events,
+            drops: Arc::default(),
+            timing: Arc::default(),
+            crc32c,
+            compression,
+            qos: QosConfig::default(),
+            #[cfg(feature = "shm")]
+            shm: None,
+            stopped: false,
+        }
+    }
+
+    pub fn session(&self) -> &zenoh::Session {
+        &self.session
+    }
+
+    // The session as a Bus, for node logic written against the trait.
+    pub fn bus(&self) -> ZenohBus {
+        ZenohBus::new(self.session.clone())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // The QoS publishers are declared with, through common::qos::PublisherBuilderQosExt.
+    // The node's heartbeats and drop statistics follow it too when it is set during init.
+    pub fn qos(&self) -> &QosConfig {
+        &self.qos
+    }
+
+    pub fn set_qos(&mut self, qos: QosConfig) {
+        self.qos = qos;
+    }
+
+    // The codec the node's --compress rules give payloads published on a key, for nodes
+    // publishing without a SequencedPublisher.
+    pub fn compression(&self, key: &KeyExpr<'_>) -> Option<Codec> {
+        compression::codec(&self.compression, key)
+    }
+
+    #[cfg(feature = "shm")]
+    pub(crate) fn set_shm(&mut self, pool: Option<common::shm::ShmPool>) {
+        self.shm = pool.map(Arc::new);
+    }
+
+    // Wraps a declared publisher into a SequencedPublisher attributed to the node, which
+    // attaches CRC32Cs of its payloads if the node was started with --crc32c, compresses
+    // them if a --compress rule matches its key, and publishes large ones from shared
+    // memory if it was started with --shm-pool.
+    pub fn sequenced<'a>(&self, publisher: Publisher<'a>) -> SequencedPublisher<'a> {
+        self.configure(SequencedPublisher::new(publisher, &self.name))
+    }
+
+    // Like sequenced, for a publisher declared through common::sequence::cached.
+    pub fn sequenced_cached<'a>(&self, publisher: AdvancedPublisher<'a>) -> SequencedPublisher<'a> {
+        self.configure(SequencedPublisher::cached(publisher, &self.name))
+    }
+
+    fn configure<'a>(&self, publisher: SequencedPublisher<'a>) -> SequencedPublisher<'a> {
+        #[cfg(feature = "shm")]
+        let publisher = publisher.with_shm(self.shm.clone());
+        let codec = self.compression(publisher.key_expr());
+        publisher.with_crc32c(self.crc32c).with_compression(codec)
+    }
+
+    pub(crate) fn drops(&self) -> Arc<Mutex<DropTracker>> {
+        self.drops.clone()
+    }
+
+    pub(crate) fn timing(&self) -> Arc<Mutex<LoopTiming>> {
+        self.timing.clone()
+    }
+
+    // Adds a timer that fires right away and then every period. Ticks missed while the
+    // node was busy are dropped rather than fired back to back.
+    pub fn add_timer(&mut self, period: Duration) -> TimerId {
+        let mut timer = interval(period);
+        timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        self.timers.push(timer);
+        TimerId(self.timers.len() - 1)
+    }
+
+    // Subscribes to a key expression. Samples of every subscription and queries of every
+    // queryable reach step through one queue in the order Zenoh delivered them, so a node
+    // sees samples from a single publishing session in publication order. Samples of a
+    // schema version this build cannot read are dropped, with a warning once per key, and
+    // gaps in the numbers of sequenced samples are counted for stats/drops. So are sequenced
+    // samples whose payload fails its CRC32C, which are dropped too. Compressed payloads are
+    // decompressed before they reach step; ones that fail to are dropped, with a warning
+    // once per key.
+    pub async fn subscribe(&mut self, key_expr: &str) -> zenoh::Result<SubscriptionId> {
+        let id = SubscriptionId(self.subscribers.len());
+        let subscriber = self
+            .session
+            .declare_subscriber(key_expr)
+            .callback(self.deliver(id))
+            .await?;
+        self.subscribers
+            .push((key_expr.to_string(), Subscription::Plain(subscriber)));
+        Ok(id)
+    }
+
+    // Like subscribe, but first fetches up to history recent samples from every cached
+    // publisher on the key expression, including ones that appear later, and recovers
+    // samples of theirs that went missing. Samples after a gap are held back until the
+    // missing ones are recovered, so they still reach step in publication order.
+    pub async fn subscribe_advanced(
+        &mut self,
+        key_expr: &str,
+        history: usize,
+    ) -> zenoh::Result<SubscriptionId> {
+        let id = SubscriptionId(self.subscribers.len());
+        let subscriber = self
+            .session
+            .declare_subscriber(key_expr)
+            .history(
+                HistoryConfig::default()
+                    .detect_late_publishers()
+                    .max_samples(history),
+            )
+            .recovery(RecoveryConfig::default().heartbeat())
+            .callback(self.deliver(id))
+            .await?;
+        self.subscribers
+            .push((key_expr.to_string(), Subscription::Advanced(subscriber)));
+        Ok(id)
+    }
+
+    // Hands the samples of a subscription to step, decompressed, dropping the unreadable and
+    // corrupted ones and counting gaps as subscribe describes.
+    fn deliver(&self, id: SubscriptionId) -> impl Fn(Sample) + Send + Sync + 'static {
+        let events = self.events.clone();
+        let drops = self.drops.clone();
+        let unreadable = Mutex::new(HashSet::new());
+        let undecompressable = Mutex::new(HashSet::new());
+        move |sample| {
+            metrics::counter!("zenoh_samples_received_total", "key" => sample.key_expr().to_string())
+                .increment(1);
+            let version = encoding::schema_version(sample.encoding());
+            if !version.is_some_and(encoding::readable) {
+                let key = sample.key_expr().to_string();
+                if unreadable.lock().unwrap().insert(key.clone()) {
+                    warn!(
+                        "Dropping samples on {}: schema version {}, this node reads {} to {}",
+                        key,
+                        version.map_or("unknown".to_string(), |v| v.to_string()),
+                        encoding::OLDEST_SCHEMA_VERSION,
+                        encoding::SCHEMA_VERSION
+                    );
+                }
+                return;
+            }
+            if let Some(sequence) = Sequence::of(&sample) {
+                let key = sample.key_expr().as_str();
+                let mut drops = drops.lock().unwrap();
+                drops.record(key, &sequence);
+                if !sequence.verify(&sample.payload().to_bytes()) {
+                    drops.corrupted(key, &sequence);
+                    return;
+                }
+            }
+            let key = sample.key_expr().clone();
+            let sample = match compression::decompress(sample) {
+                Ok(sample) => sample,
+                Err(e) => {
+                    if undecompressable.lock().unwrap().insert(key.to_string()) {
+                        warn!("Dropping samples on {}: {}", key, e);
+                    }
+                    return;
+                }
+            };
+            let _ = events.send(Event::Sample(id, sample));
+        }
+    }
+
+    // Subscribes to liveliness tokens matching a key expression. Tokens present when the
+    // subscription is declared arrive first as puts; afterwards a put means a token
+    // appeared and a delete that it went away.
+    pub async fn subscribe_liveliness(&mut self, key_expr: &str) -> zenoh::Result<SubscriptionId> {
+        let id = SubscriptionId(self.subscribers.len());
+        let events = self.events.clone();
+        let subscriber = self
+            .session
+            .liveliness()
+            .declare_subscriber(key_expr)
+            .history(true)
+            .callback(move |sample| {
+                let _ = events.send(Event::Sample(id, sample));
+            })
+            .await?;
+        self.subscribers
+            .push((key_expr.to_string(), Subscription::Plain(subscriber)));
+        Ok(id)
+    }
+
+    // Answers queries on a key expression; each one is handed to step as an Event::Query.
+    pub async fn declare_queryable(&mut self, key_expr: &str) -> zenoh::Result<QueryableId> {
+        let id = QueryableId(self.queryables.len());
+        let events = self.events.clone();
+        let queryable = self
+            .session
+            .declare_queryable(key_expr)
+            .callback(move |query| {
+                let _ = events.send(Event::Query(id, query));
+            })
+            .await?;
+        self.queryables.push((key_expr.to_string(), queryable));
+        Ok(id)
+    }
+
+    // Answers the named command on cmd/<node>/<name>; each one received is handed to step
+    // as an Event::Command. The node's commands share one queryable on cmd/<node>/*, which
+    // nacks commands the node did not register.
+    pub async fn register_command(&mut self, name: &str) -> zenoh::Result<CommandId> {
+        self.register(name, None).await
+    }
+
+    // Like register_command, for hazardous commands: the command is only handed to step
+    // if it arrives within window of an arm command=<name> on cmd/<node>/arm, and each
+    // arm lets it through once. Otherwise it is nacked as rejected. Arming, disarming
+    // (disarm command=<name>), expiry, and firing are published on events/arming.
+    pub async fn register_armed_command(
+        &mut self,
+        name: &str,
+        window: Duration,
+    ) -> zenoh::Result<CommandId> {
+        self.register(name, Some(window)).await
+    }
+
+    async fn register(&mut self, name: &str, window: Option<Duration>) -> zenoh::Result<CommandId> {
+        let registry = self.serve_commands().await?;
+        Ok(command_handler::register(registry, name, window))
+    }
+
+    // Declares the queryable on cmd/<node>/* if it is not yet, so the built-in commands
+    // are answered even by nodes that register none.
+    pub(crate) async fn serve_commands(&mut self) -> zenoh::Result<&Registry> {
+        if self.commands.is_none() {
+            let registry =
+                command_handler::registry(&self.session, &self.name, self.command_key.clone());
+            let key_expr = keys::node_commands(&self.name);
+            let events = self.events.clone();
+            let callback_registry = registry.clone();
+            let queryable = self
+                .session
+                .declare_queryable(&key_expr)
+                .callback(move |query| {
+                    command_handler::dispatch(&callback_registry, &events, query)
+                })
+                .await?;
+            self.queryables.push((key_expr, queryable));
+            self.commands = Some(registry);
+        }
+        Ok(self.commands.as_ref().unwrap())
+    }
+
+    // Declares a parameter served on param/<node>/<name>, starting at its default or, if
+    // the node saves parameters, at the value it was last set to. Sets within range are
+    // published there and handed to step as an Event::Param. The node's
+    // parameters share one queryable on param/<node>/**; sets must be signed like commands
+    // if the node checks signatures.
+    pub async fn declare_param<T: params::Type>(
+        &mut self,
+        name: &str,
+        default: T,
+        range: Option<RangeInclusive<T>>,
+    ) -> zenoh::Result<ParamId<T>> {
+        let store = match &self.params {
+            Some(store) => store.clone(),
+            None => {
+                let store = param_server::store(
+                    &self.name,
+                    self.command_key.clone(),
+                    self.param_file.clone(),
+                );
+                let key_expr = keys::node_params(&self.name);
+                let events = self.events.clone();
+                let session = self.session.clone();
+                let callback_store = store.clone();
+                let queryable = self
+                    .session
+                    .declare_queryable(&key_expr)
+                    .callback(move |query| {
+                        param_server::dispatch(&callback_store, &session, &events, query)
+                    })
+                    .await?;
+                self.queryables.push((key_expr, queryable));
+                self.params = Some(store.clone());
+                store
+            }
+        };
+        Ok(param_server::declare(&store, name, default, range)?)
+    }
+
+    // Current value of a parameter of this node.
+    pub fn param<T: params::Type>(&self, id: ParamId<T>) -> T {
+        let store = self.params.as_ref().expect("ids come from declare_param");
+        param_server::get(store, id)
+    }
+
+    // Ends the node once the current step returns.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    pub(crate) fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    // Waits for the next timer to fire, recording how late it fired, and returns it with
+    // when it was due; never resolves for a node without timers.
+    pub(crate) async fn next_timer(&mut self) -> (TimerId, Instant) {
+        let (id, due) = poll_fn(|cx| {
+            for (i, timer) in self.timers.iter_mut().enumerate() {
+                if let Poll::Ready(due) = timer.poll_tick(cx) {
+                    return Poll::Ready((TimerId(i), due));
+                }
+            }
+            Poll::Pending
+        })
+        .await;
+        let period = self.timers[id.0].period();
+        let jitter = Instant::now().saturating_duration_since(due);
+        self.timing.lock().unwrap().tick(id, period, jitter);
+        (id, due)
+    }
+
+    pub(crate) fn timer_period(&self, id: TimerId) -> Duration {
+        self.timers[id.0].period()
+    }
+
+    // Undeclares the subscriptions and queryables and closes the session, reporting every
+    // failure. Returns whether all of it succeeded.
+    pub(crate) async fn close(self) -> bool {
+        let mut ok = true;
+        for (key_expr, subscriber) in self.subscribers {
+            if let Err(e) = subscriber.undeclare().await {
+                error!("Failed to undeclare subscriber on {}: {}", key_expr, e);
+                ok = false;
+            }
+        }
+        for (key_expr, queryable) in self.queryables {
+            if let Err(e) = queryable.undeclare().await {
+                error!("Failed to undeclare queryable on {}: {}", key_expr, e);
+                ok = false;
+            }
+        }
+        if let Err(e) = self.session.close().await {
+            error!("Failed to close Zenoh session: {}", e);
+            ok = false;
+        }
+        ok
+    }
+}
@@ -0,0 +1,184 @@
+// Turns a panic anywhere in a node into a crash report and an exit. The report is written to
+// the node's crash file first, as that needs nothing but the disk, then published on
+// events/crash/<node> as sensors.CrashReport, best effort, as the session may be what
+// panicked. The process then exits with 101, the status of a panicking main, also when the
+// panic was in a spawned task that would otherwise have died alone.
+use crate::args::NodeArgs;
+use common::encoding;
+use keyspace::keys;
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zenoh::Wait;
+
+// Status a process whose main thread panics exits with.
+const EXIT_STATUS: i32 = 101;
+
+// How long the report is given to leave before the process exits.
+const LINGER: Duration = Duration::from_millis(200);
+
+// Where the hook reports to, set by install().
+struct Reporter {
+    session: zenoh::Session,
+    node: String,
+    dir: PathBuf,
+}
+
+static REPORTER: Mutex<Option<Reporter>> = Mutex::new(None);
+static HOOK: Once = Once::new();
+
+// What a crash report says about the build: the framework version, the commit if the build
+// was given one in GIT_COMMIT, the target and the profile.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const COMMIT: &str = match option_env!("GIT_COMMIT") {
+    Some(commit) => commit,
+    None => "",
+};
+const PROFILE: &str = if cfg!(debug_assertions) {
+    "debug"
+} else {
+    "release"
+};
+
+struct Report {
+    timestamp_us: u64,
+    node: String,
+    pid: u32,
+    message: String,
+    location: String,
+    thread: String,
+    backtrace: String,
+}
+
+impl Report {
+    fn of(node: &str, info: &PanicHookInfo) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        Report {
+            timestamp_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_micros() as u64)
+                .unwrap_or(0),
+            node: node.to_string(),
+            pid: std::process::id(),
+            message,
+            location: info
+                .location()
+                .map(|location| location.to_string())
+                .unwrap_or_default(),
+            thread: thread::current().name().unwrap_or("<unnamed>").to_string(),
+            backtrace: Backtrace::force_capture().to_string(),
+        }
+    }
+
+    // The report as the crash file holds it, one field a line and the backtrace last.
+    fn text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "node: {}", self.node);
+        let _ = writeln!(text, "pid: {}", self.pid);
+        let _ = writeln!(text, "timestamp_us: {}", self.timestamp_us);
+        let _ = writeln!(text, "message: {}", self.message);
+        let _ = writeln!(text, "location: {}", self.location);
+        let _ = writeln!(text, "thread: {}", self.thread);
+        let _ = writeln!(text, "version: {}", VERSION);
+        let _ = writeln!(text, "commit: {}", COMMIT);
+        let _ = writeln!(text, "target: {}", target());
+        let _ = writeln!(text, "profile: {}", PROFILE);
+        let _ = writeln!(text, "backtrace:\n{}", self.backtrace);
+        text
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(4096);
+        let node = builder.create_string(&self.node);
+        let message = builder.create_string(&self.message);
+        let location = builder.create_string(&self.location);
+        let thread = builder.create_string(&self.thread);
+        let backtrace = builder.create_string(&self.backtrace);
+        let version = builder.create_string(VERSION);
+        let commit = builder.create_string(COMMIT);
+        let target = builder.create_string(&target());
+        let profile = builder.create_string(PROFILE);
+        let report = sensors_rs::sensors::CrashReport::create(
+            &mut builder,
+            &sensors_rs::sensors::CrashReportArgs {
+                timestamp_us: self.timestamp_us,
+                node: Some(node),
+                pid: self.pid,
+                message: Some(message),
+                location: Some(location),
+                thread: Some(thread),
+                backtrace: Some(backtrace),
+                version: Some(version),
+                commit: Some(commit),
+                target: Some(target),
+                profile: Some(profile),
+            },
+        );
+        builder.finish(report, None);
+        builder.finished_data().to_vec()
+    }
+}
+
+fn target() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+// Reports panics of the process as crashes of the node, on the session, into the crash
+// directory of args, and exits after. Panics keep being printed to stderr as before. Nodes
+// with their own loop call it once their session is open; run() does for the nodes it
+// runs. The last call decides where reports go.
+pub fn install(session: &zenoh::Session, node: &str, args: &NodeArgs) {
+    *reporter() = Some(Reporter {
+        session: session.clone(),
+        node: node.to_string(),
+        dir: args.crash_dir.clone(),
+    });
+    HOOK.call_once(|| {
+        let print = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            print(info);
+            if let Some(reporter) = reporter().as_ref() {
+                report(reporter, info);
+            }
+            std::process::exit(EXIT_STATUS);
+        }));
+    });
+}
+
+fn reporter() -> std::sync::MutexGuard<'static, Option<Reporter>> {
+    REPORTER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Not logged through tracing past this point, as nothing queued for log/<node> is
+// published before the exit.
+fn report(reporter: &Reporter, info: &PanicHookInfo) {
+    let report = Report::of(&reporter.node, info);
+    let path = reporter
+        .dir
+        .join(format!("{}-{}.crash", reporter.node, report.timestamp_us));
+    match std::fs::create_dir_all(&reporter.dir).and_then(|()| std::fs::write(&path, report.text()))
+    {
+        Ok(()) => eprintln!("Crash report written to {}", path.display()),
+        Err(e) => eprintln!("Failed to write crash report {}: {}", path.display(), e),
+    }
+    let key = keys::crash(&reporter.node);
+    let published = reporter
+        .session
+        .put(&key, report.encode())
+        .encoding(encoding::flatbuffer("sensors.CrashReport"))
+        .express(true)
+        .wait();
+    match published {
+        Ok(()) => thread::sleep(LINGER),
+        Err(e) => eprintln!("Failed to publish crash report on {}: {}", key, e),
+    }
+}
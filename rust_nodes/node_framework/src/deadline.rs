@@ -0,0 +1,108 @@
+use crate::context::TimerId;
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zenoh::pubsub::Publisher;
+
+// A tick of a timer the node was not done with by the time the next one was due, handed to
+// Node::overrun. Response is the time from when the tick was due to when the node was done
+// with it, step the part of it the node spent stepping; the rest it waited for earlier
+// steps to finish.
+#[derive(Clone, Debug)]
+pub struct Overrun {
+    pub timer: TimerId,
+    pub period: Duration,
+    pub response: Duration,
+    pub step: Duration,
+    // Overruns of the timer since the node started, this one included.
+    pub count: u64,
+}
+
+// Checks every timer tick against its deadline, the next tick, and publishes the ticks
+// that overran it as DeadlineOverruns on events/overrun/<node> once declared to.
+pub(crate) struct DeadlineMonitor {
+    name: String,
+    publisher: Option<Publisher<'static>>,
+    counts: HashMap<TimerId, u64>,
+}
+
+impl DeadlineMonitor {
+    pub(crate) fn new(name: &str) -> Self {
+        DeadlineMonitor {
+            name: name.to_string(),
+            publisher: None,
+            counts: HashMap::new(),
+        }
+    }
+
+    // Declares the publisher of the overruns; they are only counted until then.
+    pub(crate) async fn declare(
+        &mut self,
+        session: &zenoh::Session,
+        qos: &QosConfig,
+    ) -> zenoh::Result<()> {
+        let key = keys::overrun(&self.name);
+        let publisher = session
+            .declare_publisher(key.clone())
+            .encoding(encoding::flatbuffer("sensors.DeadlineOverrun"))
+            .qos(qos, &key)
+            .await?;
+        self.publisher = Some(publisher);
+        Ok(())
+    }
+
+    // The overrun of a tick of a timer the node was done with response after it was due,
+    // having stepped for step, if it overran.
+    pub(crate) fn check(
+        &mut self,
+        timer: TimerId,
+        period: Duration,
+        response: Duration,
+        step: Duration,
+    ) -> Option<Overrun> {
+        if response <= period {
+            return None;
+        }
+        let count = self.counts.entry(timer).or_default();
+        *count += 1;
+        Some(Overrun {
+            timer,
+            period,
+            response,
+            step,
+            count: *count,
+        })
+    }
+
+    pub(crate) async fn publish(&self, overrun: &Overrun) -> zenoh::Result<()> {
+        match &self.publisher {
+            Some(publisher) => publisher.put(encode(&self.name, overrun)).await,
+            None => Ok(()),
+        }
+    }
+}
+
+fn encode(name: &str, overrun: &Overrun) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(64);
+    let node = builder.create_string(name);
+    let event = sensors::DeadlineOverrun::create(
+        &mut builder,
+        &sensors::DeadlineOverrunArgs {
+            timestamp_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_micros() as u64)
+                .unwrap_or(0),
+            node: Some(node),
+            timer: overrun.timer.0 as u32,
+            period_us: overrun.period.as_micros() as u64,
+            response_us: overrun.response.as_micros() as u64,
+            step_us: overrun.step.as_micros() as u64,
+            overruns: overrun.count,
+        },
+    );
+    builder.finish(event, None);
+    builder.finished_data().to_vec()
+}
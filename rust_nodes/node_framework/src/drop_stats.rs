@@ -0,0 +1,93 @@
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use common::sequence::DropTracker;
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::error;
+
+// Publishes what a node's subscriptions saw of sequenced samples as a DropStats on
+// stats/drops every period, from the first sequenced sample on.
+pub(crate) struct DropReporter {
+    task: JoinHandle<()>,
+}
+
+impl DropReporter {
+    pub(crate) async fn start(
+        session: &zenoh::Session,
+        name: &str,
+        period: Duration,
+        tracker: Arc<Mutex<DropTracker>>,
+        qos: &QosConfig,
+    ) -> zenoh::Result<Self> {
+        let publisher = session
+            .declare_publisher(keys::drop_stats())
+            .encoding(encoding::flatbuffer("sensors.DropStats"))
+            .qos(qos, keys::drop_stats())
+            .await?;
+        let name = name.to_string();
+        let task = tokio::spawn(async move {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let payload = {
+                    let tracker = tracker.lock().unwrap();
+                    if tracker.is_empty() {
+                        continue;
+                    }
+                    encode(&name, &tracker)
+                };
+                if let Err(e) = publisher.put(payload).await {
+                    error!("Failed to publish drop statistics: {}", e);
+                }
+            }
+        });
+        Ok(DropReporter { task })
+    }
+
+    pub(crate) fn stop(self) {
+        self.task.abort();
+    }
+}
+
+fn encode(name: &str, tracker: &DropTracker) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(256);
+    let streams: Vec<_> = tracker
+        .streams()
+        .map(|(key, publisher, stats)| {
+            let key = builder.create_string(key);
+            let publisher = builder.create_string(publisher);
+            sensors::KeyDrops::create(
+                &mut builder,
+                &sensors::KeyDropsArgs {
+                    key: Some(key),
+                    publisher: Some(publisher),
+                    received: stats.received,
+                    dropped: stats.dropped,
+                    restarts: stats.restarts,
+                    corrupted: stats.corrupted,
+                },
+            )
+        })
+        .collect();
+    let keys = builder.create_vector(&streams);
+    let node = builder.create_string(name);
+    let stats = sensors::DropStats::create(
+        &mut builder,
+        &sensors::DropStatsArgs {
+            timestamp_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_micros() as u64)
+                .unwrap_or(0),
+            node: Some(node),
+            keys: Some(keys),
+        },
+    );
+    builder.finish(stats, None);
+    builder.finished_data().to_vec()
+}
@@ -0,0 +1,91 @@
+use crate::context::{Event, TimerId};
+use common::ZenohConfigError;
+use std::fmt;
+use thiserror::Error;
+use tracing::error;
+use zenoh::key_expr::KeyExpr;
+
+// What a node was stepped with, for the report of a step that failed. Samples and queries
+// are named by their key expression, commands by their id. Taken before every step, so it
+// holds the key expression as received rather than a copy of it.
+#[derive(Clone, Debug)]
+pub enum EventContext {
+    Timer(TimerId),
+    Sample(KeyExpr<'static>),
+    Query(KeyExpr<'static>),
+    Command(String),
+    Param,
+    // The deadline overrun of a timer's tick, handed to Node::overrun.
+    Overrun(TimerId),
+}
+
+impl EventContext {
+    pub(crate) fn of(event: &Event) -> Self {
+        match event {
+            Event::Timer(id) => EventContext::Timer(*id),
+            Event::Sample(_, sample) => EventContext::Sample(sample.key_expr().clone()),
+            Event::Query(_, query) => EventContext::Query(query.key_expr().clone()),
+            Event::Command(request) => EventContext::Command(request.command.id.clone()),
+            Event::Param(_) => EventContext::Param,
+        }
+    }
+}
+
+impl fmt::Display for EventContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EventContext::Timer(id) => write!(f, "timer {}", id.0),
+            EventContext::Sample(key) => write!(f, "sample on {}", key),
+            EventContext::Query(key) => write!(f, "query on {}", key),
+            EventContext::Command(id) => write!(f, "command {}", id),
+            EventContext::Param => write!(f, "parameter change"),
+            EventContext::Overrun(id) => write!(f, "overrun of timer {}", id.0),
+        }
+    }
+}
+
+// Why running a node, or a part of it, failed, with the node it happened to. The runner
+// reports every one of them in one place; nodes with their own loop report theirs the same
+// way.
+#[derive(Debug, Error)]
+pub enum NodeError {
+    #[error("{0}")]
+    CommandKey(String),
+    #[error(transparent)]
+    Zenoh(#[from] ZenohConfigError),
+    #[error("failed to start {node}: {source}")]
+    Start { node: String, source: zenoh::Error },
+    #[error("{node}: step {step} on {event}: {source}")]
+    Step {
+        node: String,
+        step: u64,
+        event: EventContext,
+        source: zenoh::Error,
+    },
+    #[error("failed to declare {what}: {source}")]
+    Declare {
+        what: &'static str,
+        source: zenoh::Error,
+    },
+    #[error("failed to serve metrics on {addr}: {source}")]
+    Metrics {
+        addr: std::net::SocketAddr,
+        source: std::io::Error,
+    },
+    #[error("{node}: failed to withdraw liveliness token: {source}")]
+    Withdraw { node: String, source: zenoh::Error },
+    #[error("failed to shut down {node}: {source}")]
+    Shutdown { node: String, source: zenoh::Error },
+}
+
+impl NodeError {
+    // For map_err on the declaration of what: `.map_err(NodeError::declare("publisher"))?`.
+    pub fn declare(what: &'static str) -> impl FnOnce(zenoh::Error) -> NodeError {
+        move |source| NodeError::Declare { what, source }
+    }
+
+    // Logs the error, the one way node errors are reported.
+    pub fn report(&self) {
+        error!("{}", self);
+    }
+}
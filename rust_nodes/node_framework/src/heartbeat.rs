@@ -0,0 +1,122 @@
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::error;
+use zenoh::liveliness::LivelinessToken;
+
+// Main loop statistics reported with each heartbeat.
+#[derive(Default)]
+struct LoopStats {
+    cycles: u64,
+    errors: u64,
+    // Passes since the previous heartbeat and their total and longest duration.
+    window_cycles: u64,
+    window_total: Duration,
+    window_max: Duration,
+}
+
+// Announces a running node: a liveliness token on @/alive/<name> for as long as it is up,
+// and a Heartbeat on heartbeat/<name> every period with its uptime and loop statistics.
+// The token disappears by itself when the process dies; the heartbeat stopping while the
+// token is still there means the node hangs.
+pub struct Heartbeat {
+    token: LivelinessToken,
+    stats: Arc<Mutex<LoopStats>>,
+    task: JoinHandle<()>,
+}
+
+impl Heartbeat {
+    pub async fn start(
+        session: &zenoh::Session,
+        name: &str,
+        period: Duration,
+    ) -> zenoh::Result<Self> {
+        Self::start_with_qos(session, name, period, &QosConfig::default()).await
+    }
+
+    // Like start, publishing heartbeats with the QoS of the node's config.
+    pub(crate) async fn start_with_qos(
+        session: &zenoh::Session,
+        name: &str,
+        period: Duration,
+        qos: &QosConfig,
+    ) -> zenoh::Result<Self> {
+        let key = keys::heartbeat(name);
+        let token = session
+            .liveliness()
+            .declare_token(keys::alive(name))
+            .await?;
+        let publisher = session
+            .declare_publisher(key.clone())
+            .encoding(encoding::flatbuffer("sensors.Heartbeat"))
+            .qos(qos, &key)
+            .await?;
+
+        let stats = Arc::new(Mutex::new(LoopStats::default()));
+        let task_stats = stats.clone();
+        let name = name.to_string();
+        let started = Instant::now();
+        let task = tokio::spawn(async move {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let payload = encode(&name, started.elapsed(), &mut task_stats.lock().unwrap());
+                if let Err(e) = publisher.put(payload).await {
+                    error!("Failed to publish heartbeat: {}", e);
+                }
+            }
+        });
+
+        Ok(Heartbeat { token, stats, task })
+    }
+
+    // Records one pass through the node's main loop and whether it failed.
+    pub fn record(&self, elapsed: Duration, ok: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.cycles += 1;
+        if !ok {
+            stats.errors += 1;
+        }
+        stats.window_cycles += 1;
+        stats.window_total += elapsed;
+        stats.window_max = stats.window_max.max(elapsed);
+    }
+
+    // Stops the heartbeats and withdraws the liveliness token.
+    pub async fn stop(self) -> zenoh::Result<()> {
+        self.task.abort();
+        self.token.undeclare().await
+    }
+}
+
+// Encodes a heartbeat and starts a new statistics window.
+fn encode(name: &str, uptime: Duration, stats: &mut LoopStats) -> Vec<u8> {
+    let mean = match stats.window_cycles {
+        0 => Duration::ZERO,
+        n => stats.window_total / n as u32,
+    };
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(64);
+    let node = builder.create_string(name);
+    let heartbeat = sensors::Heartbeat::create(
+        &mut builder,
+        &sensors::HeartbeatArgs {
+            node: Some(node),
+            uptime_s: uptime.as_secs_f64(),
+            cycles: stats.cycles,
+            errors: stats.errors,
+            mean_cycle_us: mean.as_secs_f32() * 1e6,
+            max_cycle_us: stats.window_max.as_secs_f32() * 1e6,
+        },
+    );
+    builder.finish(heartbeat, None);
+    stats.window_cycles = 0;
+    stats.window_total = Duration::ZERO;
+    stats.window_max = Duration::ZERO;
+    builder.finished_data().to_vec()
+}
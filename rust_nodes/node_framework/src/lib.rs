@@ -0,0 +1,50 @@
+// Shared lifecycle for Zenoh nodes. A node implements Node, declares its timers,
+// subscriptions and queryables on the Context during init, and is handed one Event at a
+// time in step; run() owns the session, the heartbeat, the shutdown signal and the
+// teardown around it.
+// Every node logs through tracing once logging::init is called, publishes its warnings and
+// errors on log/<node>, and answers log_level on cmd/<node>/log_level to change what it
+// logs without a restart.
+// Every timer tick is checked against the next one; a node not done with it by then has
+// the overrun published on events/overrun/<node> and handed to Node::overrun.
+// A node that panics writes a crash report to its crash directory and publishes it on
+// events/crash/<node> before it exits, once crash::install is called.
+// Built with the prometheus feature, a node started with --metrics-addr serves what it
+// records through the metrics crate for Prometheus.
+// A node stepped with a sample published in a traced span steps within a span continuing
+// that trace, and built with the otel feature exports its spans over OTLP.
+// Nodes with their own loop can still announce themselves with Heartbeat, LogControl and
+// crash::install. Node logic that gets, puts, or subscribes through the Bus trait can be
+// unit-tested on MockBus.
+mod args;
+mod arming;
+mod bus;
+mod command_handler;
+mod context;
+pub mod crash;
+mod deadline;
+mod drop_stats;
+mod error;
+mod heartbeat;
+mod log_publisher;
+pub mod logging;
+mod node;
+mod node_stats;
+mod param_file;
+mod param_server;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod runner;
+pub mod trace;
+
+pub use args::NodeArgs;
+pub use bus::{Bus, GetOptions, Message, MockBus, Subscription, ZenohBus};
+pub use command_handler::{CommandId, CommandRequest};
+pub use context::{Context, Event, QueryableId, SubscriptionId, TimerId};
+pub use deadline::Overrun;
+pub use error::{EventContext, NodeError};
+pub use heartbeat::Heartbeat;
+pub use logging::LogControl;
+pub use node::Node;
+pub use param_server::{ParamChanged, ParamId};
+pub use runner::run;
@@ -0,0 +1,247 @@
+// Publishes the warnings and errors a node logs on log/<node> as sensors.LogRecord, so the
+// ground and the recorder capture them alongside telemetry. The layer only queues encoded
+// records and a task publishes them, so logging never waits on the bus. Zenoh's own
+// warnings stay on stderr, as publishing them could make Zenoh log more of them.
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::fmt::{self, Write as _};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+// Where the layer queues records while a node publishes them.
+struct Sink {
+    node: String,
+    records: mpsc::UnboundedSender<Vec<u8>>,
+}
+
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+fn sink() -> std::sync::MutexGuard<'static, Option<Sink>> {
+    SINK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Records queued and not yet published, and the most there were since depth() was last
+// called.
+static QUEUED: AtomicUsize = AtomicUsize::new(0);
+static MAX_QUEUED: AtomicUsize = AtomicUsize::new(0);
+
+// How many records are waiting to be published, and the most that were since the
+// previous call.
+pub(crate) fn depth() -> (usize, usize) {
+    let depth = QUEUED.load(Ordering::Relaxed);
+    (depth, MAX_QUEUED.swap(depth, Ordering::Relaxed).max(depth))
+}
+
+// The fields of a span as text, kept in the span's extensions for the records logged
+// within it.
+#[derive(Default)]
+struct SpanFields(String);
+
+// Writes fields as name=value, space separated, leaving strings unquoted.
+struct FieldWriter<'a>(&'a mut String);
+
+impl Visit for FieldWriter<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let separator = if self.0.is_empty() { "" } else { " " };
+        let _ = write!(self.0, "{}{}={:?}", separator, field.name(), value);
+    }
+}
+
+// The message of an event and its other fields.
+#[derive(Default)]
+struct EventFields {
+    message: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Visit for EventFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let value = format!("{:?}", value);
+        match field.name() {
+            "message" => self.message = value,
+            name => self.fields.push((name, value)),
+        }
+    }
+}
+
+pub(crate) struct BusLayer;
+
+impl<S> Layer<S> for BusLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = SpanFields::default();
+        attrs.record(&mut FieldWriter(&mut fields.0));
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut FieldWriter(&mut fields.0));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > Level::WARN || metadata.target().starts_with("zenoh") {
+            return;
+        }
+        let sink = sink();
+        let Some(sink) = sink.as_ref() else {
+            return;
+        };
+        let mut fields = EventFields::default();
+        event.record(&mut fields);
+        let mut spans = String::new();
+        for span in ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+        {
+            let separator = if spans.is_empty() { "" } else { ":" };
+            let _ = write!(spans, "{}{}", separator, span.name());
+            if let Some(SpanFields(values)) = span
+                .extensions()
+                .get::<SpanFields>()
+                .filter(|SpanFields(values)| !values.is_empty())
+            {
+                let _ = write!(spans, "{{{}}}", values);
+            }
+        }
+        let level = match *metadata.level() {
+            Level::ERROR => sensors::LogLevel::Error,
+            _ => sensors::LogLevel::Warn,
+        };
+        let record = encode(&sink.node, level, metadata.target(), &fields, &spans);
+        // Counted before it is sent, so the task never takes it off the count first.
+        let depth = QUEUED.fetch_add(1, Ordering::Relaxed) + 1;
+        MAX_QUEUED.fetch_max(depth, Ordering::Relaxed);
+        if sink.records.send(record).is_err() {
+            QUEUED.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn encode(
+    node: &str,
+    level: sensors::LogLevel,
+    target: &str,
+    fields: &EventFields,
+    spans: &str,
+) -> Vec<u8> {
+    let timestamp_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0);
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(256);
+    let node = builder.create_string(node);
+    let target = builder.create_string(target);
+    let message = builder.create_string(&fields.message);
+    let spans = builder.create_string(spans);
+    let entries: Vec<_> = fields
+        .fields
+        .iter()
+        .map(|(name, value)| {
+            let name = builder.create_string(name);
+            let value = builder.create_string(value);
+            sensors::LogField::create(
+                &mut builder,
+                &sensors::LogFieldArgs {
+                    name: Some(name),
+                    value: Some(value),
+                },
+            )
+        })
+        .collect();
+    let entries = builder.create_vector(&entries);
+    let record = sensors::LogRecord::create(
+        &mut builder,
+        &sensors::LogRecordArgs {
+            timestamp_us,
+            node: Some(node),
+            level,
+            target: Some(target),
+            message: Some(message),
+            spans: Some(spans),
+            fields: Some(entries),
+        },
+    );
+    builder.finish(record, None);
+    builder.finished_data().to_vec()
+}
+
+// Publishes what the layer queues on log/<node> until stopped or dropped, for the one node
+// of the process.
+pub(crate) struct LogPublisher {
+    task: JoinHandle<()>,
+}
+
+impl LogPublisher {
+    pub(crate) async fn start(
+        session: &zenoh::Session,
+        node: &str,
+        qos: &QosConfig,
+    ) -> zenoh::Result<Self> {
+        let key = keys::node_log(node);
+        let publisher = session
+            .declare_publisher(key.clone())
+            .encoding(encoding::flatbuffer("sensors.LogRecord"))
+            .qos(qos, &key)
+            .await?;
+        let (records, mut queued) = mpsc::unbounded_channel();
+        *sink() = Some(Sink {
+            node: node.to_string(),
+            records,
+        });
+        let task = tokio::spawn(async move {
+            while let Some(record) = queued.recv().await {
+                QUEUED.fetch_sub(1, Ordering::Relaxed);
+                if let Err(e) = publisher.put(record).await {
+                    // Not logged through tracing, where the failure would be queued for
+                    // publishing in turn.
+                    eprintln!("Failed to publish log record: {}", e);
+                }
+            }
+        });
+        Ok(LogPublisher { task })
+    }
+
+    // Stops queueing records and returns once those already queued are published.
+    pub(crate) async fn stop(mut self) {
+        sink().take();
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for LogPublisher {
+    fn drop(&mut self) {
+        sink().take();
+        self.task.abort();
+        QUEUED.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::args::NodeArgs;
+use crate::command_handler;
+use crate::error::NodeError;
+use crate::log_publisher::{BusLayer, LogPublisher};
+#[cfg(feature = "otel")]
+use crate::trace;
+use command::{Key, Nack, NackReason};
+use common::qos::QosConfig;
+use keyspace::keys;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+use tokio::sync::mpsc;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry, fmt, reload};
+use zenoh::query::Queryable;
+
+// Name of the built-in command replacing the log filter of a running node, taking the new
+// filter as filter=<directives>, e.g. filter=debug or filter=info,fusion=trace.
+pub(crate) const LOG_LEVEL: &str = "log_level";
+
+// What nodes log unless told otherwise: their own events, and only Zenoh's warnings.
+pub const DEFAULT_FILTER: &str = "info,zenoh=warn";
+
+// The filter of the installed logger, swapped by the log_level command.
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+// Checks a filter given on the command line, for clap.
+pub(crate) fn parse_filter(directives: &str) -> Result<String, String> {
+    EnvFilter::try_new(directives)
+        .map(|_| directives.to_string())
+        .map_err(|e| e.to_string())
+}
+
+// Installs the process's logger, writing what passes the given filter, e.g. "info" or
+// "info,fusion=debug", to stderr, in color on a terminal, and the warnings and errors among
+// it also on log/<node> once the node publishes its log. Built with the otel feature, it
+// also exports the spans that pass the filter; see trace.rs. Nodes call it first thing, as
+// events logged before are lost; run() calls it too. Only the first call installs the
+// logger, later ones are ignored.
+pub fn init(directives: &str) {
+    FILTER.get_or_init(|| {
+        let filter =
+            EnvFilter::try_new(directives).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+        let (filter, handle) = reload::Layer::new(filter);
+        let registry = tracing_subscriber::registry()
+            .with(filter)
+            .with(
+                fmt::layer()
+                    .with_writer(std::io::stderr)
+                    .with_ansi(std::io::stderr().is_terminal()),
+            )
+            .with(BusLayer);
+        #[cfg(feature = "otel")]
+        let registry = registry.with(
+            trace::otel::tracer().map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)),
+        );
+        let _ = registry.try_init();
+        handle
+    });
+}
+
+// Replaces the filter of the installed logger, returning a report of the change.
+pub(crate) fn set_filter(directives: &str) -> Result<String, Nack> {
+    let failed = |e: reload::Error| Nack::new(NackReason::Failed, e.to_string());
+    let handle = FILTER
+        .get()
+        .ok_or_else(|| Nack::new(NackReason::Failed, "logging is not initialized"))?;
+    let filter = EnvFilter::try_new(directives).map_err(|e| {
+        Nack::new(
+            NackReason::InvalidArgs,
+            format!("invalid filter '{}': {}", directives, e),
+        )
+    })?;
+    let previous = handle
+        .with_current(|filter| filter.to_string())
+        .map_err(failed)?;
+    handle.reload(filter).map_err(failed)?;
+    Ok(format!(
+        "log filter set to {} (was {})",
+        directives, previous
+    ))
+}
+
+// Answers log_level on cmd/<node>/log_level and publishes the node's log on log/<node>,
+// for nodes with their own loop; run() does both for the nodes it runs. Commands are
+// checked against the node's command key like run() does. Kept up until dropped, when the
+// spans not exported yet are.
+pub struct LogControl {
+    _queryable: Queryable<()>,
+    _publisher: LogPublisher,
+}
+
+impl LogControl {
+    pub async fn start(
+        session: &zenoh::Session,
+        name: &str,
+        args: &NodeArgs,
+    ) -> Result<Self, NodeError> {
+        let key = args
+            .command_key
+            .as_deref()
+            .map(Key::load)
+            .transpose()
+            .map_err(NodeError::CommandKey)?;
+        let registry = command_handler::registry(session, name, key);
+        // Nothing is queued for a step, as log_level is answered as it arrives. Declared on
+        // the one key only, so the node's own commands are left to the node.
+        let (events, _) = mpsc::unbounded_channel();
+        let queryable = session
+            .declare_queryable(keys::command(name, LOG_LEVEL))
+            .callback(move |query| command_handler::dispatch(&registry, &events, query))
+            .await
+            .map_err(NodeError::declare("log level command"))?;
+        let publisher = LogPublisher::start(session, name, &QosConfig::default())
+            .await
+            .map_err(NodeError::declare("log publisher"))?;
+        Ok(LogControl {
+            _queryable: queryable,
+            _publisher: publisher,
+        })
+    }
+}
+
+impl Drop for LogControl {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        trace::otel::flush();
+    }
+}
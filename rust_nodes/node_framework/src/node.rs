@@ -0,0 +1,33 @@
+use crate::context::{Context, Event};
+use crate::deadline::Overrun;
+use std::future::{Future, ready};
+
+pub trait Node {
+    // Name the runner reports the node under.
+    const NAME: &'static str;
+
+    // Declares the node's timers, subscriptions and publishers. The node is not stepped if
+    // this fails.
+    fn init(&mut self, ctx: &mut Context) -> impl Future<Output = zenoh::Result<()>>;
+
+    // Handles one timer tick or received sample. Errors are reported and the node keeps
+    // running; call Context::stop to end it.
+    fn step(&mut self, ctx: &mut Context, event: Event) -> impl Future<Output = zenoh::Result<()>>;
+
+    // Called after a step on a timer tick the node was not done with by the time the next
+    // tick was due, once the overrun is published, e.g. to shed work until the node keeps up
+    // again. Errors are reported like those of step.
+    fn overrun(
+        &mut self,
+        _ctx: &mut Context,
+        _overrun: &Overrun,
+    ) -> impl Future<Output = zenoh::Result<()>> {
+        ready(Ok(()))
+    }
+
+    // Releases whatever init declared besides timers and subscriptions, which the runner
+    // undeclares itself. Called after a failed init too.
+    fn shutdown(&mut self, _ctx: &mut Context) -> impl Future<Output = zenoh::Result<()>> {
+        ready(Ok(()))
+    }
+}
@@ -0,0 +1,240 @@
+use crate::context::TimerId;
+use crate::log_publisher;
+use common::encoding;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::error;
+
+// Clock ticks per second of the CPU times in /proc, the same on every Linux platform.
+const USER_HZ: u64 = 100;
+
+// How one timer kept to its period since the previous NodeStats.
+#[derive(Default)]
+struct TimerWindow {
+    period: Duration,
+    ticks: u64,
+    total_jitter: Duration,
+    max_jitter: Duration,
+    missed: u64,
+    overruns: u64,
+}
+
+// How deep the node's event queue was, when last stepped and at most since the previous
+// NodeStats.
+#[derive(Default)]
+struct QueueWindow {
+    depth: usize,
+    max_depth: usize,
+}
+
+// What the runner measures of a node's loop for its NodeStats.
+#[derive(Default)]
+pub(crate) struct LoopTiming {
+    timers: Vec<TimerWindow>,
+    events: QueueWindow,
+}
+
+impl LoopTiming {
+    // Records a tick of a timer the node was stepped with jitter after it was due. Ticks
+    // a whole period or more late were skipped, as timers drop the ticks they miss.
+    pub(crate) fn tick(&mut self, timer: TimerId, period: Duration, jitter: Duration) {
+        if self.timers.len() <= timer.0 {
+            self.timers.resize_with(timer.0 + 1, TimerWindow::default);
+        }
+        let window = &mut self.timers[timer.0];
+        window.period = period;
+        window.ticks += 1;
+        window.total_jitter += jitter;
+        window.max_jitter = window.max_jitter.max(jitter);
+        window.missed += (jitter.as_nanos() / period.as_nanos().max(1)) as u64;
+        metrics::histogram!("node_timer_jitter_seconds", "timer" => timer.0.to_string())
+            .record(jitter);
+    }
+
+    // Records a tick of a timer the node was not done with by the time the next was due.
+    pub(crate) fn overrun(&mut self, timer: TimerId) {
+        if let Some(window) = self.timers.get_mut(timer.0) {
+            window.overruns += 1;
+        }
+        metrics::counter!("node_deadline_overruns_total", "timer" => timer.0.to_string())
+            .increment(1);
+    }
+
+    // Records how many events were left waiting when the node was stepped with one.
+    pub(crate) fn queued(&mut self, depth: usize) {
+        self.events.depth = depth;
+        self.events.max_depth = self.events.max_depth.max(depth);
+    }
+}
+
+// Publishes a node's resource usage, how its timers kept to their periods, and how deep
+// its event and log queues got as a NodeStats on stats/<node> every period, so a node
+// falling behind shows before it misses cycles.
+pub(crate) struct StatsReporter {
+    task: JoinHandle<()>,
+}
+
+impl StatsReporter {
+    pub(crate) async fn start(
+        session: &zenoh::Session,
+        name: &str,
+        period: Duration,
+        timing: Arc<Mutex<LoopTiming>>,
+        qos: &QosConfig,
+    ) -> zenoh::Result<Self> {
+        let key = keys::node_stats(name);
+        let publisher = session
+            .declare_publisher(key.clone())
+            .encoding(encoding::flatbuffer("sensors.NodeStats"))
+            .qos(qos, &key)
+            .await?;
+        let name = name.to_string();
+        let task = tokio::spawn(async move {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            ticker.tick().await;
+            let mut cpu = CpuUsage::new();
+            loop {
+                ticker.tick().await;
+                let cpu_percent = cpu.percent();
+                let payload = encode(&name, cpu_percent, &mut timing.lock().unwrap());
+                if let Err(e) = publisher.put(payload).await {
+                    error!("Failed to publish node statistics: {}", e);
+                }
+            }
+        });
+        Ok(StatsReporter { task })
+    }
+
+    pub(crate) fn stop(self) {
+        self.task.abort();
+    }
+}
+
+// The CPU time the process used between two readings, as a percentage of one core.
+struct CpuUsage {
+    read_at: Instant,
+    used: Option<Duration>,
+}
+
+impl CpuUsage {
+    fn new() -> Self {
+        CpuUsage {
+            read_at: Instant::now(),
+            used: cpu_time(),
+        }
+    }
+
+    fn percent(&mut self) -> f32 {
+        let previous = std::mem::replace(self, CpuUsage::new());
+        match (previous.used, self.used) {
+            (Some(before), Some(after)) => {
+                let elapsed = self.read_at.duration_since(previous.read_at);
+                after.saturating_sub(before).as_secs_f32() / elapsed.as_secs_f32().max(1e-6) * 100.0
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+// User and system CPU time the process used since it started, from /proc/self/stat; None
+// where there is no /proc.
+fn cpu_time() -> Option<Duration> {
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // The command name may hold spaces, so fields are counted from the ')' closing it, with
+    // the state, the third field, first.
+    let mut fields = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .skip(11);
+    let user: u64 = fields.next()?.parse().ok()?;
+    let system: u64 = fields.next()?.parse().ok()?;
+    Some(Duration::from_millis((user + system) * 1000 / USER_HZ))
+}
+
+// Resident memory of the process in bytes, from /proc/self/status; None where there is
+// no /proc.
+fn rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim();
+    Some(kb.parse::<u64>().ok()? * 1024)
+}
+
+// Encodes the statistics and starts new timer and queue windows.
+fn encode(name: &str, cpu_percent: f32, timing: &mut LoopTiming) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(256);
+    let timers: Vec<_> = timing
+        .timers
+        .iter_mut()
+        .map(|window| {
+            let mean = match window.ticks {
+                0 => Duration::ZERO,
+                n => window.total_jitter / n as u32,
+            };
+            let stats = sensors::TimerStats::create(
+                &mut builder,
+                &sensors::TimerStatsArgs {
+                    period_us: window.period.as_micros() as u64,
+                    ticks: window.ticks,
+                    mean_jitter_us: mean.as_secs_f32() * 1e6,
+                    max_jitter_us: window.max_jitter.as_secs_f32() * 1e6,
+                    missed: window.missed,
+                    overruns: window.overruns,
+                },
+            );
+            *window = TimerWindow {
+                period: window.period,
+                ..TimerWindow::default()
+            };
+            stats
+        })
+        .collect();
+    let timers = builder.create_vector(&timers);
+    let (log_depth, log_max_depth) = log_publisher::depth();
+    let queues: Vec<_> = [
+        ("events", timing.events.depth, timing.events.max_depth),
+        ("log", log_depth, log_max_depth),
+    ]
+    .into_iter()
+    .map(|(name, depth, max_depth)| {
+        let name = builder.create_string(name);
+        sensors::QueueStats::create(
+            &mut builder,
+            &sensors::QueueStatsArgs {
+                name: Some(name),
+                depth: depth as u64,
+                max_depth: max_depth as u64,
+            },
+        )
+    })
+    .collect();
+    timing.events.max_depth = timing.events.depth;
+    let queues = builder.create_vector(&queues);
+    let node = builder.create_string(name);
+    let stats = sensors::NodeStats::create(
+        &mut builder,
+        &sensors::NodeStatsArgs {
+            timestamp_us: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_micros() as u64)
+                .unwrap_or(0),
+            node: Some(node),
+            rss_bytes: rss_bytes().unwrap_or(0),
+            cpu_percent,
+            timers: Some(timers),
+            queues: Some(queues),
+        },
+    );
+    builder.finish(stats, None);
+    builder.finished_data().to_vec()
+}
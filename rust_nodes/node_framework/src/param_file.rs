@@ -0,0 +1,111 @@
+use params::Parameter;
+use std::path::PathBuf;
+
+// Format of the files parameters are saved in. Bump it when the layout changes in a way
+// older nodes would misread; nodes leave files of other versions alone.
+const VERSION: i64 = 1;
+
+// The values a node's parameters were set to, saved as TOML so they survive restarts:
+//
+//     version = 1
+//
+//     [values]
+//     "voting/imu" = 6.0
+//
+// Only values that differ from their default are kept. Each is checked against its
+// parameter when declared, like a set, so a parameter whose type or range has changed
+// since falls back to its default instead of taking a value it no longer accepts.
+// Values of parameters the node does not declare, e.g. ones of another build, are kept
+// as they are.
+pub(crate) struct ParamFile {
+    path: PathBuf,
+    values: toml::Table,
+}
+
+impl ParamFile {
+    // Reads the file, or starts an empty one if there is none yet.
+    pub(crate) fn load(path: PathBuf) -> Result<Self, String> {
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(ParamFile {
+                    path,
+                    values: toml::Table::new(),
+                });
+            }
+            Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+        };
+        let mut file: toml::Table =
+            toml::from_str(&text).map_err(|e| format!("cannot parse {}: {}", path.display(), e))?;
+        match file.get("version").and_then(toml::Value::as_integer) {
+            Some(VERSION) => {}
+            Some(version) => {
+                return Err(format!(
+                    "{} is in format version {}, this node reads version {}",
+                    path.display(),
+                    version,
+                    VERSION
+                ));
+            }
+            None => return Err(format!("{} has no format version", path.display())),
+        }
+        let values = match file.remove("values") {
+            Some(toml::Value::Table(values)) => values,
+            Some(_) => return Err(format!("values in {} is not a table", path.display())),
+            None => toml::Table::new(),
+        };
+        Ok(ParamFile { path, values })
+    }
+
+    // The saved value of a parameter as text, to be checked with Parameter::validate.
+    pub(crate) fn saved(&self, name: &str) -> Option<String> {
+        self.values.get(name).map(|value| match value {
+            toml::Value::String(text) => text.clone(),
+            value => value.to_string(),
+        })
+    }
+
+    // Drops a saved value, e.g. one its parameter no longer accepts.
+    pub(crate) fn discard(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    // Drops the saved values of parameters matching a name, declared or not.
+    pub(crate) fn discard_matching(&mut self, matches: impl Fn(&str) -> bool) {
+        self.values.retain(|name, _| !matches(name));
+    }
+
+    // Records a parameter's current value, or forgets it if it is back at its default.
+    pub(crate) fn record(&mut self, parameter: &Parameter) {
+        if parameter.value == parameter.default {
+            self.values.remove(&parameter.name);
+            return;
+        }
+        let value = match &parameter.value {
+            params::Value::Float(v) => toml::Value::Float(*v),
+            params::Value::Int(v) => toml::Value::Integer(*v),
+            params::Value::Bool(v) => toml::Value::Boolean(*v),
+            params::Value::Text(v) => toml::Value::String(v.clone()),
+        };
+        self.values.insert(parameter.name.clone(), value);
+    }
+
+    // Writes the file, replacing the previous one only once the new one is complete.
+    pub(crate) fn save(&self) -> Result<(), String> {
+        let mut file = toml::Table::new();
+        file.insert("version".to_string(), toml::Value::Integer(VERSION));
+        file.insert(
+            "values".to_string(),
+            toml::Value::Table(self.values.clone()),
+        );
+        let text = toml::to_string(&file).map_err(|e| e.to_string())?;
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        let partial = self.path.with_extension("toml.partial");
+        std::fs::write(&partial, text)
+            .and_then(|()| std::fs::rename(&partial, &self.path))
+            .map_err(|e| format!("cannot write {}: {}", self.path.display(), e))
+    }
+}
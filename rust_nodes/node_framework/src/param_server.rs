@@ -0,0 +1,287 @@
+use crate::context::Event;
+use crate::param_file::ParamFile;
+use command::Key;
+use common::encoding;
+use keyspace::keys;
+use params::{Parameter, Type};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use zenoh::Wait;
+use zenoh::key_expr::OwnedKeyExpr;
+use zenoh::query::Query;
+
+// Identifies a parameter declared with Context::declare_param, as the type it was
+// declared as.
+pub struct ParamId<T> {
+    index: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for ParamId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ParamId<T> {}
+
+impl<T> PartialEq for ParamId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for ParamId<T> {}
+
+impl<T> fmt::Debug for ParamId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ParamId({})", self.index)
+    }
+}
+
+// Which parameter an Event::Param reports as set; Context::param reads its new value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamChanged(usize);
+
+impl ParamChanged {
+    pub fn is<T>(self, id: ParamId<T>) -> bool {
+        self.0 == id.index
+    }
+}
+
+pub(crate) struct Params {
+    node: String,
+    key: Option<Key>,
+    parameters: Vec<(OwnedKeyExpr, Parameter)>,
+    // Where set values are saved, if anywhere.
+    file: Option<ParamFile>,
+}
+
+// The node's parameters, shared with the queryable's callback.
+pub(crate) type Store = Arc<Mutex<Params>>;
+
+// A file that cannot be read back, e.g. one written by a newer build, is left alone and
+// the node runs on its defaults without saving anything.
+pub(crate) fn store(node: &str, key: Option<Key>, file: Option<PathBuf>) -> Store {
+    let file = file.and_then(|path| match ParamFile::load(path) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            warn!("Not saving parameters: {}", e);
+            None
+        }
+    });
+    Arc::new(Mutex::new(Params {
+        node: node.to_string(),
+        key,
+        parameters: Vec::new(),
+        file,
+    }))
+}
+
+fn lock(store: &Store) -> MutexGuard<'_, Params> {
+    store.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub(crate) fn declare<T: Type>(
+    store: &Store,
+    name: &str,
+    default: T,
+    range: Option<RangeInclusive<T>>,
+) -> Result<ParamId<T>, String> {
+    let mut guard = lock(store);
+    let params = &mut *guard;
+    let key = keys::param(&params.node, name);
+    let key_expr = OwnedKeyExpr::try_from(key.clone())
+        .ok()
+        .filter(|key_expr| !key_expr.is_wild())
+        .ok_or_else(|| format!("invalid parameter name '{}'", name))?;
+    if params.parameters.iter().any(|(_, p)| p.name == name) {
+        return Err(format!("parameter {} declared twice", name));
+    }
+    let default = default.into_value();
+    let (min, max) = match range {
+        Some(range) => {
+            let (min, max) = range.into_inner();
+            (Some(min.into_value()), Some(max.into_value()))
+        }
+        None => (None, None),
+    };
+    let mut parameter = Parameter {
+        node: params.node.clone(),
+        name: name.to_string(),
+        value: default.clone(),
+        default,
+        min,
+        max,
+    };
+    parameter
+        .validate(&parameter.default.to_string())
+        .map_err(|e| format!("default of parameter {}: {}", name, e))?;
+    if let Some(file) = &mut params.file {
+        match file.saved(name).map(|text| parameter.validate(&text)) {
+            Some(Ok(value)) => {
+                info!("Parameter {} restored to {}", name, value);
+                parameter.value = value;
+            }
+            Some(Err(e)) => {
+                warn!("Ignoring saved value of parameter {}: {}", name, e);
+                file.discard(name);
+            }
+            None => {}
+        }
+    }
+    params.parameters.push((key_expr, parameter));
+    Ok(ParamId {
+        index: params.parameters.len() - 1,
+        _type: PhantomData,
+    })
+}
+
+pub(crate) fn get<T: Type>(store: &Store, id: ParamId<T>) -> T {
+    let params = lock(store);
+    T::from_value(&params.parameters[id.index].1.value)
+        .expect("parameters only take values of their declared type")
+}
+
+// Checks a set's or reset's signature, if the node checks them. Resets carry no payload
+// and are signed over their key expression followed by ?reset.
+fn verify(params: &Params, query: &Query, signed: &str, payload: &[u8]) -> Result<(), String> {
+    let Some(key) = &params.key else {
+        return Ok(());
+    };
+    let attachment = query.attachment().map(|a| a.to_bytes());
+    key.verify(signed, payload, attachment.as_deref())
+        .map_err(|nack| nack.message)
+}
+
+// Sets the parameter a query is for from its payload. Returns the parameter's index and
+// whether its value changed.
+fn set(params: &mut Params, query: &Query, payload: &[u8]) -> Result<(usize, bool), String> {
+    verify(params, query, query.key_expr().as_str(), payload)?;
+    let index = params
+        .parameters
+        .iter()
+        .position(|(key_expr, _)| key_expr.as_str() == query.key_expr().as_str())
+        .ok_or_else(|| format!("no parameter on {}", query.key_expr()))?;
+    let parameter = &mut params.parameters[index].1;
+    let text = std::str::from_utf8(payload).map_err(|e| format!("invalid value: {}", e))?;
+    let value = parameter.validate(text)?;
+    let changed = value != parameter.value;
+    parameter.value = value;
+    Ok((index, changed))
+}
+
+// Returns every parameter the query's key matches to its default, and forgets their saved
+// values. Returns their indices and whether each value changed.
+fn reset(params: &mut Params, query: &Query) -> Result<Vec<(usize, bool)>, String> {
+    let signed = format!("{}?{}", query.key_expr(), params::RESET);
+    verify(params, query, &signed, &[])?;
+    let mut reset = Vec::new();
+    for (index, (key_expr, parameter)) in params.parameters.iter_mut().enumerate() {
+        if query.key_expr().intersects(key_expr) {
+            let changed = parameter.value != parameter.default;
+            parameter.value = parameter.default.clone();
+            reset.push((index, changed));
+        }
+    }
+    if let Some(file) = &mut params.file {
+        let node = &params.node;
+        file.discard_matching(|name| {
+            OwnedKeyExpr::try_from(keys::param(node, name))
+                .is_ok_and(|key_expr| query.key_expr().intersects(&key_expr))
+        });
+    }
+    if reset.is_empty() {
+        return Err(format!("no parameter on {}", query.key_expr()));
+    }
+    Ok(reset)
+}
+
+fn reply(query: &Query, key_expr: &OwnedKeyExpr, parameter: &Parameter) {
+    let _ = query
+        .reply(key_expr.clone(), parameter.encode())
+        .encoding(encoding::flatbuffer("sensors.Parameter"))
+        .wait();
+}
+
+// Publishes a parameter's new value on its key and hands it to step.
+fn changed(
+    session: &zenoh::Session,
+    events: &mpsc::UnboundedSender<Event>,
+    index: usize,
+    key_expr: &OwnedKeyExpr,
+    parameter: &Parameter,
+) {
+    let published = session
+        .put(key_expr.clone(), parameter.encode())
+        .encoding(encoding::flatbuffer("sensors.Parameter"))
+        .wait();
+    if let Err(e) = published {
+        error!("Failed to publish parameter {}: {}", parameter.name, e);
+    }
+    let _ = events.send(Event::Param(ParamChanged(index)));
+}
+
+// Records the current values in the parameter file, if the node has one.
+fn save(params: &mut Params) {
+    let Some(file) = &mut params.file else {
+        return;
+    };
+    for (_, parameter) in &params.parameters {
+        file.record(parameter);
+    }
+    if let Err(e) = file.save() {
+        error!("Failed to save parameters: {}", e);
+    }
+}
+
+// Called on every query on param/<node>/**. A query without a payload gets every
+// parameter its key matches; one with a payload sets the parameter on its key to the
+// value it holds as text, and one with the selector parameter reset returns every
+// parameter its key matches to its default. Values that change are saved to the node's
+// parameter file, published on the parameter's key and handed to step as an Event::Param.
+pub(crate) fn dispatch(
+    store: &Store,
+    session: &zenoh::Session,
+    events: &mpsc::UnboundedSender<Event>,
+    query: Query,
+) {
+    let mut guard = lock(store);
+    let params = &mut *guard;
+    let payload = query.payload().map(|p| p.to_bytes().into_owned());
+    let result = match payload {
+        _ if query.parameters().contains_key(params::RESET) => reset(params, &query),
+        Some(payload) => set(params, &query, &payload).map(|set| vec![set]),
+        None => {
+            for (key_expr, parameter) in &params.parameters {
+                if query.key_expr().intersects(key_expr) {
+                    reply(&query, key_expr, parameter);
+                }
+            }
+            return;
+        }
+    };
+
+    match result {
+        Ok(updates) => {
+            save(params);
+            for (index, updated) in updates {
+                let (key_expr, parameter) = &params.parameters[index];
+                reply(&query, key_expr, parameter);
+                if updated {
+                    info!("Parameter {} set to {}", parameter.name, parameter.value);
+                    changed(session, events, index, key_expr, parameter);
+                }
+            }
+        }
+        Err(e) => {
+            warn!("Refused parameter update on {}: {}", query.key_expr(), e);
+            let _ = query.reply_err(e).wait();
+        }
+    }
+}
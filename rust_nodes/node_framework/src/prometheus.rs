@@ -0,0 +1,85 @@
+// Serves what the node records through the metrics crate in Prometheus text format on
+// GET /metrics, for ground-side dashboards. Without it, or before it is started, recording
+// a metric does nothing. Every metric carries the node's name as its node label.
+use axum::Router;
+use axum::routing::get;
+use metrics::{Unit, describe_counter, describe_histogram};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+// Bucket bounds of every histogram, in seconds: from the 10 ms cycle of fusion down to its
+// tenth of a millisecond, and up to the timeout of a slow query.
+const BUCKETS: [f64; 13] = [
+    0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
+
+// The listener; the metrics recorded stay installed for the life of the process.
+pub(crate) struct MetricsServer {
+    task: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    pub(crate) async fn start(addr: SocketAddr, node: &str) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let handle = PrometheusBuilder::new()
+            .add_global_label("node", node)
+            .set_buckets(&BUCKETS)
+            .and_then(|builder| builder.install_recorder())
+            .map_err(std::io::Error::other)?;
+        describe();
+        info!(
+            "Serving metrics on http://{}/metrics",
+            listener.local_addr()?
+        );
+        let app = Router::new().route("/metrics", get(move || render(handle.clone())));
+        let task = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                error!("Metrics server failed: {}", e);
+            }
+        });
+        Ok(MetricsServer { task })
+    }
+
+    pub(crate) fn stop(self) {
+        self.task.abort();
+    }
+}
+
+async fn render(handle: PrometheusHandle) -> String {
+    handle.run_upkeep();
+    handle.render()
+}
+
+// The metrics the framework records for every node.
+fn describe() {
+    describe_counter!(
+        "zenoh_samples_published_total",
+        "Samples published through sequenced publishers, by key."
+    );
+    describe_counter!(
+        "zenoh_samples_received_total",
+        "Samples received on the node's subscriptions, by key, before any are dropped."
+    );
+    describe_histogram!(
+        "zenoh_query_duration_seconds",
+        Unit::Seconds,
+        "Time from sending a query through the bus to its first reply or timeout, by key."
+    );
+    describe_histogram!(
+        "node_step_duration_seconds",
+        Unit::Seconds,
+        "Time the node spent stepping with one event."
+    );
+    describe_histogram!(
+        "node_timer_jitter_seconds",
+        Unit::Seconds,
+        "How late the node was stepped with a timer tick after it was due, by timer."
+    );
+    describe_counter!(
+        "node_deadline_overruns_total",
+        "Timer ticks the node was not done with by the time the next was due, by timer."
+    );
+}
@@ -0,0 +1,257 @@
+use crate::args::NodeArgs;
+use crate::context::{Context, Event};
+use crate::crash;
+use crate::deadline::DeadlineMonitor;
+use crate::drop_stats::DropReporter;
+use crate::error::{EventContext, NodeError};
+use crate::heartbeat::Heartbeat;
+use crate::log_publisher::LogPublisher;
+use crate::logging;
+use crate::node::Node;
+use crate::node_stats::StatsReporter;
+#[cfg(feature = "prometheus")]
+use crate::prometheus::MetricsServer;
+use crate::trace;
+use command::Key;
+use common::shutdown_signal;
+use std::process::ExitCode;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::{Instrument, Span, info, info_span};
+
+// Opens a session, initializes the node and steps it with timer ticks, samples, queries
+// and commands until shutdown is signalled or the node stops itself, then tears
+// everything down. The node answers the built-in log_level command whether it registers
+// commands or not, and reports a panic as a crash before the process exits. It holds its
+// liveliness token, sends heartbeats, and publishes its warnings and errors on log/<node>
+// from a successful init until shutdown, and reports the drops its subscriptions saw on
+// stats/drops and its resource usage, timer jitter and queue depths on stats/<node>. A
+// timer tick the node is not done with by the time the next is due is published as an
+// overrun on events/overrun/<node> and handed to Node::overrun. A sample published in a
+// traced span is stepped within a span continuing its trace. Every error is reported
+// as a NodeError naming the node, and a failed step also the step's number and what the
+// node was stepped with. Fails if init or any part of the teardown fails.
+pub async fn run<N: Node>(mut node: N, args: &NodeArgs) -> ExitCode {
+    logging::init(&args.log_level);
+    let name = args.name(N::NAME);
+    let command_key = match args.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            NodeError::CommandKey(e).report();
+            return ExitCode::FAILURE;
+        }
+    };
+    // The node runs on without it, its metrics unrecorded.
+    #[cfg(feature = "prometheus")]
+    let metrics = match args.metrics_addr {
+        Some(addr) => match MetricsServer::start(addr, name).await {
+            Ok(server) => Some(server),
+            Err(source) => {
+                NodeError::Metrics { addr, source }.report();
+                None
+            }
+        },
+        None => None,
+    };
+    #[cfg(feature = "shm")]
+    let shm = match args.zenoh.shm_pool() {
+        Ok(shm) => shm,
+        Err(e) => {
+            NodeError::from(e).report();
+            return ExitCode::FAILURE;
+        }
+    };
+    let session = match args.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            NodeError::from(e).report();
+            return ExitCode::FAILURE;
+        }
+    };
+    crash::install(&session, name, args);
+    let (tx, mut events) = mpsc::unbounded_channel();
+    let mut ctx = Context::new(
+        session,
+        name,
+        command_key,
+        args.param_file(name),
+        args.crc32c,
+        args.compress.clone(),
+        tx,
+    );
+    #[cfg(feature = "shm")]
+    ctx.set_shm(shm);
+
+    let mut status = ExitCode::SUCCESS;
+    let initialized = match ctx.serve_commands().await {
+        Ok(_) => node.init(&mut ctx).await,
+        Err(e) => Err(e),
+    };
+    let started = match initialized {
+        Ok(()) => {
+            match Heartbeat::start_with_qos(ctx.session(), name, args.heartbeat_period(), ctx.qos())
+                .await
+            {
+                Ok(heartbeat) => {
+                    let period = args.drop_stats_period();
+                    match DropReporter::start(ctx.session(), name, period, ctx.drops(), ctx.qos())
+                        .await
+                    {
+                        Ok(reporter) => Ok((heartbeat, reporter)),
+                        Err(e) => {
+                            let _ = heartbeat.stop().await;
+                            Err(e)
+                        }
+                    }
+                }
+                Err(e) => Err(e),
+            }
+        }
+        Err(e) => Err(e),
+    };
+    match started {
+        Ok((heartbeat, reporter)) => {
+            // The node runs on without it, logging to stderr only.
+            let log = match LogPublisher::start(ctx.session(), name, ctx.qos()).await {
+                Ok(log) => Some(log),
+                Err(source) => {
+                    NodeError::Declare {
+                        what: "log publisher",
+                        source,
+                    }
+                    .report();
+                    None
+                }
+            };
+            // The node runs on without it too, unobserved.
+            let period = args.stats_period();
+            let stats =
+                match StatsReporter::start(ctx.session(), name, period, ctx.timing(), ctx.qos())
+                    .await
+                {
+                    Ok(stats) => Some(stats),
+                    Err(source) => {
+                        NodeError::Declare {
+                            what: "node statistics publisher",
+                            source,
+                        }
+                        .report();
+                        None
+                    }
+                };
+            let mut deadlines = DeadlineMonitor::new(name);
+            // Overruns are still handed to the node without it, just not published.
+            if let Err(source) = deadlines.declare(ctx.session(), ctx.qos()).await {
+                NodeError::Declare {
+                    what: "overrun publisher",
+                    source,
+                }
+                .report();
+            }
+            let timing = ctx.timing();
+            let shutdown = shutdown_signal();
+            tokio::pin!(shutdown);
+            let mut step: u64 = 0;
+            while !ctx.stopped() {
+                let mut tick = None;
+                let event = tokio::select! {
+                    biased;
+                    _ = &mut shutdown => break,
+                    (timer, due) = ctx.next_timer() => {
+                        tick = Some((timer, due));
+                        Event::Timer(timer)
+                    }
+                    event = events.recv() => match event {
+                        Some(event) => {
+                            timing.lock().unwrap().queued(events.len());
+                            event
+                        }
+                        None => break,
+                    },
+                };
+                step += 1;
+                let context = EventContext::of(&event);
+                let span = match &event {
+                    Event::Sample(_, sample) => match trace::of(sample) {
+                        Some(parent) => {
+                            let span = info_span!("sample", key = %sample.key_expr());
+                            trace::continue_from(&span, parent);
+                            span
+                        }
+                        None => Span::none(),
+                    },
+                    _ => Span::none(),
+                };
+                let started = Instant::now();
+                let stepped = node.step(&mut ctx, event).instrument(span).await;
+                let elapsed = started.elapsed();
+                heartbeat.record(elapsed, stepped.is_ok());
+                metrics::histogram!("node_step_duration_seconds").record(elapsed);
+                if let Err(source) = stepped {
+                    NodeError::Step {
+                        node: name.to_string(),
+                        step,
+                        event: context,
+                        source,
+                    }
+                    .report();
+                }
+                // A tick is due by the next one, whether the node was late to start it or
+                // slow to step it.
+                let overrun = tick.and_then(|(timer, due)| {
+                    deadlines
+                        .check(timer, ctx.timer_period(timer), due.elapsed(), elapsed)
+                        .map(|overrun| (timer, overrun))
+                });
+                if let Some((timer, overrun)) = overrun {
+                    timing.lock().unwrap().overrun(timer);
+                    let published = deadlines.publish(&overrun).await;
+                    let handled = node.overrun(&mut ctx, &overrun).await;
+                    for source in [published.err(), handled.err()].into_iter().flatten() {
+                        NodeError::Step {
+                            node: name.to_string(),
+                            step,
+                            event: EventContext::Overrun(timer),
+                            source,
+                        }
+                        .report();
+                    }
+                }
+            }
+            reporter.stop();
+            if let Some(stats) = stats {
+                stats.stop();
+            }
+            if let Some(log) = log {
+                log.stop().await;
+            }
+            if let Err(source) = heartbeat.stop().await {
+                let node = name.to_string();
+                NodeError::Withdraw { node, source }.report();
+                status = ExitCode::FAILURE;
+            }
+        }
+        Err(source) => {
+            let node = name.to_string();
+            NodeError::Start { node, source }.report();
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    info!("Shutting down {}...", name);
+    if let Err(source) = node.shutdown(&mut ctx).await {
+        let node = name.to_string();
+        NodeError::Shutdown { node, source }.report();
+        status = ExitCode::FAILURE;
+    }
+    if !ctx.close().await {
+        status = ExitCode::FAILURE;
+    }
+    #[cfg(feature = "prometheus")]
+    if let Some(metrics) = metrics {
+        metrics.stop();
+    }
+    #[cfg(feature = "otel")]
+    trace::otel::flush();
+    status
+}
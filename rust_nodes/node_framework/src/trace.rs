@@ -0,0 +1,118 @@
+// Continues traces across nodes. Sequenced publishers attach the context of the span a
+// sample is published in, and a node receiving the sample continues its trace in a span
+// of its own, so a trace follows a sensor sample through fusion and the controller to the
+// actuators. Built with the otel feature and OTEL_EXPORTER_OTLP_ENDPOINT set, the node's
+// spans are exported over OTLP/HTTP, named after OTEL_SERVICE_NAME or else the node's
+// executable and sampled as OTEL_TRACES_SAMPLER says; otherwise no span is exported or
+// continued and no context attached.
+use common::sequence::Sequence;
+pub use common::trace::TraceContext;
+use tracing::Span;
+use zenoh::sample::Sample;
+
+// The context of the span a sample was published in, if it was traced.
+pub fn of(sample: &Sample) -> Option<TraceContext> {
+    Sequence::of(sample)?.trace
+}
+
+// Makes span part of the trace of context, as its child. Only a span not yet entered can
+// be, so call it right after creating the span.
+#[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+pub fn continue_from(span: &Span, context: TraceContext) {
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        let parent = opentelemetry::Context::new().with_remote_span_context(otel::span(context));
+        let _ = span.set_parent(parent);
+    }
+}
+
+// Links span to the span of context, for spans that follow from more than one.
+#[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+pub fn link(span: &Span, context: TraceContext) {
+    #[cfg(feature = "otel")]
+    {
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        span.add_link(otel::span(context));
+    }
+}
+
+#[cfg(feature = "otel")]
+pub(crate) mod otel {
+    use super::TraceContext;
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider,
+    };
+    use opentelemetry_otlp::SpanExporter;
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+    use std::sync::OnceLock;
+    use tracing::Span;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+    // The tracer spans are exported with, if an OTLP endpoint is set. Sequenced publishers
+    // attach the context of the current span from then on.
+    pub(crate) fn tracer() -> Option<SdkTracer> {
+        let endpoint = [
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+        ];
+        if !endpoint.iter().any(|name| std::env::var_os(name).is_some()) {
+            return None;
+        }
+        let exporter = match SpanExporter::builder().with_http().build() {
+            Ok(exporter) => exporter,
+            Err(e) => {
+                eprintln!("Not exporting spans: {}", e);
+                return None;
+            }
+        };
+        let mut resource = Resource::builder();
+        let name = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem()?.to_str().map(str::to_string))
+            .filter(|_| std::env::var_os("OTEL_SERVICE_NAME").is_none());
+        if let Some(name) = name {
+            resource = resource.with_service_name(name);
+        }
+        let provider = PROVIDER.get_or_init(|| {
+            SdkTracerProvider::builder()
+                .with_batch_exporter(exporter)
+                .with_resource(resource.build())
+                .build()
+        });
+        common::trace::set_current(current);
+        Some(provider.tracer("node_framework"))
+    }
+
+    // Exports the spans not exported yet, before the process exits.
+    pub(crate) fn flush() {
+        if let Some(Err(e)) = PROVIDER.get().map(SdkTracerProvider::force_flush) {
+            eprintln!("Failed to export spans: {}", e);
+        }
+    }
+
+    fn current() -> Option<TraceContext> {
+        let context = Span::current().context();
+        let span = context.span();
+        let span = span.span_context();
+        span.is_valid().then(|| TraceContext {
+            trace_id: span.trace_id().to_bytes(),
+            span_id: span.span_id().to_bytes(),
+            flags: span.trace_flags().to_u8(),
+        })
+    }
+
+    pub(super) fn span(context: TraceContext) -> SpanContext {
+        SpanContext::new(
+            TraceId::from_bytes(context.trace_id),
+            SpanId::from_bytes(context.span_id),
+            TraceFlags::new(context.flags),
+            true,
+            TraceState::default(),
+        )
+    }
+}
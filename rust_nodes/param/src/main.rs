@@ -0,0 +1,172 @@
+use clap::{Parser, Subcommand};
+use command::Key;
+use common::ZenohArgs;
+use keyspace::keys;
+use params::Parameter;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::time::timeout;
+use zenoh::sample::Locality;
+
+#[derive(Parser)]
+#[command(about = "Gets, sets and resets node parameters on param/<node>/<name>")]
+struct Args {
+    #[command(subcommand)]
+    action: Action,
+    /// How long to wait for the node to be reachable and to reply, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    timeout_ms: u64,
+    /// File holding the secret to sign sets and resets with, for nodes that check signatures.
+    #[arg(long, value_name = "PATH", env = "COMMAND_KEY")]
+    command_key: Option<PathBuf>,
+    #[command(flatten)]
+    zenoh: ZenohArgs,
+}
+
+#[derive(Subcommand)]
+enum Action {
+    /// Prints the node's parameters matching NAME.
+    Get {
+        node: String,
+        /// Parameter name or key expression, e.g. voting/imu or voting/*.
+        #[arg(default_value = "**")]
+        name: String,
+    },
+    /// Sets a parameter and prints its new value.
+    Set {
+        node: String,
+        name: String,
+        value: String,
+    },
+    /// Returns the node's parameters matching NAME to their defaults and prints them.
+    Reset {
+        node: String,
+        /// Parameter name or key expression, e.g. voting/imu or voting/*.
+        #[arg(default_value = "**")]
+        name: String,
+    },
+}
+
+// What a query asks of the node.
+enum Request<'a> {
+    Get,
+    Set(&'a str),
+    Reset,
+}
+
+// Sends one query once the node's parameter queryable is reachable and returns the
+// parameters replied.
+async fn query(
+    session: &zenoh::Session,
+    key: &str,
+    request: Request<'_>,
+    signer: Option<&Key>,
+    wait: Duration,
+) -> Result<Vec<Parameter>, String> {
+    let querier = session
+        .declare_querier(key.to_string())
+        .allowed_destination(Locality::Any)
+        .timeout(wait)
+        .await
+        .map_err(|e| format!("cannot declare querier on {}: {}", key, e))?;
+    // A fresh session has not discovered the node yet; querying right away would reach
+    // nobody.
+    let listener = querier
+        .matching_listener()
+        .await
+        .map_err(|e| e.to_string())?;
+    let matching = querier
+        .matching_status()
+        .await
+        .map_err(|e| e.to_string())?
+        .matching();
+    if !matching {
+        let found = timeout(wait, async {
+            while let Ok(status) = listener.recv_async().await {
+                if status.matching() {
+                    return true;
+                }
+            }
+            false
+        })
+        .await;
+        if found != Ok(true) {
+            return Err(format!("nothing answers on {}", key));
+        }
+    }
+
+    let mut get = querier.get();
+    match request {
+        Request::Get => {}
+        Request::Set(value) => {
+            let attachment = signer.map(|signer| signer.sign(key, value.as_bytes()));
+            get = get.payload(value).attachment(attachment);
+        }
+        Request::Reset => {
+            let signed = format!("{}?{}", key, params::RESET);
+            let attachment = signer.map(|signer| signer.sign(&signed, &[]));
+            get = get.parameters(params::RESET).attachment(attachment);
+        }
+    }
+    let replies = get.await.map_err(|e| e.to_string())?;
+    let mut parameters = Vec::new();
+    while let Ok(reply) = replies.recv_async().await {
+        match reply.result() {
+            Ok(sample) => {
+                let parameter = Parameter::decode(&sample.payload().to_bytes())
+                    .map_err(|e| format!("malformed parameter on {}: {}", sample.key_expr(), e))?;
+                parameters.push(parameter);
+            }
+            Err(e) => return Err(String::from_utf8_lossy(&e.payload().to_bytes()).into_owned()),
+        }
+    }
+    Ok(parameters)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let signer = match args.command_key.as_deref().map(Key::load).transpose() {
+        Ok(signer) => signer,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (key, request) = match &args.action {
+        Action::Get { node, name } => (keys::param(node, name), Request::Get),
+        Action::Set { node, name, value } => (keys::param(node, name), Request::Set(value)),
+        Action::Reset { node, name } => (keys::param(node, name), Request::Reset),
+    };
+
+    let session = match args.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let wait = Duration::from_millis(args.timeout_ms);
+    let status = match query(&session, &key, request, signer.as_ref(), wait).await {
+        Ok(parameters) if parameters.is_empty() => {
+            eprintln!("No parameters on {}", key);
+            ExitCode::FAILURE
+        }
+        Ok(mut parameters) => {
+            parameters.sort_by(|a, b| a.name.cmp(&b.name));
+            for parameter in parameters {
+                println!("{}", parameter);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    };
+    if let Err(e) = session.close().await {
+        eprintln!("Failed to close Zenoh session: {}", e);
+    }
+    status
+}
@@ -0,0 +1,239 @@
+// Tunable node parameters served on param/<node>/<name>, encoded as sensors::Parameter.
+// Nodes declare them through node_framework's parameter server; gets and sets are queries
+// on the parameter's key, a set carrying the new value as text.
+use sensors_rs::sensors;
+use std::fmt;
+
+// Selector parameter that makes a query on param/<node>/<name> return the parameters it
+// matches to their defaults, e.g. param/fusion/**?reset. Resets carry no payload and are
+// signed over the key expression followed by ?reset.
+pub const RESET: &str = "reset";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Float,
+    Int,
+    Bool,
+    Text,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Float(f64),
+    Int(i64),
+    Bool(bool),
+    Text(String),
+}
+
+impl Value {
+    pub fn kind(&self) -> Kind {
+        match self {
+            Value::Float(_) => Kind::Float,
+            Value::Int(_) => Kind::Int,
+            Value::Bool(_) => Kind::Bool,
+            Value::Text(_) => Kind::Text,
+        }
+    }
+
+    pub fn parse(kind: Kind, text: &str) -> Result<Value, String> {
+        let text = text.trim();
+        let invalid = |e: &dyn fmt::Display| format!("invalid {:?} '{}': {}", kind, text, e);
+        match kind {
+            Kind::Float => match text.parse::<f64>() {
+                Ok(v) if v.is_finite() => Ok(Value::Float(v)),
+                Ok(_) => Err(invalid(&"not finite")),
+                Err(e) => Err(invalid(&e)),
+            },
+            Kind::Int => text.parse().map(Value::Int).map_err(|e| invalid(&e)),
+            Kind::Bool => text.parse().map(Value::Bool).map_err(|e| invalid(&e)),
+            Kind::Text => Ok(Value::Text(text.to_string())),
+        }
+    }
+
+    // Numeric values as f64, for range checks.
+    fn number(&self) -> Option<f64> {
+        match self {
+            Value::Float(v) => Some(*v),
+            Value::Int(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+// Rust types a parameter can be declared as.
+pub trait Type: Sized {
+    fn into_value(self) -> Value;
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl Type for f64 {
+    fn into_value(self) -> Value {
+        Value::Float(self)
+    }
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl Type for i64 {
+    fn into_value(self) -> Value {
+        Value::Int(self)
+    }
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl Type for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl Type for String {
+    fn into_value(self) -> Value {
+        Value::Text(self)
+    }
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Text(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+}
+
+// A parameter with its current value and the values it may take.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    pub node: String,
+    pub name: String,
+    pub value: Value,
+    pub default: Value,
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+}
+
+impl Parameter {
+    pub fn kind(&self) -> Kind {
+        self.default.kind()
+    }
+
+    // Parses a value to set the parameter to, refusing ones of the wrong type or out of
+    // range.
+    pub fn validate(&self, text: &str) -> Result<Value, String> {
+        let value = Value::parse(self.kind(), text)?;
+        let number = value.number();
+        match (number, self.min.as_ref().and_then(Value::number)) {
+            (Some(v), Some(min)) if v < min => {
+                return Err(format!("{} is below the minimum {}", value, min));
+            }
+            _ => {}
+        }
+        match (number, self.max.as_ref().and_then(Value::number)) {
+            (Some(v), Some(max)) if v > max => {
+                return Err(format!("{} is above the maximum {}", value, max));
+            }
+            _ => {}
+        }
+        Ok(value)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+        let text = |value: &Option<Value>| value.as_ref().map(Value::to_string).unwrap_or_default();
+        let node = builder.create_string(&self.node);
+        let name = builder.create_string(&self.name);
+        let value = builder.create_string(&self.value.to_string());
+        let default_value = builder.create_string(&self.default.to_string());
+        let min = builder.create_string(&text(&self.min));
+        let max = builder.create_string(&text(&self.max));
+        let parameter = sensors::Parameter::create(
+            &mut builder,
+            &sensors::ParameterArgs {
+                node: Some(node),
+                name: Some(name),
+                type_: match self.kind() {
+                    Kind::Float => sensors::ParamType::Float,
+                    Kind::Int => sensors::ParamType::Int,
+                    Kind::Bool => sensors::ParamType::Bool,
+                    Kind::Text => sensors::ParamType::Text,
+                },
+                value: Some(value),
+                default_value: Some(default_value),
+                min: Some(min),
+                max: Some(max),
+            },
+        );
+        builder.finish(parameter, None);
+        builder.finished_data().to_vec()
+    }
+
+    pub fn decode(payload: &[u8]) -> Result<Self, String> {
+        let parameter =
+            flatbuffers::root::<sensors::Parameter>(payload).map_err(|e| e.to_string())?;
+        let kind = match parameter.type_() {
+            sensors::ParamType::Float => Kind::Float,
+            sensors::ParamType::Int => Kind::Int,
+            sensors::ParamType::Bool => Kind::Bool,
+            sensors::ParamType::Text => Kind::Text,
+            other => return Err(format!("unknown parameter type {:?}", other)),
+        };
+        let bound = |text: Option<&str>| match text.unwrap_or_default() {
+            "" => Ok(None),
+            text => Value::parse(kind, text).map(Some),
+        };
+        Ok(Parameter {
+            node: parameter.node().unwrap_or_default().to_string(),
+            name: parameter.name().unwrap_or_default().to_string(),
+            value: Value::parse(kind, parameter.value().unwrap_or_default())?,
+            default: Value::parse(kind, parameter.default_value().unwrap_or_default())?,
+            min: bound(parameter.min())?,
+            max: bound(parameter.max())?,
+        })
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = {} ({:?}, default {}",
+            self.name,
+            self.value,
+            self.kind(),
+            self.default
+        )?;
+        match (&self.min, &self.max) {
+            (None, None) => {}
+            (min, max) => {
+                let bound =
+                    |v: &Option<Value>| v.as_ref().map(Value::to_string).unwrap_or_default();
+                write!(f, ", range [{}, {}]", bound(min), bound(max))?;
+            }
+        }
+        write!(f, ")")
+    }
+}
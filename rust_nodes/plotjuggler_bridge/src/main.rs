@@ -0,0 +1,138 @@
+use clap::{Parser, ValueEnum};
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tracing::{debug, info};
+use zenoh::sample::{Sample, SampleKind};
+
+// Field PlotJuggler's UDP server takes the time of a message from, in seconds, when its
+// "use field as timestamp" option is set.
+const TIMESTAMP_FIELD: &str = "timestamp";
+
+// The encodings PlotJuggler's UDP server parses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Json,
+    Msgpack,
+}
+
+#[derive(Parser)]
+#[command(
+    about = "Streams decoded telemetry to PlotJuggler's UDP server as JSON or MessagePack, for live plots"
+)]
+struct Args {
+    /// Key expression to stream. May be repeated.
+    #[arg(long, default_values_t = [keys::all_devices().to_string(), keys::all_state().to_string()])]
+    key: Vec<String>,
+    /// Address of PlotJuggler's UDP server.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:9870")]
+    udp_addr: SocketAddr,
+    /// Encoding to send, to match the protocol picked in PlotJuggler.
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct PlotJugglerBridge {
+    keys: Vec<String>,
+    addr: SocketAddr,
+    format: Format,
+    socket: Option<UdpSocket>,
+    // Keys whose samples are not streamed, reported once each.
+    skipped: HashSet<String>,
+}
+
+// When the sample was published, by its Zenoh timestamp, else when it arrived, in seconds
+// since the Unix epoch. The timestamps payloads carry are not used, as they run on
+// simulated time in simulated-time runs while the sensor readings carry none.
+fn sample_time_s(sample: &Sample) -> f64 {
+    sample
+        .timestamp()
+        .map_or_else(SystemTime::now, |t| t.get_time().to_system_time())
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+impl PlotJugglerBridge {
+    // One message per sample, with the decoded payload under its key, which PlotJuggler
+    // flattens into series named after the key and the fields, e.g.
+    // devices/imu0/acceleration_x or state/fused/attitude/x.
+    fn message(&self, sample: &Sample, value: Value) -> zenoh::Result<Vec<u8>> {
+        let mut message = Map::new();
+        message.insert(TIMESTAMP_FIELD.to_string(), sample_time_s(sample).into());
+        message.insert(sample.key_expr().to_string(), value);
+        let message = Value::Object(message);
+        Ok(match self.format {
+            Format::Json => serde_json::to_vec(&message)?,
+            Format::Msgpack => rmp_serde::to_vec_named(&message)?,
+        })
+    }
+
+    async fn on_sample(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = sample.key_expr().as_str();
+        let Some(value) = telemetry_json::decode(sample).map_err(|e| format!("{}: {}", key, e))?
+        else {
+            if self.skipped.insert(key.to_string()) {
+                debug!(
+                    "Not streaming {}: no conversion for {}",
+                    key,
+                    sample.encoding()
+                );
+            }
+            return Ok(());
+        };
+        let message = self.message(sample, value)?;
+        if let Some(socket) = &self.socket {
+            socket.send_to(&message, self.addr).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Node for PlotJugglerBridge {
+    const NAME: &'static str = "plotjuggler_bridge";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let local: SocketAddr = match self.addr {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        self.socket = Some(UdpSocket::bind(local).await?);
+        info!("Streaming to PlotJuggler on udp://{}", self.addr);
+        for key in &self.keys {
+            ctx.subscribe(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(&sample).await,
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = PlotJugglerBridge {
+        keys: args.key,
+        addr: args.udp_addr,
+        format: args.format,
+        socket: None,
+        skipped: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -1,31 +1,112 @@
+use clap::Parser;
+use common::encoding;
+use common::publication_cache::PublicationCache;
+use common::qos::PublisherBuilderQosExt;
+use common::sequence::{self, SequencedPublisher};
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
 use rand::Rng;
-use std::thread;
+use sensors_rs::sensors;
+use std::process::ExitCode;
 use std::time::Duration;
-use tokio;
-use zenoh;
-use zenoh_ext::z_deserialize;
-use zenoh_ext::z_serialize;
+use tracing::info;
+
+#[derive(Parser)]
+#[command(about = "Publishes a random temperature")]
+struct Args {
+    /// Key expression the temperature is published on.
+    #[arg(long, default_value = keys::temp())]
+    key: String,
+    /// Publish period in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    period_ms: u64,
+    /// Id of the sensor the readings are published as.
+    #[arg(long, default_value_t = 0)]
+    sensor_id: u32,
+    /// Answer queries on the key with the last SAMPLES readings published, so nodes that
+    /// start late read them right away.
+    #[arg(long, value_name = "SAMPLES", value_parser = clap::value_parser!(u64).range(1..))]
+    publication_cache: Option<u64>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
 
 fn read_temp() -> f32 {
     let mut rng = rand::rng();
     rng.random_range(0.0..100.0)
 }
 
-#[tokio::main]
-async fn main() {
-    let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+struct PubTest {
+    key: String,
+    period: Duration,
+    sensor_id: u32,
+    publisher: Option<SequencedPublisher<'static>>,
+    publication_cache: Option<u64>,
+    cache: Option<PublicationCache>,
+}
+
+impl Node for PubTest {
+    const NAME: &'static str = "pub_test";
 
-    loop {
-        let ftemp = read_temp();
-        let ftemp = z_serialize(&ftemp);
-        let deser_ftemp: f32 = z_deserialize(&ftemp).unwrap();
-        println!("Deserialized temperature: {}", deser_ftemp);
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let publisher = sequence::cached(
+            ctx.session()
+                .declare_publisher(self.key.clone())
+                .encoding(encoding::flatbuffer("sensors.Temperature"))
+                .qos(ctx.qos(), &self.key),
+        )
+        .await?;
+        self.publisher = Some(ctx.sequenced_cached(publisher));
+        if let Some(history) = self.publication_cache {
+            let cache = PublicationCache::declare(ctx.session(), &self.key, history as usize);
+            self.cache = Some(cache.await?);
+        }
+        ctx.add_timer(self.period);
+        Ok(())
+    }
 
-        session
-            .put("devices/temp", ftemp)
-            .await
-            .expect("failed to put data");
+    async fn step(&mut self, _ctx: &mut Context, _event: Event) -> zenoh::Result<()> {
+        let Some(publisher) = &self.publisher else {
+            return Ok(());
+        };
+        let value = read_temp();
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(32);
+        let temperature = sensors::Temperature::create(
+            &mut builder,
+            &sensors::TemperatureArgs {
+                value,
+                sensor_id: self.sensor_id,
+                valid: true,
+            },
+        );
+        builder.finish(temperature, None);
+        info!("Published temperature: {}", value);
+        publisher.put(builder.finished_data().to_vec()).await
+    }
 
-        thread::sleep(Duration::from_secs(1));
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(cache) = self.cache.take() {
+            cache.undeclare().await?;
+        }
+        match self.publisher.take() {
+            Some(publisher) => publisher.undeclare().await,
+            None => Ok(()),
+        }
     }
 }
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    args.node.zenoh.timestamping = args.publication_cache.is_some();
+    let node = PubTest {
+        key: args.key.clone(),
+        period: Duration::from_millis(args.period_ms),
+        sensor_id: args.sensor_id,
+        publisher: None,
+        publication_cache: args.publication_cache,
+        cache: None,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,18 @@
+use node_framework::NodeError;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+// Why recording could not start.
+#[derive(Debug, Error)]
+pub enum RecorderError {
+    // Arguments nothing can be recorded with.
+    #[error("{0}")]
+    Args(&'static str),
+    #[error("invalid recorder config: {0}")]
+    Config(String),
+    #[error("cannot create {}: {source}", path.display())]
+    Create { path: PathBuf, source: io::Error },
+    #[error(transparent)]
+    Node(#[from] NodeError),
+}
@@ -0,0 +1,94 @@
+use flight_log::LogReader;
+use keyspace::keys;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use zenoh::Wait;
+use zenoh::bytes::Encoding;
+use zenoh::key_expr::KeyExpr;
+use zenoh::query::{Parameters, Query};
+
+// Receive times a query asks for, in nanoseconds since the Unix epoch, both inclusive.
+struct Window {
+    start_ns: u64,
+    end_ns: u64,
+}
+
+// Reads the start and end parameters, in seconds since the Unix epoch; a missing bound
+// leaves that side of the window open.
+fn window(parameters: &Parameters) -> Result<Window, String> {
+    let bound = |name: &str, default: u64| match parameters.get(name) {
+        None => Ok(default),
+        Some(value) => match value.parse::<f64>() {
+            Ok(s) if s >= 0.0 => Ok((s * 1e9) as u64),
+            _ => Err(format!(
+                "{} must be seconds since the Unix epoch, not '{}'",
+                name, value
+            )),
+        },
+    };
+    let window = Window {
+        start_ns: bound("start", 0)?,
+        end_ns: bound("end", u64::MAX)?,
+    };
+    if window.start_ns > window.end_ns {
+        return Err("start is after end".to_string());
+    }
+    Ok(window)
+}
+
+// Answers a query on log/<key expr>?start=..&end=.. with every sample in the logs, given
+// oldest first, recorded on a key matching the key expression within the window, oldest first. Each
+// reply goes out on log/<key> with the sample's encoding and payload and its receive time
+// as the attachment (u64 nanoseconds since the Unix epoch, little-endian). Returns how
+// many samples were sent; a query it cannot parse is answered with an error reply.
+pub fn answer(logs: &[PathBuf], query: &Query) -> zenoh::Result<usize> {
+    let window = match window(query.parameters()) {
+        Ok(window) => window,
+        Err(e) => {
+            query.reply_err(e.clone()).wait()?;
+            return Err(e.into());
+        }
+    };
+    let mut sent = 0;
+    for path in logs {
+        let reader = match LogReader::open(path) {
+            Ok(reader) => reader,
+            // Deleted to make room since the logs were listed.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        sent += answer_from(reader, &window, query)?;
+    }
+    Ok(sent)
+}
+
+fn answer_from(
+    reader: LogReader<BufReader<File>>,
+    window: &Window,
+    query: &Query,
+) -> zenoh::Result<usize> {
+    let mut sent = 0;
+    for record in reader {
+        let record = match record {
+            Ok(record) => record,
+            // The recorder may be in the middle of writing the last record.
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        };
+        if record.timestamp_ns < window.start_ns || record.timestamp_ns > window.end_ns {
+            continue;
+        }
+        let key = KeyExpr::try_from(keys::log(&record.key))?;
+        if !query.key_expr().intersects(&key) {
+            continue;
+        }
+        query
+            .reply(key, record.payload)
+            .encoding(Encoding::from(record.encoding))
+            .attachment(record.timestamp_ns.to_le_bytes().to_vec())
+            .wait()?;
+        sent += 1;
+    }
+    Ok(sent)
+}
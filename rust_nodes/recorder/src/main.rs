@@ -0,0 +1,482 @@
+mod error;
+mod history;
+mod output;
+mod pretrigger;
+mod profiles;
+
+use clap::Parser;
+use command::{Command, Nack, NackReason, Reply};
+use common::{encoding, shutdown_signal};
+use error::RecorderError;
+use flight_log::{Limits, LogWriter, Record, SegmentWriter};
+use keyspace::keys;
+use node_framework::{Heartbeat, LogControl, NodeArgs, NodeError, trace};
+use output::Output;
+use pretrigger::PreTrigger;
+use profiles::{Profiles, Selector};
+use sensors_rs::sensors;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{Span, error, info, info_span, warn};
+use zenoh::Wait;
+use zenoh::bytes::Encoding;
+use zenoh::query::Query;
+
+#[derive(Parser)]
+#[command(about = "Records every sample received on the given key expressions into a flight log")]
+struct Args {
+    /// Log file to write, or directory of segments with --segment-mb, --segment-s or
+    /// --max-disk-mb. Defaults to flight-<unix time>.flog, or the directory
+    /// flight-<unix time>, in the working directory.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Start a new log segment once the current one would grow past this many megabytes
+    /// (10^6 bytes).
+    #[arg(long)]
+    segment_mb: Option<u64>,
+    /// Start a new log segment once the current one spans this many seconds.
+    #[arg(long)]
+    segment_s: Option<f64>,
+    /// Delete the oldest log segments to keep all of them under this many megabytes.
+    #[arg(long)]
+    max_disk_mb: Option<u64>,
+    /// Key expression to record; repeatable. Defaults to devices/**, state/**, sim/**, the
+    /// node logs on log/*, and the crash reports and deadline overruns on events/crash/* and
+    /// events/overrun/*.
+    #[arg(long = "key")]
+    keys: Vec<String>,
+    /// Interval between flushes of the log to disk in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    flush_ms: u64,
+    /// Keep only the last this many seconds of samples in memory, and start writing the
+    /// log, with those samples first, when a trigger arrives.
+    #[arg(long)]
+    pre_trigger_s: Option<f64>,
+    /// Key expression whose samples trigger recording in --pre-trigger-s mode; repeatable.
+    /// Defaults to events/flight and alarms/**.
+    #[arg(long = "trigger")]
+    triggers: Vec<String>,
+    /// TOML or JSON file of logging profiles to switch between by flight phase and event,
+    /// e.g. rust_nodes/recorder/config/profiles.toml. Without one every sample is recorded.
+    #[arg(long)]
+    config: Option<PathBuf>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// What a record holds, for the summary: the FlatBuffers table its encoding names, or else
+// the encoding itself.
+fn content(record: &Record) -> String {
+    let rendered = record.encoding.as_str();
+    encoding::flatbuffer_table(&Encoding::from(rendered)).unwrap_or_else(|| rendered.to_string())
+}
+
+// How many samples of each content were written, for the summary.
+#[derive(Default)]
+struct Summary {
+    count: u64,
+    contents: BTreeMap<String, u64>,
+}
+
+impl Summary {
+    fn add(&mut self, record: &Record) {
+        self.count += 1;
+        *self.contents.entry(content(record)).or_default() += 1;
+    }
+}
+
+// Answers a trigger command, as sent by `cmd recorder trigger`, and passes it on to the
+// main loop.
+fn on_trigger_command(query: &Query, triggers: &mpsc::UnboundedSender<String>) {
+    let payload = query.payload().map(|p| p.to_bytes()).unwrap_or_default();
+    let reply = match Command::decode(&payload) {
+        Ok(command) => {
+            let _ = triggers.send(format!("command from {}", command.sender));
+            Reply {
+                sequence: command.sequence,
+                result: Ok("Recording triggered".to_string()),
+            }
+        }
+        Err(e) => Reply {
+            sequence: 0,
+            result: Err(Nack::new(NackReason::Malformed, e)),
+        },
+    };
+    let replied = query
+        .reply(query.key_expr().clone(), reply.encode())
+        .encoding(encoding::flatbuffer("sensors.CommandReply"))
+        .wait();
+    if let Err(e) = replied {
+        error!("Failed to answer trigger command: {}", e);
+    }
+}
+
+// The flight phase or event a sample on state/phase or events/flight reports.
+fn selector(sample: &zenoh::sample::Sample) -> Result<Selector, String> {
+    let payload = sample.payload().to_bytes();
+    if sample.key_expr().as_str() == keys::phase() {
+        let state = flatbuffers::root::<sensors::PhaseState>(&payload)
+            .map_err(|e| format!("malformed phase state: {}", e))?;
+        Ok(Selector::Phase(state.phase()))
+    } else {
+        let event = flatbuffers::root::<sensors::FlightEvent>(&payload)
+            .map_err(|e| format!("malformed flight event: {}", e))?;
+        Ok(Selector::Event(event.kind()))
+    }
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    match run(args).await {
+        Ok(status) => status,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Records until shutdown. Fails if recording cannot start; failures while recording and in
+// the teardown are reported and make the status a failure.
+async fn run(args: Args) -> Result<ExitCode, RecorderError> {
+    let mut pretrigger = match args.pre_trigger_s {
+        Some(s) if s.is_finite() && s > 0.0 => Some(PreTrigger::new(Duration::from_secs_f64(s))),
+        Some(_) => return Err(RecorderError::Args("--pre-trigger-s must be positive")),
+        None => None,
+    };
+    if args.segment_s.is_some_and(|s| !(s.is_finite() && s > 0.0)) {
+        return Err(RecorderError::Args("--segment-s must be positive"));
+    }
+    let mut profiles = match &args.config {
+        Some(path) => {
+            let config = profiles::Config::load(path).map_err(RecorderError::Config)?;
+            Some(Profiles::new(&config))
+        }
+        None => None,
+    };
+    let limits = Limits {
+        segment_bytes: args.segment_mb.map(|mb| mb * 1_000_000),
+        segment_span: args.segment_s.map(Duration::from_secs_f64),
+        total_bytes: args.max_disk_mb.map(|mb| mb * 1_000_000),
+    };
+    let segmented = limits.segment_bytes.is_some()
+        || limits.segment_span.is_some()
+        || limits.total_bytes.is_some();
+    let path = args.output.clone().unwrap_or_else(|| {
+        let stem = format!("flight-{}", now_ns() / 1_000_000_000);
+        PathBuf::from(if segmented { stem } else { stem + ".flog" })
+    });
+    let created = if segmented {
+        SegmentWriter::open(&path, limits).map(Output::Segments)
+    } else {
+        LogWriter::create(&path).map(Output::File)
+    };
+    let mut log = created.map_err(|source| RecorderError::Create {
+        path: path.clone(),
+        source,
+    })?;
+    if let Output::Segments(segments) = &log {
+        for segment in segments.recovered() {
+            info!(
+                "Sealed {}, left unsealed by an earlier run",
+                segment.display()
+            );
+        }
+    }
+    let key_exprs = if args.keys.is_empty() {
+        vec![
+            keys::all_devices().to_string(),
+            keys::all_state().to_string(),
+            keys::all_sim().to_string(),
+            keys::all_node_logs().to_string(),
+            keys::all_crashes().to_string(),
+            keys::all_overruns().to_string(),
+        ]
+    } else {
+        args.keys.clone()
+    };
+
+    let session = args.node.zenoh.open().await.map_err(NodeError::from)?;
+    node_framework::crash::install(&session, args.node.name("recorder"), &args.node);
+
+    // Samples are stamped in the subscriber callbacks and written from the main loop, so
+    // disk latency never stalls Zenoh's callback threads. Traced samples carry their trace
+    // context along, so writing them continues their traces.
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    // The recorder's own log is left out, so a failing write does not log an error that is
+    // written in turn.
+    let own_log = keys::node_log(args.node.name("recorder"));
+    let mut subscribers = Vec::with_capacity(key_exprs.len());
+    for key_expr in &key_exprs {
+        let tx = tx.clone();
+        let own_log = own_log.clone();
+        let subscriber = session
+            .declare_subscriber(key_expr)
+            .callback(move |sample| {
+                if sample.key_expr().as_str() == own_log {
+                    return;
+                }
+                let record = Record {
+                    timestamp_ns: now_ns(),
+                    key: sample.key_expr().to_string(),
+                    encoding: sample.encoding().to_string(),
+                    payload: sample.payload().to_bytes().into_owned(),
+                };
+                let _ = tx.send((record, trace::of(&sample)));
+            })
+            .await
+            .map_err(NodeError::declare("recorder subscriber"))?;
+        subscribers.push(subscriber);
+    }
+    drop(tx);
+    let queryable = session
+        .declare_queryable(keys::all_log())
+        .await
+        .map_err(NodeError::declare("log queryable"))?;
+    // Triggers only matter until the first one, but stay declared so later ones are still
+    // acknowledged.
+    let (trigger_tx, mut trigger_rx) = mpsc::unbounded_channel();
+    let mut trigger_subscribers = Vec::new();
+    let mut trigger_queryable = None;
+    if pretrigger.is_some() {
+        let trigger_exprs = if args.triggers.is_empty() {
+            vec![
+                keys::flight_events().to_string(),
+                keys::all_alarms().to_string(),
+            ]
+        } else {
+            args.triggers.clone()
+        };
+        for key_expr in &trigger_exprs {
+            let tx = trigger_tx.clone();
+            let subscriber = session
+                .declare_subscriber(key_expr)
+                .callback(move |sample| {
+                    let _ = tx.send(sample.key_expr().to_string());
+                })
+                .await
+                .map_err(NodeError::declare("trigger subscriber"))?;
+            trigger_subscribers.push(subscriber);
+        }
+        let tx = trigger_tx.clone();
+        let key = keys::command(args.node.name("recorder"), "trigger");
+        let queryable = session
+            .declare_queryable(&key)
+            .callback(move |query| on_trigger_command(&query, &tx))
+            .await
+            .map_err(NodeError::declare("trigger queryable"))?;
+        trigger_queryable = Some(queryable);
+        info!(
+            "Holding the last {} s in memory until a sample on {} or a query on '{}'",
+            args.pre_trigger_s.unwrap_or_default(),
+            trigger_exprs.join(", "),
+            key
+        );
+    }
+    drop(trigger_tx);
+    let (selector_tx, mut selector_rx) = mpsc::unbounded_channel();
+    let mut selector_subscribers = Vec::new();
+    if let Some(profiles) = &profiles {
+        for key_expr in [keys::phase(), keys::flight_events()] {
+            let tx = selector_tx.clone();
+            let subscriber = session
+                .declare_subscriber(key_expr)
+                .callback(move |sample| match selector(&sample) {
+                    Ok(selector) => {
+                        let _ = tx.send(selector);
+                    }
+                    Err(e) => warn!("Ignoring {}: {}", sample.key_expr(), e),
+                })
+                .await
+                .map_err(NodeError::declare("profile subscriber"))?;
+            selector_subscribers.push(subscriber);
+        }
+        info!("Logging with profile {}", profiles.current());
+    }
+    drop(selector_tx);
+    let name = args.node.name("recorder");
+    let heartbeat = Heartbeat::start(&session, name, args.node.heartbeat_period())
+        .await
+        .map_err(|source| NodeError::Start {
+            node: name.to_string(),
+            source,
+        })?;
+    let _log_control = LogControl::start(&session, name, &args.node).await?;
+    info!("Recording {} to {}", key_exprs.join(", "), path.display());
+    info!("Answering log queries on '{}'.", keys::all_log());
+
+    let mut status = ExitCode::SUCCESS;
+    let mut summary = Summary::default();
+    let mut flush = interval(Duration::from_millis(args.flush_ms));
+    flush.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        let (record, traced) = tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            _ = flush.tick() => {
+                if let Err(e) = log.flush() {
+                    error!("Failed to flush {}: {}", path.display(), e);
+                }
+                continue;
+            }
+            query = queryable.recv_async() => {
+                let Ok(query) = query else { break };
+                // Queries read the log back from disk, so everything received so far has
+                // to be in the file, and are answered off the main loop so a long window
+                // does not hold up recording.
+                if let Err(e) = log.flush() {
+                    error!("Failed to flush {}: {}", path.display(), e);
+                }
+                let path = path.clone();
+                tokio::task::spawn_blocking(move || {
+                    let logs = if segmented {
+                        flight_log::segments(&path)
+                    } else {
+                        Ok(vec![path])
+                    };
+                    let answered = logs
+                        .map_err(zenoh::Error::from)
+                        .and_then(|logs| history::answer(&logs, &query));
+                    match answered {
+                        Ok(sent) => info!("Sent {} samples for {}", sent, query.selector()),
+                        Err(e) => error!("Failed to answer {}: {}", query.selector(), e),
+                    }
+                });
+                continue;
+            }
+            Some(selector) = selector_rx.recv() => {
+                let Some(profiles) = &mut profiles else { continue };
+                if profiles.select(selector) {
+                    info!("Switched to logging profile {} on {}", profiles.current(), selector);
+                }
+                continue;
+            }
+            Some(cause) = trigger_rx.recv() => {
+                let Some(held) = pretrigger.take() else { continue };
+                let records = held.into_records();
+                info!("Triggered by {}; writing the {} samples held", cause, records.len());
+                let written = records.iter().try_for_each(|record| {
+                    log.write(record)?;
+                    summary.add(record);
+                    Ok::<_, std::io::Error>(())
+                });
+                if let Err(e) = written {
+                    error!("Failed to write {}: {}", path.display(), e);
+                    status = ExitCode::FAILURE;
+                    break;
+                }
+                continue;
+            }
+            record = rx.recv() => match record {
+                Some(record) => record,
+                None => break,
+            },
+        };
+        if profiles
+            .as_mut()
+            .is_some_and(|profiles| !profiles.keep(&record))
+        {
+            continue;
+        }
+        if let Some(held) = &mut pretrigger {
+            held.push(record);
+            continue;
+        }
+        let span = match traced {
+            Some(parent) => {
+                let span = info_span!("record", key = %record.key);
+                trace::continue_from(&span, parent);
+                span
+            }
+            None => Span::none(),
+        };
+        let started = Instant::now();
+        let written = span.in_scope(|| log.write(&record));
+        heartbeat.record(started.elapsed(), written.is_ok());
+        if let Err(e) = written {
+            error!("Failed to write {}: {}", path.display(), e);
+            status = ExitCode::FAILURE;
+            break;
+        }
+        summary.add(&record);
+    }
+
+    info!("Shutting down recorder...");
+    if let Err(e) = heartbeat.stop().await {
+        error!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = queryable.undeclare().await {
+        error!("Failed to undeclare log queryable: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    for subscriber in selector_subscribers {
+        if let Err(e) = subscriber.undeclare().await {
+            error!("Failed to undeclare profile subscriber: {}", e);
+            status = ExitCode::FAILURE;
+        }
+    }
+    for subscriber in trigger_subscribers {
+        if let Err(e) = subscriber.undeclare().await {
+            error!("Failed to undeclare trigger subscriber: {}", e);
+            status = ExitCode::FAILURE;
+        }
+    }
+    let undeclared = match trigger_queryable {
+        Some(queryable) => queryable.undeclare().await,
+        None => Ok(()),
+    };
+    if let Err(e) = undeclared {
+        error!("Failed to undeclare trigger queryable: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    for subscriber in subscribers {
+        if let Err(e) = subscriber.undeclare().await {
+            error!("Failed to undeclare recorder subscriber: {}", e);
+            status = ExitCode::FAILURE;
+        }
+    }
+    // Keep whatever was received before the subscribers went away, unless nothing ever
+    // triggered recording.
+    if let Some(held) = pretrigger {
+        info!(
+            "Never triggered; discarding {} samples held",
+            held.into_records().len() + rx.len()
+        );
+    } else {
+        while let Ok((record, _)) = rx.try_recv() {
+            if log.write(&record).is_err() {
+                break;
+            }
+            summary.add(&record);
+        }
+    }
+    if let Err(e) = log.close() {
+        error!("Failed to close {}: {}", path.display(), e);
+        status = ExitCode::FAILURE;
+    }
+    info!("Recorded {} samples to {}", summary.count, path.display());
+    for (content, count) in &summary.contents {
+        info!("{:>10} {}", count, content);
+    }
+    if let Err(e) = session.close().await {
+        error!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    Ok(status)
+}
@@ -0,0 +1,34 @@
+use flight_log::{LogWriter, Record, SegmentWriter};
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+// Where the recorder writes: one flight log, or a directory of segments when the log is
+// rotated.
+pub enum Output {
+    File(LogWriter<BufWriter<File>>),
+    Segments(SegmentWriter),
+}
+
+impl Output {
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        match self {
+            Output::File(log) => log.write(record),
+            Output::Segments(segments) => segments.write(record),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::File(log) => log.flush(),
+            Output::Segments(segments) => segments.flush(),
+        }
+    }
+
+    // Flushes the log, sealing the last segment if it is segmented.
+    pub fn close(self) -> io::Result<()> {
+        match self {
+            Output::File(mut log) => log.flush(),
+            Output::Segments(segments) => segments.close(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use flight_log::Record;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// The records of the last window of receive time, held in memory until a trigger decides
+// they are worth keeping.
+pub struct PreTrigger {
+    window_ns: u64,
+    records: VecDeque<Record>,
+}
+
+impl PreTrigger {
+    pub fn new(window: Duration) -> Self {
+        PreTrigger {
+            window_ns: window.as_nanos() as u64,
+            records: VecDeque::new(),
+        }
+    }
+
+    // Adds a record and forgets those received more than the window before it.
+    pub fn push(&mut self, record: Record) {
+        let oldest = record.timestamp_ns.saturating_sub(self.window_ns);
+        self.records.push_back(record);
+        while self
+            .records
+            .front()
+            .is_some_and(|record| record.timestamp_ns < oldest)
+        {
+            self.records.pop_front();
+        }
+    }
+
+    // The records held, oldest first.
+    pub fn into_records(self) -> VecDeque<Record> {
+        self.records
+    }
+}
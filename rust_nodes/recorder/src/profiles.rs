@@ -0,0 +1,220 @@
+use common::config;
+use flight_log::Record;
+use sensors_rs::sensors::{FlightEventKind, FlightPhase};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+use zenoh::key_expr::{KeyExpr, OwnedKeyExpr};
+
+// Samples of the keys a stream matches are recorded at most rate_hz times a second each.
+#[derive(Clone, Debug, Deserialize)]
+pub struct StreamConfig {
+    pub key: String,
+    pub rate_hz: f64,
+}
+
+// A logging profile, taken on when mission_manager enters one of its phases or
+// event_detector reports one of its events. Keys no stream matches are recorded in full.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProfileConfig {
+    pub name: String,
+    #[serde(default)]
+    pub phases: Vec<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub streams: Vec<StreamConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    // The first profile applies until a phase or event selects another.
+    pub profiles: Vec<ProfileConfig>,
+}
+
+impl Config {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: Config = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.profiles.is_empty() {
+            return Err("no profiles configured".to_string());
+        }
+        for (i, profile) in self.profiles.iter().enumerate() {
+            let name = &profile.name;
+            if self.profiles[..i].iter().any(|other| &other.name == name) {
+                return Err(format!("profile {} configured twice", name));
+            }
+            for phase in &profile.phases {
+                if flight_phase(phase).is_none() {
+                    return Err(format!("{}: unknown flight phase {}", name, phase));
+                }
+                if self.profiles[..i]
+                    .iter()
+                    .flat_map(|other| &other.phases)
+                    .any(|other| flight_phase(other) == flight_phase(phase))
+                {
+                    return Err(format!("{}: phase {} selects two profiles", name, phase));
+                }
+            }
+            for event in &profile.events {
+                if flight_event(event).is_none() {
+                    return Err(format!("{}: unknown flight event {}", name, event));
+                }
+                if self.profiles[..i]
+                    .iter()
+                    .flat_map(|other| &other.events)
+                    .any(|other| flight_event(other) == flight_event(event))
+                {
+                    return Err(format!("{}: event {} selects two profiles", name, event));
+                }
+            }
+            for stream in &profile.streams {
+                let key = &stream.key;
+                KeyExpr::new(key.as_str()).map_err(|e| format!("{}: {}", key, e))?;
+                if !(stream.rate_hz.is_finite() && stream.rate_hz > 0.0) {
+                    return Err(format!("{}: {}: rate_hz must be positive", name, key));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn flight_phase(name: &str) -> Option<FlightPhase> {
+    FlightPhase::ENUM_VALUES.iter().copied().find(|phase| {
+        phase
+            .variant_name()
+            .is_some_and(|variant| variant.eq_ignore_ascii_case(name))
+    })
+}
+
+fn flight_event(name: &str) -> Option<FlightEventKind> {
+    FlightEventKind::ENUM_VALUES.iter().copied().find(|kind| {
+        kind.variant_name()
+            .is_some_and(|variant| variant.eq_ignore_ascii_case(name))
+    })
+}
+
+// What can select a profile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selector {
+    Phase(FlightPhase),
+    Event(FlightEventKind),
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (what, name) = match self {
+            Selector::Phase(phase) => ("phase", phase.variant_name()),
+            Selector::Event(kind) => ("event", kind.variant_name()),
+        };
+        write!(f, "{} {}", what, name.unwrap_or("unknown").to_uppercase())
+    }
+}
+
+struct Profile {
+    name: String,
+    phases: Vec<FlightPhase>,
+    events: Vec<FlightEventKind>,
+    streams: Vec<(OwnedKeyExpr, Duration)>,
+}
+
+// Decides which samples the profile in effect records.
+pub struct Profiles {
+    profiles: Vec<Profile>,
+    current: usize,
+    // mission_manager republishes its phase periodically, so only a change of phase
+    // selects a profile; a profile an event selected stays until the phase moves on.
+    phase: Option<FlightPhase>,
+    // Receive time of the last sample recorded on each rate-limited key.
+    last_kept: HashMap<String, u64>,
+}
+
+impl Profiles {
+    pub fn new(config: &Config) -> Self {
+        let profiles = config
+            .profiles
+            .iter()
+            .map(|profile| Profile {
+                name: profile.name.clone(),
+                phases: profile
+                    .phases
+                    .iter()
+                    .filter_map(|p| flight_phase(p))
+                    .collect(),
+                events: profile
+                    .events
+                    .iter()
+                    .filter_map(|e| flight_event(e))
+                    .collect(),
+                streams: profile
+                    .streams
+                    .iter()
+                    .map(|stream| {
+                        let key = OwnedKeyExpr::new(stream.key.as_str()).expect("validated");
+                        (key, Duration::from_secs_f64(1.0 / stream.rate_hz))
+                    })
+                    .collect(),
+            })
+            .collect();
+        Profiles {
+            profiles,
+            current: 0,
+            phase: None,
+            last_kept: HashMap::new(),
+        }
+    }
+
+    pub fn current(&self) -> &str {
+        &self.profiles[self.current].name
+    }
+
+    // Switches to the profile the selector picks, if any; returns whether that changed the
+    // profile in effect.
+    pub fn select(&mut self, selector: Selector) -> bool {
+        if let Selector::Phase(phase) = selector {
+            if self.phase == Some(phase) {
+                return false;
+            }
+            self.phase = Some(phase);
+        }
+        let picked = self.profiles.iter().position(|profile| match selector {
+            Selector::Phase(phase) => profile.phases.contains(&phase),
+            Selector::Event(kind) => profile.events.contains(&kind),
+        });
+        match picked {
+            Some(index) if index != self.current => {
+                self.current = index;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Whether the record is recorded under the profile in effect.
+    pub fn keep(&mut self, record: &Record) -> bool {
+        let Ok(key) = KeyExpr::new(record.key.as_str()) else {
+            return true;
+        };
+        let streams = &self.profiles[self.current].streams;
+        let Some((_, period)) = streams.iter().find(|(expr, _)| expr.intersects(&key)) else {
+            return true;
+        };
+        let period = period.as_nanos() as u64;
+        match self.last_kept.get(&record.key) {
+            Some(&last) if record.timestamp_ns.saturating_sub(last) < period => false,
+            _ => {
+                self.last_kept
+                    .insert(record.key.clone(), record.timestamp_ns);
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use common::encoding;
+use sensors_rs::sensors;
+use std::time::Duration;
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+
+// Publishes sensors::Clock ticks on a fixed grid of log time, starting one period after the
+// first replayed sample. Each tick is sent after every sample stamped before it, which is
+// the guarantee fusion --clock sim relies on.
+pub struct SimClock {
+    publisher: Publisher<'static>,
+    period_ns: u64,
+    next_ns: Option<u64>,
+}
+
+impl SimClock {
+    pub async fn declare(
+        session: &zenoh::Session,
+        key: &str,
+        period: Duration,
+    ) -> zenoh::Result<Self> {
+        let publisher = session
+            .declare_publisher(key.to_string())
+            .encoding(encoding::flatbuffer("sensors.Clock"))
+            .congestion_control(CongestionControl::Block)
+            .await?;
+        Ok(SimClock {
+            publisher,
+            period_ns: period.as_nanos() as u64,
+            next_ns: None,
+        })
+    }
+
+    // Next tick that must go out before a sample stamped time_ns, if any.
+    pub fn due_before(&mut self, time_ns: u64) -> Option<u64> {
+        let next = *self.next_ns.get_or_insert(time_ns + self.period_ns);
+        (next < time_ns).then_some(next)
+    }
+
+    // Tick that has not been published yet, covering the samples since the last one.
+    pub fn pending(&self) -> Option<u64> {
+        self.next_ns
+    }
+
+    pub async fn publish(&mut self, time_ns: u64) -> zenoh::Result<()> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(32);
+        let clock = sensors::Clock::create(&mut builder, &sensors::ClockArgs { time_ns });
+        builder.finish(clock, None);
+        self.next_ns = Some(time_ns + self.period_ns);
+        self.publisher.put(builder.finished_data().to_vec()).await
+    }
+
+    pub async fn undeclare(self) -> zenoh::Result<()> {
+        self.publisher.undeclare().await
+    }
+}
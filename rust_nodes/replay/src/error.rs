@@ -0,0 +1,16 @@
+use node_framework::NodeError;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+// Why a replay could not start.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    // Arguments the log cannot be replayed with.
+    #[error("{0}")]
+    Args(&'static str),
+    #[error("cannot read {}: {source}", path.display())]
+    Open { path: PathBuf, source: io::Error },
+    #[error(transparent)]
+    Node(#[from] NodeError),
+}
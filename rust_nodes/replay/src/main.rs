@@ -0,0 +1,235 @@
+mod clock;
+mod error;
+
+use clap::Parser;
+use clock::SimClock;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use common::shutdown_signal;
+use error::ReplayError;
+use flight_log::{LogReader, Record};
+use keyspace::keys;
+use node_framework::{Heartbeat, LogControl, NodeArgs, NodeError};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::time::{Instant, sleep_until};
+use tracing::{error, info, warn};
+use zenoh::bytes::Encoding;
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+
+#[derive(Parser)]
+#[command(about = "Republishes a recorded flight log onto its original keys")]
+struct Args {
+    /// Flight log written by the recorder.
+    log: PathBuf,
+    /// Playback speed multiplier; 2 plays twice as fast as recorded.
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+    /// Publish as fast as possible instead of at the recorded timing.
+    #[arg(long, conflicts_with = "speed")]
+    max_speed: bool,
+    /// Skip samples recorded earlier than this many seconds into the log.
+    #[arg(long)]
+    start_s: Option<f64>,
+    /// Stop at samples recorded later than this many seconds into the log.
+    #[arg(long)]
+    end_s: Option<f64>,
+    /// Publish simulation clock ticks at this period of log time, for fusion --clock sim.
+    #[arg(long)]
+    clock_period_ms: Option<u64>,
+    /// Key expression clock ticks are published on.
+    #[arg(long, default_value = keys::sim_clock())]
+    clock_key: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// Publishes the record on its original key, declaring a publisher the first time a key is
+// seen. Replay never drops samples under congestion: with --max-speed it would otherwise
+// lose most of them, and a clock tick must not overtake samples it vouches for.
+async fn publish(
+    session: &zenoh::Session,
+    publishers: &mut HashMap<String, Publisher<'static>>,
+    record: Record,
+) -> zenoh::Result<()> {
+    if !publishers.contains_key(&record.key) {
+        let publisher = session
+            .declare_publisher(record.key.clone())
+            .congestion_control(CongestionControl::Block)
+            .qos(&QosConfig::default(), &record.key)
+            .await?;
+        publishers.insert(record.key.clone(), publisher);
+    }
+    publishers[&record.key]
+        .put(record.payload)
+        .encoding(Encoding::from(record.encoding))
+        .await
+}
+
+// Sleeps until due, or not at all without one. Returns false if shutdown came first.
+async fn wait_until(shutdown: Pin<&mut impl Future<Output = ()>>, due: Option<Instant>) -> bool {
+    let due = async {
+        if let Some(due) = due {
+            sleep_until(due).await;
+        }
+    };
+    tokio::select! {
+        biased;
+        _ = shutdown => false,
+        _ = due => true,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    match run(args).await {
+        Ok(status) => status,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Replays the log until its end or shutdown. Fails if the replay cannot start; failures
+// while replaying and in the teardown are reported and make the status a failure.
+async fn run(args: Args) -> Result<ExitCode, ReplayError> {
+    if args.speed.is_nan() || args.speed <= 0.0 {
+        return Err(ReplayError::Args("--speed must be positive"));
+    }
+    if args.clock_period_ms == Some(0) {
+        return Err(ReplayError::Args("--clock-period-ms must be positive"));
+    }
+    let mut log = LogReader::open(&args.log).map_err(|source| ReplayError::Open {
+        path: args.log.clone(),
+        source,
+    })?;
+    let start_s = args.start_s.unwrap_or(0.0);
+    let end_s = args.end_s.unwrap_or(f64::INFINITY);
+
+    let session = args.node.zenoh.open().await.map_err(NodeError::from)?;
+    node_framework::crash::install(&session, args.node.name("replay"), &args.node);
+    let mut publishers = HashMap::new();
+    let mut clock = match args.clock_period_ms {
+        Some(period_ms) => Some(
+            SimClock::declare(&session, &args.clock_key, Duration::from_millis(period_ms))
+                .await
+                .map_err(NodeError::declare("clock publisher"))?,
+        ),
+        None => None,
+    };
+
+    let name = args.node.name("replay");
+    let heartbeat = Heartbeat::start(&session, name, args.node.heartbeat_period())
+        .await
+        .map_err(|source| NodeError::Start {
+            node: name.to_string(),
+            source,
+        })?;
+    let _log_control = LogControl::start(&session, name, &args.node).await?;
+
+    let mut status = ExitCode::SUCCESS;
+    let mut count: u64 = 0;
+    // Timestamp of the first record in the log, and the log and wall-clock times playback
+    // started at; samples are released at their original spacing divided by the speed.
+    let mut log_origin: Option<u64> = None;
+    let mut playback_origin: Option<(f64, Instant)> = None;
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    'replay: loop {
+        let record = match log.read() {
+            Ok(Some(record)) => record,
+            Ok(None) => break,
+            // Logs cut short by a crash end in a partial record; play what is there.
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                warn!("{}: {}", args.log.display(), e);
+                break;
+            }
+            Err(e) => {
+                warn!(
+                    "Stopping at unreadable record in {}: {}",
+                    args.log.display(),
+                    e
+                );
+                status = ExitCode::FAILURE;
+                break;
+            }
+        };
+        let origin = *log_origin.get_or_insert(record.timestamp_ns);
+        let offset_s = |time_ns: u64| time_ns.saturating_sub(origin) as f64 / 1e9;
+        if offset_s(record.timestamp_ns) < start_s {
+            continue;
+        }
+        if offset_s(record.timestamp_ns) > end_s {
+            break;
+        }
+
+        let (first_s, started) =
+            *playback_origin.get_or_insert((offset_s(record.timestamp_ns), Instant::now()));
+        let due = |time_ns: u64| {
+            (!args.max_speed).then(|| {
+                started + Duration::from_secs_f64((offset_s(time_ns) - first_s) / args.speed)
+            })
+        };
+
+        // Ticks falling before this sample go out first, each at its own time.
+        if let Some(clock) = &mut clock {
+            while let Some(tick_ns) = clock.due_before(record.timestamp_ns) {
+                if !wait_until(shutdown.as_mut(), due(tick_ns)).await {
+                    break 'replay;
+                }
+                if let Err(e) = clock.publish(tick_ns).await {
+                    error!("Failed to publish clock tick: {}", e);
+                }
+            }
+        }
+        if !wait_until(shutdown.as_mut(), due(record.timestamp_ns)).await {
+            break;
+        }
+        let key = record.key.clone();
+        let started = Instant::now();
+        let published = publish(&session, &mut publishers, record).await;
+        heartbeat.record(started.elapsed(), published.is_ok());
+        if let Err(e) = published {
+            error!("Failed to publish on {}: {}", key, e);
+        }
+        count += 1;
+    }
+
+    info!("Replayed {} samples from {}", count, args.log.display());
+    if let Err(e) = heartbeat.stop().await {
+        error!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Some(mut clock) = clock {
+        // One last tick so the final samples are processed too.
+        let published = match clock.pending() {
+            Some(tick_ns) => clock.publish(tick_ns).await,
+            None => Ok(()),
+        };
+        if let Err(e) = published {
+            error!("Failed to publish clock tick: {}", e);
+        }
+        if let Err(e) = clock.undeclare().await {
+            error!("Failed to undeclare clock publisher: {}", e);
+            status = ExitCode::FAILURE;
+        }
+    }
+    for (key, publisher) in publishers {
+        if let Err(e) = publisher.undeclare().await {
+            error!("Failed to undeclare publisher on {}: {}", key, e);
+            status = ExitCode::FAILURE;
+        }
+    }
+    if let Err(e) = session.close().await {
+        error!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    Ok(status)
+}
@@ -0,0 +1,131 @@
+// Serializes messages as ROS 2 carries them over DDS, and so as zenoh-plugin-dds passes
+// them through: a 4-byte encapsulation header naming little-endian CDR, then the fields in
+// declaration order, each primitive aligned to its own size counting from the end of the
+// header. Fixed-size arrays are their elements back to back; strings are a u32 length
+// counting a terminating NUL, the bytes, and the NUL.
+
+// CDR_LE, with no options.
+const ENCAPSULATION: [u8; 4] = [0x00, 0x01, 0x00, 0x00];
+
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        let mut buf = Vec::with_capacity(512);
+        buf.extend_from_slice(&ENCAPSULATION);
+        Writer { buf }
+    }
+
+    fn align(&mut self, size: usize) {
+        let offset = self.buf.len() - ENCAPSULATION.len();
+        let padding = (size - offset % size) % size;
+        self.buf.resize(self.buf.len() + padding, 0);
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    pub fn i8(&mut self, value: i8) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.align(2);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn i32(&mut self, value: i32) {
+        self.align(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.align(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn f64(&mut self, value: f64) {
+        self.align(8);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn f64s(&mut self, values: &[f64]) {
+        for &value in values {
+            self.f64(value);
+        }
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32 + 1);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+// A message or a part of one, written field by field.
+pub trait Serialize {
+    fn serialize(&self, w: &mut Writer);
+
+    // The message as a whole payload, behind the encapsulation header.
+    fn to_cdr(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        self.serialize(&mut w);
+        w.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leads_with_the_encapsulation_header() {
+        assert_eq!(Writer::new().finish(), [0x00, 0x01, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn aligns_from_the_end_of_the_header() {
+        let mut w = Writer::new();
+        w.u8(0xaa);
+        w.u16(0x0102);
+        w.u8(0xbb);
+        w.u32(0x03040506);
+        w.i8(-1);
+        w.f64(1.0);
+        let expected = [
+            &ENCAPSULATION[..],
+            &[0xaa, 0x00, 0x02, 0x01, 0xbb, 0x00, 0x00, 0x00],
+            &[0x06, 0x05, 0x04, 0x03, 0xff, 0x00, 0x00, 0x00],
+            &[0, 0, 0, 0, 0, 0, 0xf0, 0x3f],
+        ]
+        .concat();
+        assert_eq!(w.finish(), expected);
+    }
+
+    #[test]
+    fn counts_the_nul_in_a_string() {
+        let mut w = Writer::new();
+        w.string("");
+        w.string("map");
+        w.u8(7);
+        w.string("odom");
+        let expected = [
+            &ENCAPSULATION[..],
+            &[1, 0, 0, 0, 0],
+            // Aligned to 4 again for the next length.
+            &[0, 0, 0],
+            &[4, 0, 0, 0, b'm', b'a', b'p', 0],
+            &[7, 0, 0, 0],
+            &[5, 0, 0, 0, b'o', b'd', b'o', b'm', 0],
+        ]
+        .concat();
+        assert_eq!(w.finish(), expected);
+    }
+}
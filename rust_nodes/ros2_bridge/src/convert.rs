@@ -0,0 +1,217 @@
+use crate::cdr::Serialize;
+use crate::msgs::{
+    COVARIANCE_TYPE_UNKNOWN, Header, Imu, NavSatFix, Odometry, Quaternion, SERVICE_GPS, STATUS_FIX,
+    STATUS_NO_FIX, Time, Vector3,
+};
+use common::{attitude, encoding};
+use sensor_decode::MessageType;
+use sensors_rs::readings::{FixType, GnssReading, GyroReading, ImuReading, Reading};
+use sensors_rs::sensors;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zenoh::sample::Sample;
+
+// Variance given what the fused state does not estimate or holds no valid value of, so
+// large that filters downstream give it no weight. Odometry has no way to leave a field
+// out.
+const UNKNOWN_VARIANCE: f64 = 1e9;
+
+// Frames the messages are stamped with: a local east-north-up world frame whose origin is
+// on the ground below the vehicle, and the body frame the sensors measure in.
+pub struct Frames {
+    pub world: String,
+    pub body: String,
+}
+
+// A sample as a ROS 2 message: its type, as ROS 2 subscribers name it, and its CDR.
+pub struct Message {
+    pub ros_type: &'static str,
+    pub cdr: Vec<u8>,
+}
+
+impl Message {
+    fn new(ros_type: &'static str, message: &impl Serialize) -> Self {
+        Message {
+            ros_type,
+            cdr: message.to_cdr(),
+        }
+    }
+}
+
+// When the sample was published, by its Zenoh timestamp, else when it arrived. The
+// timestamps payloads carry are not used, as they run on simulated time in simulated-time
+// runs while the sensor readings carry none.
+fn stamp(sample: &Sample) -> Time {
+    let time = sample
+        .timestamp()
+        .map_or_else(SystemTime::now, |t| t.get_time().to_system_time())
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Time {
+        sec: time.as_secs() as i32,
+        nanosec: time.subsec_nanos(),
+    }
+}
+
+fn vector3(v: [f32; 3]) -> Vector3 {
+    Vector3 {
+        x: v[0] as f64,
+        y: v[1] as f64,
+        z: v[2] as f64,
+    }
+}
+
+// The sample as a ROS 2 message, None if the bridge has no conversion for its payload.
+pub fn convert(sample: &Sample, frames: &Frames) -> Result<Option<Message>, String> {
+    let key = sample.key_expr().as_str();
+    let encoding = sample.encoding();
+    let payload = sample.payload().to_bytes();
+    let header = |frame: &str| Header {
+        stamp: stamp(sample),
+        frame_id: frame.to_string(),
+    };
+    if encoding::flatbuffer_table(encoding).as_deref() == Some("sensors.FusedState") {
+        let state = flatbuffers::root::<sensors::FusedState>(&payload)
+            .map_err(|e| format!("malformed sensors.FusedState: {}", e))?;
+        let odometry = odometry(state, header(&frames.world), frames);
+        return Ok(Some(Message::new("nav_msgs/msg/Odometry", &odometry)));
+    }
+    let message = match MessageType::detect(key, encoding) {
+        Some(MessageType::Imu) => {
+            let imu = ImuReading::decode(&payload).map_err(|e| e.to_string())?;
+            let imu = Imu {
+                header: header(&frames.body),
+                orientation_covariance: not_measured(),
+                angular_velocity_covariance: not_measured(),
+                linear_acceleration: vector3(imu.acceleration),
+                ..Imu::default()
+            };
+            Message::new("sensor_msgs/msg/Imu", &imu)
+        }
+        Some(MessageType::Gyro) => {
+            let gyro = GyroReading::decode(&payload).map_err(|e| e.to_string())?;
+            let imu = Imu {
+                header: header(&frames.body),
+                orientation_covariance: not_measured(),
+                angular_velocity: vector3(gyro.omega),
+                linear_acceleration_covariance: not_measured(),
+                ..Imu::default()
+            };
+            Message::new("sensor_msgs/msg/Imu", &imu)
+        }
+        Some(MessageType::Gnss) => {
+            let fix = GnssReading::decode(&payload).map_err(|e| e.to_string())?;
+            Message::new(
+                "sensor_msgs/msg/NavSatFix",
+                &nav_sat_fix(&fix, header(&frames.body)),
+            )
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(message))
+}
+
+// The covariance sensor_msgs/Imu marks a field it does not carry with.
+fn not_measured() -> [f64; 9] {
+    let mut covariance = [0.0; 9];
+    covariance[0] = -1.0;
+    covariance
+}
+
+fn nav_sat_fix(fix: &GnssReading, header: Header) -> NavSatFix {
+    NavSatFix {
+        header,
+        status: match fix.fix_type {
+            FixType::NoFix => STATUS_NO_FIX,
+            FixType::Fix2D | FixType::Fix3D => STATUS_FIX,
+        },
+        // The receiver does not say which constellations it tracks.
+        service: SERVICE_GPS,
+        latitude: fix.latitude_deg,
+        longitude: fix.longitude_deg,
+        altitude: fix.altitude as f64,
+        position_covariance: [0.0; 9],
+        position_covariance_type: COVARIANCE_TYPE_UNKNOWN,
+    }
+}
+
+// The fused state as the pose of the body frame in the world frame, raised by the
+// altitude and rotated by the attitude, and the body's velocity and angular rate in the
+// body frame. Only altitude is estimated of the position.
+fn odometry(state: sensors::FusedState, header: Header, frames: &Frames) -> Odometry {
+    let mut pose_covariance = [0.0; 36];
+    let mut twist_covariance = [0.0; 36];
+    let unknown = |covariance: &mut [f64; 36], axes: std::ops::Range<usize>| {
+        for i in axes {
+            covariance[i * 7] = UNKNOWN_VARIANCE;
+        }
+    };
+    unknown(&mut pose_covariance, 0..2);
+    let altitude = state.altitude_valid().then(|| state.altitude());
+    if altitude.is_none() {
+        unknown(&mut pose_covariance, 2..3);
+    }
+    let attitude = state
+        .attitude()
+        .filter(|_| state.attitude_valid())
+        .map(|a| attitude::quaternion(a.x() as f64, a.y() as f64, a.z() as f64));
+    if attitude.is_none() {
+        unknown(&mut pose_covariance, 3..6);
+    }
+    // The velocity is east-north-up, so it takes the attitude to have it in the body frame.
+    let linear = match (
+        state.velocity().filter(|_| state.velocity_valid()),
+        attitude,
+    ) {
+        (Some(v), Some(q)) => to_body(q, [v.x() as f64, v.y() as f64, v.z() as f64]),
+        _ => {
+            unknown(&mut twist_covariance, 0..3);
+            Vector3::default()
+        }
+    };
+    let angular = match state.angular_rate().filter(|_| state.angular_rate_valid()) {
+        Some(w) => vector3([w.x(), w.y(), w.z()]),
+        None => {
+            unknown(&mut twist_covariance, 3..6);
+            Vector3::default()
+        }
+    };
+    Odometry {
+        header,
+        child_frame_id: frames.body.clone(),
+        position: Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: altitude.unwrap_or_default() as f64,
+        },
+        orientation: attitude.map_or_else(Quaternion::default, |[x, y, z, w]| Quaternion {
+            x,
+            y,
+            z,
+            w,
+        }),
+        pose_covariance,
+        linear,
+        angular,
+        twist_covariance,
+    }
+}
+
+// A world-frame vector in the body frame, given the rotation [x, y, z, w] of the body into
+// the world: rotated by the inverse of it.
+fn to_body([x, y, z, w]: [f64; 4], v: [f64; 3]) -> Vector3 {
+    let u = [-x, -y, -z];
+    let cross = |a: [f64; 3], b: [f64; 3]| {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    };
+    let t = cross(u, v).map(|c| 2.0 * c);
+    let ut = cross(u, t);
+    Vector3 {
+        x: v[0] + w * t[0] + ut[0],
+        y: v[1] + w * t[1] + ut[1],
+        z: v[2] + w * t[2] + ut[2],
+    }
+}
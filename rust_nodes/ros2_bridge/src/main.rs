@@ -0,0 +1,129 @@
+mod cdr;
+mod convert;
+mod msgs;
+
+use clap::Parser;
+use common::qos::PublisherBuilderQosExt;
+use convert::Frames;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use std::collections::{HashMap, HashSet};
+use std::process::ExitCode;
+use tracing::{debug, info};
+use zenoh::bytes::Encoding;
+use zenoh::pubsub::Publisher;
+use zenoh::sample::{Sample, SampleKind};
+
+// Prefix zenoh-plugin-dds maps ROS 2 topics under, as ROS 2 names their DDS topics.
+const ROS_TOPICS: &str = "rt";
+
+#[derive(Parser)]
+#[command(
+    about = "Republishes IMU, gyro, and GNSS readings and the fused state as ROS 2 messages in CDR, for zenoh-plugin-dds to carry to ROS 2 nodes"
+)]
+struct Args {
+    /// Key expression to bridge. May be repeated.
+    #[arg(long, default_values_t = [keys::all_devices().to_string(), keys::fused_state().to_string()])]
+    key: Vec<String>,
+    /// Scope zenoh-plugin-dds is configured with, which it puts before the keys of the
+    /// topics it carries.
+    #[arg(long)]
+    scope: Option<String>,
+    /// Frame the fused state's pose is given in.
+    #[arg(long, default_value = "odom")]
+    world_frame: String,
+    /// Frame the readings are measured in and the fused state's pose is of.
+    #[arg(long, default_value = "base_link")]
+    body_frame: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct Ros2Bridge {
+    keys: Vec<String>,
+    scope: Option<String>,
+    frames: Frames,
+    // Publishers are declared on the first sample of their key, by key.
+    publishers: HashMap<String, Publisher<'static>>,
+    // Keys whose samples are not bridged, reported once each.
+    skipped: HashSet<String>,
+}
+
+impl Ros2Bridge {
+    // The key zenoh-plugin-dds carries the topic named after the sample's key on, e.g.
+    // rt/devices/imu0 for the ROS 2 topic /devices/imu0.
+    fn topic_key(&self, key: &str) -> String {
+        match &self.scope {
+            Some(scope) => format!("{}/{}/{}", scope, ROS_TOPICS, key),
+            None => format!("{}/{}", ROS_TOPICS, key),
+        }
+    }
+
+    async fn on_sample(&mut self, ctx: &Context, sample: &Sample) -> zenoh::Result<()> {
+        if sample.kind() == SampleKind::Delete {
+            return Ok(());
+        }
+        let key = sample.key_expr().as_str();
+        let message =
+            convert::convert(sample, &self.frames).map_err(|e| format!("{}: {}", key, e))?;
+        let Some(message) = message else {
+            if self.skipped.insert(key.to_string()) {
+                debug!(
+                    "Not bridging {}: no conversion for {}",
+                    key,
+                    sample.encoding()
+                );
+            }
+            return Ok(());
+        };
+        let topic_key = self.topic_key(key);
+        if !self.publishers.contains_key(&topic_key) {
+            // Published without the node's sequencing, as DDS readers take the payload
+            // as it is: a compressed or shared-memory payload would not be CDR.
+            let publisher = ctx
+                .session()
+                .declare_publisher(topic_key.clone())
+                .encoding(Encoding::APPLICATION_CDR)
+                .qos(ctx.qos(), &topic_key)
+                .await?;
+            info!("Publishing {} on /{} as {}", key, key, message.ros_type);
+            self.publishers.insert(topic_key.clone(), publisher);
+        }
+        self.publishers[&topic_key].put(message.cdr).await
+    }
+}
+
+impl Node for Ros2Bridge {
+    const NAME: &'static str = "ros2_bridge";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        for key in &self.keys {
+            ctx.subscribe(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(ctx, &sample).await,
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = Ros2Bridge {
+        keys: args.key,
+        scope: args.scope,
+        frames: Frames {
+            world: args.world_frame,
+            body: args.body_frame,
+        },
+        publishers: HashMap::new(),
+        skipped: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,247 @@
+// The ROS 2 messages the bridge publishes, with their fields in the order of their .msg
+// definitions, which is the order CDR carries them in.
+use crate::cdr::{Serialize, Writer};
+
+// builtin_interfaces/msg/Time.
+#[derive(Clone, Copy, Default)]
+pub struct Time {
+    pub sec: i32,
+    pub nanosec: u32,
+}
+
+impl Serialize for Time {
+    fn serialize(&self, w: &mut Writer) {
+        w.i32(self.sec);
+        w.u32(self.nanosec);
+    }
+}
+
+// std_msgs/msg/Header.
+#[derive(Clone, Default)]
+pub struct Header {
+    pub stamp: Time,
+    pub frame_id: String,
+}
+
+impl Serialize for Header {
+    fn serialize(&self, w: &mut Writer) {
+        self.stamp.serialize(w);
+        w.string(&self.frame_id);
+    }
+}
+
+// geometry_msgs/msg/Vector3, and geometry_msgs/msg/Point, which has the same fields.
+#[derive(Clone, Copy, Default)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Serialize for Vector3 {
+    fn serialize(&self, w: &mut Writer) {
+        w.f64s(&[self.x, self.y, self.z]);
+    }
+}
+
+// geometry_msgs/msg/Quaternion.
+#[derive(Clone, Copy)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        }
+    }
+}
+
+impl Serialize for Quaternion {
+    fn serialize(&self, w: &mut Writer) {
+        w.f64s(&[self.x, self.y, self.z, self.w]);
+    }
+}
+
+// sensor_msgs/msg/Imu. A covariance whose first element is -1 marks its field as not
+// measured; one of all zeros marks the covariance as unknown.
+#[derive(Clone, Default)]
+pub struct Imu {
+    pub header: Header,
+    pub orientation: Quaternion,
+    pub orientation_covariance: [f64; 9],
+    pub angular_velocity: Vector3,
+    pub angular_velocity_covariance: [f64; 9],
+    pub linear_acceleration: Vector3,
+    pub linear_acceleration_covariance: [f64; 9],
+}
+
+impl Serialize for Imu {
+    fn serialize(&self, w: &mut Writer) {
+        self.header.serialize(w);
+        self.orientation.serialize(w);
+        w.f64s(&self.orientation_covariance);
+        self.angular_velocity.serialize(w);
+        w.f64s(&self.angular_velocity_covariance);
+        self.linear_acceleration.serialize(w);
+        w.f64s(&self.linear_acceleration_covariance);
+    }
+}
+
+// sensor_msgs/msg/NavSatStatus status values.
+pub const STATUS_NO_FIX: i8 = -1;
+pub const STATUS_FIX: i8 = 0;
+// sensor_msgs/msg/NavSatStatus service bits.
+pub const SERVICE_GPS: u16 = 1;
+// sensor_msgs/msg/NavSatFix position_covariance_type values.
+pub const COVARIANCE_TYPE_UNKNOWN: u8 = 0;
+
+// sensor_msgs/msg/NavSatFix, with its sensor_msgs/msg/NavSatStatus inlined.
+#[derive(Clone, Default)]
+pub struct NavSatFix {
+    pub header: Header,
+    pub status: i8,
+    pub service: u16,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: f64,
+    pub position_covariance: [f64; 9],
+    pub position_covariance_type: u8,
+}
+
+impl Serialize for NavSatFix {
+    fn serialize(&self, w: &mut Writer) {
+        self.header.serialize(w);
+        w.i8(self.status);
+        w.u16(self.service);
+        w.f64(self.latitude);
+        w.f64(self.longitude);
+        w.f64(self.altitude);
+        w.f64s(&self.position_covariance);
+        w.u8(self.position_covariance_type);
+    }
+}
+
+// nav_msgs/msg/Odometry, with its geometry_msgs PoseWithCovariance and TwistWithCovariance
+// inlined. The pose is in the header's frame and the twist in child_frame_id's; both
+// covariances are row-major 6x6 over x, y, z and rotation about x, y, z.
+#[derive(Clone)]
+pub struct Odometry {
+    pub header: Header,
+    pub child_frame_id: String,
+    pub position: Vector3,
+    pub orientation: Quaternion,
+    pub pose_covariance: [f64; 36],
+    pub linear: Vector3,
+    pub angular: Vector3,
+    pub twist_covariance: [f64; 36],
+}
+
+impl Serialize for Odometry {
+    fn serialize(&self, w: &mut Writer) {
+        self.header.serialize(w);
+        w.string(&self.child_frame_id);
+        self.position.serialize(w);
+        self.orientation.serialize(w);
+        w.f64s(&self.pose_covariance);
+        self.linear.serialize(w);
+        self.angular.serialize(w);
+        w.f64s(&self.twist_covariance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f64s(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn header(sec: i32, nanosec: u32, frame_id: &str) -> Header {
+        Header {
+            stamp: Time { sec, nanosec },
+            frame_id: frame_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn writes_an_imu_as_ros_does() {
+        let imu = Imu {
+            header: header(1, 2, "imu0"),
+            orientation: Quaternion::default(),
+            orientation_covariance: [-1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            angular_velocity: Vector3 {
+                x: 0.5,
+                y: -0.5,
+                z: 2.0,
+            },
+            angular_velocity_covariance: [0.0; 9],
+            linear_acceleration: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 9.80665,
+            },
+            linear_acceleration_covariance: [0.25; 9],
+        };
+        let expected = [
+            vec![0x00, 0x01, 0x00, 0x00],
+            // Offset 0: the stamp.
+            vec![1, 0, 0, 0, 2, 0, 0, 0],
+            // 8: frame_id, 5 octets with its NUL.
+            vec![5, 0, 0, 0, b'i', b'm', b'u', b'0', 0],
+            // 17: padding to 24, a multiple of 8 from the end of the encapsulation
+            // header, not from the start of the buffer.
+            vec![0; 7],
+            f64s(&[0.0, 0.0, 0.0, 1.0]),
+            f64s(&imu.orientation_covariance),
+            f64s(&[0.5, -0.5, 2.0]),
+            f64s(&[0.0; 9]),
+            f64s(&[0.0, 0.0, 9.80665]),
+            f64s(&[0.25; 9]),
+        ]
+        .concat();
+        let cdr = imu.to_cdr();
+        assert_eq!(cdr.len(), 4 + 320);
+        assert_eq!(cdr, expected);
+    }
+
+    #[test]
+    fn writes_a_nav_sat_fix_as_ros_does() {
+        let fix = NavSatFix {
+            header: header(1_700_000_000, 500_000_000, "gps"),
+            status: STATUS_NO_FIX,
+            service: SERVICE_GPS,
+            latitude: 32.99,
+            longitude: -106.97,
+            altitude: 1401.0,
+            position_covariance: [4.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 16.0],
+            position_covariance_type: COVARIANCE_TYPE_UNKNOWN,
+        };
+        let expected = [
+            vec![0x00, 0x01, 0x00, 0x00],
+            vec![0x00, 0xf1, 0x53, 0x65, 0x00, 0x65, 0xcd, 0x1d],
+            // 8: frame_id, ending at 16 with no padding after it.
+            vec![4, 0, 0, 0, b'g', b'p', b's', 0],
+            // 16: status, then service aligned to 18.
+            vec![0xff, 0x00, 0x01, 0x00],
+            // 20: padding to 24 for the latitude.
+            vec![0; 4],
+            f64s(&[32.99, -106.97, 1401.0]),
+            f64s(&fix.position_covariance),
+            // 120: the covariance type, with nothing after it.
+            vec![0],
+        ]
+        .concat();
+        let cdr = fix.to_cdr();
+        assert_eq!(cdr.len(), 4 + 121);
+        assert_eq!(cdr, expected);
+    }
+}
@@ -0,0 +1,215 @@
+mod timeline;
+
+use clap::Parser;
+use command::{Key, Reply, Senders};
+use common::ZenohArgs;
+use fault_injector::COMMAND_TIMEOUT;
+use fault_injector::faults::Command;
+use keyspace::keys;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use timeline::{Action, Scenario};
+use tokio::time::{Instant, sleep, sleep_until};
+use zenoh::bytes::Encoding;
+
+// How often the required nodes are looked for while waiting for them.
+const REQUIRE_RETRY: Duration = Duration::from_millis(250);
+
+#[derive(Parser)]
+#[command(
+    about = "Plays a YAML timeline of launch, faults, thrust changes, and commands against the running simulation"
+)]
+struct Args {
+    /// Scenario file (YAML).
+    scenario: PathBuf,
+    /// Check the scenario and print its timeline without sending anything.
+    #[arg(long)]
+    check: bool,
+    /// Seconds to wait for the nodes the scenario requires before giving up.
+    #[arg(long, default_value_t = 30.0)]
+    require_timeout_s: f64,
+    /// File holding the secret to sign commands with, for nodes that check signatures.
+    #[arg(long, value_name = "PATH", env = "COMMAND_KEY")]
+    command_key: Option<PathBuf>,
+    #[command(flatten)]
+    zenoh: ZenohArgs,
+}
+
+// Waits until a liveliness token is up on @/alive/<node> for every node.
+async fn require(session: &zenoh::Session, nodes: &[String], wait: Duration) -> Result<(), String> {
+    let deadline = Instant::now() + wait;
+    for node in nodes {
+        loop {
+            let replies = session
+                .liveliness()
+                .get(keys::alive(node))
+                .timeout(REQUIRE_RETRY)
+                .await
+                .map_err(|e| format!("cannot look for {}: {}", node, e))?;
+            if replies.recv_async().await.is_ok() {
+                break;
+            }
+            if Instant::now() >= deadline {
+                return Err(format!("{} is not running", node));
+            }
+            sleep(REQUIRE_RETRY).await;
+        }
+    }
+    Ok(())
+}
+
+// Carries out one action and says how it went.
+async fn execute(
+    session: &zenoh::Session,
+    senders: &Senders,
+    action: &Action,
+) -> Result<String, String> {
+    match action {
+        Action::Launch => session
+            .put(keys::launch(), "s")
+            .encoding(Encoding::TEXT_PLAIN)
+            .await
+            .map(|()| format!("published on {}", keys::launch()))
+            .map_err(|e| e.to_string()),
+        Action::Fault(fault) => {
+            let command = Command::Add(fault.clone());
+            fault_injector::send(session, &command, COMMAND_TIMEOUT)
+                .await
+                .map(|replies| replies.join("; "))
+        }
+        Action::ClearFaults(target) => {
+            let command = Command::Clear(fault_injector::faults::target_key(target)?);
+            fault_injector::send(session, &command, COMMAND_TIMEOUT)
+                .await
+                .map(|replies| replies.join("; "))
+        }
+        Action::ThrustScale(scale) => session
+            .put(keys::sim_thrust_scale(), scale.to_string())
+            .encoding(Encoding::TEXT_PLAIN)
+            .await
+            .map(|()| format!("published on {}", keys::sim_thrust_scale()))
+            .map_err(|e| e.to_string()),
+        Action::Command {
+            node,
+            command,
+            args,
+        } => match senders.send(node, command, args).await {
+            Ok(Reply {
+                result: Ok(message),
+                ..
+            }) => Ok(format!("ack: {}", message)),
+            Ok(Reply {
+                result: Err(nack), ..
+            }) => Err(format!("nack: {}", nack)),
+            Err(e) => Err(e.to_string()),
+        },
+    }
+}
+
+// A time from launch as T-1.50 or T+12.00, padded to line up.
+fn stamp(t: f64) -> String {
+    format!("{:<8}", format!("T{:+.2}", t))
+}
+
+fn print_timeline(scenario: &Scenario) {
+    if !scenario.description.is_empty() {
+        println!("{}", scenario.description);
+    }
+    println!("{}  countdown", stamp(-scenario.countdown_s));
+    for event in &scenario.events {
+        println!("{}  {}", stamp(event.at), event.action);
+    }
+    if let Some(end_s) = scenario.end_s {
+        println!("{}  end", stamp(end_s));
+    }
+}
+
+// Plays the timeline from now, stopping at the first event that fails.
+async fn play(session: &zenoh::Session, scenario: &Scenario, key: Option<Key>) -> ExitCode {
+    let launch = Instant::now() + Duration::from_secs_f64(scenario.countdown_s);
+    let at = |t: f64| {
+        if t >= 0.0 {
+            launch + Duration::from_secs_f64(t)
+        } else {
+            launch - Duration::from_secs_f64(-t)
+        }
+    };
+    let now = || {
+        let now = Instant::now();
+        if now >= launch {
+            (now - launch).as_secs_f64()
+        } else {
+            -(launch - now).as_secs_f64()
+        }
+    };
+    println!("{}  countdown", stamp(now()));
+    // One sender per node, so its sequence numbers run on through the scenario.
+    let senders = Senders::new(session, COMMAND_TIMEOUT, key);
+    for event in &scenario.events {
+        sleep_until(at(event.at)).await;
+        let started = now();
+        match execute(session, &senders, &event.action).await {
+            Ok(outcome) => println!("{}  {}: {}", stamp(started), event.action, outcome),
+            Err(e) => {
+                eprintln!("{}  {} failed: {}", stamp(started), event.action, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Some(end_s) = scenario.end_s {
+        sleep_until(at(end_s)).await;
+    }
+    println!("{}  end", stamp(now()));
+    ExitCode::SUCCESS
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let scenario = match Scenario::load(&args.scenario) {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            eprintln!("Invalid scenario: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.check {
+        print_timeline(&scenario);
+        return ExitCode::SUCCESS;
+    }
+    if !(args.require_timeout_s.is_finite() && args.require_timeout_s >= 0.0) {
+        eprintln!("--require-timeout-s must not be negative");
+        return ExitCode::FAILURE;
+    }
+    let key = match args.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let session = match args.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if !scenario.description.is_empty() {
+        println!("{}", scenario.description);
+    }
+    let wait = Duration::from_secs_f64(args.require_timeout_s);
+    let status = match require(&session, &scenario.require, wait).await {
+        Ok(()) => play(&session, &scenario, key).await,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    };
+    if let Err(e) = session.close().await {
+        eprintln!("Failed to close Zenoh session: {}", e);
+    }
+    status
+}
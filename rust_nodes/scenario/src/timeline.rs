@@ -0,0 +1,228 @@
+// A scenario file: a YAML timeline of events in seconds from launch (T-0), checked when it
+// is loaded so a mistake fails before anything has been sent.
+use clap::ValueEnum;
+use fault_injector::faults::{self, Fault, FaultKind};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    require: Vec<String>,
+    #[serde(default = "default_countdown_s")]
+    countdown_s: f64,
+    #[serde(default = "default_launch")]
+    launch: bool,
+    end_s: Option<f64>,
+    #[serde(default)]
+    events: Vec<EventEntry>,
+}
+
+fn default_countdown_s() -> f64 {
+    5.0
+}
+
+fn default_launch() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct EventEntry {
+    at: f64,
+    #[serde(flatten)]
+    action: ActionEntry,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "event", rename_all = "snake_case")]
+enum ActionEntry {
+    Fault(FaultEntry),
+    ClearFaults(String),
+    ThrustScale(f64),
+    Command(CommandEntry),
+}
+
+#[derive(Deserialize)]
+struct FaultEntry {
+    target: String,
+    kind: String,
+    #[serde(default)]
+    magnitude: f64,
+    probability: Option<f64>,
+    channel: Option<String>,
+    duration_s: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct CommandEntry {
+    node: String,
+    command: String,
+    #[serde(default)]
+    args: Mapping,
+}
+
+pub enum Action {
+    // Publishes "s" on the launch key.
+    Launch,
+    // Sent to fault_injector on faults/command.
+    Fault(Fault),
+    ClearFaults(String),
+    // Published on sim/thrust_scale for dynamics_sim.
+    ThrustScale(f64),
+    // Sent to a node on cmd/<node>/<command>.
+    Command {
+        node: String,
+        command: String,
+        args: Vec<(String, String)>,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Launch => write!(f, "launch"),
+            Action::Fault(fault) => write!(f, "fault {}", fault),
+            Action::ClearFaults(target) => write!(f, "clear faults on {}", target),
+            Action::ThrustScale(scale) => write!(f, "thrust scale {}", scale),
+            Action::Command {
+                node,
+                command,
+                args,
+            } => {
+                write!(f, "command {} {}", node, command)?;
+                for (name, value) in args {
+                    write!(f, " {}={}", name, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub struct Event {
+    // Seconds from launch; negative before it.
+    pub at: f64,
+    pub action: Action,
+}
+
+pub struct Scenario {
+    pub description: String,
+    // Nodes whose liveliness tokens must be up before the countdown starts.
+    pub require: Vec<String>,
+    // Seconds from the start of the scenario to T-0.
+    pub countdown_s: f64,
+    // Seconds from launch the scenario ends at; it ends after its last event if None.
+    pub end_s: Option<f64>,
+    // In order of time; events at the same time in the order of the file, after launch.
+    pub events: Vec<Event>,
+}
+
+// A command argument given as any YAML scalar, as the text nodes receive.
+fn argument(name: &Value, value: &Value) -> Result<(String, String), String> {
+    let text = |value: &Value| match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    };
+    match (text(name), text(value)) {
+        (Some(name), Some(value)) => Ok((name, value)),
+        _ => Err("command arguments must be scalars".to_string()),
+    }
+}
+
+impl ActionEntry {
+    fn into_action(self) -> Result<Action, String> {
+        Ok(match self {
+            ActionEntry::Fault(fault) => {
+                let kind = FaultKind::from_str(&fault.kind, true)
+                    .map_err(|_| format!("unknown fault kind '{}'", fault.kind))?;
+                Action::Fault(Fault::new(
+                    &fault.target,
+                    kind,
+                    fault.magnitude,
+                    fault.probability,
+                    fault.channel,
+                    fault.duration_s,
+                )?)
+            }
+            ActionEntry::ClearFaults(target) => {
+                faults::target_key(&target)?;
+                Action::ClearFaults(target)
+            }
+            ActionEntry::ThrustScale(scale) => {
+                if !(scale.is_finite() && scale >= 0.0) {
+                    return Err("thrust scale must not be negative".to_string());
+                }
+                Action::ThrustScale(scale)
+            }
+            ActionEntry::Command(command) => Action::Command {
+                node: command.node,
+                command: command.command,
+                args: command
+                    .args
+                    .iter()
+                    .map(|(name, value)| argument(name, value))
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let file: File = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+        if !(file.countdown_s.is_finite() && file.countdown_s >= 0.0) {
+            return Err("countdown_s must not be negative".to_string());
+        }
+        let mut events = Vec::with_capacity(file.events.len() + 1);
+        if file.launch {
+            events.push(Event {
+                at: 0.0,
+                action: Action::Launch,
+            });
+        }
+        for (i, entry) in file.events.into_iter().enumerate() {
+            if !entry.at.is_finite() || entry.at < -file.countdown_s {
+                return Err(format!(
+                    "event {} at T{:+} is before the countdown starts at T-{}",
+                    i + 1,
+                    entry.at,
+                    file.countdown_s
+                ));
+            }
+            let action = entry
+                .action
+                .into_action()
+                .map_err(|e| format!("event {}: {}", i + 1, e))?;
+            events.push(Event {
+                at: entry.at,
+                action,
+            });
+        }
+        events.sort_by(|a, b| a.at.total_cmp(&b.at));
+        if let Some(end_s) = file.end_s {
+            let last = events.last().map_or(f64::NEG_INFINITY, |event| event.at);
+            if !end_s.is_finite() || end_s < last {
+                return Err(format!("end_s is before the last event at T{:+}", last));
+            }
+        }
+        Ok(Scenario {
+            description: file.description,
+            require: file.require,
+            countdown_s: file.countdown_s,
+            end_s: file.end_s,
+            events,
+        })
+    }
+}
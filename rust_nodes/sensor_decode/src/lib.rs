@@ -0,0 +1,291 @@
+use common::encoding;
+use sensors_rs::sensors;
+use zenoh::bytes::{Encoding, ZBytes};
+
+// Sensor payload types that generic tools know how to decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageType {
+    Imu,
+    Gyro,
+    Altitude,
+    Barometer,
+    Magnetometer,
+    Gnss,
+    Temperature,
+    Power,
+}
+
+impl MessageType {
+    pub const ALL: [MessageType; 8] = [
+        MessageType::Imu,
+        MessageType::Gyro,
+        MessageType::Altitude,
+        MessageType::Barometer,
+        MessageType::Magnetometer,
+        MessageType::Gnss,
+        MessageType::Temperature,
+        MessageType::Power,
+    ];
+
+    // Picks the decoder from the table the payload encoding names. Only payloads published
+    // without an encoding fall back to the device kind in the key; a payload tagged with
+    // another table or encoding is not a sensor reading, whatever its key.
+    pub fn detect(key: &str, encoding: &Encoding) -> Option<Self> {
+        if let Some(table) = encoding::flatbuffer_table(encoding) {
+            return Self::ALL.into_iter().find(|kind| kind.table() == table);
+        }
+        if *encoding == Encoding::default() || *encoding == Encoding::APPLICATION_OCTET_STREAM {
+            return Self::from_key(key);
+        }
+        None
+    }
+
+    // The sensors table samples of this type carry, as tagged by encoding::flatbuffer().
+    pub fn table(self) -> &'static str {
+        match self {
+            MessageType::Imu => "sensors.IMU",
+            MessageType::Gyro => "sensors.Gyro",
+            MessageType::Altitude => "sensors.Altitude",
+            MessageType::Barometer => "sensors.Barometer",
+            MessageType::Magnetometer => "sensors.Magnetometer",
+            MessageType::Gnss => "sensors.GnssFix",
+            MessageType::Temperature => "sensors.Temperature",
+            MessageType::Power => "sensors.Power",
+        }
+    }
+
+    // Device kind from the last key chunk with its numeric id stripped, e.g. devices/imu2.
+    fn from_key(key: &str) -> Option<Self> {
+        let device = key.rsplit('/').next()?;
+        match device.trim_end_matches(|c: char| c.is_ascii_digit()) {
+            "imu" => Some(MessageType::Imu),
+            "gyro" => Some(MessageType::Gyro),
+            "altitude" => Some(MessageType::Altitude),
+            "baro" => Some(MessageType::Barometer),
+            "mag" => Some(MessageType::Magnetometer),
+            "gnss" => Some(MessageType::Gnss),
+            "temp" => Some(MessageType::Temperature),
+            "power" => Some(MessageType::Power),
+            _ => None,
+        }
+    }
+
+    // Names of the values a sample of this type carries, in the order values() returns
+    // them. They follow the schema's field names.
+    pub fn channels(self) -> &'static [&'static str] {
+        match self {
+            MessageType::Imu => &["acceleration_x", "acceleration_y", "acceleration_z"],
+            MessageType::Gyro => &["omega_x", "omega_y", "omega_z"],
+            MessageType::Altitude => &["altitude"],
+            MessageType::Barometer => &["pressure", "temperature"],
+            MessageType::Magnetometer => &["field_x", "field_y", "field_z"],
+            // fix_type is the GnssFixType value: 0 no fix, 1 2D, 2 3D.
+            MessageType::Gnss => &[
+                "timestamp_us",
+                "fix_type",
+                "latitude_deg",
+                "longitude_deg",
+                "altitude",
+                "velocity_x",
+                "velocity_y",
+                "velocity_z",
+                "hdop",
+                "vdop",
+                "satellites",
+            ],
+            // valid is 1 or 0.
+            MessageType::Temperature => &["value", "sensor_id", "valid"],
+            MessageType::Power => &["voltage", "current", "temperature"],
+        }
+    }
+}
+
+// Decodes the payload, published with the given encoding, into the values named by the
+// type's channels(). Payloads of every schema version a node reads are decoded; see
+// encoding::SCHEMA_VERSION.
+pub fn values(
+    kind: MessageType,
+    encoding: &Encoding,
+    payload: &ZBytes,
+) -> Result<Vec<f64>, String> {
+    let bytes = payload.to_bytes();
+    let version = encoding::schema_version(encoding).unwrap_or(encoding::SCHEMA_VERSION);
+    if kind == MessageType::Temperature && version == 1 {
+        // A bare little-endian f32, with no id and always valid.
+        let value: [u8; 4] = bytes[..]
+            .try_into()
+            .map_err(|_| format!("expected a 4-byte f32, got {} bytes", bytes.len()))?;
+        return Ok(vec![f32::from_le_bytes(value) as f64, 0.0, 1.0]);
+    }
+    match kind {
+        MessageType::Imu => {
+            let imu = flatbuffers::root::<sensors::IMU>(&bytes).map_err(|e| e.to_string())?;
+            let a = imu
+                .acceleration()
+                .ok_or_else(|| "IMU sample has no acceleration".to_string())?;
+            Ok(vec![a.x() as f64, a.y() as f64, a.z() as f64])
+        }
+        MessageType::Gyro => {
+            let gyro = flatbuffers::root::<sensors::Gyro>(&bytes).map_err(|e| e.to_string())?;
+            Ok(vec![
+                gyro.omega_x() as f64,
+                gyro.omega_y() as f64,
+                gyro.omega_z() as f64,
+            ])
+        }
+        MessageType::Altitude => {
+            let altitude =
+                flatbuffers::root::<sensors::Altitude>(&bytes).map_err(|e| e.to_string())?;
+            Ok(vec![altitude.altitude() as f64])
+        }
+        MessageType::Barometer => {
+            let barometer =
+                flatbuffers::root::<sensors::Barometer>(&bytes).map_err(|e| e.to_string())?;
+            Ok(vec![
+                barometer.pressure() as f64,
+                barometer.temperature() as f64,
+            ])
+        }
+        MessageType::Magnetometer => {
+            let magnetometer =
+                flatbuffers::root::<sensors::Magnetometer>(&bytes).map_err(|e| e.to_string())?;
+            let b = magnetometer
+                .field()
+                .ok_or_else(|| "magnetometer sample has no field".to_string())?;
+            Ok(vec![b.x() as f64, b.y() as f64, b.z() as f64])
+        }
+        MessageType::Gnss => {
+            let fix = flatbuffers::root::<sensors::GnssFix>(&bytes).map_err(|e| e.to_string())?;
+            let velocity = fix.velocity().copied().unwrap_or_default();
+            Ok(vec![
+                fix.timestamp_us() as f64,
+                fix.fix_type().0 as f64,
+                fix.latitude_deg(),
+                fix.longitude_deg(),
+                fix.altitude() as f64,
+                velocity.x() as f64,
+                velocity.y() as f64,
+                velocity.z() as f64,
+                fix.hdop() as f64,
+                fix.vdop() as f64,
+                fix.satellites() as f64,
+            ])
+        }
+        MessageType::Temperature => {
+            let temperature =
+                flatbuffers::root::<sensors::Temperature>(&bytes).map_err(|e| e.to_string())?;
+            Ok(vec![
+                temperature.value() as f64,
+                temperature.sensor_id() as f64,
+                temperature.valid() as u8 as f64,
+            ])
+        }
+        MessageType::Power => {
+            let power = flatbuffers::root::<sensors::Power>(&bytes).map_err(|e| e.to_string())?;
+            Ok(vec![
+                power.voltage() as f64,
+                power.current() as f64,
+                power.temperature() as f64,
+            ])
+        }
+    }
+}
+
+// Encodes values in the order of the type's channels() as a payload of the type at the
+// current schema version, to be tagged encoding::flatbuffer(kind.table()); the inverse
+// of values().
+pub fn encode(kind: MessageType, values: &[f64]) -> ZBytes {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(64);
+    let v = |i: usize| values.get(i).copied().unwrap_or(0.0) as f32;
+    match kind {
+        MessageType::Imu => {
+            let acceleration = sensors::Vec3::new(v(0), v(1), v(2));
+            let imu = sensors::IMU::create(
+                &mut builder,
+                &sensors::IMUArgs {
+                    acceleration: Some(&acceleration),
+                },
+            );
+            builder.finish(imu, None);
+        }
+        MessageType::Gyro => {
+            let gyro = sensors::Gyro::create(
+                &mut builder,
+                &sensors::GyroArgs {
+                    omega_x: v(0),
+                    omega_y: v(1),
+                    omega_z: v(2),
+                },
+            );
+            builder.finish(gyro, None);
+        }
+        MessageType::Altitude => {
+            let altitude =
+                sensors::Altitude::create(&mut builder, &sensors::AltitudeArgs { altitude: v(0) });
+            builder.finish(altitude, None);
+        }
+        MessageType::Barometer => {
+            let barometer = sensors::Barometer::create(
+                &mut builder,
+                &sensors::BarometerArgs {
+                    pressure: v(0),
+                    temperature: v(1),
+                },
+            );
+            builder.finish(barometer, None);
+        }
+        MessageType::Magnetometer => {
+            let field = sensors::Vec3::new(v(0), v(1), v(2));
+            let magnetometer = sensors::Magnetometer::create(
+                &mut builder,
+                &sensors::MagnetometerArgs {
+                    field: Some(&field),
+                },
+            );
+            builder.finish(magnetometer, None);
+        }
+        MessageType::Gnss => {
+            let raw = |i: usize| values.get(i).copied().unwrap_or(0.0);
+            let velocity = sensors::Vec3::new(v(5), v(6), v(7));
+            let fix = sensors::GnssFix::create(
+                &mut builder,
+                &sensors::GnssFixArgs {
+                    timestamp_us: raw(0) as u64,
+                    fix_type: sensors::GnssFixType(raw(1) as i8),
+                    latitude_deg: raw(2),
+                    longitude_deg: raw(3),
+                    altitude: v(4),
+                    velocity: Some(&velocity),
+                    hdop: v(8),
+                    vdop: v(9),
+                    satellites: raw(10) as u8,
+                },
+            );
+            builder.finish(fix, None);
+        }
+        MessageType::Temperature => {
+            let raw = |i: usize| values.get(i).copied().unwrap_or(0.0);
+            let temperature = sensors::Temperature::create(
+                &mut builder,
+                &sensors::TemperatureArgs {
+                    value: v(0),
+                    sensor_id: raw(1) as u32,
+                    valid: raw(2) != 0.0,
+                },
+            );
+            builder.finish(temperature, None);
+        }
+        MessageType::Power => {
+            let power = sensors::Power::create(
+                &mut builder,
+                &sensors::PowerArgs {
+                    voltage: v(0),
+                    current: v(1),
+                    temperature: v(2),
+                },
+            );
+            builder.finish(power, None);
+        }
+    }
+    builder.finished_data().to_vec().into()
+}
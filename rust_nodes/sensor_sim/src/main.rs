@@ -0,0 +1,121 @@
+mod trajectory;
+
+use clap::Parser;
+use common::shutdown_signal;
+use keyspace::keys;
+use node_framework::{Heartbeat, LogControl, NodeArgs, NodeError};
+use sim_common::{FlightClock, SensorArgs, SensorInputs, SimError};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+use trajectory::Trajectory;
+
+// Simulated flight: 5 g boost for 3 s off a rail 5 degrees from vertical, spinning at
+// 1 rad/s about the long axis.
+const BOOST_ACCEL: f64 = 50.0;
+const BURN_TIME: f64 = 3.0;
+const ELEVATION_DEG: f64 = 85.0;
+const AZIMUTH_DEG: f64 = 30.0;
+const SPIN_RATE: f64 = 1.0;
+
+#[derive(Parser)]
+#[command(
+    about = "Publishes simulated IMU, gyro, altimeter, barometer, magnetometer and GNSS samples on devices/*"
+)]
+struct Args {
+    /// Launch this many seconds after startup instead of waiting for a launch command.
+    #[arg(long)]
+    launch_after_s: Option<f64>,
+    #[command(flatten)]
+    sensors: SensorArgs,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    match run(args).await {
+        Ok(status) => status,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Simulates the sensors until shutdown. Fails if the simulation cannot start; failures
+// in the teardown are reported and make the status a failure.
+async fn run(mut args: Args) -> Result<ExitCode, SimError> {
+    args.sensors.validate().map_err(SimError::Args)?;
+
+    args.node.zenoh.timestamping = args.sensors.publication_cache.is_some();
+    let session = args.node.zenoh.open().await.map_err(NodeError::from)?;
+    node_framework::crash::install(&session, args.node.name("sensor_sim"), &args.node);
+    let trajectory = Trajectory::new(
+        BOOST_ACCEL,
+        BURN_TIME,
+        ELEVATION_DEG.to_radians(),
+        AZIMUTH_DEG.to_radians(),
+        SPIN_RATE,
+    );
+    let clock = FlightClock::default();
+    let launch_subscriber = clock
+        .listen(&session)
+        .await
+        .map_err(NodeError::declare("launch subscriber"))?;
+
+    let inputs_clock = clock.clone();
+    let tasks = args.sensors.spawn(
+        &session,
+        args.node.name("sensor_sim"),
+        args.node.crc32c,
+        Arc::new(move || {
+            let truth = trajectory.at(inputs_clock.elapsed());
+            SensorInputs {
+                specific_force: truth.specific_force().into(),
+                angular_rate: truth.angular_rate.into(),
+                altitude: truth.position.z,
+                magnetic_field: truth.magnetic_field().into(),
+                position: truth.position.into(),
+                velocity: truth.velocity.into(),
+            }
+        }),
+    );
+
+    let name = args.node.name("sensor_sim");
+    let heartbeat = Heartbeat::start(&session, name, args.node.heartbeat_period())
+        .await
+        .map_err(|source| NodeError::Start {
+            node: name.to_string(),
+            source,
+        })?;
+    let _log_control = LogControl::start(&session, name, &args.node).await?;
+
+    match args.launch_after_s {
+        Some(delay) => clock.launch_after(Duration::from_secs_f64(delay.max(0.0))),
+        None => info!("Waiting for launch command on '{}'.", keys::launch()),
+    }
+    shutdown_signal().await;
+
+    info!("Shutting down sensor_sim...");
+    for task in &tasks {
+        task.abort();
+    }
+    let mut status = ExitCode::SUCCESS;
+    if let Err(e) = heartbeat.stop().await {
+        error!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = launch_subscriber.undeclare().await {
+        error!("Failed to undeclare launch subscriber: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = session.close().await {
+        error!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    Ok(status)
+}
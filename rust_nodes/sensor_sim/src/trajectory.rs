@@ -0,0 +1,118 @@
+use nalgebra::{Rotation3, Unit, Vector3};
+
+pub const GRAVITY: f64 = 9.81;
+
+// True kinematic state of the vehicle at one instant, in an east-north-up world frame.
+// The body z axis points along the vehicle's long axis.
+pub struct Truth {
+    pub position: Vector3<f64>,
+    pub velocity: Vector3<f64>,
+    pub acceleration: Vector3<f64>,
+    pub body_to_world: Rotation3<f64>,
+    pub angular_rate: Vector3<f64>,
+}
+
+impl Truth {
+    // What an ideal body-fixed accelerometer reads: kinematic acceleration minus gravity,
+    // in the body frame.
+    pub fn specific_force(&self) -> Vector3<f64> {
+        self.body_to_world.inverse() * (self.acceleration + Vector3::new(0.0, 0.0, GRAVITY))
+    }
+
+    // What an ideal body-fixed magnetometer reads: Earth's field in the body frame.
+    pub fn magnetic_field(&self) -> Vector3<f64> {
+        self.body_to_world.inverse() * Vector3::from(sim_common::devices::MAGNETIC_FIELD)
+    }
+}
+
+// Rail launch with a constant-thrust boost followed by a ballistic coast. The vehicle leaves
+// the rail at a fixed elevation and azimuth, spins about its long axis while airborne, and
+// comes to rest where it hits the ground.
+pub struct Trajectory {
+    boost_accel: f64,
+    burn_time: f64,
+    spin_rate: f64,
+    direction: Unit<Vector3<f64>>,
+    rail: Rotation3<f64>,
+    burnout: (Vector3<f64>, Vector3<f64>),
+    landing_time: f64,
+}
+
+impl Trajectory {
+    pub fn new(
+        boost_accel: f64,
+        burn_time: f64,
+        elevation: f64,
+        azimuth: f64,
+        spin_rate: f64,
+    ) -> Self {
+        let direction = Unit::new_normalize(Vector3::new(
+            elevation.cos() * azimuth.cos(),
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+        ));
+        let rail = Rotation3::rotation_between(&Vector3::z(), &direction)
+            .unwrap_or_else(Rotation3::identity);
+
+        let v = direction.into_inner() * boost_accel * burn_time;
+        let p = v * burn_time / 2.0;
+        // Time after burnout at which the coast arc returns to the ground.
+        let coast = (v.z + (v.z * v.z + 2.0 * GRAVITY * p.z).sqrt()) / GRAVITY;
+
+        Trajectory {
+            boost_accel,
+            burn_time,
+            spin_rate,
+            direction,
+            rail,
+            burnout: (p, v),
+            landing_time: burn_time + coast,
+        }
+    }
+
+    // Truth at t seconds after launch; None means the vehicle is still on the pad.
+    pub fn at(&self, t: Option<f64>) -> Truth {
+        let t = match t {
+            Some(t) if t > 0.0 => t.min(self.landing_time),
+            _ => return self.resting(Vector3::zeros(), self.rail),
+        };
+        let spin = Rotation3::from_axis_angle(&Vector3::z_axis(), self.spin_rate * t);
+        let attitude = self.rail * spin;
+
+        if t >= self.landing_time {
+            let (p, _) = self.coast(self.landing_time - self.burn_time);
+            return self.resting(Vector3::new(p.x, p.y, 0.0), attitude);
+        }
+
+        let (position, velocity, acceleration) = if t < self.burn_time {
+            let a = self.direction.into_inner() * self.boost_accel;
+            (a * t * t / 2.0, a * t, a)
+        } else {
+            let (p, v) = self.coast(t - self.burn_time);
+            (p, v, Vector3::new(0.0, 0.0, -GRAVITY))
+        };
+        Truth {
+            position,
+            velocity,
+            acceleration,
+            body_to_world: attitude,
+            angular_rate: Vector3::new(0.0, 0.0, self.spin_rate),
+        }
+    }
+
+    fn coast(&self, dt: f64) -> (Vector3<f64>, Vector3<f64>) {
+        let (p0, v0) = self.burnout;
+        let g = Vector3::new(0.0, 0.0, -GRAVITY);
+        (p0 + v0 * dt + g * dt * dt / 2.0, v0 + g * dt)
+    }
+
+    fn resting(&self, position: Vector3<f64>, attitude: Rotation3<f64>) -> Truth {
+        Truth {
+            position,
+            velocity: Vector3::zeros(),
+            acceleration: Vector3::zeros(),
+            body_to_world: attitude,
+            angular_rate: Vector3::zeros(),
+        }
+    }
+}
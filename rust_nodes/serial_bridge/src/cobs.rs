@@ -0,0 +1,224 @@
+// Consistent Overhead Byte Stuffing: rewrites a frame so it holds no zero octets, at a cost
+// of one octet in 254, so a zero can end each frame on the link. A receiver that joins
+// mid-frame, or loses octets to line noise, picks up again at the next zero.
+const DELIMITER: u8 = 0x00;
+// Longest run of non-zero octets one code octet covers.
+const MAX_RUN: usize = 254;
+
+// Appends the frame, stuffed and followed by the delimiter.
+pub fn encode(frame: &[u8], out: &mut Vec<u8>) {
+    let mut code_at = out.len();
+    out.push(0);
+    let mut run = 0;
+    for &byte in frame {
+        // A full run is closed only once another octet follows it, as a frame ending on
+        // one needs no further code octet.
+        if run == MAX_RUN {
+            out[code_at] = run as u8 + 1;
+            code_at = out.len();
+            out.push(0);
+            run = 0;
+        }
+        if byte == DELIMITER {
+            out[code_at] = run as u8 + 1;
+            code_at = out.len();
+            out.push(0);
+            run = 0;
+            continue;
+        }
+        out.push(byte);
+        run += 1;
+    }
+    out[code_at] = run as u8 + 1;
+    out.push(DELIMITER);
+}
+
+// Unstuffs a frame received between two delimiters, without either.
+pub fn decode(stuffed: &[u8]) -> Result<Vec<u8>, String> {
+    let mut frame = Vec::with_capacity(stuffed.len());
+    let mut rest = stuffed;
+    while let Some((&code, after)) = rest.split_first() {
+        let run = code as usize - 1;
+        let Some(bytes) = after.get(..run) else {
+            return Err(format!(
+                "code octet {} runs past the end of the frame",
+                code
+            ));
+        };
+        frame.extend_from_slice(bytes);
+        rest = &after[run..];
+        // A run of MAX_RUN octets ends without a zero, and so does the last run.
+        if run < MAX_RUN && !rest.is_empty() {
+            frame.push(DELIMITER);
+        }
+    }
+    Ok(frame)
+}
+
+// Takes the stuffed frames out of the octets the link delivers, as they arrive.
+pub struct Decoder {
+    stuffed: Vec<u8>,
+    max_len: usize,
+    // Whether the frame being received has grown too long to keep.
+    overrun: bool,
+}
+
+impl Decoder {
+    // A decoder dropping frames over max_len octets unstuffed.
+    pub fn new(max_len: usize) -> Self {
+        Decoder {
+            stuffed: Vec::new(),
+            max_len: max_len + max_len / MAX_RUN + 1,
+            overrun: false,
+        }
+    }
+
+    // Takes the next octet, returning the frame it ends, unstuffed. Empty frames, as two
+    // delimiters in a row make, are skipped.
+    pub fn push(&mut self, byte: u8) -> Option<Result<Vec<u8>, String>> {
+        if byte != DELIMITER {
+            if self.stuffed.len() == self.max_len {
+                self.overrun = true;
+            } else {
+                self.stuffed.push(byte);
+            }
+            return None;
+        }
+        let stuffed = std::mem::take(&mut self.stuffed);
+        if std::mem::take(&mut self.overrun) {
+            return Some(Err("frame is too long".to_string()));
+        }
+        if stuffed.is_empty() {
+            return None;
+        }
+        Some(decode(&stuffed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stuff(frame: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode(frame, &mut out);
+        out
+    }
+
+    // Octets 1 to n, none of them zero.
+    fn counting(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i % 255) as u8 + 1).collect()
+    }
+
+    #[test]
+    fn encodes_the_paper_examples() {
+        // The example of Cheshire and Baker's paper, then the edge cases commonly given
+        // with it.
+        let cases: [(&[u8], &[u8]); 6] = [
+            (
+                &[0x11, 0x22, 0x00, 0x33],
+                &[0x03, 0x11, 0x22, 0x02, 0x33, 0x00],
+            ),
+            (&[0x00], &[0x01, 0x01, 0x00]),
+            (&[0x00, 0x00], &[0x01, 0x01, 0x01, 0x00]),
+            (&[0x00, 0x11, 0x00], &[0x01, 0x02, 0x11, 0x01, 0x00]),
+            (
+                &[0x11, 0x22, 0x33, 0x44],
+                &[0x05, 0x11, 0x22, 0x33, 0x44, 0x00],
+            ),
+            (
+                &[0x11, 0x00, 0x00, 0x00],
+                &[0x02, 0x11, 0x01, 0x01, 0x01, 0x00],
+            ),
+        ];
+        for (frame, stuffed) in cases {
+            assert_eq!(stuff(frame), stuffed, "{:02x?}", frame);
+        }
+    }
+
+    #[test]
+    fn ends_a_full_run_without_another_code() {
+        let frame = counting(254);
+        let expected = [&[0xff], frame.as_slice(), &[0x00]].concat();
+        assert_eq!(stuff(&frame), expected);
+
+        let frame = [&[0x00], counting(254).as_slice()].concat();
+        let expected = [&[0x01, 0xff], &frame[1..], &[0x00]].concat();
+        assert_eq!(stuff(&frame), expected);
+
+        // A full run, then a zero.
+        let frame = [counting(254).as_slice(), &[0x00]].concat();
+        let expected = [&[0xff], &frame[..254], &[0x01, 0x01, 0x00]].concat();
+        assert_eq!(stuff(&frame), expected);
+
+        let frame = counting(255);
+        let expected = [&[0xff], &frame[..254], &[0x02, frame[254], 0x00]].concat();
+        assert_eq!(stuff(&frame), expected);
+    }
+
+    #[test]
+    fn round_trips_zero_free_frames() {
+        // Code octets added: one per full run begun.
+        for (len, codes) in [(0, 1), (253, 1), (254, 1), (255, 2), (508, 2)] {
+            let frame = counting(len);
+            let stuffed = stuff(&frame);
+            assert_eq!(stuffed.len(), len + codes + 1, "{} octets", len);
+            let (&last, body) = stuffed.split_last().unwrap();
+            assert_eq!(last, DELIMITER);
+            assert!(!body.contains(&DELIMITER), "{} octets", len);
+            assert_eq!(decode(body).unwrap(), frame, "{} octets", len);
+        }
+    }
+
+    #[test]
+    fn round_trips_frames_with_zeros() {
+        let mut frames: Vec<Vec<u8>> = vec![vec![0; 300], vec![0, 0, 7]];
+        for at in [0, 1, 253, 254, 255, 507, 508] {
+            let mut frame = counting(509);
+            frame[at] = 0;
+            frames.push(frame);
+        }
+        for frame in frames {
+            let stuffed = stuff(&frame);
+            let body = &stuffed[..stuffed.len() - 1];
+            assert!(!body.contains(&DELIMITER));
+            assert_eq!(decode(body).unwrap(), frame);
+        }
+    }
+
+    #[test]
+    fn refuses_a_code_past_the_end() {
+        assert!(decode(&[0x05, 0x11, 0x22]).is_err());
+    }
+
+    #[test]
+    fn decodes_frames_as_they_arrive() {
+        let mut link = vec![0x00, 0x00];
+        for frame in [counting(254), vec![0x00], counting(508)] {
+            encode(&frame, &mut link);
+        }
+        let mut decoder = Decoder::new(508);
+        let frames: Vec<Vec<u8>> = link
+            .into_iter()
+            .filter_map(|byte| decoder.push(byte))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(frames, [counting(254), vec![0x00], counting(508)]);
+    }
+
+    #[test]
+    fn drops_frames_too_long() {
+        let mut link = Vec::new();
+        encode(&counting(20), &mut link);
+        encode(&counting(5), &mut link);
+        let mut decoder = Decoder::new(10);
+        let results: Vec<_> = link
+            .into_iter()
+            .filter_map(|byte| decoder.push(byte))
+            .collect();
+        assert_eq!(
+            results,
+            [Err("frame is too long".to_string()), Ok(counting(5))]
+        );
+    }
+}
@@ -0,0 +1,109 @@
+// One sample as it crosses the link, before stuffing, all little-endian:
+//
+// - a flags octet: the Zenoh priority (1 to 7) in the low three bits, then whether the
+//   sample is a delete, then whether a timestamp follows;
+// - the sample's timestamp as a u64 NTP64, if it has one;
+// - the u16 length of the key, then the key;
+// - the u16 length of the encoding, then the encoding;
+// - the payload;
+// - the u32 CRC32C of everything before it, as line noise the serial link does not catch
+//   would otherwise republish a corrupted sample.
+use common::crc32c;
+
+const PRIORITY: u8 = 0b0000_0111;
+const DELETE: u8 = 0b0000_1000;
+const TIMESTAMP: u8 = 0b0001_0000;
+const CRC_LEN: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame<'a> {
+    pub priority: u8,
+    pub delete: bool,
+    // NTP64 time the sample was published at.
+    pub time: Option<u64>,
+    pub key: &'a str,
+    pub encoding: &'a str,
+    pub payload: &'a [u8],
+}
+
+// Reads a length-prefixed string off the front of rest.
+fn take_str<'a>(rest: &mut &'a [u8], what: &str) -> Result<&'a str, String> {
+    let Some((len, after)) = rest.split_first_chunk::<2>() else {
+        return Err(format!("frame ends in the length of its {}", what));
+    };
+    let len = u16::from_le_bytes(*len) as usize;
+    let Some(bytes) = after.get(..len) else {
+        return Err(format!("frame ends in its {}", what));
+    };
+    *rest = &after[len..];
+    std::str::from_utf8(bytes).map_err(|_| format!("{} is not UTF-8", what))
+}
+
+impl<'a> Frame<'a> {
+    // Octets the frame takes before stuffing.
+    pub fn encoded_len(&self) -> usize {
+        1 + self.time.map_or(0, |_| 8)
+            + 2
+            + self.key.len()
+            + 2
+            + self.encoding.len()
+            + self.payload.len()
+            + CRC_LEN
+    }
+
+    // Appends the frame. Keys and encodings are at most 65535 bytes, as Zenoh's are in
+    // practice.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let start = out.len();
+        let mut flags = self.priority & PRIORITY;
+        if self.delete {
+            flags |= DELETE;
+        }
+        if self.time.is_some() {
+            flags |= TIMESTAMP;
+        }
+        out.push(flags);
+        if let Some(time) = self.time {
+            out.extend_from_slice(&time.to_le_bytes());
+        }
+        out.extend_from_slice(&(self.key.len() as u16).to_le_bytes());
+        out.extend_from_slice(self.key.as_bytes());
+        out.extend_from_slice(&(self.encoding.len() as u16).to_le_bytes());
+        out.extend_from_slice(self.encoding.as_bytes());
+        out.extend_from_slice(self.payload);
+        let crc = crc32c::checksum(&out[start..]);
+        out.extend_from_slice(&crc.to_le_bytes());
+    }
+
+    // Parses a frame, unstuffed, refusing it if its CRC32C does not match.
+    pub fn decode(frame: &'a [u8]) -> Result<Self, String> {
+        let Some((body, crc)) = frame.split_last_chunk::<CRC_LEN>() else {
+            return Err(format!("frame of {} octets has no CRC32C", frame.len()));
+        };
+        if crc32c::checksum(body) != u32::from_le_bytes(*crc) {
+            return Err("frame fails its CRC32C".to_string());
+        }
+        let Some((&flags, mut rest)) = body.split_first() else {
+            return Err("frame has no flags".to_string());
+        };
+        let time = if flags & TIMESTAMP != 0 {
+            let Some((time, after)) = rest.split_first_chunk::<8>() else {
+                return Err("frame ends in its timestamp".to_string());
+            };
+            rest = after;
+            Some(u64::from_le_bytes(*time))
+        } else {
+            None
+        };
+        let key = take_str(&mut rest, "key")?;
+        let encoding = take_str(&mut rest, "encoding")?;
+        Ok(Frame {
+            priority: flags & PRIORITY,
+            delete: flags & DELETE != 0,
+            time,
+            key,
+            encoding,
+            payload: rest,
+        })
+    }
+}
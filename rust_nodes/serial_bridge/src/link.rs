@@ -0,0 +1,158 @@
+// The serial link to the other bridge: frames from the queues go out, frames from the
+// other side are republished here, and the port is opened again whenever it fails.
+use crate::cobs;
+use crate::frame::Frame;
+use crate::queue::PriorityQueues;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::time::{Instant, sleep, sleep_until};
+use tokio_serial::{FlowControl, SerialPortBuilderExt, SerialStream};
+use tracing::{debug, info, warn};
+use zenoh::Session;
+use zenoh::bytes::Encoding;
+use zenoh::qos::Priority;
+use zenoh::sample::Locality;
+use zenoh::time::{NTP64, Timestamp};
+
+// How long to wait before opening the port again after it failed.
+const RETRY: Duration = Duration::from_secs(1);
+
+pub struct Link {
+    pub device: String,
+    pub baud: u32,
+    // Whether the port waits for CTS before sending, as radio modems with a small buffer
+    // assert it.
+    pub rts_cts: bool,
+    // Bytes per second to pace frames to, for radios whose air rate is below the port's.
+    pub max_rate: Option<u32>,
+    // Longest frame taken from the other side, unstuffed.
+    pub max_frame: usize,
+    pub queues: Arc<PriorityQueues>,
+    // Frames from the other side dropped since the last report, as corrupted or not
+    // publishable.
+    pub rejected: Arc<AtomicU64>,
+}
+
+impl Link {
+    // Runs the link until aborted.
+    pub async fn run(self, session: Session) {
+        loop {
+            let port = self.open().await;
+            let (reader, writer) = tokio::io::split(port);
+            let e = tokio::select! {
+                e = self.receive(reader, &session) => e,
+                e = self.send(writer) => e,
+            };
+            warn!("Lost the link on {}: {}", self.device, e);
+            sleep(RETRY).await;
+        }
+    }
+
+    // Opens the port at the baud rate, 8 data bits, no parity, and 1 stop bit, retrying
+    // until it opens. Only the first failure in a row is reported.
+    async fn open(&self) -> SerialStream {
+        let flow_control = if self.rts_cts {
+            FlowControl::Hardware
+        } else {
+            FlowControl::None
+        };
+        let mut failed = false;
+        loop {
+            match tokio_serial::new(&self.device, self.baud)
+                .flow_control(flow_control)
+                .open_native_async()
+            {
+                Ok(port) => {
+                    info!("Opened link on {} at {} baud", self.device, self.baud);
+                    return port;
+                }
+                Err(e) => {
+                    if !failed {
+                        warn!(
+                            "Cannot open link on {}: {}; retrying every {} s",
+                            self.device,
+                            e,
+                            RETRY.as_secs()
+                        );
+                        failed = true;
+                    }
+                    sleep(RETRY).await;
+                }
+            }
+        }
+    }
+
+    // Writes the queued frames, highest priority first, paced to max_rate if given. A
+    // frame being written when the link fails is lost.
+    async fn send(&self, mut writer: WriteHalf<SerialStream>) -> String {
+        loop {
+            let frame = self.queues.pop().await;
+            let started = Instant::now();
+            if let Err(e) = writer.write_all(&frame).await {
+                return e.to_string();
+            }
+            if let Some(rate) = self.max_rate {
+                let airtime = Duration::from_secs_f64(frame.len() as f64 / rate as f64);
+                sleep_until(started + airtime).await;
+            }
+        }
+    }
+
+    // Republishes the frames from the other side on their keys, returning why the link
+    // failed.
+    async fn receive(&self, mut reader: ReadHalf<SerialStream>, session: &Session) -> String {
+        let mut decoder = cobs::Decoder::new(self.max_frame);
+        let mut buf = [0u8; 1024];
+        loop {
+            let len = match reader.read(&mut buf).await {
+                Ok(0) => return "port closed".to_string(),
+                Ok(len) => len,
+                Err(e) => return e.to_string(),
+            };
+            for &byte in &buf[..len] {
+                let Some(frame) = decoder.push(byte) else {
+                    continue;
+                };
+                let republished = match frame {
+                    Ok(frame) => match Frame::decode(&frame) {
+                        Ok(frame) => republish(session, &frame).await,
+                        Err(e) => Err(e),
+                    },
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = republished {
+                    debug!("Dropping frame from {}: {}", self.device, e);
+                    self.rejected.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+// Publishes the sample the frame carries as it was published on the other side. It goes
+// to other sessions only, so the bridge does not send it straight back.
+async fn republish(session: &Session, frame: &Frame<'_>) -> Result<(), String> {
+    let priority = Priority::try_from(frame.priority).map_err(|e| e.to_string())?;
+    let timestamp = frame
+        .time
+        .map(|time| Timestamp::new(NTP64(time), *session.new_timestamp().get_id()));
+    let result = if frame.delete {
+        session
+            .delete(frame.key)
+            .priority(priority)
+            .timestamp(timestamp)
+            .allowed_destination(Locality::Remote)
+            .await
+    } else {
+        session
+            .put(frame.key, frame.payload.to_vec())
+            .encoding(Encoding::from(frame.encoding))
+            .priority(priority)
+            .timestamp(timestamp)
+            .allowed_destination(Locality::Remote)
+            .await
+    };
+    result.map_err(|e| format!("cannot republish on {}: {}", frame.key, e))
+}
@@ -0,0 +1,197 @@
+mod cobs;
+mod frame;
+mod link;
+mod queue;
+
+use clap::Parser;
+use frame::Frame;
+use link::Link;
+use node_framework::{Context, Event, Node, NodeArgs};
+use queue::PriorityQueues;
+use std::collections::HashSet;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use zenoh::sample::{Sample, SampleKind};
+
+// How often the frames dropped since the last report are reported.
+const REPORT_PERIOD: Duration = Duration::from_secs(10);
+
+// Names of the Zenoh priorities, from 1.
+const PRIORITY_NAMES: [&str; queue::PRIORITIES] = [
+    "real_time",
+    "interactive_high",
+    "interactive_low",
+    "data_high",
+    "data",
+    "data_low",
+    "background",
+];
+
+#[derive(Parser)]
+#[command(
+    about = "Tunnels samples on selected key expressions over a plain serial link, such as a radio modem, to a serial_bridge on the other end"
+)]
+struct Args {
+    /// Serial port of the link, e.g. /dev/ttyUSB0.
+    #[arg(long, value_name = "PATH")]
+    device: String,
+    #[arg(long, default_value_t = 57600)]
+    baud: u32,
+    /// Key expression to send to the other end, which republishes its samples on the same
+    /// keys; repeatable. Without any, the bridge only receives.
+    #[arg(long, value_name = "KEY_EXPR")]
+    forward: Vec<String>,
+    /// Wait for CTS before sending, for radio modems that deassert it while their buffer is
+    /// full.
+    #[arg(long)]
+    rts_cts: bool,
+    /// Bytes per second to send at most, for radios whose air rate is below the serial
+    /// port's; as fast as the port takes them if not given.
+    #[arg(long, value_name = "BYTES_PER_S", value_parser = clap::value_parser!(u32).range(1..))]
+    max_rate: Option<u32>,
+    /// Frames held per priority while the link is slow or down; the oldest of a priority
+    /// is dropped for each one past this.
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u64).range(1..))]
+    queue: u64,
+    /// Longest frame sent or received, in bytes; larger samples are dropped.
+    #[arg(long, default_value_t = 4096)]
+    max_frame: usize,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct SerialBridge {
+    link: Option<Link>,
+    forward: Vec<String>,
+    max_frame: usize,
+    queues: Arc<PriorityQueues>,
+    rejected: Arc<AtomicU64>,
+    task: Option<JoinHandle<()>>,
+    // Keys whose samples are too large to send, reported once each.
+    oversize: HashSet<String>,
+}
+
+impl SerialBridge {
+    fn on_sample(&mut self, sample: &Sample) {
+        let payload = sample.payload().to_bytes();
+        let encoding = sample.encoding().to_string();
+        let frame = Frame {
+            priority: sample.priority() as u8,
+            delete: sample.kind() == SampleKind::Delete,
+            time: sample.timestamp().map(|ts| ts.get_time().as_u64()),
+            key: sample.key_expr().as_str(),
+            encoding: &encoding,
+            payload: &payload,
+        };
+        if frame.encoded_len() > self.max_frame {
+            if self.oversize.insert(frame.key.to_string()) {
+                warn!(
+                    "Dropping samples of {}: {} bytes are over --max-frame {}",
+                    frame.key,
+                    frame.encoded_len(),
+                    self.max_frame
+                );
+            }
+            return;
+        }
+        let mut unstuffed = Vec::with_capacity(frame.encoded_len());
+        frame.encode(&mut unstuffed);
+        let mut stuffed = Vec::with_capacity(unstuffed.len() + unstuffed.len() / 254 + 2);
+        cobs::encode(&unstuffed, &mut stuffed);
+        self.queues.push(frame.priority, stuffed);
+    }
+
+    fn report(&self) {
+        let dropped = self.queues.take_dropped();
+        let total: u64 = dropped.iter().sum();
+        if total > 0 {
+            let by_priority: Vec<String> = dropped
+                .iter()
+                .zip(PRIORITY_NAMES)
+                .filter(|(dropped, _)| **dropped > 0)
+                .map(|(dropped, name)| format!("{} of priority {}", dropped, name))
+                .collect();
+            warn!(
+                "Dropped {} queued frames in the last {} s: {}",
+                total,
+                REPORT_PERIOD.as_secs(),
+                by_priority.join(", ")
+            );
+        }
+        let rejected = self.rejected.swap(0, Ordering::Relaxed);
+        if rejected > 0 {
+            warn!(
+                "Dropped {} frames from the other end in the last {} s",
+                rejected,
+                REPORT_PERIOD.as_secs()
+            );
+        }
+    }
+}
+
+impl Node for SerialBridge {
+    const NAME: &'static str = "serial_bridge";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        for key in &self.forward {
+            ctx.subscribe(key).await?;
+        }
+        let link = self.link.take().expect("init runs once");
+        self.task = Some(tokio::spawn(link.run(ctx.session().clone())));
+        ctx.add_timer(REPORT_PERIOD);
+        if self.forward.is_empty() {
+            info!("Only receiving over the link");
+        } else {
+            info!("Forwarding {} over the link", self.forward.join(", "));
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(&sample),
+            Event::Timer(_) => self.report(),
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.report();
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let queues = Arc::new(PriorityQueues::new(args.queue as usize));
+    let rejected = Arc::new(AtomicU64::new(0));
+    let link = Link {
+        device: args.device,
+        baud: args.baud,
+        rts_cts: args.rts_cts,
+        max_rate: args.max_rate,
+        max_frame: args.max_frame,
+        queues: queues.clone(),
+        rejected: rejected.clone(),
+    };
+    let node = SerialBridge {
+        link: Some(link),
+        forward: args.forward,
+        max_frame: args.max_frame,
+        queues,
+        rejected,
+        task: None,
+        oversize: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
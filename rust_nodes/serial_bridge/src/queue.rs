@@ -0,0 +1,71 @@
+// The frames waiting for the link, one queue per Zenoh priority, so a burst of bulk data
+// does not hold up commands or real-time telemetry behind it.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+// Zenoh priorities, 1 (real time) to 7 (background).
+pub const PRIORITIES: usize = 7;
+
+struct Queues {
+    queues: [VecDeque<Vec<u8>>; PRIORITIES],
+    // Frames dropped since the last report, by priority.
+    dropped: [u64; PRIORITIES],
+}
+
+pub struct PriorityQueues {
+    queues: Mutex<Queues>,
+    // Frames each queue holds before its oldest are dropped.
+    capacity: usize,
+    pushed: Notify,
+}
+
+impl PriorityQueues {
+    pub fn new(capacity: usize) -> Self {
+        PriorityQueues {
+            queues: Mutex::new(Queues {
+                queues: Default::default(),
+                dropped: [0; PRIORITIES],
+            }),
+            capacity,
+            pushed: Notify::new(),
+        }
+    }
+
+    // Queues a frame of the priority, dropping the oldest of that priority when its queue
+    // is full: on a link too slow for everything, the latest samples are the ones wanted.
+    pub fn push(&self, priority: u8, frame: Vec<u8>) {
+        let index = (priority.clamp(1, PRIORITIES as u8) - 1) as usize;
+        let mut queues = self.queues.lock().unwrap();
+        let queue = &mut queues.queues[index];
+        if queue.len() == self.capacity {
+            queue.pop_front();
+            queues.dropped[index] += 1;
+        }
+        queues.queues[index].push_back(frame);
+        drop(queues);
+        self.pushed.notify_one();
+    }
+
+    // Takes the oldest frame of the highest priority queued, waiting for one if none is.
+    pub async fn pop(&self) -> Vec<u8> {
+        loop {
+            let frame = self
+                .queues
+                .lock()
+                .unwrap()
+                .queues
+                .iter_mut()
+                .find_map(|queue| queue.pop_front());
+            if let Some(frame) = frame {
+                return frame;
+            }
+            self.pushed.notified().await;
+        }
+    }
+
+    // Frames dropped since the last call, by priority from 1.
+    pub fn take_dropped(&self) -> [u64; PRIORITIES] {
+        std::mem::take(&mut self.queues.lock().unwrap().dropped)
+    }
+}
@@ -0,0 +1,574 @@
+use crate::error::SimError;
+use crate::noise::{NoiseModel, SensorNoise};
+use common::matching::Matching;
+use common::publication_cache::PublicationCache;
+use common::qos::{PublisherBuilderQosExt, QosConfig};
+use common::sequence::{self, SequencedPublisher};
+use common::{atmosphere, encoding};
+use keyspace::keys;
+use sensors_rs::sensors;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{Instrument, error, info, info_span};
+use zenoh::handlers::FifoChannelHandler;
+use zenoh::key_expr::OwnedKeyExpr;
+use zenoh::query::{Query, Queryable};
+
+// Error models per sensor type, before --noise-scale is applied.
+const IMU_NOISE: NoiseModel = NoiseModel {
+    white: 0.05,
+    turn_on_bias: 0.05,
+    bias_walk: 1e-3,
+};
+const GYRO_NOISE: NoiseModel = NoiseModel {
+    white: 5e-3,
+    turn_on_bias: 0.01,
+    bias_walk: 1e-4,
+};
+const ALTITUDE_NOISE: NoiseModel = NoiseModel {
+    white: 0.5,
+    turn_on_bias: 2.0,
+    bias_walk: 0.05,
+};
+// Barometer pressure in pascals, about 0.1 m per Pa near the ground, and temperature in
+// kelvin. The pressure turn-on bias is what fusion's ground reference takes out.
+const PRESSURE_NOISE: NoiseModel = NoiseModel {
+    white: 5.0,
+    turn_on_bias: 100.0,
+    bias_walk: 0.5,
+};
+const TEMPERATURE_NOISE: NoiseModel = NoiseModel {
+    white: 0.05,
+    turn_on_bias: 0.5,
+    bias_walk: 0.01,
+};
+// The turn-on bias stands in for the hard-iron offset of the airframe.
+const MAGNETOMETER_NOISE: NoiseModel = NoiseModel {
+    white: 0.3,
+    turn_on_bias: 1.0,
+    bias_walk: 0.01,
+};
+
+// GNSS solution errors: horizontal and vertical position in meters, velocity in m/s.
+const GNSS_HORIZONTAL_NOISE: NoiseModel = NoiseModel {
+    white: 0.5,
+    turn_on_bias: 1.5,
+    bias_walk: 0.1,
+};
+const GNSS_VERTICAL_NOISE: NoiseModel = NoiseModel {
+    white: 1.0,
+    turn_on_bias: 3.0,
+    bias_walk: 0.2,
+};
+const GNSS_VELOCITY_NOISE: NoiseModel = NoiseModel {
+    white: 0.1,
+    turn_on_bias: 0.0,
+    bias_walk: 0.0,
+};
+// Dilution of precision and satellites in view the simulated receivers report with a fix.
+const GNSS_HDOP: f32 = 1.2;
+const GNSS_VDOP: f32 = 1.8;
+const GNSS_SATELLITES: u8 = 9;
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+// Where the simulated pad is: WGS84 latitude and longitude in degrees and altitude above
+// mean sea level in meters.
+pub const SITE: [f64; 3] = [32.99, -106.97, 1401.0];
+
+// Earth's magnetic field at the simulated pad, east-north-up in microtesla: about 20 µT
+// horizontal pointing north and 45 µT down, as at northern mid-latitudes. Fusion's default
+// reference field matches.
+pub const MAGNETIC_FIELD: [f64; 3] = [0.0, 20.0, -45.0];
+
+// Ideal values the simulated devices measure at one instant.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SensorInputs {
+    // Body-frame specific force (kinematic acceleration minus gravity) in m/s^2.
+    pub specific_force: [f64; 3],
+    // Body-frame angular rate in rad/s.
+    pub angular_rate: [f64; 3],
+    // Height above the pad in meters.
+    pub altitude: f64,
+    // Body-frame magnetic field in microtesla, MAGNETIC_FIELD seen at the body's attitude.
+    pub magnetic_field: [f64; 3],
+    // Position and velocity east-north-up relative to the pad, in m and m/s.
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+// Called by every sensor task whenever it samples.
+pub type InputSource = Arc<dyn Fn() -> SensorInputs + Send + Sync>;
+
+#[derive(clap::Args)]
+pub struct SensorArgs {
+    /// Number of IMUs, published on devices/imu0 and up.
+    #[arg(long, default_value_t = 3)]
+    pub imus: u32,
+    /// Number of gyroscopes, published on devices/gyro0 and up.
+    #[arg(long, default_value_t = 2)]
+    pub gyros: u32,
+    /// Number of altimeters, published on devices/altitude0 and up.
+    #[arg(long, default_value_t = 4)]
+    pub altimeters: u32,
+    /// Number of magnetometers, published on devices/mag0 and up.
+    #[arg(long, default_value_t = 1)]
+    pub magnetometers: u32,
+    /// Number of barometers, published on devices/baro0 and up.
+    #[arg(long, default_value_t = 1)]
+    pub barometers: u32,
+    /// Number of GNSS receivers, published on devices/gnss0 and up.
+    #[arg(long, default_value_t = 1)]
+    pub gnss: u32,
+    /// IMU sample rate in Hz.
+    #[arg(long, default_value_t = 100.0)]
+    pub imu_rate_hz: f64,
+    /// Gyroscope sample rate in Hz.
+    #[arg(long, default_value_t = 100.0)]
+    pub gyro_rate_hz: f64,
+    /// Altimeter sample rate in Hz.
+    #[arg(long, default_value_t = 100.0)]
+    pub altitude_rate_hz: f64,
+    /// Barometer sample rate in Hz.
+    #[arg(long, default_value_t = 50.0)]
+    pub barometer_rate_hz: f64,
+    /// Magnetometer sample rate in Hz.
+    #[arg(long, default_value_t = 100.0)]
+    pub magnetometer_rate_hz: f64,
+    /// GNSS fix rate in Hz.
+    #[arg(long, default_value_t = 10.0)]
+    pub gnss_rate_hz: f64,
+    /// Seconds a GNSS receiver takes to acquire its first fix.
+    #[arg(long, default_value_t = 2.0)]
+    pub gnss_acquisition_s: f64,
+    /// Specific force above which a GNSS receiver loses its fix, in m/s^2, as receivers
+    /// lose lock under high dynamics. It reacquires once below.
+    #[arg(long, default_value_t = 40.0)]
+    pub gnss_max_acceleration: f64,
+    /// Multiplier applied to every noise model; 0 publishes exact truth.
+    #[arg(long, default_value_t = 1.0)]
+    pub noise_scale: f64,
+    /// Seed of the noise generators. Runs with the same seed draw the same noise.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Publish on <PREFIX>/devices/... instead, e.g. "raw" to feed fault_injector.
+    #[arg(long)]
+    pub key_prefix: Option<String>,
+    /// Answer queries on every device key with its last SAMPLES samples, timestamped and
+    /// sequenced as published, from a publication cache instead of with the latest one.
+    #[arg(long, value_name = "SAMPLES", value_parser = clap::value_parser!(u64).range(1..))]
+    pub publication_cache: Option<u64>,
+    /// Keep publishing on the keys matching KEY_EXPR while no subscriber matches them,
+    /// which otherwise pauses publishing until one does; for topics a recording must have
+    /// from the first sample after a subscriber appears. May be repeated.
+    #[arg(long, value_name = "KEY_EXPR")]
+    pub always_publish: Vec<OwnedKeyExpr>,
+}
+
+// How every device publishes: attributed to node, with CRC32Cs if crc32c is set, and
+// through a publication cache of that many samples if one is set. Unless always is set,
+// it only publishes while a subscriber matches its key.
+#[derive(Clone)]
+struct Publishing {
+    node: String,
+    crc32c: bool,
+    publication_cache: Option<usize>,
+    always: bool,
+}
+
+impl SensorArgs {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, rate) in [
+            ("--imu-rate-hz", self.imu_rate_hz),
+            ("--gyro-rate-hz", self.gyro_rate_hz),
+            ("--altitude-rate-hz", self.altitude_rate_hz),
+            ("--barometer-rate-hz", self.barometer_rate_hz),
+            ("--magnetometer-rate-hz", self.magnetometer_rate_hz),
+            ("--gnss-rate-hz", self.gnss_rate_hz),
+        ] {
+            if rate.is_nan() || rate <= 0.0 {
+                return Err(format!("{} must be positive", name));
+            }
+        }
+        if self.gnss_acquisition_s.is_nan() || self.gnss_acquisition_s < 0.0 {
+            return Err("--gnss-acquisition-s must not be negative".to_string());
+        }
+        if self.gnss_max_acceleration.is_nan() || self.gnss_max_acceleration <= 0.0 {
+            return Err("--gnss-max-acceleration must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    // Whether key is published on with no subscriber matching, by --always-publish.
+    pub fn always_publishes(&self, key: &str) -> bool {
+        let Ok(key) = OwnedKeyExpr::try_from(key) else {
+            return false;
+        };
+        self.always_publish
+            .iter()
+            .any(|key_expr| key_expr.intersects(&key))
+    }
+
+    // Spawns one task per configured device, each sampling the inputs at its own rate and
+    // publishing sequenced samples attributed to node, with their CRC32C if crc32c is set.
+    // Every device draws from its own generator so adding a sensor does not change the
+    // noise of the others.
+    pub fn spawn(
+        &self,
+        session: &zenoh::Session,
+        node: &str,
+        crc32c: bool,
+        inputs: InputSource,
+    ) -> Vec<JoinHandle<()>> {
+        let scale = self.noise_scale;
+        let mut seed = self.seed;
+        let mut next_seed = || {
+            seed = seed.wrapping_add(1);
+            seed
+        };
+        let key = |key: String| match &self.key_prefix {
+            Some(prefix) => format!("{}/{}", prefix, key),
+            None => key,
+        };
+        let mut devices = Vec::new();
+        for i in 0..self.imus {
+            let noise = SensorNoise::new(IMU_NOISE.scaled(scale), next_seed());
+            devices.push((key(keys::imu(i)), self.imu_rate_hz, Device::Imu(noise)));
+        }
+        for i in 0..self.gyros {
+            let noise = SensorNoise::new(GYRO_NOISE.scaled(scale), next_seed());
+            devices.push((key(keys::gyro(i)), self.gyro_rate_hz, Device::Gyro(noise)));
+        }
+        for i in 0..self.altimeters {
+            let noise = SensorNoise::new(ALTITUDE_NOISE.scaled(scale), next_seed());
+            devices.push((
+                key(keys::altitude(i)),
+                self.altitude_rate_hz,
+                Device::Altitude(noise),
+            ));
+        }
+        for i in 0..self.barometers {
+            let barometer = Barometer {
+                pressure: SensorNoise::new(PRESSURE_NOISE.scaled(scale), next_seed()),
+                temperature: SensorNoise::new(TEMPERATURE_NOISE.scaled(scale), next_seed()),
+            };
+            devices.push((
+                key(keys::barometer(i)),
+                self.barometer_rate_hz,
+                Device::Barometer(Box::new(barometer)),
+            ));
+        }
+        for i in 0..self.magnetometers {
+            let noise = SensorNoise::new(MAGNETOMETER_NOISE.scaled(scale), next_seed());
+            devices.push((
+                key(keys::magnetometer(i)),
+                self.magnetometer_rate_hz,
+                Device::Magnetometer(noise),
+            ));
+        }
+        for i in 0..self.gnss {
+            let receiver = Receiver {
+                horizontal: SensorNoise::new(GNSS_HORIZONTAL_NOISE.scaled(scale), next_seed()),
+                vertical: SensorNoise::new(GNSS_VERTICAL_NOISE.scaled(scale), next_seed()),
+                velocity: SensorNoise::new(GNSS_VELOCITY_NOISE.scaled(scale), next_seed()),
+                started: Instant::now(),
+                acquisition: Duration::from_secs_f64(self.gnss_acquisition_s),
+                max_acceleration: self.gnss_max_acceleration,
+            };
+            devices.push((
+                key(keys::gnss(i)),
+                self.gnss_rate_hz,
+                Device::Gnss(Box::new(receiver)),
+            ));
+        }
+
+        let publishing = Publishing {
+            node: node.to_string(),
+            crc32c,
+            publication_cache: self.publication_cache.map(|samples| samples as usize),
+            always: false,
+        };
+        devices
+            .into_iter()
+            .map(|(key, rate_hz, device)| {
+                info!("Simulating {} at {} Hz", key, rate_hz);
+                // Queries are answered from the publication cache, which only holds what
+                // was published.
+                let publishing = Publishing {
+                    always: self.publication_cache.is_some() || self.always_publishes(&key),
+                    ..publishing.clone()
+                };
+                let device = run_device(
+                    session.clone(),
+                    publishing,
+                    key,
+                    rate_hz,
+                    device,
+                    inputs.clone(),
+                );
+                tokio::spawn(async move {
+                    if let Err(e) = device.await {
+                        error!("{}", e);
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+// One simulated device with its own noise state.
+enum Device {
+    Imu(SensorNoise<3>),
+    Gyro(SensorNoise<3>),
+    Altitude(SensorNoise<1>),
+    Barometer(Box<Barometer>),
+    Magnetometer(SensorNoise<3>),
+    Gnss(Box<Receiver>),
+}
+
+// A simulated barometer: the ISA standard atmosphere at the pad's altitude above mean sea
+// level plus the height above it.
+struct Barometer {
+    pressure: SensorNoise<1>,
+    temperature: SensorNoise<1>,
+}
+
+// A simulated GNSS receiver: noisy fixes once it has acquired, none while the vehicle
+// accelerates harder than it can track.
+struct Receiver {
+    horizontal: SensorNoise<2>,
+    vertical: SensorNoise<1>,
+    velocity: SensorNoise<3>,
+    started: Instant,
+    acquisition: Duration,
+    max_acceleration: f64,
+}
+
+impl Receiver {
+    // Fills in args with a fix of the inputs, returning the velocity solution, or leaves
+    // them with no fix.
+    fn fix(
+        &mut self,
+        inputs: &SensorInputs,
+        dt: f64,
+        args: &mut sensors::GnssFixArgs,
+    ) -> Option<sensors::Vec3> {
+        let [east, north, up] = inputs.position;
+        // Errors keep evolving without a fix, so they are drawn regardless.
+        let horizontal = self.horizontal.apply([east, north], dt);
+        let vertical = self.vertical.apply([up], dt);
+        let velocity = self.velocity.apply(inputs.velocity, dt);
+        let force = inputs.specific_force;
+        let acceleration = (force[0].powi(2) + force[1].powi(2) + force[2].powi(2)).sqrt();
+        if self.started.elapsed() < self.acquisition || acceleration > self.max_acceleration {
+            return None;
+        }
+        let [latitude, longitude, altitude] = SITE;
+        args.fix_type = sensors::GnssFixType::Fix3D;
+        args.latitude_deg = latitude + (horizontal[1] as f64 / EARTH_RADIUS).to_degrees();
+        args.longitude_deg = longitude
+            + (horizontal[0] as f64 / (EARTH_RADIUS * latitude.to_radians().cos())).to_degrees();
+        args.altitude = (altitude + vertical[0] as f64) as f32;
+        args.hdop = GNSS_HDOP;
+        args.vdop = GNSS_VDOP;
+        args.satellites = GNSS_SATELLITES;
+        Some(sensors::Vec3::new(velocity[0], velocity[1], velocity[2]))
+    }
+}
+
+impl Device {
+    // FlatBuffers table the device's samples are encoded as.
+    fn table(&self) -> &'static str {
+        match self {
+            Device::Imu(_) => "sensors.IMU",
+            Device::Gyro(_) => "sensors.Gyro",
+            Device::Altitude(_) => "sensors.Altitude",
+            Device::Barometer(_) => "sensors.Barometer",
+            Device::Magnetometer(_) => "sensors.Magnetometer",
+            Device::Gnss(_) => "sensors.GnssFix",
+        }
+    }
+
+    // Samples the device and encodes the reading as a finished FlatBuffer of the matching
+    // sensors table.
+    fn sample(&mut self, inputs: &SensorInputs, dt: f64) -> Vec<u8> {
+        let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(64);
+        match self {
+            Device::Imu(noise) => {
+                let f = noise.apply(inputs.specific_force, dt);
+                let acceleration = sensors::Vec3::new(f[0], f[1], f[2]);
+                let imu = sensors::IMU::create(
+                    &mut builder,
+                    &sensors::IMUArgs {
+                        acceleration: Some(&acceleration),
+                    },
+                );
+                builder.finish(imu, None);
+            }
+            Device::Gyro(noise) => {
+                let w = noise.apply(inputs.angular_rate, dt);
+                let gyro = sensors::Gyro::create(
+                    &mut builder,
+                    &sensors::GyroArgs {
+                        omega_x: w[0],
+                        omega_y: w[1],
+                        omega_z: w[2],
+                    },
+                );
+                builder.finish(gyro, None);
+            }
+            Device::Altitude(noise) => {
+                let h = noise.apply([inputs.altitude], dt);
+                let altitude = sensors::Altitude::create(
+                    &mut builder,
+                    &sensors::AltitudeArgs { altitude: h[0] },
+                );
+                builder.finish(altitude, None);
+            }
+            Device::Barometer(barometer) => {
+                let altitude = SITE[2] + inputs.altitude;
+                let p = barometer
+                    .pressure
+                    .apply([atmosphere::pressure(altitude)], dt);
+                let temperature = atmosphere::temperature(altitude) - 273.15;
+                let t = barometer.temperature.apply([temperature], dt);
+                let barometer = sensors::Barometer::create(
+                    &mut builder,
+                    &sensors::BarometerArgs {
+                        pressure: p[0],
+                        temperature: t[0],
+                    },
+                );
+                builder.finish(barometer, None);
+            }
+            Device::Magnetometer(noise) => {
+                let b = noise.apply(inputs.magnetic_field, dt);
+                let field = sensors::Vec3::new(b[0], b[1], b[2]);
+                let magnetometer = sensors::Magnetometer::create(
+                    &mut builder,
+                    &sensors::MagnetometerArgs {
+                        field: Some(&field),
+                    },
+                );
+                builder.finish(magnetometer, None);
+            }
+            Device::Gnss(receiver) => {
+                let mut args = sensors::GnssFixArgs {
+                    timestamp_us: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|t| t.as_micros() as u64)
+                        .unwrap_or(0),
+                    ..Default::default()
+                };
+                let velocity = receiver.fix(inputs, dt, &mut args);
+                args.velocity = velocity.as_ref();
+                let fix = sensors::GnssFix::create(&mut builder, &args);
+                builder.finish(fix, None);
+            }
+        }
+        builder.finished_data().to_vec()
+    }
+}
+
+// Publishes a device's samples on its key at the given rate and answers queries on the key
+// with the latest sample, or the publication cache's, so fusion can read it in either query
+// or subscribe mode. A liveliness token on the key tells whether the device is running.
+// The device samples on every tick so queries get the latest sample, but only publishes
+// while a subscriber matches, unless it always publishes. Fails only if one of the device's
+// declarations does.
+async fn run_device(
+    session: zenoh::Session,
+    publishing: Publishing,
+    key: String,
+    rate_hz: f64,
+    mut device: Device,
+    inputs: InputSource,
+) -> Result<(), SimError> {
+    let publisher = sequence::cached(
+        session
+            .declare_publisher(key.clone())
+            .encoding(encoding::flatbuffer(device.table()))
+            .qos(&QosConfig::default(), &key),
+    )
+    .await
+    .map_err(SimError::device(&key, "sensor publisher"))?;
+    let matching = if publishing.always {
+        Matching::always()
+    } else {
+        Matching::watch_advanced(&publisher)
+            .await
+            .map_err(SimError::device(&key, "sensor matching listener"))?
+    };
+    let publisher =
+        SequencedPublisher::cached(publisher, &publishing.node).with_crc32c(publishing.crc32c);
+    // Announces the device on its key for as long as it runs, so fusion --discover reads it.
+    let _token = session
+        .liveliness()
+        .declare_token(key.clone())
+        .await
+        .map_err(SimError::device(&key, "sensor liveliness token"))?;
+    // The cache answers queries itself once declared; it is undeclared when dropped with
+    // the task.
+    let (_cache, queryable) = match publishing.publication_cache {
+        Some(history) => {
+            let cache = PublicationCache::declare(&session, &key, history)
+                .await
+                .map_err(SimError::device(&key, "publication cache"))?;
+            (Some(cache), None)
+        }
+        None => {
+            let queryable = session
+                .declare_queryable(key.clone())
+                .await
+                .map_err(SimError::device(&key, "sensor queryable"))?;
+            (None, Some(queryable))
+        }
+    };
+
+    let mut ticker = interval(Duration::from_secs_f64(1.0 / rate_hz));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut latest: Option<Vec<u8>> = None;
+    let mut last_sample = Instant::now();
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let dt = last_sample.elapsed().as_secs_f64();
+                last_sample = Instant::now();
+                let payload = device.sample(&inputs(), dt);
+                // Every sample starts a trace, which the nodes it flows through continue.
+                let published = if matching.matching() {
+                    let span = info_span!("sample", key = %key);
+                    publisher.put(payload.clone()).instrument(span).await
+                } else {
+                    Ok(())
+                };
+                if let Err(e) = published {
+                    error!("Failed to publish on {}: {}", key, e);
+                }
+                latest = Some(payload);
+            }
+            query = next_query(&queryable) => {
+                let Ok(query) = query else { break };
+                let Some(payload) = &latest else { continue };
+                let reply = query
+                    .reply(&key, payload.clone())
+                    .encoding(encoding::flatbuffer(device.table()));
+                if let Err(e) = reply.await {
+                    error!("Failed to reply on {}: {}", key, e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// The next query the device's own queryable received, or never if it has none.
+async fn next_query(
+    queryable: &Option<Queryable<FifoChannelHandler<Query>>>,
+) -> zenoh::Result<Query> {
+    match queryable {
+        Some(queryable) => queryable.recv_async().await,
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,26 @@
+use node_framework::NodeError;
+use thiserror::Error;
+
+// Why a simulator could not start or one of its devices stopped.
+#[derive(Debug, Error)]
+pub enum SimError {
+    // Arguments the simulation cannot be run with.
+    #[error("{0}")]
+    Args(String),
+    #[error("{key}: failed to declare {what}: {source}")]
+    Device {
+        key: String,
+        what: &'static str,
+        source: zenoh::Error,
+    },
+    #[error(transparent)]
+    Node(#[from] NodeError),
+}
+
+impl SimError {
+    // For map_err on the declaration of what for the device on key.
+    pub fn device(key: &str, what: &'static str) -> impl FnOnce(zenoh::Error) -> SimError {
+        let key = key.to_string();
+        move |source| SimError::Device { key, what, source }
+    }
+}
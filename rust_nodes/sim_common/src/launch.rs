@@ -0,0 +1,52 @@
+use keyspace::keys;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+use zenoh::pubsub::Subscriber;
+
+// Time since launch shared by a simulator's tasks; None until launch.
+#[derive(Clone, Default)]
+pub struct FlightClock {
+    launched_at: Arc<Mutex<Option<Instant>>>,
+}
+
+impl FlightClock {
+    // Starts the flight. Later calls are ignored.
+    pub fn launch(&self) {
+        let mut launched_at = self.launched_at.lock().unwrap();
+        if launched_at.is_none() {
+            info!("Launch!");
+            *launched_at = Some(Instant::now());
+        }
+    }
+
+    pub fn elapsed(&self) -> Option<f64> {
+        self.launched_at
+            .lock()
+            .unwrap()
+            .map(|t| t.elapsed().as_secs_f64())
+    }
+
+    // Launches once the delay has passed.
+    pub fn launch_after(&self, delay: Duration) {
+        let clock = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            clock.launch();
+        });
+    }
+
+    // Launches when "s" is published on the launch key, like the Python mock sensors. The
+    // trigger stays armed for as long as the returned subscriber is declared.
+    pub async fn listen(&self, session: &zenoh::Session) -> zenoh::Result<Subscriber<()>> {
+        let clock = self.clone();
+        session
+            .declare_subscriber(keys::launch())
+            .callback(move |sample| {
+                if sample.payload().try_to_string().is_ok_and(|msg| msg == "s") {
+                    clock.launch();
+                }
+            })
+            .await
+    }
+}
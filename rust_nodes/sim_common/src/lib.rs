@@ -0,0 +1,10 @@
+// Building blocks shared by the simulator nodes: sensor error models, simulated sensor
+// publishers, and the launch trigger.
+pub mod devices;
+pub mod error;
+pub mod launch;
+pub mod noise;
+
+pub use devices::{SensorArgs, SensorInputs};
+pub use error::SimError;
+pub use launch::FlightClock;
@@ -0,0 +1,61 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal};
+
+// Error model of one sensor axis, as standard deviations. Each axis gets a constant
+// turn-on bias, a bias that random-walks over time, and white noise on every sample.
+#[derive(Clone, Copy, Debug)]
+pub struct NoiseModel {
+    pub white: f64,
+    pub turn_on_bias: f64,
+    // Random walk density, in units per sqrt(second).
+    pub bias_walk: f64,
+}
+
+impl NoiseModel {
+    pub fn scaled(self, scale: f64) -> Self {
+        NoiseModel {
+            white: self.white * scale,
+            turn_on_bias: self.turn_on_bias * scale,
+            bias_walk: self.bias_walk * scale,
+        }
+    }
+}
+
+// Per-sensor noise state for N axes, driven by its own seeded generator so runs with the
+// same seed produce the same samples.
+pub struct SensorNoise<const N: usize> {
+    model: NoiseModel,
+    bias: [f64; N],
+    rng: StdRng,
+}
+
+impl<const N: usize> SensorNoise<N> {
+    pub fn new(model: NoiseModel, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut bias = [0.0; N];
+        for b in &mut bias {
+            *b = gaussian(&mut rng, model.turn_on_bias);
+        }
+        SensorNoise { model, bias, rng }
+    }
+
+    // Advances the bias random walk by dt seconds and returns the corrupted sample.
+    pub fn apply(&mut self, truth: [f64; N], dt: f64) -> [f32; N] {
+        let walk = self.model.bias_walk * dt.max(0.0).sqrt();
+        let mut out = [0.0; N];
+        for i in 0..N {
+            self.bias[i] += gaussian(&mut self.rng, walk);
+            out[i] = (truth[i] + self.bias[i] + gaussian(&mut self.rng, self.model.white)) as f32;
+        }
+        out
+    }
+}
+
+fn gaussian(rng: &mut StdRng, sigma: f64) -> f64 {
+    match Normal::new(0.0, sigma) {
+        Ok(normal) => normal.sample(rng),
+        // Negative or NaN sigma; treat as a noiseless axis.
+        Err(_) => 0.0,
+    }
+}
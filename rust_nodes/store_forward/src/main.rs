@@ -0,0 +1,281 @@
+use clap::Parser;
+use common::compression;
+use common::qos::PublisherBuilderQosExt;
+use flight_log::{LogReader, LogWriter, Record};
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId, TimerId};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use zenoh::bytes::Encoding;
+use zenoh::key_expr::KeyExpr;
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+use zenoh::sample::{Sample, SampleKind};
+use zenoh::time::{NTP64, Timestamp};
+
+#[derive(Parser)]
+#[command(
+    about = "Stores downlink telemetry on disk while the ground node is out of contact and replays it under downlink/backlog/ once contact resumes"
+)]
+struct Args {
+    /// Node whose liveliness token tells the ground is in contact.
+    #[arg(long, default_value = "ground")]
+    ground: String,
+    /// Key expression to store while out of contact; repeatable. Defaults to downlink/**.
+    #[arg(long = "key")]
+    keys: Vec<String>,
+    /// File the backlog is kept in, as a flight log. A backlog left by an earlier run is
+    /// replayed too.
+    #[arg(long, default_value = "store_forward.flog")]
+    backlog: PathBuf,
+    /// Samples of the backlog replayed per second once contact resumes.
+    #[arg(long, default_value_t = 100.0)]
+    replay_rate_hz: f64,
+    /// Interval between flushes of the backlog to disk in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    flush_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+struct StoreForward {
+    ground: String,
+    keys: Vec<String>,
+    path: PathBuf,
+    replay_period: Duration,
+    flush_period: Duration,
+    writer: Option<LogWriter<BufWriter<File>>>,
+    // Reads the backlog from the first record not replayed yet.
+    reader: Option<LogReader<BufReader<File>>>,
+    // Records stored and not replayed yet.
+    backlog: u64,
+    contact: bool,
+    liveliness: Option<SubscriptionId>,
+    replay_timer: Option<TimerId>,
+    publishers: HashMap<String, Publisher<'static>>,
+}
+
+impl StoreForward {
+    fn writer(&mut self) -> &mut LogWriter<BufWriter<File>> {
+        self.writer.as_mut().expect("opened in init")
+    }
+
+    fn on_contact(&mut self, sample: &Sample) {
+        let contact = sample.kind() == SampleKind::Put;
+        if contact == self.contact {
+            return;
+        }
+        self.contact = contact;
+        if contact {
+            info!("Ground in contact, replaying {} samples", self.backlog);
+        } else {
+            info!("Ground out of contact, storing to {}", self.path.display());
+        }
+    }
+
+    // Stores a sample while the ground is out of contact, stamped with its Zenoh timestamp
+    // or, if its publisher gave it none, the time it arrived.
+    fn on_sample(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        if self.contact {
+            return Ok(());
+        }
+        // What this node replays is not stored again.
+        let backlog = KeyExpr::new(keys::all_backlog()).expect("checked by key!");
+        if backlog.includes(sample.key_expr()) {
+            return Ok(());
+        }
+        let timestamp_ns = sample
+            .timestamp()
+            .map(|timestamp| timestamp.get_time().to_duration().as_nanos() as u64)
+            .unwrap_or_else(now_ns);
+        let record = Record {
+            timestamp_ns,
+            key: sample.key_expr().to_string(),
+            encoding: sample.encoding().to_string(),
+            payload: sample.payload().to_bytes().into_owned(),
+        };
+        self.writer()
+            .write(&record)
+            .map_err(|e| format!("cannot store to {}: {}", self.path.display(), e))?;
+        self.backlog += 1;
+        Ok(())
+    }
+
+    // Replays the oldest stored sample under downlink/backlog/, with the timestamp it was
+    // stored with. Once the whole backlog is replayed, the file is emptied.
+    async fn replay(&mut self, ctx: &Context) -> zenoh::Result<()> {
+        if !self.contact || self.backlog == 0 {
+            return Ok(());
+        }
+        let path = self.path.display().to_string();
+        // The reader only sees records once they are written out.
+        self.writer()
+            .flush()
+            .map_err(|e| format!("cannot flush {}: {}", path, e))?;
+        let reader = self.reader.as_mut().expect("opened in init");
+        let record = reader
+            .read()
+            .map_err(|e| format!("cannot read {}: {}", path, e))?
+            .ok_or_else(|| format!("{} holds fewer samples than were stored", path))?;
+        self.backlog -= 1;
+
+        let key = record
+            .key
+            .strip_prefix(keys::DOWNLINK)
+            .and_then(|key| key.strip_prefix('/'))
+            .unwrap_or(&record.key);
+        let key = keys::backlog(key);
+        if !self.publishers.contains_key(&key) {
+            // Replay never drops samples under congestion: the backlog is all the ground
+            // gets of them.
+            let publisher = ctx
+                .session()
+                .declare_publisher(key.clone())
+                .congestion_control(CongestionControl::Block)
+                .qos(ctx.qos(), &key)
+                .await?;
+            self.publishers.insert(key.clone(), publisher);
+        }
+        let id = *ctx.session().new_timestamp().get_id();
+        let timestamp = Timestamp::new(NTP64::from(Duration::from_nanos(record.timestamp_ns)), id);
+        let publisher = &self.publishers[&key];
+        let mut payload = record.payload;
+        let mut encoding = Encoding::from(record.encoding);
+        let codec = ctx.compression(publisher.key_expr());
+        if let Some((compressed, tagged)) =
+            codec.and_then(|codec| compression::compress(codec, &payload, &encoding))
+        {
+            payload = compressed;
+            encoding = tagged;
+        }
+        publisher
+            .put(payload)
+            .encoding(encoding)
+            .timestamp(timestamp)
+            .await?;
+
+        if self.backlog == 0 {
+            let mut writer = LogWriter::create(&self.path)
+                .map_err(|e| format!("cannot empty {}: {}", path, e))?;
+            // Written out before it is read back from.
+            writer
+                .flush()
+                .map_err(|e| format!("cannot flush {}: {}", path, e))?;
+            self.writer = Some(writer);
+            self.reader = Some(
+                LogReader::open(&self.path).map_err(|e| format!("cannot read {}: {}", path, e))?,
+            );
+            info!("Backlog replayed");
+        }
+        Ok(())
+    }
+}
+
+impl Node for StoreForward {
+    const NAME: &'static str = "store_forward";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let path = self.path.display().to_string();
+        let mut writer =
+            LogWriter::append(&self.path).map_err(|e| format!("cannot open {}: {}", path, e))?;
+        // A log append just created holds its header only once that is written out.
+        writer
+            .flush()
+            .map_err(|e| format!("cannot flush {}: {}", path, e))?;
+        self.writer = Some(writer);
+        // Counted up to the last complete record, where append cut the log.
+        self.backlog = LogReader::open(&self.path)
+            .map_err(|e| format!("cannot read {}: {}", path, e))?
+            .take_while(|record| record.is_ok())
+            .count() as u64;
+        self.reader =
+            Some(LogReader::open(&self.path).map_err(|e| format!("cannot read {}: {}", path, e))?);
+        if self.backlog > 0 {
+            info!("{} samples left in {} to replay", self.backlog, path);
+        }
+
+        self.liveliness = Some(ctx.subscribe_liveliness(&keys::alive(&self.ground)).await?);
+        for key in &self.keys {
+            ctx.subscribe(key).await?;
+        }
+        self.replay_timer = Some(ctx.add_timer(self.replay_period));
+        ctx.add_timer(self.flush_period);
+        Ok(())
+    }
+
+    async fn step(&mut self, ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(id, sample) if Some(id) == self.liveliness => {
+                self.on_contact(&sample);
+                Ok(())
+            }
+            Event::Sample(_, sample) => self.on_sample(&sample),
+            Event::Timer(id) if Some(id) == self.replay_timer => self.replay(ctx).await,
+            Event::Timer(_) => {
+                let path = self.path.display().to_string();
+                self.writer()
+                    .flush()
+                    .map_err(|e| format!("cannot flush {}: {}", path, e).into())
+            }
+            Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(writer) = &mut self.writer {
+            writer
+                .flush()
+                .map_err(|e| format!("cannot flush {}: {}", self.path.display(), e))?;
+        }
+        if self.backlog > 0 {
+            info!("{} samples left in {}", self.backlog, self.path.display());
+        }
+        for (_, publisher) in self.publishers.drain() {
+            publisher.undeclare().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    if !args.replay_rate_hz.is_finite() || args.replay_rate_hz <= 0.0 {
+        error!("--replay-rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    let keys = if args.keys.is_empty() {
+        vec![keys::all_downlink().to_string()]
+    } else {
+        args.keys.clone()
+    };
+
+    let node = StoreForward {
+        ground: args.ground.clone(),
+        keys,
+        path: args.backlog.clone(),
+        replay_period: Duration::from_secs_f64(1.0 / args.replay_rate_hz),
+        flush_period: Duration::from_millis(args.flush_ms),
+        writer: None,
+        reader: None,
+        backlog: 0,
+        contact: false,
+        liveliness: None,
+        replay_timer: None,
+        publishers: HashMap::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,34 @@
+use sensor_decode::MessageType;
+use zenoh::bytes::{Encoding, ZBytes};
+
+// Decodes the payload and formats its values for display.
+pub fn format(kind: MessageType, encoding: &Encoding, payload: &ZBytes) -> Result<String, String> {
+    let v = sensor_decode::values(kind, encoding, payload)?;
+    Ok(match kind {
+        MessageType::Imu => format!(
+            "IMU       accel = ({:8.3}, {:8.3}, {:8.3}) m/s^2",
+            v[0], v[1], v[2]
+        ),
+        MessageType::Gyro => format!(
+            "Gyro      omega = ({:8.4}, {:8.4}, {:8.4}) rad/s",
+            v[0], v[1], v[2]
+        ),
+        MessageType::Altitude => format!("Altitude  {:8.2} m", v[0]),
+        MessageType::Barometer => format!("Baro      {:10.1} Pa {:7.2} C", v[0], v[1]),
+        MessageType::Magnetometer => format!(
+            "Mag       field = ({:8.2}, {:8.2}, {:8.2}) uT",
+            v[0], v[1], v[2]
+        ),
+        MessageType::Gnss => format!(
+            "GNSS      fix {} {:.7},{:.7} alt {:8.2} m vel = ({:7.2}, {:7.2}, {:7.2}) m/s sats {}",
+            v[1], v[2], v[3], v[4], v[5], v[6], v[7], v[10]
+        ),
+        MessageType::Temperature => format!(
+            "Temp      {:8.2} C sensor {}{}",
+            v[0],
+            v[1],
+            if v[2] == 0.0 { " (invalid)" } else { "" }
+        ),
+        MessageType::Power => format!("Power     {:7.2} V {:7.3} A {:7.2} C", v[0], v[1], v[2]),
+    })
+}
@@ -1,28 +1,76 @@
-use zenoh_ext::z_deserialize;
+mod decode;
 
-#[tokio::main]
-async fn main() {
-    let session = zenoh::open(zenoh::Config::default())
-        .await
-        .expect("Failed to open Zenoh session.");
-
-    let subscriber = session
-        .declare_subscriber("devices/temp")
-        .await
-        .expect("Failed to declare subscriber.");
-
-    while let Ok(sample) = subscriber.recv_async().await {
-        let load = sample.payload();
-        println!("Raw payload: {:?}", load);
-
-        let sample: f32 = match z_deserialize(load) {
-            Ok(value) => value,
-            Err(e) => {
-                eprintln!("Deserialization error: {}", e);
-                continue;
-            }
+use clap::Parser;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use sensor_decode::MessageType;
+use std::process::ExitCode;
+use tracing::{info, warn};
+
+#[derive(Parser)]
+#[command(about = "Prints the typed sensor samples received on a key expression")]
+struct Args {
+    /// Key expression to subscribe to.
+    #[arg(long, default_value = keys::all_devices())]
+    key: String,
+    /// Also print the raw payload bytes of every sample.
+    #[arg(long)]
+    raw: bool,
+    /// Recent samples to fetch from every sensor on startup, before the live ones.
+    #[arg(long, default_value_t = 1)]
+    history: usize,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct SubTest {
+    key: String,
+    raw: bool,
+    history: usize,
+}
+
+impl Node for SubTest {
+    const NAME: &'static str = "sub_test";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.subscribe_advanced(&self.key, self.history).await?;
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        let Event::Sample(_, sample) = event else {
+            return Ok(());
         };
+        let key = sample.key_expr().as_str();
+        let payload = sample.payload();
+        if self.raw {
+            info!("{} raw: {:?}", key, payload);
+        }
 
-        println!("Received: {:?}", sample);
+        match MessageType::detect(key, sample.encoding()) {
+            Some(kind) => match decode::format(kind, sample.encoding(), payload) {
+                Ok(text) => info!("{:<20} {}", key, text),
+                Err(e) => warn!("{}: failed to decode {:?}: {}", key, kind, e),
+            },
+            None => info!(
+                "{:<20} {} bytes of {} (no decoder)",
+                key,
+                payload.len(),
+                sample.encoding()
+            ),
+        }
+        Ok(())
     }
 }
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let node = SubTest {
+        key: args.key.clone(),
+        raw: args.raw,
+        history: args.history,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,172 @@
+use crate::config::{NodeConfig, RestartPolicy};
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::Duration;
+use tokio::process::{Child, Command};
+use tokio::time::{Instant, timeout};
+
+// What the supervisor has heard from a running node over Zenoh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Liveness {
+    // Started, but its liveliness token has not come up yet.
+    Starting,
+    Up,
+    // The token was withdrawn while the process kept running, e.g. because it is shutting
+    // down or lost its session.
+    Down,
+}
+
+// One supervised node and its current process, if any.
+pub struct Supervised {
+    pub config: NodeConfig,
+    // Times the node has been started after its first start.
+    pub restarts: u32,
+    process: Option<Child>,
+    // Id of the current or most recent process.
+    pub pid: u32,
+    liveness: Liveness,
+    // When the process was started, its token changed, or its last heartbeat arrived.
+    last_seen: Instant,
+    // When a stopped node is due to be started again; None while it runs or stays down.
+    restart_at: Option<Instant>,
+    started_once: bool,
+}
+
+impl Supervised {
+    pub fn new(config: NodeConfig) -> Self {
+        Supervised {
+            config,
+            restarts: 0,
+            process: None,
+            pid: 0,
+            liveness: Liveness::Starting,
+            last_seen: Instant::now(),
+            restart_at: None,
+            started_once: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn running(&self) -> bool {
+        self.process.is_some()
+    }
+
+    // Starts the node's process in its own process group, so a Ctrl-C meant for the
+    // supervisor does not reach the nodes before the supervisor stops them.
+    pub fn start(&mut self, bin_dir: &Path) -> io::Result<()> {
+        if self.started_once {
+            self.restarts += 1;
+        }
+        self.started_once = true;
+        self.restart_at = None;
+        let child = Command::new(self.program(bin_dir))
+            .args(&self.config.args)
+            .arg("--name")
+            .arg(&self.config.name)
+            .envs(&self.config.env)
+            .process_group(0)
+            .kill_on_drop(true)
+            .spawn()?;
+        self.pid = child.id().unwrap_or(0);
+        self.process = Some(child);
+        self.liveness = Liveness::Starting;
+        self.last_seen = Instant::now();
+        Ok(())
+    }
+
+    fn program(&self, bin_dir: &Path) -> PathBuf {
+        let program = &self.config.program;
+        let local = bin_dir.join(program);
+        if !program.contains('/') && local.is_file() {
+            local
+        } else {
+            PathBuf::from(program)
+        }
+    }
+
+    pub fn token(&mut self, up: bool) {
+        if self.running() {
+            self.liveness = if up { Liveness::Up } else { Liveness::Down };
+            self.last_seen = Instant::now();
+        }
+    }
+
+    pub fn heartbeat(&mut self) {
+        if self.running() {
+            self.last_seen = Instant::now();
+        }
+    }
+
+    // How long a running node has been silent, if that is longer than allowed: the startup
+    // timeout until its token first comes up, the heartbeat timeout afterwards.
+    pub fn silent_for(
+        &self,
+        heartbeat_timeout: Duration,
+        startup_timeout: Duration,
+    ) -> Option<Duration> {
+        let limit = match self.liveness {
+            Liveness::Starting => startup_timeout,
+            Liveness::Up | Liveness::Down => heartbeat_timeout,
+        };
+        let silent = self.last_seen.elapsed();
+        (self.running() && silent > limit).then_some(silent)
+    }
+
+    // Reaps the process if it has exited.
+    pub fn try_exit(&mut self) -> Option<io::Result<ExitStatus>> {
+        let exited = self.process.as_mut()?.try_wait().transpose()?;
+        self.process = None;
+        Some(exited)
+    }
+
+    // Kills a running process outright and reaps it.
+    pub async fn kill(&mut self) -> Option<io::Result<ExitStatus>> {
+        let mut child = self.process.take()?;
+        Some(match child.start_kill() {
+            Ok(()) => child.wait().await,
+            Err(e) => Err(e),
+        })
+    }
+
+    // Asks a running process to shut down with SIGTERM, killing it if it is still there
+    // after the grace period.
+    pub async fn stop(&mut self, grace: Duration) -> Option<io::Result<ExitStatus>> {
+        let child = self.process.as_mut()?;
+        if let Some(pid) = child.id() {
+            let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
+        }
+        match timeout(grace, child.wait()).await {
+            Ok(exited) => {
+                self.process = None;
+                Some(exited)
+            }
+            Err(_) => self.kill().await,
+        }
+    }
+
+    // Schedules the next start after the process ended, if the restart policy allows it.
+    // Unresponsive nodes count as failed.
+    pub fn schedule_restart(&mut self, failed: bool, delay: Duration) {
+        let restart = match self.config.restart {
+            RestartPolicy::Always => true,
+            RestartPolicy::OnFailure => failed,
+            RestartPolicy::Never => false,
+        };
+        self.restart_at = restart.then(|| Instant::now() + delay);
+    }
+
+    // Starts the node again at the next check, whatever its restart policy.
+    pub fn restart_now(&mut self) {
+        self.restart_at = Some(Instant::now());
+    }
+
+    pub fn restart_due(&self) -> bool {
+        self.restart_at.is_some_and(|at| Instant::now() >= at)
+    }
+}
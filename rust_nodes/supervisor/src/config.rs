@@ -0,0 +1,90 @@
+use common::config;
+use common::qos::QosConfig;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+// Nodes run when no config file is given.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+// When a node whose process exited is started again.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    #[default]
+    Always,
+    OnFailure,
+    Never,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct NodeConfig {
+    // Passed to the node with --name, so its liveliness token and heartbeat can be told
+    // apart from those of other nodes.
+    pub name: String,
+    // Executable to run. A bare name is looked up in --bin-dir first, then on PATH.
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    // Extra environment variables of the process.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SupervisorConfig {
+    pub heartbeat_timeout_ms: u64,
+    pub startup_timeout_ms: u64,
+    pub restart_delay_ms: u64,
+    pub nodes: Vec<NodeConfig>,
+    // QoS of the events published, and of the supervisor's own heartbeats.
+    #[serde(default)]
+    pub qos: QosConfig,
+}
+
+impl SupervisorConfig {
+    // Loads a config from a TOML or JSON file, chosen by extension.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let config: SupervisorConfig = config::load_toml(path).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn heartbeat_timeout(&self) -> Duration {
+        Duration::from_millis(self.heartbeat_timeout_ms)
+    }
+
+    pub fn startup_timeout(&self) -> Duration {
+        Duration::from_millis(self.startup_timeout_ms)
+    }
+
+    pub fn restart_delay(&self) -> Duration {
+        Duration::from_millis(self.restart_delay_ms)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.qos.validate()?;
+        if self.heartbeat_timeout_ms == 0 || self.startup_timeout_ms == 0 {
+            return Err("heartbeat_timeout_ms and startup_timeout_ms must be positive".into());
+        }
+        let mut names = HashSet::new();
+        for node in &self.nodes {
+            if node.name.is_empty() || node.name.contains(['/', '*', '$', '#', '?']) {
+                return Err(format!("'{}' is not a valid node name", node.name));
+            }
+            if !names.insert(&node.name) {
+                return Err(format!("node '{}' is configured twice", node.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        toml::from_str(DEFAULT_CONFIG).expect("Built-in supervisor config is invalid.")
+    }
+}
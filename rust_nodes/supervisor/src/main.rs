@@ -0,0 +1,272 @@
+mod child;
+mod config;
+
+use child::Supervised;
+use clap::Parser;
+use command::NackReason;
+use common::encoding;
+use common::qos::PublisherBuilderQosExt;
+use config::SupervisorConfig;
+use keyspace::keys;
+use node_framework::{CommandRequest, Context, Event, Node, NodeArgs, SubscriptionId};
+use sensors_rs::sensors::{self, SupervisorAction};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{ExitCode, ExitStatus};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use zenoh::pubsub::Publisher;
+use zenoh::qos::CongestionControl;
+use zenoh::sample::{Sample, SampleKind};
+
+// How often the nodes' processes and heartbeat ages are checked.
+const CHECK_PERIOD: Duration = Duration::from_millis(100);
+// How long a node gets to exit after SIGTERM when the supervisor shuts down.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+
+#[derive(Parser)]
+#[command(
+    about = "Runs nodes as child processes and restarts them when they exit or stop sending heartbeats"
+)]
+struct Args {
+    /// Nodes to run (TOML or JSON). Defaults to the built-in simulation stack.
+    config: Option<PathBuf>,
+    /// Directory bare program names are looked up in before PATH. Defaults to the
+    /// directory of the supervisor's own executable.
+    #[arg(long)]
+    bin_dir: Option<PathBuf>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+fn describe(exit: &io::Result<ExitStatus>) -> (bool, String) {
+    match exit {
+        Ok(status) => (!status.success(), status.to_string()),
+        Err(e) => (true, format!("cannot wait for process: {}", e)),
+    }
+}
+
+// Prints the event and publishes it on events/supervisor.
+async fn report(
+    publisher: &Option<Publisher<'static>>,
+    node: &Supervised,
+    action: SupervisorAction,
+    detail: &str,
+) {
+    info!("{} [{}]: {:?} ({})", node.name(), node.pid, action, detail);
+    let Some(publisher) = publisher else { return };
+    let timestamp_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0);
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(128);
+    let name = builder.create_string(node.name());
+    let detail = builder.create_string(detail);
+    let event = sensors::SupervisorEvent::create(
+        &mut builder,
+        &sensors::SupervisorEventArgs {
+            timestamp_us,
+            node: Some(name),
+            action,
+            pid: node.pid,
+            restarts: node.restarts,
+            detail: Some(detail),
+        },
+    );
+    builder.finish(event, None);
+    if let Err(e) = publisher.put(builder.finished_data().to_vec()).await {
+        error!("Failed to publish supervisor event: {}", e);
+    }
+}
+
+// Starts the node's process, reporting the start or why it failed.
+async fn start(
+    publisher: &Option<Publisher<'static>>,
+    node: &mut Supervised,
+    bin_dir: &Path,
+    restart_delay: Duration,
+) {
+    match node.start(bin_dir) {
+        Ok(()) => {
+            let action = match node.restarts {
+                0 => SupervisorAction::Started,
+                _ => SupervisorAction::Restarted,
+            };
+            let detail = format!("{} {}", node.config.program, node.config.args.join(" "));
+            report(publisher, node, action, detail.trim_end()).await;
+        }
+        Err(e) => {
+            let detail = format!("cannot start {}: {}", node.config.program, e);
+            report(publisher, node, SupervisorAction::Exited, &detail).await;
+            node.schedule_restart(true, restart_delay);
+        }
+    }
+}
+
+struct Supervisor {
+    config: SupervisorConfig,
+    bin_dir: PathBuf,
+    nodes: Vec<Supervised>,
+    alive: Option<SubscriptionId>,
+    publisher: Option<Publisher<'static>>,
+}
+
+impl Supervisor {
+    fn node_mut(&mut self, name: &str) -> Option<&mut Supervised> {
+        self.nodes.iter_mut().find(|node| node.name() == name)
+    }
+
+    fn on_liveliness(&mut self, sample: &Sample) {
+        let key = sample.key_expr().as_str();
+        let Some(name) = key
+            .strip_prefix(keys::ALIVE)
+            .map(|n| n.trim_start_matches('/'))
+        else {
+            return;
+        };
+        let up = sample.kind() == SampleKind::Put;
+        if let Some(node) = self.node_mut(name) {
+            node.token(up);
+        }
+    }
+
+    fn on_heartbeat(&mut self, sample: &Sample) {
+        let key = sample.key_expr().as_str();
+        let Some(name) = key
+            .strip_prefix(keys::HEARTBEAT)
+            .map(|n| n.trim_start_matches('/'))
+        else {
+            return;
+        };
+        if let Some(node) = self.node_mut(name) {
+            node.heartbeat();
+        }
+    }
+
+    // Handles the restart command: stops the node given by the node argument, if it runs,
+    // and starts it again right away.
+    async fn restart(&mut self, request: CommandRequest) -> zenoh::Result<()> {
+        let name: String = match request.command.parse("node") {
+            Ok(name) => name,
+            Err(nack) => return request.reply(Err(nack)).await,
+        };
+        let publisher = &self.publisher;
+        let Some(node) = self.nodes.iter_mut().find(|node| node.name() == name) else {
+            return request
+                .nack(NackReason::InvalidArgs, format!("no node named {}", name))
+                .await;
+        };
+        if let Some(exit) = node.stop(STOP_GRACE).await {
+            let (_, detail) = describe(&exit);
+            let detail = format!("{}, restart commanded", detail);
+            report(publisher, node, SupervisorAction::Stopped, &detail).await;
+        }
+        node.restart_now();
+        request.ack(format!("restarting {}", name)).await
+    }
+
+    // Reaps exited processes, kills unresponsive ones, and starts the nodes that are due.
+    async fn check(&mut self) {
+        let heartbeat_timeout = self.config.heartbeat_timeout();
+        let startup_timeout = self.config.startup_timeout();
+        let restart_delay = self.config.restart_delay();
+        let publisher = &self.publisher;
+        for node in &mut self.nodes {
+            if let Some(exit) = node.try_exit() {
+                let (failed, detail) = describe(&exit);
+                report(publisher, node, SupervisorAction::Exited, &detail).await;
+                node.schedule_restart(failed, restart_delay);
+            } else if let Some(silent) = node.silent_for(heartbeat_timeout, startup_timeout) {
+                let detail = format!("no heartbeat for {} ms, killed", silent.as_millis());
+                let _ = node.kill().await;
+                report(publisher, node, SupervisorAction::Unresponsive, &detail).await;
+                node.schedule_restart(true, restart_delay);
+            } else if node.restart_due() {
+                start(publisher, node, &self.bin_dir, restart_delay).await;
+            }
+        }
+    }
+}
+
+impl Node for Supervisor {
+    const NAME: &'static str = "supervisor";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        ctx.set_qos(self.config.qos.clone());
+        // Events are rare and each one matters, so none are dropped under congestion.
+        let publisher = ctx
+            .session()
+            .declare_publisher(keys::supervisor_events())
+            .encoding(encoding::flatbuffer("sensors.SupervisorEvent"))
+            .congestion_control(CongestionControl::Block)
+            .qos(ctx.qos(), keys::supervisor_events())
+            .await?;
+        self.publisher = Some(publisher);
+        self.alive = Some(ctx.subscribe_liveliness(keys::all_alive()).await?);
+        ctx.subscribe(keys::all_heartbeats()).await?;
+
+        let restart_delay = self.config.restart_delay();
+        for node in &mut self.nodes {
+            start(&self.publisher, node, &self.bin_dir, restart_delay).await;
+        }
+        ctx.add_timer(CHECK_PERIOD);
+        ctx.register_command("restart").await?;
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Timer(_) => self.check().await,
+            Event::Sample(id, sample) if Some(id) == self.alive => self.on_liveliness(&sample),
+            Event::Sample(_, sample) => self.on_heartbeat(&sample),
+            Event::Command(request) => return self.restart(request).await,
+            Event::Query(..) | Event::Param(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        let publisher = &self.publisher;
+        for node in &mut self.nodes {
+            if let Some(exit) = node.stop(STOP_GRACE).await {
+                let (_, detail) = describe(&exit);
+                report(publisher, node, SupervisorAction::Stopped, &detail).await;
+            }
+        }
+        match self.publisher.take() {
+            Some(publisher) => publisher.undeclare().await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let config = match &args.config {
+        Some(path) => match SupervisorConfig::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid supervisor config: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => SupervisorConfig::default(),
+    };
+    let bin_dir = args.bin_dir.clone().unwrap_or_else(|| {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+    });
+
+    let node = Supervisor {
+        nodes: config.nodes.iter().cloned().map(Supervised::new).collect(),
+        config,
+        bin_dir,
+        alive: None,
+        publisher: None,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,236 @@
+// Samples of the bus as JSON, for the tools that hand telemetry to software that knows
+// neither Zenoh nor FlatBuffers. Fields are named after the schema's, enums are given by
+// their variant names, and fields an estimate flags invalid are left out rather than
+// reported as zeros. JSON has no NaN, so NaN values, such as a FlightEvent's altitude when
+// it was not valid, become null.
+use common::encoding;
+use sensor_decode::MessageType;
+use sensors_rs::sensors;
+use serde_json::{Map, Value, json};
+use zenoh::bytes::Encoding;
+use zenoh::sample::Sample;
+
+// The sample's payload as JSON, or None if it is of no kind this crate converts. Sensor
+// readings become an object with a number per value, named as sensor_decode names them;
+// JSON payloads are passed through and text becomes a string.
+pub fn decode(sample: &Sample) -> Result<Option<Value>, String> {
+    let key = sample.key_expr().as_str();
+    let encoding = sample.encoding();
+    let payload = sample.payload().to_bytes();
+    if let Some(kind) = MessageType::detect(key, encoding) {
+        let values = sensor_decode::values(kind, encoding, sample.payload())?;
+        let object = kind
+            .channels()
+            .iter()
+            .zip(values)
+            .map(|(channel, value)| (channel.to_string(), json!(value)))
+            .collect();
+        return Ok(Some(Value::Object(object)));
+    }
+    if *encoding == Encoding::APPLICATION_JSON {
+        let value =
+            serde_json::from_slice(&payload).map_err(|e| format!("malformed JSON: {}", e))?;
+        return Ok(Some(value));
+    }
+    if *encoding == Encoding::TEXT_PLAIN {
+        return Ok(Some(Value::String(
+            String::from_utf8_lossy(&payload).into_owned(),
+        )));
+    }
+    let Some(table) = encoding::flatbuffer_table(encoding) else {
+        return Ok(None);
+    };
+    let malformed = |e: flatbuffers::InvalidFlatbuffer| format!("malformed {}: {}", table, e);
+    Ok(Some(match table.as_str() {
+        "sensors.FusedState" => {
+            fused_state(flatbuffers::root::<sensors::FusedState>(&payload).map_err(malformed)?)
+        }
+        "sensors.SimTruth" => {
+            let truth = flatbuffers::root::<sensors::SimTruth>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": truth.timestamp_us(),
+                "flight_time_s": truth.flight_time_s(),
+                "position": vec3(truth.position()),
+                "velocity": vec3(truth.velocity()),
+                "acceleration": vec3(truth.acceleration()),
+                "attitude": vec3(truth.attitude()),
+                "angular_rate": vec3(truth.angular_rate()),
+                "mass": truth.mass(),
+                "thrust": truth.thrust(),
+            })
+        }
+        "sensors.PhaseState" => {
+            let state = flatbuffers::root::<sensors::PhaseState>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": state.timestamp_us(),
+                "phase": variant(state.phase().variant_name(), state.phase().0),
+                "previous": variant(state.previous().variant_name(), state.previous().0),
+                "since_s": state.since_s(),
+                "reason": state.reason().unwrap_or_default(),
+                "max_altitude": state.max_altitude(),
+            })
+        }
+        "sensors.VotingStatus" => {
+            let status = flatbuffers::root::<sensors::VotingStatus>(&payload).map_err(malformed)?;
+            let excluded: Vec<&str> = status.excluded().iter().flatten().collect();
+            let disagreeing: Vec<&str> = status.disagreeing().iter().flatten().collect();
+            json!({
+                "timestamp_us": status.timestamp_us(),
+                "excluded": excluded,
+                "disagreeing": disagreeing,
+            })
+        }
+        "sensors.ActiveSensors" => {
+            let active =
+                flatbuffers::root::<sensors::ActiveSensors>(&payload).map_err(malformed)?;
+            let sensors: Vec<Value> = active
+                .sensors()
+                .iter()
+                .flatten()
+                .map(|sensor| {
+                    json!({
+                        "key": sensor.key().unwrap_or_default(),
+                        "kind": sensor.kind().unwrap_or_default(),
+                        "index": sensor.index(),
+                    })
+                })
+                .collect();
+            json!({
+                "timestamp_us": active.timestamp_us(),
+                "sensors": sensors,
+            })
+        }
+        "sensors.ActuatorState" => {
+            let state = flatbuffers::root::<sensors::ActuatorState>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": state.timestamp_us(),
+                "commanded": state.commanded(),
+                "target": state.target(),
+                "position": state.position(),
+                "limited": state.limited(),
+                "stale": state.stale(),
+            })
+        }
+        "sensors.ActuatorCommand" => {
+            let command =
+                flatbuffers::root::<sensors::ActuatorCommand>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": command.timestamp_us(),
+                "position": command.position(),
+            })
+        }
+        "sensors.Heartbeat" => {
+            let heartbeat = flatbuffers::root::<sensors::Heartbeat>(&payload).map_err(malformed)?;
+            json!({
+                "node": heartbeat.node().unwrap_or_default(),
+                "uptime_s": heartbeat.uptime_s(),
+                "cycles": heartbeat.cycles(),
+                "errors": heartbeat.errors(),
+                "mean_cycle_us": heartbeat.mean_cycle_us(),
+                "max_cycle_us": heartbeat.max_cycle_us(),
+            })
+        }
+        "sensors.FlightEvent" => {
+            let event = flatbuffers::root::<sensors::FlightEvent>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": event.timestamp_us(),
+                "detected_us": event.detected_us(),
+                "kind": variant(event.kind().variant_name(), event.kind().0),
+                "confidence": event.confidence(),
+                "altitude": event.altitude(),
+            })
+        }
+        "sensors.Alarm" => {
+            let alarm = flatbuffers::root::<sensors::Alarm>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": alarm.timestamp_us(),
+                "source": alarm.source().unwrap_or_default(),
+                "channel": alarm.channel().unwrap_or_default(),
+                "kind": variant(alarm.kind().variant_name(), alarm.kind().0),
+                "severity": variant(alarm.severity().variant_name(), alarm.severity().0),
+                "active": alarm.active(),
+                "value": alarm.value(),
+                "limit": alarm.limit(),
+            })
+        }
+        "sensors.LogRecord" => {
+            let record = flatbuffers::root::<sensors::LogRecord>(&payload).map_err(malformed)?;
+            let fields: Map<String, Value> = record
+                .fields()
+                .iter()
+                .flatten()
+                .map(|field| {
+                    (
+                        field.name().unwrap_or_default().to_string(),
+                        json!(field.value().unwrap_or_default()),
+                    )
+                })
+                .collect();
+            json!({
+                "timestamp_us": record.timestamp_us(),
+                "node": record.node().unwrap_or_default(),
+                "level": variant(record.level().variant_name(), record.level().0),
+                "target": record.target().unwrap_or_default(),
+                "message": record.message().unwrap_or_default(),
+                "spans": record.spans().unwrap_or_default(),
+                "fields": fields,
+            })
+        }
+        "sensors.CrashReport" => {
+            let crash = flatbuffers::root::<sensors::CrashReport>(&payload).map_err(malformed)?;
+            json!({
+                "timestamp_us": crash.timestamp_us(),
+                "node": crash.node().unwrap_or_default(),
+                "pid": crash.pid(),
+                "message": crash.message().unwrap_or_default(),
+                "location": crash.location().unwrap_or_default(),
+                "thread": crash.thread().unwrap_or_default(),
+                "backtrace": crash.backtrace().unwrap_or_default(),
+            })
+        }
+        _ => return Ok(None),
+    }))
+}
+
+fn fused_state(state: sensors::FusedState) -> Value {
+    let mut object = Map::new();
+    object.insert("timestamp_us".to_string(), json!(state.timestamp_us()));
+    let fields = [
+        ("attitude", state.attitude(), state.attitude_valid()),
+        (
+            "angular_rate",
+            state.angular_rate(),
+            state.angular_rate_valid(),
+        ),
+        ("velocity", state.velocity(), state.velocity_valid()),
+        (
+            "acceleration",
+            state.acceleration(),
+            state.acceleration_valid(),
+        ),
+    ];
+    for (name, v, valid) in fields {
+        if valid && v.is_some() {
+            object.insert(name.to_string(), vec3(v));
+        }
+    }
+    if state.altitude_valid() {
+        object.insert("altitude".to_string(), json!(state.altitude()));
+    }
+    Value::Object(object)
+}
+
+fn vec3(v: Option<&sensors::Vec3>) -> Value {
+    match v {
+        Some(v) => json!({"x": v.x(), "y": v.y(), "z": v.z()}),
+        None => Value::Null,
+    }
+}
+
+// An enum by its variant name, or by its number if this build does not know it.
+fn variant(name: Option<&str>, value: impl std::fmt::Display) -> String {
+    match name {
+        Some(name) => name.to_string(),
+        None => value.to_string(),
+    }
+}
@@ -0,0 +1,307 @@
+use clap::{Parser, ValueEnum};
+use common::shutdown_signal;
+use keyspace::keys;
+use node_framework::{Heartbeat, NodeArgs};
+use std::process::ExitCode;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::{interval, sleep};
+use zenoh::bytes::ZBytes;
+use zenoh::qos::CongestionControl;
+
+// Every sample starts with the run and step number, little-endian u32s, so the subscriber
+// counts each step of each run apart; the rest is zero padding up to the size.
+const HEADER_LEN: usize = 8;
+
+// Samples published back to back between yields when publishing as fast as possible, so
+// Ctrl-C is still noticed.
+const BURST: u64 = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Congestion {
+    // Publishing waits for room when the link backs up.
+    Block,
+    // Samples are dropped when the link backs up.
+    Drop,
+}
+
+impl Congestion {
+    fn control(self) -> CongestionControl {
+        match self {
+            Congestion::Block => CongestionControl::Block,
+            Congestion::Drop => CongestionControl::Drop,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Congestion::Block => "block",
+            Congestion::Drop => "drop",
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(
+    about = "Publishes a sweep of payload sizes to throughput_sub and tabulates the throughput achieved"
+)]
+struct Args {
+    /// Key expression to publish on.
+    #[arg(long, default_value = keys::throughput_data())]
+    key: String,
+    /// Key expression of the subscriber's report queryable.
+    #[arg(long, default_value = keys::throughput_report())]
+    report_key: String,
+    /// Payload sizes to sweep in bytes, including the 8-byte header, comma separated.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_values_t = [8, 64, 512, 4096, 32768, 262144],
+        value_parser = clap::value_parser!(u64).range(HEADER_LEN as u64..)
+    )]
+    sizes: Vec<u64>,
+    /// Congestion control modes to publish every size with, comma separated.
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [Congestion::Block, Congestion::Drop]
+    )]
+    congestion: Vec<Congestion>,
+    /// Samples published per second in every step. Samples are published as fast as
+    /// possible if this is not given.
+    #[arg(long)]
+    rate_hz: Option<f64>,
+    /// Length of every step in seconds.
+    #[arg(long, default_value_t = 5.0)]
+    step_s: f64,
+    /// Milliseconds to wait after a step for samples still in flight before asking the
+    /// subscriber what it received.
+    #[arg(long, default_value_t = 500)]
+    settle_ms: u64,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// What throughput_sub received of a step.
+struct Received {
+    messages: u64,
+    span: Duration,
+}
+
+// One row of the summary table.
+struct StepResult {
+    size: u64,
+    congestion: Congestion,
+    sent: u64,
+    elapsed: Duration,
+    received: Option<Received>,
+}
+
+impl StepResult {
+    fn print(&self) {
+        let sent_rate = self.sent as f64 / self.elapsed.as_secs_f64();
+        let Some(received) = &self.received else {
+            println!(
+                "{:>8}  {:<10} {:>12.0} {:>12} {:>10} {:>8}",
+                self.size,
+                self.congestion.name(),
+                sent_rate,
+                "-",
+                "-",
+                "-"
+            );
+            return;
+        };
+        // Rates of received samples are taken over the time they took to arrive, which
+        // drifts from the publishing time under backpressure.
+        let received_rate = if received.messages > 1 && !received.span.is_zero() {
+            (received.messages - 1) as f64 / received.span.as_secs_f64()
+        } else {
+            0.0
+        };
+        let lost = self.sent.saturating_sub(received.messages) as f64 / self.sent.max(1) as f64;
+        println!(
+            "{:>8}  {:<10} {:>12.0} {:>12.0} {:>10.2} {:>7.2}%",
+            self.size,
+            self.congestion.name(),
+            sent_rate,
+            received_rate,
+            received_rate * self.size as f64 / 1e6,
+            lost * 100.0
+        );
+    }
+}
+
+// Publishes a step's samples for --step-s and returns how many it published and how long
+// that took.
+async fn publish(
+    session: &zenoh::Session,
+    args: &Args,
+    run: u32,
+    step: u32,
+    size: u64,
+    congestion: Congestion,
+) -> zenoh::Result<(u64, Duration)> {
+    let publisher = session
+        .declare_publisher(&args.key)
+        .congestion_control(congestion.control())
+        .await?;
+    let mut payload = vec![0; size as usize];
+    payload[..4].copy_from_slice(&run.to_le_bytes());
+    payload[4..HEADER_LEN].copy_from_slice(&step.to_le_bytes());
+    let payload = ZBytes::from(payload);
+
+    let length = Duration::from_secs_f64(args.step_s);
+    let mut sent: u64 = 0;
+    let started = Instant::now();
+    match args.rate_hz {
+        Some(rate) => {
+            // Missed ticks are fired back to back, which keeps the average rate at periods
+            // finer than the timer's resolution.
+            let mut ticker = interval(Duration::from_secs_f64(1.0 / rate));
+            while started.elapsed() < length {
+                ticker.tick().await;
+                publisher.put(payload.clone()).await?;
+                sent += 1;
+            }
+        }
+        None => {
+            while started.elapsed() < length {
+                publisher.put(payload.clone()).await?;
+                sent += 1;
+                if sent.is_multiple_of(BURST) {
+                    tokio::task::yield_now().await;
+                }
+            }
+        }
+    }
+    let elapsed = started.elapsed();
+    publisher.undeclare().await?;
+    Ok((sent, elapsed))
+}
+
+// Asks the subscriber what it received of a step, if one answers.
+async fn received(session: &zenoh::Session, args: &Args, run: u32, step: u32) -> Option<Received> {
+    let selector = format!("{}?run={};step={}", args.report_key, run, step);
+    let replies = match session.get(selector).await {
+        Ok(replies) => replies,
+        Err(e) => {
+            eprintln!("Failed to query {}: {}", args.report_key, e);
+            return None;
+        }
+    };
+    let reply = replies.recv_async().await.ok()?;
+    let report = reply.result().ok()?.payload().to_bytes();
+    let field = |i: usize| -> Option<u64> {
+        Some(u64::from_le_bytes(
+            report.get(i * 8..i * 8 + 8)?.try_into().ok()?,
+        ))
+    };
+    Some(Received {
+        messages: field(0)?,
+        span: Duration::from_nanos(field(2)?),
+    })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    if args
+        .rate_hz
+        .is_some_and(|rate| !rate.is_finite() || rate <= 0.0)
+    {
+        eprintln!("--rate-hz must be positive");
+        return ExitCode::FAILURE;
+    }
+    if !args.step_s.is_finite() || args.step_s <= 0.0 {
+        eprintln!("--step-s must be positive");
+        return ExitCode::FAILURE;
+    }
+
+    let session = match args.node.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let heartbeat = Heartbeat::start(
+        &session,
+        args.node.name("throughput_pub"),
+        args.node.heartbeat_period(),
+    )
+    .await
+    .expect("Failed to start heartbeat.");
+
+    // Tells this run's steps apart from those of earlier runs the subscriber still counts.
+    let run = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_millis() as u32)
+        .unwrap_or(0);
+    let steps: Vec<_> = args
+        .sizes
+        .iter()
+        .flat_map(|&size| {
+            args.congestion
+                .iter()
+                .map(move |&congestion| (size, congestion))
+        })
+        .collect();
+    let mut status = ExitCode::SUCCESS;
+    let mut results = Vec::with_capacity(steps.len());
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    for (step, &(size, congestion)) in steps.iter().enumerate() {
+        let step = step as u32;
+        println!(
+            "Step {}/{}: {} byte samples, {} congestion control, for {} s",
+            step + 1,
+            steps.len(),
+            size,
+            congestion.name(),
+            args.step_s
+        );
+        let published = tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            published = publish(&session, &args, run, step, size, congestion) => published,
+        };
+        let (sent, elapsed) = match published {
+            Ok(published) => published,
+            Err(e) => {
+                eprintln!("Failed to publish on {}: {}", args.key, e);
+                heartbeat.record(Duration::ZERO, false);
+                status = ExitCode::FAILURE;
+                break;
+            }
+        };
+        heartbeat.record(elapsed, true);
+        sleep(Duration::from_millis(args.settle_ms)).await;
+        results.push(StepResult {
+            size,
+            congestion,
+            sent,
+            elapsed,
+            received: received(&session, &args, run, step).await,
+        });
+    }
+
+    println!(
+        "{:>8}  {:<10} {:>12} {:>12} {:>10} {:>8}",
+        "size", "congestion", "sent/s", "received/s", "MB/s", "lost"
+    );
+    for result in &results {
+        result.print();
+    }
+
+    println!("Shutting down throughput_pub...");
+    if let Err(e) = heartbeat.stop().await {
+        eprintln!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = session.close().await {
+        eprintln!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    status
+}
@@ -0,0 +1,175 @@
+use clap::Parser;
+use common::shutdown_signal;
+use keyspace::keys;
+use node_framework::{Heartbeat, NodeArgs};
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use zenoh::bytes::ZBytes;
+
+// Every sample starts with the publisher's run and step number, little-endian u32s.
+const HEADER_LEN: usize = 8;
+
+#[derive(Parser)]
+#[command(about = "Counts the samples throughput_pub publishes and reports them per step")]
+struct Args {
+    /// Key expression the publisher publishes on.
+    #[arg(long, default_value = keys::throughput_data())]
+    key: String,
+    /// Key expression of the report queryable.
+    #[arg(long, default_value = keys::throughput_report())]
+    report_key: String,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+// The samples received of one step, and when the first and the last of them arrived.
+struct Step {
+    messages: u64,
+    bytes: u64,
+    first: Instant,
+    last: Instant,
+}
+
+// A step's report as the publisher reads it: the samples and bytes received, and the
+// nanoseconds from the first sample to the last, little-endian u64s.
+fn report(messages: u64, bytes: u64, span: Duration) -> Vec<u8> {
+    [messages, bytes, span.as_nanos() as u64]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+// Run and step a sample belongs to, if it has the header. Only the header is read, so
+// large fragmented payloads are not copied.
+fn header(payload: &ZBytes) -> Option<(u32, u32)> {
+    let mut header = [0; HEADER_LEN];
+    payload.reader().read_exact(&mut header).ok()?;
+    Some((
+        u32::from_le_bytes(header[..4].try_into().unwrap()),
+        u32::from_le_bytes(header[4..].try_into().unwrap()),
+    ))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let session = match args.node.zenoh.open().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Samples are only counted in the callback, so the subscriber keeps up with as fast a
+    // publisher as it can.
+    let steps: Arc<Mutex<HashMap<(u32, u32), Step>>> = Arc::default();
+    let counted = steps.clone();
+    let subscriber = session
+        .declare_subscriber(&args.key)
+        .callback(move |sample| {
+            let arrived = Instant::now();
+            let payload = sample.payload();
+            let Some(step) = header(payload) else {
+                return;
+            };
+            let mut steps = counted.lock().unwrap();
+            let step = steps.entry(step).or_insert(Step {
+                messages: 0,
+                bytes: 0,
+                first: arrived,
+                last: arrived,
+            });
+            step.messages += 1;
+            step.bytes += payload.len() as u64;
+            step.last = arrived;
+        })
+        .await
+        .expect("Failed to declare throughput subscriber.");
+    let queryable = session
+        .declare_queryable(&args.report_key)
+        .await
+        .expect("Failed to declare report queryable.");
+    let heartbeat = Heartbeat::start(
+        &session,
+        args.node.name("throughput_sub"),
+        args.node.heartbeat_period(),
+    )
+    .await
+    .expect("Failed to start heartbeat.");
+    println!(
+        "Counting samples on '{}', reporting on '{}'.",
+        args.key, args.report_key
+    );
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        let query = tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            query = queryable.recv_async() => match query {
+                Ok(query) => query,
+                Err(_) => break,
+            },
+        };
+        let started = Instant::now();
+        let parameters = query.parameters();
+        let step = parameters
+            .get("run")
+            .zip(parameters.get("step"))
+            .and_then(|(run, step)| Some((run.parse().ok()?, step.parse().ok()?)));
+        let Some(step) = step else {
+            eprintln!(
+                "Ignoring report query without run and step: {}",
+                query.selector()
+            );
+            heartbeat.record(started.elapsed(), false);
+            continue;
+        };
+        // Each step is reported once, so the counts of finished runs do not pile up.
+        let payload = match steps.lock().unwrap().remove(&step) {
+            Some(received) => {
+                let span = received.last - received.first;
+                println!(
+                    "Run {} step {}: {} samples, {} bytes in {:.3} s",
+                    step.0,
+                    step.1,
+                    received.messages,
+                    received.bytes,
+                    span.as_secs_f64()
+                );
+                report(received.messages, received.bytes, span)
+            }
+            None => report(0, 0, Duration::ZERO),
+        };
+        let replied = query.reply(query.key_expr().clone(), payload).await;
+        if let Err(e) = &replied {
+            eprintln!("Failed to report step: {}", e);
+        }
+        heartbeat.record(started.elapsed(), replied.is_ok());
+    }
+
+    println!("Shutting down throughput_sub...");
+    let mut status = ExitCode::SUCCESS;
+    if let Err(e) = heartbeat.stop().await {
+        eprintln!("Failed to withdraw liveliness token: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = queryable.undeclare().await {
+        eprintln!("Failed to undeclare report queryable: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = subscriber.undeclare().await {
+        eprintln!("Failed to undeclare throughput subscriber: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    if let Err(e) = session.close().await {
+        eprintln!("Failed to close Zenoh session: {}", e);
+        status = ExitCode::FAILURE;
+    }
+    status
+}
@@ -0,0 +1,227 @@
+use clap::Parser;
+use common::attitude;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs, SubscriptionId};
+use rerun::{RecordingStream, RecordingStreamBuilder};
+use sensor_decode::MessageType;
+use sensors_rs::sensors;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use zenoh::sample::{Sample, SampleKind};
+
+// Timeline everything is logged on, from the timestamps the samples carry where they carry
+// one, else from when they arrived.
+const TIMELINE: &str = "time";
+
+#[derive(Parser)]
+#[command(
+    about = "Forwards the fused attitude and altitude, the simulator's truth, and the sensor readings to a Rerun viewer"
+)]
+struct Args {
+    /// gRPC URL of the Rerun viewer to stream to.
+    #[arg(
+        long,
+        value_name = "URL",
+        default_value = "rerun+http://127.0.0.1:9876/proxy"
+    )]
+    viewer: String,
+    /// Start a Rerun viewer from the rerun executable on PATH, unless one is already
+    /// listening, and stream to it.
+    #[arg(long, conflicts_with = "save")]
+    spawn: bool,
+    /// Write the recording to this .rrd file instead, for opening in Rerun later.
+    #[arg(long, value_name = "PATH")]
+    save: Option<PathBuf>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct Viz {
+    recording: RecordingStream,
+    fused: Option<SubscriptionId>,
+    truth: Option<SubscriptionId>,
+}
+
+// When the sample was published, by its Zenoh timestamp, else when it arrived.
+fn sample_time_us(sample: &Sample) -> u64 {
+    sample
+        .timestamp()
+        .map_or_else(SystemTime::now, |t| t.get_time().to_system_time())
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+fn vec3(v: &sensors::Vec3) -> [f32; 3] {
+    [v.x(), v.y(), v.z()]
+}
+
+fn rotation(attitude: &sensors::Vec3) -> rerun::Quaternion {
+    let [x, y, z, w] = attitude::quaternion(
+        attitude.x() as f64,
+        attitude.y() as f64,
+        attitude.z() as f64,
+    );
+    rerun::Quaternion::from_xyzw([x as f32, y as f32, z as f32, w as f32])
+}
+
+impl Viz {
+    fn set_time(&self, timestamp_us: u64) {
+        self.recording
+            .set_timestamp_nanos_since_epoch(TIMELINE, timestamp_us as i64 * 1000);
+    }
+
+    fn scalar(&self, path: &str, value: f64) -> rerun::RecordingStreamResult<()> {
+        self.recording.log(path, &rerun::Scalars::single(value))
+    }
+
+    // The scene: a Z-up world the vehicle's body, and the simulator's truth if one runs, is
+    // drawn in as axes, and the series of the fused state named after its fields.
+    fn log_static(&self) -> rerun::RecordingStreamResult<()> {
+        self.recording
+            .log_static("world", &rerun::ViewCoordinates::RIGHT_HAND_Z_UP())?;
+        self.recording
+            .log_static("world/fused", &rerun::TransformAxes3D::new(1.0))?;
+        self.recording
+            .log_static("world/truth", &rerun::TransformAxes3D::new(0.5))?;
+        Ok(())
+    }
+
+    // The body frame, at the fused altitude above the origin and rotated by the fused
+    // attitude, and the fused state's fields as series.
+    fn on_fused(&self, sample: &Sample) -> zenoh::Result<()> {
+        let payload = sample.payload().to_bytes();
+        let state = flatbuffers::root::<sensors::FusedState>(&payload)
+            .map_err(|e| format!("malformed fused state: {}", e))?;
+        self.set_time(state.timestamp_us());
+        let altitude = state.altitude_valid().then(|| state.altitude());
+        if let Some(altitude) = altitude {
+            self.scalar("altitude/fused", altitude as f64)?;
+        }
+        let attitude = state.attitude().filter(|_| state.attitude_valid());
+        if attitude.is_some() || altitude.is_some() {
+            let translation = [0.0, 0.0, altitude.unwrap_or_default()];
+            let transform = match attitude {
+                Some(attitude) => {
+                    rerun::Transform3D::from_translation_rotation(translation, rotation(attitude))
+                }
+                None => rerun::Transform3D::from_translation(translation),
+            };
+            self.recording.log("world/fused", &transform)?;
+        }
+        let series = [
+            ("attitude", state.attitude(), state.attitude_valid()),
+            (
+                "angular_rate",
+                state.angular_rate(),
+                state.angular_rate_valid(),
+            ),
+            ("velocity", state.velocity(), state.velocity_valid()),
+            (
+                "acceleration",
+                state.acceleration(),
+                state.acceleration_valid(),
+            ),
+        ];
+        for (name, v, valid) in series {
+            if let Some(v) = v.filter(|_| valid) {
+                for (axis, value) in ["x", "y", "z"].iter().zip(vec3(v)) {
+                    self.scalar(&format!("fused/{}/{}", name, axis), value as f64)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // The simulator's truth, to compare the fused state against in SITL runs.
+    fn on_truth(&self, sample: &Sample) -> zenoh::Result<()> {
+        let payload = sample.payload().to_bytes();
+        let truth = flatbuffers::root::<sensors::SimTruth>(&payload)
+            .map_err(|e| format!("malformed truth: {}", e))?;
+        self.set_time(truth.timestamp_us());
+        let (Some(position), Some(attitude)) = (truth.position(), truth.attitude()) else {
+            return Ok(());
+        };
+        self.scalar("altitude/truth", position.z() as f64)?;
+        self.recording.log(
+            "world/truth",
+            &rerun::Transform3D::from_translation_rotation(vec3(position), rotation(attitude)),
+        )?;
+        Ok(())
+    }
+
+    // Every value of a sensor reading as a series, e.g. devices/imu0/acceleration_x.
+    fn on_sensor(&self, sample: &Sample) -> zenoh::Result<()> {
+        let key = sample.key_expr().as_str();
+        let Some(kind) = MessageType::detect(key, sample.encoding()) else {
+            return Ok(());
+        };
+        let values = sensor_decode::values(kind, sample.encoding(), sample.payload())
+            .map_err(|e| format!("malformed sample on {}: {}", key, e))?;
+        self.set_time(sample_time_us(sample));
+        for (channel, value) in kind.channels().iter().zip(values) {
+            if *channel != "timestamp_us" {
+                self.scalar(&format!("{}/{}", key, channel), value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Node for Viz {
+    const NAME: &'static str = "viz";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        self.log_static()?;
+        self.fused = Some(ctx.subscribe(keys::fused_state()).await?);
+        self.truth = Some(ctx.subscribe(keys::sim_truth()).await?);
+        ctx.subscribe(keys::all_devices()).await?;
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) if sample.kind() == SampleKind::Delete => Ok(()),
+            Event::Sample(id, sample) if Some(id) == self.fused => self.on_fused(&sample),
+            Event::Sample(id, sample) if Some(id) == self.truth => self.on_truth(&sample),
+            Event::Sample(_, sample) => self.on_sensor(&sample),
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        self.recording.flush_blocking()?;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    let builder = RecordingStreamBuilder::new("zenoh_ci");
+    let recording = match (&args.save, args.spawn) {
+        (Some(path), _) => builder.save(path),
+        (None, true) => builder.spawn(),
+        (None, false) => builder.connect_grpc_opts(args.viewer.clone()),
+    };
+    let recording = match recording {
+        Ok(recording) => recording,
+        Err(e) => {
+            error!("Cannot start the recording: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match &args.save {
+        Some(path) => info!("Recording to {}", path.display()),
+        None => info!("Streaming to the Rerun viewer"),
+    }
+    let node = Viz {
+        recording,
+        fused: None,
+        truth: None,
+    };
+    node_framework::run(node, &args.node).await
+}
@@ -0,0 +1,86 @@
+// Who may use the gateway. Any page the operator has open in a browser can make requests
+// to it, so a request from a page whose origin is not allowed is refused; browsers send
+// the origin of the page with every WebSocket upgrade and cross-site POST, and tools such
+// as curl send none. Commands are signed with the node's key only for a client holding
+// the gateway's token: as a bearer token on a REST request or the WebSocket upgrade, or in
+// an auth message once connected, since browsers cannot set headers on WebSockets.
+// Without a token, the gateway sends no commands at all.
+use crate::rest;
+use crate::server::Gateway;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::sync::Arc;
+use tracing::warn;
+
+pub struct Auth {
+    token: Option<String>,
+    // Origins as browsers send them, e.g. http://localhost:3000, without a trailing slash.
+    origins: Vec<String>,
+}
+
+impl Auth {
+    pub fn new(token: Option<String>, origins: Vec<String>) -> Self {
+        Auth {
+            token: token.filter(|token| !token.is_empty()),
+            origins: origins
+                .into_iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .collect(),
+        }
+    }
+
+    pub fn commands_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    pub fn token_matches(&self, token: &str) -> bool {
+        self.token
+            .as_ref()
+            .is_some_and(|expected| constant_time_eq(expected.as_bytes(), token.as_bytes()))
+    }
+
+    // Whether the request carries the token as Authorization: Bearer <token>.
+    pub fn bearer(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| self.token_matches(token.trim()))
+    }
+
+    fn origin_allowed(&self, headers: &HeaderMap) -> bool {
+        match headers.get(header::ORIGIN) {
+            None => true,
+            Some(origin) => origin
+                .to_str()
+                .is_ok_and(|origin| self.origins.iter().any(|allowed| allowed == origin)),
+        }
+    }
+}
+
+// Compares without returning at the first difference, so the time it takes tells nothing
+// of how much of a guess was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+// Refuses requests from the pages of origins not allowed, before any route sees them.
+pub async fn check_origin(
+    State(gateway): State<Arc<Gateway>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !gateway.auth().origin_allowed(request.headers()) {
+        let origin = request.headers().get(header::ORIGIN);
+        warn!(
+            "Refusing {} {} from origin {:?}",
+            request.method(),
+            request.uri(),
+            origin
+        );
+        return rest::error(StatusCode::FORBIDDEN, "origin not allowed");
+    }
+    next.run(request).await
+}
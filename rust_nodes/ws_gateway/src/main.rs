@@ -0,0 +1,154 @@
+mod auth;
+mod rest;
+mod server;
+
+use auth::Auth;
+use clap::Parser;
+use command::Key;
+use keyspace::keys;
+use node_framework::{Context, Event, Node, NodeArgs};
+use server::{Gateway, Server};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error};
+use zenoh::sample::{Sample, SampleKind};
+
+#[derive(Parser)]
+#[command(
+    about = "Streams telemetry as JSON to browser clients over WebSocket, answers REST requests for it, and sends the commands they submit"
+)]
+struct Args {
+    /// Key expression to stream. May be repeated.
+    #[arg(long, default_values_t = [
+        keys::all_devices().to_string(),
+        keys::all_state().to_string(),
+        keys::all_alarms().to_string(),
+        keys::flight_events().to_string(),
+    ])]
+    key: Vec<String>,
+    /// Address to serve WebSocket clients on, at /ws, and REST requests.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+    http_addr: SocketAddr,
+    /// How long each attempt of a command waits for the node to be reachable and to
+    /// reply, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    command_timeout_ms: u64,
+    /// How long GET /telemetry waits for the latest samples, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    query_timeout_ms: u64,
+    /// Token a client must give to send commands. Commands are refused without one.
+    #[arg(long, env = "WS_GATEWAY_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    /// Origin of a page allowed to use the gateway from a browser, e.g.
+    /// http://localhost:3000. Requests from pages of other origins are refused. May be
+    /// repeated.
+    #[arg(long = "allow-origin", value_name = "ORIGIN")]
+    origins: Vec<String>,
+    #[command(flatten)]
+    node: NodeArgs,
+}
+
+struct WsGateway {
+    keys: Vec<String>,
+    addr: SocketAddr,
+    command_timeout: Duration,
+    command_key: Option<Key>,
+    query_timeout: Duration,
+    auth: Option<Auth>,
+    gateway: Option<Arc<Gateway>>,
+    server: Option<Server>,
+    // Keys whose samples are not streamed, reported once each.
+    skipped: HashSet<String>,
+}
+
+impl WsGateway {
+    fn on_sample(&mut self, sample: &Sample) -> zenoh::Result<()> {
+        let Some(gateway) = &self.gateway else {
+            return Ok(());
+        };
+        // Decoding is skipped while no client is connected.
+        if sample.kind() == SampleKind::Delete || !gateway.has_clients() {
+            return Ok(());
+        }
+        let key = sample.key_expr();
+        match telemetry_json::decode(sample).map_err(|e| format!("{}: {}", key, e))? {
+            Some(value) => gateway.publish(key, value),
+            None => {
+                if self.skipped.insert(key.to_string()) {
+                    debug!(
+                        "Not streaming {}: no conversion for {}",
+                        key,
+                        sample.encoding()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Node for WsGateway {
+    const NAME: &'static str = "ws_gateway";
+
+    async fn init(&mut self, ctx: &mut Context) -> zenoh::Result<()> {
+        let gateway = Arc::new(Gateway::new(
+            ctx.session(),
+            self.command_timeout,
+            self.command_key.take(),
+            self.query_timeout,
+            self.auth
+                .take()
+                .unwrap_or_else(|| Auth::new(None, Vec::new())),
+        ));
+        self.server = Some(Server::start(self.addr, gateway.clone()).await?);
+        self.gateway = Some(gateway);
+        for key in &self.keys {
+            ctx.subscribe(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn step(&mut self, _ctx: &mut Context, event: Event) -> zenoh::Result<()> {
+        match event {
+            Event::Sample(_, sample) => self.on_sample(&sample),
+            Event::Timer(_) | Event::Query(..) | Event::Command(..) | Event::Param(_) => Ok(()),
+        }
+    }
+
+    async fn shutdown(&mut self, _ctx: &mut Context) -> zenoh::Result<()> {
+        if let Some(server) = self.server.take() {
+            server.stop();
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    node_framework::logging::init(&args.node.log_level);
+    // The gateway signs the commands it sends with the secret it checks the commands it
+    // receives against, the one the other nodes check them against too.
+    let command_key = match args.node.command_key.as_deref().map(Key::load).transpose() {
+        Ok(key) => key,
+        Err(e) => {
+            error!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let node = WsGateway {
+        keys: args.key,
+        addr: args.http_addr,
+        command_timeout: Duration::from_millis(args.command_timeout_ms),
+        command_key,
+        query_timeout: Duration::from_millis(args.query_timeout_ms),
+        auth: Some(Auth::new(args.token, args.origins)),
+        gateway: None,
+        server: None,
+        skipped: HashSet::new(),
+    };
+    node_framework::run(node, &args.node).await
+}
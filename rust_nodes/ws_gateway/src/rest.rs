@@ -0,0 +1,254 @@
+// The gateway's REST endpoints, for scripted checks with curl. Both answer with a JSON
+// object, and with {"message": "..."} and an error status if there is nothing to answer:
+//
+//   GET  /telemetry/<key>        the latest sample on every key matching the key
+//                                expression, as {"<key>": <value>, ...}, from whatever
+//                                publication cache or storage answers for it
+//   POST /cmd/<node>/<command>   sends the command, with the arguments in the JSON object
+//                                of the body if there is one, and answers with its outcome
+//                                as a WebSocket reply has it, and a status telling it apart
+//
+// A command must carry the gateway's token as Authorization: Bearer <token>, and its body
+// be application/json even if it is empty, which a page cannot send another origin
+// without the browser asking first.
+use crate::server::{self, Gateway, command_args, outcome};
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use command::{NackReason, Reply, SendError};
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{debug, warn};
+use zenoh::key_expr::KeyExpr;
+
+pub fn routes() -> Router<Arc<Gateway>> {
+    Router::new()
+        .route("/telemetry/{*key}", get(telemetry))
+        .route("/cmd/{node}/{command}", post(send_command))
+}
+
+fn respond(status: StatusCode, value: Value) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        value.to_string(),
+    )
+        .into_response()
+}
+
+pub fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    respond(status, json!({"message": message.into()}))
+}
+
+async fn telemetry(State(gateway): State<Arc<Gateway>>, Path(key): Path<String>) -> Response {
+    let key = match KeyExpr::autocanonize(key) {
+        Ok(key) => key,
+        Err(e) => return error(StatusCode::BAD_REQUEST, e.to_string()),
+    };
+    let samples = match gateway.latest(&key).await {
+        Ok(samples) => samples,
+        Err(e) => return error(StatusCode::BAD_GATEWAY, e),
+    };
+    let mut values = Map::new();
+    for sample in samples {
+        let key = sample.key_expr();
+        match telemetry_json::decode(&sample) {
+            Ok(Some(value)) => {
+                values.insert(key.to_string(), value);
+            }
+            Ok(None) => debug!(
+                "Not answering for {}: no conversion for {}",
+                key,
+                sample.encoding()
+            ),
+            Err(e) => warn!("Not answering for {}: {}", key, e),
+        }
+    }
+    if values.is_empty() {
+        return error(StatusCode::NOT_FOUND, format!("no sample on {}", key));
+    }
+    respond(StatusCode::OK, Value::Object(values))
+}
+
+async fn send_command(
+    State(gateway): State<Arc<Gateway>>,
+    Path((node, command)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if !gateway.auth().commands_enabled() {
+        return error(StatusCode::FORBIDDEN, server::DISABLED);
+    }
+    if !gateway.auth().bearer(&headers) {
+        let mut response = error(
+            StatusCode::UNAUTHORIZED,
+            "expected the gateway's bearer token",
+        );
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        return response;
+    }
+    if !is_json(&headers) {
+        return error(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "expected Content-Type: application/json",
+        );
+    }
+    let args: BTreeMap<String, Value> = if body.is_empty() {
+        BTreeMap::new()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(args) => args,
+            Err(e) => {
+                return error(
+                    StatusCode::BAD_REQUEST,
+                    format!("expected a JSON object of arguments: {}", e),
+                );
+            }
+        }
+    };
+    let result = gateway.command(&node, &command, &command_args(args)).await;
+    let status = match &result {
+        Ok(Reply { result: Ok(_), .. }) => StatusCode::OK,
+        Ok(Reply {
+            result: Err(nack), ..
+        }) => match nack.reason {
+            NackReason::UnknownCommand => StatusCode::NOT_FOUND,
+            NackReason::Malformed | NackReason::InvalidArgs => StatusCode::BAD_REQUEST,
+            NackReason::Rejected => StatusCode::CONFLICT,
+            NackReason::Unauthorized => StatusCode::FORBIDDEN,
+            NackReason::Failed => StatusCode::BAD_GATEWAY,
+        },
+        Err(SendError::Unreachable(_)) => StatusCode::SERVICE_UNAVAILABLE,
+        Err(SendError::NoReply { .. }) => StatusCode::GATEWAY_TIMEOUT,
+        Err(SendError::Malformed(_) | SendError::Zenoh(_)) => StatusCode::BAD_GATEWAY,
+    };
+    respond(status, outcome(&result))
+}
+
+// Whether the body is declared application/json, with or without parameters.
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media| media.trim().eq_ignore_ascii_case("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::auth::Auth;
+    use crate::server::{Gateway, router};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "s3cret";
+
+    // A gateway on a session of its own, which reaches no other.
+    async fn gateway(token: Option<&str>) -> Arc<Gateway> {
+        let mut config = zenoh::Config::default();
+        for (key, value) in [
+            ("mode", "\"peer\""),
+            ("listen/endpoints", "[]"),
+            ("scouting/multicast/enabled", "false"),
+            ("scouting/gossip/enabled", "false"),
+        ] {
+            config.insert_json5(key, value).expect("Invalid config.");
+        }
+        let session = zenoh::open(config).await.expect("Cannot open a session.");
+        let auth = Auth::new(
+            token.map(str::to_string),
+            vec!["http://localhost:3000/".to_string()],
+        );
+        let timeout = Duration::from_millis(50);
+        Arc::new(Gateway::new(&session, timeout, None, timeout, auth))
+    }
+
+    fn command() -> axum::http::request::Builder {
+        Request::post("/cmd/fusion/calibrate")
+    }
+
+    async fn status(gateway: &Arc<Gateway>, request: axum::http::request::Builder) -> StatusCode {
+        let request = request.body(Body::from("{}")).unwrap();
+        router(gateway.clone())
+            .oneshot(request)
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn command_without_token_is_unauthorized() {
+        let gateway = gateway(Some(TOKEN)).await;
+        let request = command().header(header::CONTENT_TYPE, "application/json");
+        assert_eq!(status(&gateway, request).await, StatusCode::UNAUTHORIZED);
+        let request = command()
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::AUTHORIZATION, "Bearer guess");
+        assert_eq!(status(&gateway, request).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn command_without_json_is_unsupported() {
+        let gateway = gateway(Some(TOKEN)).await;
+        let bearer = format!("Bearer {}", TOKEN);
+        for content_type in [
+            None,
+            Some("text/plain"),
+            Some("application/x-www-form-urlencoded"),
+        ] {
+            let mut request = command().header(header::AUTHORIZATION, &bearer);
+            if let Some(content_type) = content_type {
+                request = request.header(header::CONTENT_TYPE, content_type);
+            }
+            let status = status(&gateway, request).await;
+            assert_eq!(
+                status,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "{:?}",
+                content_type
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn command_from_other_origin_is_forbidden() {
+        let gateway = gateway(Some(TOKEN)).await;
+        let request = command()
+            .header(header::ORIGIN, "https://evil.example")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN));
+        assert_eq!(status(&gateway, request).await, StatusCode::FORBIDDEN);
+        let request = Request::get("/ws").header(header::ORIGIN, "https://evil.example");
+        assert_eq!(status(&gateway, request).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn command_without_configured_token_is_forbidden() {
+        let gateway = gateway(None).await;
+        let request = command().header(header::CONTENT_TYPE, "application/json");
+        assert_eq!(status(&gateway, request).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn authorized_command_is_sent() {
+        let gateway = gateway(Some(TOKEN)).await;
+        // Nothing serves commands on this session, so the command goes out unanswered.
+        let request = command()
+            .header(header::ORIGIN, "http://localhost:3000")
+            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+            .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN));
+        assert_eq!(
+            status(&gateway, request).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
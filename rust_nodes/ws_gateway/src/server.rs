@@ -0,0 +1,339 @@
+// The gateway's server and its WebSocket endpoint, GET /ws; see rest.rs for the others.
+// Every message either way on /ws is a JSON object in a text frame, told apart by its
+// "type":
+//
+//   server: {"type": "sample", "key": "state/fused", "value": {...}}
+//           {"type": "reply", "id": 7, "ok": true, "message": "armed"}
+//           {"type": "lagged", "missed": 120}
+//           {"type": "error", "message": "..."}
+//           {"type": "authorized"}
+//   client: {"type": "subscribe", "keys": ["state/fused", "devices/*"]}
+//           {"type": "auth", "token": "..."}
+//           {"type": "command", "id": 7, "node": "mission_manager", "command": "arm",
+//            "args": {"name": "value"}}
+//
+// A client is streamed the samples on every key the gateway subscribes to until it
+// subscribes to fewer. Commands are answered in any order; the reply carries the id the
+// command was sent with. They are refused until the client gives the gateway's token,
+// unless it did so on the upgrade; see auth.rs.
+use crate::auth::{self, Auth};
+use crate::rest;
+use axum::Router;
+use axum::extract::ws::{Message, Utf8Bytes, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, State};
+use axum::http::HeaderMap;
+use axum::middleware;
+use axum::response::Response;
+use axum::routing::get;
+use command::{Key, Reply, SendError, Senders};
+use common::compression;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use zenoh::Session;
+use zenoh::key_expr::{KeyExpr, OwnedKeyExpr};
+use zenoh::query::ConsolidationMode;
+use zenoh::sample::Sample;
+
+// Samples held for each client before a slow one misses some.
+const BACKLOG: usize = 4096;
+
+// Why a command is refused when the gateway has no token to check clients' against.
+pub const DISABLED: &str = "commands are disabled: the gateway was started without a token";
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Subscribe {
+        keys: Vec<String>,
+    },
+    Auth {
+        token: String,
+    },
+    Command {
+        #[serde(default)]
+        id: Value,
+        node: String,
+        command: String,
+        // Values that are not strings are sent as their JSON text, e.g. 5 as "5".
+        #[serde(default)]
+        args: BTreeMap<String, Value>,
+    },
+}
+
+// A sample as sent to the clients, serialized once for all of them.
+pub struct Update {
+    key: OwnedKeyExpr,
+    text: Utf8Bytes,
+}
+
+// What the clients share: the samples the node receives, and the senders of the commands
+// they submit.
+pub struct Gateway {
+    session: Session,
+    updates: broadcast::Sender<Arc<Update>>,
+    senders: Senders,
+    query_timeout: Duration,
+    auth: Auth,
+}
+
+impl Gateway {
+    pub fn new(
+        session: &Session,
+        command_timeout: Duration,
+        command_key: Option<Key>,
+        query_timeout: Duration,
+        auth: Auth,
+    ) -> Self {
+        Gateway {
+            session: session.clone(),
+            updates: broadcast::channel(BACKLOG).0,
+            senders: Senders::new(session, command_timeout, command_key),
+            query_timeout,
+            auth,
+        }
+    }
+
+    pub fn auth(&self) -> &Auth {
+        &self.auth
+    }
+
+    // Whether any client is connected to be sent samples.
+    pub fn has_clients(&self) -> bool {
+        self.updates.receiver_count() > 0
+    }
+
+    pub fn publish(&self, key: &KeyExpr<'_>, value: Value) {
+        let text = json!({"type": "sample", "key": key.as_str(), "value": value}).to_string();
+        // Sending fails only with no client connected.
+        let _ = self.updates.send(Arc::new(Update {
+            key: key.clone().into_owned().into(),
+            text: text.into(),
+        }));
+    }
+
+    pub async fn command(
+        &self,
+        node: &str,
+        command: &str,
+        args: &[(String, String)],
+    ) -> Result<Reply, SendError> {
+        self.senders.send(node, command, args).await
+    }
+
+    // The latest sample on every key matching the key expression that a publication cache
+    // or storage answers for, decompressed.
+    pub async fn latest(&self, key: &KeyExpr<'_>) -> Result<Vec<Sample>, String> {
+        let replies = self
+            .session
+            .get(key)
+            .consolidation(ConsolidationMode::Latest)
+            .timeout(self.query_timeout)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut samples = Vec::new();
+        while let Ok(reply) = replies.recv_async().await {
+            match reply.into_result() {
+                Ok(sample) => samples.push(compression::decompress(sample)?),
+                Err(e) => return Err(String::from_utf8_lossy(&e.payload().to_bytes()).into_owned()),
+            }
+        }
+        Ok(samples)
+    }
+}
+
+// The outcome of a command as JSON: ok and the node's message, or not ok with the reason
+// the node gave, or without one if the command never got an answer.
+pub fn outcome(result: &Result<Reply, SendError>) -> Value {
+    match result {
+        Ok(Reply {
+            result: Ok(message),
+            ..
+        }) => json!({"ok": true, "message": message}),
+        Ok(Reply {
+            result: Err(nack), ..
+        }) => json!({"ok": false, "reason": format!("{:?}", nack.reason), "message": nack.message}),
+        Err(e) => json!({"ok": false, "message": e.to_string()}),
+    }
+}
+
+// Command arguments as the command subsystem takes them, as text.
+pub fn command_args(args: BTreeMap<String, Value>) -> Vec<(String, String)> {
+    args.into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value,
+                value => value.to_string(),
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+pub struct Server {
+    task: JoinHandle<()>,
+}
+
+impl Server {
+    pub async fn start(addr: SocketAddr, gateway: Arc<Gateway>) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        info!("Serving clients on http://{}", listener.local_addr()?);
+        let app = router(gateway).into_make_service_with_connect_info::<SocketAddr>();
+        let task = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                error!("Gateway server failed: {}", e);
+            }
+        });
+        Ok(Server { task })
+    }
+
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+// The WebSocket and REST routes, behind the origin check.
+pub fn router(gateway: Arc<Gateway>) -> Router {
+    Router::new()
+        .route("/ws", get(upgrade))
+        .merge(rest::routes())
+        .layer(middleware::from_fn_with_state(
+            gateway.clone(),
+            auth::check_origin,
+        ))
+        .with_state(gateway)
+}
+
+async fn upgrade(
+    upgrade: WebSocketUpgrade,
+    State(gateway): State<Arc<Gateway>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    let authorized = gateway.auth().bearer(&headers);
+    upgrade.on_upgrade(move |socket| async move {
+        info!("Client {} connected", peer);
+        Client::new(gateway, peer, authorized).run(socket).await;
+        info!("Client {} disconnected", peer);
+    })
+}
+
+struct Client {
+    gateway: Arc<Gateway>,
+    peer: SocketAddr,
+    // Whether the client gave the token, so its commands are sent.
+    authorized: bool,
+    updates: broadcast::Receiver<Arc<Update>>,
+    // Key expressions the client subscribed to, or None for every key.
+    keys: Option<Vec<OwnedKeyExpr>>,
+    // Command replies, sent from the tasks awaiting them.
+    replies: (mpsc::Sender<String>, mpsc::Receiver<String>),
+}
+
+impl Client {
+    fn new(gateway: Arc<Gateway>, peer: SocketAddr, authorized: bool) -> Self {
+        Client {
+            updates: gateway.updates.subscribe(),
+            gateway,
+            peer,
+            authorized,
+            keys: None,
+            replies: mpsc::channel(16),
+        }
+    }
+
+    async fn run(mut self, mut socket: WebSocket) {
+        loop {
+            let text: Utf8Bytes = tokio::select! {
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Text(text))) => match self.on_request(&text) {
+                        Ok(Some(answer)) => answer.into(),
+                        Ok(None) => continue,
+                        Err(e) => json!({"type": "error", "message": e}).to_string().into(),
+                    },
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => break,
+                },
+                update = self.updates.recv() => match update {
+                    Ok(update) if self.wants(&update.key) => update.text.clone(),
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        json!({"type": "lagged", "missed": missed}).to_string().into()
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                Some(reply) = self.replies.1.recv() => reply.into(),
+            };
+            if socket.send(Message::Text(text)).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    fn wants(&self, key: &OwnedKeyExpr) -> bool {
+        match &self.keys {
+            Some(keys) => keys.iter().any(|k| k.intersects(key)),
+            None => true,
+        }
+    }
+
+    // Handles a request, answering it at once if it is not answered by a task.
+    fn on_request(&mut self, text: &str) -> Result<Option<String>, String> {
+        let request: Request =
+            serde_json::from_str(text).map_err(|e| format!("malformed request: {}", e))?;
+        match request {
+            Request::Subscribe { keys } => {
+                let keys = keys
+                    .into_iter()
+                    .map(|key| OwnedKeyExpr::autocanonize(key).map_err(|e| e.to_string()))
+                    .collect::<Result<_, _>>()?;
+                self.keys = Some(keys);
+            }
+            Request::Auth { token } => {
+                if !self.gateway.auth().commands_enabled() {
+                    return Err(DISABLED.to_string());
+                }
+                if !self.gateway.auth().token_matches(&token) {
+                    warn!("Client {} gave a wrong token", self.peer);
+                    return Err("wrong token".to_string());
+                }
+                self.authorized = true;
+                return Ok(Some(json!({"type": "authorized"}).to_string()));
+            }
+            Request::Command { id, .. } if !self.authorized => {
+                let message = if self.gateway.auth().commands_enabled() {
+                    "not authorized: send {\"type\": \"auth\", \"token\": ...} first"
+                } else {
+                    DISABLED
+                };
+                let reply = json!({"type": "reply", "id": id, "ok": false, "message": message});
+                return Ok(Some(reply.to_string()));
+            }
+            Request::Command {
+                id,
+                node,
+                command,
+                args,
+            } => {
+                let gateway = self.gateway.clone();
+                let replies = self.replies.0.clone();
+                tokio::spawn(async move {
+                    let result = gateway.command(&node, &command, &command_args(args)).await;
+                    let mut reply = outcome(&result);
+                    reply["type"] = json!("reply");
+                    reply["id"] = id;
+                    // Fails only once the client is gone.
+                    let _ = replies.send(reply.to_string()).await;
+                });
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,92 @@
+// Generates the bindings of sensors.fbs into OUT_DIR with flatc, taken from $FLATC or the
+// PATH, so they always match the schema. Machines without flatc can build with the
+// pregenerated feature instead, which uses the bindings checked in under pregenerated/ and
+// warns when the schema has changed since they were generated; building with
+// SENSORS_RS_PREGENERATE=1 on a machine with flatc brings them up to date.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const GENERATED: &str = "sensors_generated.rs";
+// First line of the pregenerated bindings, followed by the hash of the schema they were
+// generated from.
+const HEADER: &str = "// sensors.fbs fnv1a64 ";
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    // Bazel points at the schema in the execroot; Cargo finds it next to the package.
+    let schema = env::var("SENSORS_FBS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| manifest_dir.join("../sensors.fbs"));
+    let pregenerated = manifest_dir.join("pregenerated").join(GENERATED);
+    println!("cargo:rerun-if-changed={}", schema.display());
+    println!("cargo:rerun-if-changed={}", pregenerated.display());
+    println!("cargo:rerun-if-env-changed=FLATC");
+    println!("cargo:rerun-if-env-changed=SENSORS_FBS");
+    println!("cargo:rerun-if-env-changed=SENSORS_RS_PREGENERATE");
+
+    let hash = fnv1a64(&read(&schema));
+    let bindings = if env::var_os("CARGO_FEATURE_PREGENERATED").is_some() {
+        let bindings = String::from_utf8(read(&pregenerated)).unwrap();
+        let generated_from = bindings
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix(HEADER));
+        if generated_from != Some(&format!("{:016x}", hash)) {
+            println!(
+                "cargo:warning=pregenerated sensors_rs bindings are out of date with {}; \
+                 rebuild with SENSORS_RS_PREGENERATE=1 on a machine with flatc",
+                schema.display()
+            );
+        }
+        bindings
+    } else {
+        let bindings = flatc(&schema, &out_dir);
+        if env::var_os("SENSORS_RS_PREGENERATE").is_some() {
+            let contents = format!("{}{:016x}\n{}", HEADER, hash, bindings);
+            fs::create_dir_all(pregenerated.parent().unwrap()).unwrap();
+            fs::write(&pregenerated, contents).unwrap();
+        }
+        bindings
+    };
+    fs::write(out_dir.join(GENERATED), bindings).unwrap();
+}
+
+// Runs flatc on the schema and returns the bindings it generates.
+fn flatc(schema: &Path, out_dir: &Path) -> String {
+    let flatc = env::var_os("FLATC").unwrap_or_else(|| "flatc".into());
+    let dir = out_dir.join("flatc");
+    fs::create_dir_all(&dir).unwrap();
+    let status = Command::new(&flatc)
+        .arg("--rust")
+        .arg("-o")
+        .arg(&dir)
+        .arg(schema)
+        .status()
+        .unwrap_or_else(|e| {
+            panic!(
+                "could not run {}: {}; install flatc, point FLATC at it, or build with \
+                 --features pregenerated",
+                flatc.to_string_lossy(),
+                e
+            )
+        });
+    if !status.success() {
+        panic!("flatc failed on {}: {}", schema.display(), status);
+    }
+    String::from_utf8(read(&dir.join(GENERATED))).unwrap()
+}
+
+fn read(path: &Path) -> Vec<u8> {
+    fs::read(path).unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e))
+}
+
+// A hash that, unlike std's, is the same on every toolchain.
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
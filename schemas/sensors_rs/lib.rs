@@ -0,0 +1,15 @@
+// The code flatc generates from sensors.fbs, as sensors_rs::sensors, and native Rust types
+// for the sensor readings in readings. build.rs generates the former.
+
+// The schema itself, for tools that lay out or describe its tables without the generated
+// code, such as mcs_dictionary.
+pub const SCHEMA: &str = include_str!("../sensors.fbs");
+
+#[allow(warnings, clippy::all)]
+mod sensors_generated {
+    include!(concat!(env!("OUT_DIR"), "/sensors_generated.rs"));
+}
+
+pub mod readings;
+
+pub use sensors_generated::*;
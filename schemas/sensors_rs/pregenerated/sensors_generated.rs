@@ -0,0 +1,8142 @@
+// sensors.fbs fnv1a64 9b1910d786f1ef3f
+// automatically generated by the FlatBuffers compiler, do not modify
+
+
+// @generated
+
+use core::mem;
+use core::cmp::Ordering;
+
+extern crate flatbuffers;
+use self::flatbuffers::{EndianScalar, Follow};
+
+#[allow(unused_imports, dead_code)]
+pub mod sensors {
+
+  use core::mem;
+  use core::cmp::Ordering;
+
+  extern crate flatbuffers;
+  use self::flatbuffers::{EndianScalar, Follow};
+
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_GNSS_FIX_TYPE: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_GNSS_FIX_TYPE: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_GNSS_FIX_TYPE: [GnssFixType; 3] = [
+  GnssFixType::NoFix,
+  GnssFixType::Fix2D,
+  GnssFixType::Fix3D,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct GnssFixType(pub i8);
+#[allow(non_upper_case_globals)]
+impl GnssFixType {
+  pub const NoFix: Self = Self(0);
+  pub const Fix2D: Self = Self(1);
+  pub const Fix3D: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::NoFix,
+    Self::Fix2D,
+    Self::Fix3D,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::NoFix => Some("NoFix"),
+      Self::Fix2D => Some("Fix2D"),
+      Self::Fix3D => Some("Fix3D"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for GnssFixType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for GnssFixType {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for GnssFixType {
+    type Output = GnssFixType;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for GnssFixType {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for GnssFixType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for GnssFixType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_SUPERVISOR_ACTION: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_SUPERVISOR_ACTION: i8 = 4;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_SUPERVISOR_ACTION: [SupervisorAction; 5] = [
+  SupervisorAction::Started,
+  SupervisorAction::Exited,
+  SupervisorAction::Unresponsive,
+  SupervisorAction::Restarted,
+  SupervisorAction::Stopped,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct SupervisorAction(pub i8);
+#[allow(non_upper_case_globals)]
+impl SupervisorAction {
+  pub const Started: Self = Self(0);
+  pub const Exited: Self = Self(1);
+  pub const Unresponsive: Self = Self(2);
+  pub const Restarted: Self = Self(3);
+  pub const Stopped: Self = Self(4);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 4;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Started,
+    Self::Exited,
+    Self::Unresponsive,
+    Self::Restarted,
+    Self::Stopped,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Started => Some("Started"),
+      Self::Exited => Some("Exited"),
+      Self::Unresponsive => Some("Unresponsive"),
+      Self::Restarted => Some("Restarted"),
+      Self::Stopped => Some("Stopped"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for SupervisorAction {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for SupervisorAction {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for SupervisorAction {
+    type Output = SupervisorAction;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for SupervisorAction {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for SupervisorAction {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for SupervisorAction {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_ALARM_SEVERITY: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_ALARM_SEVERITY: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_ALARM_SEVERITY: [AlarmSeverity; 3] = [
+  AlarmSeverity::Info,
+  AlarmSeverity::Warning,
+  AlarmSeverity::Critical,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct AlarmSeverity(pub i8);
+#[allow(non_upper_case_globals)]
+impl AlarmSeverity {
+  pub const Info: Self = Self(0);
+  pub const Warning: Self = Self(1);
+  pub const Critical: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Info,
+    Self::Warning,
+    Self::Critical,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Info => Some("Info"),
+      Self::Warning => Some("Warning"),
+      Self::Critical => Some("Critical"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for AlarmSeverity {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for AlarmSeverity {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for AlarmSeverity {
+    type Output = AlarmSeverity;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for AlarmSeverity {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for AlarmSeverity {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for AlarmSeverity {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_ALARM_KIND: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_ALARM_KIND: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_ALARM_KIND: [AlarmKind; 3] = [
+  AlarmKind::BelowMin,
+  AlarmKind::AboveMax,
+  AlarmKind::RateOfChange,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct AlarmKind(pub i8);
+#[allow(non_upper_case_globals)]
+impl AlarmKind {
+  pub const BelowMin: Self = Self(0);
+  pub const AboveMax: Self = Self(1);
+  pub const RateOfChange: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::BelowMin,
+    Self::AboveMax,
+    Self::RateOfChange,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::BelowMin => Some("BelowMin"),
+      Self::AboveMax => Some("AboveMax"),
+      Self::RateOfChange => Some("RateOfChange"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for AlarmKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for AlarmKind {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for AlarmKind {
+    type Output = AlarmKind;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for AlarmKind {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for AlarmKind {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for AlarmKind {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_FAULT_KIND: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_FAULT_KIND: i8 = 4;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_FAULT_KIND: [FaultKind; 5] = [
+  FaultKind::Bias,
+  FaultKind::Freeze,
+  FaultKind::Dropout,
+  FaultKind::Noise,
+  FaultKind::Spike,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct FaultKind(pub i8);
+#[allow(non_upper_case_globals)]
+impl FaultKind {
+  pub const Bias: Self = Self(0);
+  pub const Freeze: Self = Self(1);
+  pub const Dropout: Self = Self(2);
+  pub const Noise: Self = Self(3);
+  pub const Spike: Self = Self(4);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 4;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Bias,
+    Self::Freeze,
+    Self::Dropout,
+    Self::Noise,
+    Self::Spike,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Bias => Some("Bias"),
+      Self::Freeze => Some("Freeze"),
+      Self::Dropout => Some("Dropout"),
+      Self::Noise => Some("Noise"),
+      Self::Spike => Some("Spike"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for FaultKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for FaultKind {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for FaultKind {
+    type Output = FaultKind;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for FaultKind {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for FaultKind {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for FaultKind {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_SENSOR_HEALTH: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_SENSOR_HEALTH: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_SENSOR_HEALTH: [SensorHealth; 3] = [
+  SensorHealth::Healthy,
+  SensorHealth::Isolated,
+  SensorHealth::Recovering,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct SensorHealth(pub i8);
+#[allow(non_upper_case_globals)]
+impl SensorHealth {
+  pub const Healthy: Self = Self(0);
+  pub const Isolated: Self = Self(1);
+  pub const Recovering: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Healthy,
+    Self::Isolated,
+    Self::Recovering,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Healthy => Some("Healthy"),
+      Self::Isolated => Some("Isolated"),
+      Self::Recovering => Some("Recovering"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for SensorHealth {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for SensorHealth {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for SensorHealth {
+    type Output = SensorHealth;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for SensorHealth {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for SensorHealth {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for SensorHealth {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_COMMAND_STATUS: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_COMMAND_STATUS: i8 = 1;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_COMMAND_STATUS: [CommandStatus; 2] = [
+  CommandStatus::Ack,
+  CommandStatus::Nack,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct CommandStatus(pub i8);
+#[allow(non_upper_case_globals)]
+impl CommandStatus {
+  pub const Ack: Self = Self(0);
+  pub const Nack: Self = Self(1);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 1;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Ack,
+    Self::Nack,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Ack => Some("Ack"),
+      Self::Nack => Some("Nack"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for CommandStatus {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for CommandStatus {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for CommandStatus {
+    type Output = CommandStatus;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for CommandStatus {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for CommandStatus {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for CommandStatus {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_NACK_REASON: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_NACK_REASON: i8 = 6;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_NACK_REASON: [NackReason; 7] = [
+  NackReason::None,
+  NackReason::UnknownCommand,
+  NackReason::Malformed,
+  NackReason::InvalidArgs,
+  NackReason::Rejected,
+  NackReason::Failed,
+  NackReason::Unauthorized,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct NackReason(pub i8);
+#[allow(non_upper_case_globals)]
+impl NackReason {
+  pub const None: Self = Self(0);
+  pub const UnknownCommand: Self = Self(1);
+  pub const Malformed: Self = Self(2);
+  pub const InvalidArgs: Self = Self(3);
+  pub const Rejected: Self = Self(4);
+  pub const Failed: Self = Self(5);
+  pub const Unauthorized: Self = Self(6);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 6;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::None,
+    Self::UnknownCommand,
+    Self::Malformed,
+    Self::InvalidArgs,
+    Self::Rejected,
+    Self::Failed,
+    Self::Unauthorized,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::None => Some("None"),
+      Self::UnknownCommand => Some("UnknownCommand"),
+      Self::Malformed => Some("Malformed"),
+      Self::InvalidArgs => Some("InvalidArgs"),
+      Self::Rejected => Some("Rejected"),
+      Self::Failed => Some("Failed"),
+      Self::Unauthorized => Some("Unauthorized"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for NackReason {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for NackReason {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for NackReason {
+    type Output = NackReason;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for NackReason {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for NackReason {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for NackReason {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_ARMING_TRANSITION: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_ARMING_TRANSITION: i8 = 4;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_ARMING_TRANSITION: [ArmingTransition; 5] = [
+  ArmingTransition::Armed,
+  ArmingTransition::Disarmed,
+  ArmingTransition::Expired,
+  ArmingTransition::Fired,
+  ArmingTransition::Refused,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ArmingTransition(pub i8);
+#[allow(non_upper_case_globals)]
+impl ArmingTransition {
+  pub const Armed: Self = Self(0);
+  pub const Disarmed: Self = Self(1);
+  pub const Expired: Self = Self(2);
+  pub const Fired: Self = Self(3);
+  pub const Refused: Self = Self(4);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 4;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Armed,
+    Self::Disarmed,
+    Self::Expired,
+    Self::Fired,
+    Self::Refused,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Armed => Some("Armed"),
+      Self::Disarmed => Some("Disarmed"),
+      Self::Expired => Some("Expired"),
+      Self::Fired => Some("Fired"),
+      Self::Refused => Some("Refused"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ArmingTransition {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ArmingTransition {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ArmingTransition {
+    type Output = ArmingTransition;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ArmingTransition {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ArmingTransition {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ArmingTransition {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_PARAM_TYPE: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_PARAM_TYPE: i8 = 3;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_PARAM_TYPE: [ParamType; 4] = [
+  ParamType::Float,
+  ParamType::Int,
+  ParamType::Bool,
+  ParamType::Text,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ParamType(pub i8);
+#[allow(non_upper_case_globals)]
+impl ParamType {
+  pub const Float: Self = Self(0);
+  pub const Int: Self = Self(1);
+  pub const Bool: Self = Self(2);
+  pub const Text: Self = Self(3);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 3;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Float,
+    Self::Int,
+    Self::Bool,
+    Self::Text,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Float => Some("Float"),
+      Self::Int => Some("Int"),
+      Self::Bool => Some("Bool"),
+      Self::Text => Some("Text"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ParamType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ParamType {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ParamType {
+    type Output = ParamType;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ParamType {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ParamType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ParamType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_FLIGHT_PHASE: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_FLIGHT_PHASE: i8 = 5;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_FLIGHT_PHASE: [FlightPhase; 6] = [
+  FlightPhase::Pad,
+  FlightPhase::Boost,
+  FlightPhase::Coast,
+  FlightPhase::Apogee,
+  FlightPhase::Descent,
+  FlightPhase::Landed,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct FlightPhase(pub i8);
+#[allow(non_upper_case_globals)]
+impl FlightPhase {
+  pub const Pad: Self = Self(0);
+  pub const Boost: Self = Self(1);
+  pub const Coast: Self = Self(2);
+  pub const Apogee: Self = Self(3);
+  pub const Descent: Self = Self(4);
+  pub const Landed: Self = Self(5);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 5;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Pad,
+    Self::Boost,
+    Self::Coast,
+    Self::Apogee,
+    Self::Descent,
+    Self::Landed,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Pad => Some("Pad"),
+      Self::Boost => Some("Boost"),
+      Self::Coast => Some("Coast"),
+      Self::Apogee => Some("Apogee"),
+      Self::Descent => Some("Descent"),
+      Self::Landed => Some("Landed"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for FlightPhase {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for FlightPhase {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for FlightPhase {
+    type Output = FlightPhase;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for FlightPhase {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for FlightPhase {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for FlightPhase {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_FLIGHT_EVENT_KIND: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_FLIGHT_EVENT_KIND: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_FLIGHT_EVENT_KIND: [FlightEventKind; 3] = [
+  FlightEventKind::Launch,
+  FlightEventKind::Apogee,
+  FlightEventKind::Landing,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct FlightEventKind(pub i8);
+#[allow(non_upper_case_globals)]
+impl FlightEventKind {
+  pub const Launch: Self = Self(0);
+  pub const Apogee: Self = Self(1);
+  pub const Landing: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Launch,
+    Self::Apogee,
+    Self::Landing,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Launch => Some("Launch"),
+      Self::Apogee => Some("Apogee"),
+      Self::Landing => Some("Landing"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for FlightEventKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for FlightEventKind {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for FlightEventKind {
+    type Output = FlightEventKind;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for FlightEventKind {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for FlightEventKind {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for FlightEventKind {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_LOG_LEVEL: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_LOG_LEVEL: i8 = 4;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_LOG_LEVEL: [LogLevel; 5] = [
+  LogLevel::Trace,
+  LogLevel::Debug,
+  LogLevel::Info,
+  LogLevel::Warn,
+  LogLevel::Error,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct LogLevel(pub i8);
+#[allow(non_upper_case_globals)]
+impl LogLevel {
+  pub const Trace: Self = Self(0);
+  pub const Debug: Self = Self(1);
+  pub const Info: Self = Self(2);
+  pub const Warn: Self = Self(3);
+  pub const Error: Self = Self(4);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 4;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Trace,
+    Self::Debug,
+    Self::Info,
+    Self::Warn,
+    Self::Error,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Trace => Some("Trace"),
+      Self::Debug => Some("Debug"),
+      Self::Info => Some("Info"),
+      Self::Warn => Some("Warn"),
+      Self::Error => Some("Error"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for LogLevel {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for LogLevel {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for LogLevel {
+    type Output = LogLevel;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for LogLevel {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for LogLevel {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for LogLevel {}
+// struct Vec3, aligned to 4
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Vec3(pub [u8; 12]);
+impl Default for Vec3 { 
+  fn default() -> Self { 
+    Self([0; 12])
+  }
+}
+impl core::fmt::Debug for Vec3 {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("Vec3")
+      .field("x", &self.x())
+      .field("y", &self.y())
+      .field("z", &self.z())
+      .finish()
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Vec3 {}
+impl<'a> flatbuffers::Follow<'a> for Vec3 {
+  type Inner = &'a Vec3;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    <&'a Vec3>::follow(buf, loc)
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for &'a Vec3 {
+  type Inner = &'a Vec3;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    flatbuffers::follow_cast_ref::<Vec3>(buf, loc)
+  }
+}
+impl<'b> flatbuffers::Push for Vec3 {
+    type Output = Vec3;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        let src = ::core::slice::from_raw_parts(self as *const Vec3 as *const u8, Self::size());
+        dst.copy_from_slice(src);
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for Vec3 {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.in_buffer::<Self>(pos)
+  }
+}
+
+impl<'a> Vec3 {
+  #[allow(clippy::too_many_arguments)]
+  pub fn new(
+    x: f32,
+    y: f32,
+    z: f32,
+  ) -> Self {
+    let mut s = Self([0; 12]);
+    s.set_x(x);
+    s.set_y(y);
+    s.set_z(z);
+    s
+  }
+
+  pub fn x(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[0..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_x(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[0..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn y(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[4..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_y(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[4..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn z(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[8..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_z(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[8..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+}
+
+pub enum IMUOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct IMU<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for IMU<'a> {
+  type Inner = IMU<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> IMU<'a> {
+  pub const VT_ACCELERATION: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    IMU { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args IMUArgs<'args>
+  ) -> flatbuffers::WIPOffset<IMU<'bldr>> {
+    let mut builder = IMUBuilder::new(_fbb);
+    if let Some(x) = args.acceleration { builder.add_acceleration(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn acceleration(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(IMU::VT_ACCELERATION, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for IMU<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<Vec3>("acceleration", Self::VT_ACCELERATION, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct IMUArgs<'a> {
+    pub acceleration: Option<&'a Vec3>,
+}
+impl<'a> Default for IMUArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    IMUArgs {
+      acceleration: None,
+    }
+  }
+}
+
+pub struct IMUBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> IMUBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_acceleration(&mut self, acceleration: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(IMU::VT_ACCELERATION, acceleration);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> IMUBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    IMUBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<IMU<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for IMU<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("IMU");
+      ds.field("acceleration", &self.acceleration());
+      ds.finish()
+  }
+}
+pub enum AltitudeOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Altitude<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Altitude<'a> {
+  type Inner = Altitude<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Altitude<'a> {
+  pub const VT_ALTITUDE: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Altitude { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args AltitudeArgs
+  ) -> flatbuffers::WIPOffset<Altitude<'bldr>> {
+    let mut builder = AltitudeBuilder::new(_fbb);
+    builder.add_altitude(args.altitude);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn altitude(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Altitude::VT_ALTITUDE, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Altitude<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f32>("altitude", Self::VT_ALTITUDE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct AltitudeArgs {
+    pub altitude: f32,
+}
+impl<'a> Default for AltitudeArgs {
+  #[inline]
+  fn default() -> Self {
+    AltitudeArgs {
+      altitude: 0.0,
+    }
+  }
+}
+
+pub struct AltitudeBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> AltitudeBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_altitude(&mut self, altitude: f32) {
+    self.fbb_.push_slot::<f32>(Altitude::VT_ALTITUDE, altitude, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> AltitudeBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    AltitudeBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Altitude<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Altitude<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Altitude");
+      ds.field("altitude", &self.altitude());
+      ds.finish()
+  }
+}
+pub enum TemperatureOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Temperature<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Temperature<'a> {
+  type Inner = Temperature<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Temperature<'a> {
+  pub const VT_VALUE: flatbuffers::VOffsetT = 4;
+  pub const VT_SENSOR_ID: flatbuffers::VOffsetT = 6;
+  pub const VT_VALID: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Temperature { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args TemperatureArgs
+  ) -> flatbuffers::WIPOffset<Temperature<'bldr>> {
+    let mut builder = TemperatureBuilder::new(_fbb);
+    builder.add_sensor_id(args.sensor_id);
+    builder.add_value(args.value);
+    builder.add_valid(args.valid);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn value(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Temperature::VT_VALUE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn sensor_id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Temperature::VT_SENSOR_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn valid(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(Temperature::VT_VALID, Some(true)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Temperature<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f32>("value", Self::VT_VALUE, false)?
+     .visit_field::<u32>("sensor_id", Self::VT_SENSOR_ID, false)?
+     .visit_field::<bool>("valid", Self::VT_VALID, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct TemperatureArgs {
+    pub value: f32,
+    pub sensor_id: u32,
+    pub valid: bool,
+}
+impl<'a> Default for TemperatureArgs {
+  #[inline]
+  fn default() -> Self {
+    TemperatureArgs {
+      value: 0.0,
+      sensor_id: 0,
+      valid: true,
+    }
+  }
+}
+
+pub struct TemperatureBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> TemperatureBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_value(&mut self, value: f32) {
+    self.fbb_.push_slot::<f32>(Temperature::VT_VALUE, value, 0.0);
+  }
+  #[inline]
+  pub fn add_sensor_id(&mut self, sensor_id: u32) {
+    self.fbb_.push_slot::<u32>(Temperature::VT_SENSOR_ID, sensor_id, 0);
+  }
+  #[inline]
+  pub fn add_valid(&mut self, valid: bool) {
+    self.fbb_.push_slot::<bool>(Temperature::VT_VALID, valid, true);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TemperatureBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    TemperatureBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Temperature<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Temperature<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Temperature");
+      ds.field("value", &self.value());
+      ds.field("sensor_id", &self.sensor_id());
+      ds.field("valid", &self.valid());
+      ds.finish()
+  }
+}
+pub enum BarometerOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Barometer<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Barometer<'a> {
+  type Inner = Barometer<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Barometer<'a> {
+  pub const VT_PRESSURE: flatbuffers::VOffsetT = 4;
+  pub const VT_TEMPERATURE: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Barometer { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args BarometerArgs
+  ) -> flatbuffers::WIPOffset<Barometer<'bldr>> {
+    let mut builder = BarometerBuilder::new(_fbb);
+    builder.add_temperature(args.temperature);
+    builder.add_pressure(args.pressure);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn pressure(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Barometer::VT_PRESSURE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn temperature(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Barometer::VT_TEMPERATURE, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Barometer<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f32>("pressure", Self::VT_PRESSURE, false)?
+     .visit_field::<f32>("temperature", Self::VT_TEMPERATURE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct BarometerArgs {
+    pub pressure: f32,
+    pub temperature: f32,
+}
+impl<'a> Default for BarometerArgs {
+  #[inline]
+  fn default() -> Self {
+    BarometerArgs {
+      pressure: 0.0,
+      temperature: 0.0,
+    }
+  }
+}
+
+pub struct BarometerBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> BarometerBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_pressure(&mut self, pressure: f32) {
+    self.fbb_.push_slot::<f32>(Barometer::VT_PRESSURE, pressure, 0.0);
+  }
+  #[inline]
+  pub fn add_temperature(&mut self, temperature: f32) {
+    self.fbb_.push_slot::<f32>(Barometer::VT_TEMPERATURE, temperature, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> BarometerBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    BarometerBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Barometer<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Barometer<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Barometer");
+      ds.field("pressure", &self.pressure());
+      ds.field("temperature", &self.temperature());
+      ds.finish()
+  }
+}
+pub enum GyroOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Gyro<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Gyro<'a> {
+  type Inner = Gyro<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Gyro<'a> {
+  pub const VT_OMEGA_X: flatbuffers::VOffsetT = 4;
+  pub const VT_OMEGA_Y: flatbuffers::VOffsetT = 6;
+  pub const VT_OMEGA_Z: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Gyro { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args GyroArgs
+  ) -> flatbuffers::WIPOffset<Gyro<'bldr>> {
+    let mut builder = GyroBuilder::new(_fbb);
+    builder.add_omega_z(args.omega_z);
+    builder.add_omega_y(args.omega_y);
+    builder.add_omega_x(args.omega_x);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn omega_x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Gyro::VT_OMEGA_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn omega_y(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Gyro::VT_OMEGA_Y, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn omega_z(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Gyro::VT_OMEGA_Z, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Gyro<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f32>("omega_x", Self::VT_OMEGA_X, false)?
+     .visit_field::<f32>("omega_y", Self::VT_OMEGA_Y, false)?
+     .visit_field::<f32>("omega_z", Self::VT_OMEGA_Z, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct GyroArgs {
+    pub omega_x: f32,
+    pub omega_y: f32,
+    pub omega_z: f32,
+}
+impl<'a> Default for GyroArgs {
+  #[inline]
+  fn default() -> Self {
+    GyroArgs {
+      omega_x: 0.0,
+      omega_y: 0.0,
+      omega_z: 0.0,
+    }
+  }
+}
+
+pub struct GyroBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> GyroBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_omega_x(&mut self, omega_x: f32) {
+    self.fbb_.push_slot::<f32>(Gyro::VT_OMEGA_X, omega_x, 0.0);
+  }
+  #[inline]
+  pub fn add_omega_y(&mut self, omega_y: f32) {
+    self.fbb_.push_slot::<f32>(Gyro::VT_OMEGA_Y, omega_y, 0.0);
+  }
+  #[inline]
+  pub fn add_omega_z(&mut self, omega_z: f32) {
+    self.fbb_.push_slot::<f32>(Gyro::VT_OMEGA_Z, omega_z, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> GyroBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    GyroBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Gyro<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Gyro<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Gyro");
+      ds.field("omega_x", &self.omega_x());
+      ds.field("omega_y", &self.omega_y());
+      ds.field("omega_z", &self.omega_z());
+      ds.finish()
+  }
+}
+pub enum MagnetometerOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Magnetometer<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Magnetometer<'a> {
+  type Inner = Magnetometer<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Magnetometer<'a> {
+  pub const VT_FIELD: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Magnetometer { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args MagnetometerArgs<'args>
+  ) -> flatbuffers::WIPOffset<Magnetometer<'bldr>> {
+    let mut builder = MagnetometerBuilder::new(_fbb);
+    if let Some(x) = args.field { builder.add_field(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn field(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(Magnetometer::VT_FIELD, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Magnetometer<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<Vec3>("field", Self::VT_FIELD, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct MagnetometerArgs<'a> {
+    pub field: Option<&'a Vec3>,
+}
+impl<'a> Default for MagnetometerArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    MagnetometerArgs {
+      field: None,
+    }
+  }
+}
+
+pub struct MagnetometerBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> MagnetometerBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_field(&mut self, field: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(Magnetometer::VT_FIELD, field);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> MagnetometerBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    MagnetometerBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Magnetometer<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Magnetometer<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Magnetometer");
+      ds.field("field", &self.field());
+      ds.finish()
+  }
+}
+pub enum GnssFixOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct GnssFix<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for GnssFix<'a> {
+  type Inner = GnssFix<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> GnssFix<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_FIX_TYPE: flatbuffers::VOffsetT = 6;
+  pub const VT_LATITUDE_DEG: flatbuffers::VOffsetT = 8;
+  pub const VT_LONGITUDE_DEG: flatbuffers::VOffsetT = 10;
+  pub const VT_ALTITUDE: flatbuffers::VOffsetT = 12;
+  pub const VT_VELOCITY: flatbuffers::VOffsetT = 14;
+  pub const VT_HDOP: flatbuffers::VOffsetT = 16;
+  pub const VT_VDOP: flatbuffers::VOffsetT = 18;
+  pub const VT_SATELLITES: flatbuffers::VOffsetT = 20;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    GnssFix { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args GnssFixArgs<'args>
+  ) -> flatbuffers::WIPOffset<GnssFix<'bldr>> {
+    let mut builder = GnssFixBuilder::new(_fbb);
+    builder.add_longitude_deg(args.longitude_deg);
+    builder.add_latitude_deg(args.latitude_deg);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_vdop(args.vdop);
+    builder.add_hdop(args.hdop);
+    if let Some(x) = args.velocity { builder.add_velocity(x); }
+    builder.add_altitude(args.altitude);
+    builder.add_satellites(args.satellites);
+    builder.add_fix_type(args.fix_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(GnssFix::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn fix_type(&self) -> GnssFixType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<GnssFixType>(GnssFix::VT_FIX_TYPE, Some(GnssFixType::NoFix)).unwrap()}
+  }
+  #[inline]
+  pub fn latitude_deg(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(GnssFix::VT_LATITUDE_DEG, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn longitude_deg(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(GnssFix::VT_LONGITUDE_DEG, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn altitude(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(GnssFix::VT_ALTITUDE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn velocity(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(GnssFix::VT_VELOCITY, None)}
+  }
+  #[inline]
+  pub fn hdop(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(GnssFix::VT_HDOP, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn vdop(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(GnssFix::VT_VDOP, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn satellites(&self) -> u8 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u8>(GnssFix::VT_SATELLITES, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for GnssFix<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<GnssFixType>("fix_type", Self::VT_FIX_TYPE, false)?
+     .visit_field::<f64>("latitude_deg", Self::VT_LATITUDE_DEG, false)?
+     .visit_field::<f64>("longitude_deg", Self::VT_LONGITUDE_DEG, false)?
+     .visit_field::<f32>("altitude", Self::VT_ALTITUDE, false)?
+     .visit_field::<Vec3>("velocity", Self::VT_VELOCITY, false)?
+     .visit_field::<f32>("hdop", Self::VT_HDOP, false)?
+     .visit_field::<f32>("vdop", Self::VT_VDOP, false)?
+     .visit_field::<u8>("satellites", Self::VT_SATELLITES, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct GnssFixArgs<'a> {
+    pub timestamp_us: u64,
+    pub fix_type: GnssFixType,
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude: f32,
+    pub velocity: Option<&'a Vec3>,
+    pub hdop: f32,
+    pub vdop: f32,
+    pub satellites: u8,
+}
+impl<'a> Default for GnssFixArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    GnssFixArgs {
+      timestamp_us: 0,
+      fix_type: GnssFixType::NoFix,
+      latitude_deg: 0.0,
+      longitude_deg: 0.0,
+      altitude: 0.0,
+      velocity: None,
+      hdop: 0.0,
+      vdop: 0.0,
+      satellites: 0,
+    }
+  }
+}
+
+pub struct GnssFixBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> GnssFixBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(GnssFix::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_fix_type(&mut self, fix_type: GnssFixType) {
+    self.fbb_.push_slot::<GnssFixType>(GnssFix::VT_FIX_TYPE, fix_type, GnssFixType::NoFix);
+  }
+  #[inline]
+  pub fn add_latitude_deg(&mut self, latitude_deg: f64) {
+    self.fbb_.push_slot::<f64>(GnssFix::VT_LATITUDE_DEG, latitude_deg, 0.0);
+  }
+  #[inline]
+  pub fn add_longitude_deg(&mut self, longitude_deg: f64) {
+    self.fbb_.push_slot::<f64>(GnssFix::VT_LONGITUDE_DEG, longitude_deg, 0.0);
+  }
+  #[inline]
+  pub fn add_altitude(&mut self, altitude: f32) {
+    self.fbb_.push_slot::<f32>(GnssFix::VT_ALTITUDE, altitude, 0.0);
+  }
+  #[inline]
+  pub fn add_velocity(&mut self, velocity: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(GnssFix::VT_VELOCITY, velocity);
+  }
+  #[inline]
+  pub fn add_hdop(&mut self, hdop: f32) {
+    self.fbb_.push_slot::<f32>(GnssFix::VT_HDOP, hdop, 0.0);
+  }
+  #[inline]
+  pub fn add_vdop(&mut self, vdop: f32) {
+    self.fbb_.push_slot::<f32>(GnssFix::VT_VDOP, vdop, 0.0);
+  }
+  #[inline]
+  pub fn add_satellites(&mut self, satellites: u8) {
+    self.fbb_.push_slot::<u8>(GnssFix::VT_SATELLITES, satellites, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> GnssFixBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    GnssFixBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<GnssFix<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for GnssFix<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("GnssFix");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("fix_type", &self.fix_type());
+      ds.field("latitude_deg", &self.latitude_deg());
+      ds.field("longitude_deg", &self.longitude_deg());
+      ds.field("altitude", &self.altitude());
+      ds.field("velocity", &self.velocity());
+      ds.field("hdop", &self.hdop());
+      ds.field("vdop", &self.vdop());
+      ds.field("satellites", &self.satellites());
+      ds.finish()
+  }
+}
+pub enum PowerOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Power<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Power<'a> {
+  type Inner = Power<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Power<'a> {
+  pub const VT_VOLTAGE: flatbuffers::VOffsetT = 4;
+  pub const VT_CURRENT: flatbuffers::VOffsetT = 6;
+  pub const VT_TEMPERATURE: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Power { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args PowerArgs
+  ) -> flatbuffers::WIPOffset<Power<'bldr>> {
+    let mut builder = PowerBuilder::new(_fbb);
+    builder.add_temperature(args.temperature);
+    builder.add_current(args.current);
+    builder.add_voltage(args.voltage);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn voltage(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Power::VT_VOLTAGE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn current(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Power::VT_CURRENT, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn temperature(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Power::VT_TEMPERATURE, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Power<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f32>("voltage", Self::VT_VOLTAGE, false)?
+     .visit_field::<f32>("current", Self::VT_CURRENT, false)?
+     .visit_field::<f32>("temperature", Self::VT_TEMPERATURE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PowerArgs {
+    pub voltage: f32,
+    pub current: f32,
+    pub temperature: f32,
+}
+impl<'a> Default for PowerArgs {
+  #[inline]
+  fn default() -> Self {
+    PowerArgs {
+      voltage: 0.0,
+      current: 0.0,
+      temperature: 0.0,
+    }
+  }
+}
+
+pub struct PowerBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> PowerBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_voltage(&mut self, voltage: f32) {
+    self.fbb_.push_slot::<f32>(Power::VT_VOLTAGE, voltage, 0.0);
+  }
+  #[inline]
+  pub fn add_current(&mut self, current: f32) {
+    self.fbb_.push_slot::<f32>(Power::VT_CURRENT, current, 0.0);
+  }
+  #[inline]
+  pub fn add_temperature(&mut self, temperature: f32) {
+    self.fbb_.push_slot::<f32>(Power::VT_TEMPERATURE, temperature, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> PowerBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    PowerBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Power<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Power<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Power");
+      ds.field("voltage", &self.voltage());
+      ds.field("current", &self.current());
+      ds.field("temperature", &self.temperature());
+      ds.finish()
+  }
+}
+pub enum FusedStateOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct FusedState<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FusedState<'a> {
+  type Inner = FusedState<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> FusedState<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_ATTITUDE: flatbuffers::VOffsetT = 6;
+  pub const VT_ANGULAR_RATE: flatbuffers::VOffsetT = 8;
+  pub const VT_ALTITUDE: flatbuffers::VOffsetT = 10;
+  pub const VT_ATTITUDE_VALID: flatbuffers::VOffsetT = 12;
+  pub const VT_ANGULAR_RATE_VALID: flatbuffers::VOffsetT = 14;
+  pub const VT_ALTITUDE_VALID: flatbuffers::VOffsetT = 16;
+  pub const VT_VELOCITY: flatbuffers::VOffsetT = 18;
+  pub const VT_VELOCITY_VALID: flatbuffers::VOffsetT = 20;
+  pub const VT_ACCELERATION: flatbuffers::VOffsetT = 22;
+  pub const VT_ACCELERATION_VALID: flatbuffers::VOffsetT = 24;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    FusedState { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args FusedStateArgs<'args>
+  ) -> flatbuffers::WIPOffset<FusedState<'bldr>> {
+    let mut builder = FusedStateBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.acceleration { builder.add_acceleration(x); }
+    if let Some(x) = args.velocity { builder.add_velocity(x); }
+    builder.add_altitude(args.altitude);
+    if let Some(x) = args.angular_rate { builder.add_angular_rate(x); }
+    if let Some(x) = args.attitude { builder.add_attitude(x); }
+    builder.add_acceleration_valid(args.acceleration_valid);
+    builder.add_velocity_valid(args.velocity_valid);
+    builder.add_altitude_valid(args.altitude_valid);
+    builder.add_angular_rate_valid(args.angular_rate_valid);
+    builder.add_attitude_valid(args.attitude_valid);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(FusedState::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn attitude(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(FusedState::VT_ATTITUDE, None)}
+  }
+  #[inline]
+  pub fn angular_rate(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(FusedState::VT_ANGULAR_RATE, None)}
+  }
+  #[inline]
+  pub fn altitude(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(FusedState::VT_ALTITUDE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn attitude_valid(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(FusedState::VT_ATTITUDE_VALID, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn angular_rate_valid(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(FusedState::VT_ANGULAR_RATE_VALID, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn altitude_valid(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(FusedState::VT_ALTITUDE_VALID, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn velocity(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(FusedState::VT_VELOCITY, None)}
+  }
+  #[inline]
+  pub fn velocity_valid(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(FusedState::VT_VELOCITY_VALID, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn acceleration(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(FusedState::VT_ACCELERATION, None)}
+  }
+  #[inline]
+  pub fn acceleration_valid(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(FusedState::VT_ACCELERATION_VALID, Some(false)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for FusedState<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<Vec3>("attitude", Self::VT_ATTITUDE, false)?
+     .visit_field::<Vec3>("angular_rate", Self::VT_ANGULAR_RATE, false)?
+     .visit_field::<f32>("altitude", Self::VT_ALTITUDE, false)?
+     .visit_field::<bool>("attitude_valid", Self::VT_ATTITUDE_VALID, false)?
+     .visit_field::<bool>("angular_rate_valid", Self::VT_ANGULAR_RATE_VALID, false)?
+     .visit_field::<bool>("altitude_valid", Self::VT_ALTITUDE_VALID, false)?
+     .visit_field::<Vec3>("velocity", Self::VT_VELOCITY, false)?
+     .visit_field::<bool>("velocity_valid", Self::VT_VELOCITY_VALID, false)?
+     .visit_field::<Vec3>("acceleration", Self::VT_ACCELERATION, false)?
+     .visit_field::<bool>("acceleration_valid", Self::VT_ACCELERATION_VALID, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct FusedStateArgs<'a> {
+    pub timestamp_us: u64,
+    pub attitude: Option<&'a Vec3>,
+    pub angular_rate: Option<&'a Vec3>,
+    pub altitude: f32,
+    pub attitude_valid: bool,
+    pub angular_rate_valid: bool,
+    pub altitude_valid: bool,
+    pub velocity: Option<&'a Vec3>,
+    pub velocity_valid: bool,
+    pub acceleration: Option<&'a Vec3>,
+    pub acceleration_valid: bool,
+}
+impl<'a> Default for FusedStateArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    FusedStateArgs {
+      timestamp_us: 0,
+      attitude: None,
+      angular_rate: None,
+      altitude: 0.0,
+      attitude_valid: false,
+      angular_rate_valid: false,
+      altitude_valid: false,
+      velocity: None,
+      velocity_valid: false,
+      acceleration: None,
+      acceleration_valid: false,
+    }
+  }
+}
+
+pub struct FusedStateBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FusedStateBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(FusedState::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_attitude(&mut self, attitude: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(FusedState::VT_ATTITUDE, attitude);
+  }
+  #[inline]
+  pub fn add_angular_rate(&mut self, angular_rate: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(FusedState::VT_ANGULAR_RATE, angular_rate);
+  }
+  #[inline]
+  pub fn add_altitude(&mut self, altitude: f32) {
+    self.fbb_.push_slot::<f32>(FusedState::VT_ALTITUDE, altitude, 0.0);
+  }
+  #[inline]
+  pub fn add_attitude_valid(&mut self, attitude_valid: bool) {
+    self.fbb_.push_slot::<bool>(FusedState::VT_ATTITUDE_VALID, attitude_valid, false);
+  }
+  #[inline]
+  pub fn add_angular_rate_valid(&mut self, angular_rate_valid: bool) {
+    self.fbb_.push_slot::<bool>(FusedState::VT_ANGULAR_RATE_VALID, angular_rate_valid, false);
+  }
+  #[inline]
+  pub fn add_altitude_valid(&mut self, altitude_valid: bool) {
+    self.fbb_.push_slot::<bool>(FusedState::VT_ALTITUDE_VALID, altitude_valid, false);
+  }
+  #[inline]
+  pub fn add_velocity(&mut self, velocity: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(FusedState::VT_VELOCITY, velocity);
+  }
+  #[inline]
+  pub fn add_velocity_valid(&mut self, velocity_valid: bool) {
+    self.fbb_.push_slot::<bool>(FusedState::VT_VELOCITY_VALID, velocity_valid, false);
+  }
+  #[inline]
+  pub fn add_acceleration(&mut self, acceleration: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(FusedState::VT_ACCELERATION, acceleration);
+  }
+  #[inline]
+  pub fn add_acceleration_valid(&mut self, acceleration_valid: bool) {
+    self.fbb_.push_slot::<bool>(FusedState::VT_ACCELERATION_VALID, acceleration_valid, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FusedStateBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FusedStateBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FusedState<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for FusedState<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("FusedState");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("attitude", &self.attitude());
+      ds.field("angular_rate", &self.angular_rate());
+      ds.field("altitude", &self.altitude());
+      ds.field("attitude_valid", &self.attitude_valid());
+      ds.field("angular_rate_valid", &self.angular_rate_valid());
+      ds.field("altitude_valid", &self.altitude_valid());
+      ds.field("velocity", &self.velocity());
+      ds.field("velocity_valid", &self.velocity_valid());
+      ds.field("acceleration", &self.acceleration());
+      ds.field("acceleration_valid", &self.acceleration_valid());
+      ds.finish()
+  }
+}
+pub enum SimTruthOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct SimTruth<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for SimTruth<'a> {
+  type Inner = SimTruth<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> SimTruth<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_FLIGHT_TIME_S: flatbuffers::VOffsetT = 6;
+  pub const VT_POSITION: flatbuffers::VOffsetT = 8;
+  pub const VT_VELOCITY: flatbuffers::VOffsetT = 10;
+  pub const VT_ACCELERATION: flatbuffers::VOffsetT = 12;
+  pub const VT_ATTITUDE: flatbuffers::VOffsetT = 14;
+  pub const VT_ANGULAR_RATE: flatbuffers::VOffsetT = 16;
+  pub const VT_MASS: flatbuffers::VOffsetT = 18;
+  pub const VT_THRUST: flatbuffers::VOffsetT = 20;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    SimTruth { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args SimTruthArgs<'args>
+  ) -> flatbuffers::WIPOffset<SimTruth<'bldr>> {
+    let mut builder = SimTruthBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_thrust(args.thrust);
+    builder.add_mass(args.mass);
+    if let Some(x) = args.angular_rate { builder.add_angular_rate(x); }
+    if let Some(x) = args.attitude { builder.add_attitude(x); }
+    if let Some(x) = args.acceleration { builder.add_acceleration(x); }
+    if let Some(x) = args.velocity { builder.add_velocity(x); }
+    if let Some(x) = args.position { builder.add_position(x); }
+    builder.add_flight_time_s(args.flight_time_s);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(SimTruth::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn flight_time_s(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(SimTruth::VT_FLIGHT_TIME_S, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn position(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(SimTruth::VT_POSITION, None)}
+  }
+  #[inline]
+  pub fn velocity(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(SimTruth::VT_VELOCITY, None)}
+  }
+  #[inline]
+  pub fn acceleration(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(SimTruth::VT_ACCELERATION, None)}
+  }
+  #[inline]
+  pub fn attitude(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(SimTruth::VT_ATTITUDE, None)}
+  }
+  #[inline]
+  pub fn angular_rate(&self) -> Option<&'a Vec3> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec3>(SimTruth::VT_ANGULAR_RATE, None)}
+  }
+  #[inline]
+  pub fn mass(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(SimTruth::VT_MASS, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn thrust(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(SimTruth::VT_THRUST, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for SimTruth<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<f32>("flight_time_s", Self::VT_FLIGHT_TIME_S, false)?
+     .visit_field::<Vec3>("position", Self::VT_POSITION, false)?
+     .visit_field::<Vec3>("velocity", Self::VT_VELOCITY, false)?
+     .visit_field::<Vec3>("acceleration", Self::VT_ACCELERATION, false)?
+     .visit_field::<Vec3>("attitude", Self::VT_ATTITUDE, false)?
+     .visit_field::<Vec3>("angular_rate", Self::VT_ANGULAR_RATE, false)?
+     .visit_field::<f32>("mass", Self::VT_MASS, false)?
+     .visit_field::<f32>("thrust", Self::VT_THRUST, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct SimTruthArgs<'a> {
+    pub timestamp_us: u64,
+    pub flight_time_s: f32,
+    pub position: Option<&'a Vec3>,
+    pub velocity: Option<&'a Vec3>,
+    pub acceleration: Option<&'a Vec3>,
+    pub attitude: Option<&'a Vec3>,
+    pub angular_rate: Option<&'a Vec3>,
+    pub mass: f32,
+    pub thrust: f32,
+}
+impl<'a> Default for SimTruthArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    SimTruthArgs {
+      timestamp_us: 0,
+      flight_time_s: 0.0,
+      position: None,
+      velocity: None,
+      acceleration: None,
+      attitude: None,
+      angular_rate: None,
+      mass: 0.0,
+      thrust: 0.0,
+    }
+  }
+}
+
+pub struct SimTruthBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> SimTruthBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(SimTruth::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_flight_time_s(&mut self, flight_time_s: f32) {
+    self.fbb_.push_slot::<f32>(SimTruth::VT_FLIGHT_TIME_S, flight_time_s, 0.0);
+  }
+  #[inline]
+  pub fn add_position(&mut self, position: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(SimTruth::VT_POSITION, position);
+  }
+  #[inline]
+  pub fn add_velocity(&mut self, velocity: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(SimTruth::VT_VELOCITY, velocity);
+  }
+  #[inline]
+  pub fn add_acceleration(&mut self, acceleration: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(SimTruth::VT_ACCELERATION, acceleration);
+  }
+  #[inline]
+  pub fn add_attitude(&mut self, attitude: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(SimTruth::VT_ATTITUDE, attitude);
+  }
+  #[inline]
+  pub fn add_angular_rate(&mut self, angular_rate: &Vec3) {
+    self.fbb_.push_slot_always::<&Vec3>(SimTruth::VT_ANGULAR_RATE, angular_rate);
+  }
+  #[inline]
+  pub fn add_mass(&mut self, mass: f32) {
+    self.fbb_.push_slot::<f32>(SimTruth::VT_MASS, mass, 0.0);
+  }
+  #[inline]
+  pub fn add_thrust(&mut self, thrust: f32) {
+    self.fbb_.push_slot::<f32>(SimTruth::VT_THRUST, thrust, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> SimTruthBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    SimTruthBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<SimTruth<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for SimTruth<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("SimTruth");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("flight_time_s", &self.flight_time_s());
+      ds.field("position", &self.position());
+      ds.field("velocity", &self.velocity());
+      ds.field("acceleration", &self.acceleration());
+      ds.field("attitude", &self.attitude());
+      ds.field("angular_rate", &self.angular_rate());
+      ds.field("mass", &self.mass());
+      ds.field("thrust", &self.thrust());
+      ds.finish()
+  }
+}
+pub enum ClockOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Clock<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Clock<'a> {
+  type Inner = Clock<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Clock<'a> {
+  pub const VT_TIME_NS: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Clock { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ClockArgs
+  ) -> flatbuffers::WIPOffset<Clock<'bldr>> {
+    let mut builder = ClockBuilder::new(_fbb);
+    builder.add_time_ns(args.time_ns);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn time_ns(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Clock::VT_TIME_NS, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Clock<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("time_ns", Self::VT_TIME_NS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ClockArgs {
+    pub time_ns: u64,
+}
+impl<'a> Default for ClockArgs {
+  #[inline]
+  fn default() -> Self {
+    ClockArgs {
+      time_ns: 0,
+    }
+  }
+}
+
+pub struct ClockBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ClockBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_time_ns(&mut self, time_ns: u64) {
+    self.fbb_.push_slot::<u64>(Clock::VT_TIME_NS, time_ns, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ClockBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ClockBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Clock<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Clock<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Clock");
+      ds.field("time_ns", &self.time_ns());
+      ds.finish()
+  }
+}
+pub enum HeartbeatOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Heartbeat<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Heartbeat<'a> {
+  type Inner = Heartbeat<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Heartbeat<'a> {
+  pub const VT_NODE: flatbuffers::VOffsetT = 4;
+  pub const VT_UPTIME_S: flatbuffers::VOffsetT = 6;
+  pub const VT_CYCLES: flatbuffers::VOffsetT = 8;
+  pub const VT_ERRORS: flatbuffers::VOffsetT = 10;
+  pub const VT_MEAN_CYCLE_US: flatbuffers::VOffsetT = 12;
+  pub const VT_MAX_CYCLE_US: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Heartbeat { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args HeartbeatArgs<'args>
+  ) -> flatbuffers::WIPOffset<Heartbeat<'bldr>> {
+    let mut builder = HeartbeatBuilder::new(_fbb);
+    builder.add_errors(args.errors);
+    builder.add_cycles(args.cycles);
+    builder.add_uptime_s(args.uptime_s);
+    builder.add_max_cycle_us(args.max_cycle_us);
+    builder.add_mean_cycle_us(args.mean_cycle_us);
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Heartbeat::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn uptime_s(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(Heartbeat::VT_UPTIME_S, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn cycles(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Heartbeat::VT_CYCLES, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn errors(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Heartbeat::VT_ERRORS, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn mean_cycle_us(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Heartbeat::VT_MEAN_CYCLE_US, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn max_cycle_us(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Heartbeat::VT_MAX_CYCLE_US, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Heartbeat<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<f64>("uptime_s", Self::VT_UPTIME_S, false)?
+     .visit_field::<u64>("cycles", Self::VT_CYCLES, false)?
+     .visit_field::<u64>("errors", Self::VT_ERRORS, false)?
+     .visit_field::<f32>("mean_cycle_us", Self::VT_MEAN_CYCLE_US, false)?
+     .visit_field::<f32>("max_cycle_us", Self::VT_MAX_CYCLE_US, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct HeartbeatArgs<'a> {
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub uptime_s: f64,
+    pub cycles: u64,
+    pub errors: u64,
+    pub mean_cycle_us: f32,
+    pub max_cycle_us: f32,
+}
+impl<'a> Default for HeartbeatArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    HeartbeatArgs {
+      node: None,
+      uptime_s: 0.0,
+      cycles: 0,
+      errors: 0,
+      mean_cycle_us: 0.0,
+      max_cycle_us: 0.0,
+    }
+  }
+}
+
+pub struct HeartbeatBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> HeartbeatBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Heartbeat::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_uptime_s(&mut self, uptime_s: f64) {
+    self.fbb_.push_slot::<f64>(Heartbeat::VT_UPTIME_S, uptime_s, 0.0);
+  }
+  #[inline]
+  pub fn add_cycles(&mut self, cycles: u64) {
+    self.fbb_.push_slot::<u64>(Heartbeat::VT_CYCLES, cycles, 0);
+  }
+  #[inline]
+  pub fn add_errors(&mut self, errors: u64) {
+    self.fbb_.push_slot::<u64>(Heartbeat::VT_ERRORS, errors, 0);
+  }
+  #[inline]
+  pub fn add_mean_cycle_us(&mut self, mean_cycle_us: f32) {
+    self.fbb_.push_slot::<f32>(Heartbeat::VT_MEAN_CYCLE_US, mean_cycle_us, 0.0);
+  }
+  #[inline]
+  pub fn add_max_cycle_us(&mut self, max_cycle_us: f32) {
+    self.fbb_.push_slot::<f32>(Heartbeat::VT_MAX_CYCLE_US, max_cycle_us, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> HeartbeatBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    HeartbeatBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Heartbeat<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Heartbeat<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Heartbeat");
+      ds.field("node", &self.node());
+      ds.field("uptime_s", &self.uptime_s());
+      ds.field("cycles", &self.cycles());
+      ds.field("errors", &self.errors());
+      ds.field("mean_cycle_us", &self.mean_cycle_us());
+      ds.field("max_cycle_us", &self.max_cycle_us());
+      ds.finish()
+  }
+}
+pub enum KeyDropsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct KeyDrops<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for KeyDrops<'a> {
+  type Inner = KeyDrops<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> KeyDrops<'a> {
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_PUBLISHER: flatbuffers::VOffsetT = 6;
+  pub const VT_RECEIVED: flatbuffers::VOffsetT = 8;
+  pub const VT_DROPPED: flatbuffers::VOffsetT = 10;
+  pub const VT_RESTARTS: flatbuffers::VOffsetT = 12;
+  pub const VT_CORRUPTED: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    KeyDrops { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args KeyDropsArgs<'args>
+  ) -> flatbuffers::WIPOffset<KeyDrops<'bldr>> {
+    let mut builder = KeyDropsBuilder::new(_fbb);
+    builder.add_corrupted(args.corrupted);
+    builder.add_dropped(args.dropped);
+    builder.add_received(args.received);
+    builder.add_restarts(args.restarts);
+    if let Some(x) = args.publisher { builder.add_publisher(x); }
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn key(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyDrops::VT_KEY, None)}
+  }
+  #[inline]
+  pub fn publisher(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyDrops::VT_PUBLISHER, None)}
+  }
+  #[inline]
+  pub fn received(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(KeyDrops::VT_RECEIVED, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn dropped(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(KeyDrops::VT_DROPPED, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn restarts(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(KeyDrops::VT_RESTARTS, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn corrupted(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(KeyDrops::VT_CORRUPTED, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for KeyDrops<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("publisher", Self::VT_PUBLISHER, false)?
+     .visit_field::<u64>("received", Self::VT_RECEIVED, false)?
+     .visit_field::<u64>("dropped", Self::VT_DROPPED, false)?
+     .visit_field::<u32>("restarts", Self::VT_RESTARTS, false)?
+     .visit_field::<u64>("corrupted", Self::VT_CORRUPTED, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct KeyDropsArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub publisher: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub received: u64,
+    pub dropped: u64,
+    pub restarts: u32,
+    pub corrupted: u64,
+}
+impl<'a> Default for KeyDropsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    KeyDropsArgs {
+      key: None,
+      publisher: None,
+      received: 0,
+      dropped: 0,
+      restarts: 0,
+      corrupted: 0,
+    }
+  }
+}
+
+pub struct KeyDropsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> KeyDropsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(KeyDrops::VT_KEY, key);
+  }
+  #[inline]
+  pub fn add_publisher(&mut self, publisher: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(KeyDrops::VT_PUBLISHER, publisher);
+  }
+  #[inline]
+  pub fn add_received(&mut self, received: u64) {
+    self.fbb_.push_slot::<u64>(KeyDrops::VT_RECEIVED, received, 0);
+  }
+  #[inline]
+  pub fn add_dropped(&mut self, dropped: u64) {
+    self.fbb_.push_slot::<u64>(KeyDrops::VT_DROPPED, dropped, 0);
+  }
+  #[inline]
+  pub fn add_restarts(&mut self, restarts: u32) {
+    self.fbb_.push_slot::<u32>(KeyDrops::VT_RESTARTS, restarts, 0);
+  }
+  #[inline]
+  pub fn add_corrupted(&mut self, corrupted: u64) {
+    self.fbb_.push_slot::<u64>(KeyDrops::VT_CORRUPTED, corrupted, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> KeyDropsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    KeyDropsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<KeyDrops<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for KeyDrops<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("KeyDrops");
+      ds.field("key", &self.key());
+      ds.field("publisher", &self.publisher());
+      ds.field("received", &self.received());
+      ds.field("dropped", &self.dropped());
+      ds.field("restarts", &self.restarts());
+      ds.field("corrupted", &self.corrupted());
+      ds.finish()
+  }
+}
+pub enum DropStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct DropStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for DropStats<'a> {
+  type Inner = DropStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> DropStats<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_KEYS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    DropStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args DropStatsArgs<'args>
+  ) -> flatbuffers::WIPOffset<DropStats<'bldr>> {
+    let mut builder = DropStatsBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.keys { builder.add_keys(x); }
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DropStats::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DropStats::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn keys(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyDrops<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyDrops>>>>(DropStats::VT_KEYS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for DropStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<KeyDrops>>>>("keys", Self::VT_KEYS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct DropStatsArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub keys: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyDrops<'a>>>>>,
+}
+impl<'a> Default for DropStatsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    DropStatsArgs {
+      timestamp_us: 0,
+      node: None,
+      keys: None,
+    }
+  }
+}
+
+pub struct DropStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> DropStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(DropStats::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DropStats::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_keys(&mut self, keys: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<KeyDrops<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DropStats::VT_KEYS, keys);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> DropStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    DropStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<DropStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for DropStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("DropStats");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("keys", &self.keys());
+      ds.finish()
+  }
+}
+pub enum SensorParseStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct SensorParseStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for SensorParseStats<'a> {
+  type Inner = SensorParseStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> SensorParseStats<'a> {
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_PARSED: flatbuffers::VOffsetT = 6;
+  pub const VT_INVALID: flatbuffers::VOffsetT = 8;
+  pub const VT_MISSING_FIELD: flatbuffers::VOffsetT = 10;
+  pub const VT_OUT_OF_BOUNDS: flatbuffers::VOffsetT = 12;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    SensorParseStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args SensorParseStatsArgs<'args>
+  ) -> flatbuffers::WIPOffset<SensorParseStats<'bldr>> {
+    let mut builder = SensorParseStatsBuilder::new(_fbb);
+    builder.add_out_of_bounds(args.out_of_bounds);
+    builder.add_missing_field(args.missing_field);
+    builder.add_invalid(args.invalid);
+    builder.add_parsed(args.parsed);
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn key(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SensorParseStats::VT_KEY, None)}
+  }
+  #[inline]
+  pub fn parsed(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(SensorParseStats::VT_PARSED, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn invalid(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(SensorParseStats::VT_INVALID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn missing_field(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(SensorParseStats::VT_MISSING_FIELD, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn out_of_bounds(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(SensorParseStats::VT_OUT_OF_BOUNDS, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for SensorParseStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, false)?
+     .visit_field::<u64>("parsed", Self::VT_PARSED, false)?
+     .visit_field::<u64>("invalid", Self::VT_INVALID, false)?
+     .visit_field::<u64>("missing_field", Self::VT_MISSING_FIELD, false)?
+     .visit_field::<u64>("out_of_bounds", Self::VT_OUT_OF_BOUNDS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct SensorParseStatsArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub parsed: u64,
+    pub invalid: u64,
+    pub missing_field: u64,
+    pub out_of_bounds: u64,
+}
+impl<'a> Default for SensorParseStatsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    SensorParseStatsArgs {
+      key: None,
+      parsed: 0,
+      invalid: 0,
+      missing_field: 0,
+      out_of_bounds: 0,
+    }
+  }
+}
+
+pub struct SensorParseStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> SensorParseStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SensorParseStats::VT_KEY, key);
+  }
+  #[inline]
+  pub fn add_parsed(&mut self, parsed: u64) {
+    self.fbb_.push_slot::<u64>(SensorParseStats::VT_PARSED, parsed, 0);
+  }
+  #[inline]
+  pub fn add_invalid(&mut self, invalid: u64) {
+    self.fbb_.push_slot::<u64>(SensorParseStats::VT_INVALID, invalid, 0);
+  }
+  #[inline]
+  pub fn add_missing_field(&mut self, missing_field: u64) {
+    self.fbb_.push_slot::<u64>(SensorParseStats::VT_MISSING_FIELD, missing_field, 0);
+  }
+  #[inline]
+  pub fn add_out_of_bounds(&mut self, out_of_bounds: u64) {
+    self.fbb_.push_slot::<u64>(SensorParseStats::VT_OUT_OF_BOUNDS, out_of_bounds, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> SensorParseStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    SensorParseStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<SensorParseStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for SensorParseStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("SensorParseStats");
+      ds.field("key", &self.key());
+      ds.field("parsed", &self.parsed());
+      ds.field("invalid", &self.invalid());
+      ds.field("missing_field", &self.missing_field());
+      ds.field("out_of_bounds", &self.out_of_bounds());
+      ds.finish()
+  }
+}
+pub enum ParseStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ParseStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ParseStats<'a> {
+  type Inner = ParseStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ParseStats<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_SENSORS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ParseStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ParseStatsArgs<'args>
+  ) -> flatbuffers::WIPOffset<ParseStats<'bldr>> {
+    let mut builder = ParseStatsBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.sensors { builder.add_sensors(x); }
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ParseStats::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ParseStats::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn sensors(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SensorParseStats<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SensorParseStats>>>>(ParseStats::VT_SENSORS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for ParseStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<SensorParseStats>>>>("sensors", Self::VT_SENSORS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ParseStatsArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub sensors: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SensorParseStats<'a>>>>>,
+}
+impl<'a> Default for ParseStatsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ParseStatsArgs {
+      timestamp_us: 0,
+      node: None,
+      sensors: None,
+    }
+  }
+}
+
+pub struct ParseStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ParseStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(ParseStats::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ParseStats::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_sensors(&mut self, sensors: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<SensorParseStats<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ParseStats::VT_SENSORS, sensors);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ParseStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ParseStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ParseStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ParseStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ParseStats");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("sensors", &self.sensors());
+      ds.finish()
+  }
+}
+pub enum TimerStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct TimerStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for TimerStats<'a> {
+  type Inner = TimerStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> TimerStats<'a> {
+  pub const VT_PERIOD_US: flatbuffers::VOffsetT = 4;
+  pub const VT_TICKS: flatbuffers::VOffsetT = 6;
+  pub const VT_MEAN_JITTER_US: flatbuffers::VOffsetT = 8;
+  pub const VT_MAX_JITTER_US: flatbuffers::VOffsetT = 10;
+  pub const VT_MISSED: flatbuffers::VOffsetT = 12;
+  pub const VT_OVERRUNS: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    TimerStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args TimerStatsArgs
+  ) -> flatbuffers::WIPOffset<TimerStats<'bldr>> {
+    let mut builder = TimerStatsBuilder::new(_fbb);
+    builder.add_overruns(args.overruns);
+    builder.add_missed(args.missed);
+    builder.add_ticks(args.ticks);
+    builder.add_period_us(args.period_us);
+    builder.add_max_jitter_us(args.max_jitter_us);
+    builder.add_mean_jitter_us(args.mean_jitter_us);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn period_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(TimerStats::VT_PERIOD_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn ticks(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(TimerStats::VT_TICKS, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn mean_jitter_us(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(TimerStats::VT_MEAN_JITTER_US, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn max_jitter_us(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(TimerStats::VT_MAX_JITTER_US, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn missed(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(TimerStats::VT_MISSED, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn overruns(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(TimerStats::VT_OVERRUNS, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for TimerStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("period_us", Self::VT_PERIOD_US, false)?
+     .visit_field::<u64>("ticks", Self::VT_TICKS, false)?
+     .visit_field::<f32>("mean_jitter_us", Self::VT_MEAN_JITTER_US, false)?
+     .visit_field::<f32>("max_jitter_us", Self::VT_MAX_JITTER_US, false)?
+     .visit_field::<u64>("missed", Self::VT_MISSED, false)?
+     .visit_field::<u64>("overruns", Self::VT_OVERRUNS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct TimerStatsArgs {
+    pub period_us: u64,
+    pub ticks: u64,
+    pub mean_jitter_us: f32,
+    pub max_jitter_us: f32,
+    pub missed: u64,
+    pub overruns: u64,
+}
+impl<'a> Default for TimerStatsArgs {
+  #[inline]
+  fn default() -> Self {
+    TimerStatsArgs {
+      period_us: 0,
+      ticks: 0,
+      mean_jitter_us: 0.0,
+      max_jitter_us: 0.0,
+      missed: 0,
+      overruns: 0,
+    }
+  }
+}
+
+pub struct TimerStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> TimerStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_period_us(&mut self, period_us: u64) {
+    self.fbb_.push_slot::<u64>(TimerStats::VT_PERIOD_US, period_us, 0);
+  }
+  #[inline]
+  pub fn add_ticks(&mut self, ticks: u64) {
+    self.fbb_.push_slot::<u64>(TimerStats::VT_TICKS, ticks, 0);
+  }
+  #[inline]
+  pub fn add_mean_jitter_us(&mut self, mean_jitter_us: f32) {
+    self.fbb_.push_slot::<f32>(TimerStats::VT_MEAN_JITTER_US, mean_jitter_us, 0.0);
+  }
+  #[inline]
+  pub fn add_max_jitter_us(&mut self, max_jitter_us: f32) {
+    self.fbb_.push_slot::<f32>(TimerStats::VT_MAX_JITTER_US, max_jitter_us, 0.0);
+  }
+  #[inline]
+  pub fn add_missed(&mut self, missed: u64) {
+    self.fbb_.push_slot::<u64>(TimerStats::VT_MISSED, missed, 0);
+  }
+  #[inline]
+  pub fn add_overruns(&mut self, overruns: u64) {
+    self.fbb_.push_slot::<u64>(TimerStats::VT_OVERRUNS, overruns, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> TimerStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    TimerStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<TimerStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for TimerStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("TimerStats");
+      ds.field("period_us", &self.period_us());
+      ds.field("ticks", &self.ticks());
+      ds.field("mean_jitter_us", &self.mean_jitter_us());
+      ds.field("max_jitter_us", &self.max_jitter_us());
+      ds.field("missed", &self.missed());
+      ds.field("overruns", &self.overruns());
+      ds.finish()
+  }
+}
+pub enum QueueStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct QueueStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for QueueStats<'a> {
+  type Inner = QueueStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> QueueStats<'a> {
+  pub const VT_NAME: flatbuffers::VOffsetT = 4;
+  pub const VT_DEPTH: flatbuffers::VOffsetT = 6;
+  pub const VT_MAX_DEPTH: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    QueueStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args QueueStatsArgs<'args>
+  ) -> flatbuffers::WIPOffset<QueueStats<'bldr>> {
+    let mut builder = QueueStatsBuilder::new(_fbb);
+    builder.add_max_depth(args.max_depth);
+    builder.add_depth(args.depth);
+    if let Some(x) = args.name { builder.add_name(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn name(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(QueueStats::VT_NAME, None)}
+  }
+  #[inline]
+  pub fn depth(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(QueueStats::VT_DEPTH, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn max_depth(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(QueueStats::VT_MAX_DEPTH, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for QueueStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
+     .visit_field::<u64>("depth", Self::VT_DEPTH, false)?
+     .visit_field::<u64>("max_depth", Self::VT_MAX_DEPTH, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct QueueStatsArgs<'a> {
+    pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub depth: u64,
+    pub max_depth: u64,
+}
+impl<'a> Default for QueueStatsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    QueueStatsArgs {
+      name: None,
+      depth: 0,
+      max_depth: 0,
+    }
+  }
+}
+
+pub struct QueueStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> QueueStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(QueueStats::VT_NAME, name);
+  }
+  #[inline]
+  pub fn add_depth(&mut self, depth: u64) {
+    self.fbb_.push_slot::<u64>(QueueStats::VT_DEPTH, depth, 0);
+  }
+  #[inline]
+  pub fn add_max_depth(&mut self, max_depth: u64) {
+    self.fbb_.push_slot::<u64>(QueueStats::VT_MAX_DEPTH, max_depth, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> QueueStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    QueueStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<QueueStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for QueueStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("QueueStats");
+      ds.field("name", &self.name());
+      ds.field("depth", &self.depth());
+      ds.field("max_depth", &self.max_depth());
+      ds.finish()
+  }
+}
+pub enum NodeStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct NodeStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for NodeStats<'a> {
+  type Inner = NodeStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> NodeStats<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_RSS_BYTES: flatbuffers::VOffsetT = 8;
+  pub const VT_CPU_PERCENT: flatbuffers::VOffsetT = 10;
+  pub const VT_TIMERS: flatbuffers::VOffsetT = 12;
+  pub const VT_QUEUES: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    NodeStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args NodeStatsArgs<'args>
+  ) -> flatbuffers::WIPOffset<NodeStats<'bldr>> {
+    let mut builder = NodeStatsBuilder::new(_fbb);
+    builder.add_rss_bytes(args.rss_bytes);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.queues { builder.add_queues(x); }
+    if let Some(x) = args.timers { builder.add_timers(x); }
+    builder.add_cpu_percent(args.cpu_percent);
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(NodeStats::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(NodeStats::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn rss_bytes(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(NodeStats::VT_RSS_BYTES, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn cpu_percent(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(NodeStats::VT_CPU_PERCENT, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn timers(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TimerStats<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TimerStats>>>>(NodeStats::VT_TIMERS, None)}
+  }
+  #[inline]
+  pub fn queues(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<QueueStats<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<QueueStats>>>>(NodeStats::VT_QUEUES, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for NodeStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<u64>("rss_bytes", Self::VT_RSS_BYTES, false)?
+     .visit_field::<f32>("cpu_percent", Self::VT_CPU_PERCENT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<TimerStats>>>>("timers", Self::VT_TIMERS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<QueueStats>>>>("queues", Self::VT_QUEUES, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct NodeStatsArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub rss_bytes: u64,
+    pub cpu_percent: f32,
+    pub timers: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TimerStats<'a>>>>>,
+    pub queues: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<QueueStats<'a>>>>>,
+}
+impl<'a> Default for NodeStatsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    NodeStatsArgs {
+      timestamp_us: 0,
+      node: None,
+      rss_bytes: 0,
+      cpu_percent: 0.0,
+      timers: None,
+      queues: None,
+    }
+  }
+}
+
+pub struct NodeStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> NodeStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(NodeStats::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NodeStats::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_rss_bytes(&mut self, rss_bytes: u64) {
+    self.fbb_.push_slot::<u64>(NodeStats::VT_RSS_BYTES, rss_bytes, 0);
+  }
+  #[inline]
+  pub fn add_cpu_percent(&mut self, cpu_percent: f32) {
+    self.fbb_.push_slot::<f32>(NodeStats::VT_CPU_PERCENT, cpu_percent, 0.0);
+  }
+  #[inline]
+  pub fn add_timers(&mut self, timers: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<TimerStats<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NodeStats::VT_TIMERS, timers);
+  }
+  #[inline]
+  pub fn add_queues(&mut self, queues: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<QueueStats<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NodeStats::VT_QUEUES, queues);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> NodeStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    NodeStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<NodeStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for NodeStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("NodeStats");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("rss_bytes", &self.rss_bytes());
+      ds.field("cpu_percent", &self.cpu_percent());
+      ds.field("timers", &self.timers());
+      ds.field("queues", &self.queues());
+      ds.finish()
+  }
+}
+pub enum KeyTrafficOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct KeyTraffic<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for KeyTraffic<'a> {
+  type Inner = KeyTraffic<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> KeyTraffic<'a> {
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_MESSAGES: flatbuffers::VOffsetT = 6;
+  pub const VT_BYTES: flatbuffers::VOffsetT = 8;
+  pub const VT_MESSAGES_PER_S: flatbuffers::VOffsetT = 10;
+  pub const VT_BYTES_PER_S: flatbuffers::VOffsetT = 12;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    KeyTraffic { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args KeyTrafficArgs<'args>
+  ) -> flatbuffers::WIPOffset<KeyTraffic<'bldr>> {
+    let mut builder = KeyTrafficBuilder::new(_fbb);
+    builder.add_bytes(args.bytes);
+    builder.add_messages(args.messages);
+    builder.add_bytes_per_s(args.bytes_per_s);
+    builder.add_messages_per_s(args.messages_per_s);
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn key(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyTraffic::VT_KEY, None)}
+  }
+  #[inline]
+  pub fn messages(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(KeyTraffic::VT_MESSAGES, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn bytes(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(KeyTraffic::VT_BYTES, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn messages_per_s(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(KeyTraffic::VT_MESSAGES_PER_S, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn bytes_per_s(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(KeyTraffic::VT_BYTES_PER_S, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for KeyTraffic<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, false)?
+     .visit_field::<u64>("messages", Self::VT_MESSAGES, false)?
+     .visit_field::<u64>("bytes", Self::VT_BYTES, false)?
+     .visit_field::<f32>("messages_per_s", Self::VT_MESSAGES_PER_S, false)?
+     .visit_field::<f32>("bytes_per_s", Self::VT_BYTES_PER_S, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct KeyTrafficArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub messages: u64,
+    pub bytes: u64,
+    pub messages_per_s: f32,
+    pub bytes_per_s: f32,
+}
+impl<'a> Default for KeyTrafficArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    KeyTrafficArgs {
+      key: None,
+      messages: 0,
+      bytes: 0,
+      messages_per_s: 0.0,
+      bytes_per_s: 0.0,
+    }
+  }
+}
+
+pub struct KeyTrafficBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> KeyTrafficBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(KeyTraffic::VT_KEY, key);
+  }
+  #[inline]
+  pub fn add_messages(&mut self, messages: u64) {
+    self.fbb_.push_slot::<u64>(KeyTraffic::VT_MESSAGES, messages, 0);
+  }
+  #[inline]
+  pub fn add_bytes(&mut self, bytes: u64) {
+    self.fbb_.push_slot::<u64>(KeyTraffic::VT_BYTES, bytes, 0);
+  }
+  #[inline]
+  pub fn add_messages_per_s(&mut self, messages_per_s: f32) {
+    self.fbb_.push_slot::<f32>(KeyTraffic::VT_MESSAGES_PER_S, messages_per_s, 0.0);
+  }
+  #[inline]
+  pub fn add_bytes_per_s(&mut self, bytes_per_s: f32) {
+    self.fbb_.push_slot::<f32>(KeyTraffic::VT_BYTES_PER_S, bytes_per_s, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> KeyTrafficBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    KeyTrafficBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<KeyTraffic<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for KeyTraffic<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("KeyTraffic");
+      ds.field("key", &self.key());
+      ds.field("messages", &self.messages());
+      ds.field("bytes", &self.bytes());
+      ds.field("messages_per_s", &self.messages_per_s());
+      ds.field("bytes_per_s", &self.bytes_per_s());
+      ds.finish()
+  }
+}
+pub enum BusStatsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct BusStats<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for BusStats<'a> {
+  type Inner = BusStats<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> BusStats<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_WINDOW_MS: flatbuffers::VOffsetT = 8;
+  pub const VT_KEYS: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    BusStats { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args BusStatsArgs<'args>
+  ) -> flatbuffers::WIPOffset<BusStats<'bldr>> {
+    let mut builder = BusStatsBuilder::new(_fbb);
+    builder.add_window_ms(args.window_ms);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.keys { builder.add_keys(x); }
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(BusStats::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(BusStats::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn window_ms(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(BusStats::VT_WINDOW_MS, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn keys(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyTraffic<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyTraffic>>>>(BusStats::VT_KEYS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for BusStats<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<u64>("window_ms", Self::VT_WINDOW_MS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<KeyTraffic>>>>("keys", Self::VT_KEYS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct BusStatsArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub window_ms: u64,
+    pub keys: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyTraffic<'a>>>>>,
+}
+impl<'a> Default for BusStatsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    BusStatsArgs {
+      timestamp_us: 0,
+      node: None,
+      window_ms: 0,
+      keys: None,
+    }
+  }
+}
+
+pub struct BusStatsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> BusStatsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(BusStats::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(BusStats::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_window_ms(&mut self, window_ms: u64) {
+    self.fbb_.push_slot::<u64>(BusStats::VT_WINDOW_MS, window_ms, 0);
+  }
+  #[inline]
+  pub fn add_keys(&mut self, keys: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<KeyTraffic<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(BusStats::VT_KEYS, keys);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> BusStatsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    BusStatsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<BusStats<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for BusStats<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("BusStats");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("window_ms", &self.window_ms());
+      ds.field("keys", &self.keys());
+      ds.finish()
+  }
+}
+pub enum DeadlineOverrunOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct DeadlineOverrun<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for DeadlineOverrun<'a> {
+  type Inner = DeadlineOverrun<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> DeadlineOverrun<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_TIMER: flatbuffers::VOffsetT = 8;
+  pub const VT_PERIOD_US: flatbuffers::VOffsetT = 10;
+  pub const VT_RESPONSE_US: flatbuffers::VOffsetT = 12;
+  pub const VT_STEP_US: flatbuffers::VOffsetT = 14;
+  pub const VT_OVERRUNS: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    DeadlineOverrun { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args DeadlineOverrunArgs<'args>
+  ) -> flatbuffers::WIPOffset<DeadlineOverrun<'bldr>> {
+    let mut builder = DeadlineOverrunBuilder::new(_fbb);
+    builder.add_overruns(args.overruns);
+    builder.add_step_us(args.step_us);
+    builder.add_response_us(args.response_us);
+    builder.add_period_us(args.period_us);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_timer(args.timer);
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DeadlineOverrun::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DeadlineOverrun::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn timer(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(DeadlineOverrun::VT_TIMER, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn period_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DeadlineOverrun::VT_PERIOD_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn response_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DeadlineOverrun::VT_RESPONSE_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn step_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DeadlineOverrun::VT_STEP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn overruns(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DeadlineOverrun::VT_OVERRUNS, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for DeadlineOverrun<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<u32>("timer", Self::VT_TIMER, false)?
+     .visit_field::<u64>("period_us", Self::VT_PERIOD_US, false)?
+     .visit_field::<u64>("response_us", Self::VT_RESPONSE_US, false)?
+     .visit_field::<u64>("step_us", Self::VT_STEP_US, false)?
+     .visit_field::<u64>("overruns", Self::VT_OVERRUNS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct DeadlineOverrunArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub timer: u32,
+    pub period_us: u64,
+    pub response_us: u64,
+    pub step_us: u64,
+    pub overruns: u64,
+}
+impl<'a> Default for DeadlineOverrunArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    DeadlineOverrunArgs {
+      timestamp_us: 0,
+      node: None,
+      timer: 0,
+      period_us: 0,
+      response_us: 0,
+      step_us: 0,
+      overruns: 0,
+    }
+  }
+}
+
+pub struct DeadlineOverrunBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> DeadlineOverrunBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(DeadlineOverrun::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DeadlineOverrun::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_timer(&mut self, timer: u32) {
+    self.fbb_.push_slot::<u32>(DeadlineOverrun::VT_TIMER, timer, 0);
+  }
+  #[inline]
+  pub fn add_period_us(&mut self, period_us: u64) {
+    self.fbb_.push_slot::<u64>(DeadlineOverrun::VT_PERIOD_US, period_us, 0);
+  }
+  #[inline]
+  pub fn add_response_us(&mut self, response_us: u64) {
+    self.fbb_.push_slot::<u64>(DeadlineOverrun::VT_RESPONSE_US, response_us, 0);
+  }
+  #[inline]
+  pub fn add_step_us(&mut self, step_us: u64) {
+    self.fbb_.push_slot::<u64>(DeadlineOverrun::VT_STEP_US, step_us, 0);
+  }
+  #[inline]
+  pub fn add_overruns(&mut self, overruns: u64) {
+    self.fbb_.push_slot::<u64>(DeadlineOverrun::VT_OVERRUNS, overruns, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> DeadlineOverrunBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    DeadlineOverrunBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<DeadlineOverrun<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for DeadlineOverrun<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("DeadlineOverrun");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("timer", &self.timer());
+      ds.field("period_us", &self.period_us());
+      ds.field("response_us", &self.response_us());
+      ds.field("step_us", &self.step_us());
+      ds.field("overruns", &self.overruns());
+      ds.finish()
+  }
+}
+pub enum SupervisorEventOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct SupervisorEvent<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for SupervisorEvent<'a> {
+  type Inner = SupervisorEvent<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> SupervisorEvent<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_ACTION: flatbuffers::VOffsetT = 8;
+  pub const VT_PID: flatbuffers::VOffsetT = 10;
+  pub const VT_RESTARTS: flatbuffers::VOffsetT = 12;
+  pub const VT_DETAIL: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    SupervisorEvent { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args SupervisorEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<SupervisorEvent<'bldr>> {
+    let mut builder = SupervisorEventBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.detail { builder.add_detail(x); }
+    builder.add_restarts(args.restarts);
+    builder.add_pid(args.pid);
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.add_action(args.action);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(SupervisorEvent::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SupervisorEvent::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn action(&self) -> SupervisorAction {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<SupervisorAction>(SupervisorEvent::VT_ACTION, Some(SupervisorAction::Started)).unwrap()}
+  }
+  #[inline]
+  pub fn pid(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(SupervisorEvent::VT_PID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn restarts(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(SupervisorEvent::VT_RESTARTS, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn detail(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SupervisorEvent::VT_DETAIL, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for SupervisorEvent<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<SupervisorAction>("action", Self::VT_ACTION, false)?
+     .visit_field::<u32>("pid", Self::VT_PID, false)?
+     .visit_field::<u32>("restarts", Self::VT_RESTARTS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("detail", Self::VT_DETAIL, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct SupervisorEventArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub action: SupervisorAction,
+    pub pid: u32,
+    pub restarts: u32,
+    pub detail: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for SupervisorEventArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    SupervisorEventArgs {
+      timestamp_us: 0,
+      node: None,
+      action: SupervisorAction::Started,
+      pid: 0,
+      restarts: 0,
+      detail: None,
+    }
+  }
+}
+
+pub struct SupervisorEventBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> SupervisorEventBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(SupervisorEvent::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SupervisorEvent::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_action(&mut self, action: SupervisorAction) {
+    self.fbb_.push_slot::<SupervisorAction>(SupervisorEvent::VT_ACTION, action, SupervisorAction::Started);
+  }
+  #[inline]
+  pub fn add_pid(&mut self, pid: u32) {
+    self.fbb_.push_slot::<u32>(SupervisorEvent::VT_PID, pid, 0);
+  }
+  #[inline]
+  pub fn add_restarts(&mut self, restarts: u32) {
+    self.fbb_.push_slot::<u32>(SupervisorEvent::VT_RESTARTS, restarts, 0);
+  }
+  #[inline]
+  pub fn add_detail(&mut self, detail: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SupervisorEvent::VT_DETAIL, detail);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> SupervisorEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    SupervisorEventBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<SupervisorEvent<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for SupervisorEvent<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("SupervisorEvent");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("action", &self.action());
+      ds.field("pid", &self.pid());
+      ds.field("restarts", &self.restarts());
+      ds.field("detail", &self.detail());
+      ds.finish()
+  }
+}
+pub enum AlarmOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Alarm<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Alarm<'a> {
+  type Inner = Alarm<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Alarm<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_SOURCE: flatbuffers::VOffsetT = 6;
+  pub const VT_CHANNEL: flatbuffers::VOffsetT = 8;
+  pub const VT_KIND: flatbuffers::VOffsetT = 10;
+  pub const VT_SEVERITY: flatbuffers::VOffsetT = 12;
+  pub const VT_ACTIVE: flatbuffers::VOffsetT = 14;
+  pub const VT_VALUE: flatbuffers::VOffsetT = 16;
+  pub const VT_LIMIT: flatbuffers::VOffsetT = 18;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Alarm { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args AlarmArgs<'args>
+  ) -> flatbuffers::WIPOffset<Alarm<'bldr>> {
+    let mut builder = AlarmBuilder::new(_fbb);
+    builder.add_limit(args.limit);
+    builder.add_value(args.value);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.channel { builder.add_channel(x); }
+    if let Some(x) = args.source { builder.add_source(x); }
+    builder.add_active(args.active);
+    builder.add_severity(args.severity);
+    builder.add_kind(args.kind);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Alarm::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn source(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Alarm::VT_SOURCE, None)}
+  }
+  #[inline]
+  pub fn channel(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Alarm::VT_CHANNEL, None)}
+  }
+  #[inline]
+  pub fn kind(&self) -> AlarmKind {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<AlarmKind>(Alarm::VT_KIND, Some(AlarmKind::BelowMin)).unwrap()}
+  }
+  #[inline]
+  pub fn severity(&self) -> AlarmSeverity {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<AlarmSeverity>(Alarm::VT_SEVERITY, Some(AlarmSeverity::Info)).unwrap()}
+  }
+  #[inline]
+  pub fn active(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(Alarm::VT_ACTIVE, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn value(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(Alarm::VT_VALUE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn limit(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(Alarm::VT_LIMIT, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Alarm<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source", Self::VT_SOURCE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("channel", Self::VT_CHANNEL, false)?
+     .visit_field::<AlarmKind>("kind", Self::VT_KIND, false)?
+     .visit_field::<AlarmSeverity>("severity", Self::VT_SEVERITY, false)?
+     .visit_field::<bool>("active", Self::VT_ACTIVE, false)?
+     .visit_field::<f64>("value", Self::VT_VALUE, false)?
+     .visit_field::<f64>("limit", Self::VT_LIMIT, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct AlarmArgs<'a> {
+    pub timestamp_us: u64,
+    pub source: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub channel: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub kind: AlarmKind,
+    pub severity: AlarmSeverity,
+    pub active: bool,
+    pub value: f64,
+    pub limit: f64,
+}
+impl<'a> Default for AlarmArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    AlarmArgs {
+      timestamp_us: 0,
+      source: None,
+      channel: None,
+      kind: AlarmKind::BelowMin,
+      severity: AlarmSeverity::Info,
+      active: false,
+      value: 0.0,
+      limit: 0.0,
+    }
+  }
+}
+
+pub struct AlarmBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> AlarmBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(Alarm::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_source(&mut self, source: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Alarm::VT_SOURCE, source);
+  }
+  #[inline]
+  pub fn add_channel(&mut self, channel: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Alarm::VT_CHANNEL, channel);
+  }
+  #[inline]
+  pub fn add_kind(&mut self, kind: AlarmKind) {
+    self.fbb_.push_slot::<AlarmKind>(Alarm::VT_KIND, kind, AlarmKind::BelowMin);
+  }
+  #[inline]
+  pub fn add_severity(&mut self, severity: AlarmSeverity) {
+    self.fbb_.push_slot::<AlarmSeverity>(Alarm::VT_SEVERITY, severity, AlarmSeverity::Info);
+  }
+  #[inline]
+  pub fn add_active(&mut self, active: bool) {
+    self.fbb_.push_slot::<bool>(Alarm::VT_ACTIVE, active, false);
+  }
+  #[inline]
+  pub fn add_value(&mut self, value: f64) {
+    self.fbb_.push_slot::<f64>(Alarm::VT_VALUE, value, 0.0);
+  }
+  #[inline]
+  pub fn add_limit(&mut self, limit: f64) {
+    self.fbb_.push_slot::<f64>(Alarm::VT_LIMIT, limit, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> AlarmBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    AlarmBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Alarm<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Alarm<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Alarm");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("source", &self.source());
+      ds.field("channel", &self.channel());
+      ds.field("kind", &self.kind());
+      ds.field("severity", &self.severity());
+      ds.field("active", &self.active());
+      ds.field("value", &self.value());
+      ds.field("limit", &self.limit());
+      ds.finish()
+  }
+}
+pub enum VotingStatusOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct VotingStatus<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for VotingStatus<'a> {
+  type Inner = VotingStatus<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> VotingStatus<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_EXCLUDED: flatbuffers::VOffsetT = 6;
+  pub const VT_DISAGREEING: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    VotingStatus { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args VotingStatusArgs<'args>
+  ) -> flatbuffers::WIPOffset<VotingStatus<'bldr>> {
+    let mut builder = VotingStatusBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.disagreeing { builder.add_disagreeing(x); }
+    if let Some(x) = args.excluded { builder.add_excluded(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(VotingStatus::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn excluded(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(VotingStatus::VT_EXCLUDED, None)}
+  }
+  #[inline]
+  pub fn disagreeing(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(VotingStatus::VT_DISAGREEING, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for VotingStatus<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("excluded", Self::VT_EXCLUDED, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("disagreeing", Self::VT_DISAGREEING, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct VotingStatusArgs<'a> {
+    pub timestamp_us: u64,
+    pub excluded: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+    pub disagreeing: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+}
+impl<'a> Default for VotingStatusArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    VotingStatusArgs {
+      timestamp_us: 0,
+      excluded: None,
+      disagreeing: None,
+    }
+  }
+}
+
+pub struct VotingStatusBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> VotingStatusBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(VotingStatus::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_excluded(&mut self, excluded: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(VotingStatus::VT_EXCLUDED, excluded);
+  }
+  #[inline]
+  pub fn add_disagreeing(&mut self, disagreeing: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(VotingStatus::VT_DISAGREEING, disagreeing);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> VotingStatusBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    VotingStatusBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<VotingStatus<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for VotingStatus<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("VotingStatus");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("excluded", &self.excluded());
+      ds.field("disagreeing", &self.disagreeing());
+      ds.finish()
+  }
+}
+pub enum ActiveSensorOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ActiveSensor<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ActiveSensor<'a> {
+  type Inner = ActiveSensor<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ActiveSensor<'a> {
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_KIND: flatbuffers::VOffsetT = 6;
+  pub const VT_INDEX: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ActiveSensor { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ActiveSensorArgs<'args>
+  ) -> flatbuffers::WIPOffset<ActiveSensor<'bldr>> {
+    let mut builder = ActiveSensorBuilder::new(_fbb);
+    builder.add_index(args.index);
+    if let Some(x) = args.kind { builder.add_kind(x); }
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn key(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ActiveSensor::VT_KEY, None)}
+  }
+  #[inline]
+  pub fn kind(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ActiveSensor::VT_KIND, None)}
+  }
+  #[inline]
+  pub fn index(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(ActiveSensor::VT_INDEX, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ActiveSensor<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("kind", Self::VT_KIND, false)?
+     .visit_field::<u32>("index", Self::VT_INDEX, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ActiveSensorArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub kind: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub index: u32,
+}
+impl<'a> Default for ActiveSensorArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ActiveSensorArgs {
+      key: None,
+      kind: None,
+      index: 0,
+    }
+  }
+}
+
+pub struct ActiveSensorBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ActiveSensorBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ActiveSensor::VT_KEY, key);
+  }
+  #[inline]
+  pub fn add_kind(&mut self, kind: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ActiveSensor::VT_KIND, kind);
+  }
+  #[inline]
+  pub fn add_index(&mut self, index: u32) {
+    self.fbb_.push_slot::<u32>(ActiveSensor::VT_INDEX, index, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ActiveSensorBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ActiveSensorBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ActiveSensor<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ActiveSensor<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ActiveSensor");
+      ds.field("key", &self.key());
+      ds.field("kind", &self.kind());
+      ds.field("index", &self.index());
+      ds.finish()
+  }
+}
+pub enum ActiveSensorsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ActiveSensors<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ActiveSensors<'a> {
+  type Inner = ActiveSensors<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ActiveSensors<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_SENSORS: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ActiveSensors { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ActiveSensorsArgs<'args>
+  ) -> flatbuffers::WIPOffset<ActiveSensors<'bldr>> {
+    let mut builder = ActiveSensorsBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.sensors { builder.add_sensors(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ActiveSensors::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn sensors(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ActiveSensor<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ActiveSensor>>>>(ActiveSensors::VT_SENSORS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for ActiveSensors<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<ActiveSensor>>>>("sensors", Self::VT_SENSORS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ActiveSensorsArgs<'a> {
+    pub timestamp_us: u64,
+    pub sensors: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ActiveSensor<'a>>>>>,
+}
+impl<'a> Default for ActiveSensorsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ActiveSensorsArgs {
+      timestamp_us: 0,
+      sensors: None,
+    }
+  }
+}
+
+pub struct ActiveSensorsBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ActiveSensorsBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(ActiveSensors::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_sensors(&mut self, sensors: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<ActiveSensor<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ActiveSensors::VT_SENSORS, sensors);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ActiveSensorsBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ActiveSensorsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ActiveSensors<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ActiveSensors<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ActiveSensors");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("sensors", &self.sensors());
+      ds.finish()
+  }
+}
+pub enum FaultCommandOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct FaultCommand<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FaultCommand<'a> {
+  type Inner = FaultCommand<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> FaultCommand<'a> {
+  pub const VT_TARGET: flatbuffers::VOffsetT = 4;
+  pub const VT_CLEAR: flatbuffers::VOffsetT = 6;
+  pub const VT_KIND: flatbuffers::VOffsetT = 8;
+  pub const VT_CHANNEL: flatbuffers::VOffsetT = 10;
+  pub const VT_MAGNITUDE: flatbuffers::VOffsetT = 12;
+  pub const VT_PROBABILITY: flatbuffers::VOffsetT = 14;
+  pub const VT_DURATION_S: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    FaultCommand { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args FaultCommandArgs<'args>
+  ) -> flatbuffers::WIPOffset<FaultCommand<'bldr>> {
+    let mut builder = FaultCommandBuilder::new(_fbb);
+    builder.add_duration_s(args.duration_s);
+    builder.add_probability(args.probability);
+    builder.add_magnitude(args.magnitude);
+    if let Some(x) = args.channel { builder.add_channel(x); }
+    if let Some(x) = args.target { builder.add_target(x); }
+    builder.add_kind(args.kind);
+    builder.add_clear(args.clear);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn target(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FaultCommand::VT_TARGET, None)}
+  }
+  #[inline]
+  pub fn clear(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(FaultCommand::VT_CLEAR, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn kind(&self) -> FaultKind {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<FaultKind>(FaultCommand::VT_KIND, Some(FaultKind::Bias)).unwrap()}
+  }
+  #[inline]
+  pub fn channel(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(FaultCommand::VT_CHANNEL, None)}
+  }
+  #[inline]
+  pub fn magnitude(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(FaultCommand::VT_MAGNITUDE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn probability(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(FaultCommand::VT_PROBABILITY, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn duration_s(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(FaultCommand::VT_DURATION_S, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for FaultCommand<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("target", Self::VT_TARGET, false)?
+     .visit_field::<bool>("clear", Self::VT_CLEAR, false)?
+     .visit_field::<FaultKind>("kind", Self::VT_KIND, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("channel", Self::VT_CHANNEL, false)?
+     .visit_field::<f64>("magnitude", Self::VT_MAGNITUDE, false)?
+     .visit_field::<f64>("probability", Self::VT_PROBABILITY, false)?
+     .visit_field::<f64>("duration_s", Self::VT_DURATION_S, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct FaultCommandArgs<'a> {
+    pub target: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub clear: bool,
+    pub kind: FaultKind,
+    pub channel: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub magnitude: f64,
+    pub probability: f64,
+    pub duration_s: f64,
+}
+impl<'a> Default for FaultCommandArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    FaultCommandArgs {
+      target: None,
+      clear: false,
+      kind: FaultKind::Bias,
+      channel: None,
+      magnitude: 0.0,
+      probability: 0.0,
+      duration_s: 0.0,
+    }
+  }
+}
+
+pub struct FaultCommandBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FaultCommandBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_target(&mut self, target: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FaultCommand::VT_TARGET, target);
+  }
+  #[inline]
+  pub fn add_clear(&mut self, clear: bool) {
+    self.fbb_.push_slot::<bool>(FaultCommand::VT_CLEAR, clear, false);
+  }
+  #[inline]
+  pub fn add_kind(&mut self, kind: FaultKind) {
+    self.fbb_.push_slot::<FaultKind>(FaultCommand::VT_KIND, kind, FaultKind::Bias);
+  }
+  #[inline]
+  pub fn add_channel(&mut self, channel: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FaultCommand::VT_CHANNEL, channel);
+  }
+  #[inline]
+  pub fn add_magnitude(&mut self, magnitude: f64) {
+    self.fbb_.push_slot::<f64>(FaultCommand::VT_MAGNITUDE, magnitude, 0.0);
+  }
+  #[inline]
+  pub fn add_probability(&mut self, probability: f64) {
+    self.fbb_.push_slot::<f64>(FaultCommand::VT_PROBABILITY, probability, 0.0);
+  }
+  #[inline]
+  pub fn add_duration_s(&mut self, duration_s: f64) {
+    self.fbb_.push_slot::<f64>(FaultCommand::VT_DURATION_S, duration_s, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FaultCommandBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FaultCommandBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FaultCommand<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for FaultCommand<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("FaultCommand");
+      ds.field("target", &self.target());
+      ds.field("clear", &self.clear());
+      ds.field("kind", &self.kind());
+      ds.field("channel", &self.channel());
+      ds.field("magnitude", &self.magnitude());
+      ds.field("probability", &self.probability());
+      ds.field("duration_s", &self.duration_s());
+      ds.finish()
+  }
+}
+pub enum SensorStatusOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct SensorStatus<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for SensorStatus<'a> {
+  type Inner = SensorStatus<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> SensorStatus<'a> {
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_HEALTH: flatbuffers::VOffsetT = 6;
+  pub const VT_CAUSES: flatbuffers::VOffsetT = 8;
+  pub const VT_SINCE_S: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    SensorStatus { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args SensorStatusArgs<'args>
+  ) -> flatbuffers::WIPOffset<SensorStatus<'bldr>> {
+    let mut builder = SensorStatusBuilder::new(_fbb);
+    builder.add_since_s(args.since_s);
+    if let Some(x) = args.causes { builder.add_causes(x); }
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.add_health(args.health);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn key(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SensorStatus::VT_KEY, None)}
+  }
+  #[inline]
+  pub fn health(&self) -> SensorHealth {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<SensorHealth>(SensorStatus::VT_HEALTH, Some(SensorHealth::Healthy)).unwrap()}
+  }
+  #[inline]
+  pub fn causes(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(SensorStatus::VT_CAUSES, None)}
+  }
+  #[inline]
+  pub fn since_s(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(SensorStatus::VT_SINCE_S, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for SensorStatus<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, false)?
+     .visit_field::<SensorHealth>("health", Self::VT_HEALTH, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("causes", Self::VT_CAUSES, false)?
+     .visit_field::<f64>("since_s", Self::VT_SINCE_S, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct SensorStatusArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub health: SensorHealth,
+    pub causes: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+    pub since_s: f64,
+}
+impl<'a> Default for SensorStatusArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    SensorStatusArgs {
+      key: None,
+      health: SensorHealth::Healthy,
+      causes: None,
+      since_s: 0.0,
+    }
+  }
+}
+
+pub struct SensorStatusBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> SensorStatusBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SensorStatus::VT_KEY, key);
+  }
+  #[inline]
+  pub fn add_health(&mut self, health: SensorHealth) {
+    self.fbb_.push_slot::<SensorHealth>(SensorStatus::VT_HEALTH, health, SensorHealth::Healthy);
+  }
+  #[inline]
+  pub fn add_causes(&mut self, causes: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SensorStatus::VT_CAUSES, causes);
+  }
+  #[inline]
+  pub fn add_since_s(&mut self, since_s: f64) {
+    self.fbb_.push_slot::<f64>(SensorStatus::VT_SINCE_S, since_s, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> SensorStatusBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    SensorStatusBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<SensorStatus<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for SensorStatus<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("SensorStatus");
+      ds.field("key", &self.key());
+      ds.field("health", &self.health());
+      ds.field("causes", &self.causes());
+      ds.field("since_s", &self.since_s());
+      ds.finish()
+  }
+}
+pub enum FdirStatusOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct FdirStatus<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FdirStatus<'a> {
+  type Inner = FdirStatus<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> FdirStatus<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_SENSORS: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    FdirStatus { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args FdirStatusArgs<'args>
+  ) -> flatbuffers::WIPOffset<FdirStatus<'bldr>> {
+    let mut builder = FdirStatusBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.sensors { builder.add_sensors(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(FdirStatus::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn sensors(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SensorStatus<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SensorStatus>>>>(FdirStatus::VT_SENSORS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for FdirStatus<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<SensorStatus>>>>("sensors", Self::VT_SENSORS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct FdirStatusArgs<'a> {
+    pub timestamp_us: u64,
+    pub sensors: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SensorStatus<'a>>>>>,
+}
+impl<'a> Default for FdirStatusArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    FdirStatusArgs {
+      timestamp_us: 0,
+      sensors: None,
+    }
+  }
+}
+
+pub struct FdirStatusBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FdirStatusBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(FdirStatus::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_sensors(&mut self, sensors: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<SensorStatus<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(FdirStatus::VT_SENSORS, sensors);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FdirStatusBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FdirStatusBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FdirStatus<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for FdirStatus<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("FdirStatus");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("sensors", &self.sensors());
+      ds.finish()
+  }
+}
+pub enum CommandArgOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CommandArg<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CommandArg<'a> {
+  type Inner = CommandArg<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CommandArg<'a> {
+  pub const VT_NAME: flatbuffers::VOffsetT = 4;
+  pub const VT_VALUE: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CommandArg { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args CommandArgArgs<'args>
+  ) -> flatbuffers::WIPOffset<CommandArg<'bldr>> {
+    let mut builder = CommandArgBuilder::new(_fbb);
+    if let Some(x) = args.value { builder.add_value(x); }
+    if let Some(x) = args.name { builder.add_name(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn name(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CommandArg::VT_NAME, None)}
+  }
+  #[inline]
+  pub fn value(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CommandArg::VT_VALUE, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CommandArg<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("value", Self::VT_VALUE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CommandArgArgs<'a> {
+    pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub value: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for CommandArgArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CommandArgArgs {
+      name: None,
+      value: None,
+    }
+  }
+}
+
+pub struct CommandArgBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CommandArgBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CommandArg::VT_NAME, name);
+  }
+  #[inline]
+  pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CommandArg::VT_VALUE, value);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CommandArgBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CommandArgBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CommandArg<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CommandArg<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CommandArg");
+      ds.field("name", &self.name());
+      ds.field("value", &self.value());
+      ds.finish()
+  }
+}
+pub enum CommandOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Command<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Command<'a> {
+  type Inner = Command<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Command<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_SEQUENCE: flatbuffers::VOffsetT = 6;
+  pub const VT_ARGS: flatbuffers::VOffsetT = 8;
+  pub const VT_SENDER: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Command { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args CommandArgs<'args>
+  ) -> flatbuffers::WIPOffset<Command<'bldr>> {
+    let mut builder = CommandBuilder::new(_fbb);
+    builder.add_sequence(args.sequence);
+    if let Some(x) = args.sender { builder.add_sender(x); }
+    if let Some(x) = args.args { builder.add_args(x); }
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Command::VT_ID, None)}
+  }
+  #[inline]
+  pub fn sequence(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Command::VT_SEQUENCE, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn args(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CommandArg<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CommandArg>>>>(Command::VT_ARGS, None)}
+  }
+  #[inline]
+  pub fn sender(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Command::VT_SENDER, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Command<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, false)?
+     .visit_field::<u64>("sequence", Self::VT_SEQUENCE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CommandArg>>>>("args", Self::VT_ARGS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("sender", Self::VT_SENDER, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CommandArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub sequence: u64,
+    pub args: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CommandArg<'a>>>>>,
+    pub sender: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for CommandArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CommandArgs {
+      id: None,
+      sequence: 0,
+      args: None,
+      sender: None,
+    }
+  }
+}
+
+pub struct CommandBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CommandBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Command::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_sequence(&mut self, sequence: u64) {
+    self.fbb_.push_slot::<u64>(Command::VT_SEQUENCE, sequence, 0);
+  }
+  #[inline]
+  pub fn add_args(&mut self, args: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CommandArg<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Command::VT_ARGS, args);
+  }
+  #[inline]
+  pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Command::VT_SENDER, sender);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CommandBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CommandBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Command<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Command<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Command");
+      ds.field("id", &self.id());
+      ds.field("sequence", &self.sequence());
+      ds.field("args", &self.args());
+      ds.field("sender", &self.sender());
+      ds.finish()
+  }
+}
+pub enum CommandReplyOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CommandReply<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CommandReply<'a> {
+  type Inner = CommandReply<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CommandReply<'a> {
+  pub const VT_SEQUENCE: flatbuffers::VOffsetT = 4;
+  pub const VT_STATUS: flatbuffers::VOffsetT = 6;
+  pub const VT_REASON: flatbuffers::VOffsetT = 8;
+  pub const VT_MESSAGE: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CommandReply { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args CommandReplyArgs<'args>
+  ) -> flatbuffers::WIPOffset<CommandReply<'bldr>> {
+    let mut builder = CommandReplyBuilder::new(_fbb);
+    builder.add_sequence(args.sequence);
+    if let Some(x) = args.message { builder.add_message(x); }
+    builder.add_reason(args.reason);
+    builder.add_status(args.status);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn sequence(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(CommandReply::VT_SEQUENCE, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn status(&self) -> CommandStatus {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<CommandStatus>(CommandReply::VT_STATUS, Some(CommandStatus::Ack)).unwrap()}
+  }
+  #[inline]
+  pub fn reason(&self) -> NackReason {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<NackReason>(CommandReply::VT_REASON, Some(NackReason::None)).unwrap()}
+  }
+  #[inline]
+  pub fn message(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CommandReply::VT_MESSAGE, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CommandReply<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("sequence", Self::VT_SEQUENCE, false)?
+     .visit_field::<CommandStatus>("status", Self::VT_STATUS, false)?
+     .visit_field::<NackReason>("reason", Self::VT_REASON, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("message", Self::VT_MESSAGE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CommandReplyArgs<'a> {
+    pub sequence: u64,
+    pub status: CommandStatus,
+    pub reason: NackReason,
+    pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for CommandReplyArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CommandReplyArgs {
+      sequence: 0,
+      status: CommandStatus::Ack,
+      reason: NackReason::None,
+      message: None,
+    }
+  }
+}
+
+pub struct CommandReplyBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CommandReplyBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_sequence(&mut self, sequence: u64) {
+    self.fbb_.push_slot::<u64>(CommandReply::VT_SEQUENCE, sequence, 0);
+  }
+  #[inline]
+  pub fn add_status(&mut self, status: CommandStatus) {
+    self.fbb_.push_slot::<CommandStatus>(CommandReply::VT_STATUS, status, CommandStatus::Ack);
+  }
+  #[inline]
+  pub fn add_reason(&mut self, reason: NackReason) {
+    self.fbb_.push_slot::<NackReason>(CommandReply::VT_REASON, reason, NackReason::None);
+  }
+  #[inline]
+  pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CommandReply::VT_MESSAGE, message);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CommandReplyBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CommandReplyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CommandReply<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CommandReply<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CommandReply");
+      ds.field("sequence", &self.sequence());
+      ds.field("status", &self.status());
+      ds.field("reason", &self.reason());
+      ds.field("message", &self.message());
+      ds.finish()
+  }
+}
+pub enum ArmingEventOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ArmingEvent<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ArmingEvent<'a> {
+  type Inner = ArmingEvent<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ArmingEvent<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_COMMAND: flatbuffers::VOffsetT = 8;
+  pub const VT_TRANSITION: flatbuffers::VOffsetT = 10;
+  pub const VT_WINDOW_S: flatbuffers::VOffsetT = 12;
+  pub const VT_SENDER: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ArmingEvent { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ArmingEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<ArmingEvent<'bldr>> {
+    let mut builder = ArmingEventBuilder::new(_fbb);
+    builder.add_window_s(args.window_s);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.sender { builder.add_sender(x); }
+    if let Some(x) = args.command { builder.add_command(x); }
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.add_transition(args.transition);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ArmingEvent::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ArmingEvent::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn command(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ArmingEvent::VT_COMMAND, None)}
+  }
+  #[inline]
+  pub fn transition(&self) -> ArmingTransition {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ArmingTransition>(ArmingEvent::VT_TRANSITION, Some(ArmingTransition::Armed)).unwrap()}
+  }
+  #[inline]
+  pub fn window_s(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ArmingEvent::VT_WINDOW_S, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn sender(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ArmingEvent::VT_SENDER, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for ArmingEvent<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("command", Self::VT_COMMAND, false)?
+     .visit_field::<ArmingTransition>("transition", Self::VT_TRANSITION, false)?
+     .visit_field::<f64>("window_s", Self::VT_WINDOW_S, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("sender", Self::VT_SENDER, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ArmingEventArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub command: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub transition: ArmingTransition,
+    pub window_s: f64,
+    pub sender: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ArmingEventArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ArmingEventArgs {
+      timestamp_us: 0,
+      node: None,
+      command: None,
+      transition: ArmingTransition::Armed,
+      window_s: 0.0,
+      sender: None,
+    }
+  }
+}
+
+pub struct ArmingEventBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ArmingEventBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(ArmingEvent::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ArmingEvent::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_command(&mut self, command: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ArmingEvent::VT_COMMAND, command);
+  }
+  #[inline]
+  pub fn add_transition(&mut self, transition: ArmingTransition) {
+    self.fbb_.push_slot::<ArmingTransition>(ArmingEvent::VT_TRANSITION, transition, ArmingTransition::Armed);
+  }
+  #[inline]
+  pub fn add_window_s(&mut self, window_s: f64) {
+    self.fbb_.push_slot::<f64>(ArmingEvent::VT_WINDOW_S, window_s, 0.0);
+  }
+  #[inline]
+  pub fn add_sender(&mut self, sender: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ArmingEvent::VT_SENDER, sender);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ArmingEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ArmingEventBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ArmingEvent<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ArmingEvent<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ArmingEvent");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("command", &self.command());
+      ds.field("transition", &self.transition());
+      ds.field("window_s", &self.window_s());
+      ds.field("sender", &self.sender());
+      ds.finish()
+  }
+}
+pub enum ParameterOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Parameter<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Parameter<'a> {
+  type Inner = Parameter<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Parameter<'a> {
+  pub const VT_NODE: flatbuffers::VOffsetT = 4;
+  pub const VT_NAME: flatbuffers::VOffsetT = 6;
+  pub const VT_TYPE_: flatbuffers::VOffsetT = 8;
+  pub const VT_VALUE: flatbuffers::VOffsetT = 10;
+  pub const VT_DEFAULT_VALUE: flatbuffers::VOffsetT = 12;
+  pub const VT_MIN: flatbuffers::VOffsetT = 14;
+  pub const VT_MAX: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Parameter { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ParameterArgs<'args>
+  ) -> flatbuffers::WIPOffset<Parameter<'bldr>> {
+    let mut builder = ParameterBuilder::new(_fbb);
+    if let Some(x) = args.max { builder.add_max(x); }
+    if let Some(x) = args.min { builder.add_min(x); }
+    if let Some(x) = args.default_value { builder.add_default_value(x); }
+    if let Some(x) = args.value { builder.add_value(x); }
+    if let Some(x) = args.name { builder.add_name(x); }
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.add_type_(args.type_);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Parameter::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn name(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Parameter::VT_NAME, None)}
+  }
+  #[inline]
+  pub fn type_(&self) -> ParamType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ParamType>(Parameter::VT_TYPE_, Some(ParamType::Float)).unwrap()}
+  }
+  #[inline]
+  pub fn value(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Parameter::VT_VALUE, None)}
+  }
+  #[inline]
+  pub fn default_value(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Parameter::VT_DEFAULT_VALUE, None)}
+  }
+  #[inline]
+  pub fn min(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Parameter::VT_MIN, None)}
+  }
+  #[inline]
+  pub fn max(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Parameter::VT_MAX, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Parameter<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
+     .visit_field::<ParamType>("type_", Self::VT_TYPE_, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("value", Self::VT_VALUE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("default_value", Self::VT_DEFAULT_VALUE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("min", Self::VT_MIN, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("max", Self::VT_MAX, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ParameterArgs<'a> {
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub type_: ParamType,
+    pub value: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub default_value: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub min: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub max: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ParameterArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ParameterArgs {
+      node: None,
+      name: None,
+      type_: ParamType::Float,
+      value: None,
+      default_value: None,
+      min: None,
+      max: None,
+    }
+  }
+}
+
+pub struct ParameterBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ParameterBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Parameter::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Parameter::VT_NAME, name);
+  }
+  #[inline]
+  pub fn add_type_(&mut self, type_: ParamType) {
+    self.fbb_.push_slot::<ParamType>(Parameter::VT_TYPE_, type_, ParamType::Float);
+  }
+  #[inline]
+  pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Parameter::VT_VALUE, value);
+  }
+  #[inline]
+  pub fn add_default_value(&mut self, default_value: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Parameter::VT_DEFAULT_VALUE, default_value);
+  }
+  #[inline]
+  pub fn add_min(&mut self, min: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Parameter::VT_MIN, min);
+  }
+  #[inline]
+  pub fn add_max(&mut self, max: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Parameter::VT_MAX, max);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ParameterBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ParameterBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Parameter<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Parameter<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Parameter");
+      ds.field("node", &self.node());
+      ds.field("name", &self.name());
+      ds.field("type_", &self.type_());
+      ds.field("value", &self.value());
+      ds.field("default_value", &self.default_value());
+      ds.field("min", &self.min());
+      ds.field("max", &self.max());
+      ds.finish()
+  }
+}
+pub enum PhaseStateOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PhaseState<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PhaseState<'a> {
+  type Inner = PhaseState<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PhaseState<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_PHASE: flatbuffers::VOffsetT = 6;
+  pub const VT_PREVIOUS: flatbuffers::VOffsetT = 8;
+  pub const VT_SINCE_S: flatbuffers::VOffsetT = 10;
+  pub const VT_REASON: flatbuffers::VOffsetT = 12;
+  pub const VT_MAX_ALTITUDE: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PhaseState { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args PhaseStateArgs<'args>
+  ) -> flatbuffers::WIPOffset<PhaseState<'bldr>> {
+    let mut builder = PhaseStateBuilder::new(_fbb);
+    builder.add_since_s(args.since_s);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_max_altitude(args.max_altitude);
+    if let Some(x) = args.reason { builder.add_reason(x); }
+    builder.add_previous(args.previous);
+    builder.add_phase(args.phase);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PhaseState::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn phase(&self) -> FlightPhase {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<FlightPhase>(PhaseState::VT_PHASE, Some(FlightPhase::Pad)).unwrap()}
+  }
+  #[inline]
+  pub fn previous(&self) -> FlightPhase {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<FlightPhase>(PhaseState::VT_PREVIOUS, Some(FlightPhase::Pad)).unwrap()}
+  }
+  #[inline]
+  pub fn since_s(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(PhaseState::VT_SINCE_S, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn reason(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PhaseState::VT_REASON, None)}
+  }
+  #[inline]
+  pub fn max_altitude(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PhaseState::VT_MAX_ALTITUDE, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PhaseState<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<FlightPhase>("phase", Self::VT_PHASE, false)?
+     .visit_field::<FlightPhase>("previous", Self::VT_PREVIOUS, false)?
+     .visit_field::<f64>("since_s", Self::VT_SINCE_S, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("reason", Self::VT_REASON, false)?
+     .visit_field::<f32>("max_altitude", Self::VT_MAX_ALTITUDE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PhaseStateArgs<'a> {
+    pub timestamp_us: u64,
+    pub phase: FlightPhase,
+    pub previous: FlightPhase,
+    pub since_s: f64,
+    pub reason: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub max_altitude: f32,
+}
+impl<'a> Default for PhaseStateArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    PhaseStateArgs {
+      timestamp_us: 0,
+      phase: FlightPhase::Pad,
+      previous: FlightPhase::Pad,
+      since_s: 0.0,
+      reason: None,
+      max_altitude: 0.0,
+    }
+  }
+}
+
+pub struct PhaseStateBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> PhaseStateBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(PhaseState::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_phase(&mut self, phase: FlightPhase) {
+    self.fbb_.push_slot::<FlightPhase>(PhaseState::VT_PHASE, phase, FlightPhase::Pad);
+  }
+  #[inline]
+  pub fn add_previous(&mut self, previous: FlightPhase) {
+    self.fbb_.push_slot::<FlightPhase>(PhaseState::VT_PREVIOUS, previous, FlightPhase::Pad);
+  }
+  #[inline]
+  pub fn add_since_s(&mut self, since_s: f64) {
+    self.fbb_.push_slot::<f64>(PhaseState::VT_SINCE_S, since_s, 0.0);
+  }
+  #[inline]
+  pub fn add_reason(&mut self, reason: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PhaseState::VT_REASON, reason);
+  }
+  #[inline]
+  pub fn add_max_altitude(&mut self, max_altitude: f32) {
+    self.fbb_.push_slot::<f32>(PhaseState::VT_MAX_ALTITUDE, max_altitude, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> PhaseStateBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    PhaseStateBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PhaseState<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PhaseState<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PhaseState");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("phase", &self.phase());
+      ds.field("previous", &self.previous());
+      ds.field("since_s", &self.since_s());
+      ds.field("reason", &self.reason());
+      ds.field("max_altitude", &self.max_altitude());
+      ds.finish()
+  }
+}
+pub enum FlightEventOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct FlightEvent<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for FlightEvent<'a> {
+  type Inner = FlightEvent<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> FlightEvent<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_DETECTED_US: flatbuffers::VOffsetT = 6;
+  pub const VT_KIND: flatbuffers::VOffsetT = 8;
+  pub const VT_CONFIDENCE: flatbuffers::VOffsetT = 10;
+  pub const VT_ALTITUDE: flatbuffers::VOffsetT = 12;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    FlightEvent { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args FlightEventArgs
+  ) -> flatbuffers::WIPOffset<FlightEvent<'bldr>> {
+    let mut builder = FlightEventBuilder::new(_fbb);
+    builder.add_detected_us(args.detected_us);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_altitude(args.altitude);
+    builder.add_confidence(args.confidence);
+    builder.add_kind(args.kind);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(FlightEvent::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn detected_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(FlightEvent::VT_DETECTED_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn kind(&self) -> FlightEventKind {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<FlightEventKind>(FlightEvent::VT_KIND, Some(FlightEventKind::Launch)).unwrap()}
+  }
+  #[inline]
+  pub fn confidence(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(FlightEvent::VT_CONFIDENCE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn altitude(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(FlightEvent::VT_ALTITUDE, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for FlightEvent<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<u64>("detected_us", Self::VT_DETECTED_US, false)?
+     .visit_field::<FlightEventKind>("kind", Self::VT_KIND, false)?
+     .visit_field::<f32>("confidence", Self::VT_CONFIDENCE, false)?
+     .visit_field::<f32>("altitude", Self::VT_ALTITUDE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct FlightEventArgs {
+    pub timestamp_us: u64,
+    pub detected_us: u64,
+    pub kind: FlightEventKind,
+    pub confidence: f32,
+    pub altitude: f32,
+}
+impl<'a> Default for FlightEventArgs {
+  #[inline]
+  fn default() -> Self {
+    FlightEventArgs {
+      timestamp_us: 0,
+      detected_us: 0,
+      kind: FlightEventKind::Launch,
+      confidence: 0.0,
+      altitude: 0.0,
+    }
+  }
+}
+
+pub struct FlightEventBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> FlightEventBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(FlightEvent::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_detected_us(&mut self, detected_us: u64) {
+    self.fbb_.push_slot::<u64>(FlightEvent::VT_DETECTED_US, detected_us, 0);
+  }
+  #[inline]
+  pub fn add_kind(&mut self, kind: FlightEventKind) {
+    self.fbb_.push_slot::<FlightEventKind>(FlightEvent::VT_KIND, kind, FlightEventKind::Launch);
+  }
+  #[inline]
+  pub fn add_confidence(&mut self, confidence: f32) {
+    self.fbb_.push_slot::<f32>(FlightEvent::VT_CONFIDENCE, confidence, 0.0);
+  }
+  #[inline]
+  pub fn add_altitude(&mut self, altitude: f32) {
+    self.fbb_.push_slot::<f32>(FlightEvent::VT_ALTITUDE, altitude, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FlightEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    FlightEventBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<FlightEvent<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for FlightEvent<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("FlightEvent");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("detected_us", &self.detected_us());
+      ds.field("kind", &self.kind());
+      ds.field("confidence", &self.confidence());
+      ds.field("altitude", &self.altitude());
+      ds.finish()
+  }
+}
+pub enum ActuatorCommandOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ActuatorCommand<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ActuatorCommand<'a> {
+  type Inner = ActuatorCommand<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ActuatorCommand<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_POSITION: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ActuatorCommand { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ActuatorCommandArgs
+  ) -> flatbuffers::WIPOffset<ActuatorCommand<'bldr>> {
+    let mut builder = ActuatorCommandBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_position(args.position);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ActuatorCommand::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn position(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(ActuatorCommand::VT_POSITION, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ActuatorCommand<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<f32>("position", Self::VT_POSITION, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ActuatorCommandArgs {
+    pub timestamp_us: u64,
+    pub position: f32,
+}
+impl<'a> Default for ActuatorCommandArgs {
+  #[inline]
+  fn default() -> Self {
+    ActuatorCommandArgs {
+      timestamp_us: 0,
+      position: 0.0,
+    }
+  }
+}
+
+pub struct ActuatorCommandBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ActuatorCommandBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(ActuatorCommand::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_position(&mut self, position: f32) {
+    self.fbb_.push_slot::<f32>(ActuatorCommand::VT_POSITION, position, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ActuatorCommandBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ActuatorCommandBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ActuatorCommand<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ActuatorCommand<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ActuatorCommand");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("position", &self.position());
+      ds.finish()
+  }
+}
+pub enum ActuatorStateOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ActuatorState<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ActuatorState<'a> {
+  type Inner = ActuatorState<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ActuatorState<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_COMMANDED: flatbuffers::VOffsetT = 6;
+  pub const VT_TARGET: flatbuffers::VOffsetT = 8;
+  pub const VT_POSITION: flatbuffers::VOffsetT = 10;
+  pub const VT_LIMITED: flatbuffers::VOffsetT = 12;
+  pub const VT_STALE: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ActuatorState { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ActuatorStateArgs
+  ) -> flatbuffers::WIPOffset<ActuatorState<'bldr>> {
+    let mut builder = ActuatorStateBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    builder.add_position(args.position);
+    builder.add_target(args.target);
+    builder.add_commanded(args.commanded);
+    builder.add_stale(args.stale);
+    builder.add_limited(args.limited);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ActuatorState::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn commanded(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(ActuatorState::VT_COMMANDED, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn target(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(ActuatorState::VT_TARGET, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn position(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(ActuatorState::VT_POSITION, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn limited(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(ActuatorState::VT_LIMITED, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn stale(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(ActuatorState::VT_STALE, Some(false)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ActuatorState<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<f32>("commanded", Self::VT_COMMANDED, false)?
+     .visit_field::<f32>("target", Self::VT_TARGET, false)?
+     .visit_field::<f32>("position", Self::VT_POSITION, false)?
+     .visit_field::<bool>("limited", Self::VT_LIMITED, false)?
+     .visit_field::<bool>("stale", Self::VT_STALE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ActuatorStateArgs {
+    pub timestamp_us: u64,
+    pub commanded: f32,
+    pub target: f32,
+    pub position: f32,
+    pub limited: bool,
+    pub stale: bool,
+}
+impl<'a> Default for ActuatorStateArgs {
+  #[inline]
+  fn default() -> Self {
+    ActuatorStateArgs {
+      timestamp_us: 0,
+      commanded: 0.0,
+      target: 0.0,
+      position: 0.0,
+      limited: false,
+      stale: false,
+    }
+  }
+}
+
+pub struct ActuatorStateBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ActuatorStateBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(ActuatorState::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_commanded(&mut self, commanded: f32) {
+    self.fbb_.push_slot::<f32>(ActuatorState::VT_COMMANDED, commanded, 0.0);
+  }
+  #[inline]
+  pub fn add_target(&mut self, target: f32) {
+    self.fbb_.push_slot::<f32>(ActuatorState::VT_TARGET, target, 0.0);
+  }
+  #[inline]
+  pub fn add_position(&mut self, position: f32) {
+    self.fbb_.push_slot::<f32>(ActuatorState::VT_POSITION, position, 0.0);
+  }
+  #[inline]
+  pub fn add_limited(&mut self, limited: bool) {
+    self.fbb_.push_slot::<bool>(ActuatorState::VT_LIMITED, limited, false);
+  }
+  #[inline]
+  pub fn add_stale(&mut self, stale: bool) {
+    self.fbb_.push_slot::<bool>(ActuatorState::VT_STALE, stale, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ActuatorStateBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ActuatorStateBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ActuatorState<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ActuatorState<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ActuatorState");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("commanded", &self.commanded());
+      ds.field("target", &self.target());
+      ds.field("position", &self.position());
+      ds.field("limited", &self.limited());
+      ds.field("stale", &self.stale());
+      ds.finish()
+  }
+}
+pub enum LogFieldOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct LogField<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for LogField<'a> {
+  type Inner = LogField<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> LogField<'a> {
+  pub const VT_NAME: flatbuffers::VOffsetT = 4;
+  pub const VT_VALUE: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    LogField { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args LogFieldArgs<'args>
+  ) -> flatbuffers::WIPOffset<LogField<'bldr>> {
+    let mut builder = LogFieldBuilder::new(_fbb);
+    if let Some(x) = args.value { builder.add_value(x); }
+    if let Some(x) = args.name { builder.add_name(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn name(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LogField::VT_NAME, None)}
+  }
+  #[inline]
+  pub fn value(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LogField::VT_VALUE, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for LogField<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("value", Self::VT_VALUE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct LogFieldArgs<'a> {
+    pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub value: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for LogFieldArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    LogFieldArgs {
+      name: None,
+      value: None,
+    }
+  }
+}
+
+pub struct LogFieldBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> LogFieldBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogField::VT_NAME, name);
+  }
+  #[inline]
+  pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogField::VT_VALUE, value);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> LogFieldBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    LogFieldBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<LogField<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for LogField<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("LogField");
+      ds.field("name", &self.name());
+      ds.field("value", &self.value());
+      ds.finish()
+  }
+}
+pub enum LogRecordOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct LogRecord<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for LogRecord<'a> {
+  type Inner = LogRecord<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> LogRecord<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_LEVEL: flatbuffers::VOffsetT = 8;
+  pub const VT_TARGET: flatbuffers::VOffsetT = 10;
+  pub const VT_MESSAGE: flatbuffers::VOffsetT = 12;
+  pub const VT_SPANS: flatbuffers::VOffsetT = 14;
+  pub const VT_FIELDS: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    LogRecord { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args LogRecordArgs<'args>
+  ) -> flatbuffers::WIPOffset<LogRecord<'bldr>> {
+    let mut builder = LogRecordBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.fields { builder.add_fields(x); }
+    if let Some(x) = args.spans { builder.add_spans(x); }
+    if let Some(x) = args.message { builder.add_message(x); }
+    if let Some(x) = args.target { builder.add_target(x); }
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.add_level(args.level);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(LogRecord::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LogRecord::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn level(&self) -> LogLevel {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<LogLevel>(LogRecord::VT_LEVEL, Some(LogLevel::Trace)).unwrap()}
+  }
+  #[inline]
+  pub fn target(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LogRecord::VT_TARGET, None)}
+  }
+  #[inline]
+  pub fn message(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LogRecord::VT_MESSAGE, None)}
+  }
+  #[inline]
+  pub fn spans(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LogRecord::VT_SPANS, None)}
+  }
+  #[inline]
+  pub fn fields(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LogField<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LogField>>>>(LogRecord::VT_FIELDS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for LogRecord<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<LogLevel>("level", Self::VT_LEVEL, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("target", Self::VT_TARGET, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("message", Self::VT_MESSAGE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("spans", Self::VT_SPANS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<LogField>>>>("fields", Self::VT_FIELDS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct LogRecordArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub level: LogLevel,
+    pub target: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub spans: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub fields: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LogField<'a>>>>>,
+}
+impl<'a> Default for LogRecordArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    LogRecordArgs {
+      timestamp_us: 0,
+      node: None,
+      level: LogLevel::Trace,
+      target: None,
+      message: None,
+      spans: None,
+      fields: None,
+    }
+  }
+}
+
+pub struct LogRecordBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> LogRecordBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(LogRecord::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogRecord::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_level(&mut self, level: LogLevel) {
+    self.fbb_.push_slot::<LogLevel>(LogRecord::VT_LEVEL, level, LogLevel::Trace);
+  }
+  #[inline]
+  pub fn add_target(&mut self, target: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogRecord::VT_TARGET, target);
+  }
+  #[inline]
+  pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogRecord::VT_MESSAGE, message);
+  }
+  #[inline]
+  pub fn add_spans(&mut self, spans: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogRecord::VT_SPANS, spans);
+  }
+  #[inline]
+  pub fn add_fields(&mut self, fields: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<LogField<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LogRecord::VT_FIELDS, fields);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> LogRecordBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    LogRecordBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<LogRecord<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for LogRecord<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("LogRecord");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("level", &self.level());
+      ds.field("target", &self.target());
+      ds.field("message", &self.message());
+      ds.field("spans", &self.spans());
+      ds.field("fields", &self.fields());
+      ds.finish()
+  }
+}
+pub enum CrashReportOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CrashReport<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CrashReport<'a> {
+  type Inner = CrashReport<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CrashReport<'a> {
+  pub const VT_TIMESTAMP_US: flatbuffers::VOffsetT = 4;
+  pub const VT_NODE: flatbuffers::VOffsetT = 6;
+  pub const VT_PID: flatbuffers::VOffsetT = 8;
+  pub const VT_MESSAGE: flatbuffers::VOffsetT = 10;
+  pub const VT_LOCATION: flatbuffers::VOffsetT = 12;
+  pub const VT_THREAD: flatbuffers::VOffsetT = 14;
+  pub const VT_BACKTRACE: flatbuffers::VOffsetT = 16;
+  pub const VT_VERSION: flatbuffers::VOffsetT = 18;
+  pub const VT_COMMIT: flatbuffers::VOffsetT = 20;
+  pub const VT_TARGET: flatbuffers::VOffsetT = 22;
+  pub const VT_PROFILE: flatbuffers::VOffsetT = 24;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CrashReport { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args CrashReportArgs<'args>
+  ) -> flatbuffers::WIPOffset<CrashReport<'bldr>> {
+    let mut builder = CrashReportBuilder::new(_fbb);
+    builder.add_timestamp_us(args.timestamp_us);
+    if let Some(x) = args.profile { builder.add_profile(x); }
+    if let Some(x) = args.target { builder.add_target(x); }
+    if let Some(x) = args.commit { builder.add_commit(x); }
+    if let Some(x) = args.version { builder.add_version(x); }
+    if let Some(x) = args.backtrace { builder.add_backtrace(x); }
+    if let Some(x) = args.thread { builder.add_thread(x); }
+    if let Some(x) = args.location { builder.add_location(x); }
+    if let Some(x) = args.message { builder.add_message(x); }
+    builder.add_pid(args.pid);
+    if let Some(x) = args.node { builder.add_node(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn timestamp_us(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(CrashReport::VT_TIMESTAMP_US, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn node(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_NODE, None)}
+  }
+  #[inline]
+  pub fn pid(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(CrashReport::VT_PID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn message(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_MESSAGE, None)}
+  }
+  #[inline]
+  pub fn location(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_LOCATION, None)}
+  }
+  #[inline]
+  pub fn thread(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_THREAD, None)}
+  }
+  #[inline]
+  pub fn backtrace(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_BACKTRACE, None)}
+  }
+  #[inline]
+  pub fn version(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_VERSION, None)}
+  }
+  #[inline]
+  pub fn commit(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_COMMIT, None)}
+  }
+  #[inline]
+  pub fn target(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_TARGET, None)}
+  }
+  #[inline]
+  pub fn profile(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CrashReport::VT_PROFILE, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CrashReport<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("timestamp_us", Self::VT_TIMESTAMP_US, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("node", Self::VT_NODE, false)?
+     .visit_field::<u32>("pid", Self::VT_PID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("message", Self::VT_MESSAGE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("location", Self::VT_LOCATION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("thread", Self::VT_THREAD, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("backtrace", Self::VT_BACKTRACE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("version", Self::VT_VERSION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("commit", Self::VT_COMMIT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("target", Self::VT_TARGET, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("profile", Self::VT_PROFILE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CrashReportArgs<'a> {
+    pub timestamp_us: u64,
+    pub node: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub pid: u32,
+    pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub location: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub thread: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub backtrace: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub version: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub commit: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub target: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub profile: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for CrashReportArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CrashReportArgs {
+      timestamp_us: 0,
+      node: None,
+      pid: 0,
+      message: None,
+      location: None,
+      thread: None,
+      backtrace: None,
+      version: None,
+      commit: None,
+      target: None,
+      profile: None,
+    }
+  }
+}
+
+pub struct CrashReportBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> CrashReportBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_timestamp_us(&mut self, timestamp_us: u64) {
+    self.fbb_.push_slot::<u64>(CrashReport::VT_TIMESTAMP_US, timestamp_us, 0);
+  }
+  #[inline]
+  pub fn add_node(&mut self, node: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_NODE, node);
+  }
+  #[inline]
+  pub fn add_pid(&mut self, pid: u32) {
+    self.fbb_.push_slot::<u32>(CrashReport::VT_PID, pid, 0);
+  }
+  #[inline]
+  pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_MESSAGE, message);
+  }
+  #[inline]
+  pub fn add_location(&mut self, location: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_LOCATION, location);
+  }
+  #[inline]
+  pub fn add_thread(&mut self, thread: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_THREAD, thread);
+  }
+  #[inline]
+  pub fn add_backtrace(&mut self, backtrace: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_BACKTRACE, backtrace);
+  }
+  #[inline]
+  pub fn add_version(&mut self, version: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_VERSION, version);
+  }
+  #[inline]
+  pub fn add_commit(&mut self, commit: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_COMMIT, commit);
+  }
+  #[inline]
+  pub fn add_target(&mut self, target: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_TARGET, target);
+  }
+  #[inline]
+  pub fn add_profile(&mut self, profile: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CrashReport::VT_PROFILE, profile);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> CrashReportBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    CrashReportBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CrashReport<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CrashReport<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CrashReport");
+      ds.field("timestamp_us", &self.timestamp_us());
+      ds.field("node", &self.node());
+      ds.field("pid", &self.pid());
+      ds.field("message", &self.message());
+      ds.field("location", &self.location());
+      ds.field("thread", &self.thread());
+      ds.field("backtrace", &self.backtrace());
+      ds.field("version", &self.version());
+      ds.field("commit", &self.commit());
+      ds.field("target", &self.target());
+      ds.field("profile", &self.profile());
+      ds.finish()
+  }
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a `IMU`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_imu_unchecked`.
+pub fn root_as_imu(buf: &[u8]) -> Result<IMU, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<IMU>(buf)
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `IMU` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_imu_unchecked`.
+pub fn size_prefixed_root_as_imu(buf: &[u8]) -> Result<IMU, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<IMU>(buf)
+}
+#[inline]
+/// Verifies, with the given options, that a buffer of bytes
+/// contains a `IMU` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_imu_unchecked`.
+pub fn root_as_imu_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<IMU<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root_with_opts::<IMU<'b>>(opts, buf)
+}
+#[inline]
+/// Verifies, with the given verifier options, that a buffer of
+/// bytes contains a size prefixed `IMU` and returns
+/// it. Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_imu_unchecked`.
+pub fn size_prefixed_root_as_imu_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<IMU<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root_with_opts::<IMU<'b>>(opts, buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a IMU and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `IMU`.
+pub unsafe fn root_as_imu_unchecked(buf: &[u8]) -> IMU {
+  flatbuffers::root_unchecked::<IMU>(buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a size prefixed IMU and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid size prefixed `IMU`.
+pub unsafe fn size_prefixed_root_as_imu_unchecked(buf: &[u8]) -> IMU {
+  flatbuffers::size_prefixed_root_unchecked::<IMU>(buf)
+}
+#[inline]
+pub fn finish_imu_buffer<'a, 'b>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+    root: flatbuffers::WIPOffset<IMU<'a>>) {
+  fbb.finish(root, None);
+}
+
+#[inline]
+pub fn finish_size_prefixed_imu_buffer<'a, 'b>(fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>, root: flatbuffers::WIPOffset<IMU<'a>>) {
+  fbb.finish_size_prefixed(root, None);
+}
+}  // pub mod sensors
+
@@ -0,0 +1,409 @@
+// Native Rust counterparts of the sensor tables, for code that would rather not go through
+// the generated accessors. Each reading converts from its table, builds it back with
+// create() or encodes a finished FlatBuffer with Reading::encode, and derives serde for
+// exporting readings as JSON. Vectors are [x, y, z] in the table's frame and units.
+
+use crate::sensors;
+use flatbuffers::{FlatBufferBuilder, InvalidFlatbuffer, WIPOffset};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum DecodeError {
+    // The bytes are not a FlatBuffer of the table.
+    Invalid(InvalidFlatbuffer),
+    // The table lacks a field the reading cannot do without.
+    Missing(&'static str),
+    // An enum field holds a value the schema does not define.
+    Unknown { field: &'static str, value: i64 },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Invalid(e) => write!(f, "invalid FlatBuffer: {}", e),
+            DecodeError::Missing(field) => write!(f, "missing {}", field),
+            DecodeError::Unknown { field, value } => write!(f, "unknown {} {}", field, value),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<InvalidFlatbuffer> for DecodeError {
+    fn from(e: InvalidFlatbuffer) -> Self {
+        DecodeError::Invalid(e)
+    }
+}
+
+// Lets Reading::decode treat the tables that always convert like those that may not.
+impl From<Infallible> for DecodeError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+// A reading that travels as one sensors table.
+pub trait Reading: Sized {
+    // The table's name, e.g. "sensors.IMU", as the encoding of its samples carries it.
+    const TABLE: &'static str;
+
+    // Decodes a finished FlatBuffer of the table.
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError>;
+
+    // Encodes the reading as a finished FlatBuffer of the table.
+    fn encode(&self) -> Vec<u8>;
+}
+
+macro_rules! reading {
+    ($reading:ident, $table:ident) => {
+        impl Reading for $reading {
+            const TABLE: &'static str = concat!("sensors.", stringify!($table));
+
+            fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+                let table = flatbuffers::root::<sensors::$table>(bytes)?;
+                $reading::try_from(table).map_err(DecodeError::from)
+            }
+
+            fn encode(&self) -> Vec<u8> {
+                let mut builder = FlatBufferBuilder::with_capacity(64);
+                let table = self.create(&mut builder);
+                builder.finish(table, None);
+                builder.finished_data().to_vec()
+            }
+        }
+    };
+}
+
+impl From<sensors::Vec3> for [f32; 3] {
+    fn from(v: sensors::Vec3) -> Self {
+        [v.x(), v.y(), v.z()]
+    }
+}
+
+impl From<[f32; 3]> for sensors::Vec3 {
+    fn from(v: [f32; 3]) -> Self {
+        sensors::Vec3::new(v[0], v[1], v[2])
+    }
+}
+
+// Specific force in m/s², body frame.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ImuReading {
+    pub acceleration: [f32; 3],
+}
+
+impl TryFrom<sensors::IMU<'_>> for ImuReading {
+    type Error = DecodeError;
+
+    fn try_from(imu: sensors::IMU<'_>) -> Result<Self, DecodeError> {
+        let acceleration = imu
+            .acceleration()
+            .ok_or(DecodeError::Missing("acceleration"))?;
+        Ok(ImuReading {
+            acceleration: (*acceleration).into(),
+        })
+    }
+}
+
+impl ImuReading {
+    pub fn create<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<sensors::IMU<'a>> {
+        let acceleration = sensors::Vec3::from(self.acceleration);
+        sensors::IMU::create(
+            builder,
+            &sensors::IMUArgs {
+                acceleration: Some(&acceleration),
+            },
+        )
+    }
+}
+
+reading!(ImuReading, IMU);
+
+// Angular rate in rad/s, body frame.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GyroReading {
+    pub omega: [f32; 3],
+}
+
+impl From<sensors::Gyro<'_>> for GyroReading {
+    fn from(gyro: sensors::Gyro<'_>) -> Self {
+        GyroReading {
+            omega: [gyro.omega_x(), gyro.omega_y(), gyro.omega_z()],
+        }
+    }
+}
+
+impl GyroReading {
+    pub fn create<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<sensors::Gyro<'a>> {
+        sensors::Gyro::create(
+            builder,
+            &sensors::GyroArgs {
+                omega_x: self.omega[0],
+                omega_y: self.omega[1],
+                omega_z: self.omega[2],
+            },
+        )
+    }
+}
+
+reading!(GyroReading, Gyro);
+
+// Altitude in meters above the pad.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct AltitudeReading {
+    pub altitude: f32,
+}
+
+impl From<sensors::Altitude<'_>> for AltitudeReading {
+    fn from(altitude: sensors::Altitude<'_>) -> Self {
+        AltitudeReading {
+            altitude: altitude.altitude(),
+        }
+    }
+}
+
+impl AltitudeReading {
+    pub fn create<'a>(
+        &self,
+        builder: &mut FlatBufferBuilder<'a>,
+    ) -> WIPOffset<sensors::Altitude<'a>> {
+        sensors::Altitude::create(
+            builder,
+            &sensors::AltitudeArgs {
+                altitude: self.altitude,
+            },
+        )
+    }
+}
+
+reading!(AltitudeReading, Altitude);
+
+// Ambient temperature in degrees Celsius; a false valid means the sensor read no value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TemperatureReading {
+    pub value: f32,
+    pub sensor_id: u32,
+    pub valid: bool,
+}
+
+impl From<sensors::Temperature<'_>> for TemperatureReading {
+    fn from(temperature: sensors::Temperature<'_>) -> Self {
+        TemperatureReading {
+            value: temperature.value(),
+            sensor_id: temperature.sensor_id(),
+            valid: temperature.valid(),
+        }
+    }
+}
+
+impl TemperatureReading {
+    pub fn create<'a>(
+        &self,
+        builder: &mut FlatBufferBuilder<'a>,
+    ) -> WIPOffset<sensors::Temperature<'a>> {
+        sensors::Temperature::create(
+            builder,
+            &sensors::TemperatureArgs {
+                value: self.value,
+                sensor_id: self.sensor_id,
+                valid: self.valid,
+            },
+        )
+    }
+}
+
+reading!(TemperatureReading, Temperature);
+
+// Static pressure in pascals and the sensor's temperature in degrees Celsius.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct BarometerReading {
+    pub pressure: f32,
+    pub temperature: f32,
+}
+
+impl From<sensors::Barometer<'_>> for BarometerReading {
+    fn from(barometer: sensors::Barometer<'_>) -> Self {
+        BarometerReading {
+            pressure: barometer.pressure(),
+            temperature: barometer.temperature(),
+        }
+    }
+}
+
+impl BarometerReading {
+    pub fn create<'a>(
+        &self,
+        builder: &mut FlatBufferBuilder<'a>,
+    ) -> WIPOffset<sensors::Barometer<'a>> {
+        sensors::Barometer::create(
+            builder,
+            &sensors::BarometerArgs {
+                pressure: self.pressure,
+                temperature: self.temperature,
+            },
+        )
+    }
+}
+
+reading!(BarometerReading, Barometer);
+
+// Magnetic flux density in microtesla, body frame, uncalibrated.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct MagnetometerReading {
+    pub field: [f32; 3],
+}
+
+impl TryFrom<sensors::Magnetometer<'_>> for MagnetometerReading {
+    type Error = DecodeError;
+
+    fn try_from(magnetometer: sensors::Magnetometer<'_>) -> Result<Self, DecodeError> {
+        let field = magnetometer.field().ok_or(DecodeError::Missing("field"))?;
+        Ok(MagnetometerReading {
+            field: (*field).into(),
+        })
+    }
+}
+
+impl MagnetometerReading {
+    pub fn create<'a>(
+        &self,
+        builder: &mut FlatBufferBuilder<'a>,
+    ) -> WIPOffset<sensors::Magnetometer<'a>> {
+        let field = sensors::Vec3::from(self.field);
+        sensors::Magnetometer::create(
+            builder,
+            &sensors::MagnetometerArgs {
+                field: Some(&field),
+            },
+        )
+    }
+}
+
+reading!(MagnetometerReading, Magnetometer);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FixType {
+    #[default]
+    NoFix,
+    Fix2D,
+    Fix3D,
+}
+
+impl TryFrom<sensors::GnssFixType> for FixType {
+    type Error = DecodeError;
+
+    fn try_from(fix_type: sensors::GnssFixType) -> Result<Self, DecodeError> {
+        match fix_type {
+            sensors::GnssFixType::NoFix => Ok(FixType::NoFix),
+            sensors::GnssFixType::Fix2D => Ok(FixType::Fix2D),
+            sensors::GnssFixType::Fix3D => Ok(FixType::Fix3D),
+            _ => Err(DecodeError::Unknown {
+                field: "fix_type",
+                value: fix_type.0 as i64,
+            }),
+        }
+    }
+}
+
+impl From<FixType> for sensors::GnssFixType {
+    fn from(fix_type: FixType) -> Self {
+        match fix_type {
+            FixType::NoFix => sensors::GnssFixType::NoFix,
+            FixType::Fix2D => sensors::GnssFixType::Fix2D,
+            FixType::Fix3D => sensors::GnssFixType::Fix3D,
+        }
+    }
+}
+
+// A GNSS navigation solution, see the GnssFix table. Receivers leave velocity out while
+// they have no fix.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct GnssReading {
+    pub timestamp_us: u64,
+    pub fix_type: FixType,
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude: f32,
+    pub velocity: Option<[f32; 3]>,
+    pub hdop: f32,
+    pub vdop: f32,
+    pub satellites: u8,
+}
+
+impl TryFrom<sensors::GnssFix<'_>> for GnssReading {
+    type Error = DecodeError;
+
+    fn try_from(fix: sensors::GnssFix<'_>) -> Result<Self, DecodeError> {
+        Ok(GnssReading {
+            timestamp_us: fix.timestamp_us(),
+            fix_type: fix.fix_type().try_into()?,
+            latitude_deg: fix.latitude_deg(),
+            longitude_deg: fix.longitude_deg(),
+            altitude: fix.altitude(),
+            velocity: fix.velocity().map(|v| (*v).into()),
+            hdop: fix.hdop(),
+            vdop: fix.vdop(),
+            satellites: fix.satellites(),
+        })
+    }
+}
+
+impl GnssReading {
+    pub fn create<'a>(
+        &self,
+        builder: &mut FlatBufferBuilder<'a>,
+    ) -> WIPOffset<sensors::GnssFix<'a>> {
+        let velocity = self.velocity.map(sensors::Vec3::from);
+        sensors::GnssFix::create(
+            builder,
+            &sensors::GnssFixArgs {
+                timestamp_us: self.timestamp_us,
+                fix_type: self.fix_type.into(),
+                latitude_deg: self.latitude_deg,
+                longitude_deg: self.longitude_deg,
+                altitude: self.altitude,
+                velocity: velocity.as_ref(),
+                hdop: self.hdop,
+                vdop: self.vdop,
+                satellites: self.satellites,
+            },
+        )
+    }
+}
+
+reading!(GnssReading, GnssFix);
+
+// Bus voltage in volts, current in amperes, and board temperature in degrees Celsius.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct PowerReading {
+    pub voltage: f32,
+    pub current: f32,
+    pub temperature: f32,
+}
+
+impl From<sensors::Power<'_>> for PowerReading {
+    fn from(power: sensors::Power<'_>) -> Self {
+        PowerReading {
+            voltage: power.voltage(),
+            current: power.current(),
+            temperature: power.temperature(),
+        }
+    }
+}
+
+impl PowerReading {
+    pub fn create<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<sensors::Power<'a>> {
+        sensors::Power::create(
+            builder,
+            &sensors::PowerArgs {
+                voltage: self.voltage,
+                current: self.current,
+                temperature: self.temperature,
+            },
+        )
+    }
+}
+
+reading!(PowerReading, Power);